@@ -0,0 +1,51 @@
+//! Build script providing build details for `sunsetr --version --verbose`.
+//!
+//! Exposes as compile-time environment variables:
+//! - `SUNSETR_GIT_COMMIT`: short commit hash, or "unknown" outside a git checkout.
+//!   Packagers building from a tarball can set it in the environment instead.
+//! - `SUNSETR_RUSTC_VERSION`: output of `rustc --version` for the compiler in use
+//! - `SUNSETR_FEATURES`: comma-separated list of enabled cargo features
+
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+    println!("cargo:rerun-if-env-changed=SUNSETR_GIT_COMMIT");
+
+    let commit = std::env::var("SUNSETR_GIT_COMMIT")
+        .ok()
+        .filter(|c| !c.trim().is_empty())
+        .or_else(|| command_output("git", &["rev-parse", "--short=12", "HEAD"]))
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=SUNSETR_GIT_COMMIT={}", commit.trim());
+
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version =
+        command_output(&rustc, &["--version"]).unwrap_or_else(|| "unknown".to_string());
+    println!(
+        "cargo:rustc-env=SUNSETR_RUSTC_VERSION={}",
+        rustc_version.trim()
+    );
+
+    // Cargo sets CARGO_FEATURE_<NAME> for every enabled feature, "default" included
+    let mut features: Vec<String> = std::env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|name| name.to_lowercase().replace('_', "-"))
+        })
+        .filter(|name| name != "default")
+        .collect();
+    features.sort();
+    println!("cargo:rustc-env=SUNSETR_FEATURES={}", features.join(","));
+}
+
+/// Run a command and return its stdout when it succeeds.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
@@ -0,0 +1,323 @@
+//! Follow the desktop's light/dark appearance instead of the clock.
+//!
+//! With `transition_mode = "appearance"` (and the `portal` feature enabled), day and
+//! night come from the freedesktop `org.freedesktop.appearance color-scheme` setting,
+//! read through the settings portal: dark maps to night, light and "no preference"
+//! map to day. The setting is read once at startup and then watched for
+//! `SettingChanged` signals, each of which is delivered to the main loop as
+//! [`SignalMessage::AppearanceChanged`](crate::signals::SignalMessage::AppearanceChanged)
+//! and applied with a smooth transition.
+//!
+//! Like the logind monitor, the portal is queried through `gdbus` child processes
+//! rather than a D-Bus library.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::config::Config;
+use crate::time_state::TimeState;
+
+/// Value of the `org.freedesktop.appearance color-scheme` setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorScheme {
+    /// No preference (0), treated as light
+    NoPreference,
+    /// Prefer dark appearance (1)
+    Dark,
+    /// Prefer light appearance (2)
+    Light,
+}
+
+impl ColorScheme {
+    /// Convert the portal's numeric value, treating unknown values as no preference.
+    pub fn from_portal_value(value: u32) -> Self {
+        match value {
+            1 => ColorScheme::Dark,
+            2 => ColorScheme::Light,
+            _ => ColorScheme::NoPreference,
+        }
+    }
+
+    /// The period this appearance maps to.
+    pub fn time_state(self) -> TimeState {
+        match self {
+            ColorScheme::Dark => TimeState::Night,
+            ColorScheme::Light | ColorScheme::NoPreference => TimeState::Day,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            ColorScheme::NoPreference => 0,
+            ColorScheme::Dark => 1,
+            ColorScheme::Light => 2,
+        }
+    }
+}
+
+impl fmt::Display for ColorScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColorScheme::NoPreference => write!(f, "no preference"),
+            ColorScheme::Dark => write!(f, "dark"),
+            ColorScheme::Light => write!(f, "light"),
+        }
+    }
+}
+
+/// Latest known color scheme, shared with [`get_transition_state`](crate::time_state::get_transition_state).
+static CURRENT_SCHEME: AtomicU8 = AtomicU8::new(0);
+
+/// The latest known color scheme.
+pub fn current_scheme() -> ColorScheme {
+    ColorScheme::from_portal_value(CURRENT_SCHEME.load(Ordering::SeqCst) as u32)
+}
+
+/// Record a new color scheme, e.g. after the portal reported a change.
+pub fn set_current_scheme(scheme: ColorScheme) {
+    CURRENT_SCHEME.store(scheme.to_u8(), Ordering::SeqCst);
+}
+
+/// Whether the config follows the desktop appearance.
+pub fn is_appearance_mode(config: &Config) -> bool {
+    config.transition_mode.as_deref() == Some("appearance")
+}
+
+/// Extract the color scheme from a portal reply or signal, e.g. `(<uint32 1>,)`.
+///
+/// Newer portals answer `ReadOne` with a plain variant, older ones answer `Read`
+/// with a nested one (`(<<uint32 1>>,)`); both contain the same `uint32` value.
+pub fn parse_portal_value(text: &str) -> Option<ColorScheme> {
+    let (_, rest) = text.split_once("uint32 ")?;
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok().map(ColorScheme::from_portal_value)
+}
+
+/// Parse one line of `gdbus monitor` output into a color scheme change.
+///
+/// Lines look like `/org/freedesktop/portal/desktop: org.freedesktop.portal.Settings.SettingChanged
+/// ('org.freedesktop.appearance', 'color-scheme', <uint32 1>)`. Changes to other settings are ignored.
+#[cfg_attr(not(feature = "portal"), allow(dead_code))] // Only fed by the portal monitor
+pub fn parse_monitor_line(line: &str) -> Option<ColorScheme> {
+    let (_path, signal) = line.trim().split_once(": ")?;
+    let (member, args) = signal.split_once(' ')?;
+
+    if member != "org.freedesktop.portal.Settings.SettingChanged"
+        || !args.contains("'org.freedesktop.appearance', 'color-scheme'")
+    {
+        return None;
+    }
+    parse_portal_value(args)
+}
+
+/// Read the current color scheme when the config follows the desktop appearance.
+///
+/// Does nothing for other modes. Fails with an explanation when the portal can't be
+/// reached or sunsetr was built without the `portal` feature.
+pub fn init(config: &Config, debug_enabled: bool) -> anyhow::Result<()> {
+    if !is_appearance_mode(config) {
+        return Ok(());
+    }
+
+    #[cfg(feature = "portal")]
+    {
+        let scheme = read_color_scheme()?;
+        set_current_scheme(scheme);
+        if debug_enabled {
+            crate::logger::Log::log_pipe();
+            crate::logger::Log::log_debug(&format!("Desktop color scheme: {}", scheme));
+        }
+        Ok(())
+    }
+
+    #[cfg(not(feature = "portal"))]
+    {
+        let _ = debug_enabled;
+        anyhow::bail!(
+            "transition_mode = \"appearance\" needs sunsetr built with the portal feature \
+            (`cargo build --release --features portal`)"
+        )
+    }
+}
+
+/// Ask the settings portal for the current color scheme.
+#[cfg(feature = "portal")]
+pub fn read_color_scheme() -> anyhow::Result<ColorScheme> {
+    use anyhow::Context;
+    use std::process::Command;
+
+    let call = |method: &str| {
+        Command::new("gdbus")
+            .args([
+                "call",
+                "--session",
+                "--dest",
+                "org.freedesktop.portal.Desktop",
+                "--object-path",
+                "/org/freedesktop/portal/desktop",
+                "--method",
+                method,
+                "org.freedesktop.appearance",
+                "color-scheme",
+            ])
+            .output()
+    };
+
+    let output = call("org.freedesktop.portal.Settings.ReadOne").context(
+        "Failed to run gdbus (part of GLib), which is needed to query the settings portal",
+    )?;
+
+    // ReadOne was only added in version 2 of the Settings interface
+    let output = if output.status.success() {
+        output
+    } else {
+        call("org.freedesktop.portal.Settings.Read").context("Failed to run gdbus")?
+    };
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "The settings portal (org.freedesktop.portal.Desktop) is unavailable: {}\n\
+            transition_mode = \"appearance\" needs xdg-desktop-portal running in your session",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let reply = String::from_utf8_lossy(&output.stdout);
+    parse_portal_value(&reply).with_context(|| {
+        format!(
+            "Unexpected reply from the settings portal: {}",
+            reply.trim()
+        )
+    })
+}
+
+/// Start watching the settings portal for color scheme changes in a background thread.
+///
+/// Changes are sent to the main loop through `sender`. Failing to start the monitor is
+/// logged and otherwise ignored; the scheme read at startup stays in effect.
+#[cfg(feature = "portal")]
+pub fn start_monitor(
+    sender: std::sync::mpsc::Sender<crate::signals::SignalMessage>,
+    debug_enabled: bool,
+) {
+    use crate::logger::Log;
+    use crate::signals::SignalMessage;
+    use std::io::{BufRead, BufReader};
+    use std::os::unix::process::CommandExt;
+    use std::process::{Command, Stdio};
+
+    let mut cmd = Command::new("gdbus");
+    cmd.args([
+        "monitor",
+        "--session",
+        "--dest",
+        "org.freedesktop.portal.Desktop",
+        "--object-path",
+        "/org/freedesktop/portal/desktop",
+    ])
+    .stdout(Stdio::piped())
+    .stderr(Stdio::null());
+
+    // Make the monitor die with sunsetr, like the logind monitor
+    unsafe {
+        cmd.pre_exec(|| {
+            use nix::sys::prctl;
+            use nix::sys::signal::Signal;
+            prctl::set_pdeathsig(Signal::SIGTERM)?;
+            Ok(())
+        });
+    }
+
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            Log::log_pipe();
+            Log::log_warning(&format!(
+                "Failed to watch the settings portal for appearance changes: {}",
+                e
+            ));
+            return;
+        }
+    };
+
+    Log::log_decorated(&format!(
+        "Following the desktop appearance (currently {})",
+        current_scheme()
+    ));
+
+    let Some(stdout) = child.stdout.take() else {
+        let _ = child.kill();
+        return;
+    };
+
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else { break };
+            let Some(scheme) = parse_monitor_line(&line) else {
+                continue;
+            };
+
+            if debug_enabled {
+                Log::log_pipe();
+                Log::log_debug(&format!("Portal event: {}", line.trim()));
+            }
+
+            // The main loop has gone away, so there's nobody left to notify
+            if sender
+                .send(SignalMessage::AppearanceChanged(scheme))
+                .is_err()
+            {
+                break;
+            }
+        }
+
+        let _ = child.kill();
+        let _ = child.wait();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_portal_value() {
+        assert_eq!(parse_portal_value("(<uint32 1>,)"), Some(ColorScheme::Dark));
+        assert_eq!(
+            parse_portal_value("(<<uint32 2>>,)"),
+            Some(ColorScheme::Light)
+        );
+        assert_eq!(
+            parse_portal_value("(<uint32 0>,)"),
+            Some(ColorScheme::NoPreference)
+        );
+        assert_eq!(parse_portal_value("()"), None);
+
+        assert_eq!(ColorScheme::Dark.time_state(), TimeState::Night);
+        assert_eq!(ColorScheme::NoPreference.time_state(), TimeState::Day);
+    }
+
+    #[test]
+    fn test_parse_monitor_line() {
+        assert_eq!(
+            parse_monitor_line(
+                "/org/freedesktop/portal/desktop: org.freedesktop.portal.Settings.SettingChanged ('org.freedesktop.appearance', 'color-scheme', <uint32 1>)"
+            ),
+            Some(ColorScheme::Dark)
+        );
+
+        // Other settings and unrelated lines are ignored
+        assert_eq!(
+            parse_monitor_line(
+                "/org/freedesktop/portal/desktop: org.freedesktop.portal.Settings.SettingChanged ('org.freedesktop.appearance', 'contrast', <uint32 1>)"
+            ),
+            None
+        );
+        assert_eq!(
+            parse_monitor_line(
+                "Monitoring signals on object /org/freedesktop/portal/desktop owned by org.freedesktop.portal.Desktop"
+            ),
+            None
+        );
+    }
+}
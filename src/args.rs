@@ -4,34 +4,153 @@
 //! interface for the main application logic. It supports the standard help,
 //! version, and debug flags while gracefully handling unknown options.
 
+use crate::config::Backend;
 use crate::logger::Log;
 
+/// What to do when another instance is already running on the same compositor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IfRunning {
+    /// Report the running instance and exit with an error
+    #[default]
+    Error,
+    /// Ask the running instance to reload its configuration and exit successfully
+    Reload,
+    /// Terminate the running instance and take over
+    Replace,
+}
+
+impl IfRunning {
+    /// Parse the value given to `--if-running`.
+    pub fn from_arg(value: &str) -> Option<Self> {
+        match value {
+            "error" => Some(IfRunning::Error),
+            "reload" => Some(IfRunning::Reload),
+            "replace" => Some(IfRunning::Replace),
+            _ => None,
+        }
+    }
+}
+
 /// Represents the parsed command-line arguments and their intended actions.
 #[derive(Debug, PartialEq)]
 pub enum CliAction {
     /// Run the normal application with these settings
-    Run { debug_enabled: bool },
+    Run {
+        debug_enabled: bool,
+        if_running: IfRunning,
+        /// Log file given with `--log-file`, overriding `log_file` in the config
+        log_file: Option<String>,
+        /// Backend given with `--backend`, overriding `backend` in the config
+        backend: Option<Backend>,
+    },
     /// Run interactive geo location selection
     RunGeoSelection { debug_enabled: bool },
     /// Reset all display gamma and reload sunsetr
     Reload { debug_enabled: bool },
+    /// Switch the running instance to a profile (or cycle when no name is given)
+    SwitchProfile {
+        debug_enabled: bool,
+        name: Option<String>,
+    },
+    /// Write geo coordinates to the config without the city selector
+    SetLocation {
+        debug_enabled: bool,
+        lat: f64,
+        lon: f64,
+    },
+    /// Offset the running instance's temperature until the next state change (0 clears)
+    Nudge { debug_enabled: bool, kelvin: i32 },
+    /// Pin the running instance's values for a number of minutes, or release the hold
+    Hold {
+        debug_enabled: bool,
+        command: crate::commands::hold::HoldCommand,
+    },
+    /// Show a live dashboard of the running instance
+    Monitor { debug_enabled: bool },
+    /// Apply the current state once and exit
+    Once { debug_enabled: bool },
+    /// Move the legacy `hypr/sunsetr.toml` config to `sunsetr/` and exit
+    Migrate { debug_enabled: bool },
+    /// Report the installed hyprsunset version and whether sunsetr supports it
+    CheckHyprsunset { debug_enabled: bool },
+    /// Show which config files are in use without loading them
+    WhichConfig { debug_enabled: bool },
+    /// Pick the config file to use from the sunsetr config directory
+    SelectConfig {
+        debug_enabled: bool,
+        name: Option<String>,
+    },
+    /// Validate a config file (the active one when no path is given) and exit
+    Lint {
+        debug_enabled: bool,
+        path: Option<String>,
+    },
     /// Test specific temperature and gamma values
     Test {
         debug_enabled: bool,
         temperature: u32,
         gamma: f32,
     },
+    /// Preview a few night temperatures and write the chosen one to `night_temp`
+    Calibrate { debug_enabled: bool },
+    /// Time a number of applies on the detected backend and report the latency
+    Bench {
+        debug_enabled: bool,
+        iterations: usize,
+    },
+    /// Print the gamma ramp for a temperature and gamma as CSV and exit
+    DumpGamma {
+        debug_enabled: bool,
+        temperature: u32,
+        gamma: f32,
+        size: usize,
+    },
     /// Display help information and exit
     ShowHelp,
     /// Display version information and exit
     ShowVersion,
+    /// Display version and build details for bug reports and exit
+    ShowBuildInfo,
     /// Show help due to unknown arguments and exit
     ShowHelpDueToError,
 }
 
+impl CliAction {
+    /// Name of the command, as reported in `--json` output.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CliAction::Run { .. } => "run",
+            CliAction::RunGeoSelection { .. } => "geo",
+            CliAction::Reload { .. } => "reload",
+            CliAction::SwitchProfile { .. } => "profile",
+            CliAction::SetLocation { .. } => "set-location",
+            CliAction::Nudge { .. } => "nudge",
+            CliAction::Hold { .. } => "hold",
+            CliAction::Monitor { .. } => "monitor",
+            CliAction::Once { .. } => "once",
+            CliAction::Migrate { .. } => "migrate",
+            CliAction::CheckHyprsunset { .. } => "check-hyprsunset",
+            CliAction::WhichConfig { .. } => "which-config",
+            CliAction::SelectConfig { .. } => "select-config",
+            CliAction::Lint { .. } => "lint",
+            CliAction::Test { .. } => "test",
+            CliAction::Calibrate { .. } => "calibrate",
+            CliAction::Bench { .. } => "bench",
+            CliAction::DumpGamma { .. } => "dump-gamma",
+            CliAction::ShowHelp | CliAction::ShowHelpDueToError => "help",
+            CliAction::ShowVersion => "version",
+            CliAction::ShowBuildInfo => "build-info",
+        }
+    }
+}
+
 /// Result of parsing command-line arguments.
 pub struct ParsedArgs {
     pub action: CliAction,
+    /// `--safe`: never write config files or terminate other processes
+    pub safe_mode: bool,
+    /// `--json`: print one JSON object per command instead of decorated logs
+    pub json: bool,
 }
 
 impl ParsedArgs {
@@ -51,13 +170,38 @@ impl ParsedArgs {
         S: AsRef<str>,
     {
         let mut debug_enabled = false;
+        let mut safe_mode = false;
+        let mut json = false;
         let mut display_help = false;
         let mut display_version = false;
+        let mut verbose = false;
         let mut run_geo_selection = false;
         let mut run_reload = false;
         let mut run_test = false;
+        let mut run_profile = false;
+        let mut run_monitor = false;
+        let mut run_once = false;
+        let mut run_lint = false;
+        let mut run_migrate = false;
+        let mut run_check_hyprsunset = false;
+        let mut run_which_config = false;
+        let mut run_select_config = false;
+        let mut select_config_name: Option<String> = None;
+        let mut lint_path: Option<String> = None;
+        let mut if_running = IfRunning::default();
+        let mut log_file: Option<String> = None;
+        let mut backend: Option<Backend> = None;
+        let mut profile_name: Option<String> = None;
+        let mut nudge_kelvin: Option<i32> = None;
+        let mut hold_command: Option<crate::commands::hold::HoldCommand> = None;
+        let mut location: Option<(f64, f64)> = None;
         let mut test_temperature: Option<u32> = None;
         let mut test_gamma: Option<f32> = None;
+        let mut run_dump_gamma = false;
+        let mut run_bench = false;
+        let mut run_calibrate = false;
+        let mut bench_iterations = crate::constants::DEFAULT_BENCH_ITERATIONS;
+        let mut dump_size = crate::constants::DEFAULT_DUMP_GAMMA_SIZE;
         let mut unknown_arg_found = false;
 
         // Convert to vector for easier indexed access
@@ -74,8 +218,197 @@ impl ParsedArgs {
                 "--help" | "-h" => display_help = true,
                 "--version" | "-V" | "-v" => display_version = true,
                 "--debug" | "-d" => debug_enabled = true,
+                "--verbose" => verbose = true,
+                "--safe" => safe_mode = true,
+                "--json" => json = true,
                 "--geo" | "-g" => run_geo_selection = true,
                 "--reload" | "-r" => run_reload = true,
+                "--monitor" | "-m" => run_monitor = true,
+                "--once" | "-o" => run_once = true,
+                "--migrate" => run_migrate = true,
+                "--check-hyprsunset" => run_check_hyprsunset = true,
+                "--which-config" => run_which_config = true,
+                "--calibrate" => run_calibrate = true,
+                "--select-config" => {
+                    run_select_config = true;
+                    // Parse: --select-config [name]
+                    if let Some(next) = args_vec.get(i + 1)
+                        && !next.starts_with('-')
+                    {
+                        select_config_name = Some(next.clone());
+                        i += 1; // Skip the parsed argument
+                    }
+                }
+                "--lint" | "-l" => {
+                    run_lint = true;
+                    // Parse: --lint [path]
+                    if let Some(next) = args_vec.get(i + 1)
+                        && !next.starts_with('-')
+                    {
+                        lint_path = Some(next.clone());
+                        i += 1; // Skip the parsed argument
+                    }
+                }
+                "--bench" => {
+                    run_bench = true;
+                    // Parse: --bench [iterations]
+                    if let Some(next) = args_vec.get(i + 1)
+                        && !next.starts_with('-')
+                    {
+                        match next.parse::<usize>() {
+                            Ok(count) => bench_iterations = count,
+                            Err(_) => {
+                                Log::log_warning(&format!("Invalid iteration count: {}", next));
+                                unknown_arg_found = true;
+                            }
+                        }
+                        i += 1; // Skip the parsed argument
+                    }
+                }
+                "--backend" => {
+                    // Parse: --backend <name>
+                    match args_vec.get(i + 1).and_then(|v| Backend::from_name(v)) {
+                        Some(name) => {
+                            backend = Some(name);
+                            i += 1; // Skip the parsed argument
+                        }
+                        None => {
+                            Log::log_warning(
+                                "Missing or invalid value for --backend. Usage: --backend <auto|hyprland|wayland|drm>",
+                            );
+                            unknown_arg_found = true;
+                        }
+                    }
+                }
+                arg if arg.starts_with("--backend=") => {
+                    let value = &arg["--backend=".len()..];
+                    match Backend::from_name(value) {
+                        Some(name) => backend = Some(name),
+                        None => {
+                            Log::log_warning(&format!(
+                                "Invalid value for --backend: {} (expected auto, hyprland, wayland or drm)",
+                                value
+                            ));
+                            unknown_arg_found = true;
+                        }
+                    }
+                }
+                "--if-running" => {
+                    // Parse: --if-running <mode>
+                    match args_vec.get(i + 1).and_then(|v| IfRunning::from_arg(v)) {
+                        Some(mode) => {
+                            if_running = mode;
+                            i += 1; // Skip the parsed argument
+                        }
+                        None => {
+                            Log::log_warning(
+                                "Missing or invalid value for --if-running. Usage: --if-running <error|reload|replace>",
+                            );
+                            unknown_arg_found = true;
+                        }
+                    }
+                }
+                arg if arg.starts_with("--if-running=") => {
+                    let value = &arg["--if-running=".len()..];
+                    match IfRunning::from_arg(value) {
+                        Some(mode) => if_running = mode,
+                        None => {
+                            Log::log_warning(&format!(
+                                "Invalid value for --if-running: {} (expected error, reload or replace)",
+                                value
+                            ));
+                            unknown_arg_found = true;
+                        }
+                    }
+                }
+                "--log-file" => {
+                    // Parse: --log-file <path>
+                    match args_vec.get(i + 1).filter(|next| !next.starts_with('-')) {
+                        Some(path) => {
+                            log_file = Some(path.clone());
+                            i += 1; // Skip the parsed argument
+                        }
+                        None => {
+                            Log::log_warning(
+                                "Missing path for --log-file. Usage: --log-file <path>",
+                            );
+                            unknown_arg_found = true;
+                        }
+                    }
+                }
+                "--nudge" => {
+                    // Parse: --nudge <kelvin|reset>; the value may start with '-'
+                    match args_vec
+                        .get(i + 1)
+                        .and_then(|v| crate::commands::nudge::parse_nudge_value(v))
+                    {
+                        Some(kelvin) => {
+                            nudge_kelvin = Some(kelvin);
+                            i += 1; // Skip the parsed argument
+                        }
+                        None => {
+                            Log::log_warning(
+                                "Missing or invalid value for --nudge. Usage: --nudge <kelvin|reset>",
+                            );
+                            unknown_arg_found = true;
+                        }
+                    }
+                }
+                "--hold" => {
+                    // Parse: --hold <temperature> <gamma> <minutes> | --hold release
+                    use crate::commands::hold::HoldCommand;
+                    if args_vec.get(i + 1).map(String::as_str) == Some("release") {
+                        hold_command = Some(HoldCommand::Release);
+                        i += 1; // Skip the parsed argument
+                    } else {
+                        let temperature = args_vec.get(i + 1).and_then(|v| v.parse::<u32>().ok());
+                        let gamma = args_vec.get(i + 2).and_then(|v| v.parse::<f32>().ok());
+                        let minutes = args_vec.get(i + 3).and_then(|v| v.parse::<u32>().ok());
+                        match (temperature, gamma, minutes) {
+                            (Some(temperature), Some(gamma), Some(minutes)) => {
+                                hold_command = Some(HoldCommand::Start {
+                                    temperature,
+                                    gamma,
+                                    minutes,
+                                });
+                                i += 3; // Skip the parsed arguments
+                            }
+                            _ => {
+                                Log::log_warning(
+                                    "Missing or invalid values for --hold. Usage: --hold <temperature> <gamma> <minutes> | --hold release",
+                                );
+                                unknown_arg_found = true;
+                            }
+                        }
+                    }
+                }
+                "--set-location" => {
+                    // Parse: --set-location <lat> <lon>; either value may start with '-'
+                    let lat = args_vec.get(i + 1).and_then(|v| v.parse::<f64>().ok());
+                    let lon = args_vec.get(i + 2).and_then(|v| v.parse::<f64>().ok());
+                    match (lat, lon) {
+                        (Some(lat), Some(lon)) => {
+                            location = Some((lat, lon));
+                            i += 2; // Skip the parsed arguments
+                        }
+                        _ => {
+                            Log::log_warning(
+                                "Missing or invalid coordinates for --set-location. Usage: --set-location <lat> <lon>",
+                            );
+                            unknown_arg_found = true;
+                        }
+                    }
+                }
+                "--profile" | "-p" => {
+                    run_profile = true;
+                    // Parse: --profile [name]
+                    if let Some(next) = args_vec.get(i + 1)
+                        && !next.starts_with('-')
+                    {
+                        profile_name = Some(next.clone());
+                        i += 1; // Skip the parsed argument
+                    }
+                }
                 "--test" | "-t" => {
                     run_test = true;
                     // Parse: --test <temperature> <gamma>
@@ -110,6 +443,54 @@ impl ParsedArgs {
                         unknown_arg_found = true;
                     }
                 }
+                "--dump-gamma" => {
+                    run_dump_gamma = true;
+                    // Parse: --dump-gamma <temperature> <gamma> [size]
+                    // Shares the value slots with --test, which it takes precedence over
+                    if i + 2 < args_vec.len() {
+                        match args_vec[i + 1].parse::<u32>() {
+                            Ok(temp) => test_temperature = Some(temp),
+                            Err(_) => {
+                                Log::log_warning(&format!(
+                                    "Invalid temperature value: {}",
+                                    args_vec[i + 1]
+                                ));
+                                unknown_arg_found = true;
+                            }
+                        }
+
+                        match args_vec[i + 2].parse::<f32>() {
+                            Ok(gamma) => test_gamma = Some(gamma),
+                            Err(_) => {
+                                Log::log_warning(&format!(
+                                    "Invalid gamma value: {}",
+                                    args_vec[i + 2]
+                                ));
+                                unknown_arg_found = true;
+                            }
+                        }
+
+                        i += 2; // Skip the parsed arguments
+
+                        if let Some(next) = args_vec.get(i + 1)
+                            && !next.starts_with('-')
+                        {
+                            match next.parse::<usize>() {
+                                Ok(size) => dump_size = size,
+                                Err(_) => {
+                                    Log::log_warning(&format!("Invalid ramp size: {}", next));
+                                    unknown_arg_found = true;
+                                }
+                            }
+                            i += 1; // Skip the parsed argument
+                        }
+                    } else {
+                        Log::log_warning(
+                            "Missing arguments for --dump-gamma. Usage: --dump-gamma <temperature> <gamma> [size]",
+                        );
+                        unknown_arg_found = true;
+                    }
+                }
                 _ => {
                     // Check if the argument starts with a dash, indicating it's an option
                     if arg_str.starts_with('-') {
@@ -123,18 +504,81 @@ impl ParsedArgs {
         }
 
         // Determine the action based on parsed flags
-        let action = if display_version {
+        let action = if display_version && verbose {
+            CliAction::ShowBuildInfo
+        } else if display_version {
             CliAction::ShowVersion
+        } else if verbose {
+            Log::log_warning("--verbose is only supported together with --version");
+            CliAction::ShowHelpDueToError
         } else if display_help || unknown_arg_found {
             if unknown_arg_found {
                 CliAction::ShowHelpDueToError
             } else {
                 CliAction::ShowHelp
             }
+        } else if run_dump_gamma {
+            match (test_temperature, test_gamma) {
+                (Some(temperature), Some(gamma)) => CliAction::DumpGamma {
+                    debug_enabled,
+                    temperature,
+                    gamma,
+                    size: dump_size,
+                },
+                _ => CliAction::ShowHelpDueToError,
+            }
+        } else if run_bench {
+            CliAction::Bench {
+                debug_enabled,
+                iterations: bench_iterations,
+            }
+        } else if run_calibrate {
+            CliAction::Calibrate { debug_enabled }
         } else if run_geo_selection {
             CliAction::RunGeoSelection { debug_enabled }
+        } else if let Some((lat, lon)) = location {
+            CliAction::SetLocation {
+                debug_enabled,
+                lat,
+                lon,
+            }
         } else if run_reload {
             CliAction::Reload { debug_enabled }
+        } else if run_migrate {
+            CliAction::Migrate { debug_enabled }
+        } else if run_check_hyprsunset {
+            CliAction::CheckHyprsunset { debug_enabled }
+        } else if run_which_config {
+            CliAction::WhichConfig { debug_enabled }
+        } else if run_select_config {
+            CliAction::SelectConfig {
+                debug_enabled,
+                name: select_config_name,
+            }
+        } else if run_lint {
+            CliAction::Lint {
+                debug_enabled,
+                path: lint_path,
+            }
+        } else if run_once {
+            CliAction::Once { debug_enabled }
+        } else if run_monitor {
+            CliAction::Monitor { debug_enabled }
+        } else if let Some(kelvin) = nudge_kelvin {
+            CliAction::Nudge {
+                debug_enabled,
+                kelvin,
+            }
+        } else if let Some(command) = hold_command {
+            CliAction::Hold {
+                debug_enabled,
+                command,
+            }
+        } else if run_profile {
+            CliAction::SwitchProfile {
+                debug_enabled,
+                name: profile_name,
+            }
         } else if run_test {
             match (test_temperature, test_gamma) {
                 (Some(temp), Some(gamma)) => CliAction::Test {
@@ -148,10 +592,19 @@ impl ParsedArgs {
                 }
             }
         } else {
-            CliAction::Run { debug_enabled }
+            CliAction::Run {
+                debug_enabled,
+                if_running,
+                log_file,
+                backend,
+            }
         };
 
-        ParsedArgs { action }
+        ParsedArgs {
+            action,
+            safe_mode,
+            json,
+        }
     }
 
     /// Convenience method to parse from std::env::args()
@@ -162,9 +615,71 @@ impl ParsedArgs {
 
 /// Displays version information using custom logging style.
 pub fn display_version_info() {
+    crate::json::field("version", env!("CARGO_PKG_VERSION"));
     Log::log_version();
     Log::log_pipe();
-    println!("┗ {}", env!("CARGO_PKG_DESCRIPTION"));
+    if Log::is_console_output() {
+        println!("┗ {}", env!("CARGO_PKG_DESCRIPTION"));
+    }
+}
+
+/// Displays version and build details using custom logging style.
+///
+/// The build details come from `build.rs`, so they describe this binary rather than
+/// the environment it runs in.
+pub fn display_build_info() {
+    let features = env!("SUNSETR_FEATURES");
+    crate::json::field("version", env!("CARGO_PKG_VERSION"));
+    crate::json::field("commit", env!("SUNSETR_GIT_COMMIT"));
+    crate::json::field("compiler", env!("SUNSETR_RUSTC_VERSION"));
+    crate::json::field(
+        "features",
+        features
+            .split(',')
+            .filter(|feature| !feature.is_empty())
+            .collect::<Vec<_>>(),
+    );
+    crate::json::field("backends", build_backends());
+
+    Log::log_version();
+    Log::log_block_start("Build information:");
+    for line in build_info_lines() {
+        Log::log_indented(&line);
+    }
+    Log::log_end();
+}
+
+/// Lines describing how this binary was built.
+pub fn build_info_lines() -> Vec<String> {
+    let features = env!("SUNSETR_FEATURES");
+    let backends = build_backends();
+
+    vec![
+        format!("Version:  {}", env!("CARGO_PKG_VERSION")),
+        format!("Commit:   {}", env!("SUNSETR_GIT_COMMIT")),
+        format!("Compiler: {}", env!("SUNSETR_RUSTC_VERSION")),
+        format!(
+            "Features: {}",
+            if features.is_empty() {
+                "none"
+            } else {
+                features
+            }
+        ),
+        format!("Backends: {}", backends.join(", ")),
+    ]
+}
+
+/// Backends compiled into this binary.
+fn build_backends() -> Vec<&'static str> {
+    let mut backends = vec!["hyprland", "wayland"];
+    if cfg!(feature = "testing-support") {
+        backends.push("mock");
+    }
+    if cfg!(feature = "drm") {
+        backends.push("drm");
+    }
+    backends
 }
 
 /// Displays custom help message using logger methods.
@@ -173,12 +688,37 @@ pub fn display_help() {
     Log::log_block_start(env!("CARGO_PKG_DESCRIPTION"));
     Log::log_block_start("Usage: sunsetr [OPTIONS]");
     Log::log_block_start("Options:");
+    Log::log_indented("    --backend <name>      Override the configured backend for this run");
+    Log::log_indented("    --bench [count]       Time applies on the detected backend and exit");
+    Log::log_indented("    --calibrate           Preview night temperatures and save the favorite");
+    Log::log_indented("    --check-hyprsunset    Check the installed hyprsunset version and exit");
     Log::log_indented("-d, --debug               Enable detailed debug output");
+    Log::log_indented("    --dump-gamma <temp> <gamma> [size]");
+    Log::log_indented("                          Print the gamma ramp as CSV without applying it");
     Log::log_indented("-g, --geo                 Interactive city selection for geo mode");
     Log::log_indented("-h, --help                Print help information");
+    Log::log_indented("    --hold <temp> <gamma> <minutes>");
+    Log::log_indented("                          Hold these values for a while (release ends it)");
+    Log::log_indented("    --if-running <mode>   When already running: error, reload or replace");
+    Log::log_indented("    --json                Print each command's result as a JSON object");
+    Log::log_indented("-l, --lint [path]         Check a config file for problems and exit");
+    Log::log_indented("    --log-file <path>     Also write logs to a rotating file");
+    Log::log_indented("    --migrate             Move a legacy hypr/sunsetr.toml to sunsetr/");
+    Log::log_indented("-m, --monitor             Show a live dashboard of the running instance");
+    Log::log_indented("-o, --once                Apply the current state once and exit");
+    Log::log_indented("    --nudge <kelvin|reset>");
+    Log::log_indented("                          Shift the temperature until the next change");
+    Log::log_indented("-p, --profile [name]      Switch to a profile (cycles if no name given)");
     Log::log_indented("-r, --reload              Reset all display gamma and reload sunsetr");
+    Log::log_indented("    --safe                Never write config files or stop other processes");
+    Log::log_indented("    --select-config [name]");
+    Log::log_indented("                          Pick the config file to use from sunsetr/*.toml");
+    Log::log_indented("    --set-location <lat> <lon>");
+    Log::log_indented("                          Set geo coordinates without the city selector");
     Log::log_indented("-t, --test <temp> <gamma> Test specific temperature and gamma values");
+    Log::log_indented("    --verbose             With --version, also print build details");
     Log::log_indented("-V, --version             Print version information");
+    Log::log_indented("    --which-config        Show which config files are in use and exit");
     Log::log_end();
 }
 
@@ -193,7 +733,10 @@ mod tests {
         assert_eq!(
             parsed.action,
             CliAction::Run {
-                debug_enabled: false
+                debug_enabled: false,
+                if_running: IfRunning::Error,
+                log_file: None,
+                backend: None,
             }
         );
     }
@@ -205,7 +748,10 @@ mod tests {
         assert_eq!(
             parsed.action,
             CliAction::Run {
-                debug_enabled: true
+                debug_enabled: true,
+                if_running: IfRunning::Error,
+                log_file: None,
+                backend: None,
             }
         );
     }
@@ -217,7 +763,10 @@ mod tests {
         assert_eq!(
             parsed.action,
             CliAction::Run {
-                debug_enabled: true
+                debug_enabled: true,
+                if_running: IfRunning::Error,
+                log_file: None,
+                backend: None,
             }
         );
     }
@@ -254,6 +803,27 @@ mod tests {
         assert_eq!(parsed2.action, CliAction::ShowVersion);
     }
 
+    #[test]
+    fn test_parse_version_verbose() {
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--version", "--verbose"]);
+        assert_eq!(parsed.action, CliAction::ShowBuildInfo);
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--verbose", "-V"]);
+        assert_eq!(parsed.action, CliAction::ShowBuildInfo);
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--verbose"]);
+        assert_eq!(parsed.action, CliAction::ShowHelpDueToError);
+    }
+
+    #[test]
+    fn test_build_info_lines() {
+        let lines = build_info_lines();
+        assert!(lines[0].ends_with(env!("CARGO_PKG_VERSION")));
+        assert!(lines.iter().any(|l| l.starts_with("Compiler: ")));
+        let backends = lines.iter().find(|l| l.starts_with("Backends: ")).unwrap();
+        assert!(backends.contains("wayland") && backends.contains("hyprland"));
+    }
+
     #[test]
     fn test_parse_multiple_flags() {
         let args = vec!["sunsetr", "--debug", "--help"];
@@ -332,4 +902,386 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_parse_profile_with_name() {
+        let args = vec!["sunsetr", "--profile", "relax", "--debug"];
+        let parsed = ParsedArgs::parse(args);
+        assert_eq!(
+            parsed.action,
+            CliAction::SwitchProfile {
+                debug_enabled: true,
+                name: Some("relax".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_profile_without_name_cycles() {
+        let args = vec!["sunsetr", "-p"];
+        let parsed = ParsedArgs::parse(args);
+        assert_eq!(
+            parsed.action,
+            CliAction::SwitchProfile {
+                debug_enabled: false,
+                name: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_nudge() {
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--nudge", "-300", "--debug"]);
+        assert_eq!(
+            parsed.action,
+            CliAction::Nudge {
+                debug_enabled: true,
+                kelvin: -300,
+            }
+        );
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--nudge", "reset"]);
+        assert_eq!(
+            parsed.action,
+            CliAction::Nudge {
+                debug_enabled: false,
+                kelvin: 0,
+            }
+        );
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--nudge"]);
+        assert_eq!(parsed.action, CliAction::ShowHelpDueToError);
+    }
+
+    #[test]
+    fn test_parse_hold() {
+        use crate::commands::hold::HoldCommand;
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--hold", "3000", "90", "120"]);
+        assert_eq!(
+            parsed.action,
+            CliAction::Hold {
+                debug_enabled: false,
+                command: HoldCommand::Start {
+                    temperature: 3000,
+                    gamma: 90.0,
+                    minutes: 120,
+                },
+            }
+        );
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--hold", "release", "--debug"]);
+        assert_eq!(
+            parsed.action,
+            CliAction::Hold {
+                debug_enabled: true,
+                command: HoldCommand::Release,
+            }
+        );
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--hold", "3000", "90"]);
+        assert_eq!(parsed.action, CliAction::ShowHelpDueToError);
+    }
+
+    #[test]
+    fn test_parse_set_location() {
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--set-location", "-33.87", "151.21"]);
+        assert_eq!(
+            parsed.action,
+            CliAction::SetLocation {
+                debug_enabled: false,
+                lat: -33.87,
+                lon: 151.21,
+            }
+        );
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--set-location", "52.5"]);
+        assert_eq!(parsed.action, CliAction::ShowHelpDueToError);
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--set-location", "north", "13.4"]);
+        assert_eq!(parsed.action, CliAction::ShowHelpDueToError);
+    }
+
+    #[test]
+    fn test_parse_safe_flag() {
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--safe", "--debug"]);
+        assert!(parsed.safe_mode);
+        assert_eq!(
+            parsed.action,
+            CliAction::Run {
+                debug_enabled: true,
+                if_running: IfRunning::Error,
+                log_file: None,
+                backend: None,
+            }
+        );
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--geo", "--safe"]);
+        assert!(parsed.safe_mode);
+
+        assert!(!ParsedArgs::parse(vec!["sunsetr"]).safe_mode);
+    }
+
+    #[test]
+    fn test_parse_json_flag() {
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--json", "--nudge", "-300"]);
+        assert!(parsed.json);
+        assert_eq!(parsed.action.name(), "nudge");
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--which-config", "--json"]);
+        assert!(parsed.json);
+        assert_eq!(parsed.action.name(), "which-config");
+
+        assert!(!ParsedArgs::parse(vec!["sunsetr", "--reload"]).json);
+    }
+
+    #[test]
+    fn test_parse_monitor_flag() {
+        let args = vec!["sunsetr", "-m", "--debug"];
+        let parsed = ParsedArgs::parse(args);
+        assert_eq!(
+            parsed.action,
+            CliAction::Monitor {
+                debug_enabled: true
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_lint_flag() {
+        let args = vec!["sunsetr", "--lint", "dotfiles/sunsetr.toml"];
+        let parsed = ParsedArgs::parse(args);
+        assert_eq!(
+            parsed.action,
+            CliAction::Lint {
+                debug_enabled: false,
+                path: Some("dotfiles/sunsetr.toml".to_string()),
+            }
+        );
+
+        let args = vec!["sunsetr", "-l", "-d"];
+        let parsed = ParsedArgs::parse(args);
+        assert_eq!(
+            parsed.action,
+            CliAction::Lint {
+                debug_enabled: true,
+                path: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_migrate_flag() {
+        let args = vec!["sunsetr", "--migrate", "--debug"];
+        let parsed = ParsedArgs::parse(args);
+        assert_eq!(
+            parsed.action,
+            CliAction::Migrate {
+                debug_enabled: true
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_check_hyprsunset_flag() {
+        let args = vec!["sunsetr", "--check-hyprsunset"];
+        let parsed = ParsedArgs::parse(args);
+        assert_eq!(
+            parsed.action,
+            CliAction::CheckHyprsunset {
+                debug_enabled: false
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_which_config_flag() {
+        let args = vec!["sunsetr", "--which-config", "-d"];
+        let parsed = ParsedArgs::parse(args);
+        assert_eq!(
+            parsed.action,
+            CliAction::WhichConfig {
+                debug_enabled: true
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_calibrate_flag() {
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--calibrate", "--debug"]);
+        assert_eq!(
+            parsed.action,
+            CliAction::Calibrate {
+                debug_enabled: true
+            }
+        );
+        assert_eq!(parsed.action.name(), "calibrate");
+    }
+
+    #[test]
+    fn test_parse_select_config_flag() {
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--select-config"]);
+        assert_eq!(
+            parsed.action,
+            CliAction::SelectConfig {
+                debug_enabled: false,
+                name: None
+            }
+        );
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--select-config", "work", "-d"]);
+        assert_eq!(
+            parsed.action,
+            CliAction::SelectConfig {
+                debug_enabled: true,
+                name: Some("work".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_once_flag() {
+        let args = vec!["sunsetr", "--once"];
+        let parsed = ParsedArgs::parse(args);
+        assert_eq!(
+            parsed.action,
+            CliAction::Once {
+                debug_enabled: false
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_if_running() {
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--if-running=reload"]);
+        assert_eq!(
+            parsed.action,
+            CliAction::Run {
+                debug_enabled: false,
+                if_running: IfRunning::Reload,
+                log_file: None,
+                backend: None,
+            }
+        );
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "-d", "--if-running", "replace"]);
+        assert_eq!(
+            parsed.action,
+            CliAction::Run {
+                debug_enabled: true,
+                if_running: IfRunning::Replace,
+                log_file: None,
+                backend: None,
+            }
+        );
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--if-running=restart"]);
+        assert_eq!(parsed.action, CliAction::ShowHelpDueToError);
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--if-running"]);
+        assert_eq!(parsed.action, CliAction::ShowHelpDueToError);
+    }
+
+    #[test]
+    fn test_parse_log_file() {
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--log-file", "/tmp/sunsetr.log", "-d"]);
+        assert_eq!(
+            parsed.action,
+            CliAction::Run {
+                debug_enabled: true,
+                if_running: IfRunning::Error,
+                log_file: Some("/tmp/sunsetr.log".to_string()),
+                backend: None,
+            }
+        );
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--log-file"]);
+        assert_eq!(parsed.action, CliAction::ShowHelpDueToError);
+    }
+
+    #[test]
+    fn test_parse_backend() {
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--backend", "wayland"]);
+        assert_eq!(
+            parsed.action,
+            CliAction::Run {
+                debug_enabled: false,
+                if_running: IfRunning::Error,
+                log_file: None,
+                backend: Some(Backend::Wayland),
+            }
+        );
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "-d", "--backend=auto"]);
+        assert_eq!(
+            parsed.action,
+            CliAction::Run {
+                debug_enabled: true,
+                if_running: IfRunning::Error,
+                log_file: None,
+                backend: Some(Backend::Auto),
+            }
+        );
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--backend=gnome"]);
+        assert_eq!(parsed.action, CliAction::ShowHelpDueToError);
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--backend"]);
+        assert_eq!(parsed.action, CliAction::ShowHelpDueToError);
+    }
+
+    #[test]
+    fn test_parse_dump_gamma() {
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--dump-gamma", "3300", "90"]);
+        assert_eq!(
+            parsed.action,
+            CliAction::DumpGamma {
+                debug_enabled: false,
+                temperature: 3300,
+                gamma: 90.0,
+                size: crate::constants::DEFAULT_DUMP_GAMMA_SIZE,
+            }
+        );
+
+        let parsed =
+            ParsedArgs::parse(vec!["sunsetr", "--dump-gamma", "8000", "100", "1024", "-d"]);
+        assert_eq!(
+            parsed.action,
+            CliAction::DumpGamma {
+                debug_enabled: true,
+                temperature: 8000,
+                gamma: 100.0,
+                size: 1024,
+            }
+        );
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--dump-gamma", "3300"]);
+        assert_eq!(parsed.action, CliAction::ShowHelpDueToError);
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--dump-gamma", "3300", "90", "big"]);
+        assert_eq!(parsed.action, CliAction::ShowHelpDueToError);
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--bench"]);
+        assert_eq!(
+            parsed.action,
+            CliAction::Bench {
+                debug_enabled: false,
+                iterations: crate::constants::DEFAULT_BENCH_ITERATIONS,
+            }
+        );
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--bench", "200", "--debug"]);
+        assert_eq!(
+            parsed.action,
+            CliAction::Bench {
+                debug_enabled: true,
+                iterations: 200,
+            }
+        );
+
+        let parsed = ParsedArgs::parse(vec!["sunsetr", "--bench", "many"]);
+        assert_eq!(parsed.action, CliAction::ShowHelpDueToError);
+    }
 }
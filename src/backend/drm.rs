@@ -0,0 +1,439 @@
+//! DRM backend for setting gamma directly on the kernel's display controllers.
+//!
+//! Without a compositor (a plain TTY or kmscon session) there is nobody to speak
+//! wlr-gamma-control or hyprsunset's IPC with, but the kernel's legacy gamma LUTs
+//! are still there. This backend opens the first usable `/dev/dri/card*` device and
+//! sets the LUT of every active CRTC with the same ioctls libdrm's
+//! `drmModeCrtcSetGamma` wraps, using ramps from [`gamma::create_gamma_tables`]
+//! sized to each CRTC.
+//!
+//! Setting gamma needs DRM master, which the kernel hands to the first process that
+//! opens the device while no compositor holds it. That is why auto-detection only
+//! picks this backend when neither `WAYLAND_DISPLAY` nor `DISPLAY` is set.
+//!
+//! The LUTs found at startup are restored on exit, much like a compositor restores
+//! its own gamma when a Wayland client releases its gamma control.
+
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::os::fd::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+use crate::backend::wayland::gamma::{self, GammaTableFormat};
+use crate::backend::{BackendError, ColorTemperatureBackend};
+use crate::config::Config;
+use crate::logger::Log;
+use crate::time_state::TransitionState;
+
+/// Directory holding the DRM device nodes.
+const DRM_DEVICE_DIR: &str = "/dev/dri";
+
+/// `struct drm_mode_card_res` from `drm_mode.h`.
+#[repr(C)]
+#[derive(Debug, Default)]
+struct DrmModeCardRes {
+    fb_id_ptr: u64,
+    crtc_id_ptr: u64,
+    connector_id_ptr: u64,
+    encoder_id_ptr: u64,
+    count_fbs: u32,
+    count_crtcs: u32,
+    count_connectors: u32,
+    count_encoders: u32,
+    min_width: u32,
+    max_width: u32,
+    min_height: u32,
+    max_height: u32,
+}
+
+/// `struct drm_mode_modeinfo` from `drm_mode.h`.
+#[repr(C)]
+#[derive(Debug, Default)]
+struct DrmModeModeinfo {
+    clock: u32,
+    hdisplay: u16,
+    hsync_start: u16,
+    hsync_end: u16,
+    htotal: u16,
+    hskew: u16,
+    vdisplay: u16,
+    vsync_start: u16,
+    vsync_end: u16,
+    vtotal: u16,
+    vscan: u16,
+    vrefresh: u32,
+    flags: u32,
+    mode_type: u32,
+    name: [u8; 32],
+}
+
+/// `struct drm_mode_crtc` from `drm_mode.h`.
+#[repr(C)]
+#[derive(Debug, Default)]
+struct DrmModeCrtc {
+    set_connectors_ptr: u64,
+    count_connectors: u32,
+    crtc_id: u32,
+    fb_id: u32,
+    x: u32,
+    y: u32,
+    gamma_size: u32,
+    mode_valid: u32,
+    mode: DrmModeModeinfo,
+}
+
+/// `struct drm_mode_crtc_lut` from `drm_mode.h`.
+#[repr(C)]
+#[derive(Debug, Default)]
+struct DrmModeCrtcLut {
+    crtc_id: u32,
+    gamma_size: u32,
+    red: u64,
+    green: u64,
+    blue: u64,
+}
+
+nix::ioctl_readwrite!(drm_mode_getresources, b'd', 0xA0, DrmModeCardRes);
+nix::ioctl_readwrite!(drm_mode_getcrtc, b'd', 0xA1, DrmModeCrtc);
+nix::ioctl_readwrite!(drm_mode_getgamma, b'd', 0xA4, DrmModeCrtcLut);
+nix::ioctl_readwrite!(drm_mode_setgamma, b'd', 0xA5, DrmModeCrtcLut);
+
+/// Red, green and blue ramps of one LUT.
+type GammaRamps = (Vec<u16>, Vec<u16>, Vec<u16>);
+
+/// An active CRTC whose gamma LUT we control.
+struct Crtc {
+    id: u32,
+    gamma_size: usize,
+    /// The LUT found at startup, restored on exit when it could be read
+    original: Option<GammaRamps>,
+}
+
+/// Color temperature backend writing gamma LUTs through the kernel's DRM interface.
+pub struct DrmBackend {
+    device: File,
+    device_path: PathBuf,
+    crtcs: Vec<Crtc>,
+    channel_gamma: (f32, f32, f32),
+    whitepoint_correction: (f32, f32, f32),
+    debug_enabled: bool,
+}
+
+/// The DRM card nodes on this system, in name order.
+fn card_devices() -> Vec<PathBuf> {
+    let mut cards: Vec<PathBuf> = std::fs::read_dir(DRM_DEVICE_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.file_name().to_string_lossy().starts_with("card"))
+                .map(|entry| entry.path())
+                .collect()
+        })
+        .unwrap_or_default();
+    cards.sort();
+    cards
+}
+
+/// Whether a DRM card device exists that this backend could try.
+pub fn device_available() -> bool {
+    !card_devices().is_empty()
+}
+
+/// Query the IDs of all CRTCs on the device.
+fn crtc_ids(device: &File) -> Result<Vec<u32>> {
+    // First call reports the counts, the second fills in the CRTC IDs
+    let mut res = DrmModeCardRes::default();
+    unsafe { drm_mode_getresources(device.as_raw_fd(), &mut res) }
+        .context("DRM_IOCTL_MODE_GETRESOURCES failed (not a KMS device?)")?;
+
+    let mut ids = vec![0u32; res.count_crtcs as usize];
+    let mut res = DrmModeCardRes {
+        crtc_id_ptr: ids.as_mut_ptr() as u64,
+        count_crtcs: ids.len() as u32,
+        ..Default::default()
+    };
+    unsafe { drm_mode_getresources(device.as_raw_fd(), &mut res) }
+        .context("DRM_IOCTL_MODE_GETRESOURCES failed")?;
+
+    // CRTCs can't be hotplugged, but don't trust a count that grew in between
+    ids.truncate(res.count_crtcs as usize);
+    Ok(ids)
+}
+
+/// Read a CRTC's LUT so it can be restored later.
+fn read_gamma(device: &File, crtc_id: u32, size: usize) -> Result<GammaRamps> {
+    let (mut red, mut green, mut blue) = (vec![0u16; size], vec![0u16; size], vec![0u16; size]);
+    let mut lut = DrmModeCrtcLut {
+        crtc_id,
+        gamma_size: size as u32,
+        red: red.as_mut_ptr() as u64,
+        green: green.as_mut_ptr() as u64,
+        blue: blue.as_mut_ptr() as u64,
+    };
+    unsafe { drm_mode_getgamma(device.as_raw_fd(), &mut lut) }
+        .with_context(|| format!("Failed to read gamma of CRTC {}", crtc_id))?;
+    Ok((red, green, blue))
+}
+
+/// Write a CRTC's LUT, the equivalent of libdrm's `drmModeCrtcSetGamma`.
+fn write_gamma(
+    device: &File,
+    crtc_id: u32,
+    red: &[u16],
+    green: &[u16],
+    blue: &[u16],
+) -> Result<()> {
+    let mut lut = DrmModeCrtcLut {
+        crtc_id,
+        gamma_size: red.len() as u32,
+        red: red.as_ptr() as u64,
+        green: green.as_ptr() as u64,
+        blue: blue.as_ptr() as u64,
+    };
+    unsafe { drm_mode_setgamma(device.as_raw_fd(), &mut lut) }.map_err(|errno| {
+        let error = anyhow::anyhow!("Failed to set gamma on CRTC {}: {}", crtc_id, errno);
+        match errno {
+            // Another process (usually a compositor) holds DRM master
+            nix::errno::Errno::EACCES | nix::errno::Errno::EPERM => {
+                error.context(BackendError::PermanentlyUnavailable)
+            }
+            _ => error.context(BackendError::TemporarilyUnavailable),
+        }
+    })?;
+    Ok(())
+}
+
+/// Split the planar little-endian output of [`gamma::create_gamma_tables`] into ramps.
+fn decode_ramps(data: &[u8], size: usize) -> GammaRamps {
+    let values: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .collect();
+    (
+        values[..size].to_vec(),
+        values[size..2 * size].to_vec(),
+        values[2 * size..3 * size].to_vec(),
+    )
+}
+
+/// Find the active CRTCs of one device along with their gamma sizes.
+fn open_device(path: &Path, debug_enabled: bool) -> Result<(File, Vec<Crtc>)> {
+    let device = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+
+    let mut crtcs = Vec::new();
+    for id in crtc_ids(&device)? {
+        let mut crtc = DrmModeCrtc {
+            crtc_id: id,
+            ..Default::default()
+        };
+        unsafe { drm_mode_getcrtc(device.as_raw_fd(), &mut crtc) }
+            .with_context(|| format!("Failed to query CRTC {}", id))?;
+
+        // Only CRTCs that are scanning out a mode drive a display
+        if crtc.mode_valid == 0 || crtc.gamma_size == 0 {
+            continue;
+        }
+        let gamma_size = crtc.gamma_size as usize;
+        let original = read_gamma(&device, id, gamma_size).ok();
+
+        if debug_enabled {
+            Log::log_indented(&format!(
+                "CRTC {}: {}x{}, gamma size {}",
+                id, crtc.mode.hdisplay, crtc.mode.vdisplay, gamma_size
+            ));
+        }
+        crtcs.push(Crtc {
+            id,
+            gamma_size,
+            original,
+        });
+    }
+    Ok((device, crtcs))
+}
+
+impl DrmBackend {
+    /// Create a new DRM backend on the first card device with an active CRTC.
+    ///
+    /// # Arguments
+    /// * `config` - Configuration containing color correction settings
+    /// * `debug_enabled` - Whether to enable debug output for this backend
+    ///
+    /// # Errors
+    /// Returns an error if no `/dev/dri/card*` device can be opened or none of them
+    /// has an active CRTC with a gamma LUT.
+    pub fn new(config: &Config, debug_enabled: bool) -> Result<Self> {
+        Log::log_decorated("Initializing DRM gamma control backend...");
+
+        let mut last_error = None;
+        for path in card_devices() {
+            if debug_enabled {
+                Log::log_debug(&format!("Probing {}", path.display()));
+            }
+            match open_device(&path, debug_enabled) {
+                Ok((device, crtcs)) if !crtcs.is_empty() => {
+                    let mut backend = Self {
+                        device,
+                        device_path: path,
+                        crtcs,
+                        channel_gamma: (1.0, 1.0, 1.0),
+                        whitepoint_correction: (1.0, 1.0, 1.0),
+                        debug_enabled,
+                    };
+                    backend.update_from_config(config);
+                    Log::log_decorated(&format!(
+                        "Controlling {} CRTC(s) on {}",
+                        backend.crtcs.len(),
+                        backend.device_path.display()
+                    ));
+                    return Ok(backend);
+                }
+                Ok(_) => {
+                    last_error = Some(anyhow::anyhow!(
+                        "{} has no active CRTC with a gamma LUT",
+                        path.display()
+                    ))
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        Log::log_pipe();
+        Err(last_error
+            .unwrap_or_else(|| anyhow::anyhow!("No DRM devices found in {}", DRM_DEVICE_DIR))
+            .context("No usable DRM device for gamma control"))
+    }
+
+    /// Pick up the color correction settings that shape the ramps.
+    fn update_from_config(&mut self, config: &Config) {
+        self.whitepoint_correction = config
+            .day_whitepoint
+            .and_then(|[x, y]| gamma::whitepoint_correction(x, y))
+            .unwrap_or((1.0, 1.0, 1.0));
+    }
+
+    /// Write ramps for the given values to every active CRTC.
+    fn apply_gamma(&mut self, temperature: u32, gamma_percent: f32) -> Result<()> {
+        for crtc in &self.crtcs {
+            let data = gamma::create_gamma_tables(
+                crtc.gamma_size,
+                temperature,
+                gamma_percent,
+                self.whitepoint_correction,
+                self.channel_gamma,
+                GammaTableFormat::default(),
+                self.debug_enabled,
+            )?;
+            let (red, green, blue) = decode_ramps(&data, crtc.gamma_size);
+            write_gamma(&self.device, crtc.id, &red, &green, &blue)?;
+        }
+        Ok(())
+    }
+}
+
+impl ColorTemperatureBackend for DrmBackend {
+    fn apply_transition_state(
+        &mut self,
+        state: TransitionState,
+        config: &Config,
+        _running: &AtomicBool,
+    ) -> Result<()> {
+        self.update_from_config(config);
+
+        let (temp, gamma) = crate::time_state::get_initial_values_for_state(state, config);
+        self.channel_gamma = crate::time_state::get_channel_gamma_for_state(state, config);
+        if self.debug_enabled {
+            Log::log_pipe();
+            Log::log_debug(&format!(
+                "DRM backend applying state: temp={}K, gamma={:.1}%",
+                temp, gamma
+            ));
+        }
+        self.apply_gamma(temp, gamma / 100.0) // Convert percentage to 0.0-1.0
+    }
+
+    fn apply_startup_state(
+        &mut self,
+        state: TransitionState,
+        config: &Config,
+        running: &AtomicBool,
+    ) -> Result<()> {
+        crate::time_state::log_state_announcement(state);
+
+        if self.debug_enabled {
+            Log::log_pipe();
+            Log::log_debug("Applying DRM startup state...");
+        }
+
+        self.apply_transition_state(state, config, running)
+    }
+
+    fn apply_temperature_gamma(
+        &mut self,
+        temperature: u32,
+        gamma: f32,
+        _running: &AtomicBool,
+    ) -> Result<()> {
+        self.apply_gamma(temperature, gamma / 100.0) // Convert percentage to 0.0-1.0
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "DRM"
+    }
+
+    fn cleanup(self: Box<Self>, debug_enabled: bool) {
+        // The kernel keeps our LUT after we exit, so put back what was there before
+        let mut restored = 0;
+        for crtc in &self.crtcs {
+            let Some((red, green, blue)) = &crtc.original else {
+                continue;
+            };
+            match write_gamma(&self.device, crtc.id, red, green, blue) {
+                Ok(()) => restored += 1,
+                Err(e) => Log::log_decorated(&format!("Warning: {}", e)),
+            }
+        }
+        if debug_enabled {
+            Log::log_decorated(&format!("Restored gamma on {} CRTC(s)", restored));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drm_struct_layouts_match_kernel() {
+        // The ioctl numbers encode these sizes, so a mismatch means EINVAL from the kernel
+        assert_eq!(std::mem::size_of::<DrmModeCardRes>(), 64);
+        assert_eq!(std::mem::size_of::<DrmModeModeinfo>(), 68);
+        assert_eq!(std::mem::size_of::<DrmModeCrtc>(), 104);
+        assert_eq!(std::mem::size_of::<DrmModeCrtcLut>(), 32);
+    }
+
+    #[test]
+    fn test_decode_ramps_matches_gamma_size() {
+        for size in [256, 1024] {
+            let data = gamma::create_gamma_tables(
+                size,
+                3000,
+                0.9,
+                (1.0, 1.0, 1.0),
+                (1.0, 1.0, 1.0),
+                GammaTableFormat::default(),
+                false,
+            )
+            .unwrap();
+            let (red, green, blue) = decode_ramps(&data, size);
+            assert_eq!((red.len(), green.len(), blue.len()), (size, size, size));
+            assert_eq!(red[0], 0);
+            // Warm temperatures dim blue the most
+            assert!(blue[size - 1] < green[size - 1] && green[size - 1] <= red[size - 1]);
+        }
+    }
+}
@@ -26,6 +26,10 @@
 //! - Uses `HYPRLAND_INSTANCE_SIGNATURE` to identify the correct Hyprland instance
 //! - Falls back to `XDG_RUNTIME_DIR` or `/run/user/{uid}` for base directory
 //! - Constructs path: `{runtime_dir}/hypr/{instance}/.hyprsunset.sock`
+//!
+//! Detection can pick the wrong instance in nested or multi-instance setups, so an
+//! explicit path from the `HYPRSUNSET_SOCKET` environment variable or the
+//! `hyprsunset_socket` config option (in that order) is used as-is when set.
 
 use anyhow::{Context, Result};
 use std::io::{ErrorKind, Read, Write};
@@ -35,6 +39,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 use std::time::Duration;
 
+use crate::backend::BackendError;
 use crate::config::Config;
 use crate::constants::*;
 use crate::logger::Log;
@@ -62,35 +67,39 @@ enum ErrorType {
 /// - State application with interpolated values during transitions
 pub struct HyprsunsetClient {
     pub socket_path: PathBuf,
+    /// Where `socket_path` came from, for diagnostics
+    pub socket_source: &'static str,
     pub debug_enabled: bool,
+    /// Last (temperature, gamma) hyprsunset fully accepted, used to skip redundant applies
+    last_applied: Option<(u32, f32)>,
 }
 
 impl HyprsunsetClient {
     /// Create a new hyprsunset client with appropriate socket path.
     ///
-    /// Determines the socket path using the same logic as hyprsunset:
+    /// Uses the `HYPRSUNSET_SOCKET` environment variable or `socket_override` (the
+    /// `hyprsunset_socket` config option) when set. Otherwise the socket path is
+    /// determined using the same logic as hyprsunset:
     /// 1. Check HYPRLAND_INSTANCE_SIGNATURE environment variable
     /// 2. Use XDG_RUNTIME_DIR or fallback to /run/user/{uid}
     /// 3. Construct path: {runtime_dir}/hypr/{instance}/.hyprsunset.sock
     ///
     /// # Arguments
+    /// * `socket_override` - Socket path from the config, bypassing auto-detection
     /// * `debug_enabled` - Whether to enable debug output for this client
     ///
     /// # Returns
     /// New HyprsunsetClient instance ready for connection attempts
-    pub fn new(debug_enabled: bool) -> Result<Self> {
-        // Determine socket path (similar to how hyprsunset does it)
-        let his_env = std::env::var("HYPRLAND_INSTANCE_SIGNATURE").ok();
+    pub fn new(socket_override: Option<&str>, debug_enabled: bool) -> Result<Self> {
         let runtime_dir = std::env::var("XDG_RUNTIME_DIR")
             .unwrap_or_else(|_| format!("/run/user/{}", nix::unistd::getuid()));
 
-        let user_dir = format!("{}/hypr/", runtime_dir);
-
-        let socket_path = if let Some(his) = his_env {
-            PathBuf::from(format!("{}{}/.hyprsunset.sock", user_dir, his))
-        } else {
-            PathBuf::from(format!("{}/.hyprsunset.sock", user_dir))
-        };
+        let (socket_path, socket_source) = resolve_socket_path(
+            std::env::var(HYPRSUNSET_SOCKET_ENV).ok(),
+            socket_override,
+            std::env::var("HYPRLAND_INSTANCE_SIGNATURE").ok(),
+            &runtime_dir,
+        );
 
         // Only log socket path if file doesn't exist (for debugging)
         if !socket_path.exists() && debug_enabled {
@@ -99,7 +108,9 @@ impl HyprsunsetClient {
 
         Ok(Self {
             socket_path,
+            socket_source,
             debug_enabled,
+            last_applied: None,
         })
     }
 
@@ -143,6 +154,8 @@ impl HyprsunsetClient {
                 if self.debug_enabled {
                     Log::log_decorated("Successfully reconnected to hyprsunset");
                 }
+                // A restarted hyprsunset has lost our values, so the next apply must go through
+                self.last_applied = None;
                 return true;
             }
 
@@ -281,10 +294,13 @@ impl HyprsunsetClient {
         }
 
         // Return the last error with context
-        Err(last_error.unwrap().context(format!(
-            "Failed to send command '{}' after {} attempts and reconnection attempt",
-            command, max_retries
-        )))
+        Err(last_error
+            .unwrap()
+            .context(BackendError::PermanentlyUnavailable)
+            .context(format!(
+                "Failed to send command '{}' after {} attempts and reconnection attempt",
+                command, max_retries
+            )))
     }
 
     /// Attempt to send a single command without retry logic.
@@ -299,6 +315,18 @@ impl HyprsunsetClient {
     /// # Returns
     /// Result indicating success or the specific failure
     fn try_send_command(&mut self, command: &str) -> Result<()> {
+        match self.exchange(command)? {
+            Some(response) if self.debug_enabled => {
+                Log::log_indented(&format!("Response: {}", response.trim()));
+            }
+            None if self.debug_enabled => Log::log_indented("Connection closed without response"),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Send one command and return hyprsunset's response, if it sent one.
+    fn exchange(&self, command: &str) -> Result<Option<String>> {
         // Connect to socket
         let mut stream = UnixStream::connect(&self.socket_path)
             .with_context(|| format!("Failed to connect to socket at {:?}", self.socket_path))?;
@@ -316,20 +344,47 @@ impl HyprsunsetClient {
         // Try to read a response, but don't fail if we can't get one
         // hyprsunset may close connections without sending responses
         let mut buffer = [0; SOCKET_BUFFER_SIZE];
-        if let Ok(bytes_read) = stream.read(&mut buffer) {
-            if bytes_read > 0 {
-                let response = String::from_utf8_lossy(&buffer[0..bytes_read]);
-                if self.debug_enabled {
-                    Log::log_indented(&format!("Response: {}", response.trim()));
-                }
-            } else if self.debug_enabled {
-                Log::log_indented("Connection closed without response");
+        let response = match stream.read(&mut buffer) {
+            Ok(bytes_read) if bytes_read > 0 => {
+                Some(String::from_utf8_lossy(&buffer[0..bytes_read]).into_owned())
             }
-        }
+            _ => None,
+        };
 
         // Explicitly close the stream for cleanup
         drop(stream);
-        Ok(())
+        Ok(response)
+    }
+
+    /// Ask hyprsunset which temperature and gamma it is showing right now.
+    ///
+    /// hyprsunset answers `temperature` and `gamma` without an argument with the
+    /// current value. Versions that don't support these queries reply with an
+    /// error instead, in which case this returns `None`, as it does when either
+    /// value is out of range or hyprsunset isn't reachable.
+    pub fn query_current_values(&self) -> Option<(u32, f32)> {
+        let temperature = self
+            .exchange("temperature")
+            .ok()
+            .flatten()
+            .and_then(|response| response.trim().parse::<f32>().ok())
+            .map(|kelvin| kelvin.round() as u32)
+            .filter(|kelvin| (MINIMUM_TEMP..=MAXIMUM_TEMP).contains(kelvin))?;
+        let gamma = self
+            .exchange("gamma")
+            .ok()
+            .flatten()
+            .and_then(|response| response.trim().parse::<f32>().ok())
+            .filter(|gamma| (MINIMUM_GAMMA..=MAXIMUM_GAMMA).contains(gamma))?;
+
+        if self.debug_enabled {
+            Log::log_pipe();
+            Log::log_debug(&format!(
+                "hyprsunset currently shows {}K @ {:.1}%",
+                temperature, gamma
+            ));
+        }
+        Some((temperature, gamma))
     }
 
     /// Test connection to hyprsunset socket without sending commands.
@@ -404,7 +459,7 @@ impl HyprsunsetClient {
         match state {
             TimeState::Day => {
                 // Execute temperature command with configured day temperature
-                let day_temp = config.day_temp.unwrap_or(DEFAULT_DAY_TEMP);
+                let day_temp = crate::time_state::nudged_temperature(config.effective().day_temp);
                 if self.debug_enabled {
                     Log::log_pipe();
                     Log::log_debug(&format!("Setting temperature to {}K...", day_temp));
@@ -415,11 +470,12 @@ impl HyprsunsetClient {
                 thread::sleep(Duration::from_millis(COMMAND_DELAY_MS));
 
                 // Execute gamma command
-                let day_gamma = config.day_gamma.unwrap_or(DEFAULT_DAY_GAMMA);
+                let day_gamma = config.effective().day_gamma;
                 if self.debug_enabled {
                     Log::log_debug(&format!("Setting gamma to {:.1}%...", day_gamma));
                 }
                 let gamma_success = self.run_gamma_command(day_gamma);
+                self.record_applied(day_temp, day_gamma, temp_success && gamma_success);
 
                 // Result handling - consider partial success acceptable
                 match (temp_success, gamma_success) {
@@ -448,7 +504,8 @@ impl HyprsunsetClient {
             }
             TimeState::Night => {
                 // Execute temperature command
-                let night_temp = config.night_temp.unwrap_or(DEFAULT_NIGHT_TEMP);
+                let night_temp =
+                    crate::time_state::nudged_temperature(config.effective().night_temp);
                 if self.debug_enabled {
                     Log::log_pipe();
                     Log::log_debug(&format!("Setting temperature to {}K...", night_temp));
@@ -459,11 +516,12 @@ impl HyprsunsetClient {
                 thread::sleep(Duration::from_millis(COMMAND_DELAY_MS));
 
                 // Execute gamma command
-                let night_gamma = config.night_gamma.unwrap_or(DEFAULT_NIGHT_GAMMA);
+                let night_gamma = config.effective().night_gamma;
                 if self.debug_enabled {
                     Log::log_debug(&format!("Setting gamma to {:.1}%...", night_gamma));
                 }
                 let gamma_success = self.run_gamma_command(night_gamma);
+                self.record_applied(night_temp, night_gamma, temp_success && gamma_success);
 
                 // Result handling - consider partial success acceptable
                 match (temp_success, gamma_success) {
@@ -520,6 +578,77 @@ impl HyprsunsetClient {
             return Ok(());
         }
 
+        // Skip values hyprsunset already has, e.g. when state is reapplied after a reconnection
+        let target = match state {
+            TransitionState::Stable(_) => {
+                crate::time_state::get_initial_values_for_state(state, config)
+            }
+            TransitionState::Transitioning { from, to, progress } => (
+                crate::time_state::nudged_temperature(
+                    crate::time_state::calculate_interpolated_temp(from, to, progress, config),
+                ),
+                crate::time_state::calculate_interpolated_gamma(from, to, progress, config),
+            ),
+        };
+        if self.last_applied == Some(target) {
+            if self.debug_enabled {
+                Log::log_pipe();
+                Log::log_debug(&format!(
+                    "Skipping redundant apply: hyprsunset already at {}K @ {:.1}%",
+                    target.0, target.1
+                ));
+            }
+            return Ok(());
+        }
+
+        match self.apply_transition_state_once(state, config, running) {
+            Err(e) if running.load(Ordering::SeqCst) => {
+                self.reconnect_and_reapply(state, config, running, e)
+            }
+            result => result,
+        }
+    }
+
+    /// Retry a failed apply once the socket accepts connections again.
+    ///
+    /// A hyprsunset restart makes both commands of an apply fail. Rather than waiting a
+    /// whole update cycle, check the socket (giving it one recovery delay if it's gone)
+    /// and resend the state once. If hyprsunset can't be reached at all, the error is
+    /// marked permanent so the main loop stops retrying.
+    fn reconnect_and_reapply(
+        &mut self,
+        state: TransitionState,
+        config: &Config,
+        running: &AtomicBool,
+        error: anyhow::Error,
+    ) -> Result<()> {
+        if self.debug_enabled {
+            Log::log_pipe();
+            Log::log_debug("Apply failed, reconnecting to hyprsunset to retry once...");
+        }
+
+        if !self.test_connection() {
+            thread::sleep(Duration::from_millis(SOCKET_RECOVERY_DELAY_MS));
+            if !self.test_connection() {
+                return Err(error
+                    .context(BackendError::PermanentlyUnavailable)
+                    .context("Failed to apply state after reconnection attempt"));
+            }
+        }
+
+        // hyprsunset may have restarted and lost our values, so send everything again
+        self.last_applied = None;
+        self.apply_transition_state_once(state, config, running)
+            .context("Failed to apply state again after reconnecting")
+    }
+
+    /// Send the commands for a state, without the redundancy check or reconnect retry.
+    fn apply_transition_state_once(
+        &mut self,
+        state: TransitionState,
+        config: &Config,
+        running: &AtomicBool,
+    ) -> Result<()> {
         match state {
             TransitionState::Stable(time_state) => {
                 // Use existing apply_state method for stable periods
@@ -527,8 +656,9 @@ impl HyprsunsetClient {
             }
             TransitionState::Transitioning { from, to, progress } => {
                 // Calculate interpolated values based on transition progress
-                let current_temp =
-                    crate::time_state::calculate_interpolated_temp(from, to, progress, config);
+                let current_temp = crate::time_state::nudged_temperature(
+                    crate::time_state::calculate_interpolated_temp(from, to, progress, config),
+                );
                 let current_gamma =
                     crate::time_state::calculate_interpolated_gamma(from, to, progress, config);
 
@@ -547,6 +677,7 @@ impl HyprsunsetClient {
                     Log::log_debug(&format!("Setting gamma to {:.1}%...", current_gamma));
                 }
                 let gamma_success = self.run_gamma_command(current_gamma);
+                self.record_applied(current_temp, current_gamma, temp_success && gamma_success);
 
                 // Result handling - consider partial success acceptable
                 match (temp_success, gamma_success) {
@@ -576,6 +707,25 @@ impl HyprsunsetClient {
         }
     }
 
+    /// Remember what hyprsunset was set to after an apply.
+    ///
+    /// Only a fully successful apply is remembered; after a partial failure the
+    /// next apply is always sent, even with the same values.
+    fn record_applied(&mut self, temperature: u32, gamma: f32, success: bool) {
+        self.last_applied = success.then_some((temperature, gamma));
+    }
+
+    /// Note values hyprsunset already has without sending them, e.g. the ones
+    /// a freshly started hyprsunset was launched with.
+    pub fn mark_applied(&mut self, temperature: u32, gamma: f32) {
+        self.last_applied = Some((temperature, gamma));
+    }
+
+    /// Forget the last applied values so the next apply is always sent.
+    pub fn forget_applied(&mut self) {
+        self.last_applied = None;
+    }
+
     /// Helper method for sending temperature commands.
     ///
     /// Wraps the temperature value in the appropriate command format
@@ -659,8 +809,9 @@ impl HyprsunsetClient {
             TransitionState::Transitioning { from, to, progress } => {
                 // For transitioning states, apply the interpolated values directly
                 // Calculate interpolated values
-                let current_temp =
-                    crate::time_state::calculate_interpolated_temp(from, to, progress, config);
+                let current_temp = crate::time_state::nudged_temperature(
+                    crate::time_state::calculate_interpolated_temp(from, to, progress, config),
+                );
                 let current_gamma =
                     crate::time_state::calculate_interpolated_gamma(from, to, progress, config);
 
@@ -679,6 +830,7 @@ impl HyprsunsetClient {
                     Log::log_debug(&format!("Setting gamma to {:.1}%...", current_gamma));
                 }
                 let gamma_success = self.run_gamma_command(current_gamma);
+                self.record_applied(current_temp, current_gamma, temp_success && gamma_success);
 
                 // Add pipe at the end
                 if self.debug_enabled {
@@ -749,6 +901,9 @@ impl HyprsunsetClient {
             return Ok(());
         }
 
+        // Forget the last values until both commands are through
+        self.last_applied = None;
+
         // Apply temperature
         let temp_command = format!("temperature {}", temperature);
 
@@ -772,6 +927,7 @@ impl HyprsunsetClient {
         eprintln!("DEBUG: Sending command to hyprsunset: '{}'", gamma_command);
 
         self.send_command(&gamma_command)?;
+        self.last_applied = Some((temperature, gamma));
 
         #[cfg(debug_assertions)]
         eprintln!(
@@ -839,3 +995,184 @@ fn classify_error(error: &anyhow::Error) -> ErrorType {
         ErrorType::Temporary
     }
 }
+
+/// Pick the hyprsunset socket path and describe where it came from.
+///
+/// An explicit path from the environment wins over the config option, and both
+/// bypass the instance-signature based detection. Empty overrides are ignored.
+fn resolve_socket_path(
+    env_override: Option<String>,
+    config_override: Option<&str>,
+    instance_signature: Option<String>,
+    runtime_dir: &str,
+) -> (PathBuf, &'static str) {
+    if let Some(path) = env_override.filter(|p| !p.trim().is_empty()) {
+        return (PathBuf::from(path), HYPRSUNSET_SOCKET_ENV);
+    }
+    if let Some(path) = config_override.filter(|p| !p.trim().is_empty()) {
+        return (PathBuf::from(path), "hyprsunset_socket");
+    }
+
+    let user_dir = format!("{}/hypr/", runtime_dir);
+    let socket_path = if let Some(his) = instance_signature {
+        PathBuf::from(format!("{}{}/.hyprsunset.sock", user_dir, his))
+    } else {
+        PathBuf::from(format!("{}/.hyprsunset.sock", user_dir))
+    };
+    (socket_path, "auto-detected")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixListener;
+    use std::sync::{Arc, Mutex};
+
+    /// Fake hyprsunset socket that records every command it receives.
+    fn fake_hyprsunset(socket_path: &std::path::Path) -> Arc<Mutex<Vec<String>>> {
+        let listener = UnixListener::bind(socket_path).unwrap();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let log = received.clone();
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut buffer = [0; SOCKET_BUFFER_SIZE];
+                if let Ok(n) = stream.read(&mut buffer)
+                    && n > 0
+                {
+                    log.lock()
+                        .unwrap()
+                        .push(String::from_utf8_lossy(&buffer[..n]).into_owned());
+                    let _ = stream.write_all(b"ok");
+                }
+            }
+        });
+        received
+    }
+
+    #[test]
+    fn test_resolve_socket_path_precedence() {
+        let runtime = "/run/user/1000";
+
+        let (path, source) = resolve_socket_path(None, None, Some("abc_123".to_string()), runtime);
+        assert_eq!(
+            path,
+            PathBuf::from("/run/user/1000/hypr/abc_123/.hyprsunset.sock")
+        );
+        assert_eq!(source, "auto-detected");
+
+        let (path, source) = resolve_socket_path(
+            None,
+            Some("/tmp/nested.sock"),
+            Some("abc_123".to_string()),
+            runtime,
+        );
+        assert_eq!(path, PathBuf::from("/tmp/nested.sock"));
+        assert_eq!(source, "hyprsunset_socket");
+
+        let (path, source) = resolve_socket_path(
+            Some("/tmp/env.sock".to_string()),
+            Some("/tmp/nested.sock"),
+            None,
+            runtime,
+        );
+        assert_eq!(path, PathBuf::from("/tmp/env.sock"));
+        assert_eq!(source, HYPRSUNSET_SOCKET_ENV);
+
+        // An empty variable falls through to the next source
+        let (_, source) = resolve_socket_path(Some(String::new()), None, None, runtime);
+        assert_eq!(source, "auto-detected");
+    }
+
+    #[test]
+    fn test_query_current_values() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let socket_path = temp_dir.path().join(".hyprsunset.sock");
+        let listener = UnixListener::bind(&socket_path).unwrap();
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut buffer = [0; SOCKET_BUFFER_SIZE];
+                let n = stream.read(&mut buffer).unwrap_or(0);
+                let response: &[u8] = match &buffer[..n] {
+                    b"temperature" => b"4000\n",
+                    b"gamma" => b"85.5",
+                    _ => b"invalid command",
+                };
+                let _ = stream.write_all(response);
+            }
+        });
+
+        let client = HyprsunsetClient {
+            socket_path,
+            socket_source: "test",
+            debug_enabled: false,
+            last_applied: None,
+        };
+        assert_eq!(client.query_current_values(), Some((4000, 85.5)));
+
+        // Versions without the queries answer with an error, or just "ok"
+        let temp_dir = tempfile::tempdir().unwrap();
+        let socket_path = temp_dir.path().join(".hyprsunset.sock");
+        fake_hyprsunset(&socket_path);
+        let client = HyprsunsetClient {
+            socket_path,
+            socket_source: "test",
+            debug_enabled: false,
+            last_applied: None,
+        };
+        assert_eq!(client.query_current_values(), None);
+    }
+
+    #[test]
+    fn test_apply_transition_state_skips_redundant_applies() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let socket_path = temp_dir.path().join(".hyprsunset.sock");
+        let received = fake_hyprsunset(&socket_path);
+
+        let mut client = HyprsunsetClient {
+            socket_path,
+            socket_source: "test",
+            debug_enabled: false,
+            last_applied: None,
+        };
+        let config: Config = toml::from_str(
+            "sunset = \"19:00:00\"\nsunrise = \"06:00:00\"\nnight_temp = 3300\nnight_gamma = 90.0",
+        )
+        .unwrap();
+        let running = AtomicBool::new(true);
+        let night = TransitionState::Stable(TimeState::Night);
+
+        client
+            .apply_transition_state(night, &config, &running)
+            .unwrap();
+        client
+            .apply_transition_state(night, &config, &running)
+            .unwrap();
+        assert_eq!(
+            received.lock().unwrap().as_slice(),
+            &["temperature 3300", "gamma 90"]
+        );
+
+        // A direct apply in between means the state has to be sent again
+        client
+            .apply_temperature_gamma(5000, 100.0, &running)
+            .unwrap();
+        client
+            .apply_transition_state(night, &config, &running)
+            .unwrap();
+        assert_eq!(received.lock().unwrap().len(), 6);
+
+        // So does a reconnection, since hyprsunset may have restarted
+        assert!(client.attempt_reconnection());
+        client
+            .apply_transition_state(night, &config, &running)
+            .unwrap();
+        assert_eq!(received.lock().unwrap().len(), 8);
+
+        // And a resume or unlock, which may have reset gamma behind our back
+        client.forget_applied();
+        client
+            .apply_transition_state(night, &config, &running)
+            .unwrap();
+        assert_eq!(received.lock().unwrap().len(), 10);
+    }
+}
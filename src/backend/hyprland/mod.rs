@@ -27,13 +27,14 @@
 //! ## Error Handling and Recovery
 //!
 //! The backend includes robust error handling:
-//! - Automatic reconnection attempts when the IPC connection is lost
+//! - Automatic reconnection when the IPC connection is lost, resending the failed state once
 //! - Process restart capability when hyprsunset crashes
 //! - Graceful degradation when hyprsunset becomes unavailable
 //! - Proper cleanup during application shutdown
 
 use anyhow::Result;
 use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant};
 
 use crate::backend::ColorTemperatureBackend;
 use crate::config::Config;
@@ -79,11 +80,47 @@ impl HyprlandBackend {
         // Verify hyprsunset installation and version compatibility
         verify_hyprsunset_installed_and_version()?;
 
+        // hyprsunset only accepts a temperature, so panel calibration can't be applied
+        if config.day_whitepoint.is_some() {
+            Log::log_pipe();
+            Log::log_warning("day_whitepoint is only supported by the Wayland backend - ignoring");
+        }
+
+        // hyprsunset only takes a single gamma value for all channels
+        if config.has_channel_gamma() {
+            Log::log_pipe();
+            Log::log_warning(
+                "night_gamma_r/g/b and day_gamma_r/g/b are only supported by the Wayland backend - ignoring",
+            );
+        }
+
+        // hyprsunset always applies to every output
+        if config.outputs_include.is_some() || config.outputs_exclude.is_some() {
+            Log::log_pipe();
+            Log::log_warning(
+                "outputs_include/outputs_exclude are only supported by the Wayland backend - ignoring",
+            );
+        }
+
+        // Without per-output control there's no way to treat HDR outputs differently
+        if config.hdr_outputs.is_some() {
+            Log::log_pipe();
+            Log::log_warning(
+                "hdr_outputs and the hdr_* values are only supported by the Wayland backend - ignoring",
+            );
+        }
+
+        // hyprsunset has a single schedule for every output
+        if config.output_groups.is_some() {
+            Log::log_pipe();
+            Log::log_warning("output_groups are only supported by the Wayland backend - ignoring");
+        }
+
         // Debug logging for reload investigation
         #[cfg(debug_assertions)]
         {
-            let start_hyprsunset = config.start_hyprsunset.unwrap_or(DEFAULT_START_HYPRSUNSET);
-            let hyprsunset_running = is_hyprsunset_running();
+            let start_hyprsunset = config.effective().start_hyprsunset;
+            let hyprsunset_running = is_hyprsunset_running(config.hyprsunset_socket.as_deref());
             eprintln!(
                 "DEBUG: HyprlandBackend::new() - start_hyprsunset={}, is_hyprsunset_running()={}",
                 start_hyprsunset, hyprsunset_running
@@ -91,8 +128,8 @@ impl HyprlandBackend {
         }
 
         // Start hyprsunset if needed
-        let process = if config.start_hyprsunset.unwrap_or(DEFAULT_START_HYPRSUNSET) {
-            if is_hyprsunset_running() {
+        let process = if config.effective().start_hyprsunset {
+            if is_hyprsunset_running(config.hyprsunset_socket.as_deref()) {
                 Log::log_pipe();
                 Log::log_warning(
                     "hyprsunset is already running but start_hyprsunset is enabled in config.",
@@ -120,10 +157,15 @@ impl HyprlandBackend {
         };
 
         // Initialize hyprsunset client
-        let mut client = HyprsunsetClient::new(debug_enabled)?;
+        let mut client = HyprsunsetClient::new(config.hyprsunset_socket.as_deref(), debug_enabled)?;
 
         // Verify connection to hyprsunset
-        verify_hyprsunset_connection(&mut client)?;
+        let effective = config.effective();
+        verify_hyprsunset_connection(
+            &mut client,
+            Duration::from_secs(effective.hyprsunset_connect_timeout),
+            effective.hyprsunset_connect_retries,
+        )?;
 
         Ok(Self { client, process })
     }
@@ -136,8 +178,7 @@ impl HyprlandBackend {
 
     /// Take ownership of the managed hyprsunset process, if any.
     ///
-    /// This is used during cleanup to properly terminate the process.
-    #[allow(dead_code)]
+    /// This is used by `--once` to detach the process so it keeps running.
     pub fn take_process(self) -> Option<HyprsunsetProcess> {
         self.process
     }
@@ -173,6 +214,7 @@ impl ColorTemperatureBackend for HyprlandBackend {
             if target_temp == hyprsunset_init_temp && target_gamma == hyprsunset_init_gamma {
                 // hyprsunset already has the correct values, just announce the mode
                 crate::time_state::log_state_announcement(state);
+                self.client.mark_applied(target_temp, target_gamma);
                 return Ok(());
             }
         }
@@ -181,6 +223,24 @@ impl ColorTemperatureBackend for HyprlandBackend {
         self.client.apply_startup_state(state, config, running)
     }
 
+    fn forget_applied_state(&mut self) {
+        self.client.forget_applied();
+    }
+
+    fn has_own_startup_transition(&self) -> bool {
+        // hyprsunset forces its own transition when it starts. An externally managed
+        // instance is already running, so connecting to it doesn't trigger one.
+        self.process.is_some()
+    }
+
+    fn current_values(&mut self) -> Option<(u32, f32)> {
+        // Only an externally managed hyprsunset shows values we didn't choose
+        if self.process.is_some() {
+            return None;
+        }
+        self.client.query_current_values()
+    }
+
     fn apply_temperature_gamma(
         &mut self,
         temperature: u32,
@@ -216,11 +276,19 @@ impl ColorTemperatureBackend for HyprlandBackend {
     }
 }
 
-/// Verify that hyprsunset is installed and check version compatibility.
-///
-/// This function is moved from main.rs and performs both installation verification
-/// and version checking in a single step for efficiency.
-pub fn verify_hyprsunset_installed_and_version() -> Result<()> {
+/// What running `hyprsunset --version` revealed about the installed hyprsunset.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HyprsunsetVersion {
+    /// The version reported by hyprsunset, normalized to `vX.Y.Z`
+    Found(String),
+    /// hyprsunset is installed but its version couldn't be determined
+    Unknown,
+    /// No hyprsunset binary on the PATH
+    NotInstalled,
+}
+
+/// Detect the installed hyprsunset version by running `hyprsunset --version`.
+pub fn detect_hyprsunset_version() -> HyprsunsetVersion {
     use crate::utils::extract_version_from_output;
 
     match std::process::Command::new("hyprsunset")
@@ -233,28 +301,9 @@ pub fn verify_hyprsunset_installed_and_version() -> Result<()> {
             } else {
                 String::from_utf8_lossy(&output.stderr)
             };
-
-            if let Some(version) = extract_version_from_output(&version_output) {
-                Log::log_decorated(&format!("Found hyprsunset {}", version));
-
-                if is_version_compatible(&version) {
-                    Ok(())
-                } else {
-                    Log::log_pipe();
-                    anyhow::bail!(
-                        "hyprsunset {} is not compatible with sunsetr.\n\
-                        Required minimum version: {}\n\
-                        Compatible versions: {}\n\
-                        Please update hyprsunset to a compatible version.",
-                        version,
-                        REQUIRED_HYPRSUNSET_VERSION,
-                        COMPATIBLE_HYPRSUNSET_VERSIONS.join(", ")
-                    )
-                }
-            } else {
-                Log::log_warning("Could not parse version from hyprsunset output");
-                Log::log_decorated("Attempting to proceed with compatibility test...");
-                Ok(())
+            match extract_version_from_output(&version_output) {
+                Some(version) => HyprsunsetVersion::Found(version),
+                None => HyprsunsetVersion::Unknown,
             }
         }
         Err(_) => {
@@ -262,22 +311,51 @@ pub fn verify_hyprsunset_installed_and_version() -> Result<()> {
                 .arg("hyprsunset")
                 .output()
             {
-                Ok(which_output) if which_output.status.success() => {
-                    Log::log_warning("hyprsunset found but version check failed");
-                    Log::log_decorated(
-                        "This might be an older version. Will attempt compatibility test...",
-                    );
-                    Ok(())
-                }
-                _ => {
-                    Log::log_pipe();
-                    anyhow::bail!("hyprsunset is not installed on the system");
-                }
+                Ok(which_output) if which_output.status.success() => HyprsunsetVersion::Unknown,
+                _ => HyprsunsetVersion::NotInstalled,
             }
         }
     }
 }
 
+/// Verify that hyprsunset is installed and check version compatibility.
+///
+/// This function is moved from main.rs and performs both installation verification
+/// and version checking in a single step for efficiency.
+pub fn verify_hyprsunset_installed_and_version() -> Result<()> {
+    match detect_hyprsunset_version() {
+        HyprsunsetVersion::Found(version) => {
+            Log::log_decorated(&format!("Found hyprsunset {}", version));
+
+            if is_version_compatible(&version) {
+                Ok(())
+            } else {
+                Log::log_pipe();
+                anyhow::bail!(
+                    "hyprsunset {} is not compatible with sunsetr.\n\
+                    Required minimum version: {}\n\
+                    Compatible versions: {}\n\
+                    Please update hyprsunset to a compatible version.",
+                    version,
+                    REQUIRED_HYPRSUNSET_VERSION,
+                    COMPATIBLE_HYPRSUNSET_VERSIONS.join(", ")
+                )
+            }
+        }
+        HyprsunsetVersion::Unknown => {
+            Log::log_warning("hyprsunset found but its version could not be determined");
+            Log::log_decorated(
+                "This might be an older version. Will attempt compatibility test...",
+            );
+            Ok(())
+        }
+        HyprsunsetVersion::NotInstalled => {
+            Log::log_pipe();
+            anyhow::bail!("hyprsunset is not installed on the system");
+        }
+    }
+}
+
 /// Check if a hyprsunset version is compatible with sunsetr.
 pub fn is_version_compatible(version: &str) -> bool {
     use crate::utils::compare_versions;
@@ -290,20 +368,47 @@ pub fn is_version_compatible(version: &str) -> bool {
 }
 
 /// Verify that we can establish a connection to the hyprsunset socket.
-pub fn verify_hyprsunset_connection(client: &mut HyprsunsetClient) -> Result<()> {
-    use std::{thread, time::Duration};
+///
+/// If the first attempt fails, waits up to `timeout` for the socket `retries` times,
+/// returning as soon as a connection succeeds. A zero timeout or retry count fails
+/// right away.
+pub fn verify_hyprsunset_connection(
+    client: &mut HyprsunsetClient,
+    timeout: Duration,
+    retries: u32,
+) -> Result<()> {
+    Log::log_decorated(&format!(
+        "Using hyprsunset socket: {} ({})",
+        client.socket_path.display(),
+        client.socket_source
+    ));
 
     if client.test_connection() {
         return Ok(());
     }
 
-    Log::log_decorated("Waiting 10 seconds for hyprsunset to become available...");
-    thread::sleep(Duration::from_secs(10));
+    if !timeout.is_zero() {
+        for attempt in 1..=retries {
+            if retries > 1 {
+                Log::log_decorated(&format!(
+                    "Waiting up to {} seconds for hyprsunset to become available ({}/{})...",
+                    timeout.as_secs(),
+                    attempt,
+                    retries
+                ));
+            } else {
+                Log::log_decorated(&format!(
+                    "Waiting up to {} seconds for hyprsunset to become available...",
+                    timeout.as_secs()
+                ));
+            }
 
-    // Use non-logging version for second attempt to avoid duplicate success messages
-    if client.test_connection_with_logging(false) {
-        Log::log_decorated("Successfully connected to hyprsunset after waiting.");
-        return Ok(());
+            // Use non-logging version while polling to avoid duplicate messages
+            if wait_for_connection(timeout, || client.test_connection_with_logging(false)) {
+                Log::log_decorated("Successfully connected to hyprsunset after waiting.");
+                return Ok(());
+            }
+        }
     }
 
     Log::log_critical("Cannot connect to hyprsunset socket.");
@@ -314,6 +419,7 @@ pub fn verify_hyprsunset_connection(client: &mut HyprsunsetClient) -> Result<()>
           • hyprsunset is not running\n\
           • hyprsunset service is not enabled\n\
           • You're not running on Hyprland\n\
+          • The socket path is wrong (set hyprsunset_socket or HYPRSUNSET_SOCKET)\n\
         \n\
         Please ensure hyprsunset is running and try again.\n\
         \n\
@@ -323,3 +429,44 @@ pub fn verify_hyprsunset_connection(client: &mut HyprsunsetClient) -> Result<()>
           3. Enable the service: systemctl --user enable hyprsunset.service"
     );
 }
+
+/// Poll `connect` every `HYPRSUNSET_CONNECT_POLL_MS` until it succeeds or `timeout` passes.
+fn wait_for_connection(timeout: Duration, mut connect: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return false;
+        }
+        std::thread::sleep(remaining.min(Duration::from_millis(HYPRSUNSET_CONNECT_POLL_MS)));
+        if connect() {
+            return true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wait_for_connection_returns_early() {
+        let mut attempts = 0;
+        let started = Instant::now();
+        assert!(wait_for_connection(Duration::from_secs(10), || {
+            attempts += 1;
+            attempts == 3
+        }));
+        assert_eq!(attempts, 3);
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_wait_for_connection_gives_up_at_timeout() {
+        let started = Instant::now();
+        assert!(!wait_for_connection(Duration::from_millis(250), || false));
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(250));
+        assert!(elapsed < Duration::from_secs(2));
+    }
+}
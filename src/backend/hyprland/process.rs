@@ -120,6 +120,19 @@ impl HyprsunsetProcess {
         Ok(Self { child })
     }
 
+    /// Release the process so it keeps running after sunsetr exits.
+    ///
+    /// Used by `--once` so hyprsunset keeps holding the applied values. The
+    /// process is no longer terminated on drop and is reparented once we exit.
+    ///
+    /// # Returns
+    /// The PID of the detached hyprsunset process
+    pub fn detach(self) -> u32 {
+        let pid = self.child.id();
+        std::mem::forget(self);
+        pid
+    }
+
     /// Stop the hyprsunset process gracefully.
     ///
     /// Attempts to terminate the process cleanly and reaps it to prevent
@@ -171,10 +184,10 @@ impl HyprsunsetProcess {
                 let nix_pid = Pid::from_raw(pid as i32);
 
                 // Send SIGTERM first for graceful shutdown
-                if let Err(e) = kill(nix_pid, Signal::SIGTERM) {
-                    if debug_enabled {
-                        Log::log_warning(&format!("Failed to send SIGTERM to hyprsunset: {}", e));
-                    }
+                if let Err(e) = kill(nix_pid, Signal::SIGTERM)
+                    && debug_enabled
+                {
+                    Log::log_warning(&format!("Failed to send SIGTERM to hyprsunset: {}", e));
                 }
 
                 // Give it a brief moment to exit gracefully
@@ -245,9 +258,9 @@ impl HyprsunsetProcess {
 /// # Returns
 /// - `true` if hyprsunset is running and responsive
 /// - `false` if hyprsunset is not running or not responsive
-pub fn is_hyprsunset_running() -> bool {
+pub fn is_hyprsunset_running(socket_override: Option<&str>) -> bool {
     // Initialize a client to determine the socket path
-    if let Ok(client) = HyprsunsetClient::new(false) {
+    if let Ok(client) = HyprsunsetClient::new(socket_override, false) {
         // Check both that the socket file exists AND that we can connect to it
         let socket_exists = client.socket_path.exists();
         let can_connect = if socket_exists {
@@ -0,0 +1,153 @@
+//! Mock backend for exercising sunsetr without a compositor.
+//!
+//! Only built with the `testing-support` feature. Instead of talking to a compositor,
+//! the mock records every temperature, gamma and transition state it is asked to apply,
+//! so tests can drive the transition logic deterministically and inspect the results.
+//!
+//! The backend is selected by setting `SUNSETR_BACKEND=mock`, which takes precedence over
+//! the configured backend and skips the Wayland session checks. Backends created that way
+//! record into a process-wide log available through [`shared_log`]; tests that want their
+//! own isolated record can construct one with [`MockBackend::with_log`] instead.
+//!
+//! When the mock runs in a separate sunsetr process, `SUNSETR_MOCK_LOG=<path>` makes it
+//! also append each apply to that file as `<temperature> <gamma> <kind>`, where kind is
+//! `stable`, `transitioning` or `direct`.
+
+use anyhow::Result;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::backend::ColorTemperatureBackend;
+use crate::config::Config;
+use crate::constants::MOCK_LOG_ENV;
+use crate::logger::Log;
+use crate::time_state::{TransitionState, get_initial_values_for_state};
+
+/// One apply recorded by the mock backend.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AppliedState {
+    pub temperature: u32,
+    pub gamma: f32,
+    /// The transition state behind the values (`None` for direct temperature/gamma applies)
+    pub state: Option<TransitionState>,
+}
+
+/// Shared record of applied states.
+pub type AppliedLog = Arc<Mutex<Vec<AppliedState>>>;
+
+/// Process-wide log used by mock backends created through [`create_backend`](crate::backend::create_backend).
+pub fn shared_log() -> AppliedLog {
+    static LOG: OnceLock<AppliedLog> = OnceLock::new();
+    LOG.get_or_init(|| Arc::new(Mutex::new(Vec::new()))).clone()
+}
+
+/// Backend that records applies instead of changing any display.
+pub struct MockBackend {
+    applied: AppliedLog,
+    /// File from `SUNSETR_MOCK_LOG` that applies are also appended to
+    log_file: Option<PathBuf>,
+    debug_enabled: bool,
+}
+
+impl MockBackend {
+    /// Create a mock backend that records into the process-wide [`shared_log`].
+    pub fn new(debug_enabled: bool) -> Self {
+        Self {
+            applied: shared_log(),
+            log_file: std::env::var_os(MOCK_LOG_ENV).map(PathBuf::from),
+            debug_enabled,
+        }
+    }
+
+    /// Create a mock backend that records into the given log.
+    #[allow(dead_code)] // Only used by tests through the library crate
+    pub fn with_log(applied: AppliedLog) -> Self {
+        Self {
+            applied,
+            log_file: None,
+            debug_enabled: false,
+        }
+    }
+
+    /// Get a handle to the log this backend records into.
+    #[allow(dead_code)] // Only used by tests through the library crate
+    pub fn applied(&self) -> AppliedLog {
+        self.applied.clone()
+    }
+
+    fn record(&self, temperature: u32, gamma: f32, state: Option<TransitionState>) {
+        if self.debug_enabled {
+            Log::log_pipe();
+            Log::log_debug(&format!(
+                "Mock backend applied {}K @ {:.1}% ({:?})",
+                temperature, gamma, state
+            ));
+        }
+
+        if let Some(path) = &self.log_file {
+            let kind = match state {
+                Some(TransitionState::Stable(_)) => "stable",
+                Some(TransitionState::Transitioning { .. }) => "transitioning",
+                None => "direct",
+            };
+            let line = format!("{} {:.2} {}\n", temperature, gamma, kind);
+            if let Err(e) = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|mut file| file.write_all(line.as_bytes()))
+            {
+                Log::log_warning(&format!("Mock backend failed to write its log: {}", e));
+            }
+        }
+
+        // A test that panicked while holding the lock shouldn't hide later applies
+        self.applied
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(AppliedState {
+                temperature,
+                gamma,
+                state,
+            });
+    }
+}
+
+impl ColorTemperatureBackend for MockBackend {
+    fn apply_transition_state(
+        &mut self,
+        state: TransitionState,
+        config: &Config,
+        _running: &AtomicBool,
+    ) -> Result<()> {
+        let (temperature, gamma) = get_initial_values_for_state(state, config);
+        self.record(temperature, gamma, Some(state));
+        Ok(())
+    }
+
+    fn apply_startup_state(
+        &mut self,
+        state: TransitionState,
+        config: &Config,
+        running: &AtomicBool,
+    ) -> Result<()> {
+        // No startup animation, so runs stay deterministic
+        self.apply_transition_state(state, config, running)
+    }
+
+    fn apply_temperature_gamma(
+        &mut self,
+        temperature: u32,
+        gamma: f32,
+        _running: &AtomicBool,
+    ) -> Result<()> {
+        self.record(temperature, gamma, None);
+        Ok(())
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "Mock"
+    }
+}
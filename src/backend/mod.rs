@@ -9,6 +9,8 @@
 //!
 //! - **Hyprland Backend**: Uses the hyprsunset daemon for color temperature control
 //! - **Wayland Backend**: Direct implementation of wlr-gamma-control-unstable-v1 protocol
+//! - **DRM Backend**: Sets gamma LUTs on the kernel's CRTCs when no compositor runs (`drm` feature only)
+//! - **Mock Backend**: Records applied values for tests (`testing-support` feature only)
 //!
 //! ## Backend Selection
 //!
@@ -16,7 +18,14 @@
 //! - **Auto-detection**: Examines environment variables to determine the appropriate backend
 //! - **Explicit Configuration**: Set `backend = "hyprland"` or `backend = "wayland"` in config
 //!
-//! Auto-detection priority: Hyprland → Wayland → error
+//! Auto-detection priority: Hyprland → Wayland → DRM (no `WAYLAND_DISPLAY` or `DISPLAY`) → error
+//!
+//! With the `testing-support` feature, `SUNSETR_BACKEND=mock` selects the mock backend
+//! regardless of configuration or environment.
+//!
+//! Detection also warns when `WAYLAND_DISPLAY` looks like a compositor nested inside a
+//! Hyprland session (e.g. a Weston window), where gamma changes would land on the
+//! nested compositor instead of the real displays.
 //!
 //! ## Architecture
 //!
@@ -26,6 +35,10 @@
 //! - State application with proper error handling
 //! - Startup behavior and transitions
 //! - Cleanup during application shutdown
+//!
+//! Backend errors are regular `anyhow` errors. Where a backend knows what kind of
+//! failure it hit, it attaches a [`BackendError`] to the chain so the main loop can
+//! decide between retrying and exiting without inspecting error messages.
 
 use anyhow::Result;
 use std::sync::atomic::AtomicBool;
@@ -34,7 +47,11 @@ use crate::Log;
 use crate::config::{Backend, Config};
 use crate::time_state::TransitionState;
 
+#[cfg(feature = "drm")]
+pub mod drm;
 pub mod hyprland;
+#[cfg(feature = "testing-support")]
+pub mod mock;
 pub mod wayland;
 
 /// Enum representing different Wayland compositors that sunsetr supports
@@ -57,6 +74,42 @@ impl std::fmt::Display for Compositor {
     }
 }
 
+/// Classes of backend failure, attached as context to errors returned by backends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackendError {
+    /// The backend couldn't apply right now; the next cycle retries
+    TemporarilyUnavailable,
+    /// The backend is gone and reconnecting failed; retrying is pointless
+    PermanentlyUnavailable,
+    /// The compositor refused the request (e.g. a `failed` gamma control)
+    ProtocolRejected,
+}
+
+impl BackendError {
+    /// Find the failure class attached anywhere in an error's chain.
+    ///
+    /// Errors without one are treated as temporary, matching the main loop's
+    /// long-standing behavior of retrying on the next cycle.
+    pub fn classify(error: &anyhow::Error) -> Self {
+        error
+            .downcast_ref::<BackendError>()
+            .copied()
+            .unwrap_or(BackendError::TemporarilyUnavailable)
+    }
+}
+
+impl std::fmt::Display for BackendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackendError::TemporarilyUnavailable => write!(f, "backend temporarily unavailable"),
+            BackendError::PermanentlyUnavailable => write!(f, "backend permanently unavailable"),
+            BackendError::ProtocolRejected => write!(f, "request rejected by the compositor"),
+        }
+    }
+}
+
+impl std::error::Error for BackendError {}
+
 /// Trait for color temperature backends that can control display temperature and gamma.
 ///
 /// This trait abstracts the differences between Hyprland (hyprsunset) and Wayland
@@ -75,7 +128,8 @@ pub trait ColorTemperatureBackend {
     ///
     /// # Returns
     /// - `Ok(())` if the state was applied successfully
-    /// - `Err` if there was an error applying the state
+    /// - `Err` if there was an error applying the state, tagged with a [`BackendError`]
+    ///   when the failure class is known
     fn apply_transition_state(
         &mut self,
         state: TransitionState,
@@ -123,6 +177,52 @@ pub trait ColorTemperatureBackend {
         running: &AtomicBool,
     ) -> Result<()>;
 
+    /// Bring one output back to the current state after it woke from DPMS.
+    ///
+    /// Called when the compositor may have reset the output's gamma. Backends that
+    /// can address individual outputs fade the correction in on just that output;
+    /// the default implementation reapplies the state everywhere.
+    ///
+    /// # Arguments
+    /// * `output` - Name of the output that woke up
+    /// * `state` - The transition state to fade in
+    /// * `config` - Configuration containing color values and the fade duration
+    /// * `running` - Atomic flag to check if the application should continue
+    fn fade_in_output(
+        &mut self,
+        output: &str,
+        state: TransitionState,
+        config: &Config,
+        running: &AtomicBool,
+    ) -> Result<()> {
+        let _ = output;
+        self.apply_transition_state(state, config, running)
+    }
+
+    /// Forget which values the backend believes are on screen.
+    ///
+    /// Called after session events such as resume or unlock, which may reset gamma
+    /// without sunsetr noticing, so the next apply is sent even if it matches the
+    /// last one. Backends that never skip redundant applies can ignore this.
+    fn forget_applied_state(&mut self) {}
+
+    /// Whether the backend already animates the startup state on its own.
+    ///
+    /// When `true`, sunsetr's own startup transition is skipped so the two don't
+    /// fight over the display. The default is `false`.
+    fn has_own_startup_transition(&self) -> bool {
+        false
+    }
+
+    /// The temperature and gamma currently on screen, if the backend can ask.
+    ///
+    /// Used at startup so the startup transition continues from whatever another
+    /// tool left on screen instead of jumping from the day values. The default
+    /// is `None`, which keeps that fallback.
+    fn current_values(&mut self) -> Option<(u32, f32)> {
+        None
+    }
+
     /// Get a human-readable name for this backend.
     ///
     /// # Returns
@@ -162,25 +262,25 @@ pub trait ColorTemperatureBackend {
 /// Returns an error if no suitable backend can be determined or if the
 /// environment is not supported (e.g., not running on Wayland).
 pub fn detect_backend(config: &Config) -> Result<BackendType> {
+    // Tests can run without a compositor by asking for the mock backend
+    #[cfg(feature = "testing-support")]
+    if std::env::var(crate::constants::BACKEND_OVERRIDE_ENV).as_deref() == Ok("mock") {
+        return Ok(BackendType::Mock);
+    }
+
+    let backend_type = detect_backend_type(config)?;
+    warn_if_nested_compositor(backend_type);
+    Ok(backend_type)
+}
+
+/// Pick the backend from the configuration and environment.
+fn detect_backend_type(config: &Config) -> Result<BackendType> {
     // Check explicit configuration first
     if let Some(backend) = &config.backend {
         match backend {
             Backend::Auto => {
                 // Auto-detect based on environment
-                if std::env::var("WAYLAND_DISPLAY").is_err() {
-                    Log::log_pipe();
-                    anyhow::bail!(
-                        "sunsetr requires a Wayland session. WAYLAND_DISPLAY is not set.\n\
-                        Please ensure you're running on a Wayland compositor."
-                    );
-                }
-
-                // Check if we're running on Hyprland
-                if std::env::var("HYPRLAND_INSTANCE_SIGNATURE").is_ok() {
-                    Ok(BackendType::Hyprland)
-                } else {
-                    Ok(BackendType::Wayland)
-                }
+                auto_detect_backend_type(config)
             }
             Backend::Wayland => {
                 // Verify we're actually on Wayland
@@ -217,23 +317,147 @@ pub fn detect_backend(config: &Config) -> Result<BackendType> {
 
                 Ok(BackendType::Hyprland)
             }
+            #[cfg(feature = "drm")]
+            Backend::Drm => {
+                if !drm::device_available() {
+                    Log::log_pipe();
+                    anyhow::bail!(
+                        "Configuration specifies backend=\"drm\" but no DRM device was found in /dev/dri."
+                    );
+                }
+                Ok(BackendType::Drm)
+            }
+            #[cfg(not(feature = "drm"))]
+            Backend::Drm => {
+                Log::log_pipe();
+                anyhow::bail!(
+                    "Configuration specifies backend=\"drm\" but this build has no DRM support.\n\
+                    Rebuild with `cargo build --release --features drm` to enable it."
+                );
+            }
         }
     } else {
         // Fallback to auto-detection when backend is not specified
-        if std::env::var("WAYLAND_DISPLAY").is_err() {
-            Log::log_pipe();
-            anyhow::bail!(
-                "sunsetr requires a Wayland session. WAYLAND_DISPLAY is not set.\n\
-                Please ensure you're running on a Wayland compositor."
-            );
-        }
+        auto_detect_backend_type(config)
+    }
+}
 
-        // Check if we're running on Hyprland
-        if std::env::var("HYPRLAND_INSTANCE_SIGNATURE").is_ok() {
-            Ok(BackendType::Hyprland)
-        } else {
-            Ok(BackendType::Wayland)
+/// Pick the backend from the environment, in the order given by `auto_prefer`.
+fn auto_detect_backend_type(config: &Config) -> Result<BackendType> {
+    if std::env::var("WAYLAND_DISPLAY").is_err() {
+        // Without any display server, the DRM device may be ours to drive
+        #[cfg(feature = "drm")]
+        if std::env::var("DISPLAY").is_err() && drm::device_available() {
+            return Ok(BackendType::Drm);
         }
+
+        Log::log_pipe();
+        anyhow::bail!(
+            "sunsetr requires a Wayland session. WAYLAND_DISPLAY is not set.\n\
+            Please ensure you're running on a Wayland compositor."
+        );
+    }
+
+    let on_hyprland = std::env::var("HYPRLAND_INSTANCE_SIGNATURE").is_ok();
+    Ok(preferred_session_backend(
+        &config.auto_prefer(),
+        on_hyprland,
+    ))
+}
+
+/// Pick the first backend in `prefer` that fits the Wayland session.
+///
+/// Hyprland only fits when running on Hyprland. The generic Wayland backend always
+/// fits and is used when nothing in the list does.
+fn preferred_session_backend(prefer: &[Backend], on_hyprland: bool) -> BackendType {
+    prefer
+        .iter()
+        .find_map(|backend| match backend {
+            Backend::Hyprland if on_hyprland => Some(BackendType::Hyprland),
+            Backend::Wayland => Some(BackendType::Wayland),
+            _ => None,
+        })
+        .unwrap_or(BackendType::Wayland)
+}
+
+/// A `WAYLAND_DISPLAY` that doesn't belong to the surrounding Hyprland session.
+#[derive(Debug, PartialEq, Eq)]
+struct NestedDisplay {
+    /// The socket sunsetr would connect to
+    display: String,
+    /// The Hyprland session's own socket, when it could be read from its lock file
+    session_display: Option<String>,
+}
+
+/// Decide whether `wayland_display` likely belongs to a nested compositor.
+///
+/// `hyprland_lock` is the content of the session's `hyprland.lock` (PID on the first
+/// line, Wayland socket on the second). Without it, only the default socket names
+/// `wayland-0` and `wayland-1` count as the session's own.
+fn nested_display(wayland_display: &str, hyprland_lock: Option<&str>) -> Option<NestedDisplay> {
+    // WAYLAND_DISPLAY may also be an absolute socket path
+    let display = wayland_display
+        .rsplit('/')
+        .next()
+        .unwrap_or(wayland_display);
+    let session_display = hyprland_lock
+        .and_then(|lock| lock.lines().nth(1))
+        .map(str::trim)
+        .filter(|socket| !socket.is_empty());
+
+    let nested = match session_display {
+        Some(session) => session != display,
+        None => !matches!(display, "wayland-0" | "wayland-1"),
+    };
+
+    nested.then(|| NestedDisplay {
+        display: wayland_display.to_string(),
+        session_display: session_display.map(str::to_string),
+    })
+}
+
+/// Warn when sunsetr seems to be running inside a compositor nested in Hyprland.
+fn warn_if_nested_compositor(backend_type: BackendType) {
+    let (Ok(wayland_display), Ok(signature)) = (
+        std::env::var("WAYLAND_DISPLAY"),
+        std::env::var("HYPRLAND_INSTANCE_SIGNATURE"),
+    ) else {
+        return;
+    };
+
+    let runtime_dir = std::env::var("XDG_RUNTIME_DIR")
+        .unwrap_or_else(|_| format!("/run/user/{}", nix::unistd::getuid()));
+    let lock_path = std::path::Path::new(&runtime_dir)
+        .join("hypr")
+        .join(&signature)
+        .join("hyprland.lock");
+    let lock = std::fs::read_to_string(lock_path).ok();
+
+    let Some(nested) = nested_display(&wayland_display, lock.as_deref()) else {
+        return;
+    };
+
+    Log::log_pipe();
+    Log::log_warning(&format!(
+        "WAYLAND_DISPLAY={} does not look like the Hyprland session's display",
+        nested.display
+    ));
+    match backend_type {
+        BackendType::Hyprland => Log::log_indented(
+            "hyprsunset will change the Hyprland session, not the nested compositor you're in",
+        ),
+        _ => Log::log_indented(
+            "sunsetr is probably running inside a nested compositor and will only change its outputs",
+        ),
+    }
+    match nested.session_display {
+        Some(session) => Log::log_indented(&format!(
+            "To target the real session, run sunsetr from it or with WAYLAND_DISPLAY={}",
+            session
+        )),
+        None => Log::log_indented(
+            "To target the real session, run sunsetr from a terminal or service of that session",
+        ),
     }
 }
 
@@ -274,10 +498,10 @@ pub fn detect_compositor() -> Compositor {
         .arg("-x")
         .arg("niri")
         .output()
+        && output.status.success()
+        && !output.stdout.is_empty()
     {
-        if output.status.success() && !output.stdout.is_empty() {
-            return Compositor::Niri;
-        }
+        return Compositor::Niri;
     }
 
     // Default to Other with the desktop name if available
@@ -315,6 +539,10 @@ pub fn create_backend(
             Box::new(wayland::WaylandBackend::new(config, debug_enabled)?)
                 as Box<dyn ColorTemperatureBackend>,
         ),
+        #[cfg(feature = "drm")]
+        BackendType::Drm => Ok(Box::new(drm::DrmBackend::new(config, debug_enabled)?)),
+        #[cfg(feature = "testing-support")]
+        BackendType::Mock => Ok(Box::new(mock::MockBackend::new(debug_enabled))),
     }
 }
 
@@ -325,6 +553,12 @@ pub enum BackendType {
     Hyprland,
     /// Generic Wayland compositor using wlr-gamma-control-unstable-v1 protocol
     Wayland,
+    /// Kernel DRM gamma LUTs, for TTY sessions without a compositor
+    #[cfg(feature = "drm")]
+    Drm,
+    /// Recording backend for tests, selected with `SUNSETR_BACKEND=mock`
+    #[cfg(feature = "testing-support")]
+    Mock,
 }
 
 impl BackendType {
@@ -333,6 +567,10 @@ impl BackendType {
         match self {
             BackendType::Hyprland => "Hyprland",
             BackendType::Wayland => "Wayland",
+            #[cfg(feature = "drm")]
+            BackendType::Drm => "DRM",
+            #[cfg(feature = "testing-support")]
+            BackendType::Mock => "Mock",
         }
     }
 
@@ -345,6 +583,10 @@ impl BackendType {
         match self {
             BackendType::Hyprland => (true, Backend::Hyprland), // Start hyprsunset, use hyprland backend
             BackendType::Wayland => (false, Backend::Wayland), // Don't start hyprsunset, use wayland backend
+            #[cfg(feature = "drm")]
+            BackendType::Drm => (false, Backend::Drm), // No compositor, nothing to start
+            #[cfg(feature = "testing-support")]
+            BackendType::Mock => (false, Backend::Auto), // Nothing to start, the override selects the mock
         }
     }
 
@@ -362,3 +604,86 @@ impl BackendType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preferred_session_backend() {
+        use crate::constants::DEFAULT_AUTO_PREFER;
+
+        // The default keeps Hyprland first on Hyprland and Wayland elsewhere
+        assert_eq!(
+            preferred_session_backend(DEFAULT_AUTO_PREFER, true),
+            BackendType::Hyprland
+        );
+        assert_eq!(
+            preferred_session_backend(DEFAULT_AUTO_PREFER, false),
+            BackendType::Wayland
+        );
+
+        let wayland_first = [Backend::Wayland, Backend::Hyprland];
+        assert_eq!(
+            preferred_session_backend(&wayland_first, true),
+            BackendType::Wayland
+        );
+
+        // Wayland remains the fallback when nothing listed fits
+        assert_eq!(
+            preferred_session_backend(&[Backend::Hyprland], false),
+            BackendType::Wayland
+        );
+    }
+
+    #[test]
+    fn test_backend_error_classify() {
+        let untagged = anyhow::anyhow!("socket timed out");
+        assert_eq!(
+            BackendError::classify(&untagged),
+            BackendError::TemporarilyUnavailable
+        );
+
+        // Found underneath later context, and the top-level message is unchanged
+        let gone = anyhow::anyhow!("connection refused")
+            .context(BackendError::PermanentlyUnavailable)
+            .context("Failed to send command");
+        assert_eq!(
+            BackendError::classify(&gone),
+            BackendError::PermanentlyUnavailable
+        );
+        assert_eq!(gone.to_string(), "Failed to send command");
+
+        let rejected =
+            anyhow::Error::new(BackendError::ProtocolRejected).context("Compositor rejected");
+        assert_eq!(
+            BackendError::classify(&rejected),
+            BackendError::ProtocolRejected
+        );
+    }
+
+    #[test]
+    fn test_nested_display_from_lock_file() {
+        let lock = "1234\nwayland-1\n";
+        assert_eq!(nested_display("wayland-1", Some(lock)), None);
+        assert_eq!(nested_display("/run/user/1000/wayland-1", Some(lock)), None);
+        assert_eq!(
+            nested_display("wayland-0", Some(lock)),
+            Some(NestedDisplay {
+                display: "wayland-0".to_string(),
+                session_display: Some("wayland-1".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_nested_display_without_lock_file() {
+        assert_eq!(nested_display("wayland-0", None), None);
+        assert_eq!(nested_display("wayland-1", None), None);
+        assert_eq!(nested_display("wayland-1", Some("1234")), None);
+
+        let nested = nested_display("wayland-2", None).unwrap();
+        assert_eq!(nested.display, "wayland-2");
+        assert_eq!(nested.session_display, None);
+    }
+}
@@ -169,15 +169,35 @@ fn planckian_locus(temp: i32) -> Result<(f64, f64), &'static str> {
 /// The algorithm smoothly transitions between planckian locus and illuminant D
 /// in the 2500K-4000K range to provide subjectively pleasant colors.
 pub fn calc_whitepoint(temp: u32) -> (f32, f32, f32) {
-    let temp = temp as i32;
-
     // D65 standard (6500K) is pure white
     if temp == 6500 {
         return (1.0, 1.0, 1.0);
     }
 
+    let (wp_x, wp_y) = whitepoint_chromaticity(temp);
+
+    // Convert chromaticity coordinates to XYZ
+    let wp_z = 1.0 - wp_x - wp_y;
+    let xyz = Xyz {
+        x: wp_x,
+        y: wp_y,
+        z: wp_z,
+    };
+
+    // Convert XYZ to sRGB and normalize
+    let mut rgb = xyz_to_srgb(&xyz);
+    srgb_normalize(&mut rgb);
+
+    // Return as f32 for compatibility with existing code
+    (rgb.r as f32, rgb.g as f32, rgb.b as f32)
+}
+
+/// CIE 1931 xy chromaticity of the white point `calc_whitepoint` targets for a temperature.
+pub fn whitepoint_chromaticity(temp: u32) -> (f64, f64) {
+    let temp = temp as i32;
+
     // Calculate chromaticity coordinates based on temperature range
-    let (wp_x, wp_y) = if temp >= 25000 {
+    if temp >= 25000 {
         // Very high temperatures: use illuminant D at 25000K
         illuminant_d(25000).unwrap_or((0.31, 0.33))
     } else if temp >= 4000 {
@@ -200,22 +220,89 @@ pub fn calc_whitepoint(temp: u32) -> (f32, f32, f32) {
         // Low temperatures: use planckian locus (minimum 1667K)
         let safe_temp = temp.max(1667);
         planckian_locus(safe_temp).unwrap_or((0.45, 0.41))
-    };
+    }
+}
 
-    // Convert chromaticity coordinates to XYZ
-    let wp_z = 1.0 - wp_x - wp_y;
-    let xyz = Xyz {
-        x: wp_x,
-        y: wp_y,
-        z: wp_z,
+/// Describe the white point a temperature produces, for calibration logs.
+///
+/// Gives the sRGB channel multipliers that are applied to the gamma ramps and the
+/// xy chromaticity they aim for, so a value like 3300K can be compared with
+/// other tools or a colorimeter reading.
+pub fn describe_whitepoint(temp: u32) -> String {
+    let (r, g, b) = calc_whitepoint(temp);
+    let (x, y) = whitepoint_chromaticity(temp);
+    format!(
+        "RGB multipliers {:.3} / {:.3} / {:.3}, xy ({:.4}, {:.4})",
+        r, g, b, x, y
+    )
+}
+
+/// Calculate per-channel correction factors for a panel's measured white point.
+///
+/// Given the CIE 1931 xy chromaticity of what the panel actually shows for full
+/// white, returns the RGB multipliers that pull it back to neutral (D65). The
+/// largest factor is always 1.0 so brightness is only ever reduced.
+///
+/// Returns `None` if the coordinates can't be represented as a usable correction.
+pub fn whitepoint_correction(x: f64, y: f64) -> Option<(f32, f32, f32)> {
+    if !(x > 0.0 && y > 0.0 && x + y < 1.0) {
+        return None;
+    }
+
+    let mut panel_white = xyz_to_srgb(&Xyz {
+        x,
+        y,
+        z: 1.0 - x - y,
+    });
+    srgb_normalize(&mut panel_white);
+
+    if panel_white.r <= 0.0 || panel_white.g <= 0.0 || panel_white.b <= 0.0 {
+        return None;
+    }
+
+    // Invert the panel's tint, then normalize so the strongest channel stays at 1.0
+    let mut correction = Rgb {
+        r: 1.0 / panel_white.r,
+        g: 1.0 / panel_white.g,
+        b: 1.0 / panel_white.b,
     };
+    let max_component = correction.r.max(correction.g.max(correction.b));
+    correction.r /= max_component;
+    correction.g /= max_component;
+    correction.b /= max_component;
 
-    // Convert XYZ to sRGB and normalize
-    let mut rgb = xyz_to_srgb(&xyz);
-    srgb_normalize(&mut rgb);
+    Some((
+        correction.r as f32,
+        correction.g as f32,
+        correction.b as f32,
+    ))
+}
 
-    // Return as f32 for compatibility with existing code
-    (rgb.r as f32, rgb.g as f32, rgb.b as f32)
+/// Find the color temperature in `min..=max` whose white point best matches an sRGB color.
+///
+/// Only the hue matters: the color is scaled so its strongest channel is 1.0, so
+/// `#ffb46b` and the darker `#805a36` give the same temperature. Returns the
+/// temperature (in steps of 10K) and the remaining distance between the two colors,
+/// which is close to 0.0 for colors on the white point curve and grows for tints
+/// no temperature can produce.
+pub fn nearest_temperature(rgb: (u8, u8, u8), min: u32, max: u32) -> (u32, f32) {
+    let strongest = rgb.0.max(rgb.1).max(rgb.2).max(1) as f32;
+    let target = (
+        rgb.0 as f32 / strongest,
+        rgb.1 as f32 / strongest,
+        rgb.2 as f32 / strongest,
+    );
+
+    (min..=max)
+        .step_by(10)
+        .map(|temp| {
+            let (r, g, b) = calc_whitepoint(temp);
+            let distance =
+                ((r - target.0).powi(2) + (g - target.1).powi(2) + (b - target.2).powi(2)).sqrt();
+            (temp, distance)
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or((max, f32::INFINITY))
 }
 
 // =============================================================================
@@ -265,6 +352,289 @@ pub fn generate_gamma_table(size: usize, color_factor: f64, gamma: f64) -> Vec<u
     table
 }
 
+/// Multiply every entry of a gamma table by `factor`, clipping at 65535.
+fn scale_gamma_table(table: &mut [u16], factor: f32) {
+    if factor == 1.0 {
+        return;
+    }
+    for value in table.iter_mut() {
+        *value = (*value as f64 * factor as f64).round().clamp(0.0, 65535.0) as u16;
+    }
+}
+
+/// Byte layout of the gamma tables handed to the compositor (`gamma_table_format`).
+///
+/// The wlr-gamma-control protocol expects the red, green and blue ramps one after
+/// another as 16-bit values, which is the default. The other layouts only exist to
+/// diagnose compositors that read the tables differently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GammaTableFormat {
+    /// Interleave the channels (`r0 g0 b0 r1 g1 b1 ...`) instead of whole ramps
+    pub interleaved: bool,
+    /// Write each 16-bit value big-endian instead of little-endian
+    pub big_endian: bool,
+}
+
+impl GammaTableFormat {
+    /// Parse a `gamma_table_format` value: `planar-le`, `planar-be`, `interleaved-le` or `interleaved-be`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let (layout, endianness) = name.split_once('-')?;
+        let interleaved = match layout {
+            "planar" => false,
+            "interleaved" => true,
+            _ => return None,
+        };
+        let big_endian = match endianness {
+            "le" => false,
+            "be" => true,
+            _ => return None,
+        };
+        Some(Self {
+            interleaved,
+            big_endian,
+        })
+    }
+
+    /// The format selected by the config, falling back to the protocol layout.
+    pub fn from_config(config: &crate::config::Config) -> Self {
+        config
+            .gamma_table_format
+            .as_deref()
+            .and_then(Self::from_name)
+            .unwrap_or_default()
+    }
+
+    /// Serialize the three channel ramps in this format.
+    fn encode(self, red: &[u16], green: &[u16], blue: &[u16]) -> Vec<u8> {
+        let mut data = Vec::with_capacity((red.len() + green.len() + blue.len()) * 2);
+        let mut push = |value: u16| {
+            data.extend_from_slice(&if self.big_endian {
+                value.to_be_bytes()
+            } else {
+                value.to_le_bytes()
+            })
+        };
+
+        if self.interleaved {
+            for ((&r, &g), &b) in red.iter().zip(green).zip(blue) {
+                push(r);
+                push(g);
+                push(b);
+            }
+        } else {
+            red.iter()
+                .chain(green)
+                .chain(blue)
+                .for_each(|&value| push(value));
+        }
+
+        data
+    }
+}
+
+/// The red, green and blue ramps of a gamma table, before they are serialized.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelRamps {
+    pub red: Vec<u16>,
+    pub green: Vec<u16>,
+    pub blue: Vec<u16>,
+}
+
+impl ChannelRamps {
+    /// Compute the ramps for a temperature and gamma from scratch.
+    ///
+    /// Arguments are as for [`create_gamma_tables`].
+    pub fn new(
+        size: usize,
+        temperature: u32,
+        gamma_percent: f32,
+        whitepoint_correction: (f32, f32, f32),
+        channel_gamma: (f32, f32, f32),
+    ) -> Self {
+        // Convert temperature to RGB factors, relative to the panel's neutral white
+        let (red_factor, green_factor, blue_factor) = temperature_to_rgb(temperature);
+        let red_factor = red_factor * whitepoint_correction.0;
+        let green_factor = green_factor * whitepoint_correction.1;
+        let blue_factor = blue_factor * whitepoint_correction.2;
+
+        // Generate individual channel tables using power function gamma curves
+        let mut red = generate_gamma_table(size, red_factor as f64, gamma_percent as f64);
+        let mut green = generate_gamma_table(size, green_factor as f64, gamma_percent as f64);
+        let mut blue = generate_gamma_table(size, blue_factor as f64, gamma_percent as f64);
+
+        // Per-channel gamma scales the finished curves, clipping at full scale
+        scale_gamma_table(&mut red, channel_gamma.0);
+        scale_gamma_table(&mut green, channel_gamma.1);
+        scale_gamma_table(&mut blue, channel_gamma.2);
+
+        Self { red, green, blue }
+    }
+
+    /// Blend entry by entry towards `other`: 0.0 gives `self`, 1.0 gives `other`.
+    ///
+    /// This is how `precompute_ramps` animates a transition from two ramps computed
+    /// once. The result differs slightly from a ramp computed at the interpolated
+    /// temperature, because the temperature curve isn't linear.
+    pub fn blend(&self, other: &Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0) as f64;
+        let lerp = |from: &[u16], to: &[u16]| -> Vec<u16> {
+            from.iter()
+                .zip(to)
+                .map(|(&a, &b)| (a as f64 + (b as f64 - a as f64) * t).round() as u16)
+                .collect()
+        };
+        Self {
+            red: lerp(&self.red, &other.red),
+            green: lerp(&self.green, &other.green),
+            blue: lerp(&self.blue, &other.blue),
+        }
+    }
+
+    /// Number of entries in each ramp.
+    pub fn size(&self) -> usize {
+        self.red.len()
+    }
+
+    /// Serialize the ramps in `format`.
+    pub fn encode(&self, format: GammaTableFormat) -> Vec<u8> {
+        format.encode(&self.red, &self.green, &self.blue)
+    }
+
+    /// Check for problems that can only come from a bug in building the ramps:
+    /// a wrong number of entries, or entries that decrease (NaN-derived values
+    /// end up as zeros in the middle of a ramp).
+    pub fn validate(&self, size: usize) -> Result<()> {
+        for (channel, ramp) in [
+            ("red", &self.red),
+            ("green", &self.green),
+            ("blue", &self.blue),
+        ] {
+            if ramp.len() != size {
+                anyhow::bail!(
+                    "{} ramp has {} entries, expected {}",
+                    channel,
+                    ramp.len(),
+                    size
+                );
+            }
+            if let Some(i) = ramp.windows(2).position(|pair| pair[1] < pair[0]) {
+                anyhow::bail!(
+                    "{} ramp decreases at entry {} ({} -> {})",
+                    channel,
+                    i + 1,
+                    ramp[i],
+                    ramp[i + 1]
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Serialize the ramps in `format` once [`validate`](Self::validate) passes and
+    /// the buffer has the expected length, so a malformed table is never sent.
+    pub fn encode_checked(&self, size: usize, format: GammaTableFormat) -> Result<Vec<u8>> {
+        let checked = self.validate(size).and_then(|()| {
+            let data = self.encode(format);
+            let expected = size * 3 * std::mem::size_of::<u16>();
+            if data.len() != expected {
+                anyhow::bail!(
+                    "encoded tables are {} bytes, expected {}",
+                    data.len(),
+                    expected
+                );
+            }
+            Ok(data)
+        });
+        checked.map_err(|e| {
+            crate::logger::Log::log_error(&format!(
+                "Refusing to send malformed gamma tables: {}",
+                e
+            ));
+            e.context("Built malformed gamma tables")
+        })
+    }
+}
+
+/// The day and night values that [`PrecomputedRamps`] computes its ramps from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RampEndpoints {
+    pub day_temp: u32,
+    /// Day gamma as a fraction (0.9 = 90%)
+    pub day_gamma: f32,
+    pub day_channel_gamma: (f32, f32, f32),
+    pub night_temp: u32,
+    /// Night gamma as a fraction (0.9 = 90%)
+    pub night_gamma: f32,
+    pub night_channel_gamma: (f32, f32, f32),
+    pub whitepoint_correction: (f32, f32, f32),
+}
+
+impl RampEndpoints {
+    /// The endpoints for the config's effective day and night values.
+    pub fn from_config(
+        config: &crate::config::Config,
+        whitepoint_correction: (f32, f32, f32),
+    ) -> Self {
+        let effective = config.effective();
+        Self {
+            day_temp: effective.day_temp,
+            day_gamma: effective.day_gamma / 100.0,
+            day_channel_gamma: effective.day_channel_gamma,
+            night_temp: effective.night_temp,
+            night_gamma: effective.night_gamma / 100.0,
+            night_channel_gamma: effective.night_channel_gamma,
+            whitepoint_correction,
+        }
+    }
+}
+
+/// Day and night ramps computed once per gamma size (`precompute_ramps`).
+///
+/// Transitions then only blend the two cached ramps, which is much cheaper than
+/// computing a ramp from scratch for large gamma sizes. The cache is dropped
+/// whenever the endpoints change, e.g. on a config reload.
+#[derive(Debug, Default)]
+pub struct PrecomputedRamps {
+    endpoints: Option<RampEndpoints>,
+    by_size: std::collections::HashMap<usize, (ChannelRamps, ChannelRamps)>,
+}
+
+impl PrecomputedRamps {
+    /// Use these endpoints from now on, dropping cached ramps if they changed.
+    pub fn set_endpoints(&mut self, endpoints: RampEndpoints) {
+        if self.endpoints != Some(endpoints) {
+            self.endpoints = Some(endpoints);
+            self.by_size.clear();
+        }
+    }
+
+    /// The ramps `night_weight` of the way from day (0.0) to night (1.0).
+    ///
+    /// Returns `None` until endpoints have been set.
+    pub fn blended(&mut self, size: usize, night_weight: f32) -> Option<ChannelRamps> {
+        let endpoints = self.endpoints?;
+        let (day, night) = self.by_size.entry(size).or_insert_with(|| {
+            (
+                ChannelRamps::new(
+                    size,
+                    endpoints.day_temp,
+                    endpoints.day_gamma,
+                    endpoints.whitepoint_correction,
+                    endpoints.day_channel_gamma,
+                ),
+                ChannelRamps::new(
+                    size,
+                    endpoints.night_temp,
+                    endpoints.night_gamma,
+                    endpoints.whitepoint_correction,
+                    endpoints.night_channel_gamma,
+                ),
+            )
+        });
+        Some(day.blend(night, night_weight))
+    }
+}
+
 /// Create complete gamma tables for RGB channels using wlsunset's approach.
 ///
 /// Generates the full set of gamma lookup tables needed for the
@@ -278,71 +648,77 @@ pub fn generate_gamma_table(size: usize, color_factor: f64, gamma: f64) -> Vec<u
 /// * `size` - Size of each gamma table (reported by compositor)
 /// * `temperature` - Color temperature in Kelvin
 /// * `gamma_percent` - Gamma adjustment as percentage (90% = 0.9, 100% = 1.0)
+/// * `whitepoint_correction` - Per-channel panel correction (see [`whitepoint_correction`])
+/// * `channel_gamma` - Per-channel multipliers applied after the temperature curve
+/// * `format` - Byte layout of the result (the protocol's layout unless overridden)
 /// * `debug_enabled` - Whether to output debug information
 ///
 /// # Returns
-/// Byte vector containing concatenated R, G, B gamma tables
+/// Byte vector containing the R, G, B gamma tables laid out as `format` says
 pub fn create_gamma_tables(
     size: usize,
     temperature: u32,
     gamma_percent: f32,
+    whitepoint_correction: (f32, f32, f32),
+    channel_gamma: (f32, f32, f32),
+    format: GammaTableFormat,
     debug_enabled: bool,
 ) -> Result<Vec<u8>> {
-    use crate::logger::Log;
+    // NaN or infinite inputs would silently turn into black or clipped entries
+    let (red, green, blue) = whitepoint_correction;
+    let (red_gamma, green_gamma, blue_gamma) = channel_gamma;
+    let inputs_valid = gamma_percent.is_finite()
+        && gamma_percent > 0.0
+        && [red, green, blue, red_gamma, green_gamma, blue_gamma]
+            .iter()
+            .all(|value| value.is_finite());
+    if !inputs_valid {
+        crate::logger::Log::log_error(&format!(
+            "Refusing to build gamma tables from invalid inputs: gamma={}, whitepoint={:?}, channel gamma={:?}",
+            gamma_percent, whitepoint_correction, channel_gamma
+        ));
+        anyhow::bail!("Invalid gamma table inputs");
+    }
 
-    // Convert temperature to RGB factors
-    let (red_factor, green_factor, blue_factor) = temperature_to_rgb(temperature);
+    let ramps = ChannelRamps::new(
+        size,
+        temperature,
+        gamma_percent,
+        whitepoint_correction,
+        channel_gamma,
+    );
 
     if debug_enabled {
-        Log::log_indented(&format!(
+        let (red_factor, green_factor, blue_factor) = temperature_to_rgb(temperature);
+        crate::logger::Log::log_indented(&format!(
             "temp={}K, gamma={}%, RGB factors=({:.3}, {:.3}, {:.3})",
             temperature,
             gamma_percent * 100.0,
-            red_factor,
-            green_factor,
-            blue_factor
+            red_factor * whitepoint_correction.0,
+            green_factor * whitepoint_correction.1,
+            blue_factor * whitepoint_correction.2
         ));
+        log_ramp_samples(&ramps);
     }
 
-    // Generate individual channel tables using power function gamma curves
-    let red_table = generate_gamma_table(size, red_factor as f64, gamma_percent as f64);
-    let green_table = generate_gamma_table(size, green_factor as f64, gamma_percent as f64);
-    let blue_table = generate_gamma_table(size, blue_factor as f64, gamma_percent as f64);
-
-    // Log some sample values for debugging
-    if debug_enabled {
-        let sample_indices = [0, 10, 128, 255];
-        let r_samples: Vec<u16> = sample_indices.iter().map(|&idx| red_table[idx]).collect();
-        let g_samples: Vec<u16> = sample_indices.iter().map(|&idx| green_table[idx]).collect();
-        let b_samples: Vec<u16> = sample_indices.iter().map(|&idx| blue_table[idx]).collect();
-
-        Log::log_decorated("Sample gamma values:");
-        Log::log_indented(&format!("R: {:?}", r_samples));
-        Log::log_indented(&format!("G: {:?}", g_samples));
-        Log::log_indented(&format!("B: {:?}", b_samples));
-    }
-
-    // Convert to bytes (little-endian 16-bit values)
-    // Using the documented wlr-gamma-control protocol order: RED, GREEN, BLUE
+    // By default this is the documented wlr-gamma-control protocol order: RED, GREEN, BLUE
     // This matches wlsunset's layout: r = table, g = table + ramp_size, b = table + 2*ramp_size
-    let mut gamma_data = Vec::with_capacity(size * 3 * 2);
-
-    // Red channel
-    for value in red_table {
-        gamma_data.extend_from_slice(&value.to_le_bytes());
-    }
+    ramps.encode_checked(size, format)
+}
 
-    // Green channel
-    for value in green_table {
-        gamma_data.extend_from_slice(&value.to_le_bytes());
-    }
+/// Log a few entries of each ramp for debugging.
+pub fn log_ramp_samples(ramps: &ChannelRamps) {
+    use crate::logger::Log;
 
-    // Blue channel
-    for value in blue_table {
-        gamma_data.extend_from_slice(&value.to_le_bytes());
-    }
+    // DRM gamma sizes can be smaller than the usual 256
+    let sample_indices = [0, 10, 128, 255].map(|idx: usize| idx.min(ramps.size() - 1));
+    let samples =
+        |table: &[u16]| -> Vec<u16> { sample_indices.iter().map(|&idx| table[idx]).collect() };
 
-    Ok(gamma_data)
+    Log::log_decorated("Sample gamma values:");
+    Log::log_indented(&format!("R: {:?}", samples(&ramps.red)));
+    Log::log_indented(&format!("G: {:?}", samples(&ramps.green)));
+    Log::log_indented(&format!("B: {:?}", samples(&ramps.blue)));
 }
 
 /// Create a linear gamma table for testing protocol communication.
@@ -436,8 +812,242 @@ mod tests {
 
     #[test]
     fn test_create_gamma_tables() {
-        let tables = create_gamma_tables(256, 6500, 1.0, false).unwrap();
+        let tables = create_gamma_tables(
+            256,
+            6500,
+            1.0,
+            (1.0, 1.0, 1.0),
+            (1.0, 1.0, 1.0),
+            GammaTableFormat::default(),
+            false,
+        )
+        .unwrap();
         // Should contain 3 channels * 256 entries * 2 bytes each
         assert_eq!(tables.len(), 256 * 3 * 2);
     }
+
+    #[test]
+    fn test_malformed_gamma_tables_are_rejected() {
+        let ramps = ChannelRamps::new(256, 3300, 0.9, (1.0, 1.0, 1.0), (1.0, 1.0, 0.0));
+        assert!(ramps.validate(256).is_ok());
+        assert!(
+            ramps
+                .encode_checked(256, GammaTableFormat::default())
+                .is_ok()
+        );
+
+        // A ramp of the wrong length
+        assert!(ramps.validate(512).is_err());
+        let mut short = ramps.clone();
+        short.green.pop();
+        assert!(short.validate(256).is_err());
+
+        // A NaN-derived zero in the middle of a ramp
+        let mut dented = ramps.clone();
+        dented.red[100] = 0;
+        let err = dented
+            .encode_checked(256, GammaTableFormat::default())
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("red ramp decreases at entry 100"));
+
+        // Invalid inputs never produce a table
+        let nan_gamma = create_gamma_tables(
+            256,
+            6500,
+            f32::NAN,
+            (1.0, 1.0, 1.0),
+            (1.0, 1.0, 1.0),
+            GammaTableFormat::default(),
+            false,
+        );
+        assert!(nan_gamma.is_err());
+    }
+
+    #[test]
+    fn test_gamma_table_format() {
+        assert_eq!(
+            GammaTableFormat::from_name("planar-le"),
+            Some(GammaTableFormat::default())
+        );
+        assert_eq!(
+            GammaTableFormat::from_name("interleaved-be"),
+            Some(GammaTableFormat {
+                interleaved: true,
+                big_endian: true,
+            })
+        );
+        assert_eq!(GammaTableFormat::from_name("planar"), None);
+        assert_eq!(GammaTableFormat::from_name("bgr-le"), None);
+
+        let planar = GammaTableFormat::default().encode(&[1, 2], &[3, 4], &[5, 0x0102]);
+        assert_eq!(planar, [1, 0, 2, 0, 3, 0, 4, 0, 5, 0, 2, 1]);
+        let interleaved = GammaTableFormat::from_name("interleaved-be")
+            .unwrap()
+            .encode(&[1, 2], &[3, 4], &[5, 0x0102]);
+        assert_eq!(interleaved, [0, 1, 0, 3, 0, 5, 0, 2, 0, 4, 1, 2]);
+    }
+
+    #[test]
+    fn test_create_gamma_tables_channel_gamma() {
+        let entry = |tables: &[u8], channel: usize, i: usize| {
+            let offset = (channel * 256 + i) * 2;
+            u16::from_le_bytes([tables[offset], tables[offset + 1]])
+        };
+        let neutral = create_gamma_tables(
+            256,
+            4000,
+            0.9,
+            (1.0, 1.0, 1.0),
+            (1.0, 1.0, 1.0),
+            GammaTableFormat::default(),
+            false,
+        )
+        .unwrap();
+        let scaled = create_gamma_tables(
+            256,
+            4000,
+            0.9,
+            (1.0, 1.0, 1.0),
+            (1.0, 2.0, 0.5),
+            GammaTableFormat::default(),
+            false,
+        )
+        .unwrap();
+
+        for i in [64, 128, 255] {
+            // Red is untouched, blue is halved after the temperature curve
+            assert_eq!(entry(&scaled, 0, i), entry(&neutral, 0, i));
+            let expected_blue = (entry(&neutral, 2, i) as f64 * 0.5).round() as u16;
+            assert_eq!(entry(&scaled, 2, i), expected_blue);
+        }
+        // Doubling clips at full scale instead of wrapping
+        assert_eq!(entry(&scaled, 1, 255), 65535);
+        assert!(entry(&scaled, 1, 64) > entry(&neutral, 1, 64));
+    }
+
+    #[test]
+    fn test_nearest_temperature() {
+        // White is 6500K exactly
+        assert_eq!(
+            nearest_temperature((255, 255, 255), 1000, 20000),
+            (6500, 0.0)
+        );
+
+        // A white point round-trips to (almost) the same temperature, whatever its brightness
+        let (r, g, b) = calc_whitepoint(3000);
+        let rgb = ((r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8);
+        let (temp, distance) = nearest_temperature(rgb, 1000, 20000);
+        assert!((2900..=3100).contains(&temp), "got {}K", temp);
+        assert!(distance < 0.02);
+        let half = (rgb.0 / 2, rgb.1 / 2, rgb.2 / 2);
+        assert!(nearest_temperature(half, 1000, 20000).0.abs_diff(temp) <= 100);
+
+        // Green is nowhere near any white point
+        assert!(nearest_temperature((0, 255, 0), 1000, 20000).1 > 0.5);
+    }
+
+    #[test]
+    fn test_describe_whitepoint() {
+        assert_eq!(
+            describe_whitepoint(6500),
+            "RGB multipliers 1.000 / 1.000 / 1.000, xy (0.3128, 0.3292)"
+        );
+
+        // Warm white: red stays full, blue is cut and x moves toward red
+        let (r, g, b) = calc_whitepoint(3300);
+        assert!(r > g && g > b);
+        let (x, _) = whitepoint_chromaticity(3300);
+        assert!(x > 0.4);
+        assert!(describe_whitepoint(3300).starts_with("RGB multipliers 1.000 / "));
+    }
+
+    #[test]
+    fn test_whitepoint_correction() {
+        // D65 needs no correction
+        let (r, g, b) = whitepoint_correction(0.31271, 0.32902).unwrap();
+        assert!((r - 1.0).abs() < 0.01);
+        assert!((g - 1.0).abs() < 0.01);
+        assert!((b - 1.0).abs() < 0.01);
+
+        // A bluish panel gets its blue channel pulled down
+        let (r, _, b) = whitepoint_correction(0.295, 0.305).unwrap();
+        assert!((r - 1.0).abs() < 0.01);
+        assert!(b < 0.95);
+
+        // Outside the chromaticity diagram
+        assert!(whitepoint_correction(0.7, 0.5).is_none());
+        assert!(whitepoint_correction(0.0, 0.3).is_none());
+    }
+
+    #[test]
+    fn test_blended_ramps_stay_close_to_exact_ramps() {
+        let size = 4096;
+        let endpoints = RampEndpoints {
+            day_temp: 6500,
+            day_gamma: 1.0,
+            day_channel_gamma: (1.0, 1.0, 1.0),
+            night_temp: 3300,
+            night_gamma: 0.9,
+            night_channel_gamma: (1.0, 1.0, 1.0),
+            whitepoint_correction: (1.0, 1.0, 1.0),
+        };
+        let mut precomputed = PrecomputedRamps::default();
+        assert!(precomputed.blended(size, 0.5).is_none());
+        precomputed.set_endpoints(endpoints);
+
+        for weight in [0.0, 0.1, 0.25, 0.5, 0.75, 0.9, 1.0] {
+            let blended = precomputed.blended(size, weight).unwrap();
+            let temperature = (6500.0 + (3300.0 - 6500.0) * weight).round() as u32;
+            let gamma = 1.0 + (0.9 - 1.0) * weight;
+            let exact =
+                ChannelRamps::new(size, temperature, gamma, (1.0, 1.0, 1.0), (1.0, 1.0, 1.0));
+
+            let max_diff = [
+                (&blended.red, &exact.red),
+                (&blended.green, &exact.green),
+                (&blended.blue, &exact.blue),
+            ]
+            .iter()
+            .flat_map(|(a, b)| a.iter().zip(b.iter()))
+            .map(|(&a, &b)| (a as i32 - b as i32).abs())
+            .max()
+            .unwrap();
+            if weight == 0.0 || weight == 1.0 {
+                assert!(max_diff <= 1, "endpoint {} differs by {}", weight, max_diff);
+            } else {
+                // Within 5% of full scale; the largest gap is mid-transition
+                assert!(
+                    max_diff < 65535 / 20,
+                    "weight {} differs by {}",
+                    weight,
+                    max_diff
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_precomputed_ramps_follow_endpoint_changes() {
+        let mut endpoints = RampEndpoints {
+            day_temp: 6500,
+            day_gamma: 1.0,
+            day_channel_gamma: (1.0, 1.0, 1.0),
+            night_temp: 3300,
+            night_gamma: 0.9,
+            night_channel_gamma: (1.0, 1.0, 1.0),
+            whitepoint_correction: (1.0, 1.0, 1.0),
+        };
+        let mut precomputed = PrecomputedRamps::default();
+        precomputed.set_endpoints(endpoints);
+        let before = precomputed.blended(256, 1.0).unwrap();
+
+        endpoints.night_temp = 2500;
+        precomputed.set_endpoints(endpoints);
+        let after = precomputed.blended(256, 1.0).unwrap();
+        assert_ne!(before, after);
+        assert_eq!(
+            after,
+            ChannelRamps::new(256, 2500, 0.9, (1.0, 1.0, 1.0), (1.0, 1.0, 1.0))
+        );
+    }
 }
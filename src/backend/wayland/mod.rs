@@ -21,9 +21,21 @@
 //! ## Output Management
 //!
 //! The backend automatically discovers and manages all connected Wayland outputs:
-//! - Enumerates all available displays during initialization
+//! - Enumerates all available displays during initialization, and keeps running
+//!   without any (e.g. on a headless boot) until one connects, unless
+//!   `require_outputs` is set
 //! - Applies gamma adjustments to all outputs simultaneously
-//! - Handles dynamic output addition/removal events
+//! - Handles dynamic output addition/removal events: outputs are tracked by their
+//!   `wl_output` proxy and registry global rather than their position, so virtual
+//!   outputs that come and go (e.g. while screencasting) never shift gamma onto the
+//!   wrong monitor, and outputs added later get their own gamma control
+//! - Applies the separate `hdr_*` color values to outputs tagged via `hdr_outputs`
+//!   (wlr-gamma-control exposes no color-management information, so HDR outputs
+//!   can't be detected and must be tagged in the config)
+//! - Applies each `[output_groups.<name>]` schedule to the outputs in that group,
+//!   computing the group's own transition state on every apply
+//! - Passes gamma tables via memfd, falling back to a temporary file when unavailable
+//! - Fades gamma back in on outputs waking from DPMS (see [`power`])
 //!
 //! ## Error Handling
 //!
@@ -31,26 +43,50 @@
 //! - Protocol negotiation failures
 //! - Compositor compatibility detection
 //! - Graceful fallback when gamma control is unavailable
+//! - Startup timeouts: every roundtrip made while initializing is bounded by
+//!   `WAYLAND_INIT_TIMEOUT_MS`, so a compositor that stops answering produces an
+//!   error instead of hanging sunsetr before it has logged anything useful
+//! - Apply verification: each apply is followed by a roundtrip, and outputs track
+//!   when gamma was last sent and last confirmed (no `failed` event before the
+//!   roundtrip completed). A warning is logged when an apply is never acknowledged.
+//! - Ineffective gamma detection: when several applies in a row reach none of the
+//!   targeted outputs (no usable gamma control or size), an error is logged even
+//!   without `--debug`, repeated at most every few minutes while it persists.
 
 use anyhow::Result;
+use nix::poll::{PollFd, PollFlags, PollTimeout, poll};
+use nix::sys::memfd::{MFdFlags, memfd_create};
+use std::fs::File;
 use std::os::fd::AsFd;
-use std::sync::atomic::AtomicBool;
+use std::os::unix::fs::FileExt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::time::{Duration, Instant};
 
 use wayland_client::{
     Connection, Dispatch, EventQueue, Proxy, QueueHandle,
-    protocol::{wl_output::WlOutput, wl_registry::WlRegistry},
+    protocol::{
+        wl_callback::WlCallback, wl_display::WlDisplay, wl_output::WlOutput,
+        wl_registry::WlRegistry,
+    },
 };
 use wayland_protocols_wlr::gamma_control::v1::client::{
     zwlr_gamma_control_manager_v1::ZwlrGammaControlManagerV1,
     zwlr_gamma_control_v1::{Event as GammaControlEvent, ZwlrGammaControlV1},
 };
 
-use crate::backend::ColorTemperatureBackend;
+use crate::backend::{BackendError, ColorTemperatureBackend};
 use crate::config::Config;
+use crate::constants::{
+    BEZIER_P1X, BEZIER_P1Y, BEZIER_P2X, BEZIER_P2Y, GAMMA_SIZE_POLL_MS, GAMMA_SIZE_TIMEOUT_MS,
+    INEFFECTIVE_GAMMA_THRESHOLD, INEFFECTIVE_GAMMA_WARNING_INTERVAL_SECS, WAKE_FADE_STEP_MS,
+    WAYLAND_INIT_TIMEOUT_MS,
+};
 use crate::logger::Log;
-use crate::time_state::TransitionState;
+use crate::time_state::{TimeState, TransitionState};
 
 pub mod gamma;
+pub mod power;
 
 /// Wayland backend implementation using wlr-gamma-control-unstable-v1 protocol.
 ///
@@ -58,19 +94,146 @@ pub mod gamma;
 /// that support the wlr-gamma-control-unstable-v1 protocol (most wlroots-based
 /// compositors like Sway, river, Wayfire, etc.).
 pub struct WaylandBackend {
+    // Kept open for the backend's lifetime
     connection: Connection,
     event_queue: EventQueue<AppData>,
     app_data: AppData,
     debug_enabled: bool,
+    /// Per-channel correction derived from `day_whitepoint`
+    whitepoint_correction: (f32, f32, f32),
+    /// Per-channel gamma multipliers for the last applied state (`*_gamma_r/g/b`)
+    channel_gamma: (f32, f32, f32),
+    /// Byte layout of the gamma tables (`gamma_table_format`)
+    gamma_table_format: gamma::GammaTableFormat,
+    /// Whether transitions blend precomputed day/night ramps (`precompute_ramps`)
+    precompute_ramps: bool,
+    /// Day and night ramps per gamma size, used when `precompute_ramps` is set
+    precomputed: gamma::PrecomputedRamps,
+    /// Output name globs from `outputs_include` (empty means all outputs)
+    outputs_include: Vec<String>,
+    /// Output name globs from `outputs_exclude`
+    outputs_exclude: Vec<String>,
+    /// Output name globs from `hdr_outputs`
+    hdr_outputs: Vec<String>,
+    /// Name and output name globs of each `[output_groups.<name>]` table
+    output_groups: Vec<(String, Vec<String>)>,
+    /// Whether to confirm each apply with a roundtrip (`verify_gamma`)
+    verify_gamma: bool,
+    /// Set once the "gamma may not be taking effect" warning has been shown,
+    /// so it isn't repeated on every update until an apply is confirmed again
+    unconfirmed_warning_shown: bool,
+    /// Consecutive applies that reached none of the targeted outputs
+    ineffective_applies: IneffectiveApplies,
+    // Declared last so it is released only after the connection above is closed
+    _gamma_control: GammaControlGuard,
+}
+
+/// Whether a `WaylandBackend` in this process currently holds gamma controls.
+struct GammaControlSlot {
+    held: Mutex<bool>,
+    released: Condvar,
+}
+
+static GAMMA_CONTROL_SLOT: GammaControlSlot = GammaControlSlot {
+    held: Mutex::new(false),
+    released: Condvar::new(),
+};
+
+/// Held by the one `WaylandBackend` in the process that may bind gamma controls.
+///
+/// Two backends binding gamma controls at the same time race in the compositor:
+/// one is refused or briefly overrides the other, which shows up as a flash (the
+/// temporary backend that clears leftover Wayland gamma under Hyprland is the usual
+/// case). Each backend holds the slot for its whole lifetime, so a new one waits
+/// until the previous one has released its controls and disconnected.
+struct GammaControlGuard;
+
+impl GammaControlGuard {
+    /// Take the slot, waiting up to `timeout` for another backend to release it.
+    fn acquire(timeout: Duration) -> Result<Self> {
+        let held = GAMMA_CONTROL_SLOT
+            .held
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let (mut held, _) = GAMMA_CONTROL_SLOT
+            .released
+            .wait_timeout_while(held, timeout, |held| *held)
+            .unwrap_or_else(PoisonError::into_inner);
+        if *held {
+            anyhow::bail!(
+                "Another Wayland backend in this process still holds gamma control after {}s",
+                timeout.as_secs()
+            );
+        }
+        *held = true;
+        Ok(Self)
+    }
+}
+
+impl Drop for GammaControlGuard {
+    fn drop(&mut self) {
+        *GAMMA_CONTROL_SLOT
+            .held
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = false;
+        GAMMA_CONTROL_SLOT.released.notify_all();
+    }
+}
+
+/// Tracks applies that had outputs to target but set gamma on none of them.
+#[derive(Debug, Default)]
+struct IneffectiveApplies {
+    /// Consecutive ineffective applies so far
+    streak: u32,
+    /// When the ineffective gamma error was last shown
+    last_warned: Option<Instant>,
+}
+
+impl IneffectiveApplies {
+    /// Record an apply that targeted `targeted` outputs and reached `applied` of them.
+    ///
+    /// Returns `true` when the error should be shown now: the streak has reached
+    /// `INEFFECTIVE_GAMMA_THRESHOLD` and the error wasn't shown within the last
+    /// `INEFFECTIVE_GAMMA_WARNING_INTERVAL_SECS`. Applies with nothing to target (no
+    /// outputs connected, or all filtered out) neither extend nor break the streak.
+    fn record(&mut self, targeted: usize, applied: usize, now: Instant) -> bool {
+        if targeted == 0 {
+            return false;
+        }
+        if applied > 0 {
+            self.streak = 0;
+            self.last_warned = None;
+            return false;
+        }
+
+        self.streak += 1;
+        let interval = Duration::from_secs(INEFFECTIVE_GAMMA_WARNING_INTERVAL_SECS);
+        let due = self
+            .last_warned
+            .is_none_or(|at| now.saturating_duration_since(at) >= interval);
+        if self.streak >= INEFFECTIVE_GAMMA_THRESHOLD && due {
+            self.last_warned = Some(now);
+            return true;
+        }
+        false
+    }
 }
 
 /// Information about a Wayland output and its gamma control
 #[derive(Debug, Clone)]
 struct OutputInfo {
+    /// Registry name of the `wl_output` global, used to handle removal
+    global: u32,
     output: WlOutput,
     gamma_control: Option<ZwlrGammaControlV1>,
     gamma_size: Option<usize>,
     name: String,
+    /// Set when the compositor sends a `failed` event for this output's gamma control
+    failed: bool,
+    /// When gamma tables were last sent to this output
+    last_sent: Option<Instant>,
+    /// When the compositor last processed an apply for this output without rejecting it
+    last_confirmed: Option<Instant>,
 }
 
 /// Application data for Wayland event handling
@@ -78,6 +241,10 @@ struct OutputInfo {
 struct AppData {
     gamma_manager: Option<ZwlrGammaControlManagerV1>,
     outputs: Vec<OutputInfo>,
+    /// Names of outputs removed since they were last reported
+    removed: Vec<String>,
+    /// Outputs whose gamma size changed since they were last reported: (name, old, new)
+    resized: Vec<(String, usize, usize)>,
 }
 
 impl AppData {
@@ -85,7 +252,29 @@ impl AppData {
         Self {
             gamma_manager: None,
             outputs: Vec::new(),
+            removed: Vec::new(),
+            resized: Vec::new(),
+        }
+    }
+
+    /// Request a gamma control for every output that doesn't have one yet.
+    ///
+    /// Returns the registry globals of the outputs that got one.
+    fn watch_outputs(&mut self, qh: &QueueHandle<Self>) -> Vec<u32> {
+        let Some(ref manager) = self.gamma_manager else {
+            return Vec::new();
+        };
+        let mut requested = Vec::new();
+        for output_info in self
+            .outputs
+            .iter_mut()
+            .filter(|o| o.gamma_control.is_none())
+        {
+            output_info.gamma_control =
+                Some(manager.get_gamma_control(&output_info.output, qh, ()));
+            requested.push(output_info.global);
         }
+        requested
     }
 }
 
@@ -108,7 +297,9 @@ impl WaylandBackend {
     /// - Compositor doesn't support wlr-gamma-control-unstable-v1
     /// - Failed to connect to Wayland display server
     /// - Permission denied for gamma control
-    pub fn new(_config: &Config, debug_enabled: bool) -> Result<Self> {
+    /// - No outputs are connected and `require_outputs` is enabled
+    /// - The compositor stops answering for `WAYLAND_INIT_TIMEOUT_MS` during setup
+    pub fn new(config: &Config, debug_enabled: bool) -> Result<Self> {
         // Verify we're running on Wayland
         if std::env::var("WAYLAND_DISPLAY").is_err() {
             Log::log_pipe();
@@ -117,6 +308,10 @@ impl WaylandBackend {
 
         Log::log_decorated("Initializing Wayland gamma control backend...");
 
+        // Wait for any other backend in this process to hand its gamma controls back
+        let gamma_control =
+            GammaControlGuard::acquire(Duration::from_millis(WAYLAND_INIT_TIMEOUT_MS))?;
+
         // Connect to Wayland display
         let connection = Connection::connect_to_env()
             .map_err(|e| anyhow::anyhow!("Failed to connect to Wayland display: {}", e))?;
@@ -133,14 +328,19 @@ impl WaylandBackend {
         // Get the registry to enumerate globals
         let _registry = display.get_registry(&qh, ());
 
+        let require_outputs = config.effective().require_outputs;
+
         // Dispatch events until we have all the protocols we need
         // This may take multiple dispatch rounds
         for _ in 0..10 {
-            // Maximum 10 rounds to avoid infinite loops
-            event_queue.blocking_dispatch(&mut app_data)?;
+            // Maximum 10 rounds to avoid infinite loops. Each roundtrip is bounded too:
+            // a compositor that never answers would otherwise hang startup silently
+            roundtrip_with_timeout(&display, &mut event_queue, &mut app_data, "Wayland globals")?;
 
             // Check if we have what we need
-            if app_data.gamma_manager.is_some() && !app_data.outputs.is_empty() {
+            if app_data.gamma_manager.is_some()
+                && (!app_data.outputs.is_empty() || !require_outputs)
+            {
                 break;
             }
         }
@@ -169,23 +369,30 @@ impl WaylandBackend {
         }
 
         // Enumerate outputs and create gamma controls
-        Self::setup_gamma_controls(&mut app_data, &qh)?;
+        app_data.watch_outputs(&qh);
 
         // Dispatch events to process potential gamma_size events from the compositor
         // This ensures that the gamma_size is populated before we proceed.
-        event_queue.roundtrip(&mut app_data).map_err(|e| {
-            Log::log_pipe();
-            anyhow::anyhow!(
-                "Failed during roundtrip after setting up gamma controls: {}",
-                e
-            )
-        })?;
+        roundtrip_with_timeout(&display, &mut event_queue, &mut app_data, "gamma controls")
+            .map_err(|e| {
+                Log::log_pipe();
+                e.context("Failed during roundtrip after setting up gamma controls")
+            })?;
 
         if app_data.outputs.is_empty() {
-            Log::log_pipe();
-            anyhow::bail!("No outputs found for gamma control");
+            if require_outputs {
+                Log::log_pipe();
+                anyhow::bail!(
+                    "No outputs found for gamma control\n\
+                    Set require_outputs = false to wait for one to connect instead."
+                );
+            }
+            // Outputs connected later are picked up through hotplug handling
+            Log::log_block_start("No outputs connected, waiting for one to appear");
         }
 
+        Self::wait_for_gamma_sizes(&display, &mut event_queue, &mut app_data, debug_enabled)?;
+
         if debug_enabled {
             Log::log_debug(&format!(
                 "Initialized gamma control for {} output(s)",
@@ -193,32 +400,388 @@ impl WaylandBackend {
             ));
         }
 
-        Ok(Self {
+        let mut backend = Self {
             connection,
             event_queue,
             app_data,
             debug_enabled,
-        })
+            whitepoint_correction: whitepoint_correction_for(config),
+            channel_gamma: crate::time_state::get_channel_gamma_for_state(
+                crate::time_state::get_transition_state(config),
+                config,
+            ),
+            gamma_table_format: gamma::GammaTableFormat::from_config(config),
+            precompute_ramps: false,
+            precomputed: gamma::PrecomputedRamps::default(),
+            outputs_include: config.outputs_include.clone().unwrap_or_default(),
+            outputs_exclude: config.outputs_exclude.clone().unwrap_or_default(),
+            hdr_outputs: config.hdr_outputs.clone().unwrap_or_default(),
+            output_groups: output_group_globs(config),
+            verify_gamma: config.effective().verify_gamma,
+            unconfirmed_warning_shown: false,
+            ineffective_applies: IneffectiveApplies::default(),
+            _gamma_control: gamma_control,
+        };
+        backend.update_from_config(config);
+        backend.log_output_targets();
+
+        Ok(backend)
+    }
+
+    /// Whether gamma should be applied to the output with the given name.
+    fn is_output_targeted(&self, name: &str) -> bool {
+        output_matches_filters(name, &self.outputs_include, &self.outputs_exclude)
+    }
+
+    /// Whether the output with the given name is tagged as HDR.
+    fn is_hdr_output(&self, name: &str) -> bool {
+        output_matches_hdr(name, &self.hdr_outputs)
     }
 
-    /// Set up gamma controls for all available outputs
-    fn setup_gamma_controls(app_data: &mut AppData, qh: &QueueHandle<AppData>) -> Result<()> {
-        if let Some(ref manager) = app_data.gamma_manager {
-            for output_info in &mut app_data.outputs {
-                let gamma_control = manager.get_gamma_control(&output_info.output, qh, ());
-                output_info.gamma_control = Some(gamma_control);
+    /// Pick up whitepoint, table format, ramp precomputation, output filter, output group and verification changes from config reloads.
+    fn update_from_config(&mut self, config: &Config) {
+        self.whitepoint_correction = whitepoint_correction_for(config);
+        self.gamma_table_format = gamma::GammaTableFormat::from_config(config);
+        self.precompute_ramps = config.effective().precompute_ramps;
+        if self.precompute_ramps {
+            self.precomputed
+                .set_endpoints(gamma::RampEndpoints::from_config(
+                    config,
+                    self.whitepoint_correction,
+                ));
+        }
+        self.outputs_include = config.outputs_include.clone().unwrap_or_default();
+        self.outputs_exclude = config.outputs_exclude.clone().unwrap_or_default();
+        self.hdr_outputs = config.hdr_outputs.clone().unwrap_or_default();
+        self.output_groups = output_group_globs(config);
+        self.verify_gamma = config.effective().verify_gamma;
+    }
+
+    /// Log which outputs the include/exclude filters and HDR tags resolve to.
+    fn log_output_targets(&self) {
+        if !self.hdr_outputs.is_empty() {
+            let hdr: Vec<&str> = self
+                .app_data
+                .outputs
+                .iter()
+                .map(|o| o.name.as_str())
+                .filter(|name| self.is_hdr_output(name))
+                .collect();
+            Log::log_block_start(&format!("HDR outputs: {}", self.hdr_outputs.join(", ")));
+            if hdr.is_empty() {
+                Log::log_warning("hdr_outputs matches none of the connected outputs");
+            } else {
+                Log::log_indented(&format!("Using HDR values for: {}", hdr.join(", ")));
+            }
+        }
+
+        for (name, globs) in &self.output_groups {
+            let members: Vec<&str> = self
+                .app_data
+                .outputs
+                .iter()
+                .map(|o| o.name.as_str())
+                .filter(|output| globs.iter().any(|p| crate::utils::glob_match(p, output)))
+                .collect();
+            Log::log_block_start(&format!("Output group '{}': {}", name, globs.join(", ")));
+            if members.is_empty() {
+                Log::log_warning(&format!(
+                    "Output group '{}' matches none of the connected outputs",
+                    name
+                ));
+            } else {
+                Log::log_indented(&format!(
+                    "Using its own schedule for: {}",
+                    members.join(", ")
+                ));
             }
         }
+
+        if self.outputs_include.is_empty() && self.outputs_exclude.is_empty() {
+            return;
+        }
+
+        let targeted: Vec<&str> = self
+            .app_data
+            .outputs
+            .iter()
+            .map(|o| o.name.as_str())
+            .filter(|name| self.is_output_targeted(name))
+            .collect();
+
+        Log::log_block_start("Output targeting:");
+        if !self.outputs_include.is_empty() {
+            Log::log_indented(&format!("Include: {}", self.outputs_include.join(", ")));
+        }
+        if !self.outputs_exclude.is_empty() {
+            Log::log_indented(&format!("Exclude: {}", self.outputs_exclude.join(", ")));
+        }
+
+        if targeted.is_empty() {
+            let available: Vec<&str> = self
+                .app_data
+                .outputs
+                .iter()
+                .map(|o| o.name.as_str())
+                .collect();
+            Log::log_warning(&format!(
+                "Output filters match none of the connected outputs ({})",
+                available.join(", ")
+            ));
+        } else {
+            Log::log_indented(&format!("Applying to: {}", targeted.join(", ")));
+        }
+    }
+
+    /// Pick up outputs added, removed or resized since the last apply.
+    ///
+    /// Reads whatever the compositor has sent without blocking, then requests gamma
+    /// controls for new outputs and waits one roundtrip for their names and gamma sizes.
+    fn sync_outputs(&mut self) -> Result<()> {
+        // Nothing to read (WouldBlock) is the common case and not an error
+        if let Some(guard) = self.event_queue.prepare_read() {
+            let _ = guard.read();
+        }
+        self.event_queue
+            .dispatch_pending(&mut self.app_data)
+            .map_err(|e| {
+                // Errors on a Wayland connection are fatal, it never recovers
+                anyhow::Error::new(BackendError::PermanentlyUnavailable)
+                    .context(format!("Failed to process output events: {}", e))
+            })?;
+
+        for name in self.app_data.removed.drain(..) {
+            if self.debug_enabled {
+                Log::log_pipe();
+                Log::log_debug(&format!("Output '{}' was removed", name));
+            }
+        }
+
+        // Tables are built from the current size on every apply, so logging is all that's left
+        for (name, old, new) in self.app_data.resized.drain(..) {
+            if self.debug_enabled {
+                Log::log_pipe();
+                Log::log_debug(&format!(
+                    "Output '{}' gamma size changed from {} to {}",
+                    name, old, new
+                ));
+            }
+        }
+
+        let qh = self.event_queue.handle();
+        let added = self.app_data.watch_outputs(&qh);
+        if added.is_empty() {
+            return Ok(());
+        }
+        self.event_queue
+            .roundtrip(&mut self.app_data)
+            .map_err(|e| anyhow::anyhow!("Failed to set up gamma for new outputs: {}", e))?;
+
+        if self.debug_enabled {
+            let new_outputs: Vec<&str> = self
+                .app_data
+                .outputs
+                .iter()
+                .filter(|o| added.contains(&o.global))
+                .map(|o| o.name.as_str())
+                .collect();
+            Log::log_pipe();
+            Log::log_debug(&format!("New output(s): {}", new_outputs.join(", ")));
+        }
         Ok(())
     }
 
+    /// Give slow compositors time to report the gamma size of every output.
+    ///
+    /// Outputs whose size still hasn't arrived after `GAMMA_SIZE_TIMEOUT_MS` are skipped
+    /// (and picked up automatically if the size arrives later), as long as at least one
+    /// other output is usable. Outputs whose gamma control failed are left to the
+    /// regular retry logic.
+    fn wait_for_gamma_sizes(
+        display: &WlDisplay,
+        event_queue: &mut EventQueue<AppData>,
+        app_data: &mut AppData,
+        debug_enabled: bool,
+    ) -> Result<()> {
+        let pending = |app_data: &AppData| -> Vec<String> {
+            app_data
+                .outputs
+                .iter()
+                .filter(|o| o.gamma_control.is_some() && o.gamma_size.is_none() && !o.failed)
+                .map(|o| o.name.clone())
+                .collect()
+        };
+
+        let deadline = Instant::now() + Duration::from_millis(GAMMA_SIZE_TIMEOUT_MS);
+        let mut waiting = pending(app_data);
+        if !waiting.is_empty() && debug_enabled {
+            Log::log_pipe();
+            Log::log_debug(&format!(
+                "Waiting for gamma size from: {}",
+                waiting.join(", ")
+            ));
+        }
+
+        while !waiting.is_empty() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(GAMMA_SIZE_POLL_MS));
+            roundtrip_with_timeout(display, event_queue, app_data, "gamma sizes")
+                .map_err(|e| e.context("Failed while waiting for gamma sizes"))?;
+            waiting = pending(app_data);
+        }
+
+        if waiting.is_empty() {
+            return Ok(());
+        }
+
+        // Failed outputs are retried later, so only give up when nothing else is left
+        let usable = app_data
+            .outputs
+            .iter()
+            .any(|o| o.gamma_size.is_some() || o.failed);
+        if !usable {
+            Log::log_pipe();
+            anyhow::bail!(
+                "No output reported a gamma size within {} ms ({})",
+                GAMMA_SIZE_TIMEOUT_MS,
+                waiting.join(", ")
+            );
+        }
+
+        Log::log_pipe();
+        Log::log_warning(&format!(
+            "Skipping output(s) that never reported a gamma size: {}",
+            waiting.join(", ")
+        ));
+        Log::log_indented(&format!(
+            "The compositor didn't answer within {} ms; they'll be used if it does later",
+            GAMMA_SIZE_TIMEOUT_MS
+        ));
+        Log::log_indented("Please report this with your compositor and version");
+        Ok(())
+    }
+
+    /// Replace gamma controls the compositor marked as failed with newly requested ones.
+    ///
+    /// This commonly happens right after login when the compositor isn't fully ready yet.
+    fn recreate_failed_gamma_controls(&mut self) -> Result<()> {
+        let qh = self.event_queue.handle();
+        if let Some(ref manager) = self.app_data.gamma_manager {
+            for output_info in self.app_data.outputs.iter_mut().filter(|o| o.failed) {
+                if let Some(old_control) = output_info.gamma_control.take() {
+                    old_control.destroy();
+                }
+                output_info.gamma_control =
+                    Some(manager.get_gamma_control(&output_info.output, &qh, ()));
+                output_info.gamma_size = None;
+                output_info.failed = false;
+            }
+        }
+
+        // Pick up gamma_size (or another failure) for the new controls
+        self.event_queue
+            .roundtrip(&mut self.app_data)
+            .map_err(|e| anyhow::anyhow!("Failed to recreate gamma controls: {}", e))?;
+        Ok(())
+    }
+
+    /// Mark outputs sent in the apply at `sent_at` as confirmed, or warn when the
+    /// compositor never acknowledged the apply.
+    ///
+    /// Outputs that received a `failed` event are handled separately by the caller.
+    fn record_apply_confirmation(&mut self, sent_at: Instant, roundtrip_ok: bool) {
+        let sent_outputs = self
+            .app_data
+            .outputs
+            .iter_mut()
+            .filter(|o| o.last_sent == Some(sent_at) && !o.failed);
+
+        if roundtrip_ok {
+            for output_info in sent_outputs {
+                output_info.last_confirmed = Some(sent_at);
+            }
+            self.unconfirmed_warning_shown = false;
+            return;
+        }
+
+        let unconfirmed: Vec<(String, Option<Instant>)> = sent_outputs
+            .map(|o| (o.name.clone(), o.last_confirmed))
+            .collect();
+        if unconfirmed.is_empty() || (self.unconfirmed_warning_shown && !self.debug_enabled) {
+            return;
+        }
+
+        Log::log_pipe();
+        Log::log_warning(&format!(
+            "Compositor did not acknowledge the gamma update for {} output(s) - gamma may not be taking effect",
+            unconfirmed.len()
+        ));
+        for (name, last_confirmed) in &unconfirmed {
+            Log::log_indented(&format!(
+                "'{}': last confirmed apply {}",
+                name,
+                describe_last_confirmed(*last_confirmed, sent_at)
+            ));
+        }
+        if !self.unconfirmed_warning_shown {
+            Log::log_indented(
+                "If the screen isn't changing, your compositor may be ignoring gamma tables",
+            );
+            Log::log_indented("Set verify_gamma = false to skip this check");
+        }
+        self.unconfirmed_warning_shown = true;
+    }
+
+    /// Explain that gamma updates keep reaching none of the targeted outputs.
+    fn log_ineffective_gamma(&self) {
+        let skipped: Vec<String> = self
+            .app_data
+            .outputs
+            .iter()
+            .filter(|o| self.is_output_targeted(&o.name))
+            .map(|o| {
+                let reason = match (&o.gamma_control, o.gamma_size) {
+                    (None, _) => "no gamma control",
+                    (Some(_), None) => "no gamma size reported",
+                    _ => "not applied",
+                };
+                format!("'{}' ({})", o.name, reason)
+            })
+            .collect();
+
+        Log::log_pipe();
+        Log::log_error(&format!(
+            "The last {} gamma updates reached none of your outputs",
+            self.ineffective_applies.streak
+        ));
+        Log::log_indented(&format!("Skipped: {}", skipped.join(", ")));
+        Log::log_indented(
+            "Your compositor may advertise gamma control without actually supporting it",
+        );
+        Log::log_indented(
+            "Run with --debug for details, and please report it with your compositor and version",
+        );
+    }
+
     /// Apply gamma tables to all outputs
-    fn apply_gamma_to_outputs(&mut self, temperature: u32, gamma: f32) -> Result<()> {
+    ///
+    /// `rules` replace `temperature`/`gamma` on the outputs they match (output groups
+    /// and outputs tagged via `hdr_outputs`). When empty, every output receives the same
+    /// values. `only_output` restricts the apply to the output with that name.
+    fn apply_gamma_to_outputs(
+        &mut self,
+        temperature: u32,
+        gamma: f32,
+        rules: &[OutputRule],
+        only_output: Option<&str>,
+        night_weight: Option<f32>,
+    ) -> Result<()> {
         if self.debug_enabled {
             Log::log_pipe();
             Log::log_debug("Starting apply_gamma_to_outputs");
         }
 
+        self.sync_outputs()?;
+
         // Use app_data.outputs which has the latest gamma control information
         if self.debug_enabled {
             Log::log_debug(&format!(
@@ -227,11 +790,51 @@ impl WaylandBackend {
             ));
         }
 
-        // Keep temp files alive until after event dispatch
-        let mut temp_files = Vec::new();
+        // Keep gamma files alive until after event dispatch
+        let mut gamma_files = Vec::new();
         let mut successful_count = 0;
+        let mut targeted_count = 0;
+        let sent_at = Instant::now();
 
         for (i, output_info) in self.app_data.outputs.iter_mut().enumerate() {
+            if only_output.is_some_and(|name| name != output_info.name) {
+                continue;
+            }
+
+            if !output_matches_filters(
+                &output_info.name,
+                &self.outputs_include,
+                &self.outputs_exclude,
+            ) {
+                if self.debug_enabled {
+                    Log::log_debug(&format!(
+                        "Skipping output '{}' - excluded by output filters",
+                        output_info.name
+                    ));
+                }
+                continue;
+            }
+
+            targeted_count += 1;
+
+            // A failed control object is inert per the protocol; see the end of this function
+            if output_info.failed {
+                if self.debug_enabled {
+                    Log::log_debug(&format!(
+                        "Skipping output '{}' - the compositor rejected its gamma control",
+                        output_info.name
+                    ));
+                }
+                continue;
+            }
+
+            let (temperature, gamma) = values_for_output(
+                &output_info.name,
+                output_matches_hdr(&output_info.name, &self.hdr_outputs),
+                rules,
+                (temperature, gamma),
+            );
+
             if let (Some(gamma_control), Some(gamma_size)) =
                 (&output_info.gamma_control, output_info.gamma_size)
             {
@@ -255,8 +858,29 @@ impl WaylandBackend {
                 if self.debug_enabled {
                     Log::log_decorated("Creating gamma tables...");
                 }
-                let gamma_data =
-                    gamma::create_gamma_tables(gamma_size, temperature, gamma, self.debug_enabled)?;
+                let blended =
+                    night_weight.and_then(|weight| self.precomputed.blended(gamma_size, weight));
+                let gamma_data = match blended {
+                    Some(ramps) => {
+                        if self.debug_enabled {
+                            Log::log_indented(&format!(
+                                "Blended precomputed ramps, {:.1}% towards night",
+                                night_weight.unwrap_or_default() * 100.0
+                            ));
+                            gamma::log_ramp_samples(&ramps);
+                        }
+                        ramps.encode_checked(gamma_size, self.gamma_table_format)?
+                    }
+                    None => gamma::create_gamma_tables(
+                        gamma_size,
+                        temperature,
+                        gamma,
+                        self.whitepoint_correction,
+                        self.channel_gamma,
+                        self.gamma_table_format,
+                        self.debug_enabled,
+                    )?,
+                };
                 if self.debug_enabled {
                     Log::log_debug(&format!(
                         "Created gamma tables, size: {} bytes",
@@ -264,37 +888,18 @@ impl WaylandBackend {
                     ));
                 }
 
-                // Create temporary file for gamma data
-                if self.debug_enabled {
-                    Log::log_decorated("Creating temporary file");
-                }
-                let mut temp_file = tempfile::tempfile()
-                    .map_err(|e| anyhow::anyhow!("Failed to create temporary file: {}", e))?;
-
-                // Write gamma data to file
-                if self.debug_enabled {
-                    Log::log_decorated("Writing gamma data to file");
-                }
-                std::io::Write::write_all(&mut temp_file, &gamma_data)
-                    .map_err(|e| anyhow::anyhow!("Failed to write gamma data: {}", e))?;
-
-                // Flush to ensure data is written
-                std::io::Write::flush(&mut temp_file)
-                    .map_err(|e| anyhow::anyhow!("Failed to flush gamma data: {}", e))?;
-
-                // CRITICAL: Reset file position to beginning before sending to compositor
-                // This was the bug - compositor reads from current position, which was at EOF
-                std::io::Seek::seek(&mut temp_file, std::io::SeekFrom::Start(0))
-                    .map_err(|e| anyhow::anyhow!("Failed to reset file position: {}", e))?;
+                // Create an anonymous file holding the gamma data
+                let gamma_file = create_gamma_file(&gamma_data, self.debug_enabled)?;
 
                 // Set gamma table
                 if self.debug_enabled {
                     Log::log_decorated("Setting gamma table via Wayland protocol");
                 }
-                gamma_control.set_gamma(temp_file.as_fd());
+                gamma_control.set_gamma(gamma_file.as_fd());
+                output_info.last_sent = Some(sent_at);
 
-                // Keep the temp file alive until after event dispatch
-                temp_files.push(temp_file);
+                // Keep the gamma file alive until after event dispatch
+                gamma_files.push(gamma_file);
                 successful_count += 1;
 
                 if self.debug_enabled {
@@ -315,6 +920,19 @@ impl WaylandBackend {
             }
         }
 
+        if self
+            .ineffective_applies
+            .record(targeted_count, successful_count, sent_at)
+        {
+            self.log_ineffective_gamma();
+        }
+
+        // Send the gamma tables right away. Nothing else flushes the connection without
+        // the verifying roundtrip, so they would sit in the buffer until the next one.
+        self.event_queue
+            .flush()
+            .map_err(|e| anyhow::anyhow!("Failed to send gamma tables to the compositor: {}", e))?;
+
         // Use dispatch_pending instead of blocking_dispatch to avoid hanging
         // This processes any pending events without blocking
         match self.event_queue.dispatch_pending(&mut self.app_data) {
@@ -332,21 +950,69 @@ impl WaylandBackend {
             }
         }
 
-        // Try a roundtrip to ensure the compositor processes the gamma tables
-        if self.debug_enabled {
-            Log::log_debug("Performing roundtrip to ensure compositor processes gamma tables");
-        }
-        match self.connection.roundtrip() {
-            Ok(_) => {
-                if self.debug_enabled {
-                    Log::log_debug("Roundtrip successful");
-                }
+        // Roundtrip so the compositor has processed the gamma tables and any `failed`
+        // events are dispatched to app_data. The protocol has no explicit acknowledgement,
+        // so a completed roundtrip without `failed` is the best confirmation we can get.
+        if self.verify_gamma {
+            if self.debug_enabled {
+                Log::log_debug("Performing roundtrip to ensure compositor processes gamma tables");
             }
-            Err(e) => {
-                if self.debug_enabled {
-                    Log::log_pipe();
-                    Log::log_warning(&format!("Roundtrip failed: {}", e));
+            let roundtrip_ok = match self.event_queue.roundtrip(&mut self.app_data) {
+                Ok(_) => {
+                    if self.debug_enabled {
+                        Log::log_debug("Roundtrip successful");
+                    }
+                    true
                 }
+                Err(e) => {
+                    if self.debug_enabled {
+                        Log::log_pipe();
+                        Log::log_warning(&format!("Roundtrip failed: {}", e));
+                    }
+                    false
+                }
+            };
+            self.record_apply_confirmation(sent_at, roundtrip_ok);
+        }
+
+        // Now gamma files can be dropped
+        drop(gamma_files);
+
+        let failed_count = self.app_data.outputs.iter().filter(|o| o.failed).count();
+        if failed_count > 0 {
+            let accepted = self
+                .app_data
+                .outputs
+                .iter()
+                .any(|o| o.last_sent == Some(sent_at) && !o.failed);
+
+            // When no output took the tables, request fresh gamma controls so a retry
+            // has a chance of succeeding (a failed control object is inert per the protocol)
+            if !accepted {
+                self.recreate_failed_gamma_controls()?;
+                return Err(
+                    anyhow::Error::new(BackendError::ProtocolRejected).context(format!(
+                        "Compositor rejected gamma control for {} output(s)",
+                        failed_count
+                    )),
+                );
+            }
+
+            // The other outputs are set, so the apply stands. Retrying every cycle wouldn't
+            // help an output whose gamma another client holds, so it's only reported once.
+            let newly_rejected: Vec<&str> = self
+                .app_data
+                .outputs
+                .iter()
+                .filter(|o| o.failed && o.last_sent == Some(sent_at))
+                .map(|o| o.name.as_str())
+                .collect();
+            if !newly_rejected.is_empty() {
+                Log::log_pipe();
+                Log::log_warning(&format!(
+                    "Compositor rejected gamma control for {}; other outputs were updated",
+                    newly_rejected.join(", ")
+                ));
             }
         }
 
@@ -363,8 +1029,6 @@ impl WaylandBackend {
             Log::log_warning("No outputs were available for gamma control");
         }
 
-        // Now temp files can be dropped
-        drop(temp_files);
         if self.debug_enabled {
             Log::log_debug("apply_gamma_to_outputs completed");
         }
@@ -372,6 +1036,191 @@ impl WaylandBackend {
     }
 }
 
+/// Check an output name against include/exclude glob lists.
+///
+/// An empty include list targets every output; excludes always win.
+fn output_matches_filters(name: &str, include: &[String], exclude: &[String]) -> bool {
+    let included = include.is_empty() || include.iter().any(|p| crate::utils::glob_match(p, name));
+    included && !exclude.iter().any(|p| crate::utils::glob_match(p, name))
+}
+
+/// Check whether an output name matches one of the `hdr_outputs` globs.
+fn output_matches_hdr(name: &str, hdr_outputs: &[String]) -> bool {
+    hdr_outputs
+        .iter()
+        .any(|p| crate::utils::glob_match(p, name))
+}
+
+/// Values for outputs that don't take the top-level schedule's values.
+#[derive(Debug, Clone, PartialEq)]
+struct OutputRule {
+    /// Output name globs the rule covers
+    outputs: Vec<String>,
+    /// Only match outputs that are also tagged via `hdr_outputs`
+    hdr_only: bool,
+    /// Temperature and gamma (0.0-1.0)
+    values: (u32, f32),
+}
+
+/// Build the output rules for `state`, most specific first.
+///
+/// Output groups come first, each with its own transition state (and HDR values for
+/// its HDR outputs), followed by the HDR values for the remaining HDR outputs.
+fn output_rules(
+    state: TransitionState,
+    config: &Config,
+    hdr_outputs: &[String],
+) -> Vec<OutputRule> {
+    let values = |state, config: &Config| {
+        let (temp, gamma) = crate::time_state::get_initial_values_for_state(state, config);
+        (temp, gamma / 100.0)
+    };
+
+    let mut rules = Vec::new();
+    for (_, group, group_config) in config.output_group_configs() {
+        let group_state = crate::time_state::get_transition_state(&group_config);
+        if !hdr_outputs.is_empty() {
+            rules.push(OutputRule {
+                outputs: group.outputs.clone(),
+                hdr_only: true,
+                values: values(group_state, &group_config.hdr_color_config()),
+            });
+        }
+        rules.push(OutputRule {
+            outputs: group.outputs,
+            hdr_only: false,
+            values: values(group_state, &group_config),
+        });
+    }
+    if !hdr_outputs.is_empty() {
+        rules.push(OutputRule {
+            outputs: hdr_outputs.to_vec(),
+            hdr_only: false,
+            values: values(state, &config.hdr_color_config()),
+        });
+    }
+    rules
+}
+
+/// The values for an output: those of the first matching rule, or `default`.
+fn values_for_output(
+    name: &str,
+    is_hdr: bool,
+    rules: &[OutputRule],
+    default: (u32, f32),
+) -> (u32, f32) {
+    rules
+        .iter()
+        .find(|rule| {
+            (is_hdr || !rule.hdr_only)
+                && rule
+                    .outputs
+                    .iter()
+                    .any(|p| crate::utils::glob_match(p, name))
+        })
+        .map_or(default, |rule| rule.values)
+}
+
+/// How far towards the night ramp a transition state is, when `precompute_ramps`
+/// can produce it by blending.
+///
+/// Only plain transitions qualify. A nudge, idle dimming, `gamma_progress_offset`,
+/// HDR outputs and output groups all move values away from the straight line between the day
+/// and night ramps, so those applies compute their ramps exactly. So does `temp_quantum`:
+/// the applied temperature is snapped, while the blend would follow the raw progress.
+fn blend_weight(state: TransitionState, config: &Config, rules: &[OutputRule]) -> Option<f32> {
+    let TransitionState::Transitioning { from, to, progress } = state else {
+        return None;
+    };
+    let effective = config.effective();
+    if !rules.is_empty()
+        || crate::time_state::temperature_nudge() != 0
+        || crate::time_state::idle_dimmed()
+        || effective.gamma_progress_offset != 0.0
+        || effective.temp_quantum > 1
+    {
+        return None;
+    }
+    match (from, to) {
+        (TimeState::Day, TimeState::Night) => Some(progress),
+        (TimeState::Night, TimeState::Day) => Some(1.0 - progress),
+        _ => None,
+    }
+}
+
+/// Name and output globs of each configured output group.
+fn output_group_globs(config: &Config) -> Vec<(String, Vec<String>)> {
+    config
+        .output_groups
+        .iter()
+        .flatten()
+        .map(|(name, group)| (name.clone(), group.outputs.clone()))
+        .collect()
+}
+
+/// Describe when an output's gamma was last confirmed, relative to `now`.
+fn describe_last_confirmed(last_confirmed: Option<Instant>, now: Instant) -> String {
+    match last_confirmed {
+        Some(at) => format!("{}s ago", now.saturating_duration_since(at).as_secs()),
+        None => "never".to_string(),
+    }
+}
+
+/// Resolve the configured `day_whitepoint` into per-channel correction factors.
+fn whitepoint_correction_for(config: &Config) -> (f32, f32, f32) {
+    config
+        .day_whitepoint
+        .and_then(|[x, y]| gamma::whitepoint_correction(x, y))
+        .unwrap_or((1.0, 1.0, 1.0))
+}
+
+/// Create an anonymous file containing the gamma table data, ready to hand to the compositor.
+///
+/// A memfd is preferred since it lives purely in memory and does not depend on a writable
+/// temporary directory (which may be missing in sandboxed or restricted environments).
+/// If `memfd_create` is unavailable or fails, we fall back to a regular temporary file.
+///
+/// The compositor receives a duplicate of our descriptor, which shares its file offset,
+/// and reads the table with plain `read()` calls from wherever that offset is. A file
+/// left positioned at EOF after writing is therefore read as empty, and the compositor
+/// rejects the apply. The data is written with positional writes (`pwrite`), which never
+/// move the offset, so the returned file is always positioned at the start.
+pub fn create_gamma_file(gamma_data: &[u8], debug_enabled: bool) -> Result<File> {
+    let file = match memfd_create(c"sunsetr-gamma", MFdFlags::MFD_CLOEXEC) {
+        Ok(fd) => {
+            if debug_enabled {
+                Log::log_decorated("Created memfd for gamma data");
+            }
+            File::from(fd)
+        }
+        Err(memfd_err) => {
+            if debug_enabled {
+                Log::log_decorated(&format!(
+                    "memfd_create failed ({}), falling back to temporary file",
+                    memfd_err
+                ));
+            }
+            tempfile::tempfile().map_err(|e| {
+                anyhow::anyhow!(
+                    "Failed to create gamma buffer: memfd_create failed ({}) and temporary file creation failed ({})",
+                    memfd_err,
+                    e
+                )
+            })?
+        }
+    };
+
+    // Write gamma data to file
+    if debug_enabled {
+        Log::log_decorated("Writing gamma data to file");
+    }
+    // Positional write: the offset stays at 0 for the compositor's reads
+    file.write_all_at(gamma_data, 0)
+        .map_err(|e| anyhow::anyhow!("Failed to write gamma data: {}", e))?;
+
+    Ok(file)
+}
+
 impl ColorTemperatureBackend for WaylandBackend {
     fn apply_transition_state(
         &mut self,
@@ -379,15 +1228,33 @@ impl ColorTemperatureBackend for WaylandBackend {
         config: &Config,
         _running: &AtomicBool,
     ) -> Result<()> {
+        self.update_from_config(config);
+
         let (temp, gamma) = crate::time_state::get_initial_values_for_state(state, config);
+        self.channel_gamma = crate::time_state::get_channel_gamma_for_state(state, config);
+        let rules = output_rules(state, config, &self.hdr_outputs);
         if self.debug_enabled {
             Log::log_pipe();
             Log::log_debug(&format!(
                 "Wayland backend applying state: temp={}K, gamma={:.1}%",
                 temp, gamma
             ));
+            for rule in &rules {
+                Log::log_indented(&format!(
+                    "{}{}: temp={}K, gamma={:.1}%",
+                    rule.outputs.join(", "),
+                    if rule.hdr_only { " (HDR)" } else { "" },
+                    rule.values.0,
+                    rule.values.1 * 100.0
+                ));
+            }
         }
-        self.apply_gamma_to_outputs(temp, gamma / 100.0) // Convert percentage to 0.0-1.0
+        let night_weight = if self.precompute_ramps {
+            blend_weight(state, config, &rules)
+        } else {
+            None
+        };
+        self.apply_gamma_to_outputs(temp, gamma / 100.0, &rules, None, night_weight) // Convert percentage to 0.0-1.0
     }
 
     fn apply_startup_state(
@@ -414,15 +1281,186 @@ impl ColorTemperatureBackend for WaylandBackend {
         gamma: f32,
         _running: &AtomicBool,
     ) -> Result<()> {
-        self.apply_gamma_to_outputs(temperature, gamma / 100.0) // Convert percentage to 0.0-1.0
+        // Explicit values (test mode, startup animation, resets) go to every output as-is,
+        // keeping the channel gamma of the last applied state
+        self.apply_gamma_to_outputs(temperature, gamma / 100.0, &[], None, None) // Convert percentage to 0.0-1.0
+    }
+
+    fn fade_in_output(
+        &mut self,
+        output: &str,
+        state: TransitionState,
+        config: &Config,
+        running: &AtomicBool,
+    ) -> Result<()> {
+        self.update_from_config(config);
+        if !self.is_output_targeted(output) {
+            return Ok(());
+        }
+
+        let (default_temp, default_gamma) =
+            crate::time_state::get_initial_values_for_state(state, config);
+        let (target_temp, target_gamma) = values_for_output(
+            output,
+            self.is_hdr_output(output),
+            &output_rules(state, config, &self.hdr_outputs),
+            (default_temp, default_gamma / 100.0),
+        );
+        let target_gamma = target_gamma * 100.0;
+        let target_channels = crate::time_state::get_channel_gamma_for_state(state, config);
+        let fade = Duration::from_millis(config.effective().wake_fade_ms);
+
+        if self.debug_enabled {
+            Log::log_pipe();
+            Log::log_debug(&format!(
+                "Fading in output '{}' over {} ms: temp={}K, gamma={:.1}%",
+                output,
+                fade.as_millis(),
+                target_temp,
+                target_gamma
+            ));
+        }
+
+        // Start from identity gamma (6500K, 100%), which is what the compositor loads on wake
+        let start = Instant::now();
+        loop {
+            let linear_progress = if fade.is_zero() {
+                1.0
+            } else {
+                (start.elapsed().as_secs_f32() / fade.as_secs_f32()).min(1.0)
+            };
+            let progress = crate::utils::bezier_curve(
+                linear_progress,
+                BEZIER_P1X,
+                BEZIER_P1Y,
+                BEZIER_P2X,
+                BEZIER_P2Y,
+            );
+            let temp = crate::utils::interpolate_u32(6500, target_temp, progress);
+            let gamma = crate::utils::interpolate_f32(100.0, target_gamma, progress);
+            self.channel_gamma = (
+                crate::utils::interpolate_f32(1.0, target_channels.0, progress),
+                crate::utils::interpolate_f32(1.0, target_channels.1, progress),
+                crate::utils::interpolate_f32(1.0, target_channels.2, progress),
+            );
+            let applied = self.apply_gamma_to_outputs(temp, gamma / 100.0, &[], Some(output), None);
+
+            if applied.is_err() || progress >= 1.0 || !running.load(Ordering::SeqCst) {
+                // Other outputs and later applies use the state's channel gamma
+                self.channel_gamma = target_channels;
+                return applied;
+            }
+            std::thread::sleep(Duration::from_millis(WAKE_FADE_STEP_MS));
+        }
     }
 
     fn backend_name(&self) -> &'static str {
         "Wayland"
     }
+
+    fn cleanup(mut self: Box<Self>, debug_enabled: bool) {
+        // Destroying a gamma control hands the output back to the compositor, which
+        // restores the gamma it had before (identity on wlroots). With reset_on_exit
+        // disabled, this is all that happens on exit.
+        let mut released = 0;
+        for output_info in &mut self.app_data.outputs {
+            if let Some(control) = output_info.gamma_control.take() {
+                control.destroy();
+                released += 1;
+            }
+        }
+        // Wait for the compositor to process the release, so another backend created
+        // right after this one doesn't race it
+        let display = self.connection.display();
+        if let Err(e) = roundtrip_with_timeout(
+            &display,
+            &mut self.event_queue,
+            &mut self.app_data,
+            "gamma control release",
+        ) {
+            Log::log_decorated(&format!("Warning: Failed to release gamma controls: {}", e));
+        } else if debug_enabled {
+            Log::log_decorated(&format!("Released {} gamma control(s)", released));
+        }
+    }
+}
+
+/// Roundtrip to the compositor, giving up after `WAYLAND_INIT_TIMEOUT_MS`.
+///
+/// `EventQueue::roundtrip` blocks until the compositor answers, which is forever if
+/// it never does. This sends the same `wl_display.sync` request but waits on the
+/// socket with `poll`, so the caller gets a "timed out waiting for <what>" error.
+fn roundtrip_with_timeout(
+    display: &WlDisplay,
+    event_queue: &mut EventQueue<AppData>,
+    app_data: &mut AppData,
+    what: &str,
+) -> Result<()> {
+    let done = Arc::new(AtomicBool::new(false));
+    display.sync(&event_queue.handle(), done.clone());
+
+    let timeout = Duration::from_millis(WAYLAND_INIT_TIMEOUT_MS);
+    let deadline = Instant::now() + timeout;
+    loop {
+        event_queue
+            .flush()
+            .map_err(|e| anyhow::anyhow!("Failed to send Wayland requests: {}", e))?;
+        event_queue
+            .dispatch_pending(app_data)
+            .map_err(|e| anyhow::anyhow!("Failed to process Wayland events: {}", e))?;
+        if done.load(Ordering::Acquire) {
+            return Ok(());
+        }
+
+        // None means events are already queued, so dispatch those first
+        let Some(guard) = event_queue.prepare_read() else {
+            continue;
+        };
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            anyhow::bail!(
+                "Timed out waiting for {} after {}s. The compositor stopped responding.",
+                what,
+                timeout.as_secs()
+            );
+        }
+
+        let millis = remaining.as_millis().min(u16::MAX as u128) as u16;
+        let mut fds = [PollFd::new(guard.connection_fd(), PollFlags::POLLIN)];
+        match poll(&mut fds, PollTimeout::from(millis)) {
+            // Timed out or interrupted: the deadline check reports a timeout next pass
+            Ok(0) | Err(nix::errno::Errno::EINTR) => drop(guard),
+            Ok(_) => {
+                // WouldBlock just means another reader got there first
+                if let Err(e) = guard.read()
+                    && !matches!(&e, wayland_client::backend::WaylandError::Io(io)
+                        if io.kind() == std::io::ErrorKind::WouldBlock)
+                {
+                    anyhow::bail!("Failed to read Wayland events: {}", e);
+                }
+            }
+            Err(e) => anyhow::bail!("Failed to wait for Wayland events: {}", e),
+        }
+    }
 }
 
 // Implement Dispatch traits for Wayland protocol handling
+impl Dispatch<WlCallback, Arc<AtomicBool>> for AppData {
+    fn event(
+        _: &mut Self,
+        _: &WlCallback,
+        event: <WlCallback as Proxy>::Event,
+        done: &Arc<AtomicBool>,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        if let wayland_client::protocol::wl_callback::Event::Done { .. } = event {
+            done.store(true, Ordering::Release);
+        }
+    }
+}
+
 impl Dispatch<WlRegistry, ()> for AppData {
     fn event(
         state: &mut Self,
@@ -434,13 +1472,12 @@ impl Dispatch<WlRegistry, ()> for AppData {
     ) {
         use wayland_client::protocol::wl_registry::Event;
 
-        if let Event::Global {
-            name,
-            interface,
-            version,
-        } = event
-        {
-            match interface.as_str() {
+        match event {
+            Event::Global {
+                name,
+                interface,
+                version,
+            } => match interface.as_str() {
                 "zwlr_gamma_control_manager_v1" => {
                     let manager =
                         registry.bind::<ZwlrGammaControlManagerV1, _, _>(name, version, qh, ());
@@ -449,14 +1486,34 @@ impl Dispatch<WlRegistry, ()> for AppData {
                 "wl_output" => {
                     let output = registry.bind::<WlOutput, _, _>(name, version, qh, ());
                     state.outputs.push(OutputInfo {
+                        global: name,
                         output,
                         gamma_control: None,
                         gamma_size: None,
                         name: format!("output-{}", name),
+                        failed: false,
+                        last_sent: None,
+                        last_confirmed: None,
                     });
                 }
                 _ => {}
+            },
+            Event::GlobalRemove { name } => {
+                let AppData {
+                    outputs, removed, ..
+                } = state;
+                outputs.retain(|o| {
+                    if o.global != name {
+                        return true;
+                    }
+                    if let Some(ref control) = o.gamma_control {
+                        control.destroy();
+                    }
+                    removed.push(o.name.clone());
+                    false
+                });
             }
+            _ => {}
         }
     }
 }
@@ -489,37 +1546,45 @@ impl Dispatch<ZwlrGammaControlV1, ()> for AppData {
             GammaControlEvent::GammaSize { size } => {
                 // Find the output this belongs to and set the gamma size
                 for output_info in &mut state.outputs {
-                    if let Some(ref control) = output_info.gamma_control {
-                        if control == gamma_control {
-                            output_info.gamma_size = Some(size as usize);
-                            // Only log gamma size in debug builds or when explicitly enabled
-                            #[cfg(debug_assertions)]
-                            Log::log_decorated(&format!(
-                                "Output '{}' gamma size: {}",
-                                output_info.name, size
-                            ));
-                            break;
+                    if let Some(ref control) = output_info.gamma_control
+                        && control == gamma_control
+                    {
+                        // A second event means the compositor renegotiated the size, e.g.
+                        // after a mode change; the next apply uses the new one
+                        if let Some(old) = output_info.gamma_size
+                            && old != size as usize
+                        {
+                            state
+                                .resized
+                                .push((output_info.name.clone(), old, size as usize));
                         }
+                        output_info.gamma_size = Some(size as usize);
+                        // Only log gamma size in debug builds or when explicitly enabled
+                        #[cfg(debug_assertions)]
+                        Log::log_decorated(&format!(
+                            "Output '{}' gamma size: {}",
+                            output_info.name, size
+                        ));
+                        break;
                     }
                 }
             }
             GammaControlEvent::Failed => {
                 // This is critical - the compositor rejected our gamma control
-                for output_info in &state.outputs {
-                    if let Some(ref control) = output_info.gamma_control {
-                        if control == gamma_control {
-                            Log::log_error(&format!(
-                                "CRITICAL: Gamma control failed for output '{}' - compositor rejected our control!",
-                                output_info.name
-                            ));
-                            Log::log_error("This could mean:");
-                            Log::log_error("1. Another client already has exclusive gamma control");
-                            Log::log_error(
-                                "2. The compositor doesn't actually support gamma control",
-                            );
-                            Log::log_error("3. Permission denied for gamma control");
-                            break;
-                        }
+                for output_info in &mut state.outputs {
+                    if let Some(ref control) = output_info.gamma_control
+                        && control == gamma_control
+                    {
+                        output_info.failed = true;
+                        Log::log_error(&format!(
+                            "CRITICAL: Gamma control failed for output '{}' - compositor rejected our control!",
+                            output_info.name
+                        ));
+                        Log::log_error("This could mean:");
+                        Log::log_error("1. Another client already has exclusive gamma control");
+                        Log::log_error("2. The compositor doesn't actually support gamma control");
+                        Log::log_error("3. Permission denied for gamma control");
+                        break;
                     }
                 }
             }
@@ -555,3 +1620,109 @@ impl Dispatch<WlOutput, ()> for AppData {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gamma_control_guard_is_exclusive() {
+        let first = GammaControlGuard::acquire(Duration::from_secs(5)).unwrap();
+        assert!(GammaControlGuard::acquire(Duration::from_millis(50)).is_err());
+
+        // A waiting backend gets the slot as soon as the holder lets go
+        let waiter = std::thread::spawn(|| GammaControlGuard::acquire(Duration::from_secs(5)));
+        std::thread::sleep(Duration::from_millis(100));
+        drop(first);
+        let second = waiter.join().unwrap().unwrap();
+        assert!(GammaControlGuard::acquire(Duration::from_millis(50)).is_err());
+        drop(second);
+        drop(GammaControlGuard::acquire(Duration::from_millis(50)).unwrap());
+    }
+
+    #[test]
+    fn test_ineffective_applies_warn_after_threshold_and_rate_limit() {
+        let mut tracker = IneffectiveApplies::default();
+        let start = Instant::now();
+
+        for _ in 1..INEFFECTIVE_GAMMA_THRESHOLD {
+            assert!(!tracker.record(2, 0, start));
+        }
+        assert!(tracker.record(2, 0, start));
+
+        // Further failures stay quiet until the interval has passed
+        let interval = Duration::from_secs(INEFFECTIVE_GAMMA_WARNING_INTERVAL_SECS);
+        assert!(!tracker.record(2, 0, start + interval / 2));
+        assert!(tracker.record(2, 0, start + interval));
+    }
+
+    #[test]
+    fn test_ineffective_applies_reset_and_ignore_untargeted() {
+        let mut tracker = IneffectiveApplies::default();
+        let now = Instant::now();
+
+        // No outputs connected or all filtered out: nothing to judge
+        for _ in 0..INEFFECTIVE_GAMMA_THRESHOLD * 2 {
+            assert!(!tracker.record(0, 0, now));
+        }
+        assert_eq!(tracker.streak, 0);
+
+        for _ in 1..INEFFECTIVE_GAMMA_THRESHOLD {
+            tracker.record(1, 0, now);
+        }
+        // One successful apply breaks the streak
+        assert!(!tracker.record(1, 1, now));
+        assert!(!tracker.record(1, 0, now));
+        assert_eq!(tracker.streak, 1);
+    }
+
+    #[test]
+    fn test_output_rules_pick_group_then_hdr_values() {
+        // Identical day and night values keep the test independent of the time of day
+        let config: Config = toml::from_str(
+            r#"
+sunset = "19:00:00"
+sunrise = "06:00:00"
+night_temp = 3300
+day_temp = 3300
+night_gamma = 90.0
+day_gamma = 90.0
+hdr_outputs = ["DP-*"]
+hdr_night_temp = 5000
+hdr_day_temp = 5000
+
+[output_groups.side]
+outputs = ["HDMI-A-1", "DP-2"]
+night_temp = 2000
+day_temp = 2000
+"#,
+        )
+        .unwrap();
+        let hdr_outputs = config.hdr_outputs.clone().unwrap();
+        let state = crate::time_state::get_transition_state(&config);
+        let rules = output_rules(state, &config, &hdr_outputs);
+        assert_eq!(rules.len(), 3);
+
+        let values = |name: &str| {
+            let is_hdr = output_matches_hdr(name, &hdr_outputs);
+            values_for_output(name, is_hdr, &rules, (3300, 0.9))
+        };
+        assert_eq!(values("eDP-1"), (3300, 0.9));
+        assert_eq!(values("HDMI-A-1"), (2000, 0.9));
+        assert_eq!(values("DP-2"), (5000, 0.9));
+        assert_eq!(values("DP-1"), (5000, 0.9));
+
+        // Without groups or HDR outputs every output gets the default values
+        assert!(
+            output_rules(
+                state,
+                &Config {
+                    output_groups: None,
+                    ..config
+                },
+                &[]
+            )
+            .is_empty()
+        );
+    }
+}
@@ -0,0 +1,342 @@
+//! Output wake detection using wlr-output-power-management-unstable-v1.
+//!
+//! Compositors commonly load identity gamma when a monitor comes back from DPMS,
+//! so the screen snaps to uncorrected colors until sunsetr's next update. This
+//! module watches each output's power mode on a separate Wayland connection and
+//! reports every off → on change to the main loop as
+//! [`SignalMessage::OutputWake`](crate::signals::SignalMessage::OutputWake), which
+//! fades the correction back in on just that output. Outputs connected after
+//! startup are reported the same way as
+//! [`SignalMessage::OutputAdded`](crate::signals::SignalMessage::OutputAdded) once
+//! they are on, so a new monitor is corrected right away rather than at the next
+//! scheduled update.
+//!
+//! The monitor runs in its own thread with its own connection and event queue, so
+//! it never contends with the backend's gamma controls. Compositors without the
+//! protocol (e.g. niri) don't get wake handling, but connected outputs are still
+//! reported, assumed to be on once the compositor has described them. This is
+//! what lets sunsetr start with no outputs and correct the first one to appear.
+
+use std::sync::mpsc::Sender;
+
+use wayland_client::{
+    Connection, Dispatch, EventQueue, Proxy, QueueHandle, WEnum,
+    protocol::{wl_output::WlOutput, wl_registry::WlRegistry},
+};
+use wayland_protocols_wlr::output_power_management::v1::client::{
+    zwlr_output_power_manager_v1::ZwlrOutputPowerManagerV1,
+    zwlr_output_power_v1::{Event as OutputPowerEvent, Mode, ZwlrOutputPowerV1},
+};
+
+use crate::logger::Log;
+use crate::signals::SignalMessage;
+
+/// Power state of one output as seen by the monitor.
+struct PowerOutput {
+    /// Registry name of the `wl_output` global, used to handle removal
+    global: u32,
+    output: WlOutput,
+    power: Option<ZwlrOutputPowerV1>,
+    name: String,
+    /// Last mode reported by the compositor (`None` until the first event)
+    mode: Option<Mode>,
+    /// Whether the output was connected after the monitor started
+    hotplugged: bool,
+}
+
+/// Dispatch state for the monitor's event queue.
+struct PowerMonitor {
+    manager: Option<ZwlrOutputPowerManagerV1>,
+    outputs: Vec<PowerOutput>,
+    /// Names of outputs that woke up since the queue was last drained
+    woken: Vec<String>,
+    /// Names of hotplugged outputs that turned on since the queue was last drained
+    added: Vec<String>,
+    /// Set once the outputs present at startup have been enumerated
+    ready: bool,
+}
+
+impl PowerMonitor {
+    /// Request a power object for every output that doesn't have one yet.
+    fn watch_outputs(&mut self, qh: &QueueHandle<Self>) {
+        let Some(manager) = &self.manager else {
+            return;
+        };
+        for output in self.outputs.iter_mut().filter(|o| o.power.is_none()) {
+            output.power = Some(manager.get_output_power(&output.output, qh, ()));
+        }
+    }
+}
+
+/// Whether a power mode change means the output just woke up.
+///
+/// The first event only reports the current mode, so it never counts as a wake.
+fn is_wake(previous: Option<Mode>, current: Mode) -> bool {
+    previous == Some(Mode::Off) && current == Mode::On
+}
+
+/// Whether a power mode report means a newly connected output is ready for gamma.
+///
+/// Only the first report of an output connected after startup counts.
+fn is_added(hotplugged: bool, previous: Option<Mode>, current: Mode) -> bool {
+    hotplugged && previous.is_none() && current == Mode::On
+}
+
+/// Start watching output power modes in a background thread.
+///
+/// Each output that turns back on is sent to the main loop through `sender`.
+/// Failing to start is only reported in debug mode, since wake handling is a
+/// convenience and most compositors restore gamma on the next update anyway.
+pub fn start_wake_monitor(sender: Sender<SignalMessage>, debug_enabled: bool) {
+    let (event_queue, monitor) = match connect() {
+        Ok(setup) => setup,
+        Err(e) => {
+            if debug_enabled {
+                Log::log_pipe();
+                Log::log_debug(&format!("Failed to watch output power modes: {}", e));
+            }
+            return;
+        }
+    };
+
+    if debug_enabled {
+        Log::log_pipe();
+        if monitor.manager.is_some() {
+            Log::log_debug("Watching output power modes for DPMS wake");
+        } else {
+            Log::log_debug(
+                "Compositor lacks wlr-output-power-management, monitor wake fades disabled",
+            );
+            Log::log_indented("Still watching for newly connected outputs");
+        }
+    }
+
+    std::thread::spawn(move || run(event_queue, monitor, sender, debug_enabled));
+}
+
+/// Connect to the compositor and bind the power manager and outputs.
+///
+/// The manager is left unset when the compositor doesn't support the protocol.
+fn connect() -> anyhow::Result<(EventQueue<PowerMonitor>, PowerMonitor)> {
+    let connection = Connection::connect_to_env()?;
+    let mut event_queue = connection.new_event_queue();
+    let qh = event_queue.handle();
+    let _registry = connection.display().get_registry(&qh, ());
+
+    let mut monitor = PowerMonitor {
+        manager: None,
+        outputs: Vec::new(),
+        woken: Vec::new(),
+        added: Vec::new(),
+        ready: false,
+    };
+
+    // First roundtrip binds globals, the second delivers output names and initial modes
+    event_queue.roundtrip(&mut monitor)?;
+    monitor.watch_outputs(&qh);
+    event_queue.roundtrip(&mut monitor)?;
+    monitor.ready = true;
+
+    Ok((event_queue, monitor))
+}
+
+fn run(
+    mut event_queue: EventQueue<PowerMonitor>,
+    mut monitor: PowerMonitor,
+    sender: Sender<SignalMessage>,
+    debug_enabled: bool,
+) {
+    loop {
+        if let Err(e) = event_queue.blocking_dispatch(&mut monitor) {
+            if debug_enabled {
+                Log::log_pipe();
+                Log::log_debug(&format!("Output power monitor stopped: {}", e));
+            }
+            return;
+        }
+
+        // Outputs hotplugged since the last dispatch need their own power objects
+        monitor.watch_outputs(&event_queue.handle());
+
+        for name in monitor.woken.drain(..) {
+            if debug_enabled {
+                Log::log_pipe();
+                Log::log_debug(&format!("Output '{}' woke from DPMS", name));
+            }
+
+            // The main loop has gone away, so there's nobody left to notify
+            if sender.send(SignalMessage::OutputWake(name)).is_err() {
+                return;
+            }
+        }
+
+        for name in monitor.added.drain(..) {
+            if debug_enabled {
+                Log::log_pipe();
+                Log::log_debug(&format!("Output '{}' was connected", name));
+            }
+
+            if sender.send(SignalMessage::OutputAdded(name)).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+impl Dispatch<WlRegistry, ()> for PowerMonitor {
+    fn event(
+        state: &mut Self,
+        registry: &WlRegistry,
+        event: <WlRegistry as Proxy>::Event,
+        _: &(),
+        _: &Connection,
+        qh: &QueueHandle<Self>,
+    ) {
+        use wayland_client::protocol::wl_registry::Event;
+
+        match event {
+            Event::Global {
+                name,
+                interface,
+                version,
+            } => match interface.as_str() {
+                "zwlr_output_power_manager_v1" => {
+                    state.manager = Some(registry.bind(name, version.min(1), qh, ()));
+                }
+                "wl_output" => {
+                    state.outputs.push(PowerOutput {
+                        global: name,
+                        output: registry.bind(name, version.min(4), qh, ()),
+                        power: None,
+                        name: format!("output-{}", name),
+                        mode: None,
+                        hotplugged: state.ready,
+                    });
+                }
+                _ => {}
+            },
+            Event::GlobalRemove { name } => {
+                state.outputs.retain(|o| {
+                    if o.global != name {
+                        return true;
+                    }
+                    if let Some(power) = &o.power {
+                        power.destroy();
+                    }
+                    false
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Dispatch<ZwlrOutputPowerManagerV1, ()> for PowerMonitor {
+    fn event(
+        _: &mut Self,
+        _: &ZwlrOutputPowerManagerV1,
+        _: <ZwlrOutputPowerManagerV1 as Proxy>::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        // No events for the manager
+    }
+}
+
+impl Dispatch<ZwlrOutputPowerV1, ()> for PowerMonitor {
+    fn event(
+        state: &mut Self,
+        power: &ZwlrOutputPowerV1,
+        event: OutputPowerEvent,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        let Some(index) = state
+            .outputs
+            .iter()
+            .position(|o| o.power.as_ref() == Some(power))
+        else {
+            return;
+        };
+
+        match event {
+            OutputPowerEvent::Mode {
+                mode: WEnum::Value(mode),
+            } => {
+                let output = &mut state.outputs[index];
+                if is_wake(output.mode, mode) {
+                    state.woken.push(output.name.clone());
+                }
+                if is_added(output.hotplugged, output.mode, mode) {
+                    state.added.push(output.name.clone());
+                }
+                output.mode = Some(mode);
+            }
+            OutputPowerEvent::Failed => {
+                // The output went away or another client holds its power control.
+                // The object is inert from now on, and asking again would just fail again.
+                power.destroy();
+                state.outputs.remove(index);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Dispatch<WlOutput, ()> for PowerMonitor {
+    fn event(
+        state: &mut Self,
+        output: &WlOutput,
+        event: <WlOutput as Proxy>::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        use wayland_client::protocol::wl_output::Event;
+
+        let has_manager = state.manager.is_some();
+        let Some(info) = state.outputs.iter_mut().find(|o| &o.output == output) else {
+            return;
+        };
+        match event {
+            Event::Name { name } => info.name = name,
+            // Without power management there's no mode report, so a hotplugged output
+            // counts as on once its properties (including the name) are complete
+            Event::Done if !has_manager => {
+                if is_added(info.hotplugged, info.mode, Mode::On) {
+                    state.added.push(info.name.clone());
+                }
+                info.mode = Some(Mode::On);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_wake() {
+        assert!(is_wake(Some(Mode::Off), Mode::On));
+
+        // The initial report and repeated or opposite modes aren't wakes
+        assert!(!is_wake(None, Mode::On));
+        assert!(!is_wake(Some(Mode::On), Mode::On));
+        assert!(!is_wake(Some(Mode::On), Mode::Off));
+        assert!(!is_wake(Some(Mode::Off), Mode::Off));
+    }
+
+    #[test]
+    fn test_is_added() {
+        assert!(is_added(true, None, Mode::On));
+
+        // Outputs present at startup, later reports and outputs connected while off aren't added
+        assert!(!is_added(false, None, Mode::On));
+        assert!(!is_added(true, Some(Mode::Off), Mode::On));
+        assert!(!is_added(true, None, Mode::Off));
+    }
+}
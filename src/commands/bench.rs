@@ -0,0 +1,226 @@
+//! Implementation of the --bench command.
+//!
+//! Applies a sweep of temperature and gamma values between the configured day and
+//! night values and measures the wall-clock time of each `apply_temperature_gamma`
+//! call, then reports the min, median and max. Each Wayland apply includes the
+//! confirming roundtrip (regardless of `verify_gamma`), and each Hyprland apply
+//! includes the IPC exchange with hyprsunset.
+//!
+//! Like `--once`, this runs without the lock and refuses to run next to an active
+//! instance. Afterwards the values for the current time are applied again and the
+//! backend is cleaned up, so a hyprsunset started for the benchmark is stopped and
+//! Wayland gamma reverts when sunsetr disconnects.
+
+use crate::backend::hyprland::is_hyprsunset_running;
+use crate::backend::{create_backend, detect_backend};
+use crate::config::Config;
+use crate::constants::{BENCH_SLOW_APPLY_MS, MAXIMUM_BENCH_ITERATIONS, MINIMUM_BENCH_ITERATIONS};
+use crate::logger::Log;
+use crate::time_state::{get_initial_values_for_state, get_transition_state};
+use crate::utils::{interpolate_f32, interpolate_u32};
+use anyhow::Result;
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant};
+
+/// Summary of the measured apply latencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyStats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl LatencyStats {
+    /// Summarize a set of samples, or `None` when there are none.
+    ///
+    /// The median of an even number of samples is the mean of the middle two.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let len = sorted.len();
+        if len == 0 {
+            return None;
+        }
+
+        let median = if len.is_multiple_of(2) {
+            (sorted[len / 2 - 1] + sorted[len / 2]) / 2
+        } else {
+            sorted[len / 2]
+        };
+
+        Some(Self {
+            min: sorted[0],
+            median,
+            max: sorted[len - 1],
+        })
+    }
+}
+
+/// Handle the --bench command to measure apply latency of the detected backend.
+pub fn handle_bench_command(iterations: usize, debug_enabled: bool) -> Result<()> {
+    Log::log_version();
+
+    if !(MINIMUM_BENCH_ITERATIONS..=MAXIMUM_BENCH_ITERATIONS).contains(&iterations) {
+        anyhow::bail!(
+            "Iteration count {} is out of range ({}-{})",
+            iterations,
+            MINIMUM_BENCH_ITERATIONS,
+            MAXIMUM_BENCH_ITERATIONS
+        );
+    }
+
+    let mut config = Config::load()?;
+
+    // A running instance holds the gamma controls and would skew every measurement
+    if let Ok(pid) = crate::utils::get_running_sunsetr_pid() {
+        Log::log_pipe();
+        anyhow::bail!(
+            "sunsetr is already running (PID: {}) and manages gamma itself.\n\
+            Stop it before benchmarking.",
+            pid
+        );
+    }
+
+    crate::appearance::init(&config, debug_enabled)?;
+
+    // Time the confirming roundtrip too; it is part of what each update costs
+    config.verify_gamma = Some(true);
+
+    let backend_type = detect_backend(&config)?;
+    if matches!(backend_type, crate::backend::BackendType::Hyprland)
+        && config.effective().start_hyprsunset
+        && is_hyprsunset_running(config.hyprsunset_socket.as_deref())
+    {
+        // Talk to the already running hyprsunset, as --once does
+        config.start_hyprsunset = Some(false);
+    }
+
+    let mut backend = create_backend(backend_type, &config, debug_enabled)?;
+    crate::json::field("backend", backend.backend_name());
+    let running = AtomicBool::new(true);
+    let effective = config.effective();
+    let values = bench_values(
+        (effective.night_temp, effective.night_gamma),
+        (effective.day_temp, effective.day_gamma),
+        iterations,
+    );
+
+    Log::log_block_start(&format!(
+        "Benchmarking {} applies on the {} backend...",
+        iterations,
+        backend.backend_name()
+    ));
+
+    let mut samples = Vec::with_capacity(values.len());
+    let mut failure = None;
+    for &(temp, gamma) in &values {
+        let started = Instant::now();
+        if let Err(e) = backend.apply_temperature_gamma(temp, gamma, &running) {
+            failure = Some(e);
+            break;
+        }
+        samples.push(started.elapsed());
+
+        if debug_enabled {
+            Log::log_debug(&format!(
+                "{}K @ {:.1}%: {:.2} ms",
+                temp,
+                gamma,
+                millis(*samples.last().unwrap())
+            ));
+        }
+    }
+
+    // Put back the values for the current time before letting go of the backend
+    let state = get_transition_state(&config);
+    let (restore_temp, restore_gamma) = get_initial_values_for_state(state, &config);
+    if let Err(e) = backend.apply_temperature_gamma(restore_temp, restore_gamma, &running) {
+        Log::log_warning(&format!("Failed to restore the current values: {}", e));
+    }
+    backend.cleanup(debug_enabled);
+
+    if let Some(e) = failure {
+        anyhow::bail!("Apply failed after {} samples: {}", samples.len(), e);
+    }
+
+    crate::json::field("samples", samples.len());
+    if let Some(stats) = LatencyStats::from_samples(&samples) {
+        crate::json::field("min_ms", millis(stats.min));
+        crate::json::field("median_ms", millis(stats.median));
+        crate::json::field("max_ms", millis(stats.max));
+        Log::log_block_start(&format!("Apply latency over {} calls:", samples.len()));
+        Log::log_indented(&format!("min:    {:.2} ms", millis(stats.min)));
+        Log::log_indented(&format!("median: {:.2} ms", millis(stats.median)));
+        Log::log_indented(&format!("max:    {:.2} ms", millis(stats.max)));
+
+        if stats.median >= Duration::from_millis(BENCH_SLOW_APPLY_MS) {
+            Log::log_pipe();
+            Log::log_warning("Each apply is slow on this compositor");
+            Log::log_indented(&format!(
+                "Consider a larger update_interval (currently {} seconds)",
+                config.effective().update_interval
+            ));
+        }
+    }
+
+    Log::log_end();
+    Ok(())
+}
+
+/// The values to apply: an even sweep from the night values to the day values.
+///
+/// `night` and `day` are (temperature, gamma) pairs.
+fn bench_values(night: (u32, f32), day: (u32, f32), iterations: usize) -> Vec<(u32, f32)> {
+    (0..iterations)
+        .map(|i| {
+            let progress = if iterations > 1 {
+                i as f32 / (iterations - 1) as f32
+            } else {
+                0.0
+            };
+            (
+                interpolate_u32(night.0, day.0, progress),
+                interpolate_f32(night.1, day.1, progress),
+            )
+        })
+        .collect()
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_stats() {
+        let ms = Duration::from_millis;
+        assert_eq!(LatencyStats::from_samples(&[]), None);
+
+        let stats = LatencyStats::from_samples(&[ms(9), ms(1), ms(4)]).unwrap();
+        assert_eq!((stats.min, stats.median, stats.max), (ms(1), ms(4), ms(9)));
+
+        let stats = LatencyStats::from_samples(&[ms(8), ms(2), ms(4), ms(6)]).unwrap();
+        assert_eq!((stats.min, stats.median, stats.max), (ms(2), ms(5), ms(8)));
+    }
+
+    #[test]
+    fn test_bench_values_sweep() {
+        let (night, day) = ((3300, 90.0), (6500, 100.0));
+
+        let values = bench_values(night, day, 5);
+        assert_eq!(values.len(), 5);
+        assert_eq!(values[0], (3300, 90.0));
+        assert_eq!(values[2], (4900, 95.0));
+        assert_eq!(values[4], (6500, 100.0));
+        assert!(
+            values
+                .windows(2)
+                .all(|w| w[0].0 < w[1].0 && w[0].1 < w[1].1)
+        );
+
+        assert_eq!(bench_values(night, day, 1), vec![(3300, 90.0)]);
+    }
+}
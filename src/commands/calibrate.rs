@@ -0,0 +1,308 @@
+//! Implementation of the --calibrate command.
+//!
+//! Helps pick `night_temp` without editing the config over and over: a few candidate
+//! temperatures are shown one after another, each for a few seconds at the configured
+//! night gamma, and then the user picks the most comfortable one from a dropdown. The
+//! choice is written to `night_temp` and a running instance is reloaded.
+//!
+//! With a running instance, the candidates go through its test mode (as with `--test`),
+//! so its backend applies them. Otherwise the detected backend is created for the
+//! calibration, as for `--bench`. Either way the previous values are restored when the
+//! calibration ends, including when it is cancelled with Escape or Ctrl+C.
+
+use crate::backend::hyprland::is_hyprsunset_running;
+use crate::backend::{ColorTemperatureBackend, create_backend, detect_backend};
+use crate::config::Config;
+use crate::constants::{CALIBRATE_PREVIEW_SECS, CALIBRATE_TEMPERATURES};
+use crate::logger::Log;
+use crate::time_state::{get_initial_values_for_state, get_transition_state};
+use anyhow::{Context, Result};
+use std::io::IsTerminal;
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant};
+
+/// Where the candidate temperatures are shown.
+enum Preview {
+    /// The running instance with this PID, through its test mode
+    Instance(u32),
+    /// The detected backend, created for the calibration
+    Backend(Box<dyn ColorTemperatureBackend>),
+}
+
+impl Preview {
+    fn show(&mut self, temperature: u32, gamma: f32, running: &AtomicBool) -> Result<()> {
+        match self {
+            Preview::Instance(pid) => {
+                crate::commands::test::signal_test_mode(*pid, temperature, gamma)
+            }
+            Preview::Backend(backend) => {
+                backend.apply_temperature_gamma(temperature, gamma, running)
+            }
+        }
+    }
+
+    /// Put back the values for the current time and let go of the display.
+    fn restore(self, config: &Config, running: &AtomicBool, debug_enabled: bool) {
+        match self {
+            Preview::Instance(pid) => {
+                if let Err(e) = crate::commands::test::signal_test_mode(pid, 0, 0.0) {
+                    Log::log_warning(&format!("Failed to end test mode: {}", e));
+                }
+            }
+            Preview::Backend(mut backend) => {
+                let state = get_transition_state(config);
+                let (temperature, gamma) = get_initial_values_for_state(state, config);
+                if let Err(e) = backend.apply_temperature_gamma(temperature, gamma, running) {
+                    Log::log_warning(&format!("Failed to restore the current values: {}", e));
+                }
+                backend.cleanup(debug_enabled);
+            }
+        }
+    }
+}
+
+/// What the user chose in the dropdown.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Choice {
+    Temperature(u32),
+    ShowAgain,
+}
+
+/// Handle the --calibrate command to pick `night_temp` from a few previews.
+pub fn handle_calibrate_command(debug_enabled: bool) -> Result<()> {
+    Log::log_version();
+
+    if crate::json::is_enabled() {
+        anyhow::bail!("--calibrate is interactive and can't be used with --json");
+    }
+    if !std::io::stdin().is_terminal() {
+        Log::log_pipe();
+        anyhow::bail!("--calibrate needs an interactive terminal");
+    }
+
+    let mut config = Config::load()?;
+    let effective = config.effective();
+    let gamma = effective.night_gamma;
+    let current = effective.night_temp;
+    let candidates = calibration_candidates(&CALIBRATE_TEMPERATURES, effective.day_temp);
+    if candidates.is_empty() {
+        Log::log_pipe();
+        anyhow::bail!(
+            "day_temp ({}K) is below every candidate, so none of them can be night_temp",
+            effective.day_temp
+        );
+    }
+
+    let mut preview = match crate::utils::get_running_sunsetr_pid() {
+        Ok(pid) => {
+            Log::log_block_start(&format!(
+                "Showing the candidates through the running sunsetr (PID: {})",
+                pid
+            ));
+            Preview::Instance(pid)
+        }
+        Err(_) => {
+            crate::appearance::init(&config, debug_enabled)?;
+            let backend_type = detect_backend(&config)?;
+            if matches!(backend_type, crate::backend::BackendType::Hyprland)
+                && config.effective().start_hyprsunset
+                && is_hyprsunset_running(config.hyprsunset_socket.as_deref())
+            {
+                // Talk to the already running hyprsunset, as --once does
+                config.start_hyprsunset = Some(false);
+            }
+            let backend = create_backend(backend_type, &config, debug_enabled)?;
+            Log::log_block_start(&format!(
+                "Showing the candidates on the {} backend",
+                backend.backend_name()
+            ));
+            Preview::Backend(backend)
+        }
+    };
+
+    let running = AtomicBool::new(true);
+    let choice = choose_temperature(&mut preview, &candidates, gamma, current, &running);
+    preview.restore(&config, &running, debug_enabled);
+    let chosen = choice?;
+
+    if chosen == current {
+        Log::log_block_start(&format!("night_temp stays at {}K", chosen));
+        Log::log_end();
+        return Ok(());
+    }
+
+    if crate::utils::is_safe_mode() {
+        Log::log_block_start(&format!(
+            "Safe mode: not writing night_temp = {} to the config",
+            chosen
+        ));
+        Log::log_end();
+        return Ok(());
+    }
+
+    // The file holds mireds when temp_unit says so
+    let value = if config.temp_unit.as_deref() == Some("mired") {
+        crate::utils::kelvin_to_mired(chosen)
+    } else {
+        chosen
+    };
+    let path = Config::update_config_value("night_temp", &value.to_string())?;
+    Log::log_block_start(&format!(
+        "Set night_temp to {}K in {}",
+        chosen,
+        crate::utils::path_for_display(&path)
+    ));
+
+    if let Ok(pid) = crate::utils::get_running_sunsetr_pid() {
+        if debug_enabled {
+            Log::log_pipe();
+            Log::log_debug(&format!("Sending SIGUSR2 to PID {} to reload", pid));
+        }
+        match nix::sys::signal::kill(
+            nix::unistd::Pid::from_raw(pid as i32),
+            nix::sys::signal::Signal::SIGUSR2,
+        ) {
+            Ok(_) => Log::log_decorated(&format!("Sent reload signal to sunsetr (PID: {})", pid)),
+            Err(e) => {
+                Log::log_warning(&format!("Failed to signal existing process: {}", e));
+                Log::log_indented("You may need to run 'sunsetr --reload' manually.");
+            }
+        }
+    }
+
+    Log::log_end();
+    Ok(())
+}
+
+/// Show every candidate, then ask which one to keep, until the user picks one.
+fn choose_temperature(
+    preview: &mut Preview,
+    candidates: &[u32],
+    gamma: f32,
+    current: u32,
+    running: &AtomicBool,
+) -> Result<u32> {
+    loop {
+        Log::log_indented(&format!(
+            "Each temperature is shown for {}s at {}% gamma",
+            CALIBRATE_PREVIEW_SECS, gamma
+        ));
+        Log::log_indented("Press Escape or Ctrl+C to stop and restore the previous values");
+
+        for (i, &temperature) in candidates.iter().enumerate() {
+            Log::log_decorated(&format!(
+                "Showing {}K ({}/{})",
+                temperature,
+                i + 1,
+                candidates.len()
+            ));
+            preview
+                .show(temperature, gamma, running)
+                .with_context(|| format!("Failed to show {}K", temperature))?;
+            if wait_or_cancel(Duration::from_secs(CALIBRATE_PREVIEW_SECS))? {
+                Log::log_pipe();
+                Log::log_warning("Calibration cancelled. night_temp is unchanged.");
+                anyhow::bail!("Operation cancelled by user");
+            }
+        }
+
+        let options = choice_options(candidates, current);
+        let initial = candidates
+            .iter()
+            .position(|&temperature| temperature == current)
+            .unwrap_or(0);
+        let index = crate::utils::show_dropdown_menu_at(
+            &options,
+            initial,
+            Some("Which temperature was the most comfortable?"),
+            Some("Calibration cancelled. night_temp is unchanged."),
+        )?;
+        match options[index].1 {
+            Choice::Temperature(temperature) => return Ok(temperature),
+            Choice::ShowAgain => Log::log_block_start("Showing the candidates again"),
+        }
+    }
+}
+
+/// The candidates that can be used as `night_temp`, which must stay below `day_temp`.
+fn calibration_candidates(temperatures: &[u32], day_temp: u32) -> Vec<u32> {
+    temperatures
+        .iter()
+        .copied()
+        .filter(|&temperature| temperature < day_temp)
+        .collect()
+}
+
+/// Dropdown entries for the candidates, marking the configured one.
+fn choice_options(candidates: &[u32], current: u32) -> Vec<(String, Choice)> {
+    let mut options: Vec<(String, Choice)> = candidates
+        .iter()
+        .map(|&temperature| {
+            let label = if temperature == current {
+                format!("{}K (current)", temperature)
+            } else {
+                format!("{}K", temperature)
+            };
+            (label, Choice::Temperature(temperature))
+        })
+        .collect();
+    options.push(("Show them again".to_string(), Choice::ShowAgain));
+    options
+}
+
+/// Wait for `duration`, returning `true` if Escape or Ctrl+C was pressed meanwhile.
+fn wait_or_cancel(duration: Duration) -> Result<bool> {
+    use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+    use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+
+    enable_raw_mode()?;
+    let deadline = Instant::now() + duration;
+    let result = loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break Ok(false);
+        }
+        match event::poll(remaining) {
+            Ok(true) => match event::read() {
+                Ok(Event::Key(key))
+                    if key.code == KeyCode::Esc
+                        || (key.code == KeyCode::Char('c')
+                            && key.modifiers.contains(KeyModifiers::CONTROL)) =>
+                {
+                    break Ok(true);
+                }
+                Ok(_) => {}
+                Err(e) => break Err(e.into()),
+            },
+            Ok(false) => {}
+            Err(e) => break Err(e.into()),
+        }
+    };
+    disable_raw_mode()?;
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calibration_candidates_and_options() {
+        assert_eq!(
+            calibration_candidates(&CALIBRATE_TEMPERATURES, 6500),
+            vec![4500, 4000, 3500, 3000]
+        );
+        assert_eq!(
+            calibration_candidates(&CALIBRATE_TEMPERATURES, 4000),
+            vec![3500, 3000]
+        );
+        assert!(calibration_candidates(&CALIBRATE_TEMPERATURES, 3000).is_empty());
+
+        let options = choice_options(&[4000, 3500], 3500);
+        let labels: Vec<&str> = options.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(labels, ["4000K", "3500K (current)", "Show them again"]);
+        assert_eq!(options[1].1, Choice::Temperature(3500));
+        assert_eq!(options[2].1, Choice::ShowAgain);
+    }
+}
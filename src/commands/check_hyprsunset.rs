@@ -0,0 +1,83 @@
+//! Implementation of the --check-hyprsunset command.
+//!
+//! Runs only the hyprsunset detection that normally happens while the Hyprland
+//! backend starts: it looks for the binary, reads its version and compares it
+//! against the versions sunsetr supports. Nothing is started or applied, so this is
+//! a quick first step when sunsetr won't start on Hyprland. The command exits with
+//! a non-zero status unless a compatible hyprsunset was found.
+
+use crate::backend::hyprland::{
+    HyprsunsetVersion, detect_hyprsunset_version, is_version_compatible,
+};
+use crate::constants::{COMPATIBLE_HYPRSUNSET_VERSIONS, REQUIRED_HYPRSUNSET_VERSION};
+use crate::logger::Log;
+use anyhow::Result;
+
+/// Handle the --check-hyprsunset command.
+pub fn handle_check_hyprsunset_command(debug_enabled: bool) -> Result<()> {
+    Log::log_version();
+
+    let detected = detect_hyprsunset_version();
+    if debug_enabled {
+        Log::log_pipe();
+        Log::log_debug(&format!("Detection result: {:?}", detected));
+    }
+
+    crate::json::field(
+        "installed",
+        !matches!(detected, HyprsunsetVersion::NotInstalled),
+    );
+    crate::json::field(
+        "version",
+        match &detected {
+            HyprsunsetVersion::Found(version) => Some(version.clone()),
+            _ => None,
+        },
+    );
+    crate::json::field("required", REQUIRED_HYPRSUNSET_VERSION);
+
+    Log::log_block_start("hyprsunset compatibility:");
+    match &detected {
+        HyprsunsetVersion::Found(version) => {
+            Log::log_indented(&format!("Found version:     {}", version))
+        }
+        HyprsunsetVersion::Unknown => Log::log_indented("Found version:     unknown"),
+        HyprsunsetVersion::NotInstalled => Log::log_indented("Found version:     not installed"),
+    }
+    Log::log_indented(&format!(
+        "Required minimum:  {}",
+        REQUIRED_HYPRSUNSET_VERSION
+    ));
+    Log::log_indented(&format!(
+        "Known compatible:  {}",
+        COMPATIBLE_HYPRSUNSET_VERSIONS.join(", ")
+    ));
+
+    match detected {
+        HyprsunsetVersion::Found(version) if is_version_compatible(&version) => {
+            Log::log_block_start(&format!("hyprsunset {} is compatible", version));
+            Log::log_end();
+            Ok(())
+        }
+        HyprsunsetVersion::Found(version) => {
+            Log::log_pipe();
+            anyhow::bail!(
+                "hyprsunset {} is not compatible, please update to {} or newer",
+                version,
+                REQUIRED_HYPRSUNSET_VERSION
+            )
+        }
+        HyprsunsetVersion::Unknown => {
+            Log::log_pipe();
+            anyhow::bail!(
+                "hyprsunset is installed but `hyprsunset --version` didn't report a version, \
+                which usually means it is older than {}",
+                REQUIRED_HYPRSUNSET_VERSION
+            )
+        }
+        HyprsunsetVersion::NotInstalled => {
+            Log::log_pipe();
+            anyhow::bail!("hyprsunset is not installed or not on the PATH")
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! Implementation of the --dump-gamma command.
+//!
+//! Builds the gamma ramp the Wayland backend would send for a temperature and gamma
+//! and prints it as CSV (`index,red,green,blue` with 16-bit values) on stdout, without
+//! connecting to a compositor or touching any display. Ramps dumped this way can be
+//! diffed across versions or plotted to check the curve shape, e.g. that temperatures
+//! above 6500K lower red rather than raising blue.
+//!
+//! The ramp uses a neutral whitepoint and channel gamma, so `day_whitepoint` and the
+//! `*_gamma_r/g/b` multipliers from the config are not applied.
+//!
+//! With `--json` the ramp is part of the result object instead, as `red`, `green`
+//! and `blue` arrays.
+
+use crate::backend::wayland::gamma::{GammaTableFormat, create_gamma_tables};
+use crate::commands::test::{validate_gamma, validate_temperature};
+use crate::constants::{MAXIMUM_DUMP_GAMMA_SIZE, MINIMUM_DUMP_GAMMA_SIZE};
+use anyhow::Result;
+use std::io::Write;
+
+/// Handle the --dump-gamma command, writing the ramp for `temperature` and `gamma` to stdout.
+pub fn handle_dump_gamma_command(
+    temperature: u32,
+    gamma: f32,
+    size: usize,
+    debug_enabled: bool,
+) -> Result<()> {
+    if crate::json::is_enabled() {
+        let rows = gamma_ramp_rows(temperature, gamma, size)?;
+        crate::json::field("temperature", temperature);
+        crate::json::field("gamma", gamma);
+        crate::json::field("size", size);
+        for (key, c) in [("red", 0), ("green", 1), ("blue", 2)] {
+            crate::json::field(key, rows.iter().map(|row| row[c]).collect::<Vec<_>>());
+        }
+        return Ok(());
+    }
+
+    let csv = gamma_ramp_csv(temperature, gamma, size)?;
+
+    if debug_enabled {
+        // Keep stdout clean for redirection
+        eprintln!(
+            "Dumping {} entry gamma ramp for {}K @ {}%",
+            size, temperature, gamma
+        );
+    }
+
+    let mut stdout = std::io::stdout().lock();
+    stdout.write_all(csv.as_bytes())?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Build the CSV for a ramp of `size` entries per channel.
+///
+/// `gamma` is a percentage, as accepted by `--test` and the config.
+pub fn gamma_ramp_csv(temperature: u32, gamma: f32, size: usize) -> Result<String> {
+    let rows = gamma_ramp_rows(temperature, gamma, size)?;
+
+    let mut csv = String::with_capacity(size * 24);
+    csv.push_str("index,red,green,blue\n");
+    for (i, [red, green, blue]) in rows.iter().enumerate() {
+        csv.push_str(&format!("{},{},{},{}\n", i, red, green, blue));
+    }
+
+    Ok(csv)
+}
+
+/// Build a ramp of `size` entries as `[red, green, blue]` rows.
+fn gamma_ramp_rows(temperature: u32, gamma: f32, size: usize) -> Result<Vec<[u16; 3]>> {
+    validate_temperature(temperature)?;
+    validate_gamma(gamma)?;
+    if !(MINIMUM_DUMP_GAMMA_SIZE..=MAXIMUM_DUMP_GAMMA_SIZE).contains(&size) {
+        anyhow::bail!(
+            "Ramp size {} is out of range ({}-{})",
+            size,
+            MINIMUM_DUMP_GAMMA_SIZE,
+            MAXIMUM_DUMP_GAMMA_SIZE
+        );
+    }
+
+    let data = create_gamma_tables(
+        size,
+        temperature,
+        gamma / 100.0,
+        (1.0, 1.0, 1.0),
+        (1.0, 1.0, 1.0),
+        GammaTableFormat::default(),
+        false,
+    )?;
+    let channel = |c: usize, i: usize| {
+        let offset = (c * size + i) * 2;
+        u16::from_le_bytes([data[offset], data[offset + 1]])
+    };
+
+    Ok((0..size)
+        .map(|i| [channel(0, i), channel(1, i), channel(2, i)])
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_row(csv: &str) -> Vec<u32> {
+        csv.lines()
+            .last()
+            .unwrap()
+            .split(',')
+            .map(|v| v.parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_gamma_ramp_csv_layout() {
+        let csv = gamma_ramp_csv(6500, 100.0, 256).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines[0], "index,red,green,blue");
+        assert_eq!(lines.len(), 257);
+        assert_eq!(lines[1], "0,0,0,0");
+
+        // 6500K at full gamma is (close to) the identity ramp
+        let last = last_row(&csv);
+        assert_eq!(last[0], 255);
+        assert!(last[1..].iter().all(|&v| v > 65000));
+    }
+
+    #[test]
+    fn test_gamma_ramp_csv_colour_balance() {
+        // Warm ramps cut blue, cool ramps cut red
+        let warm = last_row(&gamma_ramp_csv(3300, 100.0, 256).unwrap());
+        assert!(warm[1] > warm[2] && warm[2] > warm[3]);
+
+        let cool = last_row(&gamma_ramp_csv(8000, 100.0, 256).unwrap());
+        assert!(cool[3] > cool[1]);
+    }
+
+    #[test]
+    fn test_gamma_ramp_csv_rejects_bad_values() {
+        assert!(gamma_ramp_csv(500, 100.0, 256).is_err());
+        assert!(gamma_ramp_csv(6500, 150.0, 256).is_err());
+        assert!(gamma_ramp_csv(6500, 100.0, 1).is_err());
+        assert!(gamma_ramp_csv(6500, 100.0, 70000).is_err());
+    }
+}
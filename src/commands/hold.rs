@@ -0,0 +1,204 @@
+//! Implementation of the --hold command.
+//!
+//! This command asks a running sunsetr process to show fixed temperature and
+//! gamma values for a number of minutes ("force warm for the next 2 hours"),
+//! after which it transitions back to the scheduled state on its own. Unlike
+//! --test the hold needs no terminal to stay open, and unlike --nudge it ignores
+//! the schedule completely while active. Config reloads keep the hold; it only
+//! ends early with `--hold release`. The request is passed via a temp file and
+//! delivered with SIGUSR1, like --profile and --nudge.
+
+use crate::constants::{
+    MAXIMUM_GAMMA, MAXIMUM_HOLD_MINUTES, MAXIMUM_TEMP, MINIMUM_GAMMA, MINIMUM_TEMP,
+};
+use crate::logger::Log;
+use anyhow::Result;
+
+/// What `--hold` asks the running instance to do.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HoldCommand {
+    /// Show these values for `minutes`, then return to the schedule
+    Start {
+        temperature: u32,
+        gamma: f32,
+        minutes: u32,
+    },
+    /// End the active hold now
+    Release,
+}
+
+impl HoldCommand {
+    /// Serialize for the hold request file.
+    fn to_request(self) -> String {
+        match self {
+            HoldCommand::Start {
+                temperature,
+                gamma,
+                minutes,
+            } => format!("{}\n{}\n{}", temperature, gamma, minutes),
+            HoldCommand::Release => "release".to_string(),
+        }
+    }
+
+    /// Parse the content of a hold request file.
+    pub fn from_request(content: &str) -> Option<Self> {
+        let content = content.trim();
+        if content == "release" {
+            return Some(HoldCommand::Release);
+        }
+        let mut lines = content.lines();
+        let temperature = lines.next()?.trim().parse().ok()?;
+        let gamma = lines.next()?.trim().parse().ok()?;
+        let minutes = lines.next()?.trim().parse().ok()?;
+        lines.next().is_none().then_some(HoldCommand::Start {
+            temperature,
+            gamma,
+            minutes,
+        })
+    }
+}
+
+/// Path of the hold request file for the given sunsetr PID.
+pub fn hold_file_path(pid: u32) -> Result<String> {
+    Ok(crate::utils::runtime_dir()?
+        .join(format!("sunsetr-hold-{}.tmp", pid))
+        .to_string_lossy()
+        .into_owned())
+}
+
+/// Check the values of a hold request against the supported ranges.
+pub fn validate_hold(command: HoldCommand) -> Result<()> {
+    if let HoldCommand::Start {
+        temperature,
+        gamma,
+        minutes,
+    } = command
+    {
+        if !(MINIMUM_TEMP..=MAXIMUM_TEMP).contains(&temperature) {
+            anyhow::bail!(
+                "Temperature must be between {}K and {}K (got {}K)",
+                MINIMUM_TEMP,
+                MAXIMUM_TEMP,
+                temperature
+            );
+        }
+        if !(MINIMUM_GAMMA..=MAXIMUM_GAMMA).contains(&gamma) {
+            anyhow::bail!(
+                "Gamma must be between {}% and {}% (got {}%)",
+                MINIMUM_GAMMA,
+                MAXIMUM_GAMMA,
+                gamma
+            );
+        }
+        if !(1..=MAXIMUM_HOLD_MINUTES).contains(&minutes) {
+            anyhow::bail!(
+                "Hold duration must be between 1 and {} minutes (got {})",
+                MAXIMUM_HOLD_MINUTES,
+                minutes
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Handle the --hold command to pin the running instance's values for a while.
+pub fn handle_hold_command(command: HoldCommand, debug_enabled: bool) -> Result<()> {
+    Log::log_version();
+
+    if let Err(e) = validate_hold(command) {
+        Log::log_pipe();
+        return Err(e);
+    }
+
+    let pid = match crate::utils::get_running_sunsetr_pid() {
+        Ok(pid) => pid,
+        Err(_) => {
+            Log::log_pipe();
+            anyhow::bail!("sunsetr is not running. A hold only applies to a running instance.");
+        }
+    };
+
+    let hold_file = hold_file_path(pid)?;
+    std::fs::write(&hold_file, command.to_request())?;
+
+    if debug_enabled {
+        Log::log_pipe();
+        Log::log_debug(&format!(
+            "Wrote hold request to {} and sending SIGUSR1 to PID {}",
+            hold_file, pid
+        ));
+    }
+
+    match nix::sys::signal::kill(
+        nix::unistd::Pid::from_raw(pid as i32),
+        nix::sys::signal::Signal::SIGUSR1,
+    ) {
+        Ok(_) => {
+            crate::json::field("pid", pid);
+            match command {
+                HoldCommand::Start {
+                    temperature,
+                    gamma,
+                    minutes,
+                } => {
+                    crate::json::field("temperature", temperature);
+                    crate::json::field("gamma", gamma);
+                    crate::json::field("minutes", minutes);
+                    Log::log_block_start(&format!(
+                        "Holding {}K @ {}% for {} minutes",
+                        temperature, gamma, minutes
+                    ));
+                }
+                HoldCommand::Release => {
+                    crate::json::field("released", true);
+                    Log::log_block_start("Releasing the active hold");
+                }
+            }
+        }
+        Err(e) => {
+            let _ = std::fs::remove_file(&hold_file);
+            Log::log_pipe();
+            anyhow::bail!("Failed to signal sunsetr (PID: {}): {}", pid, e);
+        }
+    }
+
+    Log::log_end();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hold_request_round_trip() {
+        let start = HoldCommand::Start {
+            temperature: 3000,
+            gamma: 90.5,
+            minutes: 120,
+        };
+        assert_eq!(HoldCommand::from_request(&start.to_request()), Some(start));
+        assert_eq!(
+            HoldCommand::from_request(&HoldCommand::Release.to_request()),
+            Some(HoldCommand::Release)
+        );
+        assert_eq!(HoldCommand::from_request("3000\n90"), None);
+        assert_eq!(HoldCommand::from_request("3000\n90\n120\n5"), None);
+        assert_eq!(HoldCommand::from_request("warm"), None);
+    }
+
+    #[test]
+    fn test_validate_hold() {
+        let hold = |temperature, gamma, minutes| HoldCommand::Start {
+            temperature,
+            gamma,
+            minutes,
+        };
+        assert!(validate_hold(hold(3000, 90.0, 120)).is_ok());
+        assert!(validate_hold(HoldCommand::Release).is_ok());
+        assert!(validate_hold(hold(MINIMUM_TEMP - 1, 90.0, 120)).is_err());
+        assert!(validate_hold(hold(3000, MAXIMUM_GAMMA + 1.0, 120)).is_err());
+        assert!(validate_hold(hold(3000, 90.0, 0)).is_err());
+        assert!(validate_hold(hold(3000, 90.0, MAXIMUM_HOLD_MINUTES + 1)).is_err());
+    }
+}
@@ -0,0 +1,65 @@
+//! Implementation of the --lint command.
+//!
+//! Validates a configuration file without applying anything and reports every
+//! problem found: syntax errors, unknown keys, out-of-range values, invalid
+//! profiles, overlapping transitions and geo mode without coordinates. The command
+//! exits with a non-zero status when any error is present, which makes it suitable
+//! for pre-commit hooks in dotfiles repositories that version `sunsetr.toml`.
+//!
+//! Checks are shared with normal config loading (see [`crate::config::lint_config`]),
+//! so anything `--lint` accepts will also be accepted at startup.
+
+use crate::config::{Config, lint_config};
+use crate::logger::Log;
+use anyhow::Result;
+use std::path::PathBuf;
+
+/// Handle the --lint command, checking `path` or the active config file.
+pub fn handle_lint_command(path: Option<&str>, debug_enabled: bool) -> Result<()> {
+    Log::log_version();
+
+    let path = match path {
+        Some(path) => PathBuf::from(path),
+        None => Config::get_config_path()?,
+    };
+
+    if debug_enabled {
+        Log::log_pipe();
+        Log::log_debug(&format!("Linting {}", path.display()));
+    }
+
+    Log::log_block_start(&format!(
+        "Checking {}",
+        crate::utils::path_for_display(&path)
+    ));
+    let report = lint_config(&path);
+    crate::json::field("path", path.display().to_string());
+    crate::json::field("errors", report.errors.clone());
+    crate::json::field("warnings", report.warnings.clone());
+
+    for warning in &report.warnings {
+        Log::log_pipe();
+        Log::log_warning(warning);
+    }
+    for error in &report.errors {
+        Log::log_pipe();
+        Log::log_error(error);
+    }
+
+    if !report.errors.is_empty() {
+        Log::log_pipe();
+        anyhow::bail!(
+            "Found {} error(s) and {} warning(s) in {}",
+            report.errors.len(),
+            report.warnings.len(),
+            path.display()
+        );
+    }
+
+    Log::log_block_start(&format!(
+        "Configuration is valid ({} warning(s))",
+        report.warnings.len()
+    ));
+    Log::log_end();
+    Ok(())
+}
@@ -0,0 +1,227 @@
+//! Implementation of the --migrate command.
+//!
+//! Moves a configuration from the legacy `~/.config/hypr/` directory to
+//! `~/.config/sunsetr/`, taking `geo.toml` along since it is always read from
+//! the directory holding `sunsetr.toml`. Nothing is deleted: each legacy file is
+//! renamed to a `.bak` backup, and a file already at the new location is backed
+//! up before being replaced. Afterwards only the new location is in use, so the
+//! conflict prompt for configs in both locations no longer appears at startup.
+
+use crate::logger::Log;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Files moved together from the legacy directory, the config itself first.
+const MIGRATED_FILES: &[&str] = &["sunsetr.toml", "geo.toml"];
+
+/// One file moved by [`migrate_config_dir`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigratedFile {
+    /// Where the file now lives
+    pub path: PathBuf,
+    /// Backup of the legacy original
+    pub legacy_backup: PathBuf,
+    /// Backup of a file that was already at the new location, if any
+    pub replaced_backup: Option<PathBuf>,
+}
+
+/// Handle the --migrate command for the user's config directory.
+pub fn handle_migrate_command(debug_enabled: bool) -> Result<()> {
+    Log::log_version();
+
+    let config_dir = dirs::config_dir().context("Could not determine config directory")?;
+    if debug_enabled {
+        Log::log_pipe();
+        Log::log_debug(&format!("Config directory: {}", config_dir.display()));
+    }
+
+    if crate::utils::is_safe_mode() {
+        Log::log_block_start("Safe mode: not moving any configuration files");
+        Log::log_end();
+        return Ok(());
+    }
+
+    let migrated = migrate_config_dir(&config_dir)?;
+    crate::json::field(
+        "migrated",
+        migrated
+            .iter()
+            .map(|file| file.path.display().to_string())
+            .collect::<Vec<_>>(),
+    );
+    if migrated.is_empty() {
+        Log::log_block_start(&format!(
+            "No legacy configuration found at {}, nothing to migrate",
+            crate::utils::path_for_display(&legacy_dir(&config_dir).join("sunsetr.toml"))
+        ));
+        Log::log_end();
+        return Ok(());
+    }
+
+    for file in &migrated {
+        Log::log_block_start(&format!(
+            "Moved to {}",
+            crate::utils::path_for_display(&file.path)
+        ));
+        Log::log_indented(&format!(
+            "Original backed up as {}",
+            crate::utils::path_for_display(&file.legacy_backup)
+        ));
+        if let Some(ref replaced) = file.replaced_backup {
+            Log::log_indented(&format!(
+                "Previous file backed up as {}",
+                crate::utils::path_for_display(replaced)
+            ));
+        }
+    }
+
+    Log::log_block_start(&format!(
+        "sunsetr now uses {}",
+        crate::utils::path_for_display(&migrated[0].path)
+    ));
+    Log::log_indented("Run `sunsetr --reload` to apply it to a running instance");
+    Log::log_end();
+    Ok(())
+}
+
+/// Move the legacy config in `config_dir` (and its `geo.toml`) to the new location.
+///
+/// Returns the files that were moved, which is empty when there is no legacy
+/// `sunsetr.toml`.
+pub fn migrate_config_dir(config_dir: &Path) -> Result<Vec<MigratedFile>> {
+    let old_dir = legacy_dir(config_dir);
+    if !old_dir.join(MIGRATED_FILES[0]).exists() {
+        return Ok(Vec::new());
+    }
+
+    let new_dir = config_dir.join("sunsetr");
+    fs::create_dir_all(&new_dir)
+        .with_context(|| format!("Failed to create {}", new_dir.display()))?;
+
+    let mut migrated = Vec::new();
+    for file_name in MIGRATED_FILES {
+        let old_path = old_dir.join(file_name);
+        if !old_path.exists() {
+            continue;
+        }
+        let new_path = new_dir.join(file_name);
+
+        let replaced_backup = if new_path.exists() {
+            let backup = backup_path(&new_path);
+            fs::rename(&new_path, &backup).with_context(|| {
+                format!("Failed to back up {} before migrating", new_path.display())
+            })?;
+            Some(backup)
+        } else {
+            None
+        };
+
+        // Copy first so the original stays in place if anything goes wrong
+        fs::copy(&old_path, &new_path).with_context(|| {
+            format!(
+                "Failed to copy {} to {}",
+                old_path.display(),
+                new_path.display()
+            )
+        })?;
+
+        let legacy_backup = backup_path(&old_path);
+        fs::rename(&old_path, &legacy_backup)
+            .with_context(|| format!("Failed to back up {}", old_path.display()))?;
+
+        migrated.push(MigratedFile {
+            path: new_path,
+            legacy_backup,
+            replaced_backup,
+        });
+    }
+
+    Ok(migrated)
+}
+
+/// The legacy config directory inside `config_dir`.
+fn legacy_dir(config_dir: &Path) -> PathBuf {
+    config_dir.join("hypr")
+}
+
+/// First unused backup name for `path`: `<name>.bak`, then `<name>.bak.1`, and so on.
+fn backup_path(path: &Path) -> PathBuf {
+    let base = format!("{}.bak", path.display());
+    std::iter::once(PathBuf::from(&base))
+        .chain((1..).map(|n| PathBuf::from(format!("{}.{}", base, n))))
+        .find(|candidate| !candidate.exists())
+        .expect("An unused backup name always exists")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_migrate_moves_config_and_geo() {
+        let dir = tempdir().unwrap();
+        let hypr = dir.path().join("hypr");
+        fs::create_dir_all(&hypr).unwrap();
+        fs::write(hypr.join("sunsetr.toml"), "night_temp = 3000\n").unwrap();
+        fs::write(hypr.join("geo.toml"), "latitude = 40.0\n").unwrap();
+        // Unrelated Hyprland files stay where they are
+        fs::write(hypr.join("hyprland.conf"), "").unwrap();
+
+        let migrated = migrate_config_dir(dir.path()).unwrap();
+        assert_eq!(migrated.len(), 2);
+
+        let new_dir = dir.path().join("sunsetr");
+        assert_eq!(
+            fs::read_to_string(new_dir.join("sunsetr.toml")).unwrap(),
+            "night_temp = 3000\n"
+        );
+        assert_eq!(
+            fs::read_to_string(new_dir.join("geo.toml")).unwrap(),
+            "latitude = 40.0\n"
+        );
+        assert!(!hypr.join("sunsetr.toml").exists());
+        assert!(hypr.join("sunsetr.toml.bak").exists());
+        assert!(hypr.join("geo.toml.bak").exists());
+        assert!(hypr.join("hyprland.conf").exists());
+        assert_eq!(migrated[0].replaced_backup, None);
+
+        // Running again finds nothing left to migrate
+        assert!(migrate_config_dir(dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_migrate_backs_up_existing_new_config() {
+        let dir = tempdir().unwrap();
+        let hypr = dir.path().join("hypr");
+        let new_dir = dir.path().join("sunsetr");
+        fs::create_dir_all(&hypr).unwrap();
+        fs::create_dir_all(&new_dir).unwrap();
+        fs::write(hypr.join("sunsetr.toml"), "legacy").unwrap();
+        fs::write(hypr.join("sunsetr.toml.bak"), "older backup").unwrap();
+        fs::write(new_dir.join("sunsetr.toml"), "new").unwrap();
+
+        let migrated = migrate_config_dir(dir.path()).unwrap();
+        assert_eq!(migrated.len(), 1);
+        assert_eq!(
+            fs::read_to_string(new_dir.join("sunsetr.toml")).unwrap(),
+            "legacy"
+        );
+        assert_eq!(
+            migrated[0].replaced_backup,
+            Some(new_dir.join("sunsetr.toml.bak"))
+        );
+        assert_eq!(
+            fs::read_to_string(new_dir.join("sunsetr.toml.bak")).unwrap(),
+            "new"
+        );
+
+        // Existing backups are never overwritten
+        assert_eq!(migrated[0].legacy_backup, hypr.join("sunsetr.toml.bak.1"));
+        assert_eq!(
+            fs::read_to_string(hypr.join("sunsetr.toml.bak")).unwrap(),
+            "older backup"
+        );
+    }
+}
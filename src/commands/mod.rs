@@ -1,10 +1,26 @@
 //! Command-line command handlers for sunsetr.
 //!
-//! This module contains implementations for one-shot CLI commands like --reload and --test.
+//! This module contains implementations for CLI commands like --reload, --test, --profile, --nudge,
+//! --hold, --monitor, --once, --lint, --migrate, --check-hyprsunset, --which-config, --select-config,
+//! --set-location, --dump-gamma, --bench and --calibrate.
 //! Each command is implemented in its own submodule to keep the code organized and maintainable.
 
+pub mod bench;
+pub mod calibrate;
+pub mod check_hyprsunset;
+pub mod dump_gamma;
+pub mod hold;
+pub mod lint;
+pub mod migrate;
+pub mod monitor;
+pub mod nudge;
+pub mod once;
+pub mod profile;
 pub mod reload;
+pub mod select_config;
+pub mod set_location;
 pub mod test;
+pub mod which_config;
 
 // Re-export from signals for backward compatibility (used by signals module)
 // pub use crate::signals::TestModeParams;
@@ -0,0 +1,262 @@
+//! Implementation of the --monitor command.
+//!
+//! Opens a small live dashboard for the running sunsetr instance: current state,
+//! applied temperature and gamma, a progress bar for the active transition and a
+//! countdown to the next event. Data comes from the status snapshot the running
+//! instance publishes (see [`crate::status`]), so nothing is recomputed here and
+//! runtime changes such as profile switches are reflected as they happen.
+//!
+//! The dashboard redraws in place once per second and exits on `q`, Escape or
+//! Ctrl+C, or when the monitored instance stops. The terminal is restored by
+//! `TerminalGuard` and raw mode is always disabled on the way out.
+//!
+//! With `--json` there is no dashboard: the current snapshot is read once and
+//! reported as the command's result.
+
+use crate::constants::{MONITOR_REFRESH_INTERVAL_MS, PROGRESS_BAR_WIDTH};
+use crate::logger::Log;
+use crate::status::{StatusSnapshot, read_status, unix_now};
+use crate::utils::{TerminalGuard, is_process_running};
+use anyhow::Result;
+use crossterm::{
+    ExecutableCommand,
+    cursor::MoveUp,
+    event::{self, Event, KeyCode, KeyModifiers},
+    style::Print,
+    terminal::{Clear, ClearType, disable_raw_mode, enable_raw_mode},
+};
+use std::io::{Write, stdout};
+use std::time::Duration;
+
+/// Why the monitor loop stopped.
+enum MonitorExit {
+    UserQuit,
+    InstanceStopped,
+}
+
+/// Handle the --monitor command to display live status of the running instance.
+pub fn handle_monitor_command(debug_enabled: bool) -> Result<()> {
+    Log::log_version();
+
+    let pid = match crate::utils::get_running_sunsetr_pid() {
+        Ok(pid) => pid,
+        Err(_) => {
+            Log::log_pipe();
+            anyhow::bail!("sunsetr is not running. Start it first, then run 'sunsetr --monitor'.");
+        }
+    };
+
+    if debug_enabled && let Ok(path) = crate::status::status_file_path(pid) {
+        Log::log_pipe();
+        Log::log_debug(&format!("Monitoring PID {} via {}", pid, path));
+    }
+
+    crate::json::field("pid", pid);
+    if crate::json::is_enabled() {
+        let snapshot = read_status(pid)?;
+        for (key, value) in snapshot.json_fields() {
+            crate::json::field(key, value);
+        }
+        return Ok(());
+    }
+
+    Log::log_block_start(&format!("Monitoring sunsetr (PID: {})", pid));
+    Log::log_indented("Press q or Escape to exit");
+
+    // Hides the cursor and suppresses echo; restored when dropped
+    let _term = TerminalGuard::new()?;
+
+    enable_raw_mode()?;
+    let result = run_monitor_loop(pid);
+    disable_raw_mode()?;
+
+    match result? {
+        MonitorExit::UserQuit => {}
+        MonitorExit::InstanceStopped => {
+            Log::log_pipe();
+            Log::log_warning("sunsetr is no longer running");
+        }
+    }
+
+    Log::log_end();
+    Ok(())
+}
+
+/// Redraw the dashboard every refresh interval until the user quits.
+fn run_monitor_loop(pid: u32) -> Result<MonitorExit> {
+    let mut stdout = stdout();
+    let refresh = Duration::from_millis(MONITOR_REFRESH_INTERVAL_MS);
+
+    let lines_drawn = loop {
+        if !is_process_running(pid) {
+            stdout.execute(Clear(ClearType::FromCursorDown))?;
+            return Ok(MonitorExit::InstanceStopped);
+        }
+
+        let lines = match read_status(pid) {
+            Ok(snapshot) => render_status_lines(&snapshot, unix_now()),
+            Err(_) => vec!["Waiting for sunsetr to publish its status...".to_string()],
+        };
+
+        stdout.execute(Clear(ClearType::FromCursorDown))?;
+        stdout.execute(Print("┃\r\n"))?;
+        for line in &lines {
+            stdout.execute(Print(format!("┃ {}\r\n", line)))?;
+        }
+        stdout.flush()?;
+
+        // Move back to the top of the dashboard so the next frame overwrites it
+        let lines_drawn = lines.len() as u16 + 1;
+        stdout.execute(MoveUp(lines_drawn))?;
+
+        if event::poll(refresh)?
+            && let Event::Key(key) = event::read()?
+        {
+            let quit = match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => true,
+                KeyCode::Char('c') => key.modifiers.contains(KeyModifiers::CONTROL),
+                _ => false,
+            };
+            if quit {
+                break lines_drawn;
+            }
+        }
+    };
+
+    // Leave the last frame on screen below the cursor
+    for _ in 0..lines_drawn {
+        stdout.execute(Print("\r\n"))?;
+    }
+    Ok(MonitorExit::UserQuit)
+}
+
+/// Format the dashboard lines for a status snapshot.
+///
+/// `now` is the current Unix timestamp, used for the countdown.
+fn render_status_lines(snapshot: &StatusSnapshot, now: u64) -> Vec<String> {
+    let state_label = match snapshot.state.as_str() {
+        "day" => "Day 󰖨 ",
+        "night" => "Night  ",
+        "sunset" => "Sunset 󰖛 ",
+        "sunrise" => "Sunrise 󰖜 ",
+        other => other,
+    };
+
+    let mut lines = vec![format!("State:       {}", state_label)];
+    lines.push(format!(
+        "Temperature: {}K   Gamma: {:.1}%",
+        snapshot.temperature, snapshot.gamma
+    ));
+
+    if let Some(nudge) = snapshot.nudge {
+        lines.push(format!("Nudge:       {:+}K until the next change", nudge));
+    }
+
+    if let Some(until) = snapshot.hold_until {
+        lines.push(format!(
+            "Hold:        {} left",
+            format_countdown(until.saturating_sub(now))
+        ));
+    }
+
+    if let Some(progress) = snapshot.progress {
+        lines.push(format!(
+            "Progress:    [{}] {}%",
+            progress_bar(progress),
+            (progress.clamp(0.0, 1.0) * 100.0) as u8
+        ));
+    }
+
+    let remaining = snapshot.seconds_until_next_event(now);
+    let next_label = if snapshot.progress.is_some() {
+        "Next update: "
+    } else {
+        "Next event:  "
+    };
+    lines.push(format!("{}in {}", next_label, format_countdown(remaining)));
+
+    if let Some(profile) = &snapshot.profile {
+        lines.push(format!("Profile:     {}", profile));
+    }
+    lines.push(format!("Backend:     {}", snapshot.backend));
+
+    lines
+}
+
+/// Build a progress bar in the same style as the startup transition.
+fn progress_bar(progress: f32) -> String {
+    let progress = progress.clamp(0.0, 1.0);
+    let filled = (PROGRESS_BAR_WIDTH as f32 * progress) as usize;
+    let empty = PROGRESS_BAR_WIDTH - filled;
+
+    if filled > 0 {
+        format!(
+            "{}>{}",
+            "=".repeat(filled.saturating_sub(1)),
+            " ".repeat(empty)
+        )
+    } else {
+        " ".repeat(PROGRESS_BAR_WIDTH)
+    }
+}
+
+/// Format a number of seconds as `1h 02m 03s`, omitting leading zero units.
+fn format_countdown(total_secs: u64) -> String {
+    let hours = total_secs / 3600;
+    let minutes = (total_secs % 3600) / 60;
+    let seconds = total_secs % 60;
+
+    if hours > 0 {
+        format!("{}h {:02}m {:02}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_status_lines() {
+        let snapshot = StatusSnapshot {
+            state: "sunset".to_string(),
+            progress: Some(0.5),
+            temperature: 4900,
+            gamma: 95.0,
+            next_event: 1_000_060,
+            profile: Some("relax".to_string()),
+            nudge: None,
+            hold_until: None,
+            backend: "wayland".to_string(),
+            updated: 1_000_000,
+        };
+
+        let lines = render_status_lines(&snapshot, 1_000_000);
+        assert!(lines[0].contains("Sunset"));
+        assert_eq!(lines[1], "Temperature: 4900K   Gamma: 95.0%");
+        assert!(lines[2].ends_with("] 50%"));
+        assert_eq!(progress_bar(0.5).len(), PROGRESS_BAR_WIDTH);
+        assert_eq!(lines[3], "Next update: in 1m 00s");
+        assert_eq!(lines[4], "Profile:     relax");
+
+        let nudged = StatusSnapshot {
+            nudge: Some(-300),
+            ..snapshot.clone()
+        };
+        let lines = render_status_lines(&nudged, 1_000_000);
+        assert_eq!(lines[2], "Nudge:       -300K until the next change");
+
+        let held = StatusSnapshot {
+            hold_until: Some(1_007_200),
+            ..snapshot
+        };
+        let lines = render_status_lines(&held, 1_000_000);
+        assert_eq!(lines[2], "Hold:        2h 00m 00s left");
+
+        assert_eq!(format_countdown(5), "5s");
+        assert_eq!(format_countdown(3723), "1h 02m 03s");
+    }
+}
@@ -0,0 +1,104 @@
+//! Implementation of the --nudge command.
+//!
+//! This command asks a running sunsetr process to shift the applied temperature
+//! by a number of Kelvin (negative is warmer), on top of the scheduled value. The
+//! nudge is never written to the config: it holds until the next day, night or
+//! transition change and then clears itself. Repeated nudges add up, and a nudge
+//! of 0 clears it right away. Like --profile, the request is passed via a file in
+//! the runtime directory and delivered with SIGUSR1, so it's easy to bind to a
+//! pair of hotkeys.
+
+use crate::constants::MAXIMUM_TEMPERATURE_NUDGE;
+use crate::logger::Log;
+use anyhow::Result;
+
+/// Path of the nudge request file for the given sunsetr PID.
+pub fn nudge_file_path(pid: u32) -> Result<String> {
+    Ok(crate::utils::runtime_dir()?
+        .join(format!("sunsetr-nudge-{}.tmp", pid))
+        .to_string_lossy()
+        .into_owned())
+}
+
+/// Handle the --nudge command to offset the running instance's temperature.
+pub fn handle_nudge_command(kelvin: i32, debug_enabled: bool) -> Result<()> {
+    Log::log_version();
+
+    if kelvin.abs() > MAXIMUM_TEMPERATURE_NUDGE {
+        Log::log_pipe();
+        anyhow::bail!(
+            "Nudge of {}K is out of range (-{}K to +{}K)",
+            kelvin,
+            MAXIMUM_TEMPERATURE_NUDGE,
+            MAXIMUM_TEMPERATURE_NUDGE
+        );
+    }
+
+    let pid = match crate::utils::get_running_sunsetr_pid() {
+        Ok(pid) => pid,
+        Err(_) => {
+            Log::log_pipe();
+            anyhow::bail!("sunsetr is not running. A nudge only applies to a running instance.");
+        }
+    };
+
+    let nudge_file = nudge_file_path(pid)?;
+    std::fs::write(&nudge_file, kelvin.to_string())?;
+
+    if debug_enabled {
+        Log::log_pipe();
+        Log::log_debug(&format!(
+            "Wrote nudge request to {} and sending SIGUSR1 to PID {}",
+            nudge_file, pid
+        ));
+    }
+
+    match nix::sys::signal::kill(
+        nix::unistd::Pid::from_raw(pid as i32),
+        nix::sys::signal::Signal::SIGUSR1,
+    ) {
+        Ok(_) => {
+            crate::json::field("pid", pid);
+            crate::json::field("kelvin", kelvin);
+            if kelvin == 0 {
+                Log::log_block_start("Clearing temperature nudge");
+            } else {
+                Log::log_block_start(&format!(
+                    "Nudging temperature by {:+}K until the next change",
+                    kelvin
+                ));
+            }
+        }
+        Err(e) => {
+            let _ = std::fs::remove_file(&nudge_file);
+            Log::log_pipe();
+            anyhow::bail!("Failed to signal sunsetr (PID: {}): {}", pid, e);
+        }
+    }
+
+    Log::log_end();
+    Ok(())
+}
+
+/// Parse the value given to `--nudge`: signed Kelvin (`-300`, `+300`) or `reset`.
+pub fn parse_nudge_value(value: &str) -> Option<i32> {
+    if value == "reset" {
+        return Some(0);
+    }
+    value.strip_prefix('+').unwrap_or(value).parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nudge_value() {
+        assert_eq!(parse_nudge_value("-300"), Some(-300));
+        assert_eq!(parse_nudge_value("+250"), Some(250));
+        assert_eq!(parse_nudge_value("400"), Some(400));
+        assert_eq!(parse_nudge_value("reset"), Some(0));
+        assert_eq!(parse_nudge_value("warmer"), None);
+        assert_eq!(parse_nudge_value("1.5"), None);
+    }
+}
@@ -0,0 +1,115 @@
+//! Implementation of the --once command.
+//!
+//! Applies the color temperature and gamma for the current time once and exits,
+//! without taking the lock or entering the main loop. This is meant for users who
+//! drive sunsetr from their own scheduler (cron, systemd timers, etc.).
+//!
+//! ## Persistence
+//!
+//! - **Hyprland**: values are held by hyprsunset. If sunsetr had to start
+//!   hyprsunset, the process is detached and left running so the values stick.
+//! - **Wayland**: wlr-gamma-control resets gamma as soon as the client that set it
+//!   disconnects, so the values only last while this command runs. A warning is
+//!   shown; use the normal daemon mode on Wayland compositors instead.
+
+use crate::backend::ColorTemperatureBackend;
+use crate::backend::hyprland::{HyprlandBackend, is_hyprsunset_running};
+use crate::backend::wayland::WaylandBackend;
+use crate::backend::{BackendType, detect_backend};
+use crate::config::Config;
+use crate::logger::Log;
+use crate::time_state::{get_initial_values_for_state, get_transition_state};
+use anyhow::Result;
+use std::sync::atomic::AtomicBool;
+
+/// Handle the --once command to apply the current state and exit.
+pub fn handle_once_command(debug_enabled: bool) -> Result<()> {
+    Log::log_version();
+
+    let mut config = Config::load()?;
+
+    // A running instance already manages gamma and would immediately override us
+    if let Ok(pid) = crate::utils::get_running_sunsetr_pid() {
+        Log::log_pipe();
+        anyhow::bail!(
+            "sunsetr is already running (PID: {}) and manages gamma itself.\n\
+            Use 'sunsetr --reload' or 'sunsetr --test' instead, or stop it first.",
+            pid
+        );
+    }
+
+    crate::appearance::init(&config, debug_enabled)?;
+
+    let backend_type = detect_backend(&config)?;
+    let state = get_transition_state(&config);
+    let (temp, gamma) = get_initial_values_for_state(state, &config);
+    let running = AtomicBool::new(true);
+    crate::json::field("backend", backend_type.name());
+    crate::json::field("state", crate::status::state_name(state));
+    crate::json::field("temperature", temp);
+    crate::json::field("gamma", gamma);
+
+    if debug_enabled {
+        Log::log_pipe();
+        Log::log_debug(&format!(
+            "One-shot apply using {} backend: {:?}",
+            backend_type.name(),
+            state
+        ));
+    }
+
+    match backend_type {
+        BackendType::Hyprland => {
+            // Talk to an already running hyprsunset rather than failing on the
+            // start_hyprsunset conflict check
+            if config.effective().start_hyprsunset
+                && is_hyprsunset_running(config.hyprsunset_socket.as_deref())
+            {
+                config.start_hyprsunset = Some(false);
+            }
+
+            let mut backend = HyprlandBackend::new(&config, debug_enabled)?;
+            backend.apply_transition_state(state, &config, &running)?;
+
+            Log::log_block_start(&format!("Applied {}K @ {:.1}%", temp, gamma));
+            if let Some(process) = backend.take_process() {
+                let pid = process.detach();
+                crate::json::field("hyprsunset_pid", pid);
+                Log::log_indented(&format!(
+                    "Left hyprsunset running (PID: {}) to hold these values",
+                    pid
+                ));
+            }
+        }
+        BackendType::Wayland => {
+            let mut backend = WaylandBackend::new(&config, debug_enabled)?;
+            backend.apply_transition_state(state, &config, &running)?;
+
+            Log::log_block_start(&format!("Applied {}K @ {:.1}%", temp, gamma));
+            Log::log_pipe();
+            Log::log_warning("Wayland gamma does not persist after sunsetr exits");
+            Log::log_indented(
+                "The compositor resets gamma when the client that set it disconnects",
+            );
+            Log::log_indented("Run sunsetr normally (without --once) to keep gamma applied");
+        }
+        #[cfg(feature = "drm")]
+        BackendType::Drm => {
+            let mut backend = crate::backend::drm::DrmBackend::new(&config, debug_enabled)?;
+            backend.apply_transition_state(state, &config, &running)?;
+
+            // Without cleanup, the kernel keeps the LUT after sunsetr exits
+            Log::log_block_start(&format!("Applied {}K @ {:.1}%", temp, gamma));
+        }
+        #[cfg(feature = "testing-support")]
+        BackendType::Mock => {
+            let mut backend = crate::backend::mock::MockBackend::new(debug_enabled);
+            backend.apply_transition_state(state, &config, &running)?;
+
+            Log::log_block_start(&format!("Applied {}K @ {:.1}%", temp, gamma));
+        }
+    }
+
+    Log::log_end();
+    Ok(())
+}
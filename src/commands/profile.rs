@@ -0,0 +1,98 @@
+//! Implementation of the --profile command.
+//!
+//! This command asks a running sunsetr process to switch to a named profile,
+//! or to cycle to the next profile when no name is given. The request is passed
+//! via a file in the runtime directory and delivered with SIGUSR1, mirroring how
+//! --test works. Bind it to a hotkey to switch between e.g. "work" and "relax" profiles.
+
+use crate::config::Config;
+use crate::constants::DEFAULT_PROFILE_NAME;
+use crate::logger::Log;
+use anyhow::Result;
+
+/// Path of the profile request file for the given sunsetr PID.
+pub fn profile_file_path(pid: u32) -> Result<String> {
+    Ok(crate::utils::runtime_dir()?
+        .join(format!("sunsetr-profile-{}.tmp", pid))
+        .to_string_lossy()
+        .into_owned())
+}
+
+/// Handle the --profile command to switch the running instance's profile.
+pub fn handle_profile_command(name: Option<&str>, debug_enabled: bool) -> Result<()> {
+    Log::log_version();
+
+    // Load and validate configuration first so unknown profiles fail fast
+    let config = Config::load()?;
+    let available = config.profile_names();
+
+    if let Some(name) = name
+        && !available.iter().any(|n| n == name)
+    {
+        Log::log_pipe();
+        anyhow::bail!(
+            "Profile '{}' is not defined. Available profiles: {}",
+            name,
+            available.join(", ")
+        );
+    }
+
+    if available.len() == 1 {
+        Log::log_pipe();
+        Log::log_warning(&format!(
+            "No profiles configured - only '{}' is available",
+            DEFAULT_PROFILE_NAME
+        ));
+        Log::log_indented("Add [profiles.<name>] sections to sunsetr.toml to define profiles");
+    }
+
+    let pid = match crate::utils::get_running_sunsetr_pid() {
+        Ok(pid) => pid,
+        Err(_) => {
+            Log::log_pipe();
+            anyhow::bail!(
+                "sunsetr is not running. Set active_profile in sunsetr.toml to choose \
+                the profile used at startup."
+            );
+        }
+    };
+
+    // Empty content requests cycling to the next profile
+    let profile_file = profile_file_path(pid)?;
+    std::fs::write(&profile_file, name.unwrap_or(""))?;
+
+    if debug_enabled {
+        Log::log_pipe();
+        Log::log_debug(&format!(
+            "Wrote profile request to {} and sending SIGUSR1 to PID {}",
+            profile_file, pid
+        ));
+    }
+
+    match nix::sys::signal::kill(
+        nix::unistd::Pid::from_raw(pid as i32),
+        nix::sys::signal::Signal::SIGUSR1,
+    ) {
+        Ok(_) => match name {
+            // The profile is null when cycling, since the running instance picks the next one
+            _ if crate::json::is_enabled() => {
+                crate::json::field("pid", pid);
+                crate::json::field("profile", name);
+            }
+            Some(name) => {
+                Log::log_block_start(&format!("Switching to profile: {}", name));
+            }
+            None => {
+                Log::log_block_start("Cycling to next profile");
+            }
+        },
+        Err(e) => {
+            let _ = std::fs::remove_file(&profile_file);
+            Log::log_pipe();
+            anyhow::bail!("Failed to signal sunsetr (PID: {}): {}", pid, e);
+        }
+    }
+
+    Log::log_end();
+    Ok(())
+}
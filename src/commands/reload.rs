@@ -37,11 +37,16 @@ pub fn handle_reload_command(debug_enabled: bool) -> Result<()> {
 
             match kill(Pid::from_raw(pid as i32), Signal::SIGUSR2) {
                 Ok(_) => {
+                    crate::json::field("pid", pid);
+                    crate::json::field("action", "signaled");
                     Log::log_decorated(&format!("Sent reload signal to sunsetr (PID: {})", pid));
                     Log::log_indented("Existing process will reload configuration");
                 }
                 Err(e) => {
                     Log::log_error(&format!("Failed to signal existing process: {}", e));
+                    if crate::json::is_enabled() {
+                        anyhow::bail!("Failed to signal existing process: {}", e);
+                    }
                 }
             }
         }
@@ -56,10 +61,9 @@ pub fn handle_reload_command(debug_enabled: bool) -> Result<()> {
             #[cfg(debug_assertions)]
             eprintln!("DEBUG: Cleaning up stale lock file");
 
-            let runtime_dir =
-                std::env::var("XDG_RUNTIME_DIR").unwrap_or_else(|_| "/tmp".to_string());
-            let lock_path = format!("{}/sunsetr.lock", runtime_dir);
-            let _ = std::fs::remove_file(&lock_path);
+            if let Ok(lock_path) = crate::utils::lock_file_path() {
+                let _ = std::fs::remove_file(&lock_path);
+            }
 
             if debug_enabled {
                 Log::log_pipe();
@@ -70,7 +74,8 @@ pub fn handle_reload_command(debug_enabled: bool) -> Result<()> {
             #[cfg(debug_assertions)]
             eprintln!("DEBUG: Checking for orphaned hyprsunset processes");
 
-            if crate::backend::hyprland::is_hyprsunset_running() {
+            if crate::backend::hyprland::is_hyprsunset_running(config.hyprsunset_socket.as_deref())
+            {
                 Log::log_pipe();
                 Log::log_warning(
                     "hyprsunset is already running but start_hyprsunset is enabled in config.",
@@ -99,14 +104,17 @@ pub fn handle_reload_command(debug_enabled: bool) -> Result<()> {
             eprintln!("DEBUG: About to call spawn_background_process()");
 
             crate::utils::spawn_background_process(debug_enabled)?;
+            crate::json::field("action", "started");
             Log::log_decorated("New sunsetr instance started");
 
             // Wait for Wayland reset to complete and log result
             match wayland_handle.join() {
                 Ok(Ok(())) => {
+                    crate::json::field("gamma_reset", true);
                     Log::log_decorated("Wayland gamma reset completed");
                 }
                 Ok(Err(e)) => {
+                    crate::json::field("gamma_reset", false);
                     Log::log_warning(&format!("Wayland reset skipped: {}", e));
                 }
                 Err(_) => {
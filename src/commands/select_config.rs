@@ -0,0 +1,181 @@
+//! Implementation of the --select-config command.
+//!
+//! Lists the `*.toml` files in the sunsetr config directory (`geo.toml` aside) and
+//! lets the user pick the one sunsetr should load, with the same dropdown as the
+//! new-vs-legacy conflict prompt. A name on the command line (`work` or `work.toml`)
+//! skips the dropdown, which suits hotkeys and scripts.
+//!
+//! The choice is remembered in `.selected-config` next to the files, so every later
+//! run (and `--reload`) loads it until another file is picked. Picking `sunsetr.toml`
+//! forgets the selection. A running instance is sent SIGUSR2 to switch right away.
+
+use crate::config::{self, Config, lint_config};
+use crate::logger::Log;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Handle the --select-config command.
+pub fn handle_select_config_command(name: Option<&str>, debug_enabled: bool) -> Result<()> {
+    Log::log_version();
+
+    let dir = dirs::config_dir()
+        .context("Could not determine config directory")?
+        .join("sunsetr");
+    let candidates = config::config_candidates(&dir)?;
+    if candidates.is_empty() {
+        Log::log_pipe();
+        anyhow::bail!(
+            "No config files found in {}",
+            crate::utils::path_for_display(&dir)
+        );
+    }
+
+    let current = Config::locate_config()?.path;
+    if debug_enabled {
+        Log::log_pipe();
+        Log::log_debug(&format!(
+            "Found {} config file(s), current: {}",
+            candidates.len(),
+            current.display()
+        ));
+    }
+
+    let chosen = match name {
+        Some(name) => find_candidate(&candidates, name).ok_or_else(|| {
+            Log::log_pipe();
+            anyhow::anyhow!(
+                "No config named '{}' in {}. Available: {}",
+                name,
+                crate::utils::path_for_display(&dir),
+                candidates
+                    .iter()
+                    .map(|path| file_name(path))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })?,
+        None if crate::json::is_enabled() => {
+            anyhow::bail!("--select-config needs a file name with --json")
+        }
+        None => {
+            let options: Vec<(String, PathBuf)> = candidates
+                .iter()
+                .map(|path| {
+                    let label = if *path == current {
+                        format!("{} (current)", file_name(path))
+                    } else {
+                        file_name(path)
+                    };
+                    (label, path.clone())
+                })
+                .collect();
+            let initial = candidates
+                .iter()
+                .position(|path| *path == current)
+                .unwrap_or(0);
+            let index = crate::utils::show_dropdown_menu_at(
+                &options,
+                initial,
+                Some("Select the config file to use:"),
+                Some("Operation cancelled. The current config stays in use."),
+            )?;
+            options[index].1.clone()
+        }
+    };
+
+    // Refuse a file that wouldn't load, rather than leave the next start broken
+    let report = lint_config(&chosen);
+    if let Some(error) = report.errors.first() {
+        Log::log_pipe();
+        anyhow::bail!(
+            "{} has problems, keeping the current config: {}",
+            crate::utils::path_for_display(&chosen),
+            error
+        );
+    }
+
+    crate::json::field("config", chosen.display().to_string());
+    crate::json::field("reloaded", false);
+
+    if crate::utils::is_safe_mode() {
+        Log::log_block_start(&format!(
+            "Safe mode: not remembering {} as the config to use",
+            file_name(&chosen)
+        ));
+        Log::log_end();
+        return Ok(());
+    }
+
+    let default = chosen
+        .file_name()
+        .is_some_and(|name| name == "sunsetr.toml");
+    let chosen_name = file_name(&chosen);
+    config::save_selected_config(&dir, (!default).then_some(chosen_name.as_str()))?;
+    Log::log_block_start(&format!(
+        "Using {}",
+        crate::utils::path_for_display(&chosen)
+    ));
+
+    let Ok(pid) = crate::utils::get_running_sunsetr_pid() else {
+        Log::log_indented("It is loaded the next time sunsetr starts");
+        Log::log_end();
+        return Ok(());
+    };
+
+    if debug_enabled {
+        Log::log_pipe();
+        Log::log_debug(&format!("Sending SIGUSR2 to PID {} to reload", pid));
+    }
+
+    match nix::sys::signal::kill(
+        nix::unistd::Pid::from_raw(pid as i32),
+        nix::sys::signal::Signal::SIGUSR2,
+    ) {
+        Ok(_) => {
+            crate::json::field("pid", pid);
+            crate::json::field("reloaded", true);
+            Log::log_decorated(&format!("Sent reload signal to sunsetr (PID: {})", pid));
+        }
+        Err(e) => {
+            Log::log_warning(&format!("Failed to signal existing process: {}", e));
+            Log::log_indented("You may need to run 'sunsetr --reload' manually.");
+        }
+    }
+
+    Log::log_end();
+    Ok(())
+}
+
+/// The candidate called `name`, with or without its `.toml` extension.
+fn find_candidate(candidates: &[PathBuf], name: &str) -> Option<PathBuf> {
+    candidates
+        .iter()
+        .find(|path| {
+            path.file_name().is_some_and(|n| n == name)
+                || path.file_stem().is_some_and(|stem| stem == name)
+        })
+        .cloned()
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_candidate() {
+        let candidates = vec![
+            PathBuf::from("/cfg/sunsetr.toml"),
+            PathBuf::from("/cfg/work.toml"),
+        ];
+        let work = Some(PathBuf::from("/cfg/work.toml"));
+        assert_eq!(find_candidate(&candidates, "work"), work);
+        assert_eq!(find_candidate(&candidates, "work.toml"), work);
+        assert_eq!(find_candidate(&candidates, "relax"), None);
+    }
+}
@@ -0,0 +1,71 @@
+//! Implementation of the --set-location command.
+//!
+//! This is the non-interactive counterpart of --geo: the coordinates are given on
+//! the command line instead of picked from the city selector, so it works without
+//! a TTY (dotfile bootstraps, install scripts). The coordinates are written to the
+//! config (or geo.toml when present) with `transition_mode = "geo"`, and a running
+//! instance is sent SIGUSR2 so it reloads and transitions smoothly to the new times.
+
+use crate::logger::Log;
+use anyhow::Result;
+
+/// Handle the --set-location command to pin geo coordinates without the city selector.
+pub fn handle_set_location_command(
+    latitude: f64,
+    longitude: f64,
+    debug_enabled: bool,
+) -> Result<()> {
+    Log::log_version();
+
+    if let Err(e) = crate::geo::solar::validate_coordinates(latitude, longitude) {
+        Log::log_pipe();
+        return Err(e);
+    }
+
+    let location = format!("{:.4}, {:.4}", latitude, longitude);
+    crate::geo::handle_config_update_with_coordinates(latitude, longitude, &location)?;
+    crate::json::field("latitude", latitude);
+    crate::json::field("longitude", longitude);
+    crate::json::field("reloaded", false);
+
+    if crate::utils::is_safe_mode() {
+        Log::log_end();
+        return Ok(());
+    }
+
+    let pid = match crate::utils::get_running_sunsetr_pid() {
+        Ok(pid) => pid,
+        Err(_) => {
+            if debug_enabled {
+                Log::log_pipe();
+                Log::log_debug("No running instance, the new location applies on next start");
+            }
+            Log::log_end();
+            return Ok(());
+        }
+    };
+
+    if debug_enabled {
+        Log::log_pipe();
+        Log::log_debug(&format!("Sending SIGUSR2 to PID {} to reload", pid));
+    }
+
+    match nix::sys::signal::kill(
+        nix::unistd::Pid::from_raw(pid as i32),
+        nix::sys::signal::Signal::SIGUSR2,
+    ) {
+        Ok(_) => {
+            crate::json::field("pid", pid);
+            crate::json::field("reloaded", true);
+            Log::log_decorated(&format!("Sent reload signal to sunsetr (PID: {})", pid));
+            Log::log_indented("The running instance will move to the new location's times");
+        }
+        Err(e) => {
+            Log::log_warning(&format!("Failed to signal existing process: {}", e));
+            Log::log_indented("You may need to run 'sunsetr --reload' manually.");
+        }
+    }
+
+    Log::log_end();
+    Ok(())
+}
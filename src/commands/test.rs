@@ -1,7 +1,8 @@
 //! Implementation of the --test command for interactive gamma/temperature testing.
 //!
 //! This command operates in two modes:
-//! 1. **With existing sunsetr process**: Sends SIGUSR1 signal with test parameters via temp file.
+//! 1. **With existing sunsetr process**: Sends SIGUSR1 signal with test parameters via a file
+//!    in the runtime directory.
 //!    The existing process temporarily applies the test values using its configured backend.
 //! 2. **Without existing process**: Uses the Wayland backend directly for testing.
 //!    This avoids backend conflicts and provides universal testing capability.
@@ -14,8 +15,16 @@ use crate::logger::Log;
 use crate::signals::TestModeParams;
 use anyhow::Result;
 
+/// Path of the test request file for the given sunsetr PID.
+pub fn test_file_path(pid: u32) -> Result<String> {
+    Ok(crate::utils::runtime_dir()?
+        .join(format!("sunsetr-test-{}.tmp", pid))
+        .to_string_lossy()
+        .into_owned())
+}
+
 /// Validate temperature value using the same logic as config validation
-fn validate_temperature(temp: u32) -> Result<()> {
+pub(crate) fn validate_temperature(temp: u32) -> Result<()> {
     use crate::constants::{MAXIMUM_TEMP, MINIMUM_TEMP};
 
     if temp < MINIMUM_TEMP {
@@ -38,7 +47,7 @@ fn validate_temperature(temp: u32) -> Result<()> {
 }
 
 /// Validate gamma value using the same logic as config validation
-fn validate_gamma(gamma: f32) -> Result<()> {
+pub(crate) fn validate_gamma(gamma: f32) -> Result<()> {
     use crate::constants::{MAXIMUM_GAMMA, MINIMUM_GAMMA};
 
     if gamma < MINIMUM_GAMMA {
@@ -64,6 +73,9 @@ pub fn handle_test_command(temperature: u32, gamma: f32, debug_enabled: bool) ->
     // This ensures we fail fast with a clear error message if config is invalid
     let config = Config::load()?;
 
+    crate::json::field("temperature", temperature);
+    crate::json::field("gamma", gamma);
+
     Log::log_block_start(&format!(
         "Testing display settings: {}K @ {}%",
         temperature, gamma
@@ -72,13 +84,15 @@ pub fn handle_test_command(temperature: u32, gamma: f32, debug_enabled: bool) ->
     // Check for existing sunsetr process
     match crate::utils::get_running_sunsetr_pid() {
         Ok(pid) => {
+            crate::json::field("via", "running instance");
+            crate::json::field("pid", pid);
             Log::log_decorated(&format!(
                 "Found existing sunsetr process (PID: {}), sending test signal...",
                 pid
             ));
 
-            // Write test parameters to temp file
-            let test_file_path = format!("/tmp/sunsetr-test-{}.tmp", pid);
+            // Write test parameters to the request file
+            let test_file_path = test_file_path(pid)?;
             std::fs::write(&test_file_path, format!("{}\n{}", temperature, gamma))?;
 
             // Send SIGUSR1 signal to existing process
@@ -113,15 +127,8 @@ pub fn handle_test_command(temperature: u32, gamma: f32, debug_enabled: bool) ->
                     // Send SIGUSR1 with special params (temp=0) to exit test mode
                     Log::log_decorated("Restoring normal operation...");
 
-                    // Write special "exit test mode" parameters
-                    let test_file_path = format!("/tmp/sunsetr-test-{}.tmp", pid);
-                    std::fs::write(&test_file_path, "0\n0")?;
-
-                    // Send SIGUSR1 to signal exit from test mode
-                    let _ = nix::sys::signal::kill(
-                        nix::unistd::Pid::from_raw(pid as i32),
-                        nix::sys::signal::Signal::SIGUSR1,
-                    );
+                    // Temperature 0 asks the process to leave test mode
+                    let _ = signal_test_mode(pid, 0, 0.0);
 
                     Log::log_decorated("Test complete");
                 }
@@ -133,6 +140,7 @@ pub fn handle_test_command(temperature: u32, gamma: f32, debug_enabled: bool) ->
             }
         }
         Err(_) => {
+            crate::json::field("via", "wayland");
             Log::log_decorated("No existing sunsetr process found, running direct test...");
 
             // Run direct test when no existing process
@@ -144,6 +152,19 @@ pub fn handle_test_command(temperature: u32, gamma: f32, debug_enabled: bool) ->
     Ok(())
 }
 
+/// Show `temperature` and `gamma` through the test mode of the running instance `pid`.
+///
+/// A temperature of 0 ends its test mode and restores the scheduled values.
+pub(crate) fn signal_test_mode(pid: u32, temperature: u32, gamma: f32) -> Result<()> {
+    let test_file_path = test_file_path(pid)?;
+    std::fs::write(&test_file_path, format!("{}\n{}", temperature, gamma))?;
+    nix::sys::signal::kill(
+        nix::unistd::Pid::from_raw(pid as i32),
+        nix::sys::signal::Signal::SIGUSR1,
+    )?;
+    Ok(())
+}
+
 /// Run direct test when no existing sunsetr process is running.
 ///
 /// Uses the Wayland backend directly regardless of configuration to:
@@ -168,17 +189,10 @@ fn run_direct_test(
             let running = Arc::new(AtomicBool::new(true));
 
             // Check if startup transition is enabled
-            let startup_transition_enabled = config
-                .startup_transition
-                .unwrap_or(crate::constants::DEFAULT_STARTUP_TRANSITION);
+            let startup_transition_enabled = config.effective().startup_transition;
 
             // Apply test values with optional smooth transition
-            if startup_transition_enabled
-                && config
-                    .startup_transition_duration
-                    .unwrap_or(crate::constants::DEFAULT_STARTUP_TRANSITION_DURATION)
-                    > 0
-            {
+            if startup_transition_enabled && config.effective().startup_transition_duration > 0 {
                 // Create a cloned config with test values as night values
                 // We use night values to transition FROM day values (6500K, 100%)
                 let mut test_config = config.clone();
@@ -236,12 +250,7 @@ fn run_direct_test(
             // Restore to standard day values (6500K, 100%)
             Log::log_block_start("Restoring display to day values...");
 
-            if startup_transition_enabled
-                && config
-                    .startup_transition_duration
-                    .unwrap_or(crate::constants::DEFAULT_STARTUP_TRANSITION_DURATION)
-                    > 0
-            {
+            if startup_transition_enabled && config.effective().startup_transition_duration > 0 {
                 // Create transition from test values back to day values
                 let mut transition = crate::startup_transition::StartupTransition::new_from_values(
                     temperature,
@@ -313,9 +322,7 @@ pub fn run_test_mode_loop(
     ));
 
     // Check if startup transition is enabled
-    let startup_transition_enabled = config
-        .startup_transition
-        .unwrap_or(crate::constants::DEFAULT_STARTUP_TRANSITION);
+    let startup_transition_enabled = config.effective().startup_transition;
 
     // Get current values before applying test values
     let current_state = crate::time_state::get_transition_state(config);
@@ -323,12 +330,7 @@ pub fn run_test_mode_loop(
         crate::time_state::get_initial_values_for_state(current_state, config);
 
     // Apply test values with optional smooth transition
-    if startup_transition_enabled
-        && config
-            .startup_transition_duration
-            .unwrap_or(crate::constants::DEFAULT_STARTUP_TRANSITION_DURATION)
-            > 0
-    {
+    if startup_transition_enabled && config.effective().startup_transition_duration > 0 {
         // Create a cloned config with test values as day values for the transition
         let mut test_config = config.clone();
         test_config.day_temp = Some(test_params.temperature);
@@ -401,6 +403,9 @@ pub fn run_test_mode_loop(
     #[cfg(debug_assertions)]
     eprintln!("DEBUG: Test mode loop waiting for exit signal");
 
+    // Test values currently on screen, reapplied after session unlock/resume
+    let mut applied_values = (test_params.temperature, test_params.gamma);
+
     loop {
         // Check if process should exit
         if !signal_state
@@ -440,13 +445,71 @@ pub fn run_test_mode_loop(
                                 new_params.gamma,
                                 &signal_state.running,
                             );
+                            applied_values = (new_params.temperature, new_params.gamma);
                         }
                     }
+                    SignalMessage::Reapply(event) => {
+                        // Keep showing the test values after the session event
+                        Log::log_decorated(&format!("Session {}, reapplying test values", event));
+                        let _ = backend.apply_temperature_gamma(
+                            applied_values.0,
+                            applied_values.1,
+                            &signal_state.running,
+                        );
+                    }
+                    SignalMessage::OutputWake(output) => {
+                        // Keep showing the test values on the woken output
+                        Log::log_decorated(&format!(
+                            "Output '{}' woke up, reapplying test values",
+                            output
+                        ));
+                        let _ = backend.apply_temperature_gamma(
+                            applied_values.0,
+                            applied_values.1,
+                            &signal_state.running,
+                        );
+                    }
+                    SignalMessage::OutputAdded(output) => {
+                        // Show the test values on the new output too
+                        Log::log_decorated(&format!(
+                            "Output '{}' connected, reapplying test values",
+                            output
+                        ));
+                        let _ = backend.apply_temperature_gamma(
+                            applied_values.0,
+                            applied_values.1,
+                            &signal_state.running,
+                        );
+                    }
+                    SignalMessage::AppearanceChanged(scheme) => {
+                        // Keep the test values; restoring afterwards picks up the new scheme
+                        crate::appearance::set_current_scheme(scheme);
+                    }
+                    SignalMessage::Nudge(step) => {
+                        // Keep the test values; restoring afterwards includes the nudge
+                        crate::time_state::set_temperature_nudge(crate::time_state::combine_nudge(
+                            crate::time_state::temperature_nudge(),
+                            step,
+                        ));
+                    }
+                    SignalMessage::Idle(idle) => {
+                        // Keep the test values; restoring afterwards includes the dimming
+                        crate::time_state::set_idle_dimmed(idle);
+                    }
+                    SignalMessage::Hold(command) => {
+                        // Keep the test values; a hold is shown once test mode ends
+                        signal_state.set_hold(command, std::time::SystemTime::now());
+                    }
                     SignalMessage::Reload => {
                         // Reload signal received during test mode - exit and let main loop handle it
                         Log::log_decorated("Reload signal received, exiting test mode...");
                         break;
                     }
+                    SignalMessage::SwitchProfile(_) => {
+                        // Profile switch during test mode - exit so normal operation resumes
+                        Log::log_decorated("Profile switch received, exiting test mode...");
+                        break;
+                    }
                     SignalMessage::Shutdown => {
                         // Shutdown signal received during test mode - exit immediately
                         Log::log_decorated("Shutdown signal received, exiting test mode...");
@@ -471,12 +534,7 @@ pub fn run_test_mode_loop(
     let (restore_temp, restore_gamma) =
         crate::time_state::get_initial_values_for_state(restore_state, config);
 
-    if startup_transition_enabled
-        && config
-            .startup_transition_duration
-            .unwrap_or(crate::constants::DEFAULT_STARTUP_TRANSITION_DURATION)
-            > 0
-    {
+    if startup_transition_enabled && config.effective().startup_transition_duration > 0 {
         // Create a cloned config with restore values as day values for the transition
         let mut restore_config = config.clone();
         restore_config.day_temp = Some(restore_temp);
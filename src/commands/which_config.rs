@@ -0,0 +1,111 @@
+//! Implementation of the --which-config command.
+//!
+//! Shows which files sunsetr would read, without loading or validating them and
+//! without the prompt that appears at startup when configs exist in both the new
+//! `sunsetr/` and the legacy `hypr/` directory. Meant for "my changes don't take
+//! effect" situations, where the edited file isn't the one in use.
+
+use crate::config::{self, Config, ConfigLocation};
+use crate::logger::Log;
+use crate::utils::path_for_display;
+use anyhow::Result;
+use std::path::Path;
+
+/// Handle the --which-config command.
+pub fn handle_which_config_command(debug_enabled: bool) -> Result<()> {
+    Log::log_version();
+
+    let location = Config::locate_config()?;
+    if debug_enabled {
+        Log::log_pipe();
+        Log::log_debug(&format!("Resolved location: {:?}", location));
+    }
+
+    crate::json::field("config", location.path.display().to_string());
+    crate::json::field("exists", location.exists);
+    crate::json::field("legacy", location.legacy);
+    crate::json::field("selected", location.selected);
+    crate::json::field(
+        "conflicting",
+        location
+            .conflicting
+            .as_ref()
+            .map(|path| path.display().to_string()),
+    );
+
+    Log::log_block_start("Configuration files:");
+    Log::log_indented(&format!("Config:       {}", describe_config(&location)));
+    if let Some(ref legacy) = location.conflicting {
+        Log::log_indented(&format!(
+            "Also found:   {} (legacy location)",
+            path_for_display(legacy)
+        ));
+    }
+
+    // geo.toml is always read next to the config file
+    if let Some(dir) = location.path.parent() {
+        let geo_path = dir.join("geo.toml");
+        crate::json::field(
+            "geo",
+            geo_path.exists().then(|| geo_path.display().to_string()),
+        );
+        Log::log_indented(&format!("Geo file:     {}", describe_file(&geo_path)));
+    }
+
+    if crate::utils::is_safe_mode() {
+        Log::log_indented("System-wide:  ignored in safe mode");
+    } else if let Some(system_path) = config::system_config_location() {
+        crate::json::field(
+            "system",
+            system_path
+                .exists()
+                .then(|| system_path.display().to_string()),
+        );
+        Log::log_indented(&format!("System-wide:  {}", describe_file(&system_path)));
+    } else {
+        Log::log_indented(&format!(
+            "System-wide:  disabled ({} is empty)",
+            crate::constants::SYSTEM_CONFIG_ENV
+        ));
+    }
+
+    let overrides = config::env_override_keys();
+    crate::json::field("env_overrides", overrides.clone());
+    if !overrides.is_empty() {
+        Log::log_indented(&format!("Environment overrides: {}", overrides.join(", ")));
+    }
+
+    if location.conflicting.is_some() {
+        Log::log_pipe();
+        Log::log_warning(
+            "Configs exist in both locations, so sunsetr asks which to keep at startup",
+        );
+        Log::log_indented("Run `sunsetr --migrate` to keep the legacy one, or remove one of them");
+    }
+
+    Log::log_end();
+    Ok(())
+}
+
+/// The config path and where it came from.
+fn describe_config(location: &ConfigLocation) -> String {
+    let origin = if !location.exists {
+        "not created yet, defaults apply"
+    } else if location.selected {
+        "picked with --select-config"
+    } else if location.legacy {
+        "legacy location"
+    } else {
+        "new location"
+    };
+    format!("{} ({})", path_for_display(&location.path), origin)
+}
+
+/// A path, marked when the file doesn't exist.
+fn describe_file(path: &Path) -> String {
+    if path.exists() {
+        path_for_display(path)
+    } else {
+        format!("{} (not present)", path_for_display(path))
+    }
+}
@@ -23,31 +23,84 @@
 //! ```toml
 //! # Backend configuration
 //! backend = "auto"                  # "auto", "hyprland", or "wayland"
+//! # auto_prefer = ["wayland", "hyprland"]  # Order "auto" tries backends in a Wayland session
 //! start_hyprsunset = true           # Whether to start hyprsunset daemon
 //!
 //! # Geolocation-based transitions (automatic transition times and durations)
 //! latitude = 40.7128                # Geographic coordinates
 //! longitude = -74.0060
 //! transition_mode = "geo"           # Use solar calculations
+//! geo_anchor = "twilight"           # "twilight" or "solar_noon"
+//! geo_noon_offset = 6.0             # Hours from solar noon to each transition ("solar_noon")
+//! solar_precision = "fast"          # "fast" or "precise" elevation-angle boundaries
+//! geo_night_boundary = "civil"      # Night from "civil" (-2°), "nautical" or "astronomical" dusk
+//! geo_round_to_minutes = 5          # Snap geo transition boundaries to 5 minutes (0 = off)
 //!
 //! # Manual mode (fixed times)
 //! sunset = "19:00:00"               # Manual sunset time
 //! sunrise = "06:00:00"              # Manual sunrise time
 //! transition_duration = 45          # Manual transition duration (minutes)
+//! sunrise_duration = 20             # Optional per-transition overrides (minutes)
+//! # sunrise_hold_temp = 5000        # Pause the sunrise at this temperature...
+//! # sunrise_hold_minutes = 15       # ...for this many minutes before finishing to day
 //! transition_mode = "finish_by"     # How to apply transitions
 //!
+//! # Geo sunset with a fixed sunrise (sunrise/sunrise_duration, "finish_by" style)
+//! # transition_mode = "manual_geo"
+//! # manual_geo_event = "sunset"     # The event that follows the sun: "sunset" or "sunrise"
+//!
+//! # Geo in winter, fixed times in summer (seasons swap south of the equator)
+//! # seasonal_modes = { summer = "finish_by", winter = "geo" }
+//!
+//! # Follow the desktop's light/dark setting instead ("portal" feature)
+//! # transition_mode = "appearance"
+//!
 //! # Color temperature settings
 //! night_temp = 3300                 # Kelvin (warm)
 //! day_temp = 6500                   # Kelvin (cool)
 //! night_gamma = 90.0                # Brightness percentage
 //! day_gamma = 100.0                 # Brightness percentage
+//! night_gamma_b = 0.9               # Per-channel multipliers (0.0-2.0, also _r/_g and day_*)
+//! color_shift = true               # Set false to only dim (temperature stays at 6500K)
+//! temp_quantum = 1                  # Snap transition temperatures to this step (Kelvin)
+//! temp_unit = "kelvin"              # Unit of the *_temp values: "kelvin" or "mired"
+//! # night_temp = "warm"            # Or a preset (candle, warm, neutral, cool) or "#ffb46b"
 //!
 //! # Transition behavior
 //! update_interval = 60              # Seconds between transtion updates (any mode)
+//! ease_out_tail_seconds = 0         # Settle gently over the last seconds of a transition
+//! gamma_progress_offset = 0.0       # Let gamma lag (+) or lead (-) the temperature (-0.5-0.5)
 //!
 //! # Startup behavior
 //! startup_transition = false        # Smooth startup transition
 //! startup_transition_duration = 1   # Second(s)
+//! startup_delay_ms = 0              # Wait before first apply (compositor readiness)
+//! wake_fade_ms = 1000               # Fade gamma in on monitors waking from DPMS (Wayland)
+//! # idle_timeout = 300              # Dim further after this many idle seconds (idle feature)
+//! # idle_gamma = 70.0               # Percentage of the current gamma kept while idle
+//! precompute_ramps = false          # Blend precomputed day/night ramps during transitions (Wayland)
+//! reset_on_exit = true              # Reset to neutral on exit (false: release gamma controls)
+//! # on_fatal_exit = "leave"         # When the backend is lost: "reset" or "leave" (default: reset_on_exit)
+//!
+//! # Troubleshooting (Wayland, only if a compositor shows wrong colors)
+//! # gamma_table_format = "planar-le" # Or "planar-be", "interleaved-le", "interleaved-be"
+//!
+//! # Logging
+//! log_file = "~/.local/state/sunsetr/sunsetr.log"  # Also write plain logs here
+//! log_file_max_bytes = 1048576      # Rotate the log file at this size
+//! log_file_keep = 3                 # Rotated log files to keep
+//! log_to_console = true             # Set false to only log to the file
+//! log_verbosity = "normal"          # "quiet": only day/night changes and errors, "debug": like --debug
+//!
+//! # Hooks (run with `sh -c`, SUNSETR_EVENT/SUNSETR_TEMP/SUNSETR_GAMMA set)
+//! on_day_command = "brightnessctl -d kbd_backlight set 0"
+//! on_night_command = "brightnessctl -d kbd_backlight set 1"
+//! on_transition_start = "notify-send sunsetr \"$SUNSETR_EVENT\""
+//!
+//! # Outputs on their own schedule (Wayland)
+//! [output_groups.secondary]
+//! outputs = ["HDMI-A-1"]
+//! sunset = "17:30:00"
 //! ```
 //!
 //! ## Validation and Error Handling
@@ -69,6 +122,7 @@
 use anyhow::{Context, Result};
 use chrono::{NaiveTime, Timelike};
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::fs::{self};
 use std::path::{Path, PathBuf};
 
@@ -99,7 +153,7 @@ struct GeoConfig {
 pub enum Backend {
     /// Automatic backend detection based on environment.
     ///
-    /// Auto-detection priority: Hyprland → Wayland → error.
+    /// Auto-detection priority: Hyprland → Wayland → DRM (`drm` feature) → error.
     /// This is the recommended setting for most users.
     Auto,
     /// Hyprland compositor backend using hyprsunset daemon.
@@ -112,6 +166,10 @@ pub enum Backend {
     /// Works with most wlroots-based compositors (Niri, Sway, river, Wayfire, etc.).
     /// Does not require external helper processes.
     Wayland,
+    /// Kernel DRM backend setting gamma LUTs on the display controllers directly.
+    ///
+    /// For TTY sessions without a compositor. Requires the `drm` cargo feature.
+    Drm,
 }
 
 impl Backend {
@@ -120,10 +178,231 @@ impl Backend {
             Backend::Auto => "auto",
             Backend::Hyprland => "hyprland",
             Backend::Wayland => "wayland",
+            Backend::Drm => "drm",
+        }
+    }
+
+    /// Parse a backend name as written in the config: `auto`, `hyprland`, `wayland` or `drm`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "auto" => Some(Backend::Auto),
+            "hyprland" => Some(Backend::Hyprland),
+            "wayland" => Some(Backend::Wayland),
+            "drm" => Some(Backend::Drm),
+            _ => None,
+        }
+    }
+}
+
+/// How much the main loop logs (`log_verbosity`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogVerbosity {
+    /// Only day/night boundary changes, warnings and errors
+    Quiet,
+    /// Transition progress on every update
+    Normal,
+    /// Detailed backend operations, like `--debug`
+    Debug,
+}
+
+impl LogVerbosity {
+    /// Parse a `log_verbosity` value: `quiet`, `normal` or `debug`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "quiet" => Some(LogVerbosity::Quiet),
+            "normal" => Some(LogVerbosity::Normal),
+            "debug" => Some(LogVerbosity::Debug),
+            _ => None,
+        }
+    }
+}
+
+/// Which event follows the sun in `transition_mode = "manual_geo"` (`manual_geo_event`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManualGeoEvent {
+    Sunset,
+    Sunrise,
+}
+
+impl ManualGeoEvent {
+    /// Parse a `manual_geo_event` value: `sunset` or `sunrise`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sunset" => Some(ManualGeoEvent::Sunset),
+            "sunrise" => Some(ManualGeoEvent::Sunrise),
+            _ => None,
+        }
+    }
+
+    /// The config name of the event.
+    pub fn name(self) -> &'static str {
+        match self {
+            ManualGeoEvent::Sunset => "sunset",
+            ManualGeoEvent::Sunrise => "sunrise",
+        }
+    }
+
+    /// The other event, which keeps the configured time.
+    pub fn other(self) -> Self {
+        match self {
+            ManualGeoEvent::Sunset => ManualGeoEvent::Sunrise,
+            ManualGeoEvent::Sunrise => ManualGeoEvent::Sunset,
         }
     }
 }
 
+/// All keys recognized at the top level of `sunsetr.toml`.
+///
+/// Keep this in sync with the fields of [`Config`]; unknown keys are rejected
+/// so that typos like `nite_temp` don't silently fall back to defaults.
+const KNOWN_CONFIG_KEYS: &[&str] = &[
+    "start_hyprsunset",
+    "hyprsunset_socket",
+    "hyprsunset_connect_timeout",
+    "hyprsunset_connect_retries",
+    "backend",
+    "auto_prefer",
+    "startup_transition",
+    "startup_transition_duration",
+    "startup_delay_ms",
+    "latitude",
+    "longitude",
+    "geo_anchor",
+    "geo_noon_offset",
+    "solar_precision",
+    "geo_night_boundary",
+    "geo_round_to_minutes",
+    "sunset",
+    "sunrise",
+    "night_temp",
+    "day_temp",
+    "night_gamma",
+    "day_gamma",
+    "color_shift",
+    "temp_quantum",
+    "temp_unit",
+    "day_whitepoint",
+    "night_gamma_r",
+    "night_gamma_g",
+    "night_gamma_b",
+    "day_gamma_r",
+    "day_gamma_g",
+    "day_gamma_b",
+    "outputs_include",
+    "outputs_exclude",
+    "hdr_outputs",
+    "hdr_night_temp",
+    "hdr_day_temp",
+    "hdr_night_gamma",
+    "hdr_day_gamma",
+    "verify_gamma",
+    "precompute_ramps",
+    "gamma_table_format",
+    "require_outputs",
+    "reset_on_exit",
+    "on_fatal_exit",
+    "reapply_on_unlock",
+    "wake_fade_ms",
+    "idle_timeout",
+    "idle_gamma",
+    "log_file",
+    "log_file_max_bytes",
+    "log_file_keep",
+    "log_to_console",
+    "log_verbosity",
+    "metrics_port",
+    "event_fifo",
+    "transition_duration",
+    "sunset_duration",
+    "sunrise_duration",
+    "sunrise_hold_temp",
+    "sunrise_hold_minutes",
+    "update_interval",
+    "ease_out_tail_seconds",
+    "gamma_progress_offset",
+    "transition_mode",
+    "manual_geo_event",
+    "on_day_command",
+    "on_night_command",
+    "on_transition_start",
+    "active_profile",
+    "profiles",
+    "output_groups",
+    "seasonal_modes",
+];
+
+/// All keys recognized inside a `[profiles.<name>]` table.
+const KNOWN_PROFILE_KEYS: &[&str] = &["night_temp", "day_temp", "night_gamma", "day_gamma"];
+
+/// Season names accepted as keys of `seasonal_modes`.
+const SEASON_NAMES: &[&str] = &["spring", "summer", "autumn", "winter"];
+
+/// The meteorological season `date` falls in, by whole months.
+///
+/// In the northern hemisphere spring is March-May, summer June-August, autumn
+/// September-November and winter December-February. The southern hemisphere swaps
+/// summer with winter and spring with autumn.
+pub fn season_for_date(date: chrono::NaiveDate, southern_hemisphere: bool) -> &'static str {
+    use chrono::Datelike;
+
+    let index = match date.month() {
+        3..=5 => 0,
+        6..=8 => 1,
+        9..=11 => 2,
+        _ => 3,
+    };
+    let index = if southern_hemisphere {
+        (index + 2) % 4
+    } else {
+        index
+    };
+    SEASON_NAMES[index]
+}
+
+/// All keys recognized inside an `[output_groups.<name>]` table.
+const KNOWN_OUTPUT_GROUP_KEYS: &[&str] = &[
+    "outputs",
+    "transition_mode",
+    "sunset",
+    "sunrise",
+    "transition_duration",
+    "night_temp",
+    "day_temp",
+    "night_gamma",
+    "day_gamma",
+];
+
+/// Named set of color overrides that can be selected at runtime.
+///
+/// Profiles are declared as `[profiles.<name>]` tables in `sunsetr.toml`.
+/// Any field left unset falls back to the corresponding top-level setting.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct ProfileConfig {
+    pub night_temp: Option<u32>,
+    pub day_temp: Option<u32>,
+    pub night_gamma: Option<f32>,
+    pub day_gamma: Option<f32>,
+}
+
+/// Outputs that follow their own schedule instead of the top-level one.
+///
+/// Declared as `[output_groups.<name>]` tables in `sunsetr.toml` (Wayland backend only).
+/// `outputs` lists the output name globs in the group; every other field overrides the
+/// corresponding top-level setting for those outputs.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct OutputGroupConfig {
+    #[serde(default)]
+    pub outputs: Vec<String>,
+    pub transition_mode: Option<String>,
+    pub sunset: Option<String>,
+    pub sunrise: Option<String>,
+    pub transition_duration: Option<u64>,
+    pub night_temp: Option<u32>,
+    pub day_temp: Option<u32>,
+    pub night_gamma: Option<f32>,
+    pub day_gamma: Option<f32>,
+}
+
 /// Configuration structure for sunsetr application settings.
 ///
 /// This structure represents all configurable options for sunsetr, loaded from
@@ -132,13 +411,28 @@ impl Backend {
 ///
 /// ## Configuration Categories
 ///
-/// - **Backend Control**: `backend`, `start_hyprsunset` (applies to all modes)
+/// - **Backend Control**: `backend`, `auto_prefer`, `start_hyprsunset`, `hyprsunset_socket`, `hyprsunset_connect_timeout`, `hyprsunset_connect_retries` (applies to all modes)
 /// - **Startup Behavior**: `startup_transition`, `startup_transition_duration` (applies to all modes)
-/// - **Color Settings**: `night_temp`, `day_temp`, `night_gamma`, `day_gamma` (applies to all modes)
-/// - **Update Frequency**: `update_interval` (applies to all transition modes)
-/// - **Geographic Mode Settings**: `latitude`, `longitude` (only used when `transition_mode = "geo"`)
-/// - **Manual Mode Settings**: `sunset`, `sunrise`, `transition_duration` (only used for manual modes: "finish_by", "start_at", "center")
-/// - **Mode Selection**: `transition_mode` ("geo" vs manual modes: "finish_by", "start_at", "center")
+/// - **Shutdown Behavior**: `reset_on_exit`, `on_fatal_exit` (Wayland backend; hyprsunset resets itself)
+/// - **Color Settings**: `night_temp`, `day_temp`, `night_gamma`, `day_gamma`, `color_shift`, `temp_unit`, `temp_quantum`, `day_whitepoint`, `night_gamma_r`/`_g`/`_b`, `day_gamma_r`/`_g`/`_b` (applies to all modes)
+/// - **Output Targeting**: `outputs_include`, `outputs_exclude`, `require_outputs` (Wayland backend only)
+/// - **HDR Outputs**: `hdr_outputs`, `hdr_night_temp`, `hdr_day_temp`, `hdr_night_gamma`, `hdr_day_gamma` (Wayland backend only)
+/// - **Apply Verification**: `verify_gamma` (Wayland backend only)
+/// - **Performance**: `precompute_ramps` (Wayland backend only)
+/// - **Troubleshooting**: `gamma_table_format` (Wayland backend only, leave unset unless colors are wrong)
+/// - **Monitoring**: `metrics_port` (requires the `metrics` build feature), `event_fifo` (read at startup)
+/// - **Session Events**: `reapply_on_unlock` (requires the `logind` build feature)
+/// - **Monitor Wake**: `wake_fade_ms` (Wayland backend only)
+/// - **Idle Dimming**: `idle_timeout`, `idle_gamma` (requires the `idle` build feature)
+/// - **Logging**: `log_file`, `log_file_max_bytes`, `log_file_keep`, `log_to_console` (read at startup), `log_verbosity`
+/// - **Update Frequency**: `update_interval`, `ease_out_tail_seconds`, `gamma_progress_offset` (applies to all transition modes)
+/// - **Sunrise Warmup**: `sunrise_hold_temp`, `sunrise_hold_minutes` (a pause partway through every sunrise)
+/// - **Geographic Mode Settings**: `latitude`, `longitude`, `geo_anchor`, `geo_noon_offset`, `solar_precision`, `geo_night_boundary`, `geo_round_to_minutes` (only used when `transition_mode = "geo"`)
+/// - **Manual Mode Settings**: `sunset`, `sunrise`, `transition_duration`, `sunset_duration`, `sunrise_duration` (only used for manual modes: "finish_by", "start_at", "center")
+/// - **Mode Selection**: `transition_mode` ("geo" vs manual modes: "finish_by", "start_at", "center", "manual_geo" to mix the two per event, or "appearance" to follow the desktop's dark mode), `manual_geo_event`, `seasonal_modes` (per-season override)
+/// - **Hooks**: `on_day_command`, `on_night_command`, `on_transition_start` (shell commands run on state changes)
+/// - **Profiles**: `active_profile`, `[profiles.<name>]` (override color settings, switchable at runtime)
+/// - **Output Groups**: `[output_groups.<name>]` (outputs with their own schedule, Wayland backend only)
 ///
 /// ## Validation
 ///
@@ -154,12 +448,31 @@ pub struct Config {
     /// Defaults to `true` for Hyprland backend, `false` for Wayland backend.
     pub start_hyprsunset: Option<bool>,
 
+    /// Path to the hyprsunset IPC socket, bypassing auto-detection (Hyprland only).
+    ///
+    /// Useful in nested or multi-instance Hyprland setups where the socket derived
+    /// from `HYPRLAND_INSTANCE_SIGNATURE` belongs to the wrong instance. The
+    /// `HYPRSUNSET_SOCKET` environment variable takes precedence over this.
+    pub hyprsunset_socket: Option<String>,
+
+    /// Seconds to wait for hyprsunset's socket when the first connection fails (Hyprland only).
+    pub hyprsunset_connect_timeout: Option<u64>,
+
+    /// How many times to wait `hyprsunset_connect_timeout` before giving up (Hyprland only).
+    pub hyprsunset_connect_retries: Option<u32>,
+
     /// Backend implementation to use for color temperature control.
     ///
     /// Determines how sunsetr communicates with the compositor.
     /// Defaults to `Auto` which detects the appropriate backend automatically.
     pub backend: Option<Backend>,
 
+    /// Order in which `backend = "auto"` tries backends in a Wayland session.
+    ///
+    /// `"hyprland"` is only picked on Hyprland; `"wayland"` works everywhere and is
+    /// the fallback. Defaults to `["hyprland", "wayland"]`.
+    pub auto_prefer: Option<Vec<String>>,
+
     /// Whether to enable smooth animated startup transitions.
     ///
     /// When `true`, sunsetr will gradually transition from day values to the
@@ -167,20 +480,175 @@ pub struct Config {
     /// When `false`, sunsetr applies the correct state immediately.
     pub startup_transition: Option<bool>, // whether to enable smooth startup transition
     pub startup_transition_duration: Option<u64>, // seconds for startup transition
+    pub startup_delay_ms: Option<u64>,            // milliseconds to wait before the first apply
     pub latitude: Option<f64>,                    // Geographic latitude for geo mode
     pub longitude: Option<f64>,                   // Geographic longitude for geo mode
+    pub geo_anchor: Option<String>, // "twilight" (elevation angles) or "solar_noon" (fixed offsets)
+    pub geo_noon_offset: Option<f64>, // Hours from solar noon to each transition center
+    pub solar_precision: Option<String>, // "fast" (linear estimates) or "precise" (solved crossings)
+    pub geo_night_boundary: Option<String>, // "civil" (-2°), "nautical" (-12°) or "astronomical" (-18°)
+    pub geo_round_to_minutes: Option<u32>,  // Snap geo transition boundaries to this many minutes
     pub sunset: String,
     pub sunrise: String,
     pub night_temp: Option<u32>,
     pub day_temp: Option<u32>,
     pub night_gamma: Option<f32>,
     pub day_gamma: Option<f32>,
+    pub color_shift: Option<bool>, // false: keep 6500K and only vary gamma (brightness only)
+    pub temp_quantum: Option<u32>, // Kelvin step transition temperatures snap to
+    pub temp_unit: Option<String>, // "kelvin" or "mired"; mireds are converted to Kelvin on load
+    pub day_whitepoint: Option<[f64; 2]>, // Panel's measured white as CIE xy (Wayland only)
+    pub night_gamma_r: Option<f32>, // Red multiplier at night (Wayland only)
+    pub night_gamma_g: Option<f32>, // Green multiplier at night (Wayland only)
+    pub night_gamma_b: Option<f32>, // Blue multiplier at night (Wayland only)
+    pub day_gamma_r: Option<f32>,  // Red multiplier during the day (Wayland only)
+    pub day_gamma_g: Option<f32>,  // Green multiplier during the day (Wayland only)
+    pub day_gamma_b: Option<f32>,  // Blue multiplier during the day (Wayland only)
+    pub outputs_include: Option<Vec<String>>, // Output name globs to apply gamma to (Wayland only)
+    pub outputs_exclude: Option<Vec<String>>, // Output name globs to leave untouched (Wayland only)
+    pub hdr_outputs: Option<Vec<String>>, // Output name globs that use the hdr_* values (Wayland only)
+    pub hdr_night_temp: Option<u32>,      // Night temperature for HDR outputs
+    pub hdr_day_temp: Option<u32>,        // Day temperature for HDR outputs
+    pub hdr_night_gamma: Option<f32>,     // Night gamma for HDR outputs
+    pub hdr_day_gamma: Option<f32>,       // Day gamma for HDR outputs
+    pub verify_gamma: Option<bool>, // Wait for the compositor to process each apply (Wayland only)
+    pub precompute_ramps: Option<bool>, // Blend precomputed day/night ramps during transitions (Wayland only)
+    pub gamma_table_format: Option<String>, // Byte layout of gamma tables, for troubleshooting (Wayland only)
+    pub require_outputs: Option<bool>, // Fail at startup instead of waiting when no output exists (Wayland only)
+    pub reset_on_exit: Option<bool>, // Reset to neutral on exit instead of only releasing gamma control
+    pub on_fatal_exit: Option<String>, // "reset" or "leave" when exiting because the backend is gone
+    pub metrics_port: Option<u16>,     // Localhost port for the Prometheus metrics endpoint
+    pub event_fifo: Option<bool>, // Write a line per state change to $XDG_RUNTIME_DIR/sunsetr_events
+    pub reapply_on_unlock: Option<bool>, // Reapply after session unlock/resume via logind
+    pub wake_fade_ms: Option<u64>, // Fade-in after an output wakes from DPMS (Wayland only)
+    pub idle_timeout: Option<u64>, // Seconds idle before dimming further (idle feature)
+    pub idle_gamma: Option<f32>,  // Percentage of the current gamma kept while idle
+    pub log_file: Option<String>, // Plain-text log file written alongside the console
+    pub log_file_max_bytes: Option<u64>, // Size at which the log file is rotated
+    pub log_file_keep: Option<u32>, // Rotated log files kept besides the current one
+    pub log_to_console: Option<bool>, // Print to stdout/journald (only turned off with a log file)
+    pub log_verbosity: Option<String>, // "quiet", "normal" or "debug" (debug is read at startup)
     pub transition_duration: Option<u64>, // minutes
-    pub update_interval: Option<u64>,     // seconds during transition
-    pub transition_mode: Option<String>,  // "finish_by", "start_at", "center", or "geo"
+    pub sunset_duration: Option<u64>, // minutes, overrides transition_duration for sunset
+    pub sunrise_duration: Option<u64>, // minutes, overrides transition_duration for sunrise
+    pub sunrise_hold_temp: Option<u32>, // Temperature the sunrise pauses at before finishing
+    pub sunrise_hold_minutes: Option<u64>, // minutes, length of that pause
+    pub update_interval: Option<u64>, // seconds during transition
+    pub ease_out_tail_seconds: Option<u64>, // Seconds at the end of a transition eased out more gently
+    pub gamma_progress_offset: Option<f32>, // Fraction of a transition gamma lags (+) or leads (-) temperature
+    pub transition_mode: Option<String>, // "finish_by", "start_at", "center", "geo", "manual_geo", or "appearance"
+    pub manual_geo_event: Option<String>, // Event that follows the sun in "manual_geo" mode: "sunset" or "sunrise"
+    pub seasonal_modes: Option<BTreeMap<String, String>>, // Transition mode by season, overriding transition_mode
+    pub on_day_command: Option<String>,                   // Shell command run when day begins
+    pub on_night_command: Option<String>,                 // Shell command run when night begins
+    pub on_transition_start: Option<String>, // Shell command run when a transition starts
+    pub active_profile: Option<String>,      // Name of the profile whose overrides are applied
+    pub profiles: Option<BTreeMap<String, ProfileConfig>>, // Named color overrides
+    pub output_groups: Option<BTreeMap<String, OutputGroupConfig>>, // Outputs on their own schedule
+}
+
+/// The scalar settings of a [`Config`] with every default filled in.
+///
+/// Obtained with [`Config::effective`]. Reading a setting through here instead of
+/// `config.x.unwrap_or(DEFAULT_X)` keeps every caller on the same default.
+/// Schedule, mode and output settings have no single default and stay on `Config`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EffectiveConfig {
+    pub start_hyprsunset: bool,
+    pub hyprsunset_connect_timeout: u64,
+    pub hyprsunset_connect_retries: u32,
+    pub startup_transition: bool,
+    pub startup_transition_duration: u64,
+    pub startup_delay_ms: u64,
+    pub geo_noon_offset: f64,
+    pub geo_round_to_minutes: u32,
+    pub night_temp: u32,
+    pub day_temp: u32,
+    pub night_gamma: f32,
+    pub day_gamma: f32,
+    pub night_channel_gamma: (f32, f32, f32),
+    pub day_channel_gamma: (f32, f32, f32),
+    pub color_shift: bool,
+    pub temp_quantum: u32,
+    pub verify_gamma: bool,
+    pub precompute_ramps: bool,
+    pub require_outputs: bool,
+    pub reset_on_exit: bool,
+    pub reapply_on_unlock: bool,
+    pub event_fifo: bool,
+    pub wake_fade_ms: u64,
+    pub idle_gamma: f32,
+    pub log_file_max_bytes: u64,
+    pub log_file_keep: u32,
+    pub log_to_console: bool,
+    pub update_interval: u64,
+    pub ease_out_tail_seconds: u64,
+    pub gamma_progress_offset: f32,
 }
 
 impl Config {
+    /// Resolve the defaults of all scalar settings at once.
+    pub fn effective(&self) -> EffectiveConfig {
+        let color_shift = self.color_shift.unwrap_or(DEFAULT_COLOR_SHIFT);
+        EffectiveConfig {
+            start_hyprsunset: self.start_hyprsunset.unwrap_or(DEFAULT_START_HYPRSUNSET),
+            hyprsunset_connect_timeout: self
+                .hyprsunset_connect_timeout
+                .unwrap_or(DEFAULT_HYPRSUNSET_CONNECT_TIMEOUT),
+            hyprsunset_connect_retries: self
+                .hyprsunset_connect_retries
+                .unwrap_or(DEFAULT_HYPRSUNSET_CONNECT_RETRIES),
+            startup_transition: self
+                .startup_transition
+                .unwrap_or(DEFAULT_STARTUP_TRANSITION),
+            startup_transition_duration: self
+                .startup_transition_duration
+                .unwrap_or(DEFAULT_STARTUP_TRANSITION_DURATION),
+            startup_delay_ms: self.startup_delay_ms.unwrap_or(DEFAULT_STARTUP_DELAY_MS),
+            geo_noon_offset: self.geo_noon_offset.unwrap_or(DEFAULT_GEO_NOON_OFFSET),
+            geo_round_to_minutes: self
+                .geo_round_to_minutes
+                .unwrap_or(DEFAULT_GEO_ROUND_TO_MINUTES),
+            // Brightness-only mode pins both ends of every transition to neutral
+            night_temp: if color_shift {
+                self.night_temp.unwrap_or(DEFAULT_NIGHT_TEMP)
+            } else {
+                NEUTRAL_TEMP
+            },
+            day_temp: if color_shift {
+                self.day_temp.unwrap_or(DEFAULT_DAY_TEMP)
+            } else {
+                NEUTRAL_TEMP
+            },
+            night_gamma: self.night_gamma.unwrap_or(DEFAULT_NIGHT_GAMMA),
+            day_gamma: self.day_gamma.unwrap_or(DEFAULT_DAY_GAMMA),
+            night_channel_gamma: self.night_channel_gamma(),
+            day_channel_gamma: self.day_channel_gamma(),
+            color_shift,
+            temp_quantum: self.temp_quantum.unwrap_or(DEFAULT_TEMP_QUANTUM),
+            verify_gamma: self.verify_gamma.unwrap_or(DEFAULT_VERIFY_GAMMA),
+            precompute_ramps: self.precompute_ramps.unwrap_or(DEFAULT_PRECOMPUTE_RAMPS),
+            require_outputs: self.require_outputs.unwrap_or(DEFAULT_REQUIRE_OUTPUTS),
+            reset_on_exit: self.reset_on_exit.unwrap_or(DEFAULT_RESET_ON_EXIT),
+            reapply_on_unlock: self.reapply_on_unlock.unwrap_or(DEFAULT_REAPPLY_ON_UNLOCK),
+            event_fifo: self.event_fifo.unwrap_or(DEFAULT_EVENT_FIFO),
+            wake_fade_ms: self.wake_fade_ms.unwrap_or(DEFAULT_WAKE_FADE_MS),
+            idle_gamma: self.idle_gamma.unwrap_or(DEFAULT_IDLE_GAMMA),
+            log_file_max_bytes: self
+                .log_file_max_bytes
+                .unwrap_or(DEFAULT_LOG_FILE_MAX_BYTES),
+            log_file_keep: self.log_file_keep.unwrap_or(DEFAULT_LOG_FILE_KEEP),
+            log_to_console: self.log_to_console.unwrap_or(DEFAULT_LOG_TO_CONSOLE),
+            update_interval: self.update_interval.unwrap_or(DEFAULT_UPDATE_INTERVAL),
+            ease_out_tail_seconds: self
+                .ease_out_tail_seconds
+                .unwrap_or(DEFAULT_EASE_OUT_TAIL_SECONDS),
+            gamma_progress_offset: self
+                .gamma_progress_offset
+                .unwrap_or(DEFAULT_GAMMA_PROGRESS_OFFSET),
+        }
+    }
+
     /// Get the path to the geo.toml file (in the same directory as sunsetr.toml)
     pub fn get_geo_path() -> Result<PathBuf> {
         let config_path = Self::get_config_path()?;
@@ -199,15 +667,10 @@ impl Config {
             Ok(config_dir.join("sunsetr").join("sunsetr.toml"))
         } else {
             // For binary execution or integration tests (when not a unit test)
-            let config_dir = dirs::config_dir().context("Could not determine config directory")?;
-            let new_config_path = config_dir.join("sunsetr").join("sunsetr.toml");
-            let old_config_path = config_dir.join("hypr").join("sunsetr.toml");
-
-            let new_exists = new_config_path.exists();
-            let old_exists = old_config_path.exists();
-
-            match (new_exists, old_exists) {
-                (true, true) => {
+            let location = Self::locate_config()?;
+            match location.conflicting {
+                Some(old_config_path) => {
+                    let new_config_path = location.path;
                     #[cfg(feature = "testing-support")]
                     {
                         Log::log_pipe();
@@ -219,21 +682,76 @@ impl Config {
                     }
                     #[cfg(not(feature = "testing-support"))]
                     {
+                        if crate::utils::is_safe_mode() {
+                            Log::log_pipe();
+                            Log::log_info(&format!(
+                                "Safe mode: found configs in both {} and {}, using the first",
+                                crate::utils::path_for_display(&new_config_path),
+                                crate::utils::path_for_display(&old_config_path)
+                            ));
+                            return Ok(new_config_path);
+                        }
                         Self::choose_config_file(new_config_path, old_config_path)
                     }
                 }
-                (true, false) => Ok(new_config_path),
-                (false, true) => Ok(old_config_path),
-                (false, false) => Ok(new_config_path), // Default to new path for creation
+                None => Ok(location.path),
             }
         }
     }
 
+    /// Find the config file without prompting, even when both locations have one.
+    ///
+    /// A file picked with `--select-config` comes first. Otherwise prefers the new
+    /// `sunsetr/` location, falls back to the legacy `hypr/` one, and defaults to the
+    /// new location when neither exists yet.
+    pub fn locate_config() -> Result<ConfigLocation> {
+        let config_dir = dirs::config_dir().context("Could not determine config directory")?;
+        if let Some(path) = selected_config(&config_dir.join("sunsetr")) {
+            return Ok(ConfigLocation {
+                path,
+                legacy: false,
+                exists: true,
+                conflicting: None,
+                selected: true,
+            });
+        }
+
+        let new_path = config_dir.join("sunsetr").join("sunsetr.toml");
+        let legacy_path = config_dir.join("hypr").join("sunsetr.toml");
+
+        Ok(match (new_path.exists(), legacy_path.exists()) {
+            (true, true) => ConfigLocation {
+                path: new_path,
+                legacy: false,
+                exists: true,
+                conflicting: Some(legacy_path),
+                selected: false,
+            },
+            (false, true) => ConfigLocation {
+                path: legacy_path,
+                legacy: true,
+                exists: true,
+                conflicting: None,
+                selected: false,
+            },
+            (exists, false) => ConfigLocation {
+                path: new_path,
+                legacy: false,
+                exists,
+                conflicting: None,
+                selected: false,
+            },
+        })
+    }
+
     /// Interactive terminal interface for choosing which config file to keep
     #[cfg(not(feature = "testing-support"))]
     fn choose_config_file(new_path: PathBuf, old_path: PathBuf) -> Result<PathBuf> {
         Log::log_pipe();
         Log::log_warning("Configuration conflict detected");
+        Log::log_indented(
+            "Run `sunsetr --migrate` to keep the legacy config and back up the new one",
+        );
         Log::log_block_start("Please select which config to keep:");
 
         let options = vec![
@@ -484,7 +1002,7 @@ impl Config {
                 "transition_duration",
                 &DEFAULT_TRANSITION_DURATION.to_string(),
                 &format!(
-                    "Transition duration in minutes ({}-{})",
+                    "Transition duration in minutes ({}-{}, or 0 to switch instantly)",
                     MINIMUM_TRANSITION_DURATION, MAXIMUM_TRANSITION_DURATION
                 ),
             )
@@ -566,9 +1084,13 @@ impl Config {
 
         // Validate time formats
         NaiveTime::parse_from_str(&config.sunset, "%H:%M:%S")
-            .context("Invalid sunset time format in config. Use HH:MM:SS format")?;
+            .context(
+                "Invalid sunset time format in config. Use HH:MM:SS format (local wall-clock time, DST changes are followed automatically)",
+            )?;
         NaiveTime::parse_from_str(&config.sunrise, "%H:%M:%S")
-            .context("Invalid sunrise time format in config. Use HH:MM:SS format")?;
+            .context(
+                "Invalid sunrise time format in config. Use HH:MM:SS format (local wall-clock time, DST changes are followed automatically)",
+            )?;
 
         // Validate temperature if specified
         if let Some(temp) = config.night_temp {
@@ -644,49 +1166,56 @@ impl Config {
             config.startup_transition_duration = Some(DEFAULT_STARTUP_TRANSITION_DURATION);
         }
 
+        if config.startup_delay_ms.is_none() {
+            config.startup_delay_ms = Some(DEFAULT_STARTUP_DELAY_MS);
+        }
+
         // Validate transition ranges
-        if let Some(duration_minutes) = config.transition_duration {
-            if !(MINIMUM_TRANSITION_DURATION..=MAXIMUM_TRANSITION_DURATION)
-                .contains(&duration_minutes)
-            {
-                anyhow::bail!(
-                    "Transition duration must be between {} and {} minutes",
-                    MINIMUM_TRANSITION_DURATION,
-                    MAXIMUM_TRANSITION_DURATION
-                );
-            }
+        if let Some(duration_minutes) = config.transition_duration
+            && !is_valid_transition_duration(duration_minutes)
+        {
+            anyhow::bail!(
+                "Transition duration must be 0 (instant switch) or between {} and {} minutes \
+                (measured in wall-clock time, so a DST change during a transition shortens or lengthens it)",
+                MINIMUM_TRANSITION_DURATION,
+                MAXIMUM_TRANSITION_DURATION
+            );
         }
 
-        if let Some(interval) = config.update_interval {
-            if !(MINIMUM_UPDATE_INTERVAL..=MAXIMUM_UPDATE_INTERVAL).contains(&interval) {
-                anyhow::bail!(
-                    "Update interval must be between {} and {} seconds",
-                    MINIMUM_UPDATE_INTERVAL,
-                    MAXIMUM_UPDATE_INTERVAL
-                );
-            }
+        if let Some(interval) = config.update_interval
+            && !(MINIMUM_UPDATE_INTERVAL..=MAXIMUM_UPDATE_INTERVAL).contains(&interval)
+        {
+            anyhow::bail!(
+                "Update interval must be between {} and {} seconds",
+                MINIMUM_UPDATE_INTERVAL,
+                MAXIMUM_UPDATE_INTERVAL
+            );
         }
 
         // Validate transition mode
-        if let Some(ref mode) = config.transition_mode {
-            if mode != "finish_by" && mode != "start_at" && mode != "center" && mode != "geo" {
-                anyhow::bail!(
-                    "Transition mode must be 'finish_by', 'start_at', 'center', or 'geo'"
-                );
-            }
+        if let Some(ref mode) = config.transition_mode
+            && mode != "finish_by"
+            && mode != "start_at"
+            && mode != "center"
+            && mode != "geo"
+            && mode != "manual_geo"
+            && mode != "appearance"
+        {
+            anyhow::bail!(
+                "Transition mode must be 'finish_by', 'start_at', 'center', 'geo', 'manual_geo', or 'appearance'"
+            );
         }
 
         // Validate startup transition duration
-        if let Some(duration_seconds) = config.startup_transition_duration {
-            if !(MINIMUM_STARTUP_TRANSITION_DURATION..=MAXIMUM_STARTUP_TRANSITION_DURATION)
+        if let Some(duration_seconds) = config.startup_transition_duration
+            && !(MINIMUM_STARTUP_TRANSITION_DURATION..=MAXIMUM_STARTUP_TRANSITION_DURATION)
                 .contains(&duration_seconds)
-            {
-                anyhow::bail!(
-                    "Startup transition duration must be between {} and {} seconds",
-                    MINIMUM_STARTUP_TRANSITION_DURATION,
-                    MAXIMUM_STARTUP_TRANSITION_DURATION
-                );
-            }
+        {
+            anyhow::bail!(
+                "Startup transition duration must be between {} and {} seconds",
+                MINIMUM_STARTUP_TRANSITION_DURATION,
+                MAXIMUM_STARTUP_TRANSITION_DURATION
+            );
         }
 
         // Validate latitude range (-90 to 90)
@@ -712,13 +1241,13 @@ impl Config {
         }
 
         // Validate longitude range (-180 to 180)
-        if let Some(lon) = config.longitude {
-            if !(-180.0..=180.0).contains(&lon) {
-                anyhow::bail!(
-                    "Longitude must be between -180 and 180 degrees (got {})",
-                    lon
-                );
-            }
+        if let Some(lon) = config.longitude
+            && !(-180.0..=180.0).contains(&lon)
+        {
+            anyhow::bail!(
+                "Longitude must be between -180 and 180 degrees (got {})",
+                lon
+            );
         }
 
         Ok(())
@@ -726,7 +1255,15 @@ impl Config {
 
     // NEW public method for loading from a specific path
     // This version does NOT create a default config if the path doesn't exist.
+    #[allow(dead_code)] // Used by the library API and tests
     pub fn load_from_path(path: &PathBuf) -> Result<Self> {
+        Self::load_from_path_with_profile(path, None)
+    }
+
+    /// Load configuration from a specific path, optionally overriding `active_profile`.
+    ///
+    /// Used when a profile has been selected at runtime so that it survives reloads.
+    pub fn load_from_path_with_profile(path: &PathBuf, profile: Option<&str>) -> Result<Self> {
         if !path.exists() {
             anyhow::bail!(
                 "Configuration file not found at specified path: {}",
@@ -737,13 +1274,105 @@ impl Config {
         let content = fs::read_to_string(path)
             .with_context(|| format!("Failed to read config from {}", path.display()))?;
 
-        let mut config: Config = toml::from_str(&content)
+        Self::load_from_content(&content, path, profile)
+    }
+
+    /// Build the configuration from file content and `SUNSETR_*` environment overrides.
+    ///
+    /// The system-wide config (`/etc/sunsetr/sunsetr.toml`), if any, is layered underneath.
+    /// The content may be empty when sunsetr is configured through the environment or
+    /// the system config alone.
+    fn load_from_content(content: &str, path: &Path, profile: Option<&str>) -> Result<Self> {
+        let system = match system_config_path() {
+            Some(system_path) => {
+                let content = fs::read_to_string(&system_path)
+                    .with_context(|| format!("Failed to read {}", system_path.display()))?;
+                Some((system_path, content))
+            }
+            None => None,
+        };
+        let system = system
+            .as_ref()
+            .map(|(system_path, content)| (system_path.as_path(), content.as_str()));
+        Self::load_from_layers(system, content, path, profile)
+    }
+
+    /// Build the configuration from the system and user layers plus environment overrides.
+    ///
+    /// Precedence is CLI (`profile`) > environment > user file > system file > defaults.
+    /// Layers are merged key by key, so the user's file only overrides the keys it sets;
+    /// tables such as `[profiles.movie]` are merged the same way.
+    fn load_from_layers(
+        system: Option<(&Path, &str)>,
+        content: &str,
+        path: &Path,
+        profile: Option<&str>,
+    ) -> Result<Self> {
+        let mut table = toml::Table::new();
+        if let Some((system_path, system)) = system {
+            check_unknown_keys(system)
+                .with_context(|| format!("Invalid configuration in {}", system_path.display()))?;
+            table = toml::from_str(system).with_context(|| {
+                format!("Failed to parse config from {}", system_path.display())
+            })?;
+        }
+
+        // Reject unknown keys before deserializing, since serde would silently ignore them
+        check_unknown_keys(content)
+            .with_context(|| format!("Invalid configuration in {}", path.display()))?;
+
+        let user: toml::Table = toml::from_str(content)
             .with_context(|| format!("Failed to parse config from {}", path.display()))?;
+        merge_tables(&mut table, user);
+
+        let overrides = env_overrides(std::env::vars());
+        for (key, value) in &overrides {
+            table.insert(key.clone(), value.clone());
+        }
+        let named_temperatures = resolve_named_temperatures(&mut table)
+            .with_context(|| format!("Invalid configuration in {}", path.display()))?;
+        if content.trim().is_empty() {
+            // Without a file, the only required keys may be missing entirely
+            for (key, default) in [("sunset", DEFAULT_SUNSET), ("sunrise", DEFAULT_SUNRISE)] {
+                table
+                    .entry(key)
+                    .or_insert_with(|| toml::Value::String(default.to_string()));
+            }
+        }
+
+        let mut config: Config = table.try_into().with_context(|| {
+            if overrides.is_empty() {
+                format!("Failed to parse config from {}", path.display())
+            } else {
+                format!(
+                    "Failed to parse config from {} and SUNSETR_* environment variables",
+                    path.display()
+                )
+            }
+        })?;
+
+        // Everything after this point, including validation, works in Kelvin
+        convert_temperature_units(&mut config, &named_temperatures)?;
+
+        if let Some(name) = profile {
+            config.active_profile = Some(name.to_string());
+        }
+
+        // Overlay the active profile before defaults so its values are validated too
+        Self::apply_active_profile(&mut config)?;
 
         Self::apply_defaults_and_validate_fields(&mut config)?;
 
-        // Load geo.toml overrides if present - pass the actual config path
+        // Load geo.toml overrides if present - pass the actual config path.
+        // Coordinates set through the environment still take precedence.
+        let coordinates = (config.latitude, config.longitude);
         Self::load_geo_override_from_path(&mut config, path)?;
+        if overrides.iter().any(|(key, _)| key == "latitude") {
+            config.latitude = coordinates.0;
+        }
+        if overrides.iter().any(|(key, _)| key == "longitude") {
+            config.longitude = coordinates.1;
+        }
 
         // Comprehensive configuration validation (this is the existing public function)
         validate_config(&config)?;
@@ -751,6 +1380,210 @@ impl Config {
         Ok(config)
     }
 
+    /// Validate all declared profiles and overlay the active one onto the top-level settings.
+    fn apply_active_profile(config: &mut Config) -> Result<()> {
+        if let Some(ref profiles) = config.profiles {
+            for (name, profile) in profiles {
+                if name == DEFAULT_PROFILE_NAME {
+                    anyhow::bail!(
+                        "Profile name '{}' is reserved for the base configuration",
+                        DEFAULT_PROFILE_NAME
+                    );
+                }
+                validate_profile(name, profile)?;
+            }
+        }
+
+        let name = match config.active_profile.as_deref() {
+            None | Some(DEFAULT_PROFILE_NAME) => return Ok(()),
+            Some(name) => name.to_string(),
+        };
+
+        let Some(profile) = config
+            .profiles
+            .as_ref()
+            .and_then(|profiles| profiles.get(&name))
+            .cloned()
+        else {
+            anyhow::bail!(
+                "Active profile '{}' is not defined. Available profiles: {}",
+                name,
+                config.profile_names().join(", ")
+            );
+        };
+
+        if let Some(temp) = profile.night_temp {
+            config.night_temp = Some(temp);
+        }
+        if let Some(temp) = profile.day_temp {
+            config.day_temp = Some(temp);
+        }
+        if let Some(gamma) = profile.night_gamma {
+            config.night_gamma = Some(gamma);
+        }
+        if let Some(gamma) = profile.day_gamma {
+            config.day_gamma = Some(gamma);
+        }
+
+        Ok(())
+    }
+
+    /// Night per-channel gamma multipliers as (red, green, blue).
+    pub fn night_channel_gamma(&self) -> (f32, f32, f32) {
+        (
+            self.night_gamma_r.unwrap_or(DEFAULT_CHANNEL_GAMMA),
+            self.night_gamma_g.unwrap_or(DEFAULT_CHANNEL_GAMMA),
+            self.night_gamma_b.unwrap_or(DEFAULT_CHANNEL_GAMMA),
+        )
+    }
+
+    /// Day per-channel gamma multipliers as (red, green, blue).
+    pub fn day_channel_gamma(&self) -> (f32, f32, f32) {
+        (
+            self.day_gamma_r.unwrap_or(DEFAULT_CHANNEL_GAMMA),
+            self.day_gamma_g.unwrap_or(DEFAULT_CHANNEL_GAMMA),
+            self.day_gamma_b.unwrap_or(DEFAULT_CHANNEL_GAMMA),
+        )
+    }
+
+    /// Whether any per-channel gamma multiplier is set.
+    pub fn has_channel_gamma(&self) -> bool {
+        [
+            self.night_gamma_r,
+            self.night_gamma_g,
+            self.night_gamma_b,
+            self.day_gamma_r,
+            self.day_gamma_g,
+            self.day_gamma_b,
+        ]
+        .iter()
+        .any(Option::is_some)
+    }
+
+    /// Color settings for outputs tagged as HDR via `hdr_outputs`.
+    ///
+    /// Returns a copy of this config with the `hdr_*` values in place of the regular
+    /// color settings. Any `hdr_*` value left unset falls back to the regular one,
+    /// including overrides from the active profile.
+    pub fn hdr_color_config(&self) -> Config {
+        let mut hdr = self.clone();
+        hdr.night_temp = self.hdr_night_temp.or(self.night_temp);
+        hdr.day_temp = self.hdr_day_temp.or(self.day_temp);
+        hdr.night_gamma = self.hdr_night_gamma.or(self.night_gamma);
+        hdr.day_gamma = self.hdr_day_gamma.or(self.day_gamma);
+        hdr
+    }
+
+    /// The transition mode in effect on `date`, taking `seasonal_modes` into account.
+    ///
+    /// Seasons follow [`season_for_date`], in the southern hemisphere when `latitude`
+    /// is negative. Seasons without an entry use `transition_mode`.
+    pub fn transition_mode_on(&self, date: chrono::NaiveDate) -> Option<&str> {
+        let southern = self.latitude.is_some_and(|lat| lat < 0.0);
+        self.seasonal_modes
+            .as_ref()
+            .and_then(|modes| modes.get(season_for_date(date, southern)))
+            .or(self.transition_mode.as_ref())
+            .map(String::as_str)
+    }
+
+    /// The configured `log_verbosity`, falling back to `normal`.
+    pub fn log_verbosity(&self) -> LogVerbosity {
+        self.log_verbosity
+            .as_deref()
+            .and_then(LogVerbosity::from_name)
+            .unwrap_or(LogVerbosity::Normal)
+    }
+
+    /// The transition mode in effect today (see [`Config::transition_mode_on`]).
+    pub fn current_transition_mode(&self) -> Option<&str> {
+        self.transition_mode_on(chrono::Local::now().date_naive())
+    }
+
+    /// The schedule of each `[output_groups.<name>]` table, by group name.
+    ///
+    /// Each entry is a copy of this config with the group's overrides in place. A group
+    /// that sets `transition_duration` replaces `sunset_duration`/`sunrise_duration` too.
+    pub fn output_group_configs(&self) -> Vec<(String, OutputGroupConfig, Config)> {
+        self.output_groups
+            .iter()
+            .flatten()
+            .map(|(name, group)| {
+                let mut config = self.clone();
+                config.output_groups = None;
+                if let Some(ref mode) = group.transition_mode {
+                    // The group's own mode holds all year
+                    config.transition_mode = Some(mode.clone());
+                    config.seasonal_modes = None;
+                }
+                if let Some(ref sunset) = group.sunset {
+                    config.sunset = sunset.clone();
+                }
+                if let Some(ref sunrise) = group.sunrise {
+                    config.sunrise = sunrise.clone();
+                }
+                if let Some(duration) = group.transition_duration {
+                    config.transition_duration = Some(duration);
+                    config.sunset_duration = None;
+                    config.sunrise_duration = None;
+                }
+                config.night_temp = group.night_temp.or(config.night_temp);
+                config.day_temp = group.day_temp.or(config.day_temp);
+                config.night_gamma = group.night_gamma.or(config.night_gamma);
+                config.day_gamma = group.day_gamma.or(config.day_gamma);
+                (name.clone(), group.clone(), config)
+            })
+            .collect()
+    }
+
+    /// Sunset and sunrise transition durations in minutes.
+    ///
+    /// `sunset_duration` and `sunrise_duration` override `transition_duration` for
+    /// their own transition. Geo mode's twilight anchor computes its own durations.
+    pub fn transition_durations(&self) -> (u64, u64) {
+        let shared = self
+            .transition_duration
+            .unwrap_or(DEFAULT_TRANSITION_DURATION);
+        (
+            self.sunset_duration.unwrap_or(shared),
+            self.sunrise_duration.unwrap_or(shared),
+        )
+    }
+
+    /// Whether day and night values are identical, which makes transitions no-ops.
+    ///
+    /// Outputs tagged via `hdr_outputs` are taken into account, since their values
+    /// may still differ.
+    pub fn is_color_shift_disabled(&self) -> bool {
+        let identical = |config: &Config| {
+            let effective = config.effective();
+            effective.night_temp == effective.day_temp
+                && effective.night_gamma == effective.day_gamma
+        };
+        identical(self) && (self.hdr_outputs.is_none() || identical(&self.hdr_color_config()))
+    }
+
+    /// Names of all selectable profiles, starting with the base configuration.
+    pub fn profile_names(&self) -> Vec<String> {
+        let mut names = vec![DEFAULT_PROFILE_NAME.to_string()];
+        if let Some(ref profiles) = self.profiles {
+            // BTreeMap keeps these sorted, giving a stable cycling order
+            names.extend(profiles.keys().cloned());
+        }
+        names
+    }
+
+    /// Name of the profile that follows the active one when cycling.
+    pub fn next_profile_name(&self) -> String {
+        let names = self.profile_names();
+        let current = self
+            .active_profile
+            .as_deref()
+            .unwrap_or(DEFAULT_PROFILE_NAME);
+        let index = names.iter().position(|n| n == current).unwrap_or(0);
+        names[(index + 1) % names.len()].clone()
+    }
+
     /// Load geo.toml from a specific config path
     fn load_geo_override_from_path(config: &mut Config, config_path: &Path) -> Result<()> {
         // Derive geo.toml path from the config path
@@ -807,17 +1640,41 @@ impl Config {
 
     // MODIFIED existing load method
     pub fn load() -> Result<Self> {
+        Self::load_with_profile(None)
+    }
+
+    /// Load configuration, optionally overriding the configured `active_profile`.
+    pub fn load_with_profile(profile: Option<&str>) -> Result<Self> {
         let config_path = Self::get_config_path()?;
 
-        if !config_path.exists() {
+        // Configured entirely through the environment: don't write a default file
+        let env_only = !config_path.exists() && !env_overrides(std::env::vars()).is_empty();
+        let safe_defaults = !config_path.exists() && !env_only && crate::utils::is_safe_mode();
+        // The admin's system-wide config stands in for a missing user config, so don't
+        // write a default file that would override every key it sets
+        let system_only =
+            !config_path.exists() && !env_only && !safe_defaults && system_config_path().is_some();
+
+        if safe_defaults {
+            Log::log_pipe();
+            Log::log_info(&format!(
+                "Safe mode: no config at {}, running on built-in defaults",
+                crate::utils::path_for_display(&config_path)
+            ));
+        } else if !config_path.exists() && !env_only && !system_only {
             Self::create_default_config(&config_path, None)
                 .context("Failed to create default config during load")?;
         }
 
-        // Now that we're sure a file exists (either pre-existing or newly created default),
-        // load it using the common path-based loader.
+        // Unless configured through the environment alone, a file now exists (either
+        // pre-existing or newly created default), so load it using the common path-based loader.
         // Note: load_from_path already calls load_geo_override_from_path, so we don't need to call it again
-        let mut config = Self::load_from_path(&config_path).with_context(|| {
+        let loaded = if env_only || safe_defaults || system_only {
+            Self::load_from_content("", &config_path, profile)
+        } else {
+            Self::load_from_path_with_profile(&config_path, profile)
+        };
+        let mut config = loaded.with_context(|| {
             Log::log_pipe();
             format!(
                 "Failed to load configuration from {}",
@@ -825,37 +1682,76 @@ impl Config {
             )
         })?;
 
-        // Check if we have geo mode but missing coordinates
-        if config.transition_mode.as_deref() == Some("geo")
-            && (config.latitude.is_none() || config.longitude.is_none())
+        // Geo mode without coordinates: detect them from the timezone or fail early
+        if let Some((lat, lon, city_name)) =
+            fill_geo_coordinates(&mut config, crate::geo::detect_coordinates_from_timezone)?
         {
-            // Try to detect coordinates from timezone
-            if let Ok((lat, lon, city_name)) = crate::geo::detect_coordinates_from_timezone() {
-                // Update the config file with detected coordinates
-                Log::log_pipe();
-                Log::log_block_start("Missing coordinates for geo mode");
-                Log::log_indented(&format!("Auto-detected location: {}", city_name));
-                Log::log_indented("Updating configuration with detected coordinates...");
+            Log::log_pipe();
+            Log::log_block_start("Missing coordinates for geo mode");
+            Log::log_indented(&format!("Auto-detected location: {}", city_name));
 
-                // Update the config file
+            // Update the config file, unless there is none to update
+            if config_path.exists() && !crate::utils::is_safe_mode() {
+                Log::log_indented("Updating configuration with detected coordinates...");
                 Self::update_config_with_geo_coordinates(lat, lon)?;
-
-                // Update our in-memory config
-                config.latitude = Some(lat);
-                config.longitude = Some(lon);
-            } else {
-                Log::log_pipe();
-                Log::log_error("Geo mode requires coordinates but none are configured");
-                Log::log_indented("Please run 'sunsetr --geo' to select your location");
-                std::process::exit(crate::constants::EXIT_FAILURE);
             }
         }
 
         Ok(config)
     }
 
+    /// Backends `backend = "auto"` tries in a Wayland session, most preferred first.
+    pub fn auto_prefer(&self) -> Vec<Backend> {
+        match &self.auto_prefer {
+            Some(names) => names
+                .iter()
+                .filter_map(|name| Backend::from_name(name))
+                .collect(),
+            None => DEFAULT_AUTO_PREFER.to_vec(),
+        }
+    }
+
+    /// Whether to reset to neutral when exiting because the backend became unavailable.
+    ///
+    /// `on_fatal_exit = "leave"` only releases the gamma controls, so a fallback tool
+    /// can keep the night values; unset, it follows `reset_on_exit`.
+    pub fn reset_on_fatal_exit(&self) -> bool {
+        match self.on_fatal_exit.as_deref() {
+            Some("reset") => true,
+            Some("leave") => false,
+            _ => self.effective().reset_on_exit,
+        }
+    }
+
+    /// The event that follows the sun in `"manual_geo"` mode.
+    pub fn manual_geo_event(&self) -> ManualGeoEvent {
+        ManualGeoEvent::from_name(
+            self.manual_geo_event
+                .as_deref()
+                .unwrap_or(DEFAULT_MANUAL_GEO_EVENT),
+        )
+        .unwrap_or(ManualGeoEvent::Sunset)
+    }
+
+    /// Whether geo mode is used, either as `transition_mode` or for a season.
+    ///
+    /// `"manual_geo"` counts, since one of its events needs coordinates.
+    pub fn uses_geo_mode(&self) -> bool {
+        matches!(self.transition_mode.as_deref(), Some("geo" | "manual_geo"))
+            || self
+                .seasonal_modes
+                .iter()
+                .flatten()
+                .any(|(_, mode)| mode == "geo")
+    }
+
     /// Update an existing config file with geo coordinates and mode
     pub fn update_config_with_geo_coordinates(mut latitude: f64, longitude: f64) -> Result<()> {
+        if crate::utils::is_safe_mode() {
+            Log::log_info("Safe mode: not writing coordinates to the config");
+            return Ok(());
+        }
+
         let config_path = Self::get_config_path()?;
         let geo_path = Self::get_geo_path()?;
 
@@ -886,11 +1782,13 @@ impl Config {
 
             let mut updated_content = content.clone();
 
-            // Update or add transition_mode to "geo"
+            // Update or add transition_mode to "geo", keeping "manual_geo" which also uses them
             if let Some(mode_line) = find_config_line(&content, "transition_mode") {
-                let new_mode_line =
-                    preserve_comment_formatting(&mode_line, "transition_mode", "\"geo\"");
-                updated_content = updated_content.replace(&mode_line, &new_mode_line);
+                if !mode_line.contains("\"manual_geo\"") {
+                    let new_mode_line =
+                        preserve_comment_formatting(&mode_line, "transition_mode", "\"geo\"");
+                    updated_content = updated_content.replace(&mode_line, &new_mode_line);
+                }
             } else {
                 // Add transition_mode at the end
                 updated_content = format!("{}transition_mode = \"geo\"\n", updated_content);
@@ -963,8 +1861,8 @@ impl Config {
 
         // Update transition_mode to "geo" only if it's not already set to "geo"
         if let Some(mode_line) = find_config_line(&content, "transition_mode") {
-            // Check if it's already set to "geo"
-            if !mode_line.contains("\"geo\"") {
+            // Check if it's already set to "geo" (or "manual_geo", which also uses coordinates)
+            if !mode_line.contains("\"geo\"") && !mode_line.contains("\"manual_geo\"") {
                 let new_mode_line =
                     preserve_comment_formatting(&mode_line, "transition_mode", "\"geo\"");
                 updated_content = updated_content.replace(&mode_line, &new_mode_line);
@@ -993,16 +1891,59 @@ impl Config {
         Ok(())
     }
 
-    pub fn log_config(&self) {
-        let config_path = Self::get_config_path()
-            .unwrap_or_else(|_| PathBuf::from("~/.config/sunsetr/sunsetr.toml"));
-        let geo_path =
-            Self::get_geo_path().unwrap_or_else(|_| PathBuf::from("~/.config/sunsetr/geo.toml"));
+    /// Set a top-level key in the active config file, keeping its comment.
+    ///
+    /// `value` is written as given, so strings must include their quotes. A key that
+    /// isn't set yet is added before the first table. Returns the file's path.
+    pub fn update_config_value(key: &str, value: &str) -> Result<PathBuf> {
+        let config_path = Self::get_config_path()?;
+        if !config_path.exists() {
+            anyhow::bail!("No existing config file found at {}", config_path.display());
+        }
 
-        Log::log_block_start(&format!(
-            "Loaded configuration from {}",
-            crate::utils::path_for_display(&config_path)
-        ));
+        let content = fs::read_to_string(&config_path)
+            .with_context(|| format!("Failed to read config from {}", config_path.display()))?;
+        fs::write(&config_path, set_top_level_value(&content, key, value)).with_context(|| {
+            format!(
+                "Failed to write updated config to {}",
+                config_path.display()
+            )
+        })?;
+        Ok(config_path)
+    }
+
+    pub fn log_config(&self) {
+        let effective = self.effective();
+        let config_path = Self::get_config_path()
+            .unwrap_or_else(|_| PathBuf::from("~/.config/sunsetr/sunsetr.toml"));
+        let geo_path =
+            Self::get_geo_path().unwrap_or_else(|_| PathBuf::from("~/.config/sunsetr/geo.toml"));
+
+        let system_config = system_config_path();
+        if config_path.exists() {
+            Log::log_block_start(&format!(
+                "Loaded configuration from {}",
+                crate::utils::path_for_display(&config_path)
+            ));
+        } else if let Some(ref system_config) = system_config {
+            Log::log_block_start(&format!(
+                "Loaded configuration from {}",
+                system_config.display()
+            ));
+        } else {
+            Log::log_block_start("Loaded configuration from SUNSETR_* environment variables");
+        }
+        if config_path.exists()
+            && let Some(ref system_config) = system_config
+        {
+            Log::log_indented(&format!("System defaults: {}", system_config.display()));
+        }
+
+        let overrides = env_overrides(std::env::vars());
+        if !overrides.is_empty() {
+            let keys: Vec<&str> = overrides.iter().map(|(key, _)| key.as_str()).collect();
+            Log::log_indented(&format!("Environment overrides: {}", keys.join(", ")));
+        }
 
         // Check if geo.toml exists to show appropriate message
         if geo_path.exists() {
@@ -1016,34 +1957,55 @@ impl Config {
             "Backend: {}",
             self.backend.as_ref().unwrap_or(&DEFAULT_BACKEND).as_str()
         ));
+        if self.auto_prefer.is_some() {
+            let order: Vec<&str> = self.auto_prefer().iter().map(Backend::as_str).collect();
+            Log::log_indented(&format!("Auto-detection order: {}", order.join(", ")));
+        }
         Log::log_indented(&format!(
             "Auto-start hyprsunset: {}",
-            self.start_hyprsunset.unwrap_or(DEFAULT_START_HYPRSUNSET)
+            effective.start_hyprsunset
         ));
+        if let Some(ref socket) = self.hyprsunset_socket {
+            Log::log_indented(&format!("Hyprsunset socket: {}", socket));
+        }
+        if self.hyprsunset_connect_timeout.is_some() || self.hyprsunset_connect_retries.is_some() {
+            Log::log_indented(&format!(
+                "Hyprsunset connect wait: {} x {}s",
+                effective.hyprsunset_connect_retries, effective.hyprsunset_connect_timeout
+            ));
+        }
         Log::log_indented(&format!(
             "Enable startup transition: {}",
-            self.startup_transition
-                .unwrap_or(DEFAULT_STARTUP_TRANSITION)
+            effective.startup_transition
         ));
 
         // Only show startup transition duration if startup transition is enabled
-        if self
-            .startup_transition
-            .unwrap_or(DEFAULT_STARTUP_TRANSITION)
-        {
+        if effective.startup_transition {
             Log::log_indented(&format!(
                 "Startup transition duration: {} seconds",
-                self.startup_transition_duration
-                    .unwrap_or(DEFAULT_STARTUP_TRANSITION_DURATION)
+                effective.startup_transition_duration
             ));
         }
 
+        // Only show the active profile if one other than the base is selected
+        if let Some(ref profile) = self.active_profile
+            && profile != DEFAULT_PROFILE_NAME
+        {
+            Log::log_indented(&format!("Active profile: {}", profile));
+        }
+
+        // Only show startup delay if one is configured
+        let startup_delay_ms = effective.startup_delay_ms;
+        if startup_delay_ms > 0 {
+            Log::log_indented(&format!("Startup delay: {} ms", startup_delay_ms));
+        }
+
         // Show geographic coordinates if in geo mode
         let mode = self
             .transition_mode
             .as_deref()
             .unwrap_or(DEFAULT_TRANSITION_MODE);
-        if mode == "geo" {
+        if mode == "geo" || mode == "manual_geo" {
             if let (Some(lat), Some(lon)) = (self.latitude, self.longitude) {
                 let lat_dir = if lat >= 0.0 { "N" } else { "S" };
                 let lon_dir = if lon >= 0.0 { "E" } else { "W" };
@@ -1061,892 +2023,3718 @@ impl Config {
 
         Log::log_indented(&format!("Sunset time: {}", self.sunset));
         Log::log_indented(&format!("Sunrise time: {}", self.sunrise));
-        Log::log_indented(&format!(
-            "Night temperature: {}K",
-            self.night_temp.unwrap_or(DEFAULT_NIGHT_TEMP)
-        ));
-        Log::log_indented(&format!(
-            "Day temperature: {}K",
-            self.day_temp.unwrap_or(DEFAULT_DAY_TEMP)
-        ));
-        Log::log_indented(&format!(
-            "Night gamma: {}%",
-            self.night_gamma.unwrap_or(DEFAULT_NIGHT_GAMMA)
-        ));
-        Log::log_indented(&format!(
-            "Day gamma: {}%",
-            self.day_gamma.unwrap_or(DEFAULT_DAY_GAMMA)
-        ));
-        Log::log_indented(&format!(
-            "Transition duration: {} minutes",
-            self.transition_duration
-                .unwrap_or(DEFAULT_TRANSITION_DURATION)
-        ));
+        if effective.color_shift {
+            Log::log_indented(&format!("Night temperature: {}K", effective.night_temp));
+            Log::log_indented(&format!("Day temperature: {}K", effective.day_temp));
+        } else {
+            Log::log_indented(&format!(
+                "Color shift: disabled (temperature fixed at {}K)",
+                NEUTRAL_TEMP
+            ));
+        }
+        Log::log_indented(&format!("Night gamma: {}%", effective.night_gamma));
+        Log::log_indented(&format!("Day gamma: {}%", effective.day_gamma));
+        if self.temp_unit.as_deref() == Some("mired") {
+            Log::log_indented("Temperature unit: mired (shown converted to Kelvin)");
+        }
+        // Only show the temperature quantum when snapping is enabled
+        let temp_quantum = effective.temp_quantum;
+        if temp_quantum > 1 {
+            Log::log_indented(&format!("Temperature quantum: {}K", temp_quantum));
+        }
+        if let Some([x, y]) = self.day_whitepoint {
+            Log::log_indented(&format!("Day whitepoint: x={:.4}, y={:.4}", x, y));
+        }
+        if self.has_channel_gamma() {
+            let (nr, ng, nb) = self.night_channel_gamma();
+            let (dr, dg, db) = self.day_channel_gamma();
+            Log::log_indented(&format!(
+                "Channel gamma (R/G/B): night {}/{}/{}, day {}/{}/{}",
+                nr, ng, nb, dr, dg, db
+            ));
+        }
+        if let Some(ref include) = self.outputs_include {
+            Log::log_indented(&format!("Outputs include: {}", include.join(", ")));
+        }
+        if let Some(ref exclude) = self.outputs_exclude {
+            Log::log_indented(&format!("Outputs exclude: {}", exclude.join(", ")));
+        }
+        if let Some(ref hdr) = self.hdr_outputs {
+            let hdr_config = self.hdr_color_config();
+            Log::log_indented(&format!(
+                "HDR outputs: {} ({}K/{}K, {}%/{}%)",
+                hdr.join(", "),
+                hdr_config.night_temp.unwrap_or(DEFAULT_NIGHT_TEMP),
+                hdr_config.day_temp.unwrap_or(DEFAULT_DAY_TEMP),
+                hdr_config.night_gamma.unwrap_or(DEFAULT_NIGHT_GAMMA),
+                hdr_config.day_gamma.unwrap_or(DEFAULT_DAY_GAMMA)
+            ));
+        }
+        for (name, group, _) in self.output_group_configs() {
+            Log::log_indented(&format!(
+                "Output group '{}': {}",
+                name,
+                group.outputs.join(", ")
+            ));
+        }
+        if let Some(port) = self.metrics_port {
+            Log::log_indented(&format!("Metrics port: {}", port));
+        }
+        if effective.reapply_on_unlock {
+            Log::log_indented("Reapply on unlock/resume: enabled");
+        }
+        if effective.event_fifo {
+            Log::log_indented("Event fifo: enabled");
+        }
+        if let Some(timeout) = self.idle_timeout {
+            Log::log_indented(&format!(
+                "Idle dimming: {}% of gamma after {}s idle",
+                effective.idle_gamma, timeout
+            ));
+        }
+        if let Some(ref log_file) = self.log_file {
+            Log::log_indented(&format!("Log file: {}", log_file));
+        }
+        if let Some(ref verbosity) = self.log_verbosity
+            && verbosity != DEFAULT_LOG_VERBOSITY
+        {
+            Log::log_indented(&format!("Log verbosity: {}", verbosity));
+        }
+        for (key, command) in [
+            ("on_day_command", &self.on_day_command),
+            ("on_night_command", &self.on_night_command),
+            ("on_transition_start", &self.on_transition_start),
+        ] {
+            if let Some(command) = command {
+                Log::log_indented(&format!("Hook {}: {}", key, command));
+            }
+        }
+        // Only show the wake fade when it differs from the default
+        let wake_fade_ms = effective.wake_fade_ms;
+        if wake_fade_ms != DEFAULT_WAKE_FADE_MS {
+            Log::log_indented(&format!("Wake fade: {} ms", wake_fade_ms));
+        }
+        if effective.require_outputs {
+            Log::log_indented("Require outputs at startup: enabled");
+        }
+        if !effective.reset_on_exit {
+            Log::log_indented("Reset on exit: disabled (gamma released to the compositor)");
+        }
+        if let Some(ref on_fatal_exit) = self.on_fatal_exit {
+            Log::log_indented(&format!("On fatal exit: {}", on_fatal_exit));
+        }
+        // Only show gamma verification when it has been turned off
+        if !effective.verify_gamma {
+            Log::log_indented("Gamma verification: disabled");
+        }
+        if effective.precompute_ramps {
+            Log::log_indented("Precomputed transition ramps: enabled");
+        }
+        if let Some(ref format) = self.gamma_table_format
+            && format != DEFAULT_GAMMA_TABLE_FORMAT
+        {
+            Log::log_indented(&format!("Gamma table format: {}", format));
+        }
+        let (sunset_minutes, sunrise_minutes) = self.transition_durations();
+        if sunset_minutes == sunrise_minutes {
+            Log::log_indented(&format!("Transition duration: {} minutes", sunset_minutes));
+        } else {
+            Log::log_indented(&format!(
+                "Transition duration: {} minutes at sunset, {} at sunrise",
+                sunset_minutes, sunrise_minutes
+            ));
+        }
         Log::log_indented(&format!(
             "Update interval: {} seconds",
-            self.update_interval.unwrap_or(DEFAULT_UPDATE_INTERVAL)
+            effective.update_interval
         ));
+        if let (Some(temp), Some(minutes)) = (self.sunrise_hold_temp, self.sunrise_hold_minutes) {
+            Log::log_indented(&format!(
+                "Sunrise pauses at {}K for {} minutes",
+                temp, minutes
+            ));
+        }
+        if let Some(tail) = self.ease_out_tail_seconds.filter(|&tail| tail > 0) {
+            Log::log_indented(&format!("Ease-out tail: {} seconds", tail));
+        }
+        if let Some(offset) = self.gamma_progress_offset.filter(|&offset| offset != 0.0) {
+            Log::log_indented(&format!(
+                "Gamma {} temperature by {}% of each transition",
+                if offset > 0.0 { "lags" } else { "leads" },
+                (offset.abs() * 100.0).round()
+            ));
+        }
         Log::log_indented(&format!(
             "Transition mode: {}",
             self.transition_mode
                 .as_deref()
                 .unwrap_or(DEFAULT_TRANSITION_MODE)
         ));
+        if mode == "manual_geo" {
+            let event = self.manual_geo_event();
+            Log::log_indented(&format!(
+                "Geo-driven event: {} (the {} uses the configured time)",
+                event.name(),
+                event.other().name()
+            ));
+        }
+        if let Some(modes) = self.seasonal_modes.as_ref().filter(|m| !m.is_empty()) {
+            let seasons: Vec<String> = SEASON_NAMES
+                .iter()
+                .filter_map(|season| {
+                    modes
+                        .get(*season)
+                        .map(|mode| format!("{} {}", season, mode))
+                })
+                .collect();
+            Log::log_indented(&format!(
+                "Seasonal modes: {} (today: {})",
+                seasons.join(", "),
+                self.current_transition_mode()
+                    .unwrap_or(DEFAULT_TRANSITION_MODE)
+            ));
+        }
+        // Only show the geo anchor when transitions are anchored to solar noon
+        if self.geo_anchor.as_deref() == Some("solar_noon") {
+            Log::log_indented(&format!(
+                "Geo anchor: solar noon ±{} hours",
+                effective.geo_noon_offset
+            ));
+        } else {
+            if self.solar_precision.as_deref() == Some("precise") {
+                Log::log_indented("Solar precision: precise");
+            }
+            if let Some(boundary) = self
+                .geo_night_boundary
+                .as_deref()
+                .filter(|&b| b != DEFAULT_GEO_NIGHT_BOUNDARY)
+            {
+                Log::log_indented(&format!("Night boundary: {} dusk/dawn", boundary));
+            }
+        }
+        if let Some(minutes) = self.geo_round_to_minutes.filter(|&m| m > 1) {
+            Log::log_indented(&format!("Geo boundaries rounded to: {} minutes", minutes));
+        }
     }
 }
 
-/// Comprehensive configuration validation to prevent impossible or problematic setups
-pub fn validate_config(config: &Config) -> Result<()> {
-    use chrono::NaiveTime;
+/// Keys (besides the same keys inside `[profiles.<name>]`) that accept a preset or hex color.
+const NAMED_TEMPERATURE_KEYS: &[&str] = &["night_temp", "day_temp"];
 
-    // 0. Validate backend configuration compatibility
-    let backend = config.backend.as_ref().unwrap_or(&DEFAULT_BACKEND);
-    let start_hyprsunset = config.start_hyprsunset.unwrap_or(DEFAULT_START_HYPRSUNSET);
+/// Replace temperature presets and hex colors in a parsed config table with Kelvin values.
+///
+/// Applies to `night_temp` and `day_temp`, at the top level, in profiles and in output
+/// groups. Returns the
+/// dotted keys that were replaced; they are in Kelvin whatever `temp_unit` says.
+fn resolve_named_temperatures(table: &mut toml::Table) -> Result<Vec<String>> {
+    let mut resolved = Vec::new();
+
+    let mut resolve = |prefix: &str, table: &mut toml::Table| -> Result<()> {
+        for key in NAMED_TEMPERATURE_KEYS {
+            if let Some(toml::Value::String(name)) = table.get(*key) {
+                let full_key = format!("{}{}", prefix, key);
+                let kelvin = temperature_from_name(&full_key, name)?;
+                table.insert(key.to_string(), toml::Value::Integer(kelvin.into()));
+                resolved.push(full_key);
+            }
+        }
+        Ok(())
+    };
 
-    // Only validate explicit backend choices, Auto will be resolved at runtime
-    if *backend == Backend::Wayland && start_hyprsunset {
-        anyhow::bail!(
-            "Incompatible configuration: backend=\"wayland\" and start_hyprsunset=true. \
-            When using Wayland protocols (backend=\"wayland\"), hyprsunset should not be started (start_hyprsunset=false). \
-            Please set either backend=\"hyprland\" or start_hyprsunset=false."
-        );
+    resolve("", table)?;
+    for section in ["profiles", "output_groups"] {
+        if let Some(toml::Value::Table(entries)) = table.get_mut(section) {
+            for (name, entry) in entries.iter_mut() {
+                if let toml::Value::Table(entry) = entry {
+                    resolve(&format!("{}.{}.", section, name), entry)?;
+                }
+            }
+        }
     }
 
-    let sunset = NaiveTime::parse_from_str(&config.sunset, "%H:%M:%S")
-        .context("Invalid sunset time format")?;
-    let sunrise = NaiveTime::parse_from_str(&config.sunrise, "%H:%M:%S")
-        .context("Invalid sunrise time format")?;
-
-    let transition_duration_mins = config
-        .transition_duration
-        .unwrap_or(DEFAULT_TRANSITION_DURATION);
-    let update_interval_secs = config.update_interval.unwrap_or(DEFAULT_UPDATE_INTERVAL);
-    let mode = config
-        .transition_mode
-        .as_deref()
-        .unwrap_or(DEFAULT_TRANSITION_MODE);
+    Ok(resolved)
+}
 
-    // Validate transition duration (hard limits)
-    if !(MINIMUM_TRANSITION_DURATION..=MAXIMUM_TRANSITION_DURATION)
-        .contains(&transition_duration_mins)
+/// Resolve a temperature preset (see [`TEMPERATURE_PRESETS`]) or `#rrggbb` color to Kelvin.
+///
+/// Hex colors map to the temperature whose white point has the closest hue. Colors no
+/// temperature can produce (e.g. green) are rejected rather than approximated.
+fn temperature_from_name(key: &str, name: &str) -> Result<u32> {
+    let name = name.trim();
+    if let Some(&(_, kelvin)) = TEMPERATURE_PRESETS
+        .iter()
+        .find(|(preset, _)| preset.eq_ignore_ascii_case(name))
     {
-        anyhow::bail!(
-            "Transition duration ({} minutes) must be between {} and {} minutes",
-            transition_duration_mins,
-            MINIMUM_TRANSITION_DURATION,
-            MAXIMUM_TRANSITION_DURATION
-        );
+        return Ok(kelvin);
     }
 
-    // Validate startup transition duration (hard limits)
-    if let Some(startup_duration_secs) = config.startup_transition_duration {
-        if !(MINIMUM_STARTUP_TRANSITION_DURATION..=MAXIMUM_STARTUP_TRANSITION_DURATION)
-            .contains(&startup_duration_secs)
-        {
+    let hex = name.strip_prefix('#').unwrap_or(name);
+    let channel = |i: usize| {
+        hex.get(i..i + 2)
+            .and_then(|c| u8::from_str_radix(c, 16).ok())
+    };
+    if hex.len() == 6
+        && let (Some(r), Some(g), Some(b)) = (channel(0), channel(2), channel(4))
+    {
+        let (kelvin, distance) = crate::backend::wayland::gamma::nearest_temperature(
+            (r, g, b),
+            MINIMUM_TEMP,
+            MAXIMUM_TEMP,
+        );
+        if distance > MAXIMUM_HEX_TEMPERATURE_DISTANCE {
             anyhow::bail!(
-                "Startup transition duration ({} seconds) must be between {} and {} seconds",
-                startup_duration_secs,
-                MINIMUM_STARTUP_TRANSITION_DURATION,
-                MAXIMUM_STARTUP_TRANSITION_DURATION
+                "{} (\"{}\") isn't a color a temperature can produce; the closest is {}K",
+                key,
+                name,
+                kelvin
             );
         }
+        return Ok(kelvin);
     }
 
-    // 0. Validate basic ranges for temperature and gamma (hard limits)
-    if let Some(temp) = config.night_temp {
-        if !(MINIMUM_TEMP..=MAXIMUM_TEMP).contains(&temp) {
-            anyhow::bail!(
-                "Night temperature ({}) must be between {} and {} Kelvin",
-                temp,
-                MINIMUM_TEMP,
-                MAXIMUM_TEMP
-            );
-        }
+    let presets: Vec<&str> = TEMPERATURE_PRESETS.iter().map(|(name, _)| *name).collect();
+    anyhow::bail!(
+        "{} (\"{}\") must be a temperature, a preset ({}) or a hex color like \"#ffb46b\"",
+        key,
+        name,
+        presets.join(", ")
+    )
+}
+
+/// Convert temperatures given in mireds (`temp_unit = "mired"`) to Kelvin in place.
+///
+/// Covers the regular, HDR, profile and output group temperatures. `temp_quantum` always stays in
+/// Kelvin, as do the keys in `already_kelvin` (presets and hex colors). Each converted
+/// value must land in the supported Kelvin range.
+fn convert_temperature_units(config: &mut Config, already_kelvin: &[String]) -> Result<()> {
+    match config.temp_unit.as_deref().unwrap_or(DEFAULT_TEMP_UNIT) {
+        "kelvin" => return Ok(()),
+        "mired" => {}
+        other => anyhow::bail!("temp_unit (\"{}\") must be \"kelvin\" or \"mired\"", other),
     }
 
-    if let Some(temp) = config.day_temp {
-        if !(MINIMUM_TEMP..=MAXIMUM_TEMP).contains(&temp) {
+    let convert = |key: &str, value: &mut Option<u32>| -> Result<()> {
+        let Some(mired) = *value else {
+            return Ok(());
+        };
+        if already_kelvin.iter().any(|k| k == key) {
+            return Ok(());
+        }
+        let kelvin = crate::utils::mired_to_kelvin(mired);
+        if !(MINIMUM_TEMP..=MAXIMUM_TEMP).contains(&kelvin) {
             anyhow::bail!(
-                "Day temperature ({}) must be between {} and {} Kelvin",
-                temp,
+                "{} ({} mired) must be between {} and {} mired ({}-{}K)",
+                key,
+                mired,
+                crate::utils::kelvin_to_mired(MAXIMUM_TEMP),
+                crate::utils::kelvin_to_mired(MINIMUM_TEMP),
                 MINIMUM_TEMP,
                 MAXIMUM_TEMP
             );
         }
+        *value = Some(kelvin);
+        Ok(())
+    };
+
+    convert("night_temp", &mut config.night_temp)?;
+    convert("day_temp", &mut config.day_temp)?;
+    convert("hdr_night_temp", &mut config.hdr_night_temp)?;
+    convert("sunrise_hold_temp", &mut config.sunrise_hold_temp)?;
+    convert("hdr_day_temp", &mut config.hdr_day_temp)?;
+    for (name, profile) in config.profiles.iter_mut().flatten() {
+        convert(
+            &format!("profiles.{}.night_temp", name),
+            &mut profile.night_temp,
+        )?;
+        convert(
+            &format!("profiles.{}.day_temp", name),
+            &mut profile.day_temp,
+        )?;
+    }
+    for (name, group) in config.output_groups.iter_mut().flatten() {
+        convert(
+            &format!("output_groups.{}.night_temp", name),
+            &mut group.night_temp,
+        )?;
+        convert(
+            &format!("output_groups.{}.day_temp", name),
+            &mut group.day_temp,
+        )?;
     }
 
-    if let Some(gamma) = config.night_gamma {
-        if !(MINIMUM_GAMMA..=MAXIMUM_GAMMA).contains(&gamma) {
-            anyhow::bail!(
-                "Night gamma ({}%) must be between {}% and {}%",
-                gamma,
-                MINIMUM_GAMMA,
-                MAXIMUM_GAMMA
-            );
-        }
+    Ok(())
+}
+
+/// Collect `SUNSETR_*` overrides for known config keys from environment variables.
+///
+/// `SUNSETR_NIGHT_TEMP=3000` overrides `night_temp`. Values are read as TOML
+/// (`4500`, `true`, `["DP-1"]`), falling back to a plain string when they don't
+/// parse, so `SUNSETR_BACKEND=wayland` needs no quotes. Times like `19:00:00`
+/// are kept as strings too. Profiles and output groups can't be defined this way, and other
+/// `SUNSETR_*` variables are ignored.
+fn env_overrides(vars: impl Iterator<Item = (String, String)>) -> Vec<(String, toml::Value)> {
+    let mut overrides: Vec<(String, toml::Value)> = vars
+        .filter_map(|(name, raw)| {
+            let key = name.strip_prefix(CONFIG_ENV_PREFIX)?.to_ascii_lowercase();
+            if key == "profiles"
+                || key == "output_groups"
+                || key == "seasonal_modes"
+                || !KNOWN_CONFIG_KEYS.contains(&key.as_str())
+            {
+                return None;
+            }
+
+            // With testing-support, SUNSETR_BACKEND=mock selects the mock backend instead
+            #[cfg(feature = "testing-support")]
+            if key == "backend" && raw == "mock" {
+                return None;
+            }
+
+            Some((key, parse_env_value(&raw)))
+        })
+        .collect();
+
+    // Environment order is arbitrary, keep overrides (and their log line) stable
+    overrides.sort_by(|a, b| a.0.cmp(&b.0));
+    overrides
+}
+
+/// Where the system-wide config is looked for, or `None` when the lookup is disabled.
+///
+/// `SUNSETR_SYSTEM_CONFIG` replaces `/etc/sunsetr/sunsetr.toml`, and setting it to an
+/// empty value turns the system config off, e.g. for tests that must not depend on
+/// the machine they run on.
+pub fn system_config_location() -> Option<PathBuf> {
+    system_config_location_from(std::env::var_os(SYSTEM_CONFIG_ENV))
+}
+
+fn system_config_location_from(value: Option<std::ffi::OsString>) -> Option<PathBuf> {
+    match value {
+        Some(value) if value.is_empty() => None,
+        Some(value) => Some(PathBuf::from(value)),
+        None => Some(PathBuf::from(SYSTEM_CONFIG_PATH)),
     }
+}
 
-    if let Some(gamma) = config.day_gamma {
-        if !(MINIMUM_GAMMA..=MAXIMUM_GAMMA).contains(&gamma) {
-            anyhow::bail!(
-                "Day gamma ({}%) must be between {}% and {}%",
-                gamma,
-                MINIMUM_GAMMA,
-                MAXIMUM_GAMMA
-            );
+/// The system-wide config file, when one exists and should be used.
+///
+/// Safe mode ignores it like any other config file, and the library's unit tests
+/// never read it.
+pub fn system_config_path() -> Option<PathBuf> {
+    if cfg!(test) || crate::utils::is_safe_mode() {
+        return None;
+    }
+    system_config_location().filter(|path| path.exists())
+}
+
+/// Overlay `overlay` onto `base` key by key.
+///
+/// Tables present in both are merged recursively; any other value in `overlay`
+/// replaces the one in `base`.
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(overlay_table)) => {
+                merge_tables(base_table, overlay_table)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
         }
     }
+}
 
-    // 1. Check for identical sunset/sunrise times
-    if sunset == sunrise {
-        anyhow::bail!(
-            "Sunset and sunrise cannot be the same time ({:?}). \
-            There must be a distinction between day and night periods.",
-            sunset
-        );
+/// Interpret an environment variable's value as a TOML value.
+fn parse_env_value(raw: &str) -> toml::Value {
+    match format!("value = {}", raw).parse::<toml::Table>() {
+        Ok(mut table) => match table.remove("value") {
+            Some(toml::Value::Datetime(_)) | None => toml::Value::String(raw.to_string()),
+            Some(value) => value,
+        },
+        Err(_) => toml::Value::String(raw.to_string()),
     }
+}
 
-    // 2. Calculate time periods and check minimums
-    let (day_duration_mins, night_duration_mins) = calculate_day_night_durations(sunset, sunrise);
+/// Error on any key not recognized by sunsetr, suggesting the closest known key.
+fn check_unknown_keys(content: &str) -> Result<()> {
+    // Syntax errors are reported by the typed parse that follows
+    let Ok(table) = content.parse::<toml::Table>() else {
+        return Ok(());
+    };
 
-    if day_duration_mins < 60 {
-        anyhow::bail!(
-            "Day period is too short ({} minutes). \
-            Day period must be at least 1 hour. \
-            Adjust sunset ({:?}) or sunrise ({:?}) times.",
-            day_duration_mins,
-            sunset,
-            sunrise
-        );
+    match unknown_key_messages(&table).into_iter().next() {
+        Some(message) => anyhow::bail!("{}", message),
+        None => Ok(()),
     }
+}
 
-    if night_duration_mins < 60 {
-        anyhow::bail!(
-            "Night period is too short ({} minutes). \
-            Night period must be at least 1 hour. \
-            Adjust sunset ({:?}) or sunrise ({:?}) times.",
-            night_duration_mins,
-            sunset,
-            sunrise
-        );
+/// Describe every unrecognized key in a parsed config, including inside profiles
+/// and output groups.
+fn unknown_key_messages(table: &toml::Table) -> Vec<String> {
+    let mut messages: Vec<String> = table
+        .keys()
+        .filter(|key| !KNOWN_CONFIG_KEYS.contains(&key.as_str()))
+        .map(|key| unknown_key_message(key, KNOWN_CONFIG_KEYS, None))
+        .collect();
+
+    for (section, known) in [
+        ("profiles", KNOWN_PROFILE_KEYS),
+        ("output_groups", KNOWN_OUTPUT_GROUP_KEYS),
+    ] {
+        if let Some(toml::Value::Table(entries)) = table.get(section) {
+            for (name, entry) in entries {
+                if let toml::Value::Table(entry) = entry {
+                    let location = format!("{}.{}", section, name);
+                    messages.extend(
+                        entry
+                            .keys()
+                            .filter(|key| !known.contains(&key.as_str()))
+                            .map(|key| unknown_key_message(key, known, Some(&location))),
+                    );
+                }
+            }
+        }
     }
 
-    // 3. Check that transitions fit within their periods
-    validate_transitions_fit_periods(sunset, sunrise, transition_duration_mins, mode)?;
+    messages
+}
 
-    // 4. Check for transition overlaps
-    validate_no_transition_overlaps(sunset, sunrise, transition_duration_mins, mode)?;
+/// Where the config file is, as found by [`Config::locate_config`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigLocation {
+    /// The config file, or where it would be created
+    pub path: PathBuf,
+    /// Whether `path` is the legacy `hypr/sunsetr.toml`
+    pub legacy: bool,
+    /// Whether `path` exists
+    pub exists: bool,
+    /// The legacy file when both locations have one; loading asks which to keep
+    pub conflicting: Option<PathBuf>,
+    /// Whether `path` was picked with `--select-config`
+    pub selected: bool,
+}
 
-    // 5. Validate update interval vs transition duration (must come before range check)
-    let transition_duration_secs = transition_duration_mins * 60;
-    if update_interval_secs > transition_duration_secs {
-        anyhow::bail!(
-            "Update interval ({} seconds) is longer than transition duration ({} seconds). \
-            Update interval should be shorter to allow smooth transitions. \
-            Reduce update_interval or increase transition_duration.",
-            update_interval_secs,
-            transition_duration_secs
-        );
+/// The config files `--select-config` offers: every `*.toml` in `dir` except `geo.toml`.
+///
+/// Sorted by file name, so `sunsetr.toml` sits among the others.
+pub fn config_candidates(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
     }
 
-    // 6. Update interval range check (with warnings for extreme values)
-    if update_interval_secs < MINIMUM_UPDATE_INTERVAL {
-        Log::log_warning(&format!(
-            "Update interval ({} seconds) is below recommended minimum ({} seconds). \
-            This may cause excessive system load.",
-            update_interval_secs, MINIMUM_UPDATE_INTERVAL
-        ));
-    } else if update_interval_secs > MAXIMUM_UPDATE_INTERVAL {
-        Log::log_warning(&format!(
-            "Update interval ({} seconds) is above recommended maximum ({} seconds). \
-            Transitions may appear choppy.",
-            update_interval_secs, MAXIMUM_UPDATE_INTERVAL
-        ));
-    }
+    let mut candidates: Vec<PathBuf> = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path.extension().is_some_and(|ext| ext == "toml")
+                && path.file_name().is_some_and(|name| name != "geo.toml")
+        })
+        .collect();
+    candidates.sort();
+    Ok(candidates)
+}
 
-    // 7. Check for reasonable transition frequency
-    if transition_duration_secs < 300 && update_interval_secs < 30 {
-        // This would create very frequent updates
-        Log::log_warning(&format!(
-            "Very short transition duration ({} min) with frequent updates ({} sec) may stress your graphics system.",
-            transition_duration_mins, update_interval_secs
-        ));
+/// The config file selected with `--select-config` in `dir`, if it still exists.
+///
+/// The selection is stored as a bare file name in `dir/.selected-config`. A missing,
+/// deleted or malformed selection falls back to the regular lookup.
+pub fn selected_config(dir: &Path) -> Option<PathBuf> {
+    let name = fs::read_to_string(dir.join(SELECTED_CONFIG_FILE)).ok()?;
+    let name = name.trim();
+    if name.is_empty() || name.contains('/') || !name.ends_with(".toml") {
+        return None;
     }
-
-    Ok(())
+    let path = dir.join(name);
+    path.is_file().then_some(path)
 }
 
-/// Builder for creating dynamically-aligned configuration files.
-///
-/// This builder maintains proper comment alignment by calculating the maximum
-/// width of all setting lines and applying consistent padding. This ensures
-/// that when constants change in constants.rs, the config file formatting
-/// remains correct.
-struct ConfigBuilder {
-    entries: Vec<ConfigEntry>,
+/// Remember `file_name` as the config to use in `dir`, or forget the selection with `None`.
+pub fn save_selected_config(dir: &Path, file_name: Option<&str>) -> Result<()> {
+    let state_path = dir.join(SELECTED_CONFIG_FILE);
+    match file_name {
+        Some(name) => fs::write(&state_path, format!("{}\n", name))
+            .with_context(|| format!("Failed to write {}", state_path.display())),
+        None if state_path.exists() => fs::remove_file(&state_path)
+            .with_context(|| format!("Failed to remove {}", state_path.display())),
+        None => Ok(()),
+    }
 }
 
-#[derive(Clone)]
-struct ConfigEntry {
-    content: String,
-    entry_type: EntryType,
+/// Config keys currently overridden by `SUNSETR_*` environment variables.
+pub fn env_override_keys() -> Vec<String> {
+    env_overrides(std::env::vars())
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect()
 }
 
-#[derive(Clone)]
-enum EntryType {
-    Section,
-    Setting { line: String, comment: String },
+/// Problems found by [`lint_config`], in the order they were detected.
+#[derive(Debug, Default)]
+pub struct LintReport {
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
 }
 
-impl ConfigBuilder {
-    fn new() -> Self {
-        Self {
-            entries: Vec::new(),
+/// Check a config file the same way loading does, but keep going after the first
+/// problem so everything can be reported at once.
+///
+/// Used by `--lint`. A `geo.toml` next to the file is taken into account, just
+/// like at runtime. Warnings from the shared validation are logged as they occur.
+pub fn lint_config(path: &Path) -> LintReport {
+    let mut report = LintReport::default();
+
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            report
+                .errors
+                .push(format!("Failed to read {}: {}", path.display(), e));
+            return report;
         }
-    }
+    };
 
-    fn add_section(mut self, title: &str) -> Self {
-        self.entries.push(ConfigEntry {
-            content: format!("#[{}]", title),
-            entry_type: EntryType::Section,
-        });
-        self
+    let mut table = match content.parse::<toml::Table>() {
+        Ok(table) => table,
+        Err(e) => {
+            report.errors.push(format!("Invalid TOML: {}", e));
+            return report;
+        }
+    };
+    report.errors.extend(unknown_key_messages(&table));
+
+    let named_temperatures = match resolve_named_temperatures(&mut table) {
+        Ok(keys) => keys,
+        Err(e) => {
+            report.errors.push(e.to_string());
+            return report;
+        }
+    };
+    let mut config: Config = match table.try_into() {
+        Ok(config) => config,
+        Err(e) => {
+            report.errors.push(format!("Invalid value: {}", e));
+            return report;
+        }
+    };
+    if let Err(e) = convert_temperature_units(&mut config, &named_temperatures) {
+        report.errors.push(e.to_string());
+        return report;
     }
 
-    fn add_setting(mut self, key: &str, value: &str, comment: &str) -> Self {
-        let line = format!("{} = {}", key, value);
-        self.entries.push(ConfigEntry {
-            content: line.clone(),
-            entry_type: EntryType::Setting {
-                line,
-                comment: format!("# {}", comment),
-            },
-        });
-        self
+    // Check every profile before overlaying the active one
+    let mut profiles_ok = true;
+    for (name, profile) in config.profiles.iter().flatten() {
+        let result = if name == DEFAULT_PROFILE_NAME {
+            Err(anyhow::anyhow!(
+                "Profile name '{}' is reserved for the base configuration",
+                DEFAULT_PROFILE_NAME
+            ))
+        } else {
+            validate_profile(name, profile)
+        };
+        if let Err(e) = result {
+            report.errors.push(e.to_string());
+            profiles_ok = false;
+        }
+    }
+    if profiles_ok && let Err(e) = Config::apply_active_profile(&mut config) {
+        report.errors.push(e.to_string());
     }
 
-    fn build(self) -> String {
-        // Calculate the maximum width of all setting lines for alignment
-        let max_width = self
-            .entries
-            .iter()
-            .filter_map(|entry| match &entry.entry_type {
-                EntryType::Setting { line, .. } => Some(line.len()),
-                EntryType::Section => None,
-            })
-            .max()
-            .unwrap_or(0)
-            + 1; // +1 for one space between setting and comment
+    if let Err(e) = Config::load_geo_override_from_path(&mut config, path) {
+        report.errors.push(e.to_string());
+    }
 
-        let mut result = Vec::new();
-        let mut first_section = true;
+    // Hard limits are independent of each other, so all of them are reported
+    report
+        .errors
+        .extend(hard_limit_errors(&config).iter().map(|e| e.to_string()));
+
+    // Remaining load-time checks (time formats, transition mode, update interval and
+    // coordinates). Fields covered by the hard limits are cleared so they aren't
+    // reported twice; the schedule only makes sense once these pass.
+    let mut fields = config.clone();
+    fields.night_temp = None;
+    fields.day_temp = None;
+    fields.night_gamma = None;
+    fields.day_gamma = None;
+    fields.transition_duration = None;
+    fields.startup_transition_duration = None;
+    match Config::apply_defaults_and_validate_fields(&mut fields) {
+        Ok(()) => {
+            if let Err(e) = validate_schedule(&config) {
+                report.errors.push(format!("{:#}", e));
+            }
+        }
+        Err(e) => report.errors.push(format!("{:#}", e)),
+    }
 
-        for entry in self.entries {
-            match entry.entry_type {
-                EntryType::Section => {
-                    if !first_section {
-                        result.push(String::new()); // Empty line before new section
-                    }
-                    result.push(entry.content);
-                    first_section = false;
-                }
-                EntryType::Setting { line, comment } => {
-                    let padding = " ".repeat(max_width - line.len());
-                    result.push(format!("{}{}{}", line, padding, comment));
-                }
+    // Output groups build on the top-level settings, so only check them once those pass
+    if report.errors.is_empty() {
+        for (name, _, group_config) in config.output_group_configs() {
+            if let Err(e) = validate_config(&group_config) {
+                report
+                    .errors
+                    .push(format!("Output group '{}': {:#}", name, e));
             }
         }
+    }
 
-        result.join("\n")
+    if config.color_shift == Some(false)
+        && (config.night_temp.is_some() || config.day_temp.is_some())
+    {
+        report.warnings.push(format!(
+            "color_shift is false, so night_temp and day_temp are ignored \
+            (the temperature stays at {}K)",
+            NEUTRAL_TEMP
+        ));
+    }
+
+    if config.color_shift == Some(false) && config.sunrise_hold_temp.is_some() {
+        report.warnings.push(
+            "color_shift is false, so the sunrise pause (sunrise_hold_temp) is ignored".to_string(),
+        );
+    }
+
+    if config.uses_geo_mode() && (config.latitude.is_none() || config.longitude.is_none()) {
+        report.warnings.push(
+            "transition_mode is \"geo\" but latitude/longitude are not set - \
+            the sunset/sunrise times will be used instead. Run 'sunsetr --geo' to pick a city"
+                .to_string(),
+        );
+    }
+
+    if config.auto_prefer.is_some()
+        && config
+            .backend
+            .is_some_and(|backend| backend != Backend::Auto)
+    {
+        report
+            .warnings
+            .push("auto_prefer only applies with backend = \"auto\"".to_string());
     }
+
+    if config.transition_mode.as_deref() != Some("manual_geo") && config.manual_geo_event.is_some()
+    {
+        report.warnings.push(
+            "manual_geo_event only applies with transition_mode = \"manual_geo\"".to_string(),
+        );
+    }
+
+    report
 }
 
-/// Calculate day and night durations in minutes
-fn calculate_day_night_durations(sunset: NaiveTime, sunrise: NaiveTime) -> (u32, u32) {
-    let sunset_mins = sunset.hour() * 60 + sunset.minute();
-    let sunrise_mins = sunrise.hour() * 60 + sunrise.minute();
+/// Fill in missing coordinates for geo mode using `detect` (the system timezone).
+///
+/// Returns the detected `(latitude, longitude, city)` when coordinates were filled
+/// in, or `None` when geo mode isn't used or coordinates are already set. Fails with
+/// advice when geo mode needs coordinates and none can be detected, rather than
+/// running on the fallback sunset/sunrise times.
+fn fill_geo_coordinates(
+    config: &mut Config,
+    detect: impl FnOnce() -> Result<(f64, f64, String)>,
+) -> Result<Option<(f64, f64, String)>> {
+    if !config.uses_geo_mode() || (config.latitude.is_some() && config.longitude.is_some()) {
+        return Ok(None);
+    }
 
-    if sunset_mins > sunrise_mins {
-        // Normal case: sunset after sunrise in the same day
-        let day_duration = sunset_mins - sunrise_mins;
-        let night_duration = (24 * 60) - day_duration;
-        (day_duration, night_duration)
-    } else {
-        // Overnight case: sunset before sunrise (next day)
-        let night_duration = sunrise_mins - sunset_mins;
-        let day_duration = (24 * 60) - night_duration;
-        (day_duration, night_duration)
+    match detect() {
+        Ok((lat, lon, city_name)) => {
+            config.latitude = Some(lat);
+            config.longitude = Some(lon);
+            Ok(Some((lat, lon, city_name)))
+        }
+        Err(e) => {
+            Log::log_pipe();
+            anyhow::bail!(
+                "Geo mode requires coordinates, but none are configured and they \
+                couldn't be detected from the system timezone ({:#}).\n\
+                \n\
+                To fix this, either:\n\
+                • Run 'sunsetr --geo' to select your location\n\
+                • Set latitude and longitude in sunsetr.toml (or geo.toml)\n\
+                • Switch to a time-based mode, e.g. transition_mode = \"finish_by\"",
+                e
+            );
+        }
     }
 }
 
-/// Validate that transitions fit within their respective day/night periods
-fn validate_transitions_fit_periods(
-    sunset: NaiveTime,
-    sunrise: NaiveTime,
-    transition_duration_mins: u64,
-    mode: &str,
-) -> Result<()> {
-    let (day_duration_mins, night_duration_mins) = calculate_day_night_durations(sunset, sunrise);
+/// Build an "unknown key" error message with a "did you mean" hint when a close match exists.
+///
+/// `table` is the dotted name of the table holding the key (e.g. `profiles.movie`),
+/// or `None` for the top level.
+fn unknown_key_message(key: &str, known: &[&str], table: Option<&str>) -> String {
+    let location = match table {
+        Some(table) => format!(" in [{}]", table),
+        None => String::new(),
+    };
 
-    // For "center" mode, transition spans both day and night periods
-    // For "finish_by" and "start_at", transition should fit within the target period
+    // Only suggest reasonably close matches to avoid nonsensical hints
+    let max_distance = (key.len() / 3).max(2);
+    let suggestion = known
+        .iter()
+        .map(|candidate| {
+            (
+                candidate,
+                crate::utils::levenshtein_distance(key, candidate),
+            )
+        })
+        .filter(|(_, distance)| *distance <= max_distance)
+        .min_by_key(|(_, distance)| *distance);
+
+    match suggestion {
+        Some((candidate, _)) => format!(
+            "Unknown config key '{}'{}. Did you mean '{}'?",
+            key, location, candidate
+        ),
+        None => format!(
+            "Unknown config key '{}'{}. Valid keys are: {}",
+            key,
+            location,
+            known.join(", ")
+        ),
+    }
+}
 
-    match mode {
-        "center" => {
-            // Transition spans across sunset/sunrise time, so we need room on both sides
-            let half_transition = transition_duration_mins / 2;
+/// Validate the temperature and gamma ranges of a single profile.
+fn validate_profile(name: &str, profile: &ProfileConfig) -> Result<()> {
+    for temp in [profile.night_temp, profile.day_temp].into_iter().flatten() {
+        if !(MINIMUM_TEMP..=MAXIMUM_TEMP).contains(&temp) {
+            anyhow::bail!(
+                "Profile '{}': temperature ({}) must be between {} and {} Kelvin",
+                name,
+                temp,
+                MINIMUM_TEMP,
+                MAXIMUM_TEMP
+            );
+        }
+    }
 
-            // Check if transition would exceed either period
-            if half_transition >= day_duration_mins.into()
-                || half_transition >= night_duration_mins.into()
-            {
-                anyhow::bail!(
-                    "Transition duration ({} minutes) is too long for 'center' mode. \
-                    With centered transitions, half the duration ({} minutes) must fit in both \
-                    day period ({} minutes) and night period ({} minutes). \
-                    Reduce transition_duration or adjust sunset/sunrise times.",
-                    transition_duration_mins,
-                    half_transition,
-                    day_duration_mins,
-                    night_duration_mins
-                );
-            }
+    for gamma in [profile.night_gamma, profile.day_gamma]
+        .into_iter()
+        .flatten()
+    {
+        if !(MINIMUM_GAMMA..=MAXIMUM_GAMMA).contains(&gamma) {
+            anyhow::bail!(
+                "Profile '{}': gamma ({}%) must be between {}% and {}%",
+                name,
+                gamma,
+                MINIMUM_GAMMA,
+                MAXIMUM_GAMMA
+            );
         }
-        "finish_by" | "start_at" => {
-            // Transitions should reasonably fit within their periods
-            let max_reasonable_ratio = 0.8; // 80% of period
-            let max_day_transition = (day_duration_mins as f64 * max_reasonable_ratio) as u64;
-            let max_night_transition = (night_duration_mins as f64 * max_reasonable_ratio) as u64;
+    }
 
-            if transition_duration_mins > max_day_transition {
-                Log::log_warning(&format!(
-                    "Transition duration ({} min) is quite long compared to day period ({} min). Consider reducing transition_duration for better experience.",
-                    transition_duration_mins, day_duration_mins
-                ));
-            }
+    Ok(())
+}
 
-            if transition_duration_mins > max_night_transition {
-                Log::log_warning(&format!(
-                    "Transition duration ({} min) is quite long compared to night period ({} min). Consider reducing transition_duration for better experience.",
-                    transition_duration_mins, night_duration_mins
-                ));
-            }
-        }
-        _ => {} // Already validated mode earlier
+/// Comprehensive configuration validation to prevent impossible or problematic setups
+pub fn validate_config(config: &Config) -> Result<()> {
+    if let Some(error) = hard_limit_errors(config).into_iter().next() {
+        return Err(error);
+    }
+    validate_schedule(config)?;
+
+    // Every mode a season switches to has to work with the configured times
+    let mut seasonal_modes: Vec<&String> = config
+        .seasonal_modes
+        .iter()
+        .flatten()
+        .map(|(_, m)| m)
+        .collect();
+    seasonal_modes.sort();
+    seasonal_modes.dedup();
+    for mode in seasonal_modes {
+        let mut seasonal = config.clone();
+        seasonal.transition_mode = Some(mode.clone());
+        seasonal.seasonal_modes = None;
+        seasonal.output_groups = None;
+        validate_schedule(&seasonal)
+            .with_context(|| format!("Seasonal transition mode \"{}\"", mode))?;
     }
 
+    // Each output group is a schedule of its own and must hold up by itself
+    for (name, _, group_config) in config.output_group_configs() {
+        validate_config(&group_config).with_context(|| format!("Output group '{}'", name))?;
+    }
     Ok(())
 }
 
-/// Validate that sunset and sunrise transitions don't overlap
-fn validate_no_transition_overlaps(
-    sunset: NaiveTime,
-    sunrise: NaiveTime,
-    transition_duration_mins: u64,
-    mode: &str,
-) -> Result<()> {
-    use std::time::Duration;
+/// Check every setting against its hard limits, returning all violations.
+///
+/// Unlike [`validate_config`], this doesn't stop at the first problem so the
+/// `--lint` command can report everything at once.
+fn hard_limit_errors(config: &Config) -> Vec<anyhow::Error> {
+    let mut errors = Vec::new();
 
-    // Calculate transition windows using the same logic as the main code
-    let transition_duration = Duration::from_secs(transition_duration_mins * 60);
+    // 0. Validate backend configuration compatibility
+    let backend = config.backend.as_ref().unwrap_or(&DEFAULT_BACKEND);
+    let start_hyprsunset = config.effective().start_hyprsunset;
 
-    let (sunset_start, sunset_end, sunrise_start, sunrise_end) = match mode {
-        "center" => {
-            let half_transition = transition_duration / 2;
-            let half_chrono = chrono::Duration::from_std(half_transition).unwrap();
-            (
-                sunset - half_chrono,
-                sunset + half_chrono,
-                sunrise - half_chrono,
-                sunrise + half_chrono,
-            )
-        }
-        "start_at" => {
-            let full_transition = chrono::Duration::from_std(transition_duration).unwrap();
-            (
-                sunset,
-                sunset + full_transition,
-                sunrise,
-                sunrise + full_transition,
-            )
-        }
-        "finish_by" => {
-            let full_transition = chrono::Duration::from_std(transition_duration).unwrap();
-            (
-                sunset - full_transition,
-                sunset,
-                sunrise - full_transition,
-                sunrise,
-            )
-        }
-        _ => {
-            // Default to "finish_by" mode for any unexpected values
-            let full_transition = chrono::Duration::from_std(transition_duration).unwrap();
-            (
-                sunset - full_transition,
-                sunset,
-                sunrise - full_transition,
-                sunrise,
-            )
-        }
-    };
+    // Only validate explicit backend choices, Auto will be resolved at runtime
+    if *backend == Backend::Wayland && start_hyprsunset {
+        errors.push(anyhow::anyhow!(
+            "Incompatible configuration: backend=\"wayland\" and start_hyprsunset=true. \
+            When using Wayland protocols (backend=\"wayland\"), hyprsunset should not be started (start_hyprsunset=false). \
+            Please set either backend=\"hyprland\" or start_hyprsunset=false."
+        ));
+    }
 
-    // Convert to minutes since midnight for easier comparison
-    let sunset_start_mins = sunset_start.hour() * 60 + sunset_start.minute();
-    let sunset_end_mins = sunset_end.hour() * 60 + sunset_end.minute();
-    let sunrise_start_mins = sunrise_start.hour() * 60 + sunrise_start.minute();
-    let sunrise_end_mins = sunrise_end.hour() * 60 + sunrise_end.minute();
+    // auto_prefer only chooses between the backends of a Wayland session
+    if let Some(ref prefer) = config.auto_prefer {
+        for (i, name) in prefer.iter().enumerate() {
+            if !matches!(name.as_str(), "hyprland" | "wayland") {
+                errors.push(anyhow::anyhow!(
+                    "auto_prefer entry \"{}\" must be \"hyprland\" or \"wayland\"",
+                    name
+                ));
+            } else if prefer[..i].contains(name) {
+                errors.push(anyhow::anyhow!(
+                    "auto_prefer lists \"{}\" more than once",
+                    name
+                ));
+            }
+        }
+    }
 
-    // Check for overlaps - this is complex due to potential midnight crossings
-    let overlap = check_time_ranges_overlap(
-        sunset_start_mins,
-        sunset_end_mins,
-        sunrise_start_mins,
-        sunrise_end_mins,
-    );
+    let transition_duration_mins = config
+        .transition_duration
+        .unwrap_or(DEFAULT_TRANSITION_DURATION);
 
-    if overlap {
-        anyhow::bail!(
-            "Transition periods overlap! \
-            Sunset transition: {:?} → {:?}, Sunrise transition: {:?} → {:?}. \
-            \nThis configuration is impossible because transitions would conflict. \
-            \nSolutions: \
-            \n  1. Reduce transition_duration from {} to {} minutes or less \
-            \n  2. Increase time between sunset ({:?}) and sunrise ({:?}) \
-            \n  3. Change transition_mode from '{}' to a different mode",
-            sunset_start,
-            sunset_end,
-            sunrise_start,
-            sunrise_end,
+    // Validate transition duration (hard limits)
+    if !is_valid_transition_duration(transition_duration_mins) {
+        errors.push(anyhow::anyhow!(
+            "Transition duration ({} minutes) must be 0 (instant switch) or between {} and {} minutes \
+            (measured in wall-clock time, so a DST change during a transition shortens or lengthens it)",
             transition_duration_mins,
-            suggest_max_transition_duration(sunset, sunrise, mode),
-            sunset,
-            sunrise,
-            mode
-        );
+            MINIMUM_TRANSITION_DURATION,
+            MAXIMUM_TRANSITION_DURATION
+        ));
     }
 
-    Ok(())
-}
-
-/// Check if two time ranges overlap, handling midnight crossings
-fn check_time_ranges_overlap(
-    start1_mins: u32,
-    end1_mins: u32,
-    start2_mins: u32,
-    end2_mins: u32,
-) -> bool {
-    // Helper function to normalize ranges that cross midnight
-    let normalize_range = |start: u32, end: u32| -> Vec<(u32, u32)> {
-        if start <= end {
-            vec![(start, end)]
-        } else {
-            // Range crosses midnight, split into two ranges
-            vec![(start, 24 * 60), (0, end)]
+    for (key, minutes) in [
+        ("sunset_duration", config.sunset_duration),
+        ("sunrise_duration", config.sunrise_duration),
+    ] {
+        if let Some(minutes) = minutes
+            && !is_valid_transition_duration(minutes)
+        {
+            errors.push(anyhow::anyhow!(
+                "{} ({} minutes) must be 0 (instant switch) or between {} and {} minutes",
+                key,
+                minutes,
+                MINIMUM_TRANSITION_DURATION,
+                MAXIMUM_TRANSITION_DURATION
+            ));
         }
-    };
+    }
 
-    let range1 = normalize_range(start1_mins, end1_mins);
-    let range2 = normalize_range(start2_mins, end2_mins);
+    // Validate startup transition duration (hard limits)
+    if let Some(startup_duration_secs) = config.startup_transition_duration
+        && !(MINIMUM_STARTUP_TRANSITION_DURATION..=MAXIMUM_STARTUP_TRANSITION_DURATION)
+            .contains(&startup_duration_secs)
+    {
+        errors.push(anyhow::anyhow!(
+            "Startup transition duration ({} seconds) must be between {} and {} seconds",
+            startup_duration_secs,
+            MINIMUM_STARTUP_TRANSITION_DURATION,
+            MAXIMUM_STARTUP_TRANSITION_DURATION
+        ));
+    }
 
-    // Check if any segment from range1 overlaps with any segment from range2
-    for (r1_start, r1_end) in &range1 {
-        for (r2_start, r2_end) in &range2 {
-            if r1_start < r2_end && r2_start < r1_end {
-                return true; // Overlap detected
-            }
+    // Validate per-channel gamma multipliers
+    for (key, value) in [
+        ("night_gamma_r", config.night_gamma_r),
+        ("night_gamma_g", config.night_gamma_g),
+        ("night_gamma_b", config.night_gamma_b),
+        ("day_gamma_r", config.day_gamma_r),
+        ("day_gamma_g", config.day_gamma_g),
+        ("day_gamma_b", config.day_gamma_b),
+    ] {
+        if let Some(value) = value
+            && !(MINIMUM_CHANNEL_GAMMA..=MAXIMUM_CHANNEL_GAMMA).contains(&value)
+        {
+            errors.push(anyhow::anyhow!(
+                "{} ({}) must be between {:.1} and {:.1}",
+                key,
+                value,
+                MINIMUM_CHANNEL_GAMMA,
+                MAXIMUM_CHANNEL_GAMMA
+            ));
         }
     }
 
-    false
-}
-
-/// Suggest a maximum safe transition duration for the given configuration
-fn suggest_max_transition_duration(sunset: NaiveTime, sunrise: NaiveTime, mode: &str) -> u64 {
-    let (day_duration_mins, night_duration_mins) = calculate_day_night_durations(sunset, sunrise);
-    let min_period = day_duration_mins.min(night_duration_mins);
+    // Validate day whitepoint lies within the chromaticity diagram
+    if let Some([x, y]) = config.day_whitepoint
+        && crate::backend::wayland::gamma::whitepoint_correction(x, y).is_none()
+    {
+        errors.push(anyhow::anyhow!(
+            "Day whitepoint [{}, {}] is not a valid CIE 1931 xy chromaticity. \
+            Use coordinates close to D65 [{}, {}]",
+            x,
+            y,
+            DEFAULT_DAY_WHITEPOINT[0],
+            DEFAULT_DAY_WHITEPOINT[1]
+        ));
+    }
 
-    match mode {
-        "center" => {
-            // For center mode, half the transition goes in each period
-            ((min_period / 2).saturating_sub(1)).into()
+    // Validate output filter patterns
+    for (key, patterns) in [
+        ("outputs_include", &config.outputs_include),
+        ("outputs_exclude", &config.outputs_exclude),
+        ("hdr_outputs", &config.hdr_outputs),
+    ] {
+        if let Some(patterns) = patterns
+            && patterns.iter().any(|p| p.trim().is_empty())
+        {
+            errors.push(anyhow::anyhow!(
+                "{} must not contain empty output names",
+                key
+            ));
         }
-        "finish_by" | "start_at" => {
-            // For these modes, leave some buffer between transitions
-            ((min_period as f64 * 0.8) as u32).into()
+    }
+    for (season, mode) in config.seasonal_modes.iter().flatten() {
+        if !SEASON_NAMES.contains(&season.as_str()) {
+            errors.push(anyhow::anyhow!(
+                "seasonal_modes: unknown season '{}' (expected spring, summer, autumn or winter)",
+                season
+            ));
+        }
+        if !["finish_by", "start_at", "center", "geo"].contains(&mode.as_str()) {
+            errors.push(anyhow::anyhow!(
+                "seasonal_modes.{} (\"{}\") must be \"finish_by\", \"start_at\", \"center\" or \"geo\"",
+                season,
+                mode
+            ));
         }
-        _ => (min_period.saturating_sub(10)).into(),
     }
-}
-
-/// Find a config line containing the specified key
-fn find_config_line(content: &str, key: &str) -> Option<String> {
-    for line in content.lines() {
-        let trimmed = line.trim();
-        if trimmed.starts_with(key) && trimmed.contains('=') && !trimmed.starts_with('#') {
-            return Some(line.to_string());
+    if config
+        .seasonal_modes
+        .as_ref()
+        .is_some_and(|m| !m.is_empty())
+        && config.transition_mode.as_deref() == Some("appearance")
+    {
+        errors.push(anyhow::anyhow!(
+            "seasonal_modes can't be combined with transition_mode = \"appearance\""
+        ));
+    }
+    for (name, group) in config.output_groups.iter().flatten() {
+        if group.outputs.is_empty() || group.outputs.iter().any(|p| p.trim().is_empty()) {
+            errors.push(anyhow::anyhow!(
+                "Output group '{}': outputs must list at least one output name, and no empty ones",
+                name
+            ));
         }
     }
-    None
-}
-
-/// Preserve the comment formatting when updating a config line value
-fn preserve_comment_formatting(original_line: &str, key: &str, new_value: &str) -> String {
-    if let Some(comment_pos) = original_line.find('#') {
-        let comment_part = &original_line[comment_pos..];
-        let key_value_part = format!("{} = {}", key, new_value);
-
-        // Calculate spacing to align with other comments (aim for column 32)
-        let target_width = 32;
-        let padding_needed = if key_value_part.len() < target_width {
-            target_width - key_value_part.len()
-        } else {
-            1 // At least one space
-        };
 
-        format!(
-            "{}{}{}",
-            key_value_part,
-            " ".repeat(padding_needed),
-            comment_part
-        )
-    } else {
-        format!("{} = {}", key, new_value)
+    // Validate temperature quantum (hard limits)
+    if let Some(quantum) = config.temp_quantum
+        && !(MINIMUM_TEMP_QUANTUM..=MAXIMUM_TEMP_QUANTUM).contains(&quantum)
+    {
+        errors.push(anyhow::anyhow!(
+            "Temperature quantum ({}K) must be between {}K and {}K",
+            quantum,
+            MINIMUM_TEMP_QUANTUM,
+            MAXIMUM_TEMP_QUANTUM
+        ));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::constants::test_constants::*;
-    use serial_test::serial;
-    use std::fs;
-    use tempfile::tempdir;
+    // Validate hyprsunset socket override
+    if config
+        .hyprsunset_socket
+        .as_deref()
+        .is_some_and(|p| p.trim().is_empty())
+    {
+        errors.push(anyhow::anyhow!(
+            "hyprsunset_socket must not be empty. Remove it to auto-detect the socket"
+        ));
+    }
 
-    #[allow(clippy::too_many_arguments)]
-    fn create_test_config(
-        sunset: &str,
-        sunrise: &str,
-        transition_duration: Option<u64>,
-        update_interval: Option<u64>,
-        transition_mode: Option<&str>,
-        night_temp: Option<u32>,
-        day_temp: Option<u32>,
-        night_gamma: Option<f32>,
-        day_gamma: Option<f32>,
-    ) -> Config {
-        Config {
-            start_hyprsunset: Some(false),
-            backend: Some(Backend::Auto),
-            startup_transition: Some(false),
-            startup_transition_duration: Some(10),
-            latitude: None,
-            longitude: None,
-            sunset: sunset.to_string(),
-            sunrise: sunrise.to_string(),
-            night_temp,
-            day_temp,
-            night_gamma,
-            day_gamma,
-            transition_duration,
-            update_interval,
-            transition_mode: transition_mode.map(|s| s.to_string()),
-        }
+    // Validate hyprsunset connection wait
+    if let Some(timeout) = config.hyprsunset_connect_timeout
+        && timeout > MAXIMUM_HYPRSUNSET_CONNECT_TIMEOUT
+    {
+        errors.push(anyhow::anyhow!(
+            "hyprsunset_connect_timeout ({} seconds) must be between 0 and {} seconds",
+            timeout,
+            MAXIMUM_HYPRSUNSET_CONNECT_TIMEOUT
+        ));
+    }
+    if let Some(retries) = config.hyprsunset_connect_retries
+        && retries > MAXIMUM_HYPRSUNSET_CONNECT_RETRIES
+    {
+        errors.push(anyhow::anyhow!(
+            "hyprsunset_connect_retries ({}) must be between 0 and {}",
+            retries,
+            MAXIMUM_HYPRSUNSET_CONNECT_RETRIES
+        ));
     }
 
-    #[test]
-    #[serial]
-    fn test_config_load_default_creation() {
-        let temp_dir = tempdir().unwrap();
-        let config_path = temp_dir.path().join("sunsetr").join("sunsetr.toml");
+    // Validate metrics port (0 would bind a random port nobody can find)
+    if config.metrics_port == Some(0) {
+        errors.push(anyhow::anyhow!("metrics_port must be between 1 and 65535"));
+    }
 
-        // Save and restore XDG_CONFIG_HOME
-        let original = std::env::var("XDG_CONFIG_HOME").ok();
-        unsafe {
-            std::env::set_var("XDG_CONFIG_HOME", temp_dir.path());
-        }
+    // Validate startup delay (hard limits)
+    if let Some(delay_ms) = config.startup_delay_ms
+        && delay_ms > MAXIMUM_STARTUP_DELAY_MS
+    {
+        errors.push(anyhow::anyhow!(
+            "Startup delay ({} ms) must be between 0 and {} milliseconds",
+            delay_ms,
+            MAXIMUM_STARTUP_DELAY_MS
+        ));
+    }
 
-        // First load should create default config
-        let result = Config::load();
+    // Validate log file settings
+    if config
+        .log_file
+        .as_deref()
+        .is_some_and(|p| p.trim().is_empty())
+    {
+        errors.push(anyhow::anyhow!(
+            "log_file must not be empty. Remove it to disable the log file"
+        ));
+    }
+    if let Some(max_bytes) = config.log_file_max_bytes
+        && max_bytes < MINIMUM_LOG_FILE_MAX_BYTES
+    {
+        errors.push(anyhow::anyhow!(
+            "log_file_max_bytes ({}) must be at least {} bytes",
+            max_bytes,
+            MINIMUM_LOG_FILE_MAX_BYTES
+        ));
+    }
+    if let Some(keep) = config.log_file_keep
+        && keep > MAXIMUM_LOG_FILE_KEEP
+    {
+        errors.push(anyhow::anyhow!(
+            "log_file_keep ({}) must be between 0 and {}",
+            keep,
+            MAXIMUM_LOG_FILE_KEEP
+        ));
+    }
+    if let Some(ref verbosity) = config.log_verbosity
+        && LogVerbosity::from_name(verbosity).is_none()
+    {
+        errors.push(anyhow::anyhow!(
+            "log_verbosity (\"{}\") must be \"quiet\", \"normal\" or \"debug\"",
+            verbosity
+        ));
+    }
 
-        // Restore original
-        unsafe {
-            match original {
-                Some(val) => std::env::set_var("XDG_CONFIG_HOME", val),
-                None => std::env::remove_var("XDG_CONFIG_HOME"),
-            }
+    // Validate hook commands
+    for (key, command) in [
+        ("on_day_command", &config.on_day_command),
+        ("on_night_command", &config.on_night_command),
+        ("on_transition_start", &config.on_transition_start),
+    ] {
+        if command.as_deref().is_some_and(|c| c.trim().is_empty()) {
+            errors.push(anyhow::anyhow!(
+                "{} must not be empty. Remove it to disable the hook",
+                key
+            ));
         }
+    }
 
-        assert!(result.is_ok());
-        assert!(config_path.exists());
+    // Validate the ease-out tail (hard limits, it is also capped at half of each transition)
+    if let Some(tail) = config.ease_out_tail_seconds
+        && tail > MAXIMUM_EASE_OUT_TAIL_SECONDS
+    {
+        errors.push(anyhow::anyhow!(
+            "ease_out_tail_seconds ({}) must be between 0 and {} seconds",
+            tail,
+            MAXIMUM_EASE_OUT_TAIL_SECONDS
+        ));
     }
 
-    #[test]
-    fn test_config_validation_basic() {
-        let config = create_test_config(
-            TEST_STANDARD_SUNSET,
-            TEST_STANDARD_SUNRISE,
-            Some(TEST_STANDARD_TRANSITION_DURATION),
-            Some(TEST_STANDARD_UPDATE_INTERVAL),
-            Some(TEST_STANDARD_MODE),
-            Some(TEST_STANDARD_NIGHT_TEMP),
-            Some(TEST_STANDARD_DAY_TEMP),
-            Some(TEST_STANDARD_NIGHT_GAMMA),
-            Some(TEST_STANDARD_DAY_GAMMA),
-        );
-        assert!(validate_config(&config).is_ok());
+    // Validate the gamma offset: its ramp is compressed to fit the transition, and must
+    // keep at least half of it so brightness never jumps
+    if let Some(offset) = config.gamma_progress_offset
+        && !(-MAXIMUM_GAMMA_PROGRESS_OFFSET..=MAXIMUM_GAMMA_PROGRESS_OFFSET).contains(&offset)
+    {
+        errors.push(anyhow::anyhow!(
+            "gamma_progress_offset ({}) must be between -{} and {} so the gamma ramp stays within the transition",
+            offset,
+            MAXIMUM_GAMMA_PROGRESS_OFFSET,
+            MAXIMUM_GAMMA_PROGRESS_OFFSET
+        ));
     }
 
-    #[test]
-    fn test_config_validation_backend_compatibility() {
-        // Test valid combinations
-        let mut config = create_test_config(
-            TEST_STANDARD_SUNSET,
-            TEST_STANDARD_SUNRISE,
-            Some(TEST_STANDARD_TRANSITION_DURATION),
-            Some(TEST_STANDARD_UPDATE_INTERVAL),
-            Some(TEST_STANDARD_MODE),
-            Some(TEST_STANDARD_NIGHT_TEMP),
-            Some(TEST_STANDARD_DAY_TEMP),
-            Some(TEST_STANDARD_NIGHT_GAMMA),
-            Some(TEST_STANDARD_DAY_GAMMA),
-        );
+    // Validate the temperature unit (mired values are already converted to Kelvin)
+    if let Some(ref unit) = config.temp_unit
+        && !["kelvin", "mired"].contains(&unit.as_str())
+    {
+        errors.push(anyhow::anyhow!(
+            "temp_unit (\"{}\") must be \"kelvin\" or \"mired\"",
+            unit
+        ));
+    }
 
-        // Valid: use_wayland=false, start_hyprsunset=true (Hyprland backend)
-        config.backend = Some(Backend::Hyprland);
-        config.start_hyprsunset = Some(true);
-        assert!(validate_config(&config).is_ok());
+    if let Some(ref event) = config.manual_geo_event
+        && ManualGeoEvent::from_name(event).is_none()
+    {
+        errors.push(anyhow::anyhow!(
+            "manual_geo_event (\"{}\") must be \"sunset\" or \"sunrise\"",
+            event
+        ));
+    }
+
+    // Validate the geo anchor and its noon offset
+    if let Some(ref anchor) = config.geo_anchor
+        && !["twilight", "solar_noon"].contains(&anchor.as_str())
+    {
+        errors.push(anyhow::anyhow!(
+            "geo_anchor (\"{}\") must be \"twilight\" or \"solar_noon\"",
+            anchor
+        ));
+    }
+    if let Some(offset) = config.geo_noon_offset
+        && !(MINIMUM_GEO_NOON_OFFSET..=MAXIMUM_GEO_NOON_OFFSET).contains(&offset)
+    {
+        errors.push(anyhow::anyhow!(
+            "geo_noon_offset ({} hours) must be between {} and {} hours",
+            offset,
+            MINIMUM_GEO_NOON_OFFSET,
+            MAXIMUM_GEO_NOON_OFFSET
+        ));
+    }
+    if let Some(ref precision) = config.solar_precision
+        && !["fast", "precise"].contains(&precision.as_str())
+    {
+        errors.push(anyhow::anyhow!(
+            "solar_precision (\"{}\") must be \"fast\" or \"precise\"",
+            precision
+        ));
+    }
+    if let Some(ref on_fatal_exit) = config.on_fatal_exit
+        && !["reset", "leave"].contains(&on_fatal_exit.as_str())
+    {
+        errors.push(anyhow::anyhow!(
+            "on_fatal_exit (\"{}\") must be \"reset\" or \"leave\"",
+            on_fatal_exit
+        ));
+    }
+    if let Some(ref format) = config.gamma_table_format
+        && crate::backend::wayland::gamma::GammaTableFormat::from_name(format).is_none()
+    {
+        errors.push(anyhow::anyhow!(
+            "gamma_table_format (\"{}\") must be \"planar-le\", \"planar-be\", \"interleaved-le\" or \"interleaved-be\"",
+            format
+        ));
+    }
+    if let Some(ref boundary) = config.geo_night_boundary
+        && !["civil", "nautical", "astronomical"].contains(&boundary.as_str())
+    {
+        errors.push(anyhow::anyhow!(
+            "geo_night_boundary (\"{}\") must be \"civil\", \"nautical\" or \"astronomical\"",
+            boundary
+        ));
+    }
+    if let Some(minutes) = config.geo_round_to_minutes
+        && minutes > MAXIMUM_GEO_ROUND_TO_MINUTES
+    {
+        errors.push(anyhow::anyhow!(
+            "geo_round_to_minutes ({} minutes) must be between 0 and {} minutes",
+            minutes,
+            MAXIMUM_GEO_ROUND_TO_MINUTES
+        ));
+    }
+
+    // Validate wake fade duration (hard limits)
+    if let Some(fade_ms) = config.wake_fade_ms
+        && fade_ms > MAXIMUM_WAKE_FADE_MS
+    {
+        errors.push(anyhow::anyhow!(
+            "Wake fade duration ({} ms) must be between 0 and {} milliseconds",
+            fade_ms,
+            MAXIMUM_WAKE_FADE_MS
+        ));
+    }
+
+    // Validate idle dimming (hard limits)
+    if let Some(timeout) = config.idle_timeout
+        && !(MINIMUM_IDLE_TIMEOUT..=MAXIMUM_IDLE_TIMEOUT).contains(&timeout)
+    {
+        errors.push(anyhow::anyhow!(
+            "Idle timeout ({} seconds) must be between {} and {} seconds",
+            timeout,
+            MINIMUM_IDLE_TIMEOUT,
+            MAXIMUM_IDLE_TIMEOUT
+        ));
+    }
+    if let Some(gamma) = config.idle_gamma
+        && !(MINIMUM_GAMMA..=MAXIMUM_GAMMA).contains(&gamma)
+    {
+        errors.push(anyhow::anyhow!(
+            "Idle gamma ({}%) must be between {}% and {}%",
+            gamma,
+            MINIMUM_GAMMA,
+            MAXIMUM_GAMMA
+        ));
+    }
+
+    // 0. Validate basic ranges for temperature and gamma (hard limits)
+    if let Some(temp) = config.night_temp
+        && !(MINIMUM_TEMP..=MAXIMUM_TEMP).contains(&temp)
+    {
+        errors.push(anyhow::anyhow!(
+            "Night temperature ({}) must be between {} and {} Kelvin",
+            temp,
+            MINIMUM_TEMP,
+            MAXIMUM_TEMP
+        ));
+    }
+
+    if let Some(temp) = config.day_temp
+        && !(MINIMUM_TEMP..=MAXIMUM_TEMP).contains(&temp)
+    {
+        errors.push(anyhow::anyhow!(
+            "Day temperature ({}) must be between {} and {} Kelvin",
+            temp,
+            MINIMUM_TEMP,
+            MAXIMUM_TEMP
+        ));
+    }
+
+    if let Some(gamma) = config.night_gamma
+        && !(MINIMUM_GAMMA..=MAXIMUM_GAMMA).contains(&gamma)
+    {
+        errors.push(anyhow::anyhow!(
+            "Night gamma ({}%) must be between {}% and {}%",
+            gamma,
+            MINIMUM_GAMMA,
+            MAXIMUM_GAMMA
+        ));
+    }
+
+    if let Some(gamma) = config.day_gamma
+        && !(MINIMUM_GAMMA..=MAXIMUM_GAMMA).contains(&gamma)
+    {
+        errors.push(anyhow::anyhow!(
+            "Day gamma ({}%) must be between {}% and {}%",
+            gamma,
+            MINIMUM_GAMMA,
+            MAXIMUM_GAMMA
+        ));
+    }
+
+    // HDR outputs share the regular hard limits
+    for (label, temp) in [
+        ("HDR night temperature", config.hdr_night_temp),
+        ("HDR day temperature", config.hdr_day_temp),
+    ] {
+        if let Some(temp) = temp
+            && !(MINIMUM_TEMP..=MAXIMUM_TEMP).contains(&temp)
+        {
+            errors.push(anyhow::anyhow!(
+                "{} ({}) must be between {} and {} Kelvin",
+                label,
+                temp,
+                MINIMUM_TEMP,
+                MAXIMUM_TEMP
+            ));
+        }
+    }
+
+    for (label, gamma) in [
+        ("HDR night gamma", config.hdr_night_gamma),
+        ("HDR day gamma", config.hdr_day_gamma),
+    ] {
+        if let Some(gamma) = gamma
+            && !(MINIMUM_GAMMA..=MAXIMUM_GAMMA).contains(&gamma)
+        {
+            errors.push(anyhow::anyhow!(
+                "{} ({}%) must be between {}% and {}%",
+                label,
+                gamma,
+                MINIMUM_GAMMA,
+                MAXIMUM_GAMMA
+            ));
+        }
+    }
+
+    // The sunrise pause needs both its temperature and its length
+    match (config.sunrise_hold_temp, config.sunrise_hold_minutes) {
+        (Some(temp), Some(minutes)) => {
+            // Without color shifting there is nothing to pause (lint_config warns about it)
+            let effective = config.effective();
+            let (night, day) = (effective.night_temp, effective.day_temp);
+            if effective.color_shift && (temp <= night.min(day) || temp >= night.max(day)) {
+                errors.push(anyhow::anyhow!(
+                    "sunrise_hold_temp ({}) must be between night_temp ({}) and day_temp ({})",
+                    temp,
+                    night,
+                    day
+                ));
+            }
+            if minutes == 0 {
+                errors.push(anyhow::anyhow!(
+                    "sunrise_hold_minutes must be at least 1. Remove both sunrise_hold keys to disable the pause"
+                ));
+            }
+        }
+        (Some(_), None) | (None, Some(_)) => errors.push(anyhow::anyhow!(
+            "sunrise_hold_temp and sunrise_hold_minutes must be set together"
+        )),
+        (None, None) => {}
+    }
+
+    errors
+}
+
+/// Check that sunset, sunrise, transitions and the update interval form a workable schedule.
+fn validate_schedule(config: &Config) -> Result<()> {
+    use chrono::NaiveTime;
+
+    let sunset = NaiveTime::parse_from_str(&config.sunset, "%H:%M:%S")
+        .context("Invalid sunset time format")?;
+    let sunrise = NaiveTime::parse_from_str(&config.sunrise, "%H:%M:%S")
+        .context("Invalid sunrise time format")?;
+
+    let durations = transition_duration_settings(config);
+    let update_interval_secs = config.effective().update_interval;
+    let mode = config
+        .transition_mode
+        .as_deref()
+        .unwrap_or(DEFAULT_TRANSITION_MODE);
+
+    // 1. Check for identical sunset/sunrise times
+    if sunset == sunrise {
+        anyhow::bail!(
+            "Sunset and sunrise cannot be the same time ({:?}). \
+            There must be a distinction between day and night periods.",
+            sunset
+        );
+    }
+
+    // 2. Calculate time periods and check minimums
+    let (day_duration_mins, night_duration_mins) = calculate_day_night_durations(sunset, sunrise);
+
+    if day_duration_mins < 60 {
+        anyhow::bail!(
+            "Day period is too short ({} minutes). \
+            Day period must be at least 1 hour. \
+            Adjust sunset ({:?}) or sunrise ({:?}) times.",
+            day_duration_mins,
+            sunset,
+            sunrise
+        );
+    }
+
+    if night_duration_mins < 60 {
+        anyhow::bail!(
+            "Night period is too short ({} minutes). \
+            Night period must be at least 1 hour. \
+            Adjust sunset ({:?}) or sunrise ({:?}) times.",
+            night_duration_mins,
+            sunset,
+            sunrise
+        );
+    }
+
+    // 3. Check that transitions fit within their periods
+    for &(key, label, minutes) in &durations {
+        validate_transitions_fit_periods(sunset, sunrise, key, label, minutes, mode)?;
+    }
+
+    // 4. Check for transition overlaps
+    let (sunset_duration_mins, sunrise_duration_mins) = config.transition_durations();
+    validate_no_transition_overlaps(
+        sunset,
+        sunrise,
+        sunset_duration_mins,
+        sunrise_duration_mins,
+        mode,
+    )?;
+    validate_manual_geo_window(config)?;
+    validate_sunrise_hold_window(config)?;
+
+    // 5. Validate update interval vs transition duration (must come before range check)
+    // An instant switch has no updates to space out
+    for &(key, label, minutes) in &durations {
+        let duration_secs = minutes * 60;
+        if duration_secs > 0 && update_interval_secs > duration_secs {
+            anyhow::bail!(
+                "Update interval ({} seconds) is longer than {} ({} seconds). \
+                Update interval should be shorter to allow smooth transitions. \
+                Reduce update_interval or increase {}.",
+                update_interval_secs,
+                label.to_lowercase(),
+                duration_secs,
+                key
+            );
+        }
+    }
+
+    // 6. Update interval range check (with warnings for extreme values)
+    if update_interval_secs < MINIMUM_UPDATE_INTERVAL {
+        Log::log_warning(&format!(
+            "Update interval ({} seconds) is below recommended minimum ({} seconds). \
+            This may cause excessive system load.",
+            update_interval_secs, MINIMUM_UPDATE_INTERVAL
+        ));
+    } else if update_interval_secs > MAXIMUM_UPDATE_INTERVAL {
+        Log::log_warning(&format!(
+            "Update interval ({} seconds) is above recommended maximum ({} seconds). \
+            Transitions may appear choppy.",
+            update_interval_secs, MAXIMUM_UPDATE_INTERVAL
+        ));
+    }
+
+    // 7. Check for reasonable transition frequency
+    for &(_, label, minutes) in &durations {
+        if minutes > 0 && minutes * 60 < 300 && update_interval_secs < 30 {
+            // This would create very frequent updates
+            Log::log_warning(&format!(
+                "Very short {} ({} min) with frequent updates ({} sec) may stress your graphics system.",
+                label.to_lowercase(),
+                minutes,
+                update_interval_secs
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// The distinct transition durations to validate, as (setting, label, minutes).
+///
+/// A single `transition_duration` entry when sunset and sunrise share a duration,
+/// otherwise one entry per transition so problems name the setting to change.
+fn transition_duration_settings(config: &Config) -> Vec<(&'static str, &'static str, u64)> {
+    let (sunset_minutes, sunrise_minutes) = config.transition_durations();
+    if sunset_minutes == sunrise_minutes {
+        vec![("transition_duration", "Transition duration", sunset_minutes)]
+    } else {
+        vec![
+            ("sunset_duration", "Sunset duration", sunset_minutes),
+            ("sunrise_duration", "Sunrise duration", sunrise_minutes),
+        ]
+    }
+}
+
+/// Whether `minutes` is an accepted `transition_duration`.
+///
+/// Zero is allowed on its own and means an instant switch at the configured times.
+fn is_valid_transition_duration(minutes: u64) -> bool {
+    minutes == 0 || (MINIMUM_TRANSITION_DURATION..=MAXIMUM_TRANSITION_DURATION).contains(&minutes)
+}
+
+/// Builder for creating dynamically-aligned configuration files.
+///
+/// This builder maintains proper comment alignment by calculating the maximum
+/// width of all setting lines and applying consistent padding. This ensures
+/// that when constants change in constants.rs, the config file formatting
+/// remains correct.
+struct ConfigBuilder {
+    entries: Vec<ConfigEntry>,
+}
+
+#[derive(Clone)]
+struct ConfigEntry {
+    content: String,
+    entry_type: EntryType,
+}
+
+#[derive(Clone)]
+enum EntryType {
+    Section,
+    Setting { line: String, comment: String },
+}
+
+impl ConfigBuilder {
+    fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    fn add_section(mut self, title: &str) -> Self {
+        self.entries.push(ConfigEntry {
+            content: format!("#[{}]", title),
+            entry_type: EntryType::Section,
+        });
+        self
+    }
+
+    fn add_setting(mut self, key: &str, value: &str, comment: &str) -> Self {
+        let line = format!("{} = {}", key, value);
+        self.entries.push(ConfigEntry {
+            content: line.clone(),
+            entry_type: EntryType::Setting {
+                line,
+                comment: format!("# {}", comment),
+            },
+        });
+        self
+    }
+
+    fn build(self) -> String {
+        // Calculate the maximum width of all setting lines for alignment
+        let max_width = self
+            .entries
+            .iter()
+            .filter_map(|entry| match &entry.entry_type {
+                EntryType::Setting { line, .. } => Some(line.len()),
+                EntryType::Section => None,
+            })
+            .max()
+            .unwrap_or(0)
+            + 1; // +1 for one space between setting and comment
+
+        let mut result = Vec::new();
+        let mut first_section = true;
+
+        for entry in self.entries {
+            match entry.entry_type {
+                EntryType::Section => {
+                    if !first_section {
+                        result.push(String::new()); // Empty line before new section
+                    }
+                    result.push(entry.content);
+                    first_section = false;
+                }
+                EntryType::Setting { line, comment } => {
+                    let padding = " ".repeat(max_width - line.len());
+                    result.push(format!("{}{}{}", line, padding, comment));
+                }
+            }
+        }
+
+        result.join("\n")
+    }
+}
+
+/// Calculate day and night durations in minutes
+fn calculate_day_night_durations(sunset: NaiveTime, sunrise: NaiveTime) -> (u32, u32) {
+    let sunset_mins = sunset.hour() * 60 + sunset.minute();
+    let sunrise_mins = sunrise.hour() * 60 + sunrise.minute();
+
+    if sunset_mins > sunrise_mins {
+        // Normal case: sunset after sunrise in the same day
+        let day_duration = sunset_mins - sunrise_mins;
+        let night_duration = (24 * 60) - day_duration;
+        (day_duration, night_duration)
+    } else {
+        // Overnight case: sunset before sunrise (next day)
+        let night_duration = sunrise_mins - sunset_mins;
+        let day_duration = (24 * 60) - night_duration;
+        (day_duration, night_duration)
+    }
+}
+
+/// Validate that a transition fits within the day/night periods.
+///
+/// `key` and `label` name the setting the duration comes from in messages.
+fn validate_transitions_fit_periods(
+    sunset: NaiveTime,
+    sunrise: NaiveTime,
+    key: &str,
+    label: &str,
+    transition_duration_mins: u64,
+    mode: &str,
+) -> Result<()> {
+    let (day_duration_mins, night_duration_mins) = calculate_day_night_durations(sunset, sunrise);
+
+    // For "center" mode, transition spans both day and night periods
+    // For "finish_by" and "start_at", transition should fit within the target period
+
+    match mode {
+        "center" => {
+            // Transition spans across sunset/sunrise time, so we need room on both sides
+            let half_transition = transition_duration_mins / 2;
+
+            // Check if transition would exceed either period
+            if half_transition >= day_duration_mins.into()
+                || half_transition >= night_duration_mins.into()
+            {
+                anyhow::bail!(
+                    "{} ({} minutes) is too long for 'center' mode. \
+                    With centered transitions, half the duration ({} minutes) must fit in both \
+                    day period ({} minutes) and night period ({} minutes). \
+                    Reduce {} or adjust sunset/sunrise times.",
+                    label,
+                    transition_duration_mins,
+                    half_transition,
+                    day_duration_mins,
+                    night_duration_mins,
+                    key
+                );
+            }
+        }
+        "finish_by" | "start_at" => {
+            // Transitions should reasonably fit within their periods
+            let max_reasonable_ratio = 0.8; // 80% of period
+            let max_day_transition = (day_duration_mins as f64 * max_reasonable_ratio) as u64;
+            let max_night_transition = (night_duration_mins as f64 * max_reasonable_ratio) as u64;
+
+            if transition_duration_mins > max_day_transition {
+                Log::log_warning(&format!(
+                    "{} ({} min) is quite long compared to day period ({} min). Consider reducing {} for better experience.",
+                    label, transition_duration_mins, day_duration_mins, key
+                ));
+            }
+
+            if transition_duration_mins > max_night_transition {
+                Log::log_warning(&format!(
+                    "{} ({} min) is quite long compared to night period ({} min). Consider reducing {} for better experience.",
+                    label, transition_duration_mins, night_duration_mins, key
+                ));
+            }
+        }
+        _ => {} // Already validated mode earlier
+    }
+
+    Ok(())
+}
+
+/// Validate that sunset and sunrise transitions don't overlap
+fn validate_no_transition_overlaps(
+    sunset: NaiveTime,
+    sunrise: NaiveTime,
+    sunset_duration_mins: u64,
+    sunrise_duration_mins: u64,
+    mode: &str,
+) -> Result<()> {
+    // Calculate transition windows using the same logic as the main code
+    let window = |anchor: NaiveTime, minutes: u64| {
+        let full = chrono::Duration::minutes(minutes as i64);
+        match mode {
+            "center" => {
+                let half = chrono::Duration::seconds(minutes as i64 * 30);
+                (anchor - half, anchor + half)
+            }
+            "start_at" => (anchor, anchor + full),
+            // "finish_by" and the default for any unexpected values
+            _ => (anchor - full, anchor),
+        }
+    };
+    let (sunset_start, sunset_end) = window(sunset, sunset_duration_mins);
+    let (sunrise_start, sunrise_end) = window(sunrise, sunrise_duration_mins);
+
+    // Convert to minutes since midnight for easier comparison
+    let sunset_start_mins = sunset_start.hour() * 60 + sunset_start.minute();
+    let sunset_end_mins = sunset_end.hour() * 60 + sunset_end.minute();
+    let sunrise_start_mins = sunrise_start.hour() * 60 + sunrise_start.minute();
+    let sunrise_end_mins = sunrise_end.hour() * 60 + sunrise_end.minute();
+
+    // Check for overlaps - this is complex due to potential midnight crossings
+    let overlap = check_time_ranges_overlap(
+        sunset_start_mins,
+        sunset_end_mins,
+        sunrise_start_mins,
+        sunrise_end_mins,
+    );
+
+    if overlap {
+        anyhow::bail!(
+            "Transition periods overlap! \
+            Sunset transition: {:?} → {:?}, Sunrise transition: {:?} → {:?}. \
+            \nThis configuration is impossible because transitions would conflict. \
+            \nSolutions: \
+            \n  1. Reduce the transition durations ({}) to {} minutes or less \
+            \n  2. Increase time between sunset ({:?}) and sunrise ({:?}) \
+            \n  3. Change transition_mode from '{}' to a different mode",
+            sunset_start,
+            sunset_end,
+            sunrise_start,
+            sunrise_end,
+            if sunset_duration_mins == sunrise_duration_mins {
+                format!("transition_duration is {}", sunset_duration_mins)
+            } else {
+                format!(
+                    "sunset_duration is {}, sunrise_duration is {}",
+                    sunset_duration_mins, sunrise_duration_mins
+                )
+            },
+            suggest_max_transition_duration(sunset, sunrise, mode),
+            sunset,
+            sunrise,
+            mode
+        );
+    }
+
+    Ok(())
+}
+
+/// Check that the configured transition of a "manual_geo" schedule fits next to the computed one.
+///
+/// The configured event finishes at its set time like "finish_by" does, so its
+/// transition has to fit between the end of the sun-following transition and that
+/// time. A sun that sets late can leave much less room than the configured times
+/// suggest. Only checked with coordinates set, since the computed window is a
+/// fallback otherwise.
+fn validate_manual_geo_window(config: &Config) -> Result<()> {
+    if config.transition_mode.as_deref() != Some("manual_geo")
+        || config.latitude.is_none()
+        || config.longitude.is_none()
+    {
+        return Ok(());
+    }
+
+    // Seasons may switch today's mode, but this checks the manual_geo schedule itself
+    let mut schedule = config.clone();
+    schedule.seasonal_modes = None;
+    let (sunset_start, sunset_end, sunrise_start, sunrise_end) =
+        crate::time_state::transition_windows(&schedule);
+    let (sunset_minutes, sunrise_minutes) = config.transition_durations();
+
+    let (key, minutes, computed_event, computed, configured_event, configured_end) =
+        match config.manual_geo_event() {
+            ManualGeoEvent::Sunset => (
+                if config.sunrise_duration.is_some() {
+                    "sunrise_duration"
+                } else {
+                    "transition_duration"
+                },
+                sunrise_minutes,
+                "sunset",
+                (sunset_start, sunset_end),
+                "sunrise",
+                sunrise_end,
+            ),
+            ManualGeoEvent::Sunrise => (
+                if config.sunset_duration.is_some() {
+                    "sunset_duration"
+                } else {
+                    "transition_duration"
+                },
+                sunset_minutes,
+                "sunrise",
+                (sunrise_start, sunrise_end),
+                "sunset",
+                sunset_end,
+            ),
+        };
+
+    // Configured times have whole seconds, so compare at that precision
+    let computed = (
+        computed.0.with_nanosecond(0).unwrap_or(computed.0),
+        computed.1.with_nanosecond(0).unwrap_or(computed.1),
+    );
+    let minutes_between =
+        |from: NaiveTime, to: NaiveTime| (to - from).num_minutes().rem_euclid(24 * 60) as u64;
+    // A configured time inside the computed transition leaves no room at all
+    let available =
+        if minutes_between(computed.0, configured_end) <= minutes_between(computed.0, computed.1) {
+            0
+        } else {
+            minutes_between(computed.1, configured_end)
+        };
+
+    if minutes > available {
+        anyhow::bail!(
+            "{} ({} minutes) is longer than the {} minutes available before {} ({}). \
+            \nThe {} transition follows the sun and today ends at {}, so the two transitions would overlap. \
+            \nShorten {} to {} minutes or less, or move {} later.",
+            key,
+            minutes,
+            available,
+            configured_event,
+            configured_end.format("%H:%M:%S"),
+            computed_event,
+            computed.1.format("%H:%M:%S"),
+            key,
+            available,
+            configured_event
+        );
+    }
+
+    Ok(())
+}
+
+/// Check that the sunrise pause leaves part of the sunrise transition to ramp in.
+///
+/// In geo modes the sunrise window changes with the seasons and this checks today's,
+/// so a pause that only fits in summer is caught when it's loaded in summer; on days
+/// with a shorter window the pause is cut to fit (see the time_state module).
+fn validate_sunrise_hold_window(config: &Config) -> Result<()> {
+    let Some(hold_minutes) = config.sunrise_hold_minutes else {
+        return Ok(());
+    };
+    if config.transition_mode.as_deref() == Some("appearance") {
+        return Ok(());
+    }
+
+    let (_, _, sunrise_start, sunrise_end) = crate::time_state::transition_windows(config);
+    let window_minutes = (sunrise_end - sunrise_start)
+        .num_minutes()
+        .rem_euclid(24 * 60) as u64;
+
+    if hold_minutes >= window_minutes {
+        anyhow::bail!(
+            "sunrise_hold_minutes ({} minutes) doesn't fit in the {} minute sunrise transition. \
+            \nThe pause has to be shorter than the transition so there's time left to change temperature. \
+            \nShorten sunrise_hold_minutes or lengthen the sunrise transition.",
+            hold_minutes,
+            window_minutes
+        );
+    }
+
+    Ok(())
+}
+
+/// Check if two time ranges overlap, handling midnight crossings
+fn check_time_ranges_overlap(
+    start1_mins: u32,
+    end1_mins: u32,
+    start2_mins: u32,
+    end2_mins: u32,
+) -> bool {
+    // Helper function to normalize ranges that cross midnight
+    let normalize_range = |start: u32, end: u32| -> Vec<(u32, u32)> {
+        if start <= end {
+            vec![(start, end)]
+        } else {
+            // Range crosses midnight, split into two ranges
+            vec![(start, 24 * 60), (0, end)]
+        }
+    };
+
+    let range1 = normalize_range(start1_mins, end1_mins);
+    let range2 = normalize_range(start2_mins, end2_mins);
+
+    // Check if any segment from range1 overlaps with any segment from range2
+    for (r1_start, r1_end) in &range1 {
+        for (r2_start, r2_end) in &range2 {
+            if r1_start < r2_end && r2_start < r1_end {
+                return true; // Overlap detected
+            }
+        }
+    }
+
+    false
+}
+
+/// Suggest a maximum safe transition duration for the given configuration
+fn suggest_max_transition_duration(sunset: NaiveTime, sunrise: NaiveTime, mode: &str) -> u64 {
+    let (day_duration_mins, night_duration_mins) = calculate_day_night_durations(sunset, sunrise);
+    let min_period = day_duration_mins.min(night_duration_mins);
+
+    match mode {
+        "center" => {
+            // For center mode, half the transition goes in each period
+            ((min_period / 2).saturating_sub(1)).into()
+        }
+        "finish_by" | "start_at" => {
+            // For these modes, leave some buffer between transitions
+            ((min_period as f64 * 0.8) as u32).into()
+        }
+        _ => (min_period.saturating_sub(10)).into(),
+    }
+}
+
+/// Find a config line containing the specified key
+fn find_config_line(content: &str, key: &str) -> Option<String> {
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with(key) && trimmed.contains('=') && !trimmed.starts_with('#') {
+            return Some(line.to_string());
+        }
+    }
+    None
+}
+
+/// Replace the top-level `key = ...` line in `content`, or add one before the first table.
+///
+/// Keys of the same name inside tables such as `[profiles.<name>]` are left alone.
+fn set_top_level_value(content: &str, key: &str, value: &str) -> String {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let first_table = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with('['))
+        .unwrap_or(lines.len());
+    let existing = lines[..first_table].iter().position(|line| {
+        line.trim_start()
+            .strip_prefix(key)
+            .is_some_and(|rest| rest.trim_start().starts_with('='))
+    });
+
+    match existing {
+        Some(index) => lines[index] = preserve_comment_formatting(&lines[index], key, value),
+        None => {
+            // After the last top-level key, so comments introducing the first table stay with it
+            let at = lines[..first_table]
+                .iter()
+                .rposition(|line| {
+                    let line = line.trim_start();
+                    !line.starts_with('#') && line.contains('=')
+                })
+                .map_or(first_table, |last| last + 1);
+            lines.insert(at, format!("{} = {}", key, value));
+        }
+    }
+
+    let mut updated = lines.join("\n");
+    updated.push('\n');
+    updated
+}
+
+/// Preserve the comment formatting when updating a config line value
+fn preserve_comment_formatting(original_line: &str, key: &str, new_value: &str) -> String {
+    if let Some(comment_pos) = original_line.find('#') {
+        let comment_part = &original_line[comment_pos..];
+        let key_value_part = format!("{} = {}", key, new_value);
+
+        // Calculate spacing to align with other comments (aim for column 32)
+        let target_width = 32;
+        let padding_needed = if key_value_part.len() < target_width {
+            target_width - key_value_part.len()
+        } else {
+            1 // At least one space
+        };
+
+        format!(
+            "{}{}{}",
+            key_value_part,
+            " ".repeat(padding_needed),
+            comment_part
+        )
+    } else {
+        format!("{} = {}", key, new_value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::test_constants::*;
+    use serial_test::serial;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_test_config(
+        sunset: &str,
+        sunrise: &str,
+        transition_duration: Option<u64>,
+        update_interval: Option<u64>,
+        transition_mode: Option<&str>,
+        night_temp: Option<u32>,
+        day_temp: Option<u32>,
+        night_gamma: Option<f32>,
+        day_gamma: Option<f32>,
+    ) -> Config {
+        Config {
+            start_hyprsunset: Some(false),
+            hyprsunset_socket: None,
+            hyprsunset_connect_timeout: None,
+            hyprsunset_connect_retries: None,
+            backend: Some(Backend::Auto),
+            auto_prefer: None,
+            startup_transition: Some(false),
+            startup_transition_duration: Some(10),
+            startup_delay_ms: None,
+            latitude: None,
+            longitude: None,
+            geo_anchor: None,
+            geo_noon_offset: None,
+            solar_precision: None,
+            geo_night_boundary: None,
+            geo_round_to_minutes: None,
+            sunset: sunset.to_string(),
+            sunrise: sunrise.to_string(),
+            night_temp,
+            day_temp,
+            night_gamma,
+            day_gamma,
+            transition_duration,
+            update_interval,
+            transition_mode: transition_mode.map(|s| s.to_string()),
+            manual_geo_event: None,
+            seasonal_modes: None,
+            on_day_command: None,
+            on_night_command: None,
+            on_transition_start: None,
+            active_profile: None,
+            profiles: None,
+            output_groups: None,
+            day_whitepoint: None,
+            night_gamma_r: None,
+            night_gamma_g: None,
+            night_gamma_b: None,
+            day_gamma_r: None,
+            day_gamma_g: None,
+            day_gamma_b: None,
+            outputs_include: None,
+            outputs_exclude: None,
+            hdr_outputs: None,
+            hdr_night_temp: None,
+            hdr_day_temp: None,
+            hdr_night_gamma: None,
+            hdr_day_gamma: None,
+            verify_gamma: None,
+            precompute_ramps: None,
+            gamma_table_format: None,
+            require_outputs: None,
+            reset_on_exit: None,
+            on_fatal_exit: None,
+            metrics_port: None,
+            event_fifo: None,
+            reapply_on_unlock: None,
+            wake_fade_ms: None,
+            idle_timeout: None,
+            idle_gamma: None,
+            log_file: None,
+            log_file_max_bytes: None,
+            log_file_keep: None,
+            log_to_console: None,
+            log_verbosity: None,
+            temp_quantum: None,
+            temp_unit: None,
+            ease_out_tail_seconds: None,
+            gamma_progress_offset: None,
+            sunset_duration: None,
+            sunrise_duration: None,
+            sunrise_hold_temp: None,
+            sunrise_hold_minutes: None,
+            color_shift: None,
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_load_default_creation() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("sunsetr").join("sunsetr.toml");
+
+        // Save and restore XDG_CONFIG_HOME
+        let original = std::env::var("XDG_CONFIG_HOME").ok();
+        unsafe {
+            std::env::set_var("XDG_CONFIG_HOME", temp_dir.path());
+        }
+
+        // First load should create default config
+        let result = Config::load();
+
+        // Restore original
+        unsafe {
+            match original {
+                Some(val) => std::env::set_var("XDG_CONFIG_HOME", val),
+                None => std::env::remove_var("XDG_CONFIG_HOME"),
+            }
+        }
+
+        assert!(result.is_ok());
+        assert!(config_path.exists());
+    }
+
+    #[test]
+    fn test_config_validation_basic() {
+        let config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_config_validation_backend_compatibility() {
+        // Test valid combinations
+        let mut config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+
+        // Valid: use_wayland=false, start_hyprsunset=true (Hyprland backend)
+        config.backend = Some(Backend::Hyprland);
+        config.start_hyprsunset = Some(true);
+        assert!(validate_config(&config).is_ok());
 
         // Valid: use_wayland=true, start_hyprsunset=false (Wayland backend)
         config.backend = Some(Backend::Wayland);
         config.start_hyprsunset = Some(false);
         assert!(validate_config(&config).is_ok());
 
-        // Valid: use_wayland=false, start_hyprsunset=false (Hyprland without auto-start)
-        config.backend = Some(Backend::Hyprland);
-        config.start_hyprsunset = Some(false);
-        assert!(validate_config(&config).is_ok());
+        // Valid: use_wayland=false, start_hyprsunset=false (Hyprland without auto-start)
+        config.backend = Some(Backend::Hyprland);
+        config.start_hyprsunset = Some(false);
+        assert!(validate_config(&config).is_ok());
+
+        // Invalid: use_wayland=true, start_hyprsunset=true (conflicting)
+        config.backend = Some(Backend::Wayland);
+        config.start_hyprsunset = Some(true);
+        let result = validate_config(&config);
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Incompatible configuration")
+        );
+    }
+
+    #[test]
+    fn test_config_validation_identical_times() {
+        let config = create_test_config(
+            "12:00:00",
+            "12:00:00",
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(validate_config(&config).is_err());
+        assert!(
+            validate_config(&config)
+                .unwrap_err()
+                .to_string()
+                .contains("cannot be the same time")
+        );
+    }
+
+    #[test]
+    fn test_config_validation_extreme_short_day() {
+        // 30 minute day period (sunrise 23:45, sunset 00:15)
+        let config = create_test_config(
+            "00:15:00",
+            "23:45:00",
+            Some(5),
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(validate_config(&config).is_err());
+        assert!(
+            validate_config(&config)
+                .unwrap_err()
+                .to_string()
+                .contains("Day period is too short")
+        );
+    }
+
+    #[test]
+    fn test_config_validation_extreme_short_night() {
+        // 30 minute night period (sunset 23:45, sunrise 00:15)
+        let config = create_test_config(
+            "23:45:00",
+            "00:15:00",
+            Some(5),
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(validate_config(&config).is_err());
+        assert!(
+            validate_config(&config)
+                .unwrap_err()
+                .to_string()
+                .contains("Night period is too short")
+        );
+    }
+
+    #[test]
+    fn test_config_validation_extreme_temperature_values() {
+        // Test minimum temperature boundary
+        let config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(MINIMUM_TEMP),
+            Some(MAXIMUM_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(validate_config(&config).is_ok());
+
+        // Test below minimum temperature
+        let config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(MINIMUM_TEMP - 1),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(validate_config(&config).is_err());
+
+        // Test above maximum temperature
+        let config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(MAXIMUM_TEMP + 1),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_config_validation_startup_delay() {
+        let mut config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+
+        config.startup_delay_ms = Some(MAXIMUM_STARTUP_DELAY_MS);
+        assert!(validate_config(&config).is_ok());
+
+        config.startup_delay_ms = Some(MAXIMUM_STARTUP_DELAY_MS + 1);
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_config_validation_log_file() {
+        let mut config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+
+        config.log_file = Some("~/.local/state/sunsetr/sunsetr.log".to_string());
+        config.log_file_max_bytes = Some(MINIMUM_LOG_FILE_MAX_BYTES);
+        config.log_file_keep = Some(MAXIMUM_LOG_FILE_KEEP);
+        assert!(validate_config(&config).is_ok());
+
+        config.log_file_max_bytes = Some(MINIMUM_LOG_FILE_MAX_BYTES - 1);
+        assert!(validate_config(&config).is_err());
+        config.log_file_max_bytes = None;
+
+        config.log_file_keep = Some(MAXIMUM_LOG_FILE_KEEP + 1);
+        assert!(validate_config(&config).is_err());
+        config.log_file_keep = None;
+
+        config.log_file = Some("  ".to_string());
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_set_top_level_value() {
+        let content =
+            "night_temp = 3300     # Warm\nday_temp = 6500\n\n[profiles.work]\nnight_temp = 4000\n";
+        assert_eq!(
+            set_top_level_value(content, "night_temp", "3500"),
+            format!(
+                "night_temp = 3500{}# Warm\nday_temp = 6500\n\n[profiles.work]\nnight_temp = 4000\n",
+                " ".repeat(15)
+            )
+        );
+
+        // Missing at the top level: added after the last key, not inside the profile
+        let content = "day_temp = 6500\n\n# Profiles\n[profiles.work]\nnight_temp = 4000";
+        assert_eq!(
+            set_top_level_value(content, "night_temp", "3500"),
+            "day_temp = 6500\nnight_temp = 3500\n\n# Profiles\n[profiles.work]\nnight_temp = 4000\n"
+        );
+        assert_eq!(
+            set_top_level_value("# Only comments\n", "night_temp", "3500"),
+            "# Only comments\nnight_temp = 3500\n"
+        );
+
+        // A key that merely starts with the same name isn't a match
+        assert_eq!(
+            set_top_level_value("night_temp_x = 1\n", "night_temp", "3500"),
+            "night_temp_x = 1\nnight_temp = 3500\n"
+        );
+    }
+
+    #[test]
+    fn test_config_validation_hooks() {
+        let mut config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+
+        config.on_day_command = Some("swww img ~/day.png".to_string());
+        config.on_night_command = Some("swww img ~/night.png".to_string());
+        config.on_transition_start = Some("notify-send sunsetr".to_string());
+        assert!(validate_config(&config).is_ok());
+
+        config.on_night_command = Some(" ".to_string());
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_config_validation_wake_fade() {
+        let mut config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+
+        config.wake_fade_ms = Some(0);
+        assert!(validate_config(&config).is_ok());
+
+        config.wake_fade_ms = Some(MAXIMUM_WAKE_FADE_MS);
+        assert!(validate_config(&config).is_ok());
+
+        config.wake_fade_ms = Some(MAXIMUM_WAKE_FADE_MS + 1);
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_require_outputs_config() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("sunsetr.toml");
+
+        fs::write(
+            &config_path,
+            "sunset = \"19:00:00\"\nsunrise = \"06:00:00\"\n",
+        )
+        .unwrap();
+        let config = Config::load_from_path(&config_path).unwrap();
+        // Waiting for outputs is the default, failing fast is opt-in
+        assert_eq!(config.require_outputs, None);
+        assert!(!config.require_outputs.unwrap_or(DEFAULT_REQUIRE_OUTPUTS));
+
+        fs::write(
+            &config_path,
+            "sunset = \"19:00:00\"\nsunrise = \"06:00:00\"\nrequire_outputs = true\n",
+        )
+        .unwrap();
+        let config = Config::load_from_path(&config_path).unwrap();
+        assert_eq!(config.require_outputs, Some(true));
+    }
+
+    #[test]
+    fn test_reset_on_exit_config() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("sunsetr.toml");
+
+        fs::write(
+            &config_path,
+            "sunset = \"19:00:00\"\nsunrise = \"06:00:00\"\n",
+        )
+        .unwrap();
+        let config = Config::load_from_path(&config_path).unwrap();
+        assert!(config.reset_on_exit.unwrap_or(DEFAULT_RESET_ON_EXIT));
+
+        fs::write(
+            &config_path,
+            "sunset = \"19:00:00\"\nsunrise = \"06:00:00\"\nreset_on_exit = false\n",
+        )
+        .unwrap();
+        let config = Config::load_from_path(&config_path).unwrap();
+        assert_eq!(config.reset_on_exit, Some(false));
+    }
+
+    #[test]
+    fn test_system_config_layered_under_user_config() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("sunsetr.toml");
+        let system_path = Path::new(SYSTEM_CONFIG_PATH);
+        let system = r#"
+backend = "wayland"
+start_hyprsunset = false
+sunset = "20:00:00"
+sunrise = "07:00:00"
+night_temp = 3500
+night_gamma = 85.0
+transition_mode = "finish_by"
+
+[profiles.movie]
+night_temp = 2700
+night_gamma = 70.0
+"#;
+        let user = r#"
+night_temp = 3000
+
+[profiles.movie]
+night_gamma = 60.0
+"#;
+
+        let config =
+            Config::load_from_layers(Some((system_path, system)), user, &path, None).unwrap();
+        // The user's key wins, everything else comes from the system config
+        assert_eq!(config.night_temp, Some(3000));
+        assert_eq!(config.night_gamma, Some(85.0));
+        assert_eq!(config.sunset, "20:00:00");
+        assert_eq!(config.sunrise, "07:00:00");
+        assert_eq!(config.backend, Some(Backend::Wayland));
+        // Tables merge key by key too
+        let movie = &config.profiles.as_ref().unwrap()["movie"];
+        assert_eq!(movie.night_temp, Some(2700));
+        assert_eq!(movie.night_gamma, Some(60.0));
+
+        // Without a user config, the system config alone is enough
+        let config =
+            Config::load_from_layers(Some((system_path, system)), "", &path, None).unwrap();
+        assert_eq!(config.night_temp, Some(3500));
+
+        // Mistakes in the system config name that file
+        let err =
+            Config::load_from_layers(Some((system_path, "nite_temp = 3000")), user, &path, None)
+                .unwrap_err();
+        assert!(format!("{:#}", err).contains(SYSTEM_CONFIG_PATH));
+    }
+
+    #[test]
+    fn test_system_config_location() {
+        assert_eq!(
+            system_config_location_from(None),
+            Some(PathBuf::from(SYSTEM_CONFIG_PATH))
+        );
+        assert_eq!(
+            system_config_location_from(Some("/opt/site/sunsetr.toml".into())),
+            Some(PathBuf::from("/opt/site/sunsetr.toml"))
+        );
+        // An empty value turns the system config off
+        assert_eq!(system_config_location_from(Some("".into())), None);
+    }
+
+    #[test]
+    fn test_selected_config() {
+        let dir = tempdir().unwrap();
+        for name in ["sunsetr.toml", "work.toml", "geo.toml", "notes.txt"] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+
+        let names: Vec<_> = config_candidates(dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["sunsetr.toml", "work.toml"]);
+
+        assert_eq!(selected_config(dir.path()), None);
+        save_selected_config(dir.path(), Some("work.toml")).unwrap();
+        assert_eq!(
+            selected_config(dir.path()),
+            Some(dir.path().join("work.toml"))
+        );
+
+        // A selection that went away falls back to the regular lookup
+        fs::remove_file(dir.path().join("work.toml")).unwrap();
+        assert_eq!(selected_config(dir.path()), None);
+
+        // Only bare file names in the directory count
+        fs::write(dir.path().join(SELECTED_CONFIG_FILE), "../work.toml").unwrap();
+        assert_eq!(selected_config(dir.path()), None);
+
+        save_selected_config(dir.path(), None).unwrap();
+        assert!(!dir.path().join(SELECTED_CONFIG_FILE).exists());
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let vars = [
+            ("SUNSETR_NIGHT_TEMP", "3000"),
+            ("SUNSETR_BACKEND", "wayland"),
+            ("SUNSETR_START_HYPRSUNSET", "false"),
+            ("SUNSETR_SUNSET", "19:30:00"),
+            ("SUNSETR_OUTPUTS_INCLUDE", "[\"eDP-*\"]"),
+            ("SUNSETR_LOG_FILE", "~/sunsetr.log"),
+            ("SUNSETR_VERIFY_GAMMA", "false"),
+            ("SUNSETR_NOT_A_KEY", "1"),
+            ("SUNSETR_PROFILES", "{}"),
+            ("NIGHT_TEMP", "2000"),
+        ]
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+
+        let overrides = env_overrides(vars.into_iter());
+        let keys: Vec<&str> = overrides.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(
+            keys,
+            [
+                "backend",
+                "log_file",
+                "night_temp",
+                "outputs_include",
+                "start_hyprsunset",
+                "sunset",
+                "verify_gamma"
+            ]
+        );
+
+        let value = |key: &str| &overrides.iter().find(|(k, _)| k == key).unwrap().1;
+        assert_eq!(value("night_temp"), &toml::Value::Integer(3000));
+        assert_eq!(value("backend"), &toml::Value::String("wayland".into()));
+        assert_eq!(value("sunset"), &toml::Value::String("19:30:00".into()));
+        assert_eq!(
+            value("log_file"),
+            &toml::Value::String("~/sunsetr.log".into())
+        );
+        assert_eq!(value("verify_gamma"), &toml::Value::Boolean(false));
+        assert!(value("outputs_include").is_array());
+
+        // The overrides deserialize like the same keys written in a file
+        let mut table = toml::Table::new();
+        table.insert("sunrise".into(), toml::Value::String("06:00:00".into()));
+        table.extend(overrides);
+        let config: Config = table.try_into().unwrap();
+        assert_eq!(config.night_temp, Some(3000));
+        assert_eq!(config.backend, Some(Backend::Wayland));
+        assert_eq!(config.outputs_include, Some(vec!["eDP-*".to_string()]));
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_named_temperatures() {
+        let mut table: toml::Table = toml::from_str(
+            r##"
+night_temp = "candle"
+day_temp = "#FFFFFF"
+temp_unit = "mired"
+
+[profiles.movie]
+night_temp = "warm"
+day_temp = 154
+"##,
+        )
+        .unwrap();
+        let resolved = resolve_named_temperatures(&mut table).unwrap();
+        assert_eq!(
+            resolved,
+            ["night_temp", "day_temp", "profiles.movie.night_temp"]
+        );
+
+        table.insert("sunset".into(), toml::Value::String("19:00:00".into()));
+        table.insert("sunrise".into(), toml::Value::String("06:00:00".into()));
+        let mut config: Config = table.try_into().unwrap();
+        convert_temperature_units(&mut config, &resolved).unwrap();
+
+        // Presets and colors are Kelvin already; plain numbers still follow temp_unit
+        assert_eq!(config.night_temp, Some(1900));
+        assert_eq!(config.day_temp, Some(6500));
+        let movie = &config.profiles.as_ref().unwrap()["movie"];
+        assert_eq!(movie.night_temp, Some(3000));
+        assert_eq!(movie.day_temp, Some(6494));
+
+        // A warm hex color lands near the temperature it looks like
+        let kelvin = temperature_from_name("night_temp", "#ffb46b").unwrap();
+        assert!((2900..=3300).contains(&kelvin), "got {}K", kelvin);
+
+        assert!(temperature_from_name("night_temp", "#00ff00").is_err());
+        assert!(temperature_from_name("night_temp", "sunset").is_err());
+        assert!(temperature_from_name("night_temp", "#ffb4").is_err());
+    }
+
+    #[test]
+    fn test_temperature_presets_in_range() {
+        for (name, kelvin) in TEMPERATURE_PRESETS {
+            assert!(
+                (MINIMUM_TEMP..=MAXIMUM_TEMP).contains(kelvin),
+                "preset {} ({}K) is out of range",
+                name,
+                kelvin
+            );
+            assert_eq!(temperature_from_name("night_temp", name).unwrap(), *kelvin);
+        }
+    }
+
+    #[test]
+    fn test_convert_temperature_units() {
+        let mut config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(303),
+            Some(154),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        config.temp_unit = Some("mired".to_string());
+        config.hdr_night_temp = Some(250);
+        config.temp_quantum = Some(50);
+        config.profiles = Some(BTreeMap::from([(
+            "movie".to_string(),
+            ProfileConfig {
+                night_temp: Some(400),
+                ..Default::default()
+            },
+        )]));
+
+        convert_temperature_units(&mut config, &[]).unwrap();
+        assert_eq!(config.night_temp, Some(3300));
+        assert_eq!(config.day_temp, Some(6494));
+        assert_eq!(config.hdr_night_temp, Some(4000));
+        assert_eq!(config.hdr_day_temp, None);
+        assert_eq!(config.temp_quantum, Some(50)); // Always Kelvin
+        assert_eq!(
+            config.profiles.as_ref().unwrap()["movie"].night_temp,
+            Some(2500)
+        );
+        assert!(validate_config(&config).is_ok());
+
+        // Kelvin (the default) is left alone
+        let mut kelvin = config.clone();
+        kelvin.temp_unit = None;
+        convert_temperature_units(&mut kelvin, &[]).unwrap();
+        assert_eq!(kelvin.night_temp, Some(3300));
+
+        // Converted values must still be in the 1000-20000K range
+        let mut too_cool = config.clone();
+        too_cool.night_temp = Some(40); // 25000K
+        assert!(convert_temperature_units(&mut too_cool, &[]).is_err());
+        let mut too_warm = config.clone();
+        too_warm.night_temp = Some(1100); // 909K
+        assert!(convert_temperature_units(&mut too_warm, &[]).is_err());
+        let mut zero = config.clone();
+        zero.night_temp = Some(0);
+        assert!(convert_temperature_units(&mut zero, &[]).is_err());
+
+        let mut unknown = config;
+        unknown.temp_unit = Some("celsius".to_string());
+        assert!(convert_temperature_units(&mut unknown, &[]).is_err());
+        assert!(validate_config(&unknown).is_err());
+    }
+
+    #[test]
+    fn test_config_validation_ease_out_tail() {
+        let mut config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+
+        config.ease_out_tail_seconds = Some(0);
+        assert!(validate_config(&config).is_ok());
+        config.ease_out_tail_seconds = Some(MAXIMUM_EASE_OUT_TAIL_SECONDS);
+        assert!(validate_config(&config).is_ok());
+        config.ease_out_tail_seconds = Some(MAXIMUM_EASE_OUT_TAIL_SECONDS + 1);
+        assert!(validate_config(&config).is_err());
+        config.ease_out_tail_seconds = None;
+
+        config.gamma_progress_offset = Some(MAXIMUM_GAMMA_PROGRESS_OFFSET);
+        assert!(validate_config(&config).is_ok());
+        config.gamma_progress_offset = Some(-MAXIMUM_GAMMA_PROGRESS_OFFSET);
+        assert!(validate_config(&config).is_ok());
+        config.gamma_progress_offset = Some(0.6);
+        assert!(validate_config(&config).is_err());
+        config.gamma_progress_offset = Some(f32::NAN);
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_config_coordinates_at_poles_and_antimeridian() {
+        let cases = [
+            ((90.0, 0.0), (65.0, 0.0)),
+            ((-90.0, 0.0), (-65.0, 0.0)),
+            ((0.0, 180.0), (0.0, 180.0)),
+            ((0.0, -180.0), (0.0, -180.0)),
+        ];
+
+        for ((lat, lon), (expected_lat, expected_lon)) in cases {
+            let mut config = create_test_config(
+                TEST_STANDARD_SUNSET,
+                TEST_STANDARD_SUNRISE,
+                Some(TEST_STANDARD_TRANSITION_DURATION),
+                Some(TEST_STANDARD_UPDATE_INTERVAL),
+                Some("geo"),
+                Some(TEST_STANDARD_NIGHT_TEMP),
+                Some(TEST_STANDARD_DAY_TEMP),
+                Some(TEST_STANDARD_NIGHT_GAMMA),
+                Some(TEST_STANDARD_DAY_GAMMA),
+            );
+            config.latitude = Some(lat);
+            config.longitude = Some(lon);
+
+            // Poles are capped like any latitude past 65°, the antimeridian is kept
+            Config::apply_defaults_and_validate_fields(&mut config).unwrap();
+            assert_eq!(config.latitude, Some(expected_lat));
+            assert_eq!(config.longitude, Some(expected_lon));
+            assert!(validate_config(&config).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_config_validation_geo_anchor() {
+        let mut config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+
+        config.geo_anchor = Some("twilight".to_string());
+        assert!(validate_config(&config).is_ok());
+        config.geo_anchor = Some("solar_noon".to_string());
+        assert!(validate_config(&config).is_ok());
+        config.geo_anchor = Some("noon".to_string());
+        assert!(validate_config(&config).is_err());
+
+        config.geo_anchor = Some("solar_noon".to_string());
+        config.geo_noon_offset = Some(MINIMUM_GEO_NOON_OFFSET);
+        assert!(validate_config(&config).is_ok());
+        config.geo_noon_offset = Some(MAXIMUM_GEO_NOON_OFFSET);
+        assert!(validate_config(&config).is_ok());
+        config.geo_noon_offset = Some(MAXIMUM_GEO_NOON_OFFSET + 0.5);
+        assert!(validate_config(&config).is_err());
+        config.geo_noon_offset = Some(f64::NAN);
+        assert!(validate_config(&config).is_err());
+        config.geo_noon_offset = None;
+
+        config.solar_precision = Some("fast".to_string());
+        assert!(validate_config(&config).is_ok());
+        config.solar_precision = Some("precise".to_string());
+        assert!(validate_config(&config).is_ok());
+        config.solar_precision = Some("exact".to_string());
+        assert!(validate_config(&config).is_err());
+        config.solar_precision = None;
+
+        for boundary in ["civil", "nautical", "astronomical"] {
+            config.geo_night_boundary = Some(boundary.to_string());
+            assert!(validate_config(&config).is_ok());
+        }
+        config.geo_night_boundary = Some("-18".to_string());
+        assert!(validate_config(&config).is_err());
+        config.geo_night_boundary = None;
+
+        config.geo_round_to_minutes = Some(MAXIMUM_GEO_ROUND_TO_MINUTES);
+        assert!(validate_config(&config).is_ok());
+        config.geo_round_to_minutes = Some(MAXIMUM_GEO_ROUND_TO_MINUTES + 1);
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_config_validation_extreme_gamma_values() {
+        // Test minimum gamma boundary
+        let config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(MINIMUM_GAMMA),
+            Some(MAXIMUM_GAMMA),
+        );
+        assert!(validate_config(&config).is_ok());
+
+        // Test below minimum gamma
+        let config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(MINIMUM_GAMMA - 0.1),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(validate_config(&config).is_err());
+
+        // Test above maximum gamma
+        let config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(MAXIMUM_GAMMA + 0.1),
+        );
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_config_validation_extreme_transition_durations() {
+        // Test minimum transition duration
+        let config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(MINIMUM_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(validate_config(&config).is_ok());
+
+        // Test maximum transition duration
+        let config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(MAXIMUM_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(validate_config(&config).is_ok());
+
+        // Test below minimum (should fail validation)
+        let config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(MINIMUM_TRANSITION_DURATION - 1),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(validate_config(&config).is_err());
+
+        // Zero is accepted on its own as an instant switch
+        let config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(0),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(validate_config(&config).is_ok());
+
+        // Test above maximum (should fail validation)
+        let config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(MAXIMUM_TRANSITION_DURATION + 1),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_config_validation_extreme_update_intervals() {
+        // Test minimum update interval
+        let config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(MINIMUM_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(validate_config(&config).is_ok());
+
+        // Test maximum update interval
+        let config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(120),
+            Some(MAXIMUM_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(validate_config(&config).is_ok());
+
+        // Test update interval longer than transition
+        let config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(30),
+            Some(30 * 60 + 1),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(validate_config(&config).is_err());
+        assert!(
+            validate_config(&config)
+                .unwrap_err()
+                .to_string()
+                .contains("longer than transition duration")
+        );
+    }
+
+    #[test]
+    fn test_config_validation_center_mode_overlapping() {
+        // Center mode with transition duration that would overlap
+        // Day period is about 11 hours (06:00-19:00), night is 13 hours
+        // Transition of 60 minutes in center mode means 30 minutes each side
+        let config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(60),
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some("center"),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(validate_config(&config).is_ok());
+
+        // But if we make the transition too long for center mode
+        // Let's try a 22-hour transition in center mode (11 hours each side)
+        let config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(22 * 60),
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some("center"),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_config_validation_midnight_crossings() {
+        // Sunset after midnight, sunrise in evening - valid but extreme
+        let config = create_test_config(
+            "01:00:00",
+            "22:00:00",
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(validate_config(&config).is_ok());
+
+        // Very late sunset, very early sunrise
+        let config = create_test_config(
+            "23:30:00",
+            "00:30:00",
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_config_validation_invalid_time_formats() {
+        // This should fail during parsing, not validation
+        assert!(NaiveTime::parse_from_str("25:00:00", "%H:%M:%S").is_err());
+        assert!(NaiveTime::parse_from_str("19:60:00", "%H:%M:%S").is_err());
+    }
+
+    #[test]
+    fn test_config_validation_transition_overlap_detection() {
+        // Test transition overlap detection with extreme short periods
+        let config = create_test_config(
+            "12:30:00",
+            "12:00:00",
+            Some(60),
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some("center"),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        // Should fail because day period is only 30 minutes, can't fit 1-hour center transition
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_hyprsunset_connect_wait_limits() {
+        let mut config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        config.hyprsunset_connect_timeout = Some(0);
+        config.hyprsunset_connect_retries = Some(0);
+        assert!(validate_config(&config).is_ok());
+
+        config.hyprsunset_connect_timeout = Some(MAXIMUM_HYPRSUNSET_CONNECT_TIMEOUT + 1);
+        let err = validate_config(&config).unwrap_err().to_string();
+        assert!(err.contains("hyprsunset_connect_timeout"), "{}", err);
+
+        config.hyprsunset_connect_timeout = None;
+        config.hyprsunset_connect_retries = Some(MAXIMUM_HYPRSUNSET_CONNECT_RETRIES + 1);
+        let err = validate_config(&config).unwrap_err().to_string();
+        assert!(err.contains("hyprsunset_connect_retries"), "{}", err);
+    }
+
+    #[test]
+    fn test_config_validation_separate_durations() {
+        let mut config = create_test_config(
+            "19:00:00",
+            "06:00:00",
+            Some(30),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some("finish_by"),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        config.sunrise_duration = Some(90);
+        assert!(validate_config(&config).is_ok());
+        assert_eq!(config.transition_durations(), (30, 90));
+
+        // Each override is checked against the hard limits on its own
+        config.sunset_duration = Some(500);
+        let err = validate_config(&config).unwrap_err().to_string();
+        assert!(err.contains("sunset_duration"), "{}", err);
+
+        // A long sunrise transition alone can overrun the short night
+        config.sunset = "05:00:00".to_string();
+        config.sunset_duration = None;
+        config.sunrise_duration = Some(120);
+        let err = validate_config(&config).unwrap_err().to_string();
+        assert!(err.contains("sunrise_duration"), "{}", err);
+    }
+
+    #[test]
+    fn test_config_validation_performance_warnings() {
+        // Test configuration that should generate performance warnings
+        let config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(5),
+            Some(5),
+            Some(TEST_STANDARD_MODE), // Very frequent updates
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        // Should pass validation but might generate warnings (captured in logs)
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_default_config_file_creation() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("sunsetr.toml");
+
+        Config::create_default_config(&config_path, None).unwrap();
+        assert!(config_path.exists());
+
+        let content = fs::read_to_string(&config_path).unwrap();
+        assert!(content.contains("start_hyprsunset"));
+        assert!(content.contains("sunset"));
+        assert!(content.contains("sunrise"));
+        assert!(content.contains("night_temp"));
+        assert!(content.contains("transition_mode"));
+    }
+
+    #[test]
+    fn test_config_toml_parsing() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("test_config.toml");
+
+        let config_content = r#"
+start_hyprsunset = false
+startup_transition = true
+startup_transition_duration = 15
+sunset = "19:00:00"
+sunrise = "06:00:00"
+night_temp = 3300
+day_temp = 6000
+night_gamma = 90.0
+day_gamma = 100.0
+transition_duration = 45
+update_interval = 60
+transition_mode = "finish_by"
+"#;
+
+        fs::write(&config_path, config_content).unwrap();
+        let content = fs::read_to_string(&config_path).unwrap();
+        let config: Config = toml::from_str(&content).unwrap();
+
+        assert_eq!(config.start_hyprsunset, Some(false));
+        assert_eq!(config.sunset, "19:00:00");
+        assert_eq!(config.sunrise, "06:00:00");
+        assert_eq!(config.night_temp, Some(3300));
+        assert_eq!(config.transition_mode, Some("finish_by".to_string()));
+    }
+
+    #[test]
+    fn test_config_malformed_toml() {
+        let malformed_content = r#"
+start_hyprsunset = false
+sunset = "19:00:00"
+sunrise = "06:00:00"
+night_temp = "not_a_number"  # This should cause parsing to fail
+"#;
+
+        let result: Result<Config, _> = toml::from_str(malformed_content);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_geo_toml_loading() {
+        let temp_dir = tempdir().unwrap();
+        let config_dir = temp_dir.path().join("sunsetr");
+        fs::create_dir_all(&config_dir).unwrap();
+
+        let config_path = config_dir.join("sunsetr.toml");
+        let geo_path = config_dir.join("geo.toml");
+
+        // Create main config without coordinates
+        let config_content = r#"
+start_hyprsunset = false
+sunset = "19:00:00"
+sunrise = "06:00:00"
+night_temp = 3300
+day_temp = 6500
+transition_mode = "geo"
+"#;
+        fs::write(&config_path, config_content).unwrap();
+
+        // Create geo.toml with coordinates
+        let geo_content = r#"
+# Geographic coordinates
+latitude = 51.5074
+longitude = -0.1278
+"#;
+        fs::write(&geo_path, geo_content).unwrap();
+
+        // Load config from path - directly load with the path
+        let config = Config::load_from_path(&config_path).unwrap();
+
+        // Check that coordinates were loaded from geo.toml
+        assert_eq!(config.latitude, Some(51.5074));
+        assert_eq!(config.longitude, Some(-0.1278));
+    }
+
+    #[test]
+    fn test_geo_toml_overrides_main_config() {
+        let temp_dir = tempdir().unwrap();
+        let config_dir = temp_dir.path().join("sunsetr");
+        fs::create_dir_all(&config_dir).unwrap();
+
+        let config_path = config_dir.join("sunsetr.toml");
+        let geo_path = config_dir.join("geo.toml");
+
+        // Create main config with coordinates
+        let config_content = r#"
+start_hyprsunset = false
+sunset = "19:00:00"
+sunrise = "06:00:00"
+latitude = 40.7128
+longitude = -74.0060
+transition_mode = "geo"
+"#;
+        fs::write(&config_path, config_content).unwrap();
+
+        // Create geo.toml with different coordinates
+        let geo_content = r#"
+latitude = 51.5074
+longitude = -0.1278
+"#;
+        fs::write(&geo_path, geo_content).unwrap();
+
+        // Load config directly from path (no env var needed)
+        let config = Config::load_from_path(&config_path).unwrap();
+
+        // Check that geo.toml coordinates override main config
+        assert_eq!(config.latitude, Some(51.5074));
+        assert_eq!(config.longitude, Some(-0.1278));
+    }
+
+    #[test]
+    #[serial]
+    fn test_update_geo_coordinates_with_geo_toml() {
+        let temp_dir = tempdir().unwrap();
+        let config_dir = temp_dir.path().join("sunsetr");
+        fs::create_dir_all(&config_dir).unwrap();
+
+        let config_path = config_dir.join("sunsetr.toml");
+        let geo_path = config_dir.join("geo.toml");
+
+        // Create main config
+        let config_content = r#"
+start_hyprsunset = false
+sunset = "19:00:00"
+sunrise = "06:00:00"
+transition_mode = "manual"
+"#;
+        fs::write(&config_path, config_content).unwrap();
+
+        // Create empty geo.toml
+        fs::write(&geo_path, "").unwrap();
+
+        // Save and restore XDG_CONFIG_HOME
+        let original = std::env::var("XDG_CONFIG_HOME").ok();
+        unsafe {
+            std::env::set_var("XDG_CONFIG_HOME", temp_dir.path());
+        }
+
+        // Update coordinates
+        Config::update_config_with_geo_coordinates(52.5200, 13.4050).unwrap();
+
+        // Restore original
+        unsafe {
+            match original {
+                Some(val) => std::env::set_var("XDG_CONFIG_HOME", val),
+                None => std::env::remove_var("XDG_CONFIG_HOME"),
+            }
+        }
+
+        // Check that geo.toml was updated
+        let geo_content = fs::read_to_string(&geo_path).unwrap();
+        assert!(geo_content.contains("latitude = 52.52"));
+        assert!(geo_content.contains("longitude = 13.405"));
+
+        // Check that main config transition_mode was updated
+        let main_content = fs::read_to_string(&config_path).unwrap();
+        assert!(main_content.contains("transition_mode = \"geo\""));
+    }
+
+    #[test]
+    fn test_malformed_geo_toml_fallback() {
+        let temp_dir = tempdir().unwrap();
+        let config_dir = temp_dir.path().join("sunsetr");
+        fs::create_dir_all(&config_dir).unwrap();
+
+        let config_path = config_dir.join("sunsetr.toml");
+        let geo_path = config_dir.join("geo.toml");
+
+        // Create main config with coordinates
+        let config_content = r#"
+start_hyprsunset = false
+sunset = "19:00:00"
+sunrise = "06:00:00"
+latitude = 40.7128
+longitude = -74.0060
+transition_mode = "geo"
+"#;
+        fs::write(&config_path, config_content).unwrap();
+
+        // Create malformed geo.toml
+        let geo_content = r#"
+latitude = "not a number"
+longitude = -0.1278
+"#;
+        fs::write(&geo_path, geo_content).unwrap();
+
+        // Load config - should use main config coordinates
+        let config = Config::load_from_path(&config_path).unwrap();
+
+        // Check that main config coordinates were used
+        assert_eq!(config.latitude, Some(40.7128));
+        assert_eq!(config.longitude, Some(-74.0060));
+    }
+
+    #[test]
+    #[serial]
+    fn test_geo_toml_exists_before_config_creation() {
+        let temp_dir = tempdir().unwrap();
+        let config_dir = temp_dir.path().join("sunsetr");
+        fs::create_dir_all(&config_dir).unwrap();
+
+        let config_path = config_dir.join("sunsetr.toml");
+        let geo_path = config_dir.join("geo.toml");
+
+        // Create empty geo.toml BEFORE creating config
+        fs::write(&geo_path, "").unwrap();
+
+        // Save and restore XDG_CONFIG_HOME
+        let original = std::env::var("XDG_CONFIG_HOME").ok();
+        unsafe {
+            std::env::set_var("XDG_CONFIG_HOME", temp_dir.path());
+        }
+
+        // Create config with coordinates (simulating --geo command)
+        Config::create_default_config(&config_path, Some((52.5200, 13.4050, "Berlin".to_string())))
+            .unwrap();
+
+        // Restore original
+        unsafe {
+            match original {
+                Some(val) => std::env::set_var("XDG_CONFIG_HOME", val),
+                None => std::env::remove_var("XDG_CONFIG_HOME"),
+            }
+        }
+
+        // Check that coordinates went to geo.toml
+        let geo_content = fs::read_to_string(&geo_path).unwrap();
+        assert!(geo_content.contains("latitude = 52.52"));
+        assert!(geo_content.contains("longitude = 13.405"));
+
+        // Check that main config does NOT have coordinates
+        let main_content = fs::read_to_string(&config_path).unwrap();
+        assert!(!main_content.contains("latitude = 52.52"));
+        assert!(!main_content.contains("longitude = 13.405"));
+
+        // But it should have geo transition mode
+        assert!(main_content.contains("transition_mode = \"geo\""));
+    }
+
+    #[test]
+    #[serial]
+    fn test_missing_coordinates_auto_save() {
+        let temp_dir = tempdir().unwrap();
+        let config_dir = temp_dir.path().join("sunsetr");
+        fs::create_dir_all(&config_dir).unwrap();
+
+        let config_path = config_dir.join("sunsetr.toml");
+
+        // Create config with geo mode but NO coordinates
+        let config_content = r#"
+start_hyprsunset = false
+sunset = "19:00:00"
+sunrise = "06:00:00"
+transition_mode = "geo"
+"#;
+        fs::write(&config_path, config_content).unwrap();
+
+        // Save and restore XDG_CONFIG_HOME
+        let original = std::env::var("XDG_CONFIG_HOME").ok();
+        unsafe {
+            std::env::set_var("XDG_CONFIG_HOME", temp_dir.path());
+        }
+
+        // Load config - should trigger coordinate detection and auto-save
+        let config = Config::load();
+
+        // Restore original
+        unsafe {
+            match original {
+                Some(val) => std::env::set_var("XDG_CONFIG_HOME", val),
+                None => std::env::remove_var("XDG_CONFIG_HOME"),
+            }
+        }
+
+        // If detection succeeded, config should have coordinates
+        if let Ok(loaded_config) = config {
+            assert!(loaded_config.latitude.is_some());
+            assert!(loaded_config.longitude.is_some());
 
-        // Invalid: use_wayland=true, start_hyprsunset=true (conflicting)
-        config.backend = Some(Backend::Wayland);
-        config.start_hyprsunset = Some(true);
-        let result = validate_config(&config);
-        assert!(result.is_err());
-        assert!(
-            result
-                .unwrap_err()
-                .to_string()
-                .contains("Incompatible configuration")
-        );
+            // Check that coordinates were saved to the file
+            let updated_content = fs::read_to_string(&config_path).unwrap();
+            assert!(updated_content.contains("latitude = "));
+            assert!(updated_content.contains("longitude = "));
+        }
+        // If detection failed, the load would have exited, so we can't test that path
     }
 
     #[test]
-    fn test_config_validation_identical_times() {
-        let config = create_test_config(
-            "12:00:00",
-            "12:00:00",
-            Some(TEST_STANDARD_TRANSITION_DURATION),
-            Some(TEST_STANDARD_UPDATE_INTERVAL),
-            Some(TEST_STANDARD_MODE),
-            Some(TEST_STANDARD_NIGHT_TEMP),
-            Some(TEST_STANDARD_DAY_TEMP),
-            Some(TEST_STANDARD_NIGHT_GAMMA),
-            Some(TEST_STANDARD_DAY_GAMMA),
-        );
-        assert!(validate_config(&config).is_err());
-        assert!(
-            validate_config(&config)
-                .unwrap_err()
-                .to_string()
-                .contains("cannot be the same time")
-        );
-    }
+    fn test_profiles_override_and_cycle() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("sunsetr.toml");
 
-    #[test]
-    fn test_config_validation_extreme_short_day() {
-        // 30 minute day period (sunrise 23:45, sunset 00:15)
-        let config = create_test_config(
-            "00:15:00",
-            "23:45:00",
-            Some(5),
-            Some(TEST_STANDARD_TRANSITION_DURATION),
-            Some(TEST_STANDARD_MODE),
-            Some(TEST_STANDARD_NIGHT_TEMP),
-            Some(TEST_STANDARD_DAY_TEMP),
-            Some(TEST_STANDARD_NIGHT_GAMMA),
-            Some(TEST_STANDARD_DAY_GAMMA),
-        );
-        assert!(validate_config(&config).is_err());
-        assert!(
-            validate_config(&config)
-                .unwrap_err()
-                .to_string()
-                .contains("Day period is too short")
-        );
+        let config_content = r#"
+sunset = "19:00:00"
+sunrise = "06:00:00"
+transition_mode = "finish_by"
+night_temp = 3300
+night_gamma = 90.0
+active_profile = "relax"
+
+[profiles.relax]
+night_temp = 2700
+
+[profiles.work]
+night_temp = 4000
+day_gamma = 95.0
+"#;
+        fs::write(&config_path, config_content).unwrap();
+
+        let config = Config::load_from_path(&config_path).unwrap();
+        assert_eq!(config.night_temp, Some(2700));
+        // Fields not overridden by the profile keep their top-level values
+        assert_eq!(config.night_gamma, Some(90.0));
+        assert_eq!(config.profile_names(), vec!["default", "relax", "work"]);
+        assert_eq!(config.next_profile_name(), "work");
+
+        // Runtime override takes precedence over the configured active profile
+        let config = Config::load_from_path_with_profile(&config_path, Some("work")).unwrap();
+        assert_eq!(config.night_temp, Some(4000));
+        assert_eq!(config.day_gamma, Some(95.0));
+        assert_eq!(config.next_profile_name(), "default");
+
+        let config =
+            Config::load_from_path_with_profile(&config_path, Some(DEFAULT_PROFILE_NAME)).unwrap();
+        assert_eq!(config.night_temp, Some(3300));
+        assert_eq!(config.next_profile_name(), "relax");
     }
 
     #[test]
-    fn test_config_validation_extreme_short_night() {
-        // 30 minute night period (sunset 23:45, sunrise 00:15)
-        let config = create_test_config(
-            "23:45:00",
-            "00:15:00",
-            Some(5),
-            Some(TEST_STANDARD_TRANSITION_DURATION),
-            Some(TEST_STANDARD_MODE),
-            Some(TEST_STANDARD_NIGHT_TEMP),
-            Some(TEST_STANDARD_DAY_TEMP),
-            Some(TEST_STANDARD_NIGHT_GAMMA),
-            Some(TEST_STANDARD_DAY_GAMMA),
-        );
-        assert!(validate_config(&config).is_err());
-        assert!(
-            validate_config(&config)
-                .unwrap_err()
-                .to_string()
-                .contains("Night period is too short")
-        );
+    fn test_profiles_validation() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("sunsetr.toml");
+
+        // Referencing an undefined profile is an error
+        fs::write(
+            &config_path,
+            r#"
+sunset = "19:00:00"
+sunrise = "06:00:00"
+active_profile = "missing"
+
+[profiles.relax]
+night_temp = 2700
+"#,
+        )
+        .unwrap();
+        let err = Config::load_from_path(&config_path).unwrap_err();
+        assert!(format!("{:#}", err).contains("'missing' is not defined"));
+
+        // Out-of-range values in inactive profiles are still caught
+        fs::write(
+            &config_path,
+            r#"
+sunset = "19:00:00"
+sunrise = "06:00:00"
+
+[profiles.broken]
+night_gamma = 150.0
+"#,
+        )
+        .unwrap();
+        assert!(Config::load_from_path(&config_path).is_err());
     }
 
     #[test]
-    fn test_config_validation_extreme_temperature_values() {
-        // Test minimum temperature boundary
-        let config = create_test_config(
-            TEST_STANDARD_SUNSET,
-            TEST_STANDARD_SUNRISE,
-            Some(TEST_STANDARD_TRANSITION_DURATION),
-            Some(TEST_STANDARD_UPDATE_INTERVAL),
-            Some(TEST_STANDARD_MODE),
-            Some(MINIMUM_TEMP),
-            Some(MAXIMUM_TEMP),
-            Some(TEST_STANDARD_NIGHT_GAMMA),
-            Some(TEST_STANDARD_DAY_GAMMA),
-        );
-        assert!(validate_config(&config).is_ok());
-
-        // Test below minimum temperature
-        let config = create_test_config(
-            TEST_STANDARD_SUNSET,
-            TEST_STANDARD_SUNRISE,
-            Some(TEST_STANDARD_TRANSITION_DURATION),
-            Some(TEST_STANDARD_UPDATE_INTERVAL),
-            Some(TEST_STANDARD_MODE),
-            Some(MINIMUM_TEMP - 1),
-            Some(TEST_STANDARD_DAY_TEMP),
-            Some(TEST_STANDARD_NIGHT_GAMMA),
-            Some(TEST_STANDARD_DAY_GAMMA),
-        );
-        assert!(validate_config(&config).is_err());
+    fn test_seasonal_modes() {
+        let date = |month| chrono::NaiveDate::from_ymd_opt(2025, month, 15).unwrap();
+        assert_eq!(season_for_date(date(1), false), "winter");
+        assert_eq!(season_for_date(date(4), false), "spring");
+        assert_eq!(season_for_date(date(7), false), "summer");
+        assert_eq!(season_for_date(date(10), false), "autumn");
+        assert_eq!(season_for_date(date(12), false), "winter");
+        assert_eq!(season_for_date(date(1), true), "summer");
+        assert_eq!(season_for_date(date(7), true), "winter");
 
-        // Test above maximum temperature
-        let config = create_test_config(
-            TEST_STANDARD_SUNSET,
-            TEST_STANDARD_SUNRISE,
-            Some(TEST_STANDARD_TRANSITION_DURATION),
-            Some(TEST_STANDARD_UPDATE_INTERVAL),
-            Some(TEST_STANDARD_MODE),
-            Some(TEST_STANDARD_NIGHT_TEMP),
-            Some(MAXIMUM_TEMP + 1),
-            Some(TEST_STANDARD_NIGHT_GAMMA),
-            Some(TEST_STANDARD_DAY_GAMMA),
-        );
-        assert!(validate_config(&config).is_err());
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("sunsetr.toml");
+        let base = "sunset = \"19:00:00\"\nsunrise = \"06:00:00\"\ntransition_mode = \"geo\"\n\
+            latitude = 52.0\nlongitude = 13.0\n";
+        fs::write(
+            &config_path,
+            format!("{}[seasonal_modes]\nsummer = \"finish_by\"\n", base),
+        )
+        .unwrap();
+        let mut config = Config::load_from_path(&config_path).unwrap();
+        assert_eq!(config.transition_mode_on(date(7)), Some("finish_by"));
+        assert_eq!(config.transition_mode_on(date(1)), Some("geo"));
+
+        // South of the equator July is winter, which has no entry
+        config.latitude = Some(-33.9);
+        assert_eq!(config.transition_mode_on(date(7)), Some("geo"));
+        assert_eq!(config.transition_mode_on(date(1)), Some("finish_by"));
+
+        for (seasonal, expected) in [
+            ("monsoon = \"geo\"", "unknown season 'monsoon'"),
+            ("winter = \"manual\"", "seasonal_modes.winter"),
+        ] {
+            fs::write(
+                &config_path,
+                format!("{}[seasonal_modes]\n{}\n", base, seasonal),
+            )
+            .unwrap();
+            let err = format!("{:#}", Config::load_from_path(&config_path).unwrap_err());
+            assert!(err.contains(expected), "{}", err);
+        }
     }
 
     #[test]
-    fn test_config_validation_extreme_gamma_values() {
-        // Test minimum gamma boundary
-        let config = create_test_config(
-            TEST_STANDARD_SUNSET,
-            TEST_STANDARD_SUNRISE,
-            Some(TEST_STANDARD_TRANSITION_DURATION),
-            Some(TEST_STANDARD_UPDATE_INTERVAL),
-            Some(TEST_STANDARD_MODE),
-            Some(TEST_STANDARD_NIGHT_TEMP),
-            Some(TEST_STANDARD_DAY_TEMP),
-            Some(MINIMUM_GAMMA),
-            Some(MAXIMUM_GAMMA),
-        );
-        assert!(validate_config(&config).is_ok());
+    fn test_output_groups() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("sunsetr.toml");
 
-        // Test below minimum gamma
-        let config = create_test_config(
-            TEST_STANDARD_SUNSET,
-            TEST_STANDARD_SUNRISE,
-            Some(TEST_STANDARD_TRANSITION_DURATION),
-            Some(TEST_STANDARD_UPDATE_INTERVAL),
-            Some(TEST_STANDARD_MODE),
-            Some(TEST_STANDARD_NIGHT_TEMP),
-            Some(TEST_STANDARD_DAY_TEMP),
-            Some(MINIMUM_GAMMA - 0.1),
-            Some(TEST_STANDARD_DAY_GAMMA),
-        );
-        assert!(validate_config(&config).is_err());
+        fs::write(
+            &config_path,
+            r#"
+sunset = "19:00:00"
+sunrise = "06:00:00"
+transition_mode = "center"
+night_temp = 3300
+sunset_duration = 60
+
+[output_groups.secondary]
+outputs = ["HDMI-A-*"]
+sunset = "17:30:00"
+transition_duration = 20
+night_temp = "candle"
+"#,
+        )
+        .unwrap();
+        let config = Config::load_from_path(&config_path).unwrap();
+        let groups = config.output_group_configs();
+        assert_eq!(groups.len(), 1);
+
+        let (name, group, group_config) = &groups[0];
+        assert_eq!(name, "secondary");
+        assert_eq!(group.outputs, vec!["HDMI-A-*".to_string()]);
+        assert_eq!(group_config.sunset, "17:30:00");
+        assert_eq!(group_config.sunrise, "06:00:00");
+        assert_eq!(group_config.transition_durations(), (20, 20));
+        assert_eq!(group_config.night_temp, Some(1900));
+        assert_eq!(group_config.transition_mode.as_deref(), Some("center"));
+        assert!(group_config.output_groups.is_none());
+
+        // A group's schedule is validated on its own
+        fs::write(
+            &config_path,
+            r#"
+sunset = "19:00:00"
+sunrise = "06:00:00"
 
-        // Test above maximum gamma
-        let config = create_test_config(
-            TEST_STANDARD_SUNSET,
-            TEST_STANDARD_SUNRISE,
-            Some(TEST_STANDARD_TRANSITION_DURATION),
-            Some(TEST_STANDARD_UPDATE_INTERVAL),
-            Some(TEST_STANDARD_MODE),
-            Some(TEST_STANDARD_NIGHT_TEMP),
-            Some(TEST_STANDARD_DAY_TEMP),
-            Some(TEST_STANDARD_NIGHT_GAMMA),
-            Some(MAXIMUM_GAMMA + 0.1),
-        );
-        assert!(validate_config(&config).is_err());
+[output_groups.broken]
+outputs = ["DP-1"]
+sunset = "06:00:00"
+"#,
+        )
+        .unwrap();
+        let err = Config::load_from_path(&config_path).unwrap_err();
+        assert!(format!("{:#}", err).contains("Output group 'broken'"));
+
+        // Groups need outputs, and unknown keys inside them are reported
+        fs::write(
+            &config_path,
+            "sunset = \"19:00:00\"\nsunrise = \"06:00:00\"\n[output_groups.empty]\nsunset = \"18:00:00\"\n",
+        )
+        .unwrap();
+        assert!(Config::load_from_path(&config_path).is_err());
+
+        fs::write(
+            &config_path,
+            "sunset = \"19:00:00\"\nsunrise = \"06:00:00\"\n[output_groups.typo]\noutputs = [\"DP-1\"]\nsunste = \"18:00:00\"\n",
+        )
+        .unwrap();
+        let err = format!("{:#}", Config::load_from_path(&config_path).unwrap_err());
+        assert!(err.contains("in [output_groups.typo]"));
+        assert!(err.contains("Did you mean 'sunset'?"));
     }
 
     #[test]
-    fn test_config_validation_extreme_transition_durations() {
-        // Test minimum transition duration
-        let config = create_test_config(
-            TEST_STANDARD_SUNSET,
-            TEST_STANDARD_SUNRISE,
-            Some(MINIMUM_TRANSITION_DURATION),
-            Some(TEST_STANDARD_UPDATE_INTERVAL),
-            Some(TEST_STANDARD_MODE),
-            Some(TEST_STANDARD_NIGHT_TEMP),
-            Some(TEST_STANDARD_DAY_TEMP),
-            Some(TEST_STANDARD_NIGHT_GAMMA),
-            Some(TEST_STANDARD_DAY_GAMMA),
-        );
-        assert!(validate_config(&config).is_ok());
+    fn test_unknown_config_keys_are_rejected() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("sunsetr.toml");
 
-        // Test maximum transition duration
-        let config = create_test_config(
-            TEST_STANDARD_SUNSET,
-            TEST_STANDARD_SUNRISE,
-            Some(MAXIMUM_TRANSITION_DURATION),
-            Some(TEST_STANDARD_UPDATE_INTERVAL),
-            Some(TEST_STANDARD_MODE),
-            Some(TEST_STANDARD_NIGHT_TEMP),
-            Some(TEST_STANDARD_DAY_TEMP),
-            Some(TEST_STANDARD_NIGHT_GAMMA),
-            Some(TEST_STANDARD_DAY_GAMMA),
-        );
-        assert!(validate_config(&config).is_ok());
+        fs::write(
+            &config_path,
+            r#"
+sunset = "19:00:00"
+sunrise = "06:00:00"
+nite_temp = 3000
+"#,
+        )
+        .unwrap();
+        let err = format!("{:#}", Config::load_from_path(&config_path).unwrap_err());
+        assert!(err.contains("Unknown config key 'nite_temp'"));
+        assert!(err.contains("Did you mean 'night_temp'?"));
+
+        fs::write(
+            &config_path,
+            r#"
+sunset = "19:00:00"
+sunrise = "06:00:00"
 
-        // Test below minimum (should fail validation)
-        let config = create_test_config(
-            TEST_STANDARD_SUNSET,
-            TEST_STANDARD_SUNRISE,
-            Some(MINIMUM_TRANSITION_DURATION - 1),
-            Some(TEST_STANDARD_UPDATE_INTERVAL),
-            Some(TEST_STANDARD_MODE),
-            Some(TEST_STANDARD_NIGHT_TEMP),
-            Some(TEST_STANDARD_DAY_TEMP),
-            Some(TEST_STANDARD_NIGHT_GAMMA),
-            Some(TEST_STANDARD_DAY_GAMMA),
-        );
-        assert!(validate_config(&config).is_err());
+[profiles.relax]
+day_gama = 90.0
+"#,
+        )
+        .unwrap();
+        let err = format!("{:#}", Config::load_from_path(&config_path).unwrap_err());
+        assert!(err.contains("in [profiles.relax]"));
+        assert!(err.contains("Did you mean 'day_gamma'?"));
+
+        // Nothing close enough to suggest
+        fs::write(
+            &config_path,
+            r#"
+sunset = "19:00:00"
+sunrise = "06:00:00"
+completely_unrelated = true
+"#,
+        )
+        .unwrap();
+        let err = format!("{:#}", Config::load_from_path(&config_path).unwrap_err());
+        assert!(err.contains("Valid keys are:"));
+    }
 
-        // Test above maximum (should fail validation)
-        let config = create_test_config(
+    #[test]
+    fn test_config_validation_day_whitepoint() {
+        let mut config = create_test_config(
             TEST_STANDARD_SUNSET,
             TEST_STANDARD_SUNRISE,
-            Some(MAXIMUM_TRANSITION_DURATION + 1),
+            Some(TEST_STANDARD_TRANSITION_DURATION),
             Some(TEST_STANDARD_UPDATE_INTERVAL),
             Some(TEST_STANDARD_MODE),
             Some(TEST_STANDARD_NIGHT_TEMP),
@@ -1954,100 +5742,92 @@ mod tests {
             Some(TEST_STANDARD_NIGHT_GAMMA),
             Some(TEST_STANDARD_DAY_GAMMA),
         );
+
+        config.day_whitepoint = Some(DEFAULT_DAY_WHITEPOINT);
+        assert!(validate_config(&config).is_ok());
+
+        config.day_whitepoint = Some([0.8, 0.4]);
         assert!(validate_config(&config).is_err());
     }
 
     #[test]
-    fn test_config_validation_extreme_update_intervals() {
-        // Test minimum update interval
-        let config = create_test_config(
+    fn test_effective_config_resolves_defaults() {
+        let config: Config = toml::from_str(
+            "sunset = \"19:00:00\"\nsunrise = \"06:00:00\"\nnight_temp = 2800\nnight_gamma_b = 0.8",
+        )
+        .unwrap();
+        let effective = config.effective();
+
+        // Set values come through, everything else falls back to its default
+        assert_eq!(effective.night_temp, 2800);
+        assert_eq!(effective.night_channel_gamma, (1.0, 1.0, 0.8));
+        assert_eq!(effective.day_temp, DEFAULT_DAY_TEMP);
+        assert_eq!(effective.night_gamma, DEFAULT_NIGHT_GAMMA);
+        assert_eq!(effective.update_interval, DEFAULT_UPDATE_INTERVAL);
+        assert_eq!(effective.startup_transition, DEFAULT_STARTUP_TRANSITION);
+        assert_eq!(effective.reset_on_exit, DEFAULT_RESET_ON_EXIT);
+        assert_eq!(effective.temp_quantum, DEFAULT_TEMP_QUANTUM);
+    }
+
+    #[test]
+    fn test_config_validation_gamma_table_format() {
+        let mut config = create_test_config(
             TEST_STANDARD_SUNSET,
             TEST_STANDARD_SUNRISE,
             Some(TEST_STANDARD_TRANSITION_DURATION),
-            Some(MINIMUM_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
             Some(TEST_STANDARD_MODE),
             Some(TEST_STANDARD_NIGHT_TEMP),
             Some(TEST_STANDARD_DAY_TEMP),
             Some(TEST_STANDARD_NIGHT_GAMMA),
             Some(TEST_STANDARD_DAY_GAMMA),
         );
-        assert!(validate_config(&config).is_ok());
 
-        // Test maximum update interval
-        let config = create_test_config(
-            TEST_STANDARD_SUNSET,
-            TEST_STANDARD_SUNRISE,
-            Some(120),
-            Some(MAXIMUM_UPDATE_INTERVAL),
-            Some(TEST_STANDARD_MODE),
-            Some(TEST_STANDARD_NIGHT_TEMP),
-            Some(TEST_STANDARD_DAY_TEMP),
-            Some(TEST_STANDARD_NIGHT_GAMMA),
-            Some(TEST_STANDARD_DAY_GAMMA),
-        );
+        config.gamma_table_format = Some("interleaved-be".to_string());
         assert!(validate_config(&config).is_ok());
 
-        // Test update interval longer than transition
-        let config = create_test_config(
-            TEST_STANDARD_SUNSET,
-            TEST_STANDARD_SUNRISE,
-            Some(30),
-            Some(30 * 60 + 1),
-            Some(TEST_STANDARD_MODE),
-            Some(TEST_STANDARD_NIGHT_TEMP),
-            Some(TEST_STANDARD_DAY_TEMP),
-            Some(TEST_STANDARD_NIGHT_GAMMA),
-            Some(TEST_STANDARD_DAY_GAMMA),
-        );
-        assert!(validate_config(&config).is_err());
-        assert!(
-            validate_config(&config)
-                .unwrap_err()
-                .to_string()
-                .contains("longer than transition duration")
-        );
+        config.gamma_table_format = Some("rgb".to_string());
+        let err = validate_config(&config).unwrap_err();
+        assert!(format!("{:#}", err).contains("gamma_table_format"));
     }
 
     #[test]
-    fn test_config_validation_center_mode_overlapping() {
-        // Center mode with transition duration that would overlap
-        // Day period is about 11 hours (06:00-19:00), night is 13 hours
-        // Transition of 60 minutes in center mode means 30 minutes each side
-        let config = create_test_config(
+    fn test_config_on_fatal_exit() {
+        let mut config = create_test_config(
             TEST_STANDARD_SUNSET,
             TEST_STANDARD_SUNRISE,
-            Some(60),
             Some(TEST_STANDARD_TRANSITION_DURATION),
-            Some("center"),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
             Some(TEST_STANDARD_NIGHT_TEMP),
             Some(TEST_STANDARD_DAY_TEMP),
             Some(TEST_STANDARD_NIGHT_GAMMA),
             Some(TEST_STANDARD_DAY_GAMMA),
         );
+
+        // Unset, a fatal exit behaves like any other exit
+        assert!(config.reset_on_fatal_exit());
+        config.reset_on_exit = Some(false);
+        assert!(!config.reset_on_fatal_exit());
+
+        config.on_fatal_exit = Some("reset".to_string());
         assert!(validate_config(&config).is_ok());
+        assert!(config.reset_on_fatal_exit());
 
-        // But if we make the transition too long for center mode
-        // Let's try a 22-hour transition in center mode (11 hours each side)
-        let config = create_test_config(
-            TEST_STANDARD_SUNSET,
-            TEST_STANDARD_SUNRISE,
-            Some(22 * 60),
-            Some(TEST_STANDARD_TRANSITION_DURATION),
-            Some("center"),
-            Some(TEST_STANDARD_NIGHT_TEMP),
-            Some(TEST_STANDARD_DAY_TEMP),
-            Some(TEST_STANDARD_NIGHT_GAMMA),
-            Some(TEST_STANDARD_DAY_GAMMA),
-        );
-        assert!(validate_config(&config).is_err());
+        config.reset_on_exit = None;
+        config.on_fatal_exit = Some("leave".to_string());
+        assert!(!config.reset_on_fatal_exit());
+
+        config.on_fatal_exit = Some("keep".to_string());
+        let err = validate_config(&config).unwrap_err();
+        assert!(format!("{:#}", err).contains("on_fatal_exit"));
     }
 
     #[test]
-    fn test_config_validation_midnight_crossings() {
-        // Sunset after midnight, sunrise in evening - valid but extreme
-        let config = create_test_config(
-            "01:00:00",
-            "22:00:00",
+    fn test_config_idle_dimming_limits() {
+        let mut config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
             Some(TEST_STANDARD_TRANSITION_DURATION),
             Some(TEST_STANDARD_UPDATE_INTERVAL),
             Some(TEST_STANDARD_MODE),
@@ -2056,371 +5836,370 @@ mod tests {
             Some(TEST_STANDARD_NIGHT_GAMMA),
             Some(TEST_STANDARD_DAY_GAMMA),
         );
-        assert!(validate_config(&config).is_ok());
+        assert_eq!(config.effective().idle_gamma, DEFAULT_IDLE_GAMMA);
 
-        // Very late sunset, very early sunrise
-        let config = create_test_config(
-            "23:30:00",
-            "00:30:00",
-            Some(TEST_STANDARD_TRANSITION_DURATION),
-            Some(TEST_STANDARD_UPDATE_INTERVAL),
-            Some(TEST_STANDARD_MODE),
-            Some(TEST_STANDARD_NIGHT_TEMP),
-            Some(TEST_STANDARD_DAY_TEMP),
-            Some(TEST_STANDARD_NIGHT_GAMMA),
-            Some(TEST_STANDARD_DAY_GAMMA),
-        );
+        config.idle_timeout = Some(300);
+        config.idle_gamma = Some(50.0);
         assert!(validate_config(&config).is_ok());
-    }
 
-    #[test]
-    fn test_config_validation_invalid_time_formats() {
-        // This should fail during parsing, not validation
-        assert!(NaiveTime::parse_from_str("25:00:00", "%H:%M:%S").is_err());
-        assert!(NaiveTime::parse_from_str("19:60:00", "%H:%M:%S").is_err());
-    }
+        config.idle_timeout = Some(MINIMUM_IDLE_TIMEOUT - 1);
+        let err = validate_config(&config).unwrap_err();
+        assert!(format!("{:#}", err).contains("Idle timeout"));
 
-    #[test]
-    fn test_config_validation_transition_overlap_detection() {
-        // Test transition overlap detection with extreme short periods
-        let config = create_test_config(
-            "12:30:00",
-            "12:00:00",
-            Some(60),
-            Some(TEST_STANDARD_TRANSITION_DURATION),
-            Some("center"),
-            Some(TEST_STANDARD_NIGHT_TEMP),
-            Some(TEST_STANDARD_DAY_TEMP),
-            Some(TEST_STANDARD_NIGHT_GAMMA),
-            Some(TEST_STANDARD_DAY_GAMMA),
-        );
-        // Should fail because day period is only 30 minutes, can't fit 1-hour center transition
-        assert!(validate_config(&config).is_err());
+        config.idle_timeout = Some(300);
+        config.idle_gamma = Some(MAXIMUM_GAMMA + 1.0);
+        let err = validate_config(&config).unwrap_err();
+        assert!(format!("{:#}", err).contains("Idle gamma"));
     }
 
     #[test]
-    fn test_config_validation_performance_warnings() {
-        // Test configuration that should generate performance warnings
-        let config = create_test_config(
+    fn test_config_sunrise_hold() {
+        let mut config = create_test_config(
             TEST_STANDARD_SUNSET,
             TEST_STANDARD_SUNRISE,
-            Some(5),
-            Some(5),
-            Some(TEST_STANDARD_MODE), // Very frequent updates
-            Some(TEST_STANDARD_NIGHT_TEMP),
-            Some(TEST_STANDARD_DAY_TEMP),
+            Some(60),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some("finish_by"),
+            Some(3300),
+            Some(6500),
             Some(TEST_STANDARD_NIGHT_GAMMA),
             Some(TEST_STANDARD_DAY_GAMMA),
         );
-        // Should pass validation but might generate warnings (captured in logs)
+        config.sunrise_hold_temp = Some(5000);
+        config.sunrise_hold_minutes = Some(20);
         assert!(validate_config(&config).is_ok());
-    }
-
-    #[test]
-    fn test_default_config_file_creation() {
-        let temp_dir = tempdir().unwrap();
-        let config_path = temp_dir.path().join("sunsetr.toml");
-
-        Config::create_default_config(&config_path, None).unwrap();
-        assert!(config_path.exists());
-
-        let content = fs::read_to_string(&config_path).unwrap();
-        assert!(content.contains("start_hyprsunset"));
-        assert!(content.contains("sunset"));
-        assert!(content.contains("sunrise"));
-        assert!(content.contains("night_temp"));
-        assert!(content.contains("transition_mode"));
-    }
-
-    #[test]
-    fn test_config_toml_parsing() {
-        let temp_dir = tempdir().unwrap();
-        let config_path = temp_dir.path().join("test_config.toml");
-
-        let config_content = r#"
-start_hyprsunset = false
-startup_transition = true
-startup_transition_duration = 15
-sunset = "19:00:00"
-sunrise = "06:00:00"
-night_temp = 3300
-day_temp = 6000
-night_gamma = 90.0
-day_gamma = 100.0
-transition_duration = 45
-update_interval = 60
-transition_mode = "finish_by"
-"#;
-
-        fs::write(&config_path, config_content).unwrap();
-        let content = fs::read_to_string(&config_path).unwrap();
-        let config: Config = toml::from_str(&content).unwrap();
-
-        assert_eq!(config.start_hyprsunset, Some(false));
-        assert_eq!(config.sunset, "19:00:00");
-        assert_eq!(config.sunrise, "06:00:00");
-        assert_eq!(config.night_temp, Some(3300));
-        assert_eq!(config.transition_mode, Some("finish_by".to_string()));
-    }
-
-    #[test]
-    fn test_config_malformed_toml() {
-        let malformed_content = r#"
-start_hyprsunset = false
-sunset = "19:00:00"
-sunrise = "06:00:00"
-night_temp = "not_a_number"  # This should cause parsing to fail
-"#;
 
-        let result: Result<Config, _> = toml::from_str(malformed_content);
-        assert!(result.is_err());
-    }
-
-    #[test]
-    fn test_geo_toml_loading() {
-        let temp_dir = tempdir().unwrap();
-        let config_dir = temp_dir.path().join("sunsetr");
-        fs::create_dir_all(&config_dir).unwrap();
-
-        let config_path = config_dir.join("sunsetr.toml");
-        let geo_path = config_dir.join("geo.toml");
-
-        // Create main config without coordinates
-        let config_content = r#"
-start_hyprsunset = false
-sunset = "19:00:00"
-sunrise = "06:00:00"
-night_temp = 3300
-day_temp = 6500
-transition_mode = "geo"
-"#;
-        fs::write(&config_path, config_content).unwrap();
-
-        // Create geo.toml with coordinates
-        let geo_content = r#"
-# Geographic coordinates
-latitude = 51.5074
-longitude = -0.1278
-"#;
-        fs::write(&geo_path, geo_content).unwrap();
-
-        // Load config from path - directly load with the path
-        let config = Config::load_from_path(&config_path).unwrap();
-
-        // Check that coordinates were loaded from geo.toml
-        assert_eq!(config.latitude, Some(51.5074));
-        assert_eq!(config.longitude, Some(-0.1278));
-    }
-
-    #[test]
-    fn test_geo_toml_overrides_main_config() {
-        let temp_dir = tempdir().unwrap();
-        let config_dir = temp_dir.path().join("sunsetr");
-        fs::create_dir_all(&config_dir).unwrap();
-
-        let config_path = config_dir.join("sunsetr.toml");
-        let geo_path = config_dir.join("geo.toml");
+        // Both keys or neither
+        config.sunrise_hold_minutes = None;
+        let err = format!("{:#}", validate_config(&config).unwrap_err());
+        assert!(err.contains("must be set together"), "{}", err);
 
-        // Create main config with coordinates
-        let config_content = r#"
-start_hyprsunset = false
-sunset = "19:00:00"
-sunrise = "06:00:00"
-latitude = 40.7128
-longitude = -74.0060
-transition_mode = "geo"
-"#;
-        fs::write(&config_path, config_content).unwrap();
+        // The hold temperature has to lie between night and day
+        config.sunrise_hold_minutes = Some(20);
+        config.sunrise_hold_temp = Some(7000);
+        let err = format!("{:#}", validate_config(&config).unwrap_err());
+        assert!(err.contains("sunrise_hold_temp (7000)"), "{}", err);
 
-        // Create geo.toml with different coordinates
-        let geo_content = r#"
-latitude = 51.5074
-longitude = -0.1278
-"#;
-        fs::write(&geo_path, geo_content).unwrap();
+        // Without color shifting the pause is ignored, so its temperature isn't checked
+        config.color_shift = Some(false);
+        assert!(validate_config(&config).is_ok());
+        config.color_shift = None;
 
-        // Load config directly from path (no env var needed)
-        let config = Config::load_from_path(&config_path).unwrap();
+        // The pause has to leave room to ramp within the 60 minute sunrise
+        config.sunrise_hold_temp = Some(5000);
+        config.sunrise_hold_minutes = Some(60);
+        let err = format!("{:#}", validate_config(&config).unwrap_err());
+        assert!(err.contains("60 minute sunrise transition"), "{}", err);
 
-        // Check that geo.toml coordinates override main config
-        assert_eq!(config.latitude, Some(51.5074));
-        assert_eq!(config.longitude, Some(-0.1278));
+        config.sunrise_duration = Some(90);
+        assert!(validate_config(&config).is_ok());
     }
 
     #[test]
-    #[serial]
-    fn test_update_geo_coordinates_with_geo_toml() {
-        let temp_dir = tempdir().unwrap();
-        let config_dir = temp_dir.path().join("sunsetr");
-        fs::create_dir_all(&config_dir).unwrap();
+    fn test_fill_geo_coordinates() {
+        let mut config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some("geo"),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
 
-        let config_path = config_dir.join("sunsetr.toml");
-        let geo_path = config_dir.join("geo.toml");
+        // No coordinates and no detectable timezone: fail with advice
+        let err = fill_geo_coordinates(&mut config, || anyhow::bail!("no timezone")).unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(message.contains("no timezone"));
+        assert!(message.contains("sunsetr --geo"));
+        assert!(message.contains("latitude and longitude"));
+        assert!(message.contains("transition_mode"));
+        assert_eq!((config.latitude, config.longitude), (None, None));
+
+        // Detected coordinates are filled in
+        let detected =
+            fill_geo_coordinates(&mut config, || Ok((51.5, -0.12, "London".to_string()))).unwrap();
+        assert_eq!(detected, Some((51.5, -0.12, "London".to_string())));
+        assert_eq!(
+            (config.latitude, config.longitude),
+            (Some(51.5), Some(-0.12))
+        );
 
-        // Create main config
-        let config_content = r#"
-start_hyprsunset = false
-sunset = "19:00:00"
-sunrise = "06:00:00"
-transition_mode = "manual"
-"#;
-        fs::write(&config_path, config_content).unwrap();
+        // Existing coordinates and other modes never need detection
+        let unreachable = || -> Result<(f64, f64, String)> { panic!("detection not needed") };
+        assert_eq!(
+            fill_geo_coordinates(&mut config, unreachable).unwrap(),
+            None
+        );
+        config.latitude = None;
+        config.transition_mode = Some("finish_by".to_string());
+        assert_eq!(
+            fill_geo_coordinates(&mut config, unreachable).unwrap(),
+            None
+        );
+    }
 
-        // Create empty geo.toml
-        fs::write(&geo_path, "").unwrap();
+    #[test]
+    fn test_config_log_verbosity() {
+        let mut config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert_eq!(config.log_verbosity(), LogVerbosity::Normal);
 
-        // Save and restore XDG_CONFIG_HOME
-        let original = std::env::var("XDG_CONFIG_HOME").ok();
-        unsafe {
-            std::env::set_var("XDG_CONFIG_HOME", temp_dir.path());
-        }
+        config.log_verbosity = Some("quiet".to_string());
+        assert!(validate_config(&config).is_ok());
+        assert_eq!(config.log_verbosity(), LogVerbosity::Quiet);
 
-        // Update coordinates
-        Config::update_config_with_geo_coordinates(52.5200, 13.4050).unwrap();
+        config.log_verbosity = Some("loud".to_string());
+        let err = validate_config(&config).unwrap_err();
+        assert!(format!("{:#}", err).contains("log_verbosity"));
+    }
 
-        // Restore original
-        unsafe {
-            match original {
-                Some(val) => std::env::set_var("XDG_CONFIG_HOME", val),
-                None => std::env::remove_var("XDG_CONFIG_HOME"),
-            }
-        }
+    #[test]
+    fn test_config_manual_geo_mode() {
+        let mut config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some("manual_geo"),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(Config::apply_defaults_and_validate_fields(&mut config.clone()).is_ok());
+        assert!(config.uses_geo_mode());
+        assert_eq!(config.manual_geo_event(), ManualGeoEvent::Sunset);
 
-        // Check that geo.toml was updated
-        let geo_content = fs::read_to_string(&geo_path).unwrap();
-        assert!(geo_content.contains("latitude = 52.52"));
-        assert!(geo_content.contains("longitude = 13.405"));
+        config.manual_geo_event = Some("sunrise".to_string());
+        assert!(validate_config(&config).is_ok());
+        assert_eq!(config.manual_geo_event(), ManualGeoEvent::Sunrise);
 
-        // Check that main config transition_mode was updated
-        let main_content = fs::read_to_string(&config_path).unwrap();
-        assert!(main_content.contains("transition_mode = \"geo\""));
+        config.manual_geo_event = Some("noon".to_string());
+        let err = validate_config(&config).unwrap_err();
+        assert!(format!("{:#}", err).contains("manual_geo_event"));
+        config.manual_geo_event = None;
+
+        // The geo-driven event needs coordinates just like geo mode
+        let err = fill_geo_coordinates(&mut config, || anyhow::bail!("no timezone")).unwrap_err();
+        assert!(format!("{:#}", err).contains("sunsetr --geo"));
     }
 
     #[test]
-    fn test_malformed_geo_toml_fallback() {
-        let temp_dir = tempdir().unwrap();
-        let config_dir = temp_dir.path().join("sunsetr");
-        fs::create_dir_all(&config_dir).unwrap();
+    fn test_config_manual_geo_transition_longer_than_window() {
+        let mut config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some("manual_geo"),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        config.latitude = Some(51.5074);
+        config.longitude = Some(-0.1278);
+
+        // Leave a 40 minute night after today's computed sunset transition
+        let (_, sunset_end, _, _) = crate::time_state::transition_windows(&config);
+        let sunset_end = sunset_end.with_nanosecond(0).unwrap();
+        let sunrise = sunset_end + chrono::Duration::minutes(40);
+        config.sunrise = sunrise.format("%H:%M:%S").to_string();
+        config.sunset = (sunrise + chrono::Duration::hours(12))
+            .format("%H:%M:%S")
+            .to_string();
+
+        config.sunrise_duration = Some(30);
+        assert!(validate_config(&config).is_ok());
 
-        let config_path = config_dir.join("sunsetr.toml");
-        let geo_path = config_dir.join("geo.toml");
+        config.sunrise_duration = Some(120);
+        let err = format!("{:#}", validate_config(&config).unwrap_err());
+        assert!(err.contains("sunrise_duration (120 minutes)"), "{}", err);
+        assert!(err.contains("40 minutes available"), "{}", err);
+    }
 
-        // Create main config with coordinates
-        let config_content = r#"
-start_hyprsunset = false
-sunset = "19:00:00"
-sunrise = "06:00:00"
-latitude = 40.7128
-longitude = -74.0060
-transition_mode = "geo"
-"#;
-        fs::write(&config_path, config_content).unwrap();
+    #[test]
+    fn test_config_auto_prefer() {
+        let mut config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert_eq!(config.auto_prefer(), DEFAULT_AUTO_PREFER);
 
-        // Create malformed geo.toml
-        let geo_content = r#"
-latitude = "not a number"
-longitude = -0.1278
-"#;
-        fs::write(&geo_path, geo_content).unwrap();
+        config.auto_prefer = Some(vec!["wayland".to_string(), "hyprland".to_string()]);
+        assert!(validate_config(&config).is_ok());
+        assert_eq!(
+            config.auto_prefer(),
+            vec![Backend::Wayland, Backend::Hyprland]
+        );
 
-        // Load config - should use main config coordinates
-        let config = Config::load_from_path(&config_path).unwrap();
+        config.auto_prefer = Some(vec!["wayland".to_string(), "gnome".to_string()]);
+        let err = validate_config(&config).unwrap_err();
+        assert!(format!("{:#}", err).contains("auto_prefer entry \"gnome\""));
 
-        // Check that main config coordinates were used
-        assert_eq!(config.latitude, Some(40.7128));
-        assert_eq!(config.longitude, Some(-74.0060));
+        config.auto_prefer = Some(vec!["wayland".to_string(), "wayland".to_string()]);
+        let err = validate_config(&config).unwrap_err();
+        assert!(format!("{:#}", err).contains("more than once"));
     }
 
     #[test]
-    #[serial]
-    fn test_geo_toml_exists_before_config_creation() {
-        let temp_dir = tempdir().unwrap();
-        let config_dir = temp_dir.path().join("sunsetr");
-        fs::create_dir_all(&config_dir).unwrap();
-
-        let config_path = config_dir.join("sunsetr.toml");
-        let geo_path = config_dir.join("geo.toml");
-
-        // Create empty geo.toml BEFORE creating config
-        fs::write(&geo_path, "").unwrap();
+    fn test_config_validation_channel_gamma() {
+        let mut config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        assert!(!config.has_channel_gamma());
+        assert_eq!(config.day_channel_gamma(), (1.0, 1.0, 1.0));
 
-        // Save and restore XDG_CONFIG_HOME
-        let original = std::env::var("XDG_CONFIG_HOME").ok();
-        unsafe {
-            std::env::set_var("XDG_CONFIG_HOME", temp_dir.path());
-        }
+        config.night_gamma_b = Some(0.0);
+        config.day_gamma_r = Some(2.0);
+        assert!(validate_config(&config).is_ok());
+        assert!(config.has_channel_gamma());
+        assert_eq!(config.night_channel_gamma(), (1.0, 1.0, 0.0));
 
-        // Create config with coordinates (simulating --geo command)
-        Config::create_default_config(&config_path, Some((52.5200, 13.4050, "Berlin".to_string())))
-            .unwrap();
+        config.day_gamma_g = Some(2.5);
+        let err = validate_config(&config).unwrap_err().to_string();
+        assert!(err.contains("day_gamma_g"), "{}", err);
 
-        // Restore original
-        unsafe {
-            match original {
-                Some(val) => std::env::set_var("XDG_CONFIG_HOME", val),
-                None => std::env::remove_var("XDG_CONFIG_HOME"),
-            }
-        }
+        config.day_gamma_g = Some(-0.1);
+        assert!(validate_config(&config).is_err());
+    }
 
-        // Check that coordinates went to geo.toml
-        let geo_content = fs::read_to_string(&geo_path).unwrap();
-        assert!(geo_content.contains("latitude = 52.52"));
-        assert!(geo_content.contains("longitude = 13.405"));
+    #[test]
+    fn test_hdr_color_config_falls_back_to_regular_values() {
+        let mut config = create_test_config(
+            TEST_STANDARD_SUNSET,
+            TEST_STANDARD_SUNRISE,
+            Some(TEST_STANDARD_TRANSITION_DURATION),
+            Some(TEST_STANDARD_UPDATE_INTERVAL),
+            Some(TEST_STANDARD_MODE),
+            Some(TEST_STANDARD_NIGHT_TEMP),
+            Some(TEST_STANDARD_DAY_TEMP),
+            Some(TEST_STANDARD_NIGHT_GAMMA),
+            Some(TEST_STANDARD_DAY_GAMMA),
+        );
+        config.hdr_outputs = Some(vec!["DP-*".to_string()]);
+        config.hdr_night_temp = Some(4500);
+        config.hdr_day_gamma = Some(80.0);
+        assert!(validate_config(&config).is_ok());
 
-        // Check that main config does NOT have coordinates
-        let main_content = fs::read_to_string(&config_path).unwrap();
-        assert!(!main_content.contains("latitude = 52.52"));
-        assert!(!main_content.contains("longitude = 13.405"));
+        let hdr = config.hdr_color_config();
+        assert_eq!(hdr.night_temp, Some(4500));
+        assert_eq!(hdr.day_temp, Some(TEST_STANDARD_DAY_TEMP));
+        assert_eq!(hdr.night_gamma, Some(TEST_STANDARD_NIGHT_GAMMA));
+        assert_eq!(hdr.day_gamma, Some(80.0));
 
-        // But it should have geo transition mode
-        assert!(main_content.contains("transition_mode = \"geo\""));
+        config.hdr_night_temp = Some(MAXIMUM_TEMP + 1);
+        assert!(validate_config(&config).is_err());
+        config.hdr_night_temp = None;
+        config.hdr_outputs = Some(vec![" ".to_string()]);
+        assert!(validate_config(&config).is_err());
     }
 
     #[test]
-    #[serial]
-    fn test_missing_coordinates_auto_save() {
+    fn test_lint_config_reports_every_problem() {
         let temp_dir = tempdir().unwrap();
-        let config_dir = temp_dir.path().join("sunsetr");
-        fs::create_dir_all(&config_dir).unwrap();
-
-        let config_path = config_dir.join("sunsetr.toml");
+        let config_path = temp_dir.path().join("sunsetr.toml");
 
-        // Create config with geo mode but NO coordinates
-        let config_content = r#"
-start_hyprsunset = false
+        fs::write(
+            &config_path,
+            r#"
 sunset = "19:00:00"
 sunrise = "06:00:00"
 transition_mode = "geo"
-"#;
-        fs::write(&config_path, config_content).unwrap();
-
-        // Save and restore XDG_CONFIG_HOME
-        let original = std::env::var("XDG_CONFIG_HOME").ok();
-        unsafe {
-            std::env::set_var("XDG_CONFIG_HOME", temp_dir.path());
-        }
-
-        // Load config - should trigger coordinate detection and auto-save
-        let config = Config::load();
-
-        // Restore original
-        unsafe {
-            match original {
-                Some(val) => std::env::set_var("XDG_CONFIG_HOME", val),
-                None => std::env::remove_var("XDG_CONFIG_HOME"),
-            }
-        }
-
-        // If detection succeeded, config should have coordinates
-        if let Ok(loaded_config) = config {
-            assert!(loaded_config.latitude.is_some());
-            assert!(loaded_config.longitude.is_some());
+nite_temp = 3000
+day_tmp = 6500
+night_gamma = 150.0
+temp_quantum = 1000
+"#,
+        )
+        .unwrap();
+        let report = lint_config(&config_path);
+        assert_eq!(report.errors.len(), 4, "{:?}", report.errors);
+        assert!(report.errors.iter().any(|e| e.contains("'nite_temp'")));
+        assert!(report.errors.iter().any(|e| e.contains("'day_tmp'")));
+        assert!(report.errors.iter().any(|e| e.contains("Night gamma")));
+        assert!(report.errors.iter().any(|e| e.contains("quantum")));
+        assert_eq!(report.warnings.len(), 1);
+
+        // Overlapping transitions come from the schedule checks
+        fs::write(
+            &config_path,
+            r#"
+sunset = "19:00:00"
+sunrise = "20:30:00"
+transition_mode = "center"
+transition_duration = 120
+"#,
+        )
+        .unwrap();
+        let report = lint_config(&config_path);
+        assert_eq!(report.errors.len(), 1, "{:?}", report.errors);
+        assert!(report.warnings.is_empty());
+
+        fs::write(
+            &config_path,
+            r#"
+sunset = "19:00:00"
+sunrise = "06:00:00"
+"#,
+        )
+        .unwrap();
+        let report = lint_config(&config_path);
+        assert!(report.errors.is_empty() && report.warnings.is_empty());
+
+        // Mireds and presets are checked after conversion, like at startup
+        fs::write(
+            &config_path,
+            r#"
+sunset = "19:00:00"
+sunrise = "06:00:00"
+temp_unit = "mired"
+night_temp = 303
+day_temp = "cool"
+"#,
+        )
+        .unwrap();
+        let report = lint_config(&config_path);
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
 
-            // Check that coordinates were saved to the file
-            let updated_content = fs::read_to_string(&config_path).unwrap();
-            assert!(updated_content.contains("latitude = "));
-            assert!(updated_content.contains("longitude = "));
-        }
-        // If detection failed, the load would have exited, so we can't test that path
+        fs::write(
+            &config_path,
+            r#"
+sunset = "19:00:00"
+sunrise = "06:00:00"
+night_temp = "moonlight"
+"#,
+        )
+        .unwrap();
+        let report = lint_config(&config_path);
+        assert_eq!(report.errors.len(), 1, "{:?}", report.errors);
+        assert!(report.errors[0].contains("preset"));
     }
 }
@@ -9,19 +9,51 @@ use crate::config::Backend;
 // These values are used when config options are not specified by the user
 
 pub const DEFAULT_START_HYPRSUNSET: bool = true;
+pub const DEFAULT_HYPRSUNSET_CONNECT_TIMEOUT: u64 = 10; // seconds to wait for the hyprsunset socket per retry
+pub const DEFAULT_HYPRSUNSET_CONNECT_RETRIES: u32 = 1; // waits before giving up on hyprsunset
 pub const DEFAULT_BACKEND: Backend = Backend::Auto; // Auto-detect backend
+pub const DEFAULT_AUTO_PREFER: &[Backend] = &[Backend::Hyprland, Backend::Wayland]; // Hyprland first when on Hyprland
 pub const DEFAULT_STARTUP_TRANSITION: bool = true;
 pub const DEFAULT_STARTUP_TRANSITION_DURATION: u64 = 1; // second(s)
+pub const DEFAULT_STARTUP_DELAY_MS: u64 = 0; // milliseconds - no delay before the first apply
 pub const DEFAULT_SUNSET: &str = "19:00:00";
 pub const DEFAULT_SUNRISE: &str = "06:00:00";
 pub const DEFAULT_NIGHT_TEMP: u32 = 3300; // Kelvin - warm, comfortable for night viewing
 pub const DEFAULT_DAY_TEMP: u32 = 6500; // Kelvin - close to natural sunlight
+pub const NEUTRAL_TEMP: u32 = 6500; // Kelvin - no color shift, used when color_shift = false
+pub const DEFAULT_CHANNEL_GAMMA: f32 = 1.0; // Per-channel multiplier, 1.0 leaves the channel unchanged
 pub const DEFAULT_NIGHT_GAMMA: f32 = 90.0; // Slightly dimmed for night (percentage)
 pub const DEFAULT_DAY_GAMMA: f32 = 100.0; // Full brightness for day (percentage)
+pub const DEFAULT_DAY_WHITEPOINT: [f64; 2] = [0.31271, 0.32902]; // CIE 1931 xy of D65 (no correction)
+pub const DEFAULT_VERIFY_GAMMA: bool = true; // Confirm each gamma apply with a compositor roundtrip
+pub const DEFAULT_PRECOMPUTE_RAMPS: bool = false; // Recompute each transition ramp from its temperature
+pub const DEFAULT_GAMMA_TABLE_FORMAT: &str = "planar-le"; // Whole R, G, B ramps of little-endian u16, per the protocol
+pub const DEFAULT_RESET_ON_EXIT: bool = true; // Reset to neutral on exit rather than just releasing gamma
+pub const DEFAULT_REQUIRE_OUTPUTS: bool = false; // Start without outputs and wait for one to appear
+pub const DEFAULT_REAPPLY_ON_UNLOCK: bool = false; // Watch logind for unlock/resume (logind feature)
+pub const DEFAULT_EVENT_FIFO: bool = false; // Write state changes to a named pipe in the runtime directory
+pub const DEFAULT_WAKE_FADE_MS: u64 = 1000; // milliseconds - fade a monitor's gamma in after DPMS wake
+pub const DEFAULT_IDLE_GAMMA: f32 = 70.0; // Percentage of the current gamma kept while idle (idle feature)
+pub const DEFAULT_LOG_TO_CONSOLE: bool = true; // Keep printing to stdout/journald when a log file is set
+pub const DEFAULT_LOG_VERBOSITY: &str = "normal"; // Log transition progress on every update
+pub const DEFAULT_LOG_FILE_MAX_BYTES: u64 = 1024 * 1024; // bytes - rotate the log file at 1 MiB
+pub const DEFAULT_LOG_FILE_KEEP: u32 = 3; // Rotated log files kept besides the current one
+pub const DEFAULT_TEMP_UNIT: &str = "kelvin"; // Unit of the *_temp settings ("kelvin" or "mired")
+pub const DEFAULT_COLOR_SHIFT: bool = true; // Vary temperature, not just gamma, between day and night
+pub const DEFAULT_TEMP_QUANTUM: u32 = 1; // Kelvin step transition temperatures snap to (1 = no snapping)
 pub const DEFAULT_TRANSITION_DURATION: u64 = 45; // minutes - gradual change
+pub const DEFAULT_EASE_OUT_TAIL_SECONDS: u64 = 0; // seconds - no extra settling at the end of transitions
+pub const DEFAULT_GAMMA_PROGRESS_OFFSET: f32 = 0.0; // Gamma ramps together with the temperature
 pub const DEFAULT_UPDATE_INTERVAL: u64 = 60; // seconds - how often to update during transitions
 pub const DEFAULT_TRANSITION_MODE: &str = "geo"; // Geographic location-based transitions
+pub const DEFAULT_GEO_ANCHOR: &str = "twilight"; // Geo transitions follow solar elevation angles
+pub const DEFAULT_MANUAL_GEO_EVENT: &str = "sunset"; // In "manual_geo" mode, sunset follows the sun and sunrise the clock
+pub const DEFAULT_GEO_NOON_OFFSET: f64 = 6.0; // hours - solar noon to transition center ("solar_noon" anchor)
+pub const DEFAULT_GEO_NIGHT_BOUNDARY: &str = "civil"; // Night begins at -2°, within civil twilight
+pub const DEFAULT_SOLAR_PRECISION: &str = "fast"; // Linear estimates of the elevation-angle boundaries
+pub const DEFAULT_GEO_ROUND_TO_MINUTES: u32 = 0; // minutes - 0 keeps geo boundaries unrounded
 pub const FALLBACK_DEFAULT_TRANSITION_MODE: &str = "finish_by"; // Fallback when default mode fails
+pub const DEFAULT_PROFILE_NAME: &str = "default"; // Reserved name for the base (no overrides) profile
 
 // ═══ hyprsunset Compatibility ═══
 // Version requirements and compatibility information
@@ -31,6 +63,19 @@ pub const COMPATIBLE_HYPRSUNSET_VERSIONS: &[&str] = &[
     "v0.2.0", "v0.3.0",
     // Add more versions as they become available and tested
 ];
+pub const HYPRSUNSET_SOCKET_ENV: &str = "HYPRSUNSET_SOCKET"; // Overrides socket auto-detection
+pub const CONFIG_ENV_PREFIX: &str = "SUNSETR_"; // SUNSETR_<KEY> overrides a config key
+pub const SYSTEM_CONFIG_PATH: &str = "/etc/sunsetr/sunsetr.toml"; // Admin defaults, layered under the user's config
+pub const SYSTEM_CONFIG_ENV: &str = "SUNSETR_SYSTEM_CONFIG"; // Replaces SYSTEM_CONFIG_PATH, empty disables it
+pub const SELECTED_CONFIG_FILE: &str = ".selected-config"; // Config picked with --select-config, next to sunsetr.toml
+pub const EVENT_FIFO_NAME: &str = "sunsetr_events"; // Named pipe in the runtime directory (event_fifo)
+#[cfg(feature = "testing-support")]
+pub const BACKEND_OVERRIDE_ENV: &str = "SUNSETR_BACKEND"; // "mock" selects the mock backend (tests only)
+#[cfg(feature = "testing-support")]
+pub const MOCK_LOG_ENV: &str = "SUNSETR_MOCK_LOG"; // File the mock backend appends each apply to (tests only)
+#[cfg(feature = "testing-support")]
+pub const FAKE_TIME_ENV: &str = "SUNSETR_FAKE_TIME"; // "HH:MM:SS" pins the schedule's clock (tests only)
+pub const SAFE_MODE_ENV: &str = "SUNSETR_SAFE"; // "1" enables safe mode, same as --safe
 
 // ═══ Validation Limits ═══
 // These limits ensure user inputs are within reasonable and safe ranges
@@ -40,19 +85,87 @@ pub const MINIMUM_STARTUP_TRANSITION_DURATION: u64 = 1; // seconds (minimum for
 pub const MAXIMUM_STARTUP_TRANSITION_DURATION: u64 = 60; // seconds (prevents excessively long startup)
 pub const MINIMUM_STARTUP_UPDATE_INTERVAL_MS: u64 = 5; // milliseconds (for short transitions)
 pub const MAXIMUM_STARTUP_UPDATE_INTERVAL_MS: u64 = 250; // milliseconds (for long transitions)
+pub const MAXIMUM_STARTUP_DELAY_MS: u64 = 10000; // milliseconds (10 seconds max wait before first apply)
+pub const MAXIMUM_WAKE_FADE_MS: u64 = 10000; // milliseconds (longer fades leave a woken monitor uncorrected)
+
+// Idle dimming limits
+pub const MINIMUM_IDLE_TIMEOUT: u64 = 10; // seconds (shorter dims while reading)
+pub const MAXIMUM_IDLE_TIMEOUT: u64 = 86400; // seconds (one day)
+
+// hyprsunset connection limits
+pub const MAXIMUM_HYPRSUNSET_CONNECT_TIMEOUT: u64 = 60; // seconds (longer looks like a hang)
+pub const MAXIMUM_HYPRSUNSET_CONNECT_RETRIES: u32 = 10;
+
+// Log file limits
+pub const MINIMUM_LOG_FILE_MAX_BYTES: u64 = 4096; // bytes (smaller files would rotate every few lines)
+pub const MAXIMUM_LOG_FILE_KEEP: u32 = 20; // rotated files
 
 // Temperature limits (Kelvin scale)
 pub const MINIMUM_TEMP: u32 = 1000; // Very warm candlelight-like
 pub const MAXIMUM_TEMP: u32 = 20000; // Very cool blue light
+pub const MINIMUM_TEMP_QUANTUM: u32 = 1; // Kelvin (no snapping)
+pub const MAXIMUM_TEMP_QUANTUM: u32 = 500; // Kelvin (coarser steps become visible jumps)
+
+/// Named presets accepted in place of a Kelvin value for `night_temp` and `day_temp`.
+///
+/// | Preset    | Kelvin | Looks like                       |
+/// |-----------|--------|----------------------------------|
+/// | `candle`  | 1900   | Candlelight, very warm           |
+/// | `warm`    | 3000   | Warm white light bulb            |
+/// | `neutral` | 4500   | Neutral white                    |
+/// | `cool`    | 6500   | Daylight (D65), no correction    |
+pub const TEMPERATURE_PRESETS: &[(&str, u32)] = &[
+    ("candle", 1900),
+    ("warm", 3000),
+    ("neutral", 4500),
+    ("cool", 6500),
+];
+pub const MAXIMUM_HEX_TEMPERATURE_DISTANCE: f32 = 0.1; // How far a hex color may be from the nearest white point
 
 // Gamma limits (percentage of full brightness)
 pub const MINIMUM_GAMMA: f32 = 0.0; // Complete darkness (not recommended)
 pub const MAXIMUM_GAMMA: f32 = 100.0; // Full brightness
 
+// Per-channel gamma limits (multipliers applied after the temperature curve)
+pub const MINIMUM_CHANNEL_GAMMA: f32 = 0.0; // Channel switched off
+pub const MAXIMUM_CHANNEL_GAMMA: f32 = 2.0; // Doubled, clipped at full scale
+
 // Transition duration limits
 pub const MINIMUM_TRANSITION_DURATION: u64 = 5; // minutes (prevents too-rapid changes)
 pub const MAXIMUM_TRANSITION_DURATION: u64 = 120; // minutes (2 hours max)
 
+// Solar noon anchor limits
+pub const MINIMUM_GEO_NOON_OFFSET: f64 = 3.0; // hours (keeps at least a few hours of day)
+pub const MAXIMUM_GEO_NOON_OFFSET: f64 = 10.0; // hours (keeps at least a few hours of night)
+
+// Geo boundary rounding limits
+pub const MAXIMUM_GEO_ROUND_TO_MINUTES: u32 = 30; // minutes (coarser steps distort short transitions)
+
+// Ease-out tail limits
+pub const MAXIMUM_EASE_OUT_TAIL_SECONDS: u64 = 3600; // seconds (also capped at half of each transition)
+
+// Gamma ramp offset limits (fraction of the transition, either direction)
+pub const MAXIMUM_GAMMA_PROGRESS_OFFSET: f32 = 0.5; // The gamma ramp keeps at least half the transition
+
+// Temperature nudge limits (--nudge)
+pub const MAXIMUM_TEMPERATURE_NUDGE: i32 = 3000; // Kelvin, either direction (total of all nudges)
+
+// Timed hold limits (--hold)
+pub const MAXIMUM_HOLD_MINUTES: u32 = 24 * 60; // A hold never outlasts a full day
+
+// Gamma ramp dump limits (--dump-gamma)
+pub const DEFAULT_DUMP_GAMMA_SIZE: usize = 256; // Entries per channel, the most common ramp size
+pub const MINIMUM_DUMP_GAMMA_SIZE: usize = 2; // A ramp needs both ends
+pub const MAXIMUM_DUMP_GAMMA_SIZE: usize = 65536; // One entry per 16-bit input value
+
+// --bench settings
+pub const DEFAULT_BENCH_ITERATIONS: usize = 50; // Applies timed by --bench
+pub const MINIMUM_BENCH_ITERATIONS: usize = 1;
+pub const MAXIMUM_BENCH_ITERATIONS: usize = 1000; // Keeps a run well under a minute on slow setups
+pub const BENCH_SLOW_APPLY_MS: u64 = 50; // Median latency that warrants a larger update_interval
+pub const CALIBRATE_TEMPERATURES: [u32; 4] = [4500, 4000, 3500, 3000]; // Night temperatures shown by --calibrate
+pub const CALIBRATE_PREVIEW_SECS: u64 = 4; // How long --calibrate shows each temperature
+
 // Update interval limits
 pub const MINIMUM_UPDATE_INTERVAL: u64 = 10; // seconds (prevents excessive CPU usage)
 pub const MAXIMUM_UPDATE_INTERVAL: u64 = 300; // seconds (5 minutes max for responsive transitions)
@@ -68,6 +181,15 @@ pub const CLOCK_DRIFT_THRESHOLD_SECS: u64 = 5; // 5 seconds - ignore small backw
 
 // Application operation timing
 pub const COMMAND_DELAY_MS: u64 = 100; // Delay between hyprsunset commands to prevent conflicts
+pub const STARTUP_APPLY_MAX_RETRIES: u32 = 3; // Extra attempts if the first apply is rejected (compositor not ready)
+pub const STARTUP_APPLY_RETRY_DELAY_MS: u64 = 250; // Delay between first-apply retries
+pub const WAKE_FADE_STEP_MS: u64 = 25; // Delay between gamma updates while fading in a woken output
+pub const WAYLAND_INIT_TIMEOUT_MS: u64 = 5000; // How long the compositor gets to answer each roundtrip during startup
+pub const GAMMA_SIZE_TIMEOUT_MS: u64 = 2000; // How long slow compositors get to report each output's gamma size
+pub const GAMMA_SIZE_POLL_MS: u64 = 50; // Delay between checks for late gamma size events
+pub const INEFFECTIVE_GAMMA_THRESHOLD: u32 = 3; // Consecutive applies reaching no output before warning
+pub const INEFFECTIVE_GAMMA_WARNING_INTERVAL_SECS: u64 = 600; // Minimum time between repeats of that warning
+pub const APPEARANCE_RECHECK_SECS: u64 = 3600; // Main loop wake-up in appearance mode (changes arrive as signals)
 
 // ═══ Transition Curve Constants ═══
 // Bezier curve control points for smooth sunrise/sunset transitions
@@ -98,11 +220,13 @@ pub const BEZIER_P2Y: f32 = 1.0; // Y coordinate of second control point (typica
 
 pub const SOCKET_TIMEOUT_MS: u64 = 1000; // 1 second timeout for socket operations
 pub const SOCKET_BUFFER_SIZE: usize = 1024; // Buffer size for socket communication
+pub const HYPRSUNSET_CONNECT_POLL_MS: u64 = 100; // How often to check for the socket while waiting
 
 // ═══ User Interface Constants ═══
 // Visual display settings
 
 pub const PROGRESS_BAR_WIDTH: usize = 30; // Characters width for progress bar display
+pub const MONITOR_REFRESH_INTERVAL_MS: u64 = 1000; // How often --monitor redraws the dashboard
 
 // ═══ Retry and Recovery Constants ═══
 // Error handling and resilience settings
@@ -132,3 +256,89 @@ pub mod test_constants {
     pub const TEST_STANDARD_DAY_GAMMA: f32 = DEFAULT_DAY_GAMMA; // 100.0%
     pub const TEST_STANDARD_MODE: &str = DEFAULT_TRANSITION_MODE; // "geo"
 }
+
+// Defaults must pass their own validation, or the shipped default config would fail to load
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::compare_versions;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn test_default_colors_within_limits() {
+        let temps = MINIMUM_TEMP..=MAXIMUM_TEMP;
+        assert!(temps.contains(&DEFAULT_NIGHT_TEMP));
+        assert!(temps.contains(&DEFAULT_DAY_TEMP));
+        for (name, kelvin) in TEMPERATURE_PRESETS {
+            assert!(temps.contains(kelvin), "preset {} is out of range", name);
+        }
+
+        let gammas = MINIMUM_GAMMA..=MAXIMUM_GAMMA;
+        assert!(gammas.contains(&DEFAULT_NIGHT_GAMMA));
+        assert!(gammas.contains(&DEFAULT_DAY_GAMMA));
+        assert!((MINIMUM_CHANNEL_GAMMA..=MAXIMUM_CHANNEL_GAMMA).contains(&DEFAULT_CHANNEL_GAMMA));
+        assert!((MINIMUM_TEMP_QUANTUM..=MAXIMUM_TEMP_QUANTUM).contains(&DEFAULT_TEMP_QUANTUM));
+        assert!(
+            (-MAXIMUM_GAMMA_PROGRESS_OFFSET..=MAXIMUM_GAMMA_PROGRESS_OFFSET)
+                .contains(&DEFAULT_GAMMA_PROGRESS_OFFSET)
+        );
+    }
+
+    #[test]
+    fn test_default_durations_within_limits() {
+        assert!(
+            (MINIMUM_TRANSITION_DURATION..=MAXIMUM_TRANSITION_DURATION)
+                .contains(&DEFAULT_TRANSITION_DURATION)
+        );
+        assert!(
+            (MINIMUM_STARTUP_TRANSITION_DURATION..=MAXIMUM_STARTUP_TRANSITION_DURATION)
+                .contains(&DEFAULT_STARTUP_TRANSITION_DURATION)
+        );
+        assert!(
+            (MINIMUM_UPDATE_INTERVAL..=MAXIMUM_UPDATE_INTERVAL).contains(&DEFAULT_UPDATE_INTERVAL)
+        );
+        assert!((0..=MAXIMUM_STARTUP_DELAY_MS).contains(&DEFAULT_STARTUP_DELAY_MS));
+        assert!((0..=MAXIMUM_WAKE_FADE_MS).contains(&DEFAULT_WAKE_FADE_MS));
+        assert!((MINIMUM_GAMMA..=MAXIMUM_GAMMA).contains(&DEFAULT_IDLE_GAMMA));
+        assert!((0..=MAXIMUM_EASE_OUT_TAIL_SECONDS).contains(&DEFAULT_EASE_OUT_TAIL_SECONDS));
+        assert!((0..=MAXIMUM_GEO_ROUND_TO_MINUTES).contains(&DEFAULT_GEO_ROUND_TO_MINUTES));
+        assert!(
+            (MINIMUM_GEO_NOON_OFFSET..=MAXIMUM_GEO_NOON_OFFSET).contains(&DEFAULT_GEO_NOON_OFFSET)
+        );
+        assert!(
+            (1..=MAXIMUM_HYPRSUNSET_CONNECT_TIMEOUT).contains(&DEFAULT_HYPRSUNSET_CONNECT_TIMEOUT)
+        );
+        assert!(
+            (0..=MAXIMUM_HYPRSUNSET_CONNECT_RETRIES).contains(&DEFAULT_HYPRSUNSET_CONNECT_RETRIES)
+        );
+        assert!((MINIMUM_LOG_FILE_MAX_BYTES..).contains(&DEFAULT_LOG_FILE_MAX_BYTES));
+        assert!((0..=MAXIMUM_LOG_FILE_KEEP).contains(&DEFAULT_LOG_FILE_KEEP));
+        assert!(
+            (MINIMUM_DUMP_GAMMA_SIZE..=MAXIMUM_DUMP_GAMMA_SIZE).contains(&DEFAULT_DUMP_GAMMA_SIZE)
+        );
+        assert!(
+            (MINIMUM_BENCH_ITERATIONS..=MAXIMUM_BENCH_ITERATIONS)
+                .contains(&DEFAULT_BENCH_ITERATIONS)
+        );
+    }
+
+    #[test]
+    fn test_hyprsunset_versions_consistent() {
+        // The minimum is itself a tested version, and nothing older is listed as compatible
+        assert!(COMPATIBLE_HYPRSUNSET_VERSIONS.contains(&REQUIRED_HYPRSUNSET_VERSION));
+        for version in COMPATIBLE_HYPRSUNSET_VERSIONS {
+            assert_ne!(
+                compare_versions(version, REQUIRED_HYPRSUNSET_VERSION),
+                Ordering::Less,
+                "{} is older than the required {}",
+                version,
+                REQUIRED_HYPRSUNSET_VERSION
+            );
+        }
+
+        // Listed oldest first, without duplicates
+        for pair in COMPATIBLE_HYPRSUNSET_VERSIONS.windows(2) {
+            assert_eq!(compare_versions(pair[0], pair[1]), Ordering::Less);
+        }
+    }
+}
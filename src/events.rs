@@ -0,0 +1,216 @@
+//! Real-time state events on a named pipe.
+//!
+//! With `event_fifo = true`, the running instance creates the fifo `sunsetr_events`
+//! in the runtime directory (see [`crate::utils::runtime_dir`]) and writes one JSON
+//! line to it on every state change and transition step:
+//!
+//! ```text
+//! {"state":"sunset","temperature":5500,"gamma":97.5,"progress":0.25}
+//! {"state":"night","temperature":3300,"gamma":90,"progress":null}
+//! ```
+//!
+//! A consumer blocks on reading the fifo and reacts the moment a line arrives,
+//! instead of polling the status files. Writes never block: without a reader, or
+//! when the reader falls behind and the pipe is full, the line is dropped. Each
+//! line is far below `PIPE_BUF`, so readers never see half of one.
+//!
+//! Only one reader gets each line, as with any pipe. The fifo is removed on shutdown.
+
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{ErrorKind, Write};
+use std::os::unix::fs::{FileTypeExt, OpenOptionsExt};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::json::JsonValue;
+use crate::time_state::TransitionState;
+
+/// The fifo used by the running instance, once [`open`] succeeded.
+static EVENT_FIFO: Mutex<Option<EventFifo>> = Mutex::new(None);
+
+/// Writer side of the events fifo.
+#[derive(Debug)]
+pub struct EventFifo {
+    path: PathBuf,
+    /// Open while a reader is attached
+    writer: Option<File>,
+    /// The last line a reader received, to skip wake-ups that changed nothing
+    last_line: Option<String>,
+}
+
+impl EventFifo {
+    /// Create the fifo at `path`, or reuse one left behind by an earlier run.
+    ///
+    /// Anything else at `path` is left alone and reported as an error.
+    pub fn create(path: &Path) -> Result<Self> {
+        match std::fs::symlink_metadata(path) {
+            Ok(metadata) if metadata.file_type().is_fifo() => {}
+            Ok(_) => anyhow::bail!("{} exists and is not a fifo", path.display()),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                nix::unistd::mkfifo(
+                    path,
+                    nix::sys::stat::Mode::S_IRUSR | nix::sys::stat::Mode::S_IWUSR,
+                )
+                .with_context(|| format!("Failed to create {}", path.display()))?;
+            }
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to inspect {}", path.display()));
+            }
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            writer: None,
+            last_line: None,
+        })
+    }
+
+    /// Write `line` (without its newline) for the attached reader, if any.
+    ///
+    /// Returns whether a reader received it. Never blocks.
+    pub fn send(&mut self, line: &str) -> bool {
+        if self.last_line.as_deref() == Some(line) {
+            return false;
+        }
+
+        if self.writer.is_none() {
+            // Opening the write end without a reader fails with ENXIO instead of waiting
+            self.writer = std::fs::OpenOptions::new()
+                .write(true)
+                .custom_flags(nix::fcntl::OFlag::O_NONBLOCK.bits())
+                .open(&self.path)
+                .ok();
+        }
+        let Some(writer) = self.writer.as_mut() else {
+            return false;
+        };
+
+        match writer.write(format!("{}\n", line).as_bytes()) {
+            Ok(_) => {
+                self.last_line = Some(line.to_string());
+                true
+            }
+            // The reader isn't keeping up; it gets the next line instead
+            Err(e) if e.kind() == ErrorKind::WouldBlock => false,
+            Err(_) => {
+                // The reader went away. The next one starts fresh.
+                self.writer = None;
+                self.last_line = None;
+                false
+            }
+        }
+    }
+}
+
+/// The event line for `state` with the applied `values`.
+pub fn event_line(state: TransitionState, values: (u32, f32)) -> String {
+    let progress = match state {
+        TransitionState::Stable(_) => None,
+        TransitionState::Transitioning { progress, .. } => Some(progress),
+    };
+    JsonValue::Object(vec![
+        (
+            "state".to_string(),
+            JsonValue::from(crate::status::state_name(state)),
+        ),
+        ("temperature".to_string(), JsonValue::from(values.0)),
+        ("gamma".to_string(), JsonValue::from(values.1)),
+        ("progress".to_string(), JsonValue::from(progress)),
+    ])
+    .render()
+}
+
+/// Path of the events fifo.
+pub fn fifo_path() -> Result<PathBuf> {
+    Ok(crate::utils::runtime_dir()?.join(crate::constants::EVENT_FIFO_NAME))
+}
+
+/// Create the events fifo for this process.
+pub fn open() -> Result<PathBuf> {
+    let path = fifo_path()?;
+    let fifo = EventFifo::create(&path)?;
+    *EVENT_FIFO
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(fifo);
+    Ok(path)
+}
+
+/// Send the current state to the fifo's reader. Does nothing unless [`open`] succeeded.
+pub fn publish(state: TransitionState, values: (u32, f32)) {
+    let mut fifo = EVENT_FIFO
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if let Some(fifo) = fifo.as_mut() {
+        fifo.send(&event_line(state, values));
+    }
+}
+
+/// Remove the events fifo, if this process created one.
+pub fn close() {
+    let fifo = EVENT_FIFO
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .take();
+    if let Some(fifo) = fifo {
+        let _ = std::fs::remove_file(&fifo.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_state::TimeState;
+    use std::io::Read;
+
+    #[test]
+    fn test_event_line() {
+        let sunset = TransitionState::Transitioning {
+            from: TimeState::Day,
+            to: TimeState::Night,
+            progress: 0.25,
+        };
+        assert_eq!(
+            event_line(sunset, (5500, 97.5)),
+            r#"{"state":"sunset","temperature":5500,"gamma":97.5,"progress":0.25}"#
+        );
+        assert_eq!(
+            event_line(TransitionState::Stable(TimeState::Night), (3300, 90.0)),
+            r#"{"state":"night","temperature":3300,"gamma":90,"progress":null}"#
+        );
+    }
+
+    #[test]
+    fn test_fifo_never_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sunsetr_events");
+        let mut fifo = EventFifo::create(&path).unwrap();
+
+        // Nobody is reading yet
+        assert!(!fifo.send("first"));
+
+        let mut reader = std::fs::OpenOptions::new()
+            .read(true)
+            .custom_flags(nix::fcntl::OFlag::O_NONBLOCK.bits())
+            .open(&path)
+            .unwrap();
+        assert!(fifo.send("second"));
+        assert!(!fifo.send("second"));
+        let mut received = String::new();
+        reader.read_to_string(&mut received).ok();
+        assert_eq!(received, "second\n");
+
+        // A reader that stops reading fills the pipe; further lines are dropped
+        let delivered = (0..10_000)
+            .filter(|i| fifo.send(&format!("line {:0>64}", i)))
+            .count();
+        assert!(delivered > 0 && delivered < 10_000, "{}", delivered);
+
+        // Reusing the fifo works, while any other file is refused
+        drop(reader);
+        assert!(EventFifo::create(&path).is_ok());
+        let plain = dir.path().join("plain");
+        std::fs::write(&plain, "").unwrap();
+        assert!(EventFifo::create(&plain).is_err());
+    }
+}
@@ -7,6 +7,9 @@
 //! ## Features
 //!
 //! - **Fuzzy search**: Type any part of a city or country name to filter results
+//! - **Accent-insensitive matching**: "München", "Muenchen" and "Munich" all find the
+//!   same city; diacritics and German-style transliterations are folded before matching
+//! - **Localized names**: Cities with a well-known local spelling are shown with it
 //! - **Real-time filtering**: Results update as you type
 //! - **Keyboard navigation**: Arrow keys to navigate, Enter to select, Esc to cancel
 //! - **Visual feedback**: Selected city is highlighted with an arrow indicator
@@ -48,6 +51,142 @@ pub struct CityInfo {
     pub longitude: f64,
 }
 
+/// Localized names and common alternate spellings for cities whose database entry
+/// uses an English or ASCII-transliterated name.
+///
+/// Entries are `(country, database name, localized display name, alternate names)`.
+/// Accent folding already matches simple cases such as "Malaga" for "Málaga", so
+/// alternates are only needed for genuinely different names (exonyms).
+const CITY_NAME_ALIASES: &[(&str, &str, Option<&str>, &[&str])] = &[
+    ("Austria", "Wien", None, &["Vienna"]),
+    ("Belgium", "Antwerp", None, &["Antwerpen", "Anvers"]),
+    ("Belgium", "Brussels", None, &["Bruxelles", "Brussel"]),
+    ("Brazil", "Belem", Some("Belém"), &[]),
+    ("Brazil", "Brasilia", Some("Brasília"), &[]),
+    ("Brazil", "Sao Paulo", Some("São Paulo"), &[]),
+    ("Cameroon", "Yaounde", Some("Yaoundé"), &[]),
+    ("Canada", "Montreal", Some("Montréal"), &[]),
+    ("Canada", "Quebec", Some("Québec"), &[]),
+    ("Colombia", "Bogota", Some("Bogotá"), &[]),
+    ("Colombia", "Medellin", Some("Medellín"), &[]),
+    ("Czech Republic", "Praha", None, &["Prague"]),
+    ("Denmark", "Copenhagen", None, &["København"]),
+    ("Germany", "Dusseldorf", Some("Düsseldorf"), &[]),
+    ("Germany", "Hannover", None, &["Hanover"]),
+    ("Germany", "Koeln", Some("Köln"), &["Cologne"]),
+    ("Germany", "Monchengladbach", Some("Mönchengladbach"), &[]),
+    ("Germany", "Muenchen", Some("München"), &["Munich"]),
+    ("Germany", "Nuernberg", Some("Nürnberg"), &["Nuremberg"]),
+    ("Greece", "Athens", None, &["Athina"]),
+    ("Iceland", "Reykjavik", Some("Reykjavík"), &[]),
+    ("India", "Mumbai", None, &["Bombay"]),
+    ("Italy", "Florence", None, &["Firenze"]),
+    ("Italy", "Milano", None, &["Milan"]),
+    ("Italy", "Napoli", None, &["Naples"]),
+    ("Italy", "Rome", None, &["Roma"]),
+    ("Italy", "Torino", None, &["Turin"]),
+    ("Italy", "Venice", None, &["Venezia"]),
+    ("Mexico", "Cancun", Some("Cancún"), &[]),
+    ("Mexico", "Mexico City", None, &["Ciudad de México", "CDMX"]),
+    ("Paraguay", "Asuncion", Some("Asunción"), &[]),
+    ("Poland", "Gdansk", Some("Gdańsk"), &[]),
+    ("Poland", "Krakow", Some("Kraków"), &["Cracow"]),
+    ("Poland", "Lodz", Some("Łódź"), &[]),
+    ("Poland", "Poznan", Some("Poznań"), &[]),
+    ("Poland", "Torun", Some("Toruń"), &[]),
+    ("Poland", "Warsaw", None, &["Warszawa"]),
+    ("Poland", "Wroclaw", Some("Wrocław"), &[]),
+    ("Portugal", "Lisbon", None, &["Lisboa"]),
+    ("Russia", "Moscow", None, &["Moskva"]),
+    ("Spain", "Cadiz", Some("Cádiz"), &[]),
+    ("Spain", "Malaga", Some("Málaga"), &[]),
+    ("Spain", "Sevilla", None, &["Seville"]),
+    ("Sweden", "Goteborg", Some("Göteborg"), &["Gothenburg"]),
+    ("Sweden", "Jonkoping", Some("Jönköping"), &[]),
+    ("Sweden", "Linkoping", Some("Linköping"), &[]),
+    ("Sweden", "Orebro", Some("Örebro"), &[]),
+    ("Switzerland", "Bern", None, &["Berne"]),
+    ("Switzerland", "Geneve", Some("Genève"), &["Geneva"]),
+    ("Switzerland", "Zurich", Some("Zürich"), &[]),
+    ("Togo", "Lome", Some("Lomé"), &[]),
+    ("Ukraine", "Kiev", None, &["Kyiv"]),
+];
+
+impl CityInfo {
+    /// Alias table entry for this city, if any.
+    fn aliases(
+        &self,
+    ) -> Option<&'static (
+        &'static str,
+        &'static str,
+        Option<&'static str>,
+        &'static [&'static str],
+    )> {
+        CITY_NAME_ALIASES
+            .iter()
+            .find(|(country, name, _, _)| *country == self.country && *name == self.name)
+    }
+
+    /// Name to show the user: the localized spelling when known, otherwise the database name.
+    pub fn display_name(&self) -> &str {
+        self.aliases()
+            .and_then(|(_, _, local, _)| *local)
+            .unwrap_or(&self.name)
+    }
+
+    /// Folded text the search query is matched against.
+    ///
+    /// Covers the database name, localized name, alternate names and country,
+    /// separated so a query can't match across two of them.
+    fn search_key(&self) -> String {
+        let mut parts = vec![fold_for_search(&self.name)];
+        if let Some((_, _, local, alternates)) = self.aliases() {
+            parts.extend(local.iter().map(|n| fold_for_search(n)));
+            parts.extend(alternates.iter().map(|n| fold_for_search(n)));
+        }
+        parts.push(fold_for_search(&self.country));
+        parts.join("\n")
+    }
+}
+
+/// Fold text for accent- and transliteration-insensitive matching.
+///
+/// Lowercases, strips diacritics ("é" → "e", "ł" → "l"), expands ligatures
+/// ("ß" → "ss") and collapses the German-style transliterations "ae", "oe" and
+/// "ue" to their base vowel. The same folding is applied to both the query and the
+/// city names, so "München", "Muenchen" and "Munchen" all produce "munchen".
+pub fn fold_for_search(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars().flat_map(char::to_lowercase) {
+        match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => folded.push('a'),
+            'æ' => folded.push_str("ae"),
+            'ç' | 'ć' | 'č' => folded.push('c'),
+            'ď' | 'đ' | 'ð' => folded.push('d'),
+            'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => folded.push('e'),
+            'ğ' => folded.push('g'),
+            'ì' | 'í' | 'î' | 'ï' | 'ī' | 'į' | 'ı' => folded.push('i'),
+            'ł' | 'ľ' => folded.push('l'),
+            'ñ' | 'ń' | 'ň' => folded.push('n'),
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => folded.push('o'),
+            'œ' => folded.push_str("oe"),
+            'ř' => folded.push('r'),
+            'ś' | 'š' | 'ş' | 'ș' => folded.push('s'),
+            'ß' => folded.push_str("ss"),
+            'ť' | 'ţ' | 'ț' => folded.push('t'),
+            'þ' => folded.push_str("th"),
+            'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' | 'ų' => folded.push('u'),
+            'ý' | 'ÿ' => folded.push('y'),
+            'ź' | 'ż' | 'ž' => folded.push('z'),
+            _ => folded.push(c),
+        }
+    }
+    folded
+        .replace("ae", "a")
+        .replace("oe", "o")
+        .replace("ue", "u")
+}
+
 /// Run interactive city selection with fuzzy search.
 ///
 /// This function provides a single-step fuzzy search across all cities worldwide.
@@ -88,13 +227,18 @@ pub fn select_city_interactive() -> Result<(f64, f64, String)> {
 
     Log::log_block_start(&format!(
         "Selected: {}, {}",
-        selected_city.name, selected_city.country
+        selected_city.display_name(),
+        selected_city.country
     ));
 
     Ok((
         selected_city.latitude,
         selected_city.longitude,
-        format!("{}, {}", selected_city.name, selected_city.country),
+        format!(
+            "{}, {}",
+            selected_city.display_name(),
+            selected_city.country
+        ),
     ))
 }
 
@@ -129,6 +273,28 @@ fn get_all_cities() -> Vec<CityInfo> {
     all_cities
 }
 
+/// Filter cities whose folded search key contains the folded query.
+///
+/// Returns at most 100 results; an empty query returns the first 100 cities.
+fn filter_cities<'a>(
+    cities: &'a [CityInfo],
+    search_keys: &[String],
+    query: &str,
+) -> Vec<&'a CityInfo> {
+    if query.is_empty() {
+        return cities.iter().take(100).collect(); // Show first 100 when no search
+    }
+
+    let folded_query = fold_for_search(query);
+    cities
+        .iter()
+        .zip(search_keys)
+        .filter(|(_, key)| key.contains(&folded_query))
+        .map(|(city, _)| city)
+        .take(100) // Limit to 100 results for performance
+        .collect()
+}
+
 /// Fuzzy search for cities with a fixed-height scrollable list.
 ///
 /// This function implements the interactive UI for city selection, handling:
@@ -192,21 +358,12 @@ fn fuzzy_search_city(cities: &[CityInfo]) -> Result<&CityInfo> {
     let (_initial_col, initial_row) = crossterm::cursor::position()?;
     let _ui_start_row = initial_row + 1; // Start one line below current position
 
+    // Fold every city once up front rather than on each keystroke
+    let search_keys: Vec<String> = cities.iter().map(CityInfo::search_key).collect();
+
     let result = loop {
         // Filter cities based on search query
-        let filtered_cities: Vec<&CityInfo> = if search_query.is_empty() {
-            cities.iter().take(100).collect() // Show first 100 when no search
-        } else {
-            cities
-                .iter()
-                .filter(|city| {
-                    let search_lower = search_query.to_lowercase();
-                    city.name.to_lowercase().contains(&search_lower)
-                        || city.country.to_lowercase().contains(&search_lower)
-                })
-                .take(100) // Limit to 100 results for performance
-                .collect()
-        };
+        let filtered_cities = filter_cities(cities, &search_keys, &search_query);
 
         // Adjust selection if it's out of bounds
         if selected_index >= filtered_cities.len() && !filtered_cities.is_empty() {
@@ -240,10 +397,14 @@ fn fuzzy_search_city(cities: &[CityInfo]) -> Result<&CityInfo> {
                 let city = &filtered_cities[scroll_offset + i];
                 let is_selected = scroll_offset + i == selected_index;
 
-                let display = format!("{}, {}", city.name, city.country);
+                let display = format!("{}, {}", city.display_name(), city.country);
                 let max_width = 60;
-                let display = if display.len() > max_width {
-                    format!("{}…", &display[..max_width - 1])
+                // Truncate by characters, localized names may contain multi-byte characters
+                let display = if display.chars().count() > max_width {
+                    format!(
+                        "{}…",
+                        display.chars().take(max_width - 1).collect::<String>()
+                    )
                 } else {
                     display
                 };
@@ -287,19 +448,15 @@ fn fuzzy_search_city(cities: &[CityInfo]) -> Result<&CityInfo> {
                 KeyCode::Esc => {
                     break Err(anyhow::anyhow!("City selection cancelled by user"));
                 }
-                KeyCode::Enter => {
-                    if !filtered_cities.is_empty() {
-                        break Ok(filtered_cities[selected_index]);
-                    }
+                KeyCode::Enter if !filtered_cities.is_empty() => {
+                    break Ok(filtered_cities[selected_index]);
                 }
                 KeyCode::Up if selected_index > 0 => {
                     selected_index -= 1;
                 }
                 KeyCode::Up => {}
-                KeyCode::Down => {
-                    if selected_index + 1 < filtered_cities.len() {
-                        selected_index += 1;
-                    }
+                KeyCode::Down if selected_index + 1 < filtered_cities.len() => {
+                    selected_index += 1;
                 }
                 KeyCode::Backspace => {
                     search_query.pop();
@@ -326,3 +483,54 @@ fn fuzzy_search_city(cities: &[CityInfo]) -> Result<&CityInfo> {
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold_for_search_matches_local_spellings() {
+        assert_eq!(fold_for_search("München"), "munchen");
+        assert_eq!(fold_for_search("Muenchen"), "munchen");
+        assert_eq!(fold_for_search("Köln"), fold_for_search("Koeln"));
+        assert_eq!(fold_for_search("Łódź"), "lodz");
+        assert_eq!(fold_for_search("Straße"), "strasse");
+    }
+
+    #[test]
+    fn test_search_finds_localized_and_alternate_names() {
+        let cities = get_all_cities();
+        let keys: Vec<String> = cities.iter().map(CityInfo::search_key).collect();
+
+        for query in [
+            "München",
+            "munich",
+            "Köln",
+            "Cologne",
+            "Zürich",
+            "São Paulo",
+        ] {
+            let results = filter_cities(&cities, &keys, query);
+            assert!(!results.is_empty(), "no results for {}", query);
+        }
+
+        let munich = filter_cities(&cities, &keys, "Munich");
+        assert!(
+            munich
+                .iter()
+                .any(|c| c.country == "Germany" && c.display_name() == "München")
+        );
+
+        // Every alias entry must refer to a city that exists in the database
+        for (country, name, _, _) in CITY_NAME_ALIASES {
+            assert!(
+                cities
+                    .iter()
+                    .any(|c| c.country == *country && c.name == *name),
+                "alias for unknown city {}, {}",
+                name,
+                country
+            );
+        }
+    }
+}
@@ -77,8 +77,7 @@ pub fn handle_geo_selection(debug_enabled: bool) -> anyhow::Result<GeoSelectionR
     }
 
     // Check if sunsetr is currently running
-    let runtime_dir = std::env::var("XDG_RUNTIME_DIR").unwrap_or_else(|_| "/tmp".to_string());
-    let lock_path = format!("{}/sunsetr.lock", runtime_dir);
+    let lock_path = crate::utils::lock_file_path()?;
     let instance_running = is_sunsetr_running(&lock_path);
 
     if instance_running {
@@ -167,6 +166,9 @@ pub fn run_city_selection(debug_enabled: bool) -> anyhow::Result<(f64, f64, Stri
         latitude,
         longitude,
         today,
+        // Selection runs before any config is read, so the preview uses the default
+        crate::geo::solar::SolarPrecision::default(),
+        crate::geo::solar::NightBoundary::default(),
         debug_enabled,
     ) {
         Ok((
@@ -216,7 +218,13 @@ pub fn run_city_selection(debug_enabled: bool) -> anyhow::Result<(f64, f64, Stri
 
             // Show detailed solar calculation debug info when debug mode is enabled
             if debug_enabled {
-                let _ = log_solar_debug_info(latitude, longitude);
+                let _ = log_solar_debug_info(
+                    latitude,
+                    longitude,
+                    crate::geo::solar::SolarPrecision::default(),
+                    crate::geo::solar::NightBoundary::default(),
+                    crate::constants::DEFAULT_GEO_ROUND_TO_MINUTES,
+                );
             }
         }
         Err(e) => {
@@ -244,7 +252,7 @@ pub fn run_city_selection(debug_enabled: bool) -> anyhow::Result<(f64, f64, Stri
 /// - Config path cannot be determined
 /// - Config file cannot be read/written
 /// - Config update fails
-fn handle_config_update_with_coordinates(
+pub(crate) fn handle_config_update_with_coordinates(
     latitude: f64,
     longitude: f64,
     city_name: &str,
@@ -252,6 +260,13 @@ fn handle_config_update_with_coordinates(
     use crate::config::Config;
     use crate::logger::Log;
 
+    if crate::utils::is_safe_mode() {
+        Log::log_block_start(&format!("Safe mode: not saving location {}", city_name));
+        Log::log_indented(&format!("Latitude: {}", latitude));
+        Log::log_indented(&format!("Longitude: {}", longitude));
+        return Ok(());
+    }
+
     let config_path = Config::get_config_path()?;
 
     if config_path.exists() {
@@ -280,71 +295,6 @@ fn handle_config_update_with_coordinates(
     Ok(())
 }
 
-/// Convert a NaiveTime from one timezone to another by reconstructing the full datetime.
-///
-/// Since NaiveTime doesn't have date/timezone info, we need to reconstruct it with the
-/// proper date and timezone to convert correctly. This handles DST transitions and
-/// timezone ambiguities gracefully.
-///
-/// # Arguments
-/// * `time` - The time to convert (naive, no timezone info)
-/// * `from_tz` - The source timezone
-/// * `date` - The date context for proper DST handling
-///
-/// # Returns
-/// The equivalent time in the user's local timezone
-fn convert_time_to_local_tz(
-    time: chrono::NaiveTime,
-    from_tz: &chrono_tz::Tz,
-    date: chrono::NaiveDate,
-) -> chrono::NaiveTime {
-    use chrono::{Local, TimeZone};
-
-    // Create a datetime in the source timezone
-    let datetime_in_tz = from_tz
-        .from_local_datetime(&date.and_time(time))
-        .single()
-        .unwrap_or_else(|| from_tz.from_utc_datetime(&date.and_time(time)));
-
-    // Convert to local timezone
-    Local.from_utc_datetime(&datetime_in_tz.naive_utc()).time()
-}
-
-/// Check if the city timezone matches the user's local timezone.
-///
-/// This is used to optimize debug output by avoiding redundant timezone conversions
-/// and display when both timezones are the same. The comparison is done by checking
-/// UTC offsets at a specific date/time to handle DST correctly.
-///
-/// # Arguments
-/// * `city_tz` - The timezone to compare against local
-/// * `date` - The date for offset comparison (important for DST)
-///
-/// # Returns
-/// `true` if the timezones have the same UTC offset at the given date
-fn is_city_timezone_same_as_local(city_tz: &chrono_tz::Tz, date: chrono::NaiveDate) -> bool {
-    use chrono::{Local, Offset, TimeZone};
-
-    // Use a test time to compare timezone offsets
-    let test_time = chrono::NaiveTime::from_hms_opt(12, 0, 0).unwrap();
-    let test_datetime = date.and_time(test_time);
-
-    // Get the offset for both timezones at the given date
-    let city_offset = city_tz
-        .from_local_datetime(&test_datetime)
-        .single()
-        .map(|dt| dt.offset().fix())
-        .unwrap_or_else(|| city_tz.from_utc_datetime(&test_datetime).offset().fix());
-
-    let local_offset = Local
-        .from_local_datetime(&test_datetime)
-        .single()
-        .map(|dt| dt.offset().fix())
-        .unwrap_or_else(|| Local.from_utc_datetime(&test_datetime).offset().fix());
-
-    city_offset == local_offset
-}
-
 /// Format a time with optional timezone conversion and display.
 ///
 /// This helper function formats times for display, showing both the city's local time
@@ -369,18 +319,7 @@ fn format_time_with_optional_local(
     date: chrono::NaiveDate,
     format_str: &str,
 ) -> String {
-    if is_city_timezone_same_as_local(city_tz, date) {
-        // Same timezone - show only the original time
-        time.format(format_str).to_string()
-    } else {
-        // Different timezones - show both times
-        let local_time = convert_time_to_local_tz(time, city_tz, date);
-        format!(
-            "{} [{}]",
-            time.format(format_str),
-            local_time.format(format_str)
-        )
-    }
+    crate::geo::timezone::format_city_time(time, city_tz, &chrono::Local, date, format_str)
 }
 
 /// Check if sunsetr is currently running by testing the lock file.
@@ -390,7 +329,7 @@ fn format_time_with_optional_local(
 /// is active.
 ///
 /// # Arguments
-/// * `lock_path` - Path to the lock file (typically in XDG_RUNTIME_DIR or /tmp)
+/// * `lock_path` - Path to the lock file (see [`crate::utils::lock_file_path`])
 ///
 /// # Returns
 /// `true` if another instance is running, `false` otherwise
@@ -416,10 +355,23 @@ fn is_sunsetr_running(lock_path: &str) -> bool {
 /// This function calculates and displays comprehensive solar timing information
 /// including sunrise/sunset times, transition boundaries, and durations.
 /// It also warns if extreme latitude fallback values are used.
-pub fn log_solar_debug_info(latitude: f64, longitude: f64) -> anyhow::Result<()> {
+pub fn log_solar_debug_info(
+    latitude: f64,
+    longitude: f64,
+    precision: crate::geo::solar::SolarPrecision,
+    night_boundary: crate::geo::solar::NightBoundary,
+    round_to_minutes: u32,
+) -> anyhow::Result<()> {
     use crate::logger::Log;
 
-    let solar_result = crate::geo::solar::calculate_solar_times_unified(latitude, longitude)?;
+    // Round exactly as the scheduler does so the times shown are the ones used
+    let solar_result = crate::geo::solar::calculate_solar_times_unified(
+        latitude,
+        longitude,
+        precision,
+        night_boundary,
+    )?
+    .rounded_to(round_to_minutes);
 
     // Check if extreme latitude fallback was used and warn the user
     if solar_result.used_extreme_latitude_fallback {
@@ -438,7 +390,13 @@ pub fn log_solar_debug_info(latitude: f64, longitude: f64) -> anyhow::Result<()>
     let today = chrono::Local::now().date_naive();
     let city_tz = solar_result.city_timezone;
 
-    // Calculate night duration (-2° evening to -2° morning)
+    // Deeper boundaries fall back to -2° on days the sun doesn't sink that low
+    let night_label = match night_boundary {
+        crate::geo::solar::NightBoundary::Civil => "-2°",
+        _ => "night",
+    };
+
+    // Calculate night duration (night boundary evening to morning)
     let night_duration = if solar_result.sunrise_minus_2_start > solar_result.sunset_minus_2_end {
         // Same day
         solar_result
@@ -481,8 +439,9 @@ pub fn log_solar_debug_info(latitude: f64, longitude: f64) -> anyhow::Result<()>
 
     // Get sunrise/sunset UTC times
     use sunrise::{Coordinates, SolarDay, SolarEvent};
-    let coord = Coordinates::new(latitude, longitude)
-        .ok_or_else(|| anyhow::anyhow!("Invalid coordinates"))?;
+    solar::validate_coordinates(latitude, longitude)?;
+    let coord =
+        Coordinates::new(latitude, longitude).expect("coordinates validated to be in range");
     let solar_day = SolarDay::new(coord, today);
     let sunrise_utc = solar_day.event_time(SolarEvent::Sunrise);
     let sunset_utc = solar_day.event_time(SolarEvent::Sunset);
@@ -496,46 +455,24 @@ pub fn log_solar_debug_info(latitude: f64, longitude: f64) -> anyhow::Result<()>
         sunset_utc.format("%H:%M")
     ));
     // Format city timezone with both name and offset
-    let city_offset_secs = {
-        use chrono::{Offset, TimeZone};
-        let test_datetime = today.and_time(chrono::NaiveTime::from_hms_opt(12, 0, 0).unwrap());
-        city_tz
-            .from_local_datetime(&test_datetime)
-            .single()
-            .map(|dt| dt.offset().fix().local_minus_utc())
-            .unwrap_or_else(|| {
-                city_tz
-                    .from_utc_datetime(&test_datetime)
-                    .offset()
-                    .fix()
-                    .local_minus_utc()
-            })
-    };
-    let city_offset_hours = city_offset_secs / 3600;
-    let city_offset_minutes = (city_offset_secs % 3600).abs() / 60;
-    let city_offset_str = if city_offset_minutes == 0 {
-        format!("{:+03}:00", city_offset_hours)
-    } else {
-        format!("{:+03}:{:02}", city_offset_hours, city_offset_minutes)
-    };
-
+    let now_utc = chrono::Utc::now();
+    let city_offset_secs = crate::geo::timezone::utc_offset_seconds(&city_tz, now_utc);
     Log::log_indented(&format!(
         "    Coordinate Timezone: {} ({})",
-        city_tz, city_offset_str
+        city_tz,
+        crate::geo::timezone::format_utc_offset(city_offset_secs)
     ));
 
     // Show timezone comparison info only if timezones differ
-    if !is_city_timezone_same_as_local(&city_tz, today) {
-        use chrono::{Local, Offset};
+    if crate::geo::timezone::offset_differs(&city_tz, &chrono::Local, today) {
+        use chrono::Local;
 
         // Get current time in both timezones
-        let now_utc = chrono::Utc::now();
         let now_city = now_utc.with_timezone(&city_tz);
         let now_local = now_utc.with_timezone(&Local);
 
         // Calculate time difference
-        let city_offset_secs = now_city.offset().fix().local_minus_utc();
-        let local_offset_secs = now_local.offset().fix().local_minus_utc();
+        let local_offset_secs = crate::geo::timezone::utc_offset_seconds(&Local, now_utc);
         let offset_diff_secs = city_offset_secs - local_offset_secs;
         let offset_diff = chrono::Duration::seconds(offset_diff_secs as i64);
         let hours_diff = offset_diff.num_hours();
@@ -550,17 +487,10 @@ pub fn log_solar_debug_info(latitude: f64, longitude: f64) -> anyhow::Result<()>
             }
         };
 
-        let local_offset_hours = local_offset_secs / 3600;
-        let local_offset_minutes = (local_offset_secs % 3600).abs() / 60;
-        let local_offset_str = if local_offset_minutes == 0 {
-            format!("{:+03}:00", local_offset_hours)
-        } else {
-            format!("{:+03}:{:02}", local_offset_hours, local_offset_minutes)
-        };
-
         Log::log_indented(&format!(
             "         Local timezone: {} ({})",
-            local_tz_name, local_offset_str
+            local_tz_name,
+            crate::geo::timezone::format_utc_offset(local_offset_secs)
         ));
         Log::log_indented(&format!(
             "  Current time (Coords): {}",
@@ -613,7 +543,8 @@ pub fn log_solar_debug_info(latitude: f64, longitude: f64) -> anyhow::Result<()>
         format_time_with_optional_local(solar_result.sunset_time, &city_tz, today, "%H:%M:%S")
     ));
     Log::log_indented(&format!(
-        "   Transition end (-2°): {}",
+        "{:>23}: {}",
+        format!("Transition end ({})", night_label),
         format_time_with_optional_local(
             solar_result.sunset_minus_2_end,
             &city_tz,
@@ -641,7 +572,8 @@ pub fn log_solar_debug_info(latitude: f64, longitude: f64) -> anyhow::Result<()>
         format_time_with_optional_local(solar_result.civil_dawn, &city_tz, tomorrow, "%H:%M:%S")
     ));
     Log::log_indented(&format!(
-        " Transition start (-2°): {}",
+        "{:>23}: {}",
+        format!("Transition start ({})", night_label),
         format_time_with_optional_local(
             solar_result.sunrise_minus_2_start,
             &city_tz,
@@ -697,6 +629,14 @@ pub fn handle_geo_command(debug_enabled: bool) -> anyhow::Result<GeoCommandResul
 
     // Handle --geo flag: delegate to geo module and handle result
     match handle_geo_selection(debug_enabled)? {
+        GeoSelectionResult::ConfigUpdated {
+            needs_restart: true,
+        } if crate::utils::is_safe_mode() => {
+            // Nothing was written, so there is nothing for the running instance to pick up
+            Log::log_decorated("Safe mode: leaving the running sunsetr instance untouched.");
+            Log::log_end();
+            Ok(GeoCommandResult::Completed)
+        }
         GeoSelectionResult::ConfigUpdated {
             needs_restart: true,
         } => {
@@ -715,10 +655,9 @@ pub fn handle_geo_command(debug_enabled: bool) -> anyhow::Result<GeoCommandResul
                         Log::log_decorated("Stopped existing sunsetr instance.");
 
                         // Clean up the lock file since the killed process can't do it
-                        let runtime_dir =
-                            std::env::var("XDG_RUNTIME_DIR").unwrap_or_else(|_| "/tmp".to_string());
-                        let lock_path = format!("{}/sunsetr.lock", runtime_dir);
-                        let _ = std::fs::remove_file(&lock_path);
+                        if let Ok(lock_path) = crate::utils::lock_file_path() {
+                            let _ = std::fs::remove_file(&lock_path);
+                        }
 
                         // Give it a moment to fully exit
                         std::thread::sleep(std::time::Duration::from_millis(500));
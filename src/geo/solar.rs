@@ -23,6 +23,25 @@
 //! - **0°**: Actual sunrise/sunset (geometric horizon)
 //! - **-2°**: Enhanced transition end (sunset) / start (sunrise)
 //! - **-6°**: Civil twilight (traditional, used for baseline calculations)
+//! - **-12°/-18°**: Nautical/astronomical twilight, optional night boundaries that
+//!   replace -2° (`geo_night_boundary`, see [`NightBoundary`])
+//!
+//! By default (`solar_precision = "fast"`) the +10° and -2° boundaries are extrapolated
+//! linearly from the sunset → civil dusk interval reported by the `sunrise` crate, which
+//! assumes the sun's elevation changes at a constant rate. With `"precise"` each boundary
+//! is instead solved for directly (see [`elevation_crossing_utc`]) from the NOAA solar
+//! position equations evaluated at every instant, which matters most at higher latitudes
+//! where the sun sets at a shallow angle.
+//!
+//! The deeper nautical and astronomical boundaries are always solved for directly,
+//! since a linear estimate that far below the horizon would be off by a lot. On days
+//! the sun never sinks that low (summer at higher latitudes), the regular -2°
+//! boundary is used instead.
+//!
+//! With `geo_anchor = "solar_noon"` these angles aren't used at all. Instead, both
+//! transitions sit a fixed offset either side of solar noon (see
+//! [`calculate_solar_noon_transition_centers`]), giving a day that is symmetric around
+//! noon and doesn't stretch or shrink with the seasons.
 //!
 //! ## Extreme Latitude Behavior
 //!
@@ -38,6 +57,68 @@ use anyhow::Result;
 use chrono::{Datelike, NaiveTime};
 use std::time::Duration;
 
+use crate::constants::{DEFAULT_GEO_NIGHT_BOUNDARY, DEFAULT_SOLAR_PRECISION};
+
+/// How the elevation-angle transition boundaries are calculated (`solar_precision`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SolarPrecision {
+    /// Extrapolate +10° and -2° linearly from sunset and civil dusk
+    #[default]
+    Fast,
+    /// Solve for the +10° and -2° crossings from the solar position at each instant
+    Precise,
+}
+
+impl SolarPrecision {
+    /// The precision selected by the config's `solar_precision`.
+    pub fn from_config(config: &crate::config::Config) -> Self {
+        match config
+            .solar_precision
+            .as_deref()
+            .unwrap_or(DEFAULT_SOLAR_PRECISION)
+        {
+            "precise" => SolarPrecision::Precise,
+            _ => SolarPrecision::Fast,
+        }
+    }
+}
+
+/// Where geo night begins and ends (`geo_night_boundary`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NightBoundary {
+    /// Night starts at -2°, within civil twilight
+    #[default]
+    Civil,
+    /// Night starts at nautical dusk (-12°)
+    Nautical,
+    /// Night starts at astronomical dusk (-18°)
+    Astronomical,
+}
+
+impl NightBoundary {
+    /// The boundary selected by the config's `geo_night_boundary`.
+    pub fn from_config(config: &crate::config::Config) -> Self {
+        match config
+            .geo_night_boundary
+            .as_deref()
+            .unwrap_or(DEFAULT_GEO_NIGHT_BOUNDARY)
+        {
+            "nautical" => NightBoundary::Nautical,
+            "astronomical" => NightBoundary::Astronomical,
+            _ => NightBoundary::Civil,
+        }
+    }
+
+    /// Solar elevation in degrees at which night begins and ends.
+    pub fn elevation(self) -> f64 {
+        match self {
+            NightBoundary::Civil => -2.0,
+            NightBoundary::Nautical => -12.0,
+            NightBoundary::Astronomical => -18.0,
+        }
+    }
+}
+
 /// Complete solar calculation result containing all transition times and metadata.
 ///
 /// This structure provides comprehensive solar timing information for a specific location,
@@ -62,9 +143,9 @@ pub struct SolarCalculationResult {
 
     /// Sunset transition start (sun at +10° elevation)
     pub sunset_plus_10_start: NaiveTime,
-    /// Sunset transition end (sun at -2° elevation)
+    /// Sunset transition end (sun at -2° elevation, or the deeper night boundary)
     pub sunset_minus_2_end: NaiveTime,
-    /// Sunrise transition start (sun at -2° elevation)
+    /// Sunrise transition start (sun at -2° elevation, or the deeper night boundary)
     pub sunrise_minus_2_start: NaiveTime,
     /// Sunrise transition end (sun at +10° elevation)
     pub sunrise_plus_10_end: NaiveTime,
@@ -93,6 +174,63 @@ pub struct SolarCalculationResult {
     pub fallback_duration_minutes: u32,
 }
 
+impl SolarCalculationResult {
+    /// Snap the transition boundaries to the nearest multiple of `minutes`.
+    ///
+    /// Used for `geo_round_to_minutes`, so the schedule doesn't shift by a minute or
+    /// two every day. The durations are recomputed from the snapped boundaries. The
+    /// sun event times (sunset, sunrise, civil twilight) are left as calculated.
+    pub fn rounded_to(mut self, minutes: u32) -> Self {
+        (self.sunset_plus_10_start, self.sunset_minus_2_end) =
+            snap_transition_window(self.sunset_plus_10_start, self.sunset_minus_2_end, minutes);
+        (self.sunrise_minus_2_start, self.sunrise_plus_10_end) = snap_transition_window(
+            self.sunrise_minus_2_start,
+            self.sunrise_plus_10_end,
+            minutes,
+        );
+        self.sunset_duration = window_length(self.sunset_plus_10_start, self.sunset_minus_2_end);
+        self.sunrise_duration = window_length(self.sunrise_minus_2_start, self.sunrise_plus_10_end);
+        self
+    }
+}
+
+/// Snap both ends of a transition window to the nearest multiple of `minutes`.
+///
+/// `minutes` of 0 or 1 leaves the window alone, as does a snap that would collapse
+/// the window to nothing.
+pub fn snap_transition_window(
+    start: NaiveTime,
+    end: NaiveTime,
+    minutes: u32,
+) -> (NaiveTime, NaiveTime) {
+    if minutes <= 1 {
+        return (start, end);
+    }
+
+    let snapped = (snap_time(start, minutes), snap_time(end, minutes));
+    if snapped.0 == snapped.1 && start != end {
+        (start, end)
+    } else {
+        snapped
+    }
+}
+
+/// Round a time of day to the nearest multiple of `minutes`, wrapping at midnight.
+fn snap_time(time: NaiveTime, minutes: u32) -> NaiveTime {
+    use chrono::Timelike;
+
+    let step = minutes * 60;
+    let seconds = time.num_seconds_from_midnight();
+    let snapped = ((seconds + step / 2) / step * step) % 86400;
+    NaiveTime::from_num_seconds_from_midnight_opt(snapped, 0).unwrap_or(time)
+}
+
+/// Length of the window from `start` to `end`, crossing midnight if needed.
+fn window_length(start: NaiveTime, end: NaiveTime) -> Duration {
+    let seconds = (end - start).num_seconds().rem_euclid(86400);
+    Duration::from_secs(seconds as u64)
+}
+
 /// Type alias for civil twilight display data returned to the UI layer.
 ///
 /// This tuple contains all the timing information needed to display sunset/sunrise
@@ -134,6 +272,8 @@ type CivilTwilightDisplayData = (
 /// * `latitude` - Geographic latitude in degrees (-90.0 to +90.0)
 /// * `longitude` - Geographic longitude in degrees (-180.0 to +180.0)
 /// * `date` - Date for calculations (currently unused - uses current system date)
+/// * `precision` - How the +10° and -2° boundaries are calculated
+/// * `night_boundary` - Elevation at which the night begins and ends
 /// * `_debug_enabled` - Debug flag (currently unused)
 ///
 /// # Returns
@@ -153,7 +293,10 @@ type CivilTwilightDisplayData = (
 /// # use sunsetr::geo::solar::calculate_civil_twilight_times_for_display;
 /// # use chrono::NaiveDate;
 /// let today = NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
-/// let result = calculate_civil_twilight_times_for_display(40.7128, -74.0060, today, false)?;
+/// let result =
+///     calculate_civil_twilight_times_for_display(
+///         40.7128, -74.0060, today, Default::default(), Default::default(), false,
+///     )?;
 /// let (sunset_time, sunset_start, sunset_end, _, _, _, sunset_duration, _) = result;
 /// println!("Sunset transition: {} to {} (duration: {} minutes)",
 ///          sunset_start.format("%H:%M"),
@@ -165,10 +308,12 @@ pub fn calculate_civil_twilight_times_for_display(
     latitude: f64,
     longitude: f64,
     _date: chrono::NaiveDate,
+    precision: SolarPrecision,
+    night_boundary: NightBoundary,
     _debug_enabled: bool,
 ) -> Result<CivilTwilightDisplayData, anyhow::Error> {
     // Use the unified calculation function that handles extreme latitudes automatically
-    let result = calculate_solar_times_unified(latitude, longitude)?;
+    let result = calculate_solar_times_unified(latitude, longitude, precision, night_boundary)?;
 
     // For geo mode display, we show the actual transition boundaries (+10° to -2°)
     // that are used for the color temperature transitions
@@ -222,6 +367,9 @@ pub fn determine_timezone_from_coordinates(latitude: f64, longitude: f64) -> chr
 /// # Arguments
 /// * `latitude` - Geographic latitude in degrees
 /// * `longitude` - Geographic longitude in degrees
+/// * `precision` - How the +10° and -2° boundaries are calculated
+/// * `night_boundary` - Elevation at which the night begins and ends (`geo_night_boundary`)
+/// * `round_to_minutes` - Snap the boundaries to this many minutes (`geo_round_to_minutes`)
 ///
 /// # Returns
 /// Tuple of (sunset_start, sunset_end, sunrise_start, sunrise_end) as NaiveTime
@@ -229,6 +377,9 @@ pub fn determine_timezone_from_coordinates(latitude: f64, longitude: f64) -> chr
 pub fn calculate_geo_transition_boundaries(
     latitude: f64,
     longitude: f64,
+    precision: SolarPrecision,
+    night_boundary: NightBoundary,
+    round_to_minutes: u32,
 ) -> Result<
     (
         chrono::NaiveTime,
@@ -240,8 +391,10 @@ pub fn calculate_geo_transition_boundaries(
 > {
     use chrono::Local;
 
-    // Use the unified calculation function that handles extreme latitudes automatically
-    let result = calculate_solar_times_unified(latitude, longitude)?;
+    // Use the unified calculation function that handles extreme latitudes automatically.
+    // Rounding happens in the city's timezone, exactly as the debug output shows it.
+    let result = calculate_solar_times_unified(latitude, longitude, precision, night_boundary)?
+        .rounded_to(round_to_minutes);
 
     // Get today's date for timezone conversion
     let today = Local::now().date_naive();
@@ -273,6 +426,201 @@ pub fn calculate_geo_transition_boundaries(
     ))
 }
 
+/// Calculate solar noon for a date as a UTC time.
+///
+/// Uses the NOAA equation of time approximation, which is accurate to well under
+/// a minute. Unlike sunrise and sunset, solar noon only depends on longitude and
+/// exists at every latitude, including during polar day and night.
+///
+/// # Arguments
+/// * `longitude` - Geographic longitude in degrees (east positive)
+/// * `date` - The calendar date (UTC) to calculate solar noon for
+pub fn solar_noon_utc(longitude: f64, date: chrono::NaiveDate) -> chrono::DateTime<chrono::Utc> {
+    use std::f64::consts::PI;
+
+    // Fractional year in radians, evaluated at noon
+    let gamma = 2.0 * PI / 365.0 * (date.ordinal() as f64 - 1.0);
+    let equation_of_time = 229.18
+        * (0.000075 + 0.001868 * gamma.cos()
+            - 0.032077 * gamma.sin()
+            - 0.014615 * (2.0 * gamma).cos()
+            - 0.040849 * (2.0 * gamma).sin());
+
+    let noon_minutes = 720.0 - 4.0 * longitude - equation_of_time;
+    let noon_seconds = (noon_minutes * 60.0).round() as i64;
+
+    date.and_time(NaiveTime::MIN).and_utc() + chrono::Duration::seconds(noon_seconds)
+}
+
+/// Geometric elevation of the sun's center in degrees at an instant.
+///
+/// Uses the NOAA solar position equations (after Meeus), with declination and the
+/// equation of time evaluated at the instant itself rather than once per day. This is
+/// accurate to about a minute of arc for dates within a few centuries of 2000.
+/// Atmospheric refraction is not included.
+///
+/// # Arguments
+/// * `latitude` - Geographic latitude in degrees
+/// * `longitude` - Geographic longitude in degrees (east positive)
+/// * `at` - The instant to evaluate
+pub fn solar_elevation(latitude: f64, longitude: f64, at: chrono::DateTime<chrono::Utc>) -> f64 {
+    use chrono::Timelike;
+
+    let julian_day = at.timestamp_millis() as f64 / 86_400_000.0 + 2_440_587.5;
+    let t = (julian_day - 2_451_545.0) / 36_525.0;
+
+    let mean_longitude = (280.46646 + t * (36000.76983 + t * 0.0003032)).rem_euclid(360.0);
+    let mean_anomaly = 357.52911 + t * (35999.05029 - 0.0001537 * t);
+    let eccentricity = 0.016708634 - t * (0.000042037 + 0.0000001267 * t);
+    let m = mean_anomaly.to_radians();
+    let center = m.sin() * (1.914602 - t * (0.004817 + 0.000014 * t))
+        + (2.0 * m).sin() * (0.019993 - 0.000101 * t)
+        + (3.0 * m).sin() * 0.000289;
+
+    let omega = (125.04 - 1934.136 * t).to_radians();
+    let apparent_longitude =
+        (mean_longitude + center - 0.00569 - 0.00478 * omega.sin()).to_radians();
+    let mean_obliquity =
+        23.0 + (26.0 + (21.448 - t * (46.815 + t * (0.00059 - t * 0.001813))) / 60.0) / 60.0;
+    let obliquity = (mean_obliquity + 0.00256 * omega.cos()).to_radians();
+
+    let declination = (obliquity.sin() * apparent_longitude.sin()).asin();
+
+    // Equation of time in minutes
+    let y = (obliquity / 2.0).tan().powi(2);
+    let l0 = mean_longitude.to_radians();
+    let equation_of_time = 4.0
+        * (y * (2.0 * l0).sin() - 2.0 * eccentricity * m.sin()
+            + 4.0 * eccentricity * y * m.sin() * (2.0 * l0).cos()
+            - 0.5 * y * y * (4.0 * l0).sin()
+            - 1.25 * eccentricity * eccentricity * (2.0 * m).sin())
+        .to_degrees();
+
+    let minutes_of_day = at.num_seconds_from_midnight() as f64 / 60.0;
+    let true_solar_minutes = minutes_of_day + equation_of_time + 4.0 * longitude;
+    let hour_angle = (true_solar_minutes / 4.0 - 180.0).to_radians();
+
+    let latitude = latitude.to_radians();
+    let cos_zenith =
+        latitude.sin() * declination.sin() + latitude.cos() * declination.cos() * hour_angle.cos();
+    90.0 - cos_zenith.clamp(-1.0, 1.0).acos().to_degrees()
+}
+
+/// Time the sun crosses `elevation` degrees on `date`, rising or setting.
+///
+/// Searches the half day between solar noon and the solar midnight before it (rising)
+/// or after it (setting), where the elevation changes monotonically, and bisects down
+/// to the second. Returns `None` when the sun stays on one side of `elevation` for
+/// that whole half day, as happens near the poles.
+///
+/// # Arguments
+/// * `latitude` - Geographic latitude in degrees
+/// * `longitude` - Geographic longitude in degrees (east positive)
+/// * `date` - The calendar date (UTC) whose solar noon anchors the search
+/// * `elevation` - Elevation angle in degrees (negative below the horizon)
+/// * `rising` - `true` for the morning crossing, `false` for the evening one
+pub fn elevation_crossing_utc(
+    latitude: f64,
+    longitude: f64,
+    date: chrono::NaiveDate,
+    elevation: f64,
+    rising: bool,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    let above = |at| solar_elevation(latitude, longitude, at) >= elevation;
+
+    let noon = solar_noon_utc(longitude, date);
+    let midnight = if rising {
+        noon - chrono::Duration::hours(12)
+    } else {
+        noon + chrono::Duration::hours(12)
+    };
+    if !above(noon) || above(midnight) {
+        return None;
+    }
+
+    // `sunny` stays above the angle and `dark` below it while the gap closes
+    let (mut sunny, mut dark) = (noon, midnight);
+    while (dark - sunny).num_milliseconds().abs() > 1000 {
+        let middle = sunny + (dark - sunny) / 2;
+        if above(middle) {
+            sunny = middle;
+        } else {
+            dark = middle;
+        }
+    }
+    Some(sunny)
+}
+
+/// The +10° → -2° sunset and -2° → +10° sunrise windows for `date`, solved directly.
+///
+/// Returns `None` if any of the four crossings doesn't happen that day.
+fn precise_transition_windows(
+    latitude: f64,
+    longitude: f64,
+    date: chrono::NaiveDate,
+) -> Option<[(chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>); 2]> {
+    let crossing =
+        |elevation, rising| elevation_crossing_utc(latitude, longitude, date, elevation, rising);
+    Some([
+        (crossing(10.0, false)?, crossing(-2.0, false)?),
+        (crossing(-2.0, true)?, crossing(10.0, true)?),
+    ])
+}
+
+/// Check that a latitude/longitude pair is a real position on the globe.
+///
+/// The bounds are inclusive and both are well defined: at exactly ±90° the sun
+/// never crosses the transition elevations, so the calculation takes the
+/// extreme-latitude fallback, and ±180° name the same antimeridian (only the
+/// timezone lookup differs, which leaves the local clock times unchanged).
+pub fn validate_coordinates(latitude: f64, longitude: f64) -> Result<(), anyhow::Error> {
+    if !(-90.0..=90.0).contains(&latitude) {
+        anyhow::bail!(
+            "Latitude must be between -90 and 90 degrees (got {})",
+            latitude
+        );
+    }
+    if !(-180.0..=180.0).contains(&longitude) {
+        anyhow::bail!(
+            "Longitude must be between -180 and 180 degrees (got {})",
+            longitude
+        );
+    }
+    Ok(())
+}
+
+/// Calculate sunset and sunrise transition centers anchored to solar noon.
+///
+/// Used by `geo_anchor = "solar_noon"`. The sunset transition is centered
+/// `offset_hours` after today's solar noon and the sunrise transition the same
+/// amount before it, so the day is always symmetric around noon. The elevation
+/// angles used by the twilight anchor (+10° to -2°) play no part here.
+///
+/// # Arguments
+/// * `latitude` - Geographic latitude in degrees (validated, but not otherwise used)
+/// * `longitude` - Geographic longitude in degrees
+/// * `offset_hours` - Hours between solar noon and the center of each transition
+///
+/// # Returns
+/// Tuple of (sunset_center, sunrise_center) as NaiveTime in the user's local timezone
+pub fn calculate_solar_noon_transition_centers(
+    latitude: f64,
+    longitude: f64,
+    offset_hours: f64,
+) -> Result<(chrono::NaiveTime, chrono::NaiveTime), anyhow::Error> {
+    use chrono::{Local, Utc};
+
+    validate_coordinates(latitude, longitude)?;
+
+    let noon = solar_noon_utc(longitude, Utc::now().date_naive());
+    let offset = chrono::Duration::seconds((offset_hours * 3600.0).round() as i64);
+
+    Ok((
+        (noon + offset).with_timezone(&Local).time(),
+        (noon - offset).with_timezone(&Local).time(),
+    ))
+}
+
 /// Convert a time from a specific city's timezone to the user's local timezone.
 ///
 /// This helper function is essential for geo mode when the user's local timezone differs
@@ -288,24 +636,15 @@ pub fn calculate_geo_transition_boundaries(
 /// The equivalent time in the user's local timezone
 ///
 /// # Note
-/// This function handles DST transitions and timezone ambiguities by falling back
-/// to UTC interpretation when local time is ambiguous.
+/// Times that DST makes ambiguous or skips are resolved by
+/// [`crate::geo::timezone::city_time_to_utc`].
 fn convert_city_time_to_local(
     time: chrono::NaiveTime,
     city_tz: &chrono_tz::Tz,
     date: chrono::NaiveDate,
 ) -> chrono::NaiveTime {
-    use chrono::{Local, TimeZone};
-
-    // Create a datetime in the city's timezone
-    let datetime_in_city = city_tz
-        .from_local_datetime(&date.and_time(time))
-        .single()
-        .unwrap_or_else(|| city_tz.from_utc_datetime(&date.and_time(time)));
-
-    // Convert to user's local timezone and extract the time
-    Local
-        .from_utc_datetime(&datetime_in_city.naive_utc())
+    crate::geo::timezone::city_time_to_utc(city_tz, date, time)
+        .with_timezone(&chrono::Local)
         .time()
 }
 
@@ -318,27 +657,45 @@ fn convert_city_time_to_local(
 /// # Arguments
 /// * `latitude` - Geographic latitude in degrees
 /// * `longitude` - Geographic longitude in degrees
+/// * `precision` - How the +10° and -2° boundaries are calculated
+/// * `night_boundary` - Elevation at which the night begins and ends
 ///
 /// # Returns
 /// Complete solar calculation result with all times in city timezone
 pub fn calculate_solar_times_unified(
     latitude: f64,
     longitude: f64,
+    precision: SolarPrecision,
+    night_boundary: NightBoundary,
 ) -> Result<SolarCalculationResult, anyhow::Error> {
-    use chrono::Local;
-    use sunrise::{Coordinates, DawnType, SolarDay, SolarEvent};
+    calculate_solar_times_for_date(
+        latitude,
+        longitude,
+        chrono::Local::now().date_naive(),
+        precision,
+        night_boundary,
+    )
+}
 
-    let today = Local::now().date_naive();
+/// Like [`calculate_solar_times_unified`], for a specific calendar date.
+pub fn calculate_solar_times_for_date(
+    latitude: f64,
+    longitude: f64,
+    today: chrono::NaiveDate,
+    precision: SolarPrecision,
+    night_boundary: NightBoundary,
+) -> Result<SolarCalculationResult, anyhow::Error> {
+    use sunrise::{Coordinates, DawnType, SolarDay, SolarEvent};
 
     // Step 1: Determine the precise timezone for these coordinates
     // This is critical for ensuring all calculations are in the correct local time
     let city_tz = determine_timezone_from_coordinates(latitude, longitude);
 
-    // Step 2: Create coordinate object and validate input
-    // The sunrise crate will reject coordinates outside valid ranges
-    let coord = Coordinates::new(latitude, longitude).ok_or_else(|| {
-        anyhow::anyhow!("Invalid coordinates: lat={}, lon={}", latitude, longitude)
-    })?;
+    // Step 2: Validate input and create coordinate object
+    // The sunrise crate accepts the same inclusive ranges, so this cannot fail afterwards
+    validate_coordinates(latitude, longitude)?;
+    let coord =
+        Coordinates::new(latitude, longitude).expect("coordinates validated to be in range");
     let solar_day = SolarDay::new(coord, today);
 
     // Step 3: Calculate core solar events using astronomical algorithms
@@ -467,7 +824,7 @@ pub fn calculate_solar_times_unified(
 
     // Step 7: Calculate final transition boundaries and durations
     // Use either calculated values or fallback values depending on validation results
-    let (sunset_plus_10_start, sunset_minus_2_end, sunset_duration) = if used_fallback {
+    let (mut sunset_plus_10_start, mut sunset_minus_2_end, mut sunset_duration) = if used_fallback {
         let fallback_duration = chrono::Duration::minutes(fallback_minutes as i64);
         let plus_10_duration = fallback_duration * 10 / 12;
         let minus_2_duration = fallback_duration * 2 / 12;
@@ -493,31 +850,75 @@ pub fn calculate_solar_times_unified(
         (start, end, total_duration)
     };
 
-    let (sunrise_minus_2_start, sunrise_plus_10_end, sunrise_duration) = if used_fallback {
-        let fallback_duration = chrono::Duration::minutes(fallback_minutes as i64);
-        let minus_2_duration = fallback_duration * 2 / 12;
-        let plus_10_duration = fallback_duration * 10 / 12;
+    let (mut sunrise_minus_2_start, mut sunrise_plus_10_end, mut sunrise_duration) =
+        if used_fallback {
+            let fallback_duration = chrono::Duration::minutes(fallback_minutes as i64);
+            let minus_2_duration = fallback_duration * 2 / 12;
+            let plus_10_duration = fallback_duration * 10 / 12;
 
-        let start = sunrise_time - minus_2_duration;
-        let end = sunrise_time + plus_10_duration;
-        let duration = std::time::Duration::from_secs(fallback_duration.num_seconds() as u64);
+            let start = sunrise_time - minus_2_duration;
+            let end = sunrise_time + plus_10_duration;
+            let duration = std::time::Duration::from_secs(fallback_duration.num_seconds() as u64);
 
-        (start, end, duration)
-    } else {
-        let duration_from_minus_2 = civil_dawn_to_sunrise_duration * 2 / 6;
-        let duration_from_plus_10 = civil_dawn_to_sunrise_duration * 10 / 6;
+            (start, end, duration)
+        } else {
+            let duration_from_minus_2 = civil_dawn_to_sunrise_duration * 2 / 6;
+            let duration_from_plus_10 = civil_dawn_to_sunrise_duration * 10 / 6;
 
-        let start = sunrise_time - duration_from_minus_2;
-        let end = sunrise_time + duration_from_plus_10;
+            let start = sunrise_time - duration_from_minus_2;
+            let end = sunrise_time + duration_from_plus_10;
 
-        let total_duration = if end > start {
-            std::time::Duration::from_secs(end.signed_duration_since(start).num_seconds() as u64)
-        } else {
-            std::time::Duration::from_secs(30 * 60)
+            let total_duration = if end > start {
+                std::time::Duration::from_secs(end.signed_duration_since(start).num_seconds() as u64)
+            } else {
+                std::time::Duration::from_secs(30 * 60)
+            };
+
+            (start, end, total_duration)
         };
 
-        (start, end, total_duration)
-    };
+    // With precise calculations, replace the linear estimates with the actual crossings.
+    // Fallback latitudes keep their fixed durations, and so does any day on which one of
+    // the crossings doesn't happen.
+    if precision == SolarPrecision::Precise
+        && !used_fallback
+        && let Some([sunset_window, sunrise_window]) =
+            precise_transition_windows(latitude, longitude, today)
+    {
+        let in_city =
+            |(start, end): (chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>)| {
+                let duration = (end - start).to_std().unwrap_or_default();
+                (
+                    start.with_timezone(&city_tz).time(),
+                    end.with_timezone(&city_tz).time(),
+                    duration,
+                )
+            };
+        (sunset_plus_10_start, sunset_minus_2_end, sunset_duration) = in_city(sunset_window);
+        (sunrise_minus_2_start, sunrise_plus_10_end, sunrise_duration) = in_city(sunrise_window);
+    }
+
+    // A deeper night boundary replaces the -2° end of the sunset window and start of
+    // the sunrise window. Fallback latitudes keep their fixed windows, and days on
+    // which the sun never sinks that low keep -2°.
+    if night_boundary != NightBoundary::Civil
+        && !used_fallback
+        && let (Some(dusk), Some(dawn)) = (
+            elevation_crossing_utc(
+                latitude,
+                longitude,
+                today,
+                night_boundary.elevation(),
+                false,
+            ),
+            elevation_crossing_utc(latitude, longitude, today, night_boundary.elevation(), true),
+        )
+    {
+        sunset_minus_2_end = dusk.with_timezone(&city_tz).time();
+        sunrise_minus_2_start = dawn.with_timezone(&city_tz).time();
+        sunset_duration = window_length(sunset_plus_10_start, sunset_minus_2_end);
+        sunrise_duration = window_length(sunrise_minus_2_start, sunrise_plus_10_end);
+    }
 
     // Calculate golden hour boundaries (traditional +6° to -6°)
     let golden_hour_start = if used_fallback {
@@ -581,15 +982,81 @@ mod tests {
     #[test]
     fn test_coordinate_validation() {
         // Valid coordinates (New York City) should work
-        assert!(calculate_solar_times_unified(40.7128, -74.0060).is_ok());
+        assert!(
+            calculate_solar_times_unified(
+                40.7128,
+                -74.0060,
+                SolarPrecision::Fast,
+                NightBoundary::Civil
+            )
+            .is_ok()
+        );
 
         // Invalid latitudes (beyond ±90°) should fail - validated by sunrise crate
-        assert!(calculate_solar_times_unified(91.0, -74.0060).is_err());
-        assert!(calculate_solar_times_unified(-91.0, -74.0060).is_err());
+        assert!(
+            calculate_solar_times_unified(
+                91.0,
+                -74.0060,
+                SolarPrecision::Fast,
+                NightBoundary::Civil
+            )
+            .is_err()
+        );
+        assert!(
+            calculate_solar_times_unified(
+                -91.0,
+                -74.0060,
+                SolarPrecision::Fast,
+                NightBoundary::Civil
+            )
+            .is_err()
+        );
 
         // Invalid longitudes (beyond ±180°) should fail
-        assert!(calculate_solar_times_unified(40.7128, 181.0).is_err());
-        assert!(calculate_solar_times_unified(40.7128, -181.0).is_err());
+        assert!(
+            calculate_solar_times_unified(
+                40.7128,
+                181.0,
+                SolarPrecision::Fast,
+                NightBoundary::Civil
+            )
+            .is_err()
+        );
+        assert!(
+            calculate_solar_times_unified(
+                40.7128,
+                -181.0,
+                SolarPrecision::Fast,
+                NightBoundary::Civil
+            )
+            .is_err()
+        );
+    }
+
+    /// Test solar noon against known values and the noon-anchored transition centers.
+    #[test]
+    fn test_solar_noon() {
+        use chrono::{NaiveDate, Timelike};
+
+        // Greenwich: solar noon is about 12:14 UTC in mid-February (equation of time minimum)
+        // and about 11:44 UTC in early November (maximum)
+        let february = solar_noon_utc(0.0, NaiveDate::from_ymd_opt(2024, 2, 11).unwrap());
+        assert_eq!((february.hour(), february.minute()), (12, 14));
+        let november = solar_noon_utc(0.0, NaiveDate::from_ymd_opt(2024, 11, 3).unwrap());
+        assert_eq!((november.hour(), november.minute()), (11, 43));
+
+        // Each 15° of longitude shifts solar noon by one hour
+        let date = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+        let shift = solar_noon_utc(0.0, date) - solar_noon_utc(-15.0, date);
+        assert_eq!(shift.num_seconds(), -3600);
+
+        // Transition centers are symmetric around noon, and exist even in polar night
+        let (sunset, sunrise) = calculate_solar_noon_transition_centers(78.2, 15.6, 6.0).unwrap();
+        let night = (sunrise - sunset + chrono::Duration::days(1)).num_seconds() % 86400;
+        assert!((night - 12 * 3600).abs() <= 1);
+
+        assert!(calculate_solar_noon_transition_centers(91.0, 0.0, 6.0).is_err());
+        assert!(calculate_solar_noon_transition_centers(0.0, 181.0, 6.0).is_err());
     }
 
     /// Test that transition durations vary realistically across different latitudes.
@@ -602,9 +1069,15 @@ mod tests {
     #[test]
     fn test_transition_duration_by_latitude() {
         // Test representative latitudes across the globe
-        let equator_result = calculate_solar_times_unified(0.0, 0.0).unwrap(); // Equator
-        let temperate_result = calculate_solar_times_unified(45.0, 0.0).unwrap(); // Mid-latitude
-        let high_latitude_result = calculate_solar_times_unified(60.0, 0.0).unwrap(); // Above 55° threshold
+        let equator_result =
+            calculate_solar_times_unified(0.0, 0.0, SolarPrecision::Fast, NightBoundary::Civil)
+                .unwrap(); // Equator
+        let temperate_result =
+            calculate_solar_times_unified(45.0, 0.0, SolarPrecision::Fast, NightBoundary::Civil)
+                .unwrap(); // Mid-latitude
+        let high_latitude_result =
+            calculate_solar_times_unified(60.0, 0.0, SolarPrecision::Fast, NightBoundary::Civil)
+                .unwrap(); // Above 55° threshold
 
         // Equatorial and temperate regions: expect moderate durations (15-90 minutes)
         assert!(equator_result.sunset_duration >= Duration::from_secs(15 * 60));
@@ -629,13 +1102,19 @@ mod tests {
     #[test]
     fn test_extreme_latitude_fallback_detection() {
         // Normal latitudes (temperate zones) should never trigger fallback
-        let normal_result = calculate_solar_times_unified(45.0, 0.0).unwrap();
+        let normal_result =
+            calculate_solar_times_unified(45.0, 0.0, SolarPrecision::Fast, NightBoundary::Civil)
+                .unwrap();
         assert!(!normal_result.used_extreme_latitude_fallback);
 
         // Test with truly extreme coordinates that are more likely to trigger validation failures
         // These represent real locations where astronomical calculations often fail
-        let arctic_north = calculate_solar_times_unified(78.0, 15.0).unwrap(); // Svalbard, Norway
-        let antarctic_south = calculate_solar_times_unified(-75.0, 0.0).unwrap(); // Antarctica
+        let arctic_north =
+            calculate_solar_times_unified(78.0, 15.0, SolarPrecision::Fast, NightBoundary::Civil)
+                .unwrap(); // Svalbard, Norway
+        let antarctic_south =
+            calculate_solar_times_unified(-75.0, 0.0, SolarPrecision::Fast, NightBoundary::Civil)
+                .unwrap(); // Antarctica
 
         // Fallback activation depends on actual calculation failure, not just latitude
         // If fallback is used, validate the fallback duration ranges
@@ -656,6 +1135,75 @@ mod tests {
         assert!(antarctic_south.sunset_duration >= Duration::from_secs(15 * 60));
     }
 
+    #[test]
+    fn test_validate_coordinates() {
+        assert!(validate_coordinates(52.52, 13.405).is_ok());
+        assert!(validate_coordinates(-90.0, 180.0).is_ok());
+        assert!(validate_coordinates(90.5, 0.0).is_err());
+        assert!(validate_coordinates(0.0, -180.5).is_err());
+        assert!(validate_coordinates(f64::NAN, 0.0).is_err());
+
+        let err = validate_coordinates(0.0, 181.0).unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "Longitude must be between -180 and 180 degrees (got 181)"
+        );
+    }
+
+    /// Exact poles and the antimeridian are valid inputs with defined results,
+    /// not "invalid coordinates" errors.
+    #[test]
+    fn test_coordinates_at_poles_and_antimeridian() {
+        let solstices = [
+            chrono::NaiveDate::from_ymd_opt(2026, 6, 21).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 12, 21).unwrap(),
+        ];
+
+        for date in solstices {
+            for latitude in [90.0, -90.0] {
+                for precision in [SolarPrecision::Fast, SolarPrecision::Precise] {
+                    let result = calculate_solar_times_for_date(
+                        latitude,
+                        0.0,
+                        date,
+                        precision,
+                        NightBoundary::Civil,
+                    )
+                    .unwrap();
+                    // The sun's elevation barely changes over a day at the pole
+                    assert!(result.used_extreme_latitude_fallback);
+                    assert!(result.sunset_duration >= Duration::from_secs(15 * 60));
+                }
+            }
+
+            let east = calculate_solar_times_for_date(
+                0.0,
+                180.0,
+                date,
+                SolarPrecision::Fast,
+                NightBoundary::Civil,
+            )
+            .unwrap();
+            let west = calculate_solar_times_for_date(
+                0.0,
+                -180.0,
+                date,
+                SolarPrecision::Fast,
+                NightBoundary::Civil,
+            )
+            .unwrap();
+            assert!(!east.used_extreme_latitude_fallback);
+            // Same meridian, timezones a whole day apart: the clock times only
+            // differ by the sun's drift over that day
+            let drift = |a: chrono::NaiveTime, b: chrono::NaiveTime| (a - b).num_seconds().abs();
+            assert!(drift(east.sunset_time, west.sunset_time) < 60);
+            assert!(drift(east.sunrise_time, west.sunrise_time) < 60);
+        }
+
+        assert!(calculate_solar_noon_transition_centers(90.0, 0.0, 6.0).is_ok());
+        assert!(calculate_solar_noon_transition_centers(0.0, -180.0, 6.0).is_ok());
+    }
+
     /// Test that the comprehensive validation logic correctly distinguishes between
     /// working astronomical calculations and failed calculations that need fallback.
     ///
@@ -665,16 +1213,30 @@ mod tests {
     #[test]
     fn test_validation_logic_behavior() {
         // **Normal latitude**: Should always work without fallback
-        let london_result = calculate_solar_times_unified(51.5074, -0.1278).unwrap();
+        let london_result = calculate_solar_times_unified(
+            51.5074,
+            -0.1278,
+            SolarPrecision::Fast,
+            NightBoundary::Civil,
+        )
+        .unwrap();
         assert!(!london_result.used_extreme_latitude_fallback);
 
         // **High latitude, but still functional**: May or may not trigger fallback
-        let reykjavik_result = calculate_solar_times_unified(64.1466, -21.9426).unwrap();
+        let reykjavik_result = calculate_solar_times_unified(
+            64.1466,
+            -21.9426,
+            SolarPrecision::Fast,
+            NightBoundary::Civil,
+        )
+        .unwrap();
         // Reykjavik (64°N) is above the 55° threshold but calculations often succeed
         // Fallback activation depends on seasonal timing and actual calculation results
 
         // **Very high latitude**: Much more likely to trigger validation failures
-        let pole_result = calculate_solar_times_unified(85.0, 0.0).unwrap();
+        let pole_result =
+            calculate_solar_times_unified(85.0, 0.0, SolarPrecision::Fast, NightBoundary::Civil)
+                .unwrap();
         // Near-polar coordinates (85°N) are extremely likely to fail validation
         // The validation system should catch issues like identical times or invalid sequences
 
@@ -696,7 +1258,12 @@ mod tests {
     #[test]
     fn test_solar_times_integration() {
         // Test with New York City coordinates (known good case)
-        let result = calculate_solar_times_unified(40.7128, -74.0060);
+        let result = calculate_solar_times_unified(
+            40.7128,
+            -74.0060,
+            SolarPrecision::Fast,
+            NightBoundary::Civil,
+        );
         assert!(result.is_ok());
 
         let solar_result = result.unwrap();
@@ -757,4 +1324,240 @@ mod tests {
             );
         }
     }
+
+    /// Minutes between two times of day, ignoring which one comes first.
+    fn minutes_apart(a: NaiveTime, b: NaiveTime) -> i64 {
+        a.signed_duration_since(b).num_minutes().abs()
+    }
+
+    #[test]
+    fn test_precise_solar_times_at_mid_latitude() {
+        use sunrise::{Coordinates, SolarDay, SolarEvent};
+
+        // New York through the year
+        let (latitude, longitude) = (40.7128, -74.0060);
+        for month in 1..=12 {
+            let date = chrono::NaiveDate::from_ymd_opt(2024, month, 15).unwrap();
+            let fast = calculate_solar_times_for_date(
+                latitude,
+                longitude,
+                date,
+                SolarPrecision::Fast,
+                NightBoundary::Civil,
+            )
+            .unwrap();
+            let precise = calculate_solar_times_for_date(
+                latitude,
+                longitude,
+                date,
+                SolarPrecision::Precise,
+                NightBoundary::Civil,
+            )
+            .unwrap();
+            assert!(!precise.used_extreme_latitude_fallback);
+
+            // The solver agrees with the sunrise crate on its own event (-0.833° is the
+            // standard horizon including refraction)
+            let crate_sunset = SolarDay::new(Coordinates::new(latitude, longitude).unwrap(), date)
+                .event_time(SolarEvent::Sunset);
+            let solved_sunset =
+                elevation_crossing_utc(latitude, longitude, date, -0.833, false).unwrap();
+            assert!(
+                (crate_sunset - solved_sunset).num_seconds().abs() <= 90,
+                "sunset on {}: crate {}, solved {}",
+                date,
+                crate_sunset,
+                solved_sunset
+            );
+
+            // The linear estimates drift from the solved boundaries by up to ~20 minutes
+            // around the winter solstice at this latitude, but no further
+            for (name, fast_time, precise_time) in [
+                (
+                    "sunset +10°",
+                    fast.sunset_plus_10_start,
+                    precise.sunset_plus_10_start,
+                ),
+                (
+                    "sunset -2°",
+                    fast.sunset_minus_2_end,
+                    precise.sunset_minus_2_end,
+                ),
+                (
+                    "sunrise -2°",
+                    fast.sunrise_minus_2_start,
+                    precise.sunrise_minus_2_start,
+                ),
+                (
+                    "sunrise +10°",
+                    fast.sunrise_plus_10_end,
+                    precise.sunrise_plus_10_end,
+                ),
+            ] {
+                let apart = minutes_apart(fast_time, precise_time);
+                assert!(
+                    apart <= 25,
+                    "{} on {} differs by {} minutes",
+                    name,
+                    date,
+                    apart
+                );
+            }
+            let duration_gap =
+                fast.sunset_duration.as_secs() as i64 - precise.sunset_duration.as_secs() as i64;
+            assert!(duration_gap.abs() <= 20 * 60);
+
+            // Events that don't depend on the boundaries are unchanged
+            assert_eq!(fast.sunset_time, precise.sunset_time);
+            assert_eq!(fast.civil_dawn, precise.civil_dawn);
+        }
+    }
+
+    #[test]
+    fn test_precise_crossings_are_monotonic() {
+        let (latitude, longitude) = (40.7128, -74.0060);
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 3, 20).unwrap();
+        let angles = [10.0, 6.0, 0.0, -2.0, -6.0];
+
+        // Setting: lower angles are crossed later, rising: earlier
+        let setting: Vec<_> = angles
+            .iter()
+            .map(|&a| elevation_crossing_utc(latitude, longitude, date, a, false).unwrap())
+            .collect();
+        assert!(setting.windows(2).all(|pair| pair[0] < pair[1]));
+        let rising: Vec<_> = angles
+            .iter()
+            .map(|&a| elevation_crossing_utc(latitude, longitude, date, a, true).unwrap())
+            .collect();
+        assert!(rising.windows(2).all(|pair| pair[0] > pair[1]));
+
+        // The elevation itself falls steadily across the sunset window
+        let mut at = setting[0];
+        let mut previous = solar_elevation(latitude, longitude, at);
+        while at < setting[4] {
+            at += chrono::Duration::minutes(2);
+            let elevation = solar_elevation(latitude, longitude, at);
+            assert!(elevation < previous);
+            previous = elevation;
+        }
+
+        // The boundaries come out in order within each transition
+        let precise = calculate_solar_times_for_date(
+            latitude,
+            longitude,
+            date,
+            SolarPrecision::Precise,
+            NightBoundary::Civil,
+        )
+        .unwrap();
+        assert!(precise.sunrise_minus_2_start < precise.sunrise_plus_10_end);
+        assert!(precise.sunrise_plus_10_end < precise.sunset_plus_10_start);
+        assert!(precise.sunset_plus_10_start < precise.sunset_minus_2_end);
+
+        // Polar night: the sun never reaches +10°, so there's no crossing to find
+        let polar = chrono::NaiveDate::from_ymd_opt(2024, 12, 21).unwrap();
+        assert_eq!(elevation_crossing_utc(78.0, 15.0, polar, 10.0, false), None);
+    }
+
+    #[test]
+    fn test_snap_transition_window() {
+        let t = |h, m, s| NaiveTime::from_hms_opt(h, m, s).unwrap();
+
+        // Nearest multiple, ties rounding up
+        assert_eq!(
+            snap_transition_window(t(19, 7, 29), t(19, 37, 30), 5),
+            (t(19, 5, 0), t(19, 40, 0))
+        );
+
+        // 0 and 1 leave the window alone
+        let window = (t(6, 12, 44), t(6, 48, 3));
+        assert_eq!(snap_transition_window(window.0, window.1, 0), window);
+        assert_eq!(snap_transition_window(window.0, window.1, 1), window);
+
+        // Snapping past midnight wraps around
+        assert_eq!(
+            snap_transition_window(t(23, 50, 0), t(23, 56, 0), 15),
+            (t(23, 45, 0), t(0, 0, 0))
+        );
+
+        // A window the snap would collapse is kept as calculated
+        let short = (t(19, 1, 0), t(19, 4, 0));
+        assert_eq!(snap_transition_window(short.0, short.1, 10), short);
+    }
+
+    #[test]
+    fn test_rounded_result_matches_boundaries() {
+        let result = calculate_solar_times_unified(
+            40.7128,
+            -74.0060,
+            SolarPrecision::Fast,
+            NightBoundary::Civil,
+        )
+        .unwrap()
+        .rounded_to(10);
+
+        for time in [
+            result.sunset_plus_10_start,
+            result.sunset_minus_2_end,
+            result.sunrise_minus_2_start,
+            result.sunrise_plus_10_end,
+        ] {
+            use chrono::Timelike;
+            assert_eq!((time.minute() % 10, time.second()), (0, 0));
+        }
+
+        // Durations follow the snapped boundaries
+        assert_eq!(
+            result.sunset_duration.as_secs() as i64,
+            (result.sunset_minus_2_end - result.sunset_plus_10_start).num_seconds()
+        );
+        assert_eq!(
+            result.sunrise_duration.as_secs() as i64,
+            (result.sunrise_plus_10_end - result.sunrise_minus_2_start).num_seconds()
+        );
+    }
+
+    #[test]
+    fn test_night_boundary_extends_the_night_window() {
+        let times = |latitude, date, boundary| {
+            calculate_solar_times_for_date(latitude, -74.0060, date, SolarPrecision::Fast, boundary)
+                .unwrap()
+        };
+
+        // New York in winter: each deeper boundary ends the sunset window later and
+        // starts the sunrise window earlier, while the +10° ends stay put
+        let winter = chrono::NaiveDate::from_ymd_opt(2024, 12, 15).unwrap();
+        let civil = times(40.7128, winter, NightBoundary::Civil);
+        let nautical = times(40.7128, winter, NightBoundary::Nautical);
+        let astronomical = times(40.7128, winter, NightBoundary::Astronomical);
+        assert!(civil.sunset_minus_2_end < nautical.sunset_minus_2_end);
+        assert!(nautical.sunset_minus_2_end < astronomical.sunset_minus_2_end);
+        assert!(astronomical.sunrise_minus_2_start < nautical.sunrise_minus_2_start);
+        assert!(nautical.sunrise_minus_2_start < civil.sunrise_minus_2_start);
+        assert_eq!(
+            civil.sunset_plus_10_start,
+            astronomical.sunset_plus_10_start
+        );
+        assert_eq!(civil.sunrise_plus_10_end, astronomical.sunrise_plus_10_end);
+        assert!(astronomical.sunset_duration > civil.sunset_duration);
+        assert_eq!(
+            astronomical.sunset_duration.as_secs() as i64,
+            (astronomical.sunset_minus_2_end - astronomical.sunset_plus_10_start).num_seconds()
+        );
+
+        // Astronomical dusk at -18° matches the elevation it's named after
+        let dusk = elevation_crossing_utc(40.7128, -74.0060, winter, -18.0, false).unwrap();
+        assert!((solar_elevation(40.7128, -74.0060, dusk) + 18.0).abs() < 0.1);
+
+        // At 52°N around the June solstice the sun never reaches -18°, so the
+        // regular -2° boundary is kept
+        let summer = chrono::NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
+        let civil = times(52.0, summer, NightBoundary::Civil);
+        let astronomical = times(52.0, summer, NightBoundary::Astronomical);
+        assert_eq!(civil.sunset_minus_2_end, astronomical.sunset_minus_2_end);
+        assert_eq!(
+            civil.sunrise_minus_2_start,
+            astronomical.sunrise_minus_2_start
+        );
+    }
 }
@@ -25,10 +25,19 @@
 //! - Unknown timezones default to UTC (London coordinates)
 //! - Failed detection results in an error rather than silent fallback
 //! - All mappings provide precise city coordinates and country information
+//!
+//! ## Timezone Comparison
+//!
+//! Geo debug output shows times in the coordinates' timezone, with the user's local
+//! time in brackets when the two differ. [`offset_differs`] and
+//! [`format_city_time`] are the single place that comparison happens. Offsets are
+//! always compared at one shared instant, and city wall times that DST makes
+//! ambiguous or skips are resolved like everywhere else in sunsetr.
 
 use crate::geo::city_selector::CityInfo;
 use crate::logger::Log;
 use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, NaiveTime, Offset, TimeZone, Utc};
 use chrono_tz::Tz;
 
 /// Detect coordinates based on system timezone.
@@ -134,10 +143,10 @@ pub fn get_system_timezone() -> Result<Tz> {
     // Try multiple methods to detect system timezone
 
     // Method 1: Check TZ environment variable
-    if let Ok(tz_str) = std::env::var("TZ") {
-        if let Ok(tz) = tz_str.parse::<Tz>() {
-            return Ok(tz);
-        }
+    if let Ok(tz_str) = std::env::var("TZ")
+        && let Ok(tz) = tz_str.parse::<Tz>()
+    {
+        return Ok(tz);
     }
 
     // Method 2: Try to read /etc/timezone (Debian/Ubuntu)
@@ -149,14 +158,14 @@ pub fn get_system_timezone() -> Result<Tz> {
     }
 
     // Method 3: Try to read /etc/localtime symlink (most Linux distros)
-    if let Ok(link_target) = std::fs::read_link("/etc/localtime") {
-        if let Some(path_str) = link_target.to_str() {
-            // Extract timezone from path like "/usr/share/zoneinfo/America/New_York"
-            if let Some(tz_part) = path_str.strip_prefix("/usr/share/zoneinfo/") {
-                if let Ok(tz) = tz_part.parse::<Tz>() {
-                    return Ok(tz);
-                }
-            }
+    if let Ok(link_target) = std::fs::read_link("/etc/localtime")
+        && let Some(path_str) = link_target.to_str()
+    {
+        // Extract timezone from path like "/usr/share/zoneinfo/America/New_York"
+        if let Some(tz_part) = path_str.strip_prefix("/usr/share/zoneinfo/")
+            && let Ok(tz) = tz_part.parse::<Tz>()
+        {
+            return Ok(tz);
         }
     }
 
@@ -166,19 +175,89 @@ pub fn get_system_timezone() -> Result<Tz> {
         .arg("--property=Timezone")
         .arg("--value")
         .output()
+        && output.status.success()
     {
-        if output.status.success() {
-            let tz_string = String::from_utf8_lossy(&output.stdout);
-            let tz_str = tz_string.trim();
-            if let Ok(tz) = tz_str.parse::<Tz>() {
-                return Ok(tz);
-            }
+        let tz_string = String::from_utf8_lossy(&output.stdout);
+        let tz_str = tz_string.trim();
+        if let Ok(tz) = tz_str.parse::<Tz>() {
+            return Ok(tz);
         }
     }
 
     anyhow::bail!("Unable to detect system timezone")
 }
 
+/// UTC offset of `tz` at the instant `at`, in seconds east of UTC.
+pub fn utc_offset_seconds<Z: TimeZone>(tz: &Z, at: DateTime<Utc>) -> i32 {
+    at.with_timezone(tz).offset().fix().local_minus_utc()
+}
+
+/// Format a UTC offset in seconds as `+HH:MM`.
+pub fn format_utc_offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.unsigned_abs();
+    format!("{}{:02}:{:02}", sign, seconds / 3600, seconds % 3600 / 60)
+}
+
+/// The instant a wall-clock `time` on `date` occurs in `city_tz`.
+///
+/// Times repeated by a DST fall-back resolve to their first occurrence, and times
+/// skipped by a spring-forward jump to the first minute after it.
+pub fn city_time_to_utc(city_tz: &Tz, date: NaiveDate, time: NaiveTime) -> DateTime<Utc> {
+    crate::time_state::resolve_local_time(city_tz, date.and_time(time)).with_timezone(&Utc)
+}
+
+/// Whether `city_tz` and `local` are at different UTC offsets on `date`.
+///
+/// Both offsets are taken at the same instant, noon on `date` in the city, so zones
+/// that switch DST on different days are compared correctly.
+pub fn offset_differs<L: TimeZone>(city_tz: &Tz, local: &L, date: NaiveDate) -> bool {
+    let noon = city_time_to_utc(city_tz, date, NaiveTime::from_hms_opt(12, 0, 0).unwrap());
+    utc_offset_seconds(city_tz, noon) != utc_offset_seconds(local, noon)
+}
+
+/// Format the instant `at` in `city_tz`, adding the `local` time in brackets when
+/// the offsets differ at that instant, e.g. `"19:30:00 [10:30:00]"`.
+pub fn format_in_city<L: TimeZone>(
+    at: DateTime<Utc>,
+    city_tz: &Tz,
+    local: &L,
+    format_str: &str,
+) -> String
+where
+    L::Offset: std::fmt::Display,
+{
+    let city_time = at.with_timezone(city_tz).format(format_str).to_string();
+    if utc_offset_seconds(city_tz, at) == utc_offset_seconds(local, at) {
+        city_time
+    } else {
+        format!(
+            "{} [{}]",
+            city_time,
+            at.with_timezone(local).format(format_str)
+        )
+    }
+}
+
+/// Format a city wall-clock `time` on `date` like [`format_in_city`].
+pub fn format_city_time<L: TimeZone>(
+    time: NaiveTime,
+    city_tz: &Tz,
+    local: &L,
+    date: NaiveDate,
+    format_str: &str,
+) -> String
+where
+    L::Offset: std::fmt::Display,
+{
+    format_in_city(
+        city_time_to_utc(city_tz, date, time),
+        city_tz,
+        local,
+        format_str,
+    )
+}
+
 /// Get city information directly from timezone string
 /// This provides accurate city data eliminating the need for coordinate approximation and distance calculations
 fn get_city_from_timezone(tz_str: &str) -> Option<CityInfo> {
@@ -2987,6 +3066,100 @@ fn get_city_from_timezone(tz_str: &str) -> Option<CityInfo> {
 mod tests {
     use super::*;
 
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_city_east_of_utc_with_user_west_across_dst() {
+        let city: Tz = "Asia/Tokyo".parse().unwrap();
+        let local: Tz = "America/New_York".parse().unwrap();
+
+        // New York springs forward on 2024-03-10 at 07:00 UTC; Tokyo has no DST.
+        // 19:00 in Tokyo is 10:00 UTC, so the local time moves from EST to EDT.
+        assert!(offset_differs(&city, &local, date(2024, 3, 9)));
+        assert_eq!(
+            format_city_time(time(19, 0), &city, &local, date(2024, 3, 9), "%H:%M"),
+            "19:00 [05:00]"
+        );
+        assert_eq!(
+            format_city_time(time(19, 0), &city, &local, date(2024, 3, 10), "%H:%M"),
+            "19:00 [06:00]"
+        );
+
+        // Early morning in Tokyo is still the previous day in New York
+        assert_eq!(
+            format_city_time(time(5, 30), &city, &local, date(2024, 3, 11), "%H:%M"),
+            "05:30 [16:30]"
+        );
+    }
+
+    #[test]
+    fn test_city_west_of_utc_with_user_east_across_dst() {
+        let city: Tz = "America/New_York".parse().unwrap();
+        let local: Tz = "Europe/Berlin".parse().unwrap();
+
+        // Between the US (March 10) and EU (March 31) switches the gap is 5 hours, not 6
+        assert_eq!(
+            format_city_time(time(19, 0), &city, &local, date(2024, 3, 5), "%H:%M"),
+            "19:00 [01:00]"
+        );
+        assert_eq!(
+            format_city_time(time(19, 0), &city, &local, date(2024, 3, 20), "%H:%M"),
+            "19:00 [00:00]"
+        );
+        assert_eq!(
+            format_city_time(time(19, 0), &city, &local, date(2024, 4, 1), "%H:%M"),
+            "19:00 [01:00]"
+        );
+
+        // A wall time repeated by the fall-back resolves to its first (EDT) occurrence
+        let utc = Tz::UTC;
+        assert_eq!(
+            format_city_time(time(1, 30), &city, &utc, date(2024, 11, 3), "%H:%M"),
+            "01:30 [05:30]"
+        );
+        // A wall time skipped by the spring-forward moves past the jump
+        assert_eq!(
+            city_time_to_utc(&city, date(2024, 3, 10), time(2, 30)),
+            date(2024, 3, 10).and_time(time(7, 0)).and_utc()
+        );
+    }
+
+    #[test]
+    fn test_offset_differs_follows_dst() {
+        let phoenix: Tz = "America/Phoenix".parse().unwrap();
+        let los_angeles: Tz = "America/Los_Angeles".parse().unwrap();
+
+        // Phoenix stays on MST, which matches Los Angeles only while it's on PDT
+        assert!(!offset_differs(&phoenix, &los_angeles, date(2024, 7, 1)));
+        assert!(offset_differs(&phoenix, &los_angeles, date(2024, 1, 15)));
+        assert_eq!(
+            format_city_time(
+                time(19, 0),
+                &phoenix,
+                &los_angeles,
+                date(2024, 7, 1),
+                "%H:%M"
+            ),
+            "19:00"
+        );
+        assert!(!offset_differs(&phoenix, &phoenix, date(2024, 1, 15)));
+    }
+
+    #[test]
+    fn test_format_utc_offset() {
+        assert_eq!(format_utc_offset(0), "+00:00");
+        assert_eq!(format_utc_offset(5 * 3600 + 45 * 60), "+05:45");
+        assert_eq!(format_utc_offset(-(3 * 3600 + 30 * 60)), "-03:30");
+        // Sub-hour negative offsets keep their sign
+        assert_eq!(format_utc_offset(-30 * 60), "-00:30");
+    }
+
     #[test]
     fn test_timezone_city_mapping() {
         // Test some common timezones with new comprehensive mapping
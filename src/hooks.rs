@@ -0,0 +1,234 @@
+//! User commands run when the day/night state changes.
+//!
+//! `on_day_command`, `on_night_command` and `on_transition_start` are run with
+//! `sh -c` whenever the main loop observes the corresponding state change. The
+//! values of the period being entered are passed as environment variables:
+//!
+//! - `SUNSETR_EVENT`: `day`, `night`, `sunset` or `sunrise` (the latter two for
+//!   `on_transition_start`)
+//! - `SUNSETR_TEMP`: color temperature in Kelvin
+//! - `SUNSETR_GAMMA`: gamma percentage
+//!
+//! Hooks never block the main loop: each command is spawned in its own process
+//! group with null stdio, and a short-lived thread waits for it so finished
+//! hooks are reaped instead of lingering as zombies.
+
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, Stdio};
+
+use crate::config::Config;
+use crate::logger::Log;
+use crate::time_state::{TimeState, TransitionState, get_initial_values_for_state};
+
+/// State change that can trigger a hook.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HookEvent {
+    /// Day has fully begun
+    Day,
+    /// Night has fully begun
+    Night,
+    /// A transition towards the given period has started
+    TransitionStart(TimeState),
+}
+
+impl HookEvent {
+    /// The event caused by moving from `previous` to `current`, if any.
+    ///
+    /// Stable states fire when first entered, whether after a transition or an
+    /// instant switch. Transitions fire when they start, or when their target
+    /// changes (e.g. after a config reload).
+    pub fn for_change(previous: TransitionState, current: TransitionState) -> Option<Self> {
+        if previous == current {
+            return None;
+        }
+
+        match (previous, current) {
+            (TransitionState::Stable(before), TransitionState::Stable(after))
+                if before == after =>
+            {
+                None
+            }
+            (_, TransitionState::Stable(TimeState::Day)) => Some(HookEvent::Day),
+            (_, TransitionState::Stable(TimeState::Night)) => Some(HookEvent::Night),
+            (
+                TransitionState::Transitioning { to: before, .. },
+                TransitionState::Transitioning { to: after, .. },
+            ) if before == after => None,
+            (_, TransitionState::Transitioning { to, .. }) => Some(HookEvent::TransitionStart(to)),
+        }
+    }
+
+    /// Name of the config key holding this event's command.
+    pub fn config_key(self) -> &'static str {
+        match self {
+            HookEvent::Day => "on_day_command",
+            HookEvent::Night => "on_night_command",
+            HookEvent::TransitionStart(_) => "on_transition_start",
+        }
+    }
+
+    /// Value passed to the hook in `SUNSETR_EVENT`.
+    pub fn name(self) -> &'static str {
+        match self {
+            HookEvent::Day => "day",
+            HookEvent::Night => "night",
+            HookEvent::TransitionStart(TimeState::Night) => "sunset",
+            HookEvent::TransitionStart(TimeState::Day) => "sunrise",
+        }
+    }
+
+    /// The configured command for this event.
+    pub fn command(self, config: &Config) -> Option<&str> {
+        match self {
+            HookEvent::Day => config.on_day_command.as_deref(),
+            HookEvent::Night => config.on_night_command.as_deref(),
+            HookEvent::TransitionStart(_) => config.on_transition_start.as_deref(),
+        }
+    }
+
+    /// The period whose values are passed to the hook.
+    fn target(self) -> TimeState {
+        match self {
+            HookEvent::Day => TimeState::Day,
+            HookEvent::Night => TimeState::Night,
+            HookEvent::TransitionStart(to) => to,
+        }
+    }
+}
+
+/// Run the hook for the change from `previous` to `current`, if one is configured.
+///
+/// Returns immediately; failures to start the command are logged as warnings.
+pub fn run_on_change(
+    previous: TransitionState,
+    current: TransitionState,
+    config: &Config,
+    debug_enabled: bool,
+) {
+    let Some(event) = HookEvent::for_change(previous, current) else {
+        return;
+    };
+    let Some(command) = event.command(config) else {
+        return;
+    };
+
+    let (temp, gamma) =
+        get_initial_values_for_state(TransitionState::Stable(event.target()), config);
+
+    if debug_enabled {
+        Log::log_pipe();
+        Log::log_debug(&format!(
+            "Running {} ({}K @ {:.1}%): {}",
+            event.config_key(),
+            temp,
+            gamma,
+            command
+        ));
+    }
+
+    match spawn_hook(command, event, temp, gamma) {
+        Ok(child) => reap_in_background(child, event, debug_enabled),
+        Err(e) => {
+            Log::log_pipe();
+            Log::log_warning(&format!("Failed to run {}: {}", event.config_key(), e));
+        }
+    }
+}
+
+/// Start `command` with the event's environment, detached from sunsetr's stdio.
+pub fn spawn_hook(
+    command: &str,
+    event: HookEvent,
+    temp: u32,
+    gamma: f32,
+) -> std::io::Result<Child> {
+    Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("SUNSETR_EVENT", event.name())
+        .env("SUNSETR_TEMP", temp.to_string())
+        .env("SUNSETR_GAMMA", format!("{:.1}", gamma))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        // Keep Ctrl+C in sunsetr's terminal from reaching the hook
+        .process_group(0)
+        .spawn()
+}
+
+/// Wait for a hook in a separate thread so it is reaped without blocking the caller.
+fn reap_in_background(mut child: Child, event: HookEvent, debug_enabled: bool) {
+    std::thread::spawn(move || match child.wait() {
+        Ok(status) if !status.success() => {
+            Log::log_pipe();
+            Log::log_warning(&format!("{} exited with {}", event.config_key(), status));
+        }
+        Ok(_) => {
+            if debug_enabled {
+                Log::log_pipe();
+                Log::log_debug(&format!("{} finished", event.config_key()));
+            }
+        }
+        Err(e) => {
+            Log::log_pipe();
+            Log::log_warning(&format!("Failed to wait for {}: {}", event.config_key(), e));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    fn transitioning(from: TimeState, to: TimeState, progress: f32) -> TransitionState {
+        TransitionState::Transitioning { from, to, progress }
+    }
+
+    #[test]
+    fn test_hook_event_for_change() {
+        let day = TransitionState::Stable(TimeState::Day);
+        let night = TransitionState::Stable(TimeState::Night);
+        let sunset = transitioning(TimeState::Day, TimeState::Night, 0.1);
+
+        assert_eq!(HookEvent::for_change(day, day), None);
+        assert_eq!(
+            HookEvent::for_change(day, sunset),
+            Some(HookEvent::TransitionStart(TimeState::Night))
+        );
+        // Progress updates within the same transition don't fire again
+        assert_eq!(
+            HookEvent::for_change(sunset, transitioning(TimeState::Day, TimeState::Night, 0.5)),
+            None
+        );
+        assert_eq!(HookEvent::for_change(sunset, night), Some(HookEvent::Night));
+        // Instant switches fire the stable hook directly
+        assert_eq!(HookEvent::for_change(night, day), Some(HookEvent::Day));
+
+        assert_eq!(
+            HookEvent::TransitionStart(TimeState::Night).name(),
+            "sunset"
+        );
+        assert_eq!(HookEvent::TransitionStart(TimeState::Day).name(), "sunrise");
+    }
+
+    #[test]
+    fn test_spawn_hook_passes_environment_without_blocking() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("hook.out");
+        let command = format!(
+            "sleep 0.3; echo \"$SUNSETR_EVENT $SUNSETR_TEMP $SUNSETR_GAMMA\" > '{}'",
+            out.display()
+        );
+
+        let started = Instant::now();
+        let mut child = spawn_hook(&command, HookEvent::Night, 3300, 90.0).unwrap();
+        assert!(started.elapsed() < Duration::from_millis(300));
+
+        assert!(child.wait().unwrap().success());
+        assert_eq!(
+            std::fs::read_to_string(&out).unwrap().trim(),
+            "night 3300 90.0"
+        );
+    }
+}
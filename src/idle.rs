@@ -0,0 +1,208 @@
+//! Extra dimming while the session is idle, using ext-idle-notify-v1.
+//!
+//! With the `idle` feature enabled and `idle_timeout` set, sunsetr asks the
+//! compositor to report when the seat has seen no input for that many seconds. The
+//! compositor sends `idled` once the timeout passes and `resumed` on the next input,
+//! which reach the main loop as [`SignalMessage::Idle`](crate::signals::SignalMessage::Idle).
+//! The main loop then fades gamma down to `idle_gamma` percent of the scheduled value,
+//! or back up to it.
+//!
+//! Idle inhibitors (video players, presentation tools) are respected, so the screen
+//! isn't dimmed while one is active. Like the output power monitor, this runs in its
+//! own thread with its own Wayland connection, so it never contends with the backend.
+
+use std::sync::mpsc::Sender;
+
+use wayland_client::{
+    Connection, Dispatch, EventQueue, Proxy, QueueHandle,
+    protocol::{wl_registry::WlRegistry, wl_seat::WlSeat},
+};
+use wayland_protocols::ext::idle_notify::v1::client::{
+    ext_idle_notification_v1::{Event as IdleEvent, ExtIdleNotificationV1},
+    ext_idle_notifier_v1::ExtIdleNotifierV1,
+};
+
+use crate::logger::Log;
+use crate::signals::SignalMessage;
+
+/// Dispatch state for the monitor's event queue.
+struct IdleMonitor {
+    notifier: Option<ExtIdleNotifierV1>,
+    seat: Option<WlSeat>,
+    /// Idle state changes since the queue was last drained, oldest first
+    changes: Vec<bool>,
+}
+
+/// Start watching for session idle in a background thread.
+///
+/// Every change between idle and active is sent to the main loop through `sender`.
+/// Failing to start is reported as a warning, since `idle_timeout` was set on purpose.
+pub fn start_monitor(sender: Sender<SignalMessage>, timeout_secs: u64, debug_enabled: bool) {
+    let (event_queue, monitor) = match connect(timeout_secs) {
+        Ok(setup) => setup,
+        Err(e) => {
+            Log::log_pipe();
+            Log::log_warning(&format!("Idle dimming is unavailable: {}", e));
+            return;
+        }
+    };
+
+    if debug_enabled {
+        Log::log_pipe();
+        Log::log_debug(&format!(
+            "Watching for {}s of session idle to dim the screen",
+            timeout_secs
+        ));
+    }
+
+    std::thread::spawn(move || run(event_queue, monitor, sender, debug_enabled));
+}
+
+/// Connect to the compositor and request an idle notification for the first seat.
+fn connect(timeout_secs: u64) -> anyhow::Result<(EventQueue<IdleMonitor>, IdleMonitor)> {
+    let connection = Connection::connect_to_env()?;
+    let mut event_queue = connection.new_event_queue();
+    let qh = event_queue.handle();
+    let _registry = connection.display().get_registry(&qh, ());
+
+    let mut monitor = IdleMonitor {
+        notifier: None,
+        seat: None,
+        changes: Vec::new(),
+    };
+    event_queue.roundtrip(&mut monitor)?;
+
+    let Some(notifier) = &monitor.notifier else {
+        anyhow::bail!("the compositor doesn't support ext-idle-notify-v1");
+    };
+    let Some(seat) = &monitor.seat else {
+        anyhow::bail!("the compositor didn't announce a seat");
+    };
+    notifier.get_idle_notification(timeout_millis(timeout_secs), seat, &qh, ());
+    event_queue.roundtrip(&mut monitor)?;
+
+    Ok((event_queue, monitor))
+}
+
+/// The protocol's timeout in milliseconds, saturating at its 32-bit limit.
+fn timeout_millis(timeout_secs: u64) -> u32 {
+    u32::try_from(timeout_secs.saturating_mul(1000)).unwrap_or(u32::MAX)
+}
+
+fn run(
+    mut event_queue: EventQueue<IdleMonitor>,
+    mut monitor: IdleMonitor,
+    sender: Sender<SignalMessage>,
+    debug_enabled: bool,
+) {
+    loop {
+        if let Err(e) = event_queue.blocking_dispatch(&mut monitor) {
+            if debug_enabled {
+                Log::log_pipe();
+                Log::log_debug(&format!("Idle monitor stopped: {}", e));
+            }
+            return;
+        }
+
+        for idle in monitor.changes.drain(..) {
+            if debug_enabled {
+                Log::log_pipe();
+                Log::log_debug(if idle {
+                    "Session went idle"
+                } else {
+                    "Session resumed from idle"
+                });
+            }
+
+            // The main loop has gone away, so there's nobody left to notify
+            if sender.send(SignalMessage::Idle(idle)).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+impl Dispatch<WlRegistry, ()> for IdleMonitor {
+    fn event(
+        state: &mut Self,
+        registry: &WlRegistry,
+        event: <WlRegistry as Proxy>::Event,
+        _: &(),
+        _: &Connection,
+        qh: &QueueHandle<Self>,
+    ) {
+        use wayland_client::protocol::wl_registry::Event;
+
+        if let Event::Global {
+            name,
+            interface,
+            version,
+        } = event
+        {
+            match interface.as_str() {
+                "ext_idle_notifier_v1" => {
+                    state.notifier = Some(registry.bind(name, version.min(1), qh, ()));
+                }
+                // Input of any seat counts as activity, so the first one is enough
+                "wl_seat" if state.seat.is_none() => {
+                    state.seat = Some(registry.bind(name, version.min(1), qh, ()));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Dispatch<ExtIdleNotifierV1, ()> for IdleMonitor {
+    fn event(
+        _: &mut Self,
+        _: &ExtIdleNotifierV1,
+        _: <ExtIdleNotifierV1 as Proxy>::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        // No events for the notifier
+    }
+}
+
+impl Dispatch<WlSeat, ()> for IdleMonitor {
+    fn event(
+        _: &mut Self,
+        _: &WlSeat,
+        _: <WlSeat as Proxy>::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        // Seat capabilities don't matter for idle notifications
+    }
+}
+
+impl Dispatch<ExtIdleNotificationV1, ()> for IdleMonitor {
+    fn event(
+        state: &mut Self,
+        _: &ExtIdleNotificationV1,
+        event: IdleEvent,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        match event {
+            IdleEvent::Idled => state.changes.push(true),
+            IdleEvent::Resumed => state.changes.push(false),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeout_millis() {
+        assert_eq!(timeout_millis(300), 300_000);
+        assert_eq!(timeout_millis(u64::MAX), u32::MAX);
+    }
+}
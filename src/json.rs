@@ -0,0 +1,265 @@
+//! Machine-readable command output for `--json`.
+//!
+//! With `--json`, the decorated console output is switched off and every command
+//! prints a single JSON object on stdout when it finishes:
+//!
+//! ```text
+//! {"command":"nudge","ok":true,"kelvin":-300}
+//! {"command":"reload","ok":false,"error":"No running sunsetr instance"}
+//! ```
+//!
+//! `command` and `ok` are always present and `error` only when the command failed.
+//! Commands add their own fields with [`field`] while they run, which does nothing
+//! without `--json`. A configured log file still receives the regular log lines.
+//!
+//! The objects are flat apart from a few arrays, so they are written by hand rather
+//! than pulling in a JSON library.
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::logger::Log;
+
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+static FIELDS: Mutex<Vec<(String, JsonValue)>> = Mutex::new(Vec::new());
+
+/// A JSON value as emitted by `--json`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// Append the JSON text of this value to `out`.
+    fn write(&self, out: &mut String) {
+        match self {
+            JsonValue::Null => out.push_str("null"),
+            JsonValue::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+            JsonValue::Int(value) => out.push_str(&value.to_string()),
+            // JSON has no NaN or infinity
+            JsonValue::Float(value) if !value.is_finite() => out.push_str("null"),
+            JsonValue::Float(value) => out.push_str(&value.to_string()),
+            JsonValue::String(value) => write_string(value, out),
+            JsonValue::Array(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    item.write(out);
+                }
+                out.push(']');
+            }
+            JsonValue::Object(entries) => {
+                out.push('{');
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    write_string(key, out);
+                    out.push(':');
+                    value.write(out);
+                }
+                out.push('}');
+            }
+        }
+    }
+
+    /// The JSON text of this value.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        self.write(&mut out);
+        out
+    }
+}
+
+/// Append `value` as a quoted JSON string to `out`.
+fn write_string(value: &str, out: &mut String) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if u32::from(c) < 0x20 => out.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+impl From<bool> for JsonValue {
+    fn from(value: bool) -> Self {
+        JsonValue::Bool(value)
+    }
+}
+
+impl From<i32> for JsonValue {
+    fn from(value: i32) -> Self {
+        JsonValue::Int(i64::from(value))
+    }
+}
+
+impl From<u32> for JsonValue {
+    fn from(value: u32) -> Self {
+        JsonValue::Int(i64::from(value))
+    }
+}
+
+impl From<u16> for JsonValue {
+    fn from(value: u16) -> Self {
+        JsonValue::Int(i64::from(value))
+    }
+}
+
+impl From<u64> for JsonValue {
+    fn from(value: u64) -> Self {
+        i64::try_from(value).map_or(JsonValue::Float(value as f64), JsonValue::Int)
+    }
+}
+
+impl From<usize> for JsonValue {
+    fn from(value: usize) -> Self {
+        JsonValue::from(value as u64)
+    }
+}
+
+impl From<f32> for JsonValue {
+    fn from(value: f32) -> Self {
+        // Go through the shortest decimal form so 0.9 stays 0.9 instead of 0.8999999761581421
+        JsonValue::Float(value.to_string().parse().unwrap_or(f64::NAN))
+    }
+}
+
+impl From<f64> for JsonValue {
+    fn from(value: f64) -> Self {
+        JsonValue::Float(value)
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(value: &str) -> Self {
+        JsonValue::String(value.to_string())
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(value: String) -> Self {
+        JsonValue::String(value)
+    }
+}
+
+impl<T: Into<JsonValue>> From<Option<T>> for JsonValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(JsonValue::Null, Into::into)
+    }
+}
+
+impl<T: Into<JsonValue>> From<Vec<T>> for JsonValue {
+    fn from(values: Vec<T>) -> Self {
+        JsonValue::Array(values.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Switch to JSON output for the rest of the process.
+pub fn enable() {
+    JSON_OUTPUT.store(true, Ordering::SeqCst);
+    Log::set_json_output(true);
+}
+
+/// Whether `--json` is active.
+pub fn is_enabled() -> bool {
+    JSON_OUTPUT.load(Ordering::SeqCst)
+}
+
+/// Add a field to the command's result object, replacing an earlier value for `key`.
+///
+/// Does nothing without `--json`.
+pub fn field(key: &str, value: impl Into<JsonValue>) {
+    if !is_enabled() {
+        return;
+    }
+    if let Ok(mut fields) = FIELDS.lock() {
+        let value = value.into();
+        match fields.iter_mut().find(|(k, _)| k == key) {
+            Some(entry) => entry.1 = value,
+            None => fields.push((key.to_string(), value)),
+        }
+    }
+}
+
+/// Build the result object for `command` from its outcome and the recorded fields.
+pub fn result_object(
+    command: &str,
+    result: &anyhow::Result<()>,
+    fields: Vec<(String, JsonValue)>,
+) -> JsonValue {
+    let mut entries = vec![
+        ("command".to_string(), JsonValue::from(command)),
+        ("ok".to_string(), JsonValue::from(result.is_ok())),
+    ];
+    if let Err(e) = result {
+        entries.push(("error".to_string(), JsonValue::from(format!("{:#}", e))));
+    }
+    entries.extend(
+        fields
+            .into_iter()
+            .filter(|(key, _)| !matches!(key.as_str(), "command" | "ok" | "error")),
+    );
+    JsonValue::Object(entries)
+}
+
+/// Print the result object for `command` on stdout.
+pub fn finish(command: &str, result: &anyhow::Result<()>) {
+    let fields = FIELDS
+        .lock()
+        .map(|mut fields| std::mem::take(&mut *fields))
+        .unwrap_or_default();
+    println!("{}", result_object(command, result, fields).render());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_rendering() {
+        let value = JsonValue::Object(vec![
+            ("name".to_string(), JsonValue::from("a \"quoted\"\\path\n")),
+            ("gamma".to_string(), JsonValue::from(0.9f32)),
+            ("temp".to_string(), JsonValue::from(3300u32)),
+            ("missing".to_string(), JsonValue::from(None::<u32>)),
+            ("nan".to_string(), JsonValue::from(f64::NAN)),
+            ("list".to_string(), JsonValue::from(vec![true, false])),
+            ("control".to_string(), JsonValue::from("\u{1}")),
+        ]);
+        assert_eq!(
+            value.render(),
+            r#"{"name":"a \"quoted\"\\path\n","gamma":0.9,"temp":3300,"missing":null,"nan":null,"list":[true,false],"control":"\u0001"}"#
+        );
+    }
+
+    #[test]
+    fn test_result_object() {
+        let fields = vec![("kelvin".to_string(), JsonValue::from(-300))];
+        assert_eq!(
+            result_object("nudge", &Ok(()), fields).render(),
+            r#"{"command":"nudge","ok":true,"kelvin":-300}"#
+        );
+
+        let failed: anyhow::Result<()> = Err(anyhow::anyhow!("inner").context("outer"));
+        assert_eq!(
+            result_object("reload", &failed, Vec::new()).render(),
+            r#"{"command":"reload","ok":false,"error":"outer: inner"}"#
+        );
+    }
+}
@@ -8,27 +8,45 @@
 //!
 //! ## Architecture
 //!
+//! - **appearance**: Following the desktop's light/dark appearance (`portal` feature)
 //! - **backend**: Backend abstraction and implementations (Hyprland and Wayland)
 //! - **config**: Configuration loading, validation, and default generation
 //! - **constants**: Application-wide constants and defaults  
+//! - **hooks**: User commands run when the day/night state changes
+//! - **idle**: Dimming further while the session is idle (`idle` feature)
+//! - **json**: Machine-readable command results for `--json`
 //! - **logger**: Structured logging with visual formatting
+//! - **logind**: Reapplying gamma after session unlock and resume (`logind` feature)
+//! - **metrics**: Prometheus-style metrics endpoint (`metrics` feature)
 //! - **startup_transition**: Smooth transitions when the application starts
+//! - **status**: Runtime status snapshot shared with `--monitor`
 //! - **time_state**: Time-based state calculations and transition logic
 //! - **utils**: Utility functions for interpolation and version handling
 
+pub mod appearance;
 pub mod backend;
 pub mod commands;
 pub mod config;
 pub mod constants;
+pub mod events;
 pub mod geo;
+pub mod hooks;
+#[cfg(feature = "idle")]
+pub mod idle;
+pub mod json;
 pub mod logger;
+pub mod logind;
+pub mod metrics;
 pub mod signals;
 pub mod startup_transition;
+pub mod status;
 pub mod time_state;
 pub mod utils;
 
 // Re-export important types for easier access
 pub use backend::{BackendType, ColorTemperatureBackend, create_backend, detect_backend};
-pub use config::Config;
+pub use config::{Config, EffectiveConfig};
 pub use logger::{Log, LogLevel};
-pub use time_state::{TimeState, TransitionState, get_transition_state, time_until_next_event};
+pub use time_state::{
+    TimeState, TransitionState, current_color, get_transition_state, time_until_next_event,
+};
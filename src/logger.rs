@@ -6,12 +6,146 @@
 //!
 //! The logger supports runtime enable/disable functionality for quiet operation
 //! during automated processes or testing.
+//!
+//! Output can additionally be written to a plain-text log file (`log_file` or
+//! `--log-file`) with timestamps and without box drawing. The file is rotated by
+//! size, keeping a fixed number of older files next to it (`sunsetr.log.1`, ...).
+//! Console output can be turned off once a log file is active.
+//!
+//! Consecutive identical warnings, like a failing apply retried every cycle, are
+//! collapsed into one line with a repeat count, written once the warning changes.
+//!
+//! With `--json` the console output is replaced by one JSON object per command (see
+//! [`crate::json`]), while a log file keeps receiving every line.
 
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use std::sync::atomic::{AtomicBool, Ordering};
 
 // Use an AtomicBool instead of thread_local for thread safety
 static LOGGING_ENABLED: AtomicBool = AtomicBool::new(true);
 
+// Whether output goes to stdout; only ever turned off while a log file is active
+static CONSOLE_ENABLED: AtomicBool = AtomicBool::new(true);
+
+// Set with `--json`, where stdout only carries the command's JSON result
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+static LOG_FILE: Mutex<Option<LogFile>> = Mutex::new(None);
+
+static WARNING_REPEATS: Mutex<WarningRepeats> = Mutex::new(WarningRepeats::new());
+
+/// Tracks the last warning so identical ones in a row are only counted.
+struct WarningRepeats {
+    last: Option<String>,
+    /// Repeats of `last` that were not printed
+    repeats: u32,
+}
+
+impl WarningRepeats {
+    const fn new() -> Self {
+        Self {
+            last: None,
+            repeats: 0,
+        }
+    }
+
+    /// Record a warning. Returns whether to print it, plus the summary of the
+    /// previous warning's repeats when this one ends them.
+    fn record(&mut self, message: &str) -> (bool, Option<String>) {
+        if self.last.as_deref() == Some(message) {
+            self.repeats += 1;
+            return (false, None);
+        }
+        let summary = self.flush();
+        self.last = Some(message.to_string());
+        (true, summary)
+    }
+
+    /// End the current run of repeats, returning its summary line if any were held back.
+    fn flush(&mut self) -> Option<String> {
+        let last = self.last.take()?;
+        let repeats = std::mem::take(&mut self.repeats);
+        (repeats > 0).then(|| format!("{} (repeated {} times)", last, repeats))
+    }
+}
+
+/// Plain-text log file with size-based rotation.
+struct LogFile {
+    path: PathBuf,
+    file: File,
+    /// Bytes in the current file
+    size: u64,
+    max_bytes: u64,
+    /// Number of rotated files to keep besides the current one
+    keep: u32,
+}
+
+impl LogFile {
+    fn open(path: &Path, max_bytes: u64, keep: u32) -> io::Result<Self> {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            size,
+            max_bytes,
+            keep,
+        })
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        let len = line.len() as u64 + 1;
+        if self.size > 0 && self.size + len > self.max_bytes {
+            self.rotate()?;
+        }
+        writeln!(self.file, "{}", line)?;
+        self.size += len;
+        Ok(())
+    }
+
+    /// Shift `path.1` .. `path.{keep-1}` up by one, move the current file to
+    /// `path.1` and start a new one. The oldest file falls off the end.
+    fn rotate(&mut self) -> io::Result<()> {
+        if self.keep == 0 {
+            self.file.set_len(0)?;
+            self.size = 0;
+            return Ok(());
+        }
+
+        let _ = fs::remove_file(rotated_log_path(&self.path, self.keep));
+        for index in (1..self.keep).rev() {
+            let _ = fs::rename(
+                rotated_log_path(&self.path, index),
+                rotated_log_path(&self.path, index + 1),
+            );
+        }
+        fs::rename(&self.path, rotated_log_path(&self.path, 1))?;
+
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+/// Path of the `index`th rotated log file, e.g. `sunsetr.log.2`.
+fn rotated_log_path(path: &Path, index: u32) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
 /// Log level enumeration for categorizing message importance.
 #[derive(Debug)]
 pub enum LogLevel {
@@ -88,6 +222,65 @@ impl Log {
         LOGGING_ENABLED.load(Ordering::SeqCst)
     }
 
+    /// Additionally write all log output to `path`, rotating it once it would grow
+    /// past `max_bytes` and keeping `keep` older files.
+    ///
+    /// Replaces any log file set up earlier.
+    pub fn enable_file(path: &Path, max_bytes: u64, keep: u32) -> io::Result<()> {
+        let log_file = LogFile::open(path, max_bytes, keep)?;
+        if let Ok(mut guard) = LOG_FILE.lock() {
+            *guard = Some(log_file);
+        }
+        Ok(())
+    }
+
+    /// Turn console output on or off.
+    ///
+    /// Console output is only turned off while a log file is active, so messages
+    /// are never dropped entirely. Returns whether the requested state took effect.
+    pub fn set_console_enabled(enabled: bool) -> bool {
+        let has_file = LOG_FILE
+            .lock()
+            .map(|guard| guard.is_some())
+            .unwrap_or(false);
+        if !enabled && !has_file {
+            return false;
+        }
+        CONSOLE_ENABLED.store(enabled, Ordering::SeqCst);
+        true
+    }
+
+    /// Keep log lines off the console because stdout carries JSON (`--json`).
+    ///
+    /// Unlike [`Log::set_enabled`], the log file still receives everything.
+    pub fn set_json_output(enabled: bool) {
+        JSON_OUTPUT.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Whether log lines and other decorations may be printed to the console.
+    pub fn is_console_output() -> bool {
+        CONSOLE_ENABLED.load(Ordering::SeqCst) && !JSON_OUTPUT.load(Ordering::SeqCst)
+    }
+
+    /// Write a message to the console and, with its plain form, to the log file.
+    ///
+    /// `plain` is `None` for purely visual lines (pipes, end markers) that are
+    /// left out of the file.
+    fn emit(console: &str, plain: Option<&str>) {
+        if Self::is_console_output() {
+            println!("{}", console);
+        }
+
+        if let Some(plain) = plain
+            && let Ok(mut guard) = LOG_FILE.lock()
+            && let Some(log_file) = guard.as_mut()
+        {
+            let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+            // Nowhere left to report a failing log file
+            let _ = log_file.write_line(&format!("{} {}", timestamp, plain));
+        }
+    }
+
     /// Main log function with level-based prefixes.
     ///
     /// Outputs messages with appropriate prefixes to indicate severity.
@@ -102,16 +295,54 @@ impl Log {
             return;
         }
 
-        match level {
-            LogLevel::Log => print!("[LOG] "),
-            LogLevel::Warn => print!("[WARN] "),
-            LogLevel::Err => print!("[ERR] "),
-            LogLevel::Crit => print!("[CRIT] "),
-            LogLevel::Info => print!("[INFO] "),
+        // Identical warnings in a row are counted instead of printed; any other
+        // warning, error or info ends the run
+        let (show, summary) = match level {
+            LogLevel::Log => (true, None),
+            LogLevel::Warn => WARNING_REPEATS
+                .lock()
+                .map(|mut repeats| repeats.record(message))
+                .unwrap_or((true, None)),
+            _ => (true, Self::take_warning_repeats()),
+        };
+        if let Some(summary) = summary {
+            let line = format!("[WARN] {}", summary);
+            Self::emit(&line, Some(&line));
+        }
+        if !show {
+            return;
         }
 
-        // Print the message with a newline at the end
-        println!("{}", message);
+        let prefix = match level {
+            LogLevel::Log => "[LOG]",
+            LogLevel::Warn => "[WARN]",
+            LogLevel::Err => "[ERR]",
+            LogLevel::Crit => "[CRIT]",
+            LogLevel::Info => "[INFO]",
+        };
+
+        let line = format!("{} {}", prefix, message);
+        Self::emit(&line, Some(&line));
+    }
+
+    /// End a run of repeated warnings, e.g. once the failure behind them is gone.
+    ///
+    /// Prints the `(repeated N times)` summary if any repeats were held back.
+    pub fn flush_repeated_warnings() {
+        if !Self::is_enabled() {
+            return;
+        }
+        if let Some(summary) = Self::take_warning_repeats() {
+            let line = format!("[WARN] {}", summary);
+            Self::emit(&line, Some(&line));
+        }
+    }
+
+    fn take_warning_repeats() -> Option<String> {
+        WARNING_REPEATS
+            .lock()
+            .ok()
+            .and_then(|mut repeats| repeats.flush())
     }
 
     // ═══ Convenience Methods for Common Log Levels ═══
@@ -156,7 +387,7 @@ impl Log {
         if !Self::is_enabled() {
             return;
         }
-        println!("┣ {}", message);
+        Self::emit(&format!("┣ {}", message), Some(message));
     }
 
     /// Log an indented message for sub-items or details within a block.
@@ -170,7 +401,10 @@ impl Log {
         if !Self::is_enabled() {
             return;
         }
-        println!("┃   {}", message);
+        Self::emit(
+            &format!("┃   {}", message),
+            Some(&format!("    {}", message)),
+        );
     }
 
     /// Log a visual pipe separator for vertical spacing at the *start* of a LogLevel type conceptual block.
@@ -187,7 +421,7 @@ impl Log {
         if !Self::is_enabled() {
             return;
         }
-        println!("┃");
+        Self::emit("┃", None);
     }
 
     /// Log a block start message, initiating a new conceptual block of information.
@@ -204,8 +438,7 @@ impl Log {
         if !Self::is_enabled() {
             return;
         }
-        println!("┃");
-        println!("┣ {}", message);
+        Self::emit(&format!("┃\n┣ {}", message), Some(message));
     }
 
     /// Log the application version header. Typically called once at application start.
@@ -215,7 +448,10 @@ impl Log {
         if !Self::is_enabled() {
             return;
         }
-        println!("┏ sunsetr v{} ━━╸", env!("CARGO_PKG_VERSION"));
+        Self::emit(
+            &format!("┏ sunsetr v{} ━━╸", env!("CARGO_PKG_VERSION")),
+            Some(&format!("sunsetr v{}", env!("CARGO_PKG_VERSION"))),
+        );
     }
 
     /// Log the final termination marker. Always called once at application shutdown.
@@ -225,6 +461,74 @@ impl Log {
         if !Self::is_enabled() {
             return;
         }
-        println!("╹");
+        Self::flush_repeated_warnings();
+        Self::emit("╹", None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_file_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs").join("sunsetr.log");
+        let mut log_file = LogFile::open(&path, 64, 2).unwrap();
+
+        for i in 0..20 {
+            log_file
+                .write_line(&format!("line {:02} of the log", i))
+                .unwrap();
+        }
+
+        // Every file stays within the size limit and only `keep` old files remain
+        for file in [
+            path.clone(),
+            rotated_log_path(&path, 1),
+            rotated_log_path(&path, 2),
+        ] {
+            let len = fs::metadata(&file).unwrap().len();
+            assert!(len > 0 && len <= 64, "{} is {} bytes", file.display(), len);
+        }
+        assert!(!rotated_log_path(&path, 3).exists());
+
+        // The newest lines are in the current file, older ones were rotated out
+        let current = fs::read_to_string(&path).unwrap();
+        assert!(current.ends_with("line 19 of the log\n"));
+        let oldest = fs::read_to_string(rotated_log_path(&path, 2)).unwrap();
+        assert!(!oldest.contains("line 00"));
+    }
+
+    #[test]
+    fn test_warning_repeats_collapse_until_the_message_changes() {
+        let mut repeats = WarningRepeats::new();
+
+        assert_eq!(repeats.record("apply failed"), (true, None));
+        for _ in 0..12 {
+            assert_eq!(repeats.record("apply failed"), (false, None));
+        }
+        assert_eq!(
+            repeats.record("socket gone"),
+            (true, Some("apply failed (repeated 12 times)".to_string()))
+        );
+
+        // A warning that wasn't repeated needs no summary, and flushing starts over
+        assert_eq!(repeats.flush(), None);
+        assert_eq!(repeats.record("socket gone"), (true, None));
+    }
+
+    #[test]
+    fn test_log_file_without_rotated_copies() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sunsetr.log");
+        let mut log_file = LogFile::open(&path, 32, 0).unwrap();
+
+        for i in 0..10 {
+            log_file.write_line(&format!("line {}", i)).unwrap();
+        }
+
+        assert!(fs::metadata(&path).unwrap().len() <= 32);
+        assert!(!rotated_log_path(&path, 1).exists());
     }
 }
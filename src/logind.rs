@@ -0,0 +1,170 @@
+//! Reapply gamma after the session is unlocked or resumes from sleep.
+//!
+//! Some screen lockers and suspend/resume paths reset gamma behind sunsetr's back,
+//! leaving the screen uncorrected until the next scheduled update. With the `logind`
+//! feature enabled and `reapply_on_unlock = true`, sunsetr watches the system bus for
+//! logind's `Session.Unlock` signal and `Manager.PrepareForSleep(false)` (sent on
+//! resume). Each event is delivered to the main loop as
+//! [`SignalMessage::Reapply`](crate::signals::SignalMessage::Reapply), which recomputes
+//! and reapplies the current state immediately, even if the backend thinks it is
+//! already on screen. Without the feature, a resume is only noticed by the wall-clock
+//! jump detection in the main loop, after its next wake-up.
+//!
+//! The bus is watched through a `gdbus monitor` child process rather than a D-Bus
+//! library, keeping the dependency tree small. `gdbus` ships with GLib and is present
+//! on practically every desktop system; it only uses regular match rules, so no extra
+//! privileges are needed on the system bus.
+
+use std::fmt;
+
+/// Session events that warrant reapplying gamma.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionEvent {
+    /// A session was unlocked (`org.freedesktop.login1.Session.Unlock`)
+    Unlock,
+    /// The system resumed from sleep (`PrepareForSleep(false)`)
+    Resume,
+}
+
+impl fmt::Display for SessionEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionEvent::Unlock => write!(f, "unlocked"),
+            SessionEvent::Resume => write!(f, "resumed from sleep"),
+        }
+    }
+}
+
+/// Parse one line of `gdbus monitor` output into a session event.
+///
+/// Lines look like `/org/freedesktop/login1: org.freedesktop.login1.Manager.PrepareForSleep (false,)`.
+/// Unlocks of any session are accepted; reapplying is harmless if it wasn't ours.
+#[cfg_attr(not(feature = "logind"), allow(dead_code))] // Only fed by the logind monitor
+pub fn parse_monitor_line(line: &str) -> Option<SessionEvent> {
+    let (_path, signal) = line.trim().split_once(": ")?;
+    let (member, args) = signal.split_once(' ').unwrap_or((signal, ""));
+
+    match member {
+        "org.freedesktop.login1.Session.Unlock" => Some(SessionEvent::Unlock),
+        // PrepareForSleep(true) is sent before suspending, (false) after resuming
+        "org.freedesktop.login1.Manager.PrepareForSleep" if args.trim() == "(false,)" => {
+            Some(SessionEvent::Resume)
+        }
+        _ => None,
+    }
+}
+
+/// Start watching logind for unlock and resume events in a background thread.
+///
+/// Events are sent to the main loop through `sender`. Failing to start the monitor
+/// is logged and otherwise ignored, since this is a convenience feature.
+#[cfg(feature = "logind")]
+pub fn start_monitor(
+    sender: std::sync::mpsc::Sender<crate::signals::SignalMessage>,
+    debug_enabled: bool,
+) {
+    use crate::logger::Log;
+    use crate::signals::SignalMessage;
+    use std::io::{BufRead, BufReader};
+    use std::os::unix::process::CommandExt;
+    use std::process::{Command, Stdio};
+
+    let mut cmd = Command::new("gdbus");
+    cmd.args(["monitor", "--system", "--dest", "org.freedesktop.login1"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+
+    // Make the monitor die with sunsetr, like the managed hyprsunset process
+    unsafe {
+        cmd.pre_exec(|| {
+            use nix::sys::prctl;
+            use nix::sys::signal::Signal;
+
+            prctl::set_pdeathsig(Signal::SIGTERM)?;
+            Ok(())
+        });
+    }
+
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            Log::log_pipe();
+            Log::log_warning(&format!(
+                "Failed to watch logind for unlock/resume events: {}",
+                e
+            ));
+            Log::log_indented("reapply_on_unlock needs the gdbus tool (part of GLib)");
+            return;
+        }
+    };
+
+    Log::log_decorated("Reapplying gamma after session unlock and resume");
+
+    let Some(stdout) = child.stdout.take() else {
+        let _ = child.kill();
+        return;
+    };
+
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else { break };
+            let Some(event) = parse_monitor_line(&line) else {
+                continue;
+            };
+
+            if debug_enabled {
+                Log::log_pipe();
+                Log::log_debug(&format!("logind event: {}", line.trim()));
+            }
+
+            // The main loop has gone away, so there's nobody left to notify
+            if sender.send(SignalMessage::Reapply(event)).is_err() {
+                break;
+            }
+        }
+
+        let _ = child.kill();
+        let _ = child.wait();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_monitor_line() {
+        assert_eq!(
+            parse_monitor_line(
+                "/org/freedesktop/login1/session/_32: org.freedesktop.login1.Session.Unlock ()"
+            ),
+            Some(SessionEvent::Unlock)
+        );
+        assert_eq!(
+            parse_monitor_line(
+                "/org/freedesktop/login1: org.freedesktop.login1.Manager.PrepareForSleep (false,)"
+            ),
+            Some(SessionEvent::Resume)
+        );
+
+        // Going to sleep, locking and unrelated signals are ignored
+        assert_eq!(
+            parse_monitor_line(
+                "/org/freedesktop/login1: org.freedesktop.login1.Manager.PrepareForSleep (true,)"
+            ),
+            None
+        );
+        assert_eq!(
+            parse_monitor_line(
+                "/org/freedesktop/login1/session/_32: org.freedesktop.login1.Session.Lock ()"
+            ),
+            None
+        );
+        assert_eq!(
+            parse_monitor_line(
+                "Monitoring signals from all objects owned by org.freedesktop.login1"
+            ),
+            None
+        );
+    }
+}
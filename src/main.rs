@@ -31,23 +31,32 @@ use std::{
     time::{Duration, SystemTime},
 };
 
+mod appearance;
 mod args;
 mod backend;
 mod commands;
 mod config;
 mod constants;
+mod events;
 mod geo;
+mod hooks;
+#[cfg(feature = "idle")]
+mod idle;
+mod json;
 mod logger;
+mod logind;
+mod metrics;
 mod signals;
 mod startup_transition;
+mod status;
 mod time_state;
 mod utils;
 
 use crate::signals::setup_signal_handler;
 use crate::utils::{TerminalGuard, cleanup_application};
-use args::{CliAction, ParsedArgs};
-use backend::{create_backend, detect_backend, detect_compositor};
-use config::Config;
+use args::{CliAction, IfRunning, ParsedArgs};
+use backend::{BackendError, create_backend, detect_backend, detect_compositor};
+use config::{Backend, Config, LogVerbosity};
 use constants::*;
 use logger::Log;
 use startup_transition::StartupTransition;
@@ -59,24 +68,124 @@ use time_state::{
 fn main() -> Result<()> {
     // Parse command-line arguments
     let parsed_args = ParsedArgs::from_env();
+    if parsed_args.safe_mode {
+        utils::enable_safe_mode();
+    }
 
-    match parsed_args.action {
+    // Handle --json flag: one result object on stdout instead of decorated logs
+    if parsed_args.json {
+        json::enable();
+        let command = parsed_args.action.name();
+        let result = match parsed_args.action {
+            CliAction::Run { .. } | CliAction::RunGeoSelection { .. } => Err(anyhow::anyhow!(
+                "--json only applies to commands that exit, not to running sunsetr"
+            )),
+            CliAction::ShowHelpDueToError => {
+                Err(anyhow::anyhow!("Invalid arguments, see sunsetr --help"))
+            }
+            action => run_command(action),
+        };
+        json::finish(command, &result);
+        if result.is_err() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    run_command(parsed_args.action)
+}
+
+/// Run the action selected on the command line.
+fn run_command(action: CliAction) -> Result<()> {
+    match action {
         CliAction::ShowVersion => {
             args::display_version_info();
             Ok(())
         }
+        CliAction::ShowBuildInfo => {
+            args::display_build_info();
+            Ok(())
+        }
         CliAction::ShowHelp | CliAction::ShowHelpDueToError => {
             args::display_help();
             Ok(())
         }
-        CliAction::Run { debug_enabled } => {
+        CliAction::Run {
+            debug_enabled,
+            if_running,
+            log_file,
+            backend,
+        } => {
             // Continue with normal application flow
-            run_application(debug_enabled)
+            run_application(debug_enabled, if_running, log_file, backend)
         }
         CliAction::Reload { debug_enabled } => {
             // Handle --reload flag: sends SIGUSR2 to running instance to reload config
             commands::reload::handle_reload_command(debug_enabled)
         }
+        CliAction::Once { debug_enabled } => {
+            // Handle --once flag: apply the current state without the main loop or lock
+            commands::once::handle_once_command(debug_enabled)
+        }
+        CliAction::Migrate { debug_enabled } => {
+            // Handle --migrate flag: move the legacy hypr/ config to the sunsetr/ directory
+            commands::migrate::handle_migrate_command(debug_enabled)
+        }
+        CliAction::CheckHyprsunset { debug_enabled } => {
+            // Handle --check-hyprsunset flag: version detection only, nothing is started
+            commands::check_hyprsunset::handle_check_hyprsunset_command(debug_enabled)
+        }
+        CliAction::WhichConfig { debug_enabled } => {
+            // Handle --which-config flag: show the resolved paths without loading anything
+            commands::which_config::handle_which_config_command(debug_enabled)
+        }
+        CliAction::SelectConfig {
+            debug_enabled,
+            name,
+        } => {
+            // Handle --select-config flag: remember which sunsetr/*.toml to load
+            commands::select_config::handle_select_config_command(name.as_deref(), debug_enabled)
+        }
+        CliAction::Lint {
+            debug_enabled,
+            path,
+        } => {
+            // Handle --lint flag: validate the config without applying anything
+            commands::lint::handle_lint_command(path.as_deref(), debug_enabled)
+        }
+        CliAction::Monitor { debug_enabled } => {
+            // Handle --monitor flag: live dashboard fed by the running instance's status file
+            commands::monitor::handle_monitor_command(debug_enabled)
+        }
+        CliAction::Nudge {
+            debug_enabled,
+            kelvin,
+        } => {
+            // Handle --nudge flag: offsets the running instance's temperature
+            commands::nudge::handle_nudge_command(kelvin, debug_enabled)
+        }
+        CliAction::Hold {
+            debug_enabled,
+            command,
+        } => {
+            // Handle --hold flag: pins the running instance's values for a while
+            commands::hold::handle_hold_command(command, debug_enabled)
+        }
+        CliAction::SetLocation {
+            debug_enabled,
+            lat,
+            lon,
+        } => {
+            // Handle --set-location flag: the non-interactive path of --geo
+            commands::set_location::handle_set_location_command(lat, lon, debug_enabled)
+        }
+        CliAction::SwitchProfile {
+            debug_enabled,
+            name,
+        } => {
+            // Handle --profile flag: asks the running instance to switch profiles
+            commands::profile::handle_profile_command(name.as_deref(), debug_enabled)
+        }
         CliAction::Test {
             debug_enabled,
             temperature,
@@ -85,6 +194,26 @@ fn main() -> Result<()> {
             // Handle --test flag: applies specified temperature/gamma values for testing
             commands::test::handle_test_command(temperature, gamma, debug_enabled)
         }
+        CliAction::Calibrate { debug_enabled } => {
+            // Handle --calibrate flag: preview night temperatures and save the chosen one
+            commands::calibrate::handle_calibrate_command(debug_enabled)
+        }
+        CliAction::Bench {
+            debug_enabled,
+            iterations,
+        } => {
+            // Handle --bench flag: time applies on the detected backend, then restore
+            commands::bench::handle_bench_command(iterations, debug_enabled)
+        }
+        CliAction::DumpGamma {
+            debug_enabled,
+            temperature,
+            gamma,
+            size,
+        } => {
+            // Handle --dump-gamma flag: print the ramp as CSV without touching any display
+            commands::dump_gamma::handle_dump_gamma_command(temperature, gamma, size, debug_enabled)
+        }
         CliAction::RunGeoSelection { debug_enabled } => {
             // Handle --geo flag: delegate to geo module for all logic
             match geo::handle_geo_command(debug_enabled)? {
@@ -112,10 +241,18 @@ fn main() -> Result<()> {
 ///
 /// # Arguments
 /// * `debug_enabled` - Whether debug logging should be enabled
+/// * `if_running` - What to do when another instance already holds the lock
+/// * `log_file` - Log file from `--log-file`, overriding the config
+/// * `backend` - Backend from `--backend`, overriding the config
 ///
 /// # Returns
 /// Result indicating success or failure of the application run
-fn run_application(debug_enabled: bool) -> Result<()> {
+fn run_application(
+    debug_enabled: bool,
+    if_running: IfRunning,
+    log_file: Option<String>,
+    backend: Option<Backend>,
+) -> Result<()> {
     // Show headers once at the application level
     Log::log_version();
 
@@ -125,7 +262,7 @@ fn run_application(debug_enabled: bool) -> Result<()> {
         Log::log_debug("Debug mode enabled - showing detailed backend operations");
     }
 
-    run_application_core(debug_enabled)
+    run_application_core(debug_enabled, if_running, log_file, backend)
 }
 
 /// Core application logic without header display.
@@ -135,11 +272,19 @@ fn run_application(debug_enabled: bool) -> Result<()> {
 ///
 /// # Arguments
 /// * `debug_enabled` - Whether debug logging should be enabled
+/// * `if_running` - What to do when another instance already holds the lock
+/// * `log_file` - Log file from `--log-file`, overriding the config
+/// * `backend` - Backend from `--backend`, overriding the config
 ///
 /// # Returns
 /// Result indicating success or failure of the application run
-fn run_application_core(debug_enabled: bool) -> Result<()> {
-    run_application_core_with_lock(debug_enabled, true)
+fn run_application_core(
+    debug_enabled: bool,
+    if_running: IfRunning,
+    log_file: Option<String>,
+    backend: Option<Backend>,
+) -> Result<()> {
+    run_application_core_with_options(debug_enabled, true, None, if_running, log_file, backend)
 }
 
 fn run_application_core_with_lock(debug_enabled: bool, create_lock: bool) -> Result<()> {
@@ -150,6 +295,24 @@ fn run_application_core_with_lock_and_state(
     debug_enabled: bool,
     create_lock: bool,
     previous_state: Option<time_state::TransitionState>,
+) -> Result<()> {
+    run_application_core_with_options(
+        debug_enabled,
+        create_lock,
+        previous_state,
+        IfRunning::default(),
+        None,
+        None,
+    )
+}
+
+fn run_application_core_with_options(
+    debug_enabled: bool,
+    create_lock: bool,
+    previous_state: Option<time_state::TransitionState>,
+    if_running: IfRunning,
+    log_file: Option<String>,
+    backend_override: Option<Backend>,
 ) -> Result<()> {
     #[cfg(debug_assertions)]
     {
@@ -176,15 +339,45 @@ fn run_application_core_with_lock_and_state(
     let signal_state = setup_signal_handler(debug_enabled)?;
 
     // Load and validate configuration first
-    let config = Config::load()?;
+    let mut config = Config::load()?;
+
+    setup_log_file(&config, log_file.as_deref());
+
+    // --backend replaces the configured backend for this run only
+    if let Some(backend) = backend_override {
+        Log::log_pipe();
+        Log::log_decorated(&format!(
+            "Using backend \"{}\" from --backend",
+            backend.as_str()
+        ));
+        config.backend = Some(backend);
+    }
+
+    // log_verbosity = "debug" works like --debug, but only takes effect at startup
+    let debug_enabled = if !debug_enabled && config.log_verbosity() == LogVerbosity::Debug {
+        Log::log_pipe();
+        Log::log_debug("Debug mode enabled by log_verbosity = \"debug\"");
+        true
+    } else {
+        debug_enabled
+    };
+
+    if utils::xdg_runtime_dir().is_none() {
+        Log::log_pipe();
+        Log::log_warning("XDG_RUNTIME_DIR is not set");
+        Log::log_indented(&format!(
+            "Using {} for runtime files instead",
+            utils::runtime_dir()?.display()
+        ));
+        Log::log_indented("Your session should set it (usually pam_systemd or elogind does)");
+    }
 
     // Detect and validate the backend early
     let backend_type = detect_backend(&config)?;
 
     if create_lock {
         // Create lock file path
-        let runtime_dir = std::env::var("XDG_RUNTIME_DIR").unwrap_or_else(|_| "/tmp".to_string());
-        let lock_path = format!("{}/sunsetr.lock", runtime_dir);
+        let lock_path = utils::lock_file_path()?;
 
         // Open lock file without truncating to preserve existing content
         // This prevents a race condition where File::create() would truncate
@@ -225,8 +418,11 @@ fn run_application_core_with_lock_and_state(
             }
             Err(_) => {
                 // Handle lock conflict with smart validation
-                match handle_lock_conflict(&lock_path) {
-                    Ok(()) => {
+                match handle_lock_conflict(&lock_path, if_running) {
+                    Ok(LockConflictOutcome::Exit) => {
+                        // The running instance was asked to reload; nothing left to do
+                    }
+                    Ok(LockConflictOutcome::Retry) => {
                         // Stale lock removed or cross-compositor cleanup completed
                         // Retry lock acquisition without truncating
                         let mut retry_lock_file = std::fs::OpenOptions::new()
@@ -302,6 +498,14 @@ fn run_sunsetr_main_logic(
     // Log configuration
     config.log_config();
 
+    if config.is_color_shift_disabled() {
+        Log::log_block_start("Day and night values are identical - color shifting is disabled");
+        Log::log_indented("Transitions are skipped until night and day values differ");
+    }
+
+    // Read the desktop appearance before anything derives the initial state from it
+    appearance::init(&config, debug_enabled)?;
+
     Log::log_block_start(&format!("Detected backend: {}", backend_type.name()));
 
     let mut backend = create_backend(backend_type, &config, debug_enabled)?;
@@ -338,6 +542,9 @@ fn run_sunsetr_main_logic(
                 } else if debug_enabled {
                     Log::log_decorated("Successfully reset Wayland gamma");
                 }
+                // Hand the gamma controls back and wait for the compositor to process
+                // that, so nothing else in this process binds them while they're held
+                Box::new(wayland_backend).cleanup(debug_enabled);
             }
             Err(e) => {
                 if debug_enabled {
@@ -351,11 +558,83 @@ fn run_sunsetr_main_logic(
         }
     }
 
+    let metrics = std::sync::Arc::new(metrics::Metrics::new());
+    if let Some(port) = config.metrics_port {
+        #[cfg(feature = "metrics")]
+        metrics::start_server(std::sync::Arc::clone(&metrics), port);
+        #[cfg(not(feature = "metrics"))]
+        {
+            Log::log_pipe();
+            Log::log_warning(&format!(
+                "metrics_port = {} is set, but sunsetr was built without the metrics feature",
+                port
+            ));
+            Log::log_indented(
+                "Rebuild with `cargo build --release --features metrics` to enable it",
+            );
+        }
+    }
+
+    if config.effective().event_fifo {
+        match events::open() {
+            Ok(path) if debug_enabled => {
+                Log::log_pipe();
+                Log::log_debug(&format!("Writing state events to {}", path.display()));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                Log::log_pipe();
+                Log::log_warning(&format!("Event fifo is unavailable: {:#}", e));
+            }
+        }
+    }
+
+    if config.effective().reapply_on_unlock {
+        #[cfg(feature = "logind")]
+        logind::start_monitor(signal_state.signal_sender.clone(), debug_enabled);
+        #[cfg(not(feature = "logind"))]
+        {
+            Log::log_pipe();
+            Log::log_warning(
+                "reapply_on_unlock is enabled, but sunsetr was built without the logind feature",
+            );
+            Log::log_indented(
+                "Rebuild with `cargo build --release --features logind` to enable it",
+            );
+        }
+    }
+
+    #[cfg(feature = "portal")]
+    if appearance::is_appearance_mode(&config) {
+        appearance::start_monitor(signal_state.signal_sender.clone(), debug_enabled);
+    }
+
+    if let Some(timeout) = config.idle_timeout {
+        #[cfg(feature = "idle")]
+        idle::start_monitor(signal_state.signal_sender.clone(), timeout, debug_enabled);
+        #[cfg(not(feature = "idle"))]
+        {
+            Log::log_pipe();
+            Log::log_warning(&format!(
+                "idle_timeout = {} is set, but sunsetr was built without the idle feature",
+                timeout
+            ));
+            Log::log_indented("Rebuild with `cargo build --release --features idle` to enable it");
+        }
+    }
+
+    if backend_type == backend::BackendType::Wayland {
+        backend::wayland::power::start_wake_monitor(
+            signal_state.signal_sender.clone(),
+            debug_enabled,
+        );
+    }
+
     let mut current_transition_state = get_transition_state(&config);
     let mut last_check_time = SystemTime::now();
 
-    // Apply initial settings
-    apply_initial_state(
+    // Apply initial settings, tracking whatever ended up on screen
+    current_transition_state = apply_initial_state(
         &mut backend,
         current_transition_state,
         initial_previous_state,
@@ -365,30 +644,45 @@ fn run_sunsetr_main_logic(
     )?;
 
     // Log solar debug info on startup for geo mode (after initial state is applied)
-    if debug_enabled && config.transition_mode.as_deref() == Some("geo") {
-        if let (Some(lat), Some(lon)) = (config.latitude, config.longitude) {
-            let _ = crate::geo::log_solar_debug_info(lat, lon);
-        }
+    if debug_enabled
+        && matches!(config.current_transition_mode(), Some("geo" | "manual_geo"))
+        && let (Some(lat), Some(lon)) = (config.latitude, config.longitude)
+    {
+        let _ = crate::geo::log_solar_debug_info(
+            lat,
+            lon,
+            crate::geo::solar::SolarPrecision::from_config(&config),
+            crate::geo::solar::NightBoundary::from_config(&config),
+            config.effective().geo_round_to_minutes,
+        );
     }
 
-    // Main application loop
-    run_main_loop(
+    // Main application loop. SIGTERM/SIGINT/SIGHUP clear the running flag and queue a
+    // Shutdown message, so an in-flight apply finishes and the loop wakes right away.
+    let loop_result = run_main_loop(
         &mut backend,
         &mut current_transition_state,
         &mut last_check_time,
         &mut config,
         signal_state,
+        &metrics,
         debug_enabled,
-    )?;
+    );
 
-    // Ensure proper cleanup on shutdown
+    // Ensure proper cleanup on shutdown, even when the loop ended with an error
     Log::log_block_start("Shutting down sunsetr...");
+    status::remove_status();
+    events::close();
+    let reset_on_exit = match loop_result {
+        Ok(LoopExit::BackendLost) => config.reset_on_fatal_exit(),
+        _ => config.effective().reset_on_exit,
+    };
     if let Some((lock_file, lock_path)) = lock_info {
-        cleanup_application(backend, lock_file, &lock_path, debug_enabled);
+        cleanup_application(backend, lock_file, &lock_path, reset_on_exit, debug_enabled);
     } else {
         // No lock file to clean up (geo selection restart case)
         let running = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
-        if let Err(e) = backend.apply_temperature_gamma(6500, 100.0, &running) {
+        if reset_on_exit && let Err(e) = backend.apply_temperature_gamma(6500, 100.0, &running) {
             Log::log_decorated(&format!(
                 "Warning: Failed to reset color temperature: {}",
                 e
@@ -398,7 +692,16 @@ fn run_sunsetr_main_logic(
     }
     Log::log_end();
 
-    Ok(())
+    loop_result.map(|_| ())
+}
+
+/// Why the main loop ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LoopExit {
+    /// Shutdown was requested
+    Shutdown,
+    /// The backend became permanently unavailable
+    BackendLost,
 }
 
 /// Apply the initial state when starting the application.
@@ -420,31 +723,50 @@ fn apply_initial_state(
     config: &Config,
     running: &std::sync::Arc<std::sync::atomic::AtomicBool>,
     debug_enabled: bool,
-) -> Result<()> {
+) -> Result<TransitionState> {
     if !running.load(Ordering::SeqCst) {
-        return Ok(());
+        return Ok(current_state);
     }
 
     // Note: No reset needed here - backends should start with correct interpolated values
     // Cross-backend reset (if needed) is handled separately before this function
 
-    // Check if startup transition is enabled and we're not using Hyprland backend
-    // Hyprland (hyprsunset) has its own forced startup transition, so we skip ours
-    let is_hyprland = backend.backend_name().to_lowercase() == "hyprland";
-    let startup_transition = config
-        .startup_transition
-        .unwrap_or(DEFAULT_STARTUP_TRANSITION);
-    let startup_duration = config
-        .startup_transition_duration
-        .unwrap_or(DEFAULT_STARTUP_TRANSITION_DURATION);
-
-    if startup_transition && startup_duration > 0 && !is_hyprland {
+    // On initial startup, optionally give the compositor a moment to become ready.
+    // Right after login the first gamma apply can otherwise be silently dropped.
+    if previous_state.is_none() {
+        let startup_delay_ms = config.effective().startup_delay_ms;
+        if startup_delay_ms > 0 {
+            if debug_enabled {
+                Log::log_pipe();
+                Log::log_debug(&format!(
+                    "Waiting {}ms before applying initial state",
+                    startup_delay_ms
+                ));
+            }
+            sleep_while_running(Duration::from_millis(startup_delay_ms), running);
+            if !running.load(Ordering::SeqCst) {
+                return Ok(current_state);
+            }
+        }
+    }
+
+    // Check if startup transition is enabled and the backend doesn't animate startup itself.
+    // A hyprsunset started by us has its own forced startup transition, so we skip ours.
+    let backend_transitions_itself = backend.has_own_startup_transition();
+    let effective = config.effective();
+    let startup_transition = effective.startup_transition;
+    let startup_duration = effective.startup_transition_duration;
+
+    if startup_transition && startup_duration > 0 && !backend_transitions_itself {
         // Create transition based on whether we have a previous state
         let mut transition = if let Some(prev_state) = previous_state {
             // Config reload: transition from previous state values to new state
             let (start_temp, start_gamma) =
                 time_state::get_initial_values_for_state(prev_state, config);
             StartupTransition::new_from_values(start_temp, start_gamma, current_state, config)
+        } else if let Some((start_temp, start_gamma)) = backend.current_values() {
+            // Initial startup: continue from what's on screen, e.g. a running hyprsunset
+            StartupTransition::new_from_values(start_temp, start_gamma, current_state, config)
         } else {
             // Initial startup: use default transition (from day values)
             StartupTransition::new(current_state, config)
@@ -465,7 +787,32 @@ fn apply_initial_state(
         apply_immediate_state(backend, current_state, config, running, debug_enabled)?;
     }
 
-    Ok(())
+    // The delay and the startup transition take a while, so starting right at a
+    // boundary can leave the state captured above on the wrong side of it. Catch up
+    // now instead of leaving the main loop to notice a full update interval later.
+    let now_state = get_transition_state(config);
+    if !time_state::crossed_state_boundary(current_state, now_state)
+        || !running.load(Ordering::SeqCst)
+    {
+        return Ok(current_state);
+    }
+
+    if debug_enabled {
+        Log::log_pipe();
+        Log::log_debug(&format!(
+            "Crossed a boundary during startup, applying {:?}",
+            now_state
+        ));
+    }
+    match backend.apply_transition_state(now_state, config, running) {
+        Ok(_) => Ok(now_state),
+        Err(e) => {
+            // The main loop compares against the state that is actually on screen,
+            // so it retries this on its first pass
+            Log::log_warning(&format!("Failed to apply the current state: {}", e));
+            Ok(current_state)
+        }
+    }
 }
 
 /// Apply state immediately without smooth transition.
@@ -476,21 +823,54 @@ fn apply_immediate_state(
     running: &std::sync::Arc<std::sync::atomic::AtomicBool>,
     debug_enabled: bool,
 ) -> Result<()> {
-    match backend.apply_startup_state(current_state, config, running) {
-        Ok(_) => {
-            if debug_enabled {
-                Log::log_pipe();
-                Log::log_debug("Initial state applied successfully");
+    // The compositor may not be fully ready right after login, so retry the
+    // first apply a few times with a short delay before giving up.
+    let mut attempt = 0;
+    loop {
+        match backend.apply_startup_state(current_state, config, running) {
+            Ok(_) => {
+                if debug_enabled {
+                    Log::log_pipe();
+                    Log::log_debug("Initial state applied successfully");
+                }
+                break;
+            }
+            Err(e) if attempt < STARTUP_APPLY_MAX_RETRIES && running.load(Ordering::SeqCst) => {
+                attempt += 1;
+                if debug_enabled {
+                    Log::log_pipe();
+                    Log::log_debug(&format!(
+                        "Initial apply failed ({}), retrying ({}/{})...",
+                        e, attempt, STARTUP_APPLY_MAX_RETRIES
+                    ));
+                }
+                sleep_while_running(Duration::from_millis(STARTUP_APPLY_RETRY_DELAY_MS), running);
+            }
+            Err(e) => {
+                Log::log_warning(&format!("Failed to apply initial state: {}", e));
+                Log::log_decorated("Continuing anyway - will retry during operation...");
+                break;
             }
-        }
-        Err(e) => {
-            Log::log_warning(&format!("Failed to apply initial state: {}", e));
-            Log::log_decorated("Continuing anyway - will retry during operation...");
         }
     }
     Ok(())
 }
 
+/// Sleep for up to `duration`, returning early if a shutdown is requested.
+fn sleep_while_running(
+    duration: Duration,
+    running: &std::sync::Arc<std::sync::atomic::AtomicBool>,
+) {
+    let deadline = std::time::Instant::now() + duration;
+    while running.load(Ordering::SeqCst) {
+        let now = std::time::Instant::now();
+        if now >= deadline {
+            break;
+        }
+        std::thread::sleep((deadline - now).min(Duration::from_millis(50)));
+    }
+}
+
 /// Run the main application loop that monitors and applies state changes.
 ///
 /// This loop continuously monitors the time-based state and applies changes
@@ -510,8 +890,9 @@ fn run_main_loop(
     last_check_time: &mut SystemTime,
     config: &mut Config,
     signal_state: &crate::signals::SignalState,
+    metrics: &metrics::Metrics,
     debug_enabled: bool,
-) -> Result<()> {
+) -> Result<LoopExit> {
     // Skip first iteration to prevent false state change detection due to startup timing
     let mut first_iteration = true;
     // Tracks if the initial transition progress log has been made using `log_block_start`.
@@ -521,6 +902,14 @@ fn run_main_loop(
     let mut previous_progress: Option<f32> = None;
     // Track the actual sleep duration used in the previous iteration
     let mut sleep_duration: Option<u64> = None;
+    // Temperature of the last transition update applied here, used to skip updates
+    // that snap to the same value with temp_quantum. Cleared whenever something else
+    // (reloads, signals) may have applied different values.
+    let mut last_applied_temp: Option<u32> = None;
+    // State the hooks last saw, so each day/night/transition change runs its hook once
+    let mut hook_state = *current_transition_state;
+    // Last applied state of each output group, which follow their own schedules
+    let mut group_states = time_state::get_output_group_states(config);
 
     #[cfg(debug_assertions)]
     {
@@ -540,6 +929,7 @@ fn run_main_loop(
 
     // Initialize current state tracking
     let mut current_state = get_transition_state(config);
+    let mut exit = LoopExit::Shutdown;
 
     while signal_state.running.load(Ordering::SeqCst) {
         #[cfg(debug_assertions)]
@@ -550,8 +940,10 @@ fn run_main_loop(
 
         // Process any pending signals immediately (non-blocking check)
         // This ensures signals sent before the loop starts are handled
-        if first_iteration {
-            while let Ok(signal_msg) = signal_state.signal_receiver.try_recv() {
+        if first_iteration && let Ok(first_msg) = signal_state.signal_receiver.try_recv() {
+            for signal_msg in
+                crate::signals::drain_signal_messages(first_msg, &signal_state.signal_receiver)
+            {
                 crate::signals::handle_signal_message(
                     signal_msg,
                     backend,
@@ -560,15 +952,30 @@ fn run_main_loop(
                     &mut current_state,
                 )?;
             }
+            last_applied_temp = None;
+            group_states = time_state::get_output_group_states(config);
         }
 
-        // Check if we need to reload state after config change
-        if signal_state.needs_reload.load(Ordering::SeqCst) {
-            #[cfg(debug_assertions)]
-            eprintln!("DEBUG: Detected needs_reload flag, applying state with startup transition");
+        // A --hold pins the display until it expires, then the schedule takes over again
+        if let Some(hold) = signal_state.take_expired_hold(SystemTime::now()) {
+            Log::log_block_start("Hold expired, returning to scheduled values");
+            crate::signals::end_hold(hold, backend, config, signal_state, &mut current_state);
+            *current_transition_state = current_state;
+            last_applied_temp = None;
+            group_states = time_state::get_output_group_states(config);
+        }
+        let hold = signal_state.active_hold(SystemTime::now());
 
-            // Clear the flag first
-            signal_state.needs_reload.store(false, Ordering::SeqCst);
+        // Check if we need to reload state after config change (held values stay until
+        // the hold ends, which applies the reloaded config anyway)
+        if hold.is_none()
+            && let Some(generation) = signal_state.needs_reload.pending()
+        {
+            #[cfg(debug_assertions)]
+            eprintln!(
+                "DEBUG: Detected reload request {}, applying state with startup transition",
+                generation
+            );
 
             // Get the new state and apply it with startup transition support
             let reload_state = get_transition_state(config);
@@ -581,10 +988,12 @@ fn run_main_loop(
                 &signal_state.running,
                 debug_enabled,
             ) {
-                Ok(_) => {
+                Ok(reload_state) => {
                     // Update our tracking variables
                     *current_transition_state = reload_state;
                     current_state = reload_state;
+                    last_applied_temp = None;
+                    group_states = time_state::get_output_group_states(config);
 
                     Log::log_decorated("Configuration reloaded and state applied successfully");
                 }
@@ -596,6 +1005,10 @@ fn run_main_loop(
                     // Don't update tracking variables if application failed
                 }
             }
+
+            // Only now, so a reload that arrived during the apply stays pending. A failed
+            // apply isn't retried until the next request, as before.
+            signal_state.needs_reload.complete(generation);
         }
 
         // Get current wall clock time for suspend detection
@@ -603,14 +1016,27 @@ fn run_main_loop(
 
         let new_state = get_transition_state(config);
 
-        // Skip first iteration to prevent false state change detection caused by
-        // timing differences between startup state application and main loop start
+        // Output groups are applied along with the main schedule whenever any of them moves
+        let new_group_states = time_state::get_output_group_states(config);
+        let groups_changed = !first_iteration && new_group_states != group_states;
+        if groups_changed {
+            log_output_group_changes(&group_states, &new_group_states);
+        }
+
+        // On the first iteration, progress that drifted since the startup apply is
+        // ignored to prevent false change detection. A crossed boundary is still applied,
+        // so the screen never waits a full cycle in the wrong phase.
         let should_update = if first_iteration {
+            first_iteration = false;
+            let crossed = time_state::crossed_state_boundary(*current_transition_state, new_state);
+
             #[cfg(debug_assertions)]
-            eprintln!("DEBUG: First iteration, skipping state update check");
+            eprintln!(
+                "DEBUG: First iteration, boundary crossed since startup: {}",
+                crossed
+            );
 
-            first_iteration = false;
-            false
+            crossed
         } else {
             let update_needed = should_update_state(
                 current_transition_state,
@@ -632,8 +1058,41 @@ fn run_main_loop(
 
         // Update last check time after state evaluation
         *last_check_time = current_time;
+        let should_update = (should_update || groups_changed) && hold.is_none();
+
+        // A nudge only lasts until the next day/night or transition change
+        if should_update
+            && time_state::temperature_nudge() != 0
+            && hooks::HookEvent::for_change(*current_transition_state, new_state).is_some()
+        {
+            time_state::set_temperature_nudge(0);
+            Log::log_pipe();
+            Log::log_decorated("Temperature nudge cleared");
+        }
+
+        // With temp_quantum, consecutive transition updates often snap to the same
+        // temperature; skip re-applying identical values to avoid redundant protocol traffic
+        let (new_temp, _) = time_state::get_initial_values_for_state(new_state, config);
+        let snapped_unchanged = config.effective().temp_quantum > 1
+            && matches!(
+                (*current_transition_state, new_state),
+                (
+                    TransitionState::Transitioning { .. },
+                    TransitionState::Transitioning { .. }
+                )
+            )
+            && last_applied_temp == Some(new_temp)
+            && !groups_changed;
+
+        if should_update && snapped_unchanged {
+            #[cfg(debug_assertions)]
+            eprintln!(
+                "DEBUG: Snapped temperature unchanged ({}K), skipping apply",
+                new_temp
+            );
 
-        if should_update && signal_state.running.load(Ordering::SeqCst) {
+            *current_transition_state = new_state;
+        } else if should_update && signal_state.running.load(Ordering::SeqCst) {
             #[cfg(debug_assertions)]
             eprintln!("DEBUG: Applying state update - state: {:?}", new_state);
 
@@ -644,47 +1103,96 @@ fn run_main_loop(
                         "DEBUG: State application successful, updating current_transition_state"
                     );
 
-                    // Success - update our state
+                    // Success - update our state, reporting how often a failure repeated
+                    Log::flush_repeated_warnings();
                     *current_transition_state = new_state;
+                    group_states = new_group_states;
+                    last_applied_temp = Some(new_temp);
+                    metrics.record_apply();
                 }
                 Err(e) => {
+                    metrics.record_apply_failure();
+
                     #[cfg(debug_assertions)]
                     eprintln!("DEBUG: State application failed: {}", e);
 
-                    // Failure - check if it's a connection issue that couldn't be resolved
-                    if e.to_string().contains("reconnection attempt") {
-                        Log::log_error(&format!(
-                            "Cannot communicate with {}: {}",
-                            backend.backend_name(),
-                            e
-                        ));
-                        Log::log_decorated(&format!(
-                            "{} appears to be permanently unavailable. Exiting...",
-                            backend.backend_name()
-                        ));
-                        break; // Exit the main loop
-                    } else {
-                        // Other error - just log it and retry next cycle
-                        Log::log_warning(&format!("Failed to apply state: {}", e));
-                        Log::log_decorated("Will retry on next cycle...");
+                    // Failure - exit only if the backend is gone for good
+                    match BackendError::classify(&e) {
+                        BackendError::PermanentlyUnavailable => {
+                            Log::log_error(&format!(
+                                "Cannot communicate with {}: {}",
+                                backend.backend_name(),
+                                e
+                            ));
+                            Log::log_decorated(&format!(
+                                "{} appears to be permanently unavailable. Exiting...",
+                                backend.backend_name()
+                            ));
+                            exit = LoopExit::BackendLost;
+                            break; // Exit the main loop
+                        }
+                        BackendError::ProtocolRejected => {
+                            // Fresh resources were requested, so the retry may succeed
+                            // One line per failure, so repeats collapse into a count
+                            Log::log_warning(&format!(
+                                "Failed to apply state: {}. Will retry on next cycle with new gamma controls...",
+                                e
+                            ));
+                        }
+                        BackendError::TemporarilyUnavailable => {
+                            Log::log_warning(&format!(
+                                "Failed to apply state: {}. Will retry on next cycle...",
+                                e
+                            ));
+                        }
                     }
                     // Don't update current_transition_state - try again next cycle
                 }
             }
         }
 
-        // Calculate sleep duration and log progress
-        let calculated_sleep_duration = calculate_and_log_sleep(
-            new_state,
-            config,
-            &mut first_transition_log_done,
-            debug_enabled,
-            &mut previous_progress,
-        )?;
+        // Run the user's hook for any state change applied above
+        hooks::run_on_change(hook_state, *current_transition_state, config, debug_enabled);
+        hook_state = *current_transition_state;
+
+        // Calculate sleep duration and log progress; a hold only needs to wake when it ends
+        let calculated_sleep_duration = match hold.and_then(|hold| hold.remaining(current_time)) {
+            Some(remaining) => remaining,
+            None => calculate_and_log_sleep(
+                new_state,
+                config,
+                &mut first_transition_log_done,
+                debug_enabled,
+                &mut previous_progress,
+            )?,
+        };
+
+        // Wake for whichever schedule needs attention first
+        let calculated_sleep_duration = config
+            .output_group_configs()
+            .iter()
+            .zip(time_state::get_output_group_states(config))
+            .fold(
+                calculated_sleep_duration,
+                |sleep, ((_, _, group_config), (_, state))| {
+                    sleep.min(sleep_duration_for(state, group_config))
+                },
+            );
 
         // Store the sleep duration for the next iteration's time anomaly detection
         sleep_duration = Some(calculated_sleep_duration.as_secs());
 
+        // Publish the current state for --monitor and the metrics endpoint
+        publish_status(
+            *current_transition_state,
+            calculated_sleep_duration,
+            config,
+            signal_state,
+            hold,
+            metrics,
+            backend.backend_name(),
+        );
+
         // Sleep with signal awareness using recv_timeout
         // This blocks until either a signal arrives or the timeout expires
         use std::sync::mpsc::RecvTimeoutError;
@@ -692,15 +1200,22 @@ fn run_main_loop(
             .signal_receiver
             .recv_timeout(calculated_sleep_duration)
         {
-            Ok(signal_msg) => {
-                // Signal received - handle it immediately
-                crate::signals::handle_signal_message(
-                    signal_msg,
-                    backend,
-                    config,
-                    signal_state,
-                    &mut current_state,
-                )?;
+            Ok(first_msg) => {
+                // Signal received - handle it and anything queued behind it, so a
+                // burst of reload requests only reloads once
+                for signal_msg in
+                    crate::signals::drain_signal_messages(first_msg, &signal_state.signal_receiver)
+                {
+                    crate::signals::handle_signal_message(
+                        signal_msg,
+                        backend,
+                        config,
+                        signal_state,
+                        &mut current_state,
+                    )?;
+                }
+                last_applied_temp = None;
+                group_states = time_state::get_output_group_states(config);
             }
             Err(RecvTimeoutError::Timeout) => {
                 // Normal timeout - continue to next iteration
@@ -741,23 +1256,87 @@ fn run_main_loop(
             });
     }
 
-    Ok(())
+    Ok(exit)
 }
 
-/// Calculate sleep duration and log progress for the main loop.
-/// Returns the duration to sleep.
-fn calculate_and_log_sleep(
-    new_state: TransitionState,
+/// Write the status snapshot read by `--monitor`, update the exported metrics and
+/// send the state to the events fifo.
+///
+/// Failures are not fatal since the status file is purely informational.
+fn publish_status(
+    state: TransitionState,
+    until_next_event: Duration,
     config: &Config,
-    first_transition_log_done: &mut bool,
-    debug_enabled: bool,
-    previous_progress: &mut Option<f32>,
-) -> Result<Duration> {
-    // Determine sleep duration based on state
-    let sleep_duration = match new_state {
+    signal_state: &crate::signals::SignalState,
+    hold: Option<crate::signals::TimedHold>,
+    metrics: &metrics::Metrics,
+    backend_name: &str,
+) {
+    let values = match hold {
+        Some(hold) => (hold.temperature, hold.gamma),
+        None => time_state::get_initial_values_for_state(state, config),
+    };
+    metrics.set_state(state, values, until_next_event);
+    events::publish(state, values);
+    let profile = signal_state
+        .active_profile
+        .lock()
+        .ok()
+        .and_then(|profile| profile.clone())
+        .or_else(|| config.active_profile.clone());
+    let mut snapshot =
+        status::StatusSnapshot::new(state, values, until_next_event, profile, backend_name);
+    snapshot.nudge = Some(time_state::temperature_nudge()).filter(|&nudge| nudge != 0);
+    snapshot.hold_until = hold.map(|hold| status::unix_timestamp(hold.until));
+
+    if let Err(_e) = status::write_status(&snapshot) {
+        #[cfg(debug_assertions)]
+        eprintln!("DEBUG: Failed to write status file: {}", _e);
+    }
+
+    // The next state change: the end of a running transition, or the start of the next one
+    let until_change = match state {
+        TransitionState::Transitioning { .. } => {
+            Some(time_until_transition_end(config).unwrap_or(until_next_event))
+        }
+        // Appearance changes aren't scheduled
+        TransitionState::Stable(_) if appearance::is_appearance_mode(config) => None,
+        TransitionState::Stable(_) => Some(time_until_next_event(config)),
+    };
+    let next = status::NextTransition::new(state, values, until_change);
+    if let Err(_e) = status::write_next_transition(&next) {
+        #[cfg(debug_assertions)]
+        eprintln!("DEBUG: Failed to write next transition file: {}", _e);
+    }
+}
+
+/// Log the day/night and transition changes of output groups.
+fn log_output_group_changes(
+    previous: &[(String, TransitionState)],
+    current: &[(String, TransitionState)],
+) {
+    for ((name, before), (_, after)) in previous.iter().zip(current) {
+        if hooks::HookEvent::for_change(*before, *after).is_none() {
+            continue;
+        }
+        let message = match after {
+            TransitionState::Transitioning { from, to, .. } => format!(
+                "Commencing {}",
+                time_state::get_transition_type_name(*from, *to)
+            ),
+            TransitionState::Stable(state) => {
+                time_state::get_stable_state_message(*state).to_string()
+            }
+        };
+        Log::log_block_start(&format!("Output group '{}': {}", name, message));
+    }
+}
+
+/// How long the main loop may sleep before `state` of the schedule in `config` needs an update.
+fn sleep_duration_for(state: TransitionState, config: &Config) -> Duration {
+    match state {
         TransitionState::Transitioning { .. } => {
-            let update_interval =
-                Duration::from_secs(config.update_interval.unwrap_or(DEFAULT_UPDATE_INTERVAL));
+            let update_interval = Duration::from_secs(config.effective().update_interval);
 
             // Check if we're near the end of the transition
             if let Some(time_remaining) = time_until_transition_end(config) {
@@ -774,7 +1353,19 @@ fn calculate_and_log_sleep(
             }
         }
         TransitionState::Stable(_) => time_until_next_event(config),
-    };
+    }
+}
+
+/// Calculate sleep duration and log progress for the main loop.
+/// Returns the duration to sleep.
+fn calculate_and_log_sleep(
+    new_state: TransitionState,
+    config: &Config,
+    first_transition_log_done: &mut bool,
+    debug_enabled: bool,
+    previous_progress: &mut Option<f32>,
+) -> Result<Duration> {
+    let sleep_duration = sleep_duration_for(new_state, config);
 
     // Show next update timing with more context
     match new_state {
@@ -812,7 +1403,13 @@ fn calculate_and_log_sleep(
             // Update the previous progress for next iteration
             *previous_progress = Some(progress);
 
-            if debug_enabled {
+            if !debug_enabled && config.log_verbosity() == LogVerbosity::Quiet {
+                // Announce the transition once instead of logging its progress
+                if !*first_transition_log_done {
+                    time_state::log_state_announcement(new_state);
+                    *first_transition_log_done = true;
+                }
+            } else if debug_enabled {
                 // In debug mode, always use log_block_start for better visibility
                 Log::log_block_start(&log_message);
             } else if !*first_transition_log_done {
@@ -825,11 +1422,29 @@ fn calculate_and_log_sleep(
             }
         }
         TransitionState::Stable(_) => {
+            // Quiet mode logs nothing else, so say where the transition ended up
+            if *first_transition_log_done
+                && !debug_enabled
+                && config.log_verbosity() == LogVerbosity::Quiet
+            {
+                time_state::log_state_announcement(new_state);
+            }
             *first_transition_log_done = false; // Reset for the next transition period
             *previous_progress = None; // Reset progress tracking for next transition
 
+            // Show what the stable temperature actually does to white, for calibrating
+            if debug_enabled {
+                let (temperature, _) = time_state::get_initial_values_for_state(new_state, config);
+                Log::log_pipe();
+                Log::log_debug(&format!(
+                    "White point at {}K: {}",
+                    temperature,
+                    backend::wayland::gamma::describe_whitepoint(temperature)
+                ));
+            }
+
             // Debug logging for geo mode to show exact transition time
-            if debug_enabled && config.transition_mode.as_deref() == Some("geo") {
+            if debug_enabled && config.current_transition_mode() == Some("geo") {
                 let now = chrono::Local::now();
                 let next_transition_time =
                     now + chrono::Duration::seconds(sleep_duration.as_secs() as i64);
@@ -839,9 +1454,6 @@ fn calculate_and_log_sleep(
                     // Use tzf-rs to get the timezone for these exact coordinates
                     let city_tz = crate::geo::solar::determine_timezone_from_coordinates(lat, lon);
 
-                    // Convert the next transition time to the city's timezone
-                    let next_transition_city_tz = next_transition_time.with_timezone(&city_tz);
-
                     // Determine transition direction based on current state
                     let transition_info = match new_state {
                         TransitionState::Stable(crate::time_state::TimeState::Day) => {
@@ -853,27 +1465,19 @@ fn calculate_and_log_sleep(
                         _ => "Transition", // Fallback for transitioning states
                     };
 
+                    // City time, plus the local time in brackets when the offsets differ
+                    let begins_at = crate::geo::timezone::format_in_city(
+                        next_transition_time.with_timezone(&chrono::Utc),
+                        &city_tz,
+                        &chrono::Local,
+                        "%H:%M:%S",
+                    );
+
                     Log::log_pipe();
-                    // Check if city timezone matches local timezone by comparing offset
-                    use chrono::Offset;
-                    let city_offset = next_transition_city_tz.offset().fix();
-                    let local_offset = next_transition_time.offset().fix();
-                    let same_timezone = city_offset == local_offset;
-
-                    if same_timezone {
-                        Log::log_debug(&format!(
-                            "Next transition will begin at: {} {}",
-                            next_transition_city_tz.format("%H:%M:%S"),
-                            transition_info
-                        ));
-                    } else {
-                        Log::log_debug(&format!(
-                            "Next transition will begin at: {} [{}] {}",
-                            next_transition_city_tz.format("%H:%M:%S"),
-                            next_transition_time.format("%H:%M:%S"),
-                            transition_info
-                        ));
-                    }
+                    Log::log_debug(&format!(
+                        "Next transition will begin at: {} {}",
+                        begins_at, transition_info
+                    ));
                 } else {
                     // This should rarely happen - geo mode without coordinates
                     // means both config coordinates and timezone auto-detection failed
@@ -888,94 +1492,181 @@ fn calculate_and_log_sleep(
                 }
             }
 
-            Log::log_block_start(&format!(
-                "Next transition in {} minutes {} seconds",
-                sleep_duration.as_secs() / 60,
-                sleep_duration.as_secs() % 60
-            ));
+            if appearance::is_appearance_mode(config) {
+                Log::log_block_start("Waiting for the desktop appearance to change");
+            } else {
+                Log::log_block_start(&format!(
+                    "Next transition in {} minutes {} seconds",
+                    sleep_duration.as_secs() / 60,
+                    sleep_duration.as_secs() % 60
+                ));
+            }
         }
     }
 
     Ok(sleep_duration)
 }
 
+/// Start writing logs to the configured file, if any.
+///
+/// `cli_path` (from `--log-file`) takes precedence over `log_file` in the config.
+/// Failing to open the file is reported and otherwise ignored, so logging falls
+/// back to the console.
+fn setup_log_file(config: &Config, cli_path: Option<&str>) {
+    let Some(path) = cli_path.or(config.log_file.as_deref()) else {
+        if !config.effective().log_to_console {
+            Log::log_pipe();
+            Log::log_warning("log_to_console = false has no effect without a log file");
+        }
+        return;
+    };
+    let path = utils::expand_home(path);
+
+    let effective = config.effective();
+    if let Err(e) = Log::enable_file(&path, effective.log_file_max_bytes, effective.log_file_keep) {
+        Log::log_pipe();
+        Log::log_warning(&format!(
+            "Failed to open log file {}: {}",
+            utils::path_for_display(&path),
+            e
+        ));
+        return;
+    }
+
+    Log::log_block_start(&format!(
+        "Writing logs to {}",
+        utils::path_for_display(&path)
+    ));
+    if !config.effective().log_to_console {
+        Log::log_indented("Console output disabled (log_to_console = false)");
+        Log::set_console_enabled(false);
+    }
+}
+
+/// How startup should proceed after [`handle_lock_conflict`] succeeds.
+enum LockConflictOutcome {
+    /// The lock was freed; try to acquire it again
+    Retry,
+    /// The running instance was left in charge; exit successfully
+    Exit,
+}
+
 /// Handle lock file conflicts with smart validation and cleanup
-fn handle_lock_conflict(lock_path: &str) -> Result<()> {
+fn handle_lock_conflict(lock_path: &str, if_running: IfRunning) -> Result<LockConflictOutcome> {
     // Read the lock file to get PID and compositor info
     let lock_content = match std::fs::read_to_string(lock_path) {
         Ok(content) => content,
         Err(_) => {
             // Lock file doesn't exist or can't be read - assume it was cleaned up
-            return Ok(());
+            return Ok(LockConflictOutcome::Retry);
         }
     };
 
-    let lines: Vec<&str> = lock_content.trim().lines().collect();
-
-    if lines.len() != 2 {
-        // Invalid lock file format
-        Log::log_warning("Lock file format invalid, removing");
-        let _ = std::fs::remove_file(lock_path);
-        return Ok(());
-    }
-
-    let pid = match lines[0].parse::<u32>() {
-        Ok(pid) => pid,
-        Err(_) => {
-            Log::log_warning("Lock file contains invalid PID, removing stale lock");
+    // Stale locks are removed, and a live instance is only replaced when it was
+    // started under a different compositor (cross-compositor switch)
+    let current_compositor = detect_compositor().to_string();
+    let lock_info = utils::parse_lock_file(&lock_content);
+
+    let pid = match utils::decide_lock_conflict(
+        lock_info.as_ref(),
+        &current_compositor,
+        utils::is_process_running,
+    ) {
+        utils::LockDecision::RemoveStale => {
+            match lock_info {
+                Some(info) => Log::log_warning(&format!(
+                    "Removing stale lock file (process {} no longer running)",
+                    info.pid
+                )),
+                None => Log::log_warning("Lock file contains invalid PID, removing stale lock"),
+            }
             let _ = std::fs::remove_file(lock_path);
-            return Ok(());
+            return Ok(LockConflictOutcome::Retry);
         }
+        utils::LockDecision::TakeOver {
+            pid,
+            compositor: existing_compositor,
+        } => {
+            // Cross-compositor switch detected - force cleanup
+            Log::log_warning(&format!(
+                "Cross-compositor switch detected: {} → {}",
+                existing_compositor, current_compositor
+            ));
+            terminate_running_instance(pid, lock_path)?;
+            Log::log_warning("Cross-compositor cleanup completed");
+            return Ok(LockConflictOutcome::Retry);
+        }
+        utils::LockDecision::RespectRunning(pid) => pid,
     };
 
-    let existing_compositor = lines[1].to_string();
+    match if_running {
+        IfRunning::Reload => {
+            use nix::sys::signal::{Signal, kill};
+            use nix::unistd::Pid;
 
-    // Check if the process is actually running
-    if !crate::utils::is_process_running(pid) {
-        Log::log_warning(&format!(
-            "Removing stale lock file (process {} no longer running)",
-            pid
-        ));
-        let _ = std::fs::remove_file(lock_path);
-        return Ok(());
+            if let Err(e) = kill(Pid::from_raw(pid as i32), Signal::SIGUSR2) {
+                Log::log_pipe();
+                Log::log_error(&format!(
+                    "Failed to signal running sunsetr instance (PID: {}): {}",
+                    pid, e
+                ));
+                anyhow::bail!("Cannot reload - running instance could not be signaled")
+            }
+            Log::log_block_start(&format!(
+                "sunsetr is already running (PID: {}), sent reload signal",
+                pid
+            ));
+            Log::log_end();
+            Ok(LockConflictOutcome::Exit)
+        }
+        IfRunning::Replace => {
+            Log::log_warning(&format!(
+                "Replacing running sunsetr instance (PID: {})",
+                pid
+            ));
+            terminate_running_instance(pid, lock_path)?;
+            Ok(LockConflictOutcome::Retry)
+        }
+        IfRunning::Error => {
+            // Same compositor - respect single instance enforcement
+            Log::log_pipe();
+            Log::log_error(&format!("sunsetr is already running (PID: {})", pid));
+            Log::log_pipe();
+            Log::log_decorated("Did you mean to:");
+            Log::log_indented("• Reload configuration: sunsetr --reload");
+            Log::log_indented("• Test new values: sunsetr --test <temp> <gamma>");
+            Log::log_indented("• Replace it when starting: sunsetr --if-running=replace");
+            Log::log_pipe();
+            anyhow::bail!("Cannot start - another sunsetr instance is running")
+        }
     }
+}
 
-    // Process is running - check if this is a cross-compositor switch scenario
-    let current_compositor = detect_compositor().to_string();
-
-    if existing_compositor != current_compositor {
-        // Cross-compositor switch detected - force cleanup
-        Log::log_warning(&format!(
-            "Cross-compositor switch detected: {} → {}",
-            existing_compositor, current_compositor
-        ));
-        Log::log_warning(&format!(
-            "Terminating existing sunsetr process (PID: {})",
+/// Terminate the instance holding the lock and remove its lock file.
+fn terminate_running_instance(pid: u32, lock_path: &str) -> Result<()> {
+    if utils::is_safe_mode() {
+        Log::log_pipe();
+        Log::log_info(&format!(
+            "Safe mode: not terminating sunsetr process (PID: {})",
             pid
         ));
+        anyhow::bail!("Cannot start - another sunsetr instance is running (safe mode)")
+    }
 
-        if utils::kill_process(pid) {
-            // Wait for process to fully exit
-            std::thread::sleep(std::time::Duration::from_millis(500));
-
-            // Clean up lock file
-            let _ = std::fs::remove_file(lock_path);
+    Log::log_warning(&format!(
+        "Terminating existing sunsetr process (PID: {})",
+        pid
+    ));
 
-            Log::log_warning("Cross-compositor cleanup completed");
-            return Ok(());
-        } else {
-            Log::log_warning("Failed to terminate existing process");
-            anyhow::bail!("Cannot force cleanup - existing process could not be terminated")
-        }
+    if !utils::kill_process(pid) {
+        Log::log_warning("Failed to terminate existing process");
+        anyhow::bail!("Cannot force cleanup - existing process could not be terminated")
     }
 
-    // Same compositor - respect single instance enforcement
-    Log::log_pipe();
-    Log::log_error(&format!("sunsetr is already running (PID: {})", pid));
-    Log::log_pipe();
-    Log::log_decorated("Did you mean to:");
-    Log::log_indented("• Reload configuration: sunsetr --reload");
-    Log::log_indented("• Test new values: sunsetr --test <temp> <gamma>");
-    Log::log_pipe();
-    anyhow::bail!("Cannot start - another sunsetr instance is running")
+    // Wait for process to fully exit
+    std::thread::sleep(std::time::Duration::from_millis(500));
+
+    // Clean up lock file
+    let _ = std::fs::remove_file(lock_path);
+    Ok(())
 }
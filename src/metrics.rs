@@ -0,0 +1,215 @@
+//! Prometheus-style metrics for home-lab dashboards.
+//!
+//! The main loop records what it applies into a shared [`Metrics`] value made of
+//! atomics. When built with the `metrics` feature and `metrics_port` is set, a small
+//! HTTP server on `127.0.0.1:<port>` serves those values in the Prometheus text
+//! exposition format at `/metrics`. The server only reads the atomics, so it never
+//! blocks or slows down the main loop.
+//!
+//! Exposed metrics:
+//! - `sunsetr_temperature_kelvin` - currently applied color temperature
+//! - `sunsetr_gamma_percent` - currently applied gamma
+//! - `sunsetr_seconds_until_next_event` - time until the next update or transition
+//! - `sunsetr_transition_progress` - progress of the active transition (0 when stable)
+//! - `sunsetr_applies_total` / `sunsetr_apply_failures_total` - apply counters
+
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::status::unix_now;
+use crate::time_state::TransitionState;
+
+/// Values exported by the metrics endpoint, shared between the main loop and the server.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    temperature: AtomicU32,
+    /// f32 bits of the gamma percentage
+    gamma: AtomicU32,
+    /// f32 bits of the transition progress
+    progress: AtomicU32,
+    /// Unix timestamp of the next wake-up
+    next_event: AtomicU64,
+    applies: AtomicU64,
+    apply_failures: AtomicU64,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the state the main loop is currently in and when it will wake next.
+    pub fn set_state(&self, state: TransitionState, values: (u32, f32), until_next: Duration) {
+        let progress = match state {
+            TransitionState::Transitioning { progress, .. } => progress,
+            TransitionState::Stable(_) => 0.0,
+        };
+        self.temperature.store(values.0, Ordering::Relaxed);
+        self.gamma.store(values.1.to_bits(), Ordering::Relaxed);
+        self.progress.store(progress.to_bits(), Ordering::Relaxed);
+        self.next_event
+            .store(unix_now() + until_next.as_secs(), Ordering::Relaxed);
+    }
+
+    /// Count a successful apply.
+    pub fn record_apply(&self) {
+        self.applies.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count a failed apply.
+    pub fn record_apply_failure(&self) {
+        self.apply_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Render all metrics in the Prometheus text exposition format.
+    ///
+    /// `now` is the current Unix timestamp, used for the countdown gauge.
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))] // Only served with the metrics feature
+    pub fn render(&self, now: u64) -> String {
+        let gauges = [
+            (
+                "sunsetr_temperature_kelvin",
+                "Currently applied color temperature in Kelvin",
+                self.temperature.load(Ordering::Relaxed).to_string(),
+            ),
+            (
+                "sunsetr_gamma_percent",
+                "Currently applied gamma in percent",
+                f32::from_bits(self.gamma.load(Ordering::Relaxed)).to_string(),
+            ),
+            (
+                "sunsetr_seconds_until_next_event",
+                "Seconds until the next update or transition",
+                self.next_event
+                    .load(Ordering::Relaxed)
+                    .saturating_sub(now)
+                    .to_string(),
+            ),
+            (
+                "sunsetr_transition_progress",
+                "Progress of the active transition from 0 to 1 (0 when stable)",
+                f32::from_bits(self.progress.load(Ordering::Relaxed)).to_string(),
+            ),
+        ];
+        let counters = [
+            (
+                "sunsetr_applies_total",
+                "Number of successful gamma applies",
+                self.applies.load(Ordering::Relaxed),
+            ),
+            (
+                "sunsetr_apply_failures_total",
+                "Number of failed gamma applies",
+                self.apply_failures.load(Ordering::Relaxed),
+            ),
+        ];
+
+        let mut out = String::new();
+        for (name, help, value) in gauges {
+            out.push_str(&format!(
+                "# HELP {name} {help}\n# TYPE {name} gauge\n{name} {value}\n"
+            ));
+        }
+        for (name, help, value) in counters {
+            out.push_str(&format!(
+                "# HELP {name} {help}\n# TYPE {name} counter\n{name} {value}\n"
+            ));
+        }
+        out
+    }
+}
+
+/// Start the metrics HTTP server on `127.0.0.1:<port>` in a background thread.
+///
+/// Binding failures are logged and otherwise ignored; metrics are never essential.
+#[cfg(feature = "metrics")]
+pub fn start_server(metrics: std::sync::Arc<Metrics>, port: u16) {
+    use crate::logger::Log;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    let listener = match TcpListener::bind(("127.0.0.1", port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            Log::log_pipe();
+            Log::log_warning(&format!(
+                "Failed to start metrics endpoint on 127.0.0.1:{}: {}",
+                port, e
+            ));
+            return;
+        }
+    };
+
+    Log::log_decorated(&format!(
+        "Serving metrics on http://127.0.0.1:{}/metrics",
+        port
+    ));
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            let _ = stream.set_read_timeout(Some(Duration::from_secs(2)));
+
+            // Only the request line matters; anything beyond the first read is ignored
+            let mut buffer = [0u8; 1024];
+            let Ok(read) = stream.read(&mut buffer) else {
+                continue;
+            };
+            let request = String::from_utf8_lossy(&buffer[..read]);
+            let path = request.split_whitespace().nth(1).unwrap_or("/");
+
+            let response = if path == "/metrics" || path == "/" {
+                let body = metrics.render(unix_now());
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+            } else {
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_string()
+            };
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_state::TimeState;
+
+    #[test]
+    fn test_render_prometheus_text() {
+        let metrics = Metrics::new();
+        metrics.set_state(
+            TransitionState::Transitioning {
+                from: TimeState::Day,
+                to: TimeState::Night,
+                progress: 0.5,
+            },
+            (4900, 95.0),
+            Duration::from_secs(60),
+        );
+        metrics.record_apply();
+        metrics.record_apply();
+        metrics.record_apply_failure();
+
+        let text = metrics.render(unix_now());
+        assert!(text.contains(
+            "# TYPE sunsetr_temperature_kelvin gauge\nsunsetr_temperature_kelvin 4900\n"
+        ));
+        assert!(text.contains("sunsetr_gamma_percent 95\n"));
+        assert!(text.contains("sunsetr_transition_progress 0.5\n"));
+        assert!(text.contains("# TYPE sunsetr_applies_total counter\nsunsetr_applies_total 2\n"));
+        assert!(text.contains("sunsetr_apply_failures_total 1\n"));
+
+        let countdown = text
+            .lines()
+            .find_map(|l| l.strip_prefix("sunsetr_seconds_until_next_event "))
+            .unwrap()
+            .parse::<u64>()
+            .unwrap();
+        assert!((59..=60).contains(&countdown));
+    }
+}
@@ -1,7 +1,21 @@
 //! Signal handling and inter-process communication for sunsetr.
 //!
 //! This module provides signal-based communication between sunsetr instances,
-//! handling configuration reloads, test mode activation, and process management.
+//! handling configuration reloads, test mode activation, profile switching,
+//! and process management.
+//!
+//! Messages that are already queued when the main loop wakes are handled as one
+//! batch, with repeated reload requests collapsed into a single reload (see
+//! [`drain_signal_messages`]). A burst of SIGUSR2 from an editor or a config-save
+//! loop therefore reloads once instead of several times back-to-back.
+//!
+//! A `--hold` is kept in [`SignalState`] as a [`TimedHold`]: while it is active the
+//! held values win over the schedule, and the main loop ends it when it expires.
+//!
+//! A reload that changes the state only asks the main loop to reapply it, through
+//! [`ReloadRequest`]. Requests are numbered and the main loop marks a number handled
+//! only once the apply for it is done, so a request made while an apply is running
+//! (from this thread or any other) is never swallowed by that apply.
 
 use anyhow::{Context, Result};
 use signal_hook::{
@@ -9,11 +23,13 @@ use signal_hook::{
     iterator::Signals,
 };
 use std::{
-    sync::Arc,
-    sync::atomic::{AtomicBool, Ordering},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    sync::{Arc, Mutex},
     thread,
+    time::{Duration, SystemTime},
 };
 
+use crate::commands::hold::HoldCommand;
 use crate::logger::Log;
 
 /// Test mode parameters passed via signal
@@ -30,8 +46,29 @@ pub enum SignalMessage {
     Reload,
     /// Test mode signal with parameters (SIGUSR1)
     TestMode(TestModeParams),
+    /// Profile switch signal (SIGUSR1 with a profile request file).
+    /// `None` cycles to the next profile, `Some(name)` selects a specific one.
+    SwitchProfile(Option<String>),
+    /// Temperature nudge signal (SIGUSR1 with a nudge request file).
+    /// Offsets the temperature by this many Kelvin; 0 clears the nudge.
+    Nudge(i32),
+    /// Timed hold signal (SIGUSR1 with a hold request file)
+    Hold(HoldCommand),
     /// Shutdown signal (SIGTERM, SIGINT, SIGHUP)
     Shutdown,
+    /// Reapply the current state after a session event that may have reset gamma
+    #[cfg_attr(not(feature = "logind"), allow(dead_code))] // Only sent by the logind monitor
+    Reapply(crate::logind::SessionEvent),
+    /// Fade the current state back in on an output that woke from DPMS
+    OutputWake(String),
+    /// Fade the current state in on an output connected after startup
+    OutputAdded(String),
+    /// The desktop switched between light and dark appearance
+    #[cfg_attr(not(feature = "portal"), allow(dead_code))] // Only sent by the portal monitor
+    AppearanceChanged(crate::appearance::ColorScheme),
+    /// The session went idle (`true`) or saw input again (`false`)
+    #[cfg_attr(not(feature = "idle"), allow(dead_code))] // Only sent by the idle monitor
+    Idle(bool),
 }
 
 /// Signal handling state shared between threads
@@ -40,8 +77,185 @@ pub struct SignalState {
     pub running: Arc<AtomicBool>,
     /// Channel receiver for unified signal messages
     pub signal_receiver: std::sync::mpsc::Receiver<SignalMessage>,
-    /// Flag indicating state needs to be reloaded after config change
-    pub needs_reload: Arc<AtomicBool>,
+    /// Sender for the same channel, for message sources other than Unix signals
+    /// (e.g. the logind and output power monitors)
+    pub signal_sender: std::sync::mpsc::Sender<SignalMessage>,
+    /// Pending requests to reapply the state after a config change
+    pub needs_reload: Arc<ReloadRequest>,
+    /// Profile selected at runtime, preserved across config reloads
+    pub active_profile: Arc<Mutex<Option<String>>>,
+    /// Values pinned with `--hold`, preserved across config reloads
+    pub hold: Arc<Mutex<Option<TimedHold>>>,
+}
+
+/// Requests to reapply the state after a config change, handled by the main loop.
+///
+/// Each request bumps a generation counter. The main loop reads the pending
+/// generation, applies, and then marks that generation handled; requests made in
+/// the meantime have a higher generation and stay pending for the next pass.
+/// Clearing a plain flag before or after the apply would instead race with a
+/// request arriving between the check and the clear.
+#[derive(Debug, Default)]
+pub struct ReloadRequest {
+    requested: AtomicU64,
+    handled: AtomicU64,
+}
+
+impl ReloadRequest {
+    /// Ask the main loop to reapply the state.
+    pub fn request(&self) {
+        self.requested.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// The newest generation, whether or not it is handled yet.
+    pub fn generation(&self) -> u64 {
+        self.requested.load(Ordering::SeqCst)
+    }
+
+    /// The generation to apply for, or `None` when every request is handled.
+    pub fn pending(&self) -> Option<u64> {
+        let requested = self.requested.load(Ordering::SeqCst);
+        (requested > self.handled.load(Ordering::SeqCst)).then_some(requested)
+    }
+
+    /// Mark every request up to `generation` as handled.
+    pub fn complete(&self, generation: u64) {
+        self.handled.fetch_max(generation, Ordering::SeqCst);
+    }
+}
+
+/// Temperature and gamma shown instead of the schedule until `until`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimedHold {
+    pub temperature: u32,
+    pub gamma: f32,
+    pub until: SystemTime,
+}
+
+impl TimedHold {
+    /// Time left at `now`, or `None` once the hold has expired.
+    pub fn remaining(&self, now: SystemTime) -> Option<Duration> {
+        self.until
+            .duration_since(now)
+            .ok()
+            .filter(|left| !left.is_zero())
+    }
+}
+
+impl SignalState {
+    /// The hold still in effect at `now`, if any.
+    pub fn active_hold(&self, now: SystemTime) -> Option<TimedHold> {
+        self.hold
+            .lock()
+            .ok()
+            .and_then(|hold| *hold)
+            .filter(|hold| hold.remaining(now).is_some())
+    }
+
+    /// Start or release a hold at `now`, returning the hold that was active before.
+    pub fn set_hold(&self, command: HoldCommand, now: SystemTime) -> Option<TimedHold> {
+        let previous = self.active_hold(now);
+        let next = match command {
+            HoldCommand::Start {
+                temperature,
+                gamma,
+                minutes,
+            } => Some(TimedHold {
+                temperature,
+                gamma,
+                until: now + Duration::from_secs(u64::from(minutes) * 60),
+            }),
+            HoldCommand::Release => None,
+        };
+        if let Ok(mut hold) = self.hold.lock() {
+            *hold = next;
+        }
+        previous
+    }
+
+    /// Remove and return the hold if it has expired by `now`.
+    pub fn take_expired_hold(&self, now: SystemTime) -> Option<TimedHold> {
+        let mut hold = self.hold.lock().ok()?;
+        if hold.is_some_and(|active| active.remaining(now).is_none()) {
+            hold.take()
+        } else {
+            None
+        }
+    }
+}
+
+/// Go back from a hold's values to the scheduled state, smoothly when enabled.
+///
+/// Any config reload made during the hold is covered by this, so the reloads
+/// pending when it starts are marked handled instead of being applied a second time.
+pub fn end_hold(
+    hold: TimedHold,
+    backend: &mut Box<dyn crate::backend::ColorTemperatureBackend>,
+    config: &crate::config::Config,
+    signal_state: &SignalState,
+    current_state: &mut crate::time_state::TransitionState,
+) {
+    let covered = signal_state.needs_reload.generation();
+
+    let new_state = crate::time_state::get_transition_state(config);
+    let result = if config.effective().startup_transition {
+        crate::startup_transition::StartupTransition::new_from_values(
+            hold.temperature,
+            hold.gamma,
+            new_state,
+            config,
+        )
+        .execute(backend.as_mut(), config, &signal_state.running)
+    } else {
+        backend.apply_transition_state(new_state, config, &signal_state.running)
+    };
+    if let Err(e) = result {
+        Log::log_warning(&format!("Failed to return from hold: {}", e));
+    }
+    signal_state.needs_reload.complete(covered);
+    *current_state = new_state;
+}
+
+/// Show the hold's values on every output.
+fn apply_hold(
+    hold: TimedHold,
+    backend: &mut Box<dyn crate::backend::ColorTemperatureBackend>,
+    signal_state: &SignalState,
+) {
+    if let Err(e) =
+        backend.apply_temperature_gamma(hold.temperature, hold.gamma, &signal_state.running)
+    {
+        Log::log_warning(&format!("Failed to apply hold: {}", e));
+    }
+}
+
+/// Collect `first` and every message already queued behind it, coalescing reloads.
+///
+/// Only the last `Reload` in the batch is kept, since every reload reads the same
+/// config file. Other messages keep their order.
+pub fn drain_signal_messages(
+    first: SignalMessage,
+    receiver: &std::sync::mpsc::Receiver<SignalMessage>,
+) -> Vec<SignalMessage> {
+    let mut messages = vec![first];
+    messages.extend(receiver.try_iter());
+
+    let last_reload = messages
+        .iter()
+        .rposition(|msg| matches!(msg, SignalMessage::Reload));
+    let mut index = 0;
+    messages.retain(|msg| {
+        let keep = !matches!(msg, SignalMessage::Reload) || Some(index) == last_reload;
+        index += 1;
+        keep
+    });
+
+    #[cfg(debug_assertions)]
+    if messages.len() > 1 {
+        eprintln!("DEBUG: Handling {} queued signal messages", messages.len());
+    }
+
+    messages
 }
 
 /// Handle a signal message received in the main loop
@@ -77,6 +291,11 @@ pub fn handle_signal_message(
             // Enter test mode loop (blocks until test mode exits)
             crate::commands::test::run_test_mode_loop(test_params, backend, signal_state, config)?;
 
+            // Test mode restores the schedule, so put a hold that is still running back
+            if let Some(hold) = signal_state.active_hold(SystemTime::now()) {
+                apply_hold(hold, backend, signal_state);
+            }
+
             #[cfg(debug_assertions)]
             eprintln!("DEBUG: Returned from test mode loop, resuming main loop");
         }
@@ -98,6 +317,177 @@ pub fn handle_signal_message(
             // Set running to false to trigger main loop exit
             signal_state.running.store(false, Ordering::SeqCst);
         }
+        SignalMessage::Reapply(event) => {
+            Log::log_pipe();
+            Log::log_decorated(&format!("Session {}, reapplying current state", event));
+
+            // Whatever the backend last sent may be gone, so don't let it skip this apply
+            backend.forget_applied_state();
+            if let Some(hold) = signal_state.active_hold(SystemTime::now()) {
+                apply_hold(hold, backend, signal_state);
+                return Ok(());
+            }
+            let new_state = crate::time_state::get_transition_state(config);
+            if let Err(e) = backend.apply_transition_state(new_state, config, &signal_state.running)
+            {
+                Log::log_warning(&format!("Failed to reapply state: {}", e));
+            }
+            *current_state = new_state;
+        }
+        SignalMessage::OutputWake(output) => {
+            Log::log_pipe();
+            Log::log_decorated(&format!(
+                "Output '{}' woke up, fading in current state",
+                output
+            ));
+
+            // Only this output is touched, so the tracked state stays as it is
+            if let Some(hold) = signal_state.active_hold(SystemTime::now()) {
+                apply_hold(hold, backend, signal_state);
+                return Ok(());
+            }
+            let state = crate::time_state::get_transition_state(config);
+            if let Err(e) = backend.fade_in_output(&output, state, config, &signal_state.running) {
+                Log::log_warning(&format!("Failed to fade in output '{}': {}", output, e));
+            }
+        }
+        SignalMessage::OutputAdded(output) => {
+            Log::log_pipe();
+            Log::log_decorated(&format!(
+                "Output '{}' connected, fading in current state",
+                output
+            ));
+
+            // New outputs start at identity gamma, just like woken ones
+            if let Some(hold) = signal_state.active_hold(SystemTime::now()) {
+                apply_hold(hold, backend, signal_state);
+                return Ok(());
+            }
+            let state = crate::time_state::get_transition_state(config);
+            if let Err(e) = backend.fade_in_output(&output, state, config, &signal_state.running) {
+                Log::log_warning(&format!("Failed to fade in output '{}': {}", output, e));
+            }
+        }
+        SignalMessage::AppearanceChanged(scheme) => {
+            // Capture what's on screen now, before the new scheme changes the state
+            let (start_temp, start_gamma) =
+                crate::time_state::get_initial_values_for_state(*current_state, config);
+            crate::appearance::set_current_scheme(scheme);
+
+            let new_state = crate::time_state::get_transition_state(config);
+            if !crate::appearance::is_appearance_mode(config) || new_state == *current_state {
+                return Ok(());
+            }
+            if signal_state.active_hold(SystemTime::now()).is_some() {
+                // The hold ends with a transition to whatever the scheme asks for then
+                return Ok(());
+            }
+
+            Log::log_block_start(&format!("Desktop switched to {} appearance", scheme));
+            let result = crate::startup_transition::StartupTransition::new_from_values(
+                start_temp,
+                start_gamma,
+                new_state,
+                config,
+            )
+            .execute(backend.as_mut(), config, &signal_state.running);
+            if let Err(e) = result {
+                Log::log_warning(&format!("Failed to follow appearance change: {}", e));
+            }
+            *current_state = new_state;
+        }
+        SignalMessage::Idle(idle) => {
+            if idle == crate::time_state::idle_dimmed() {
+                return Ok(());
+            }
+            // Capture what's on screen now, before the dimming changes the values
+            let (start_temp, start_gamma) =
+                crate::time_state::get_initial_values_for_state(*current_state, config);
+            crate::time_state::set_idle_dimmed(idle);
+
+            if idle {
+                Log::log_block_start(&format!(
+                    "Session idle, dimming to {}% of the current gamma",
+                    config.effective().idle_gamma
+                ));
+            } else {
+                Log::log_block_start("Session active again, restoring gamma");
+            }
+
+            if signal_state.active_hold(SystemTime::now()).is_some() {
+                Log::log_indented("A hold is active, its values stay as they are");
+                return Ok(());
+            }
+
+            let new_state = crate::time_state::get_transition_state(config);
+            let result = crate::startup_transition::StartupTransition::new_from_values(
+                start_temp,
+                start_gamma,
+                new_state,
+                config,
+            )
+            .execute(backend.as_mut(), config, &signal_state.running);
+            if let Err(e) = result {
+                Log::log_warning(&format!("Failed to apply idle dimming: {}", e));
+            }
+            *current_state = new_state;
+        }
+        SignalMessage::Nudge(step) => {
+            let nudge =
+                crate::time_state::combine_nudge(crate::time_state::temperature_nudge(), step);
+            crate::time_state::set_temperature_nudge(nudge);
+
+            let state = crate::time_state::get_transition_state(config);
+            let (temp, _) = crate::time_state::get_initial_values_for_state(state, config);
+            if nudge == 0 {
+                Log::log_block_start(&format!("Temperature nudge cleared, back to {}K", temp));
+            } else {
+                Log::log_block_start(&format!(
+                    "Temperature nudged by {:+}K to {}K until the next change",
+                    nudge, temp
+                ));
+            }
+
+            if signal_state.active_hold(SystemTime::now()).is_some() {
+                Log::log_indented("A hold is active, the nudge applies once it ends");
+                return Ok(());
+            }
+
+            if let Err(e) = backend.apply_transition_state(state, config, &signal_state.running) {
+                Log::log_warning(&format!("Failed to apply temperature nudge: {}", e));
+            }
+            *current_state = state;
+        }
+        SignalMessage::Hold(command) => {
+            let now = SystemTime::now();
+            let previous = signal_state.set_hold(command, now);
+            match (command, signal_state.active_hold(now)) {
+                (HoldCommand::Start { minutes, .. }, Some(hold)) => {
+                    Log::log_block_start(&format!(
+                        "Holding {}K @ {}% for {} minutes",
+                        hold.temperature, hold.gamma, minutes
+                    ));
+                    apply_hold(hold, backend, signal_state);
+                }
+                _ => match previous {
+                    Some(hold) => {
+                        Log::log_block_start("Hold released, returning to scheduled values");
+                        end_hold(hold, backend, config, signal_state, current_state);
+                    }
+                    None => {
+                        Log::log_pipe();
+                        Log::log_decorated("No active hold to release");
+                    }
+                },
+            }
+        }
+        SignalMessage::SwitchProfile(requested) => {
+            #[cfg(debug_assertions)]
+            eprintln!("DEBUG: Main loop received profile switch: {:?}", requested);
+
+            let target = requested.unwrap_or_else(|| config.next_profile_name());
+            switch_profile(&target, backend, config, signal_state, current_state);
+        }
         SignalMessage::Reload => {
             #[cfg(debug_assertions)]
             {
@@ -119,8 +509,13 @@ pub fn handle_signal_message(
                     });
             }
 
-            // Reload configuration
-            match crate::config::Config::load() {
+            // Reload configuration, keeping any profile selected at runtime
+            let runtime_profile = signal_state
+                .active_profile
+                .lock()
+                .map(|profile| profile.clone())
+                .unwrap_or_default();
+            match crate::config::Config::load_with_profile(runtime_profile.as_deref()) {
                 Ok(new_config) => {
                     #[cfg(debug_assertions)]
                     {
@@ -148,6 +543,11 @@ pub fn handle_signal_message(
                             });
                     }
 
+                    // Values the running state resolves to before the reload, so changed
+                    // color settings are applied even when the state itself is unchanged
+                    let old_values =
+                        crate::time_state::get_initial_values_for_state(*current_state, config);
+
                     // Replace config with new loaded config
                     *config = new_config;
 
@@ -175,16 +575,25 @@ pub fn handle_signal_message(
                             });
                     }
 
-                    // Only apply state if it actually changed after config reload
-                    if *current_state != new_state {
+                    let new_values =
+                        crate::time_state::get_initial_values_for_state(new_state, config);
+
+                    // Only apply state if it or its values actually changed after config reload
+                    if *current_state != new_state || old_values != new_values {
                         Log::log_pipe();
-                        Log::log_decorated(
-                            "State changed after config reload, will apply on next cycle...",
-                        );
+                        if *current_state != new_state {
+                            Log::log_decorated(
+                                "State changed after config reload, will apply on next cycle...",
+                            );
+                        } else {
+                            Log::log_decorated(
+                                "Color settings changed after config reload, will apply on next cycle...",
+                            );
+                        }
 
                         // Set flag to trigger state reapplication in main loop
                         // This allows the main loop to handle startup transitions properly
-                        signal_state.needs_reload.store(true, Ordering::SeqCst);
+                        signal_state.needs_reload.request();
 
                         #[cfg(debug_assertions)]
                         {
@@ -224,6 +633,63 @@ pub fn handle_signal_message(
     Ok(())
 }
 
+/// Switch to the named profile, smoothly transitioning from the current display values.
+fn switch_profile(
+    target: &str,
+    backend: &mut Box<dyn crate::backend::ColorTemperatureBackend>,
+    config: &mut crate::config::Config,
+    signal_state: &SignalState,
+    current_state: &mut crate::time_state::TransitionState,
+) {
+    let new_config = match crate::config::Config::load_with_profile(Some(target)) {
+        Ok(new_config) => new_config,
+        Err(e) => {
+            Log::log_pipe();
+            Log::log_warning(&format!(
+                "Failed to switch to profile '{}': {:#}",
+                target, e
+            ));
+            return;
+        }
+    };
+
+    // Capture what's on screen now, before the config changes underneath us
+    let (start_temp, start_gamma) =
+        crate::time_state::get_initial_values_for_state(*current_state, config);
+
+    *config = new_config;
+    if let Ok(mut active_profile) = signal_state.active_profile.lock() {
+        *active_profile = Some(target.to_string());
+    }
+
+    Log::log_block_start(&format!("Switched to profile: {}", target));
+    if signal_state.active_hold(SystemTime::now()).is_some() {
+        Log::log_indented("A hold is active, the profile applies once it ends");
+        return;
+    }
+
+    let new_state = crate::time_state::get_transition_state(config);
+    let smooth = config.effective().startup_transition;
+
+    let result = if smooth {
+        crate::startup_transition::StartupTransition::new_from_values(
+            start_temp,
+            start_gamma,
+            new_state,
+            config,
+        )
+        .execute(backend.as_mut(), config, &signal_state.running)
+    } else {
+        backend.apply_transition_state(new_state, config, &signal_state.running)
+    };
+
+    if let Err(e) = result {
+        Log::log_warning(&format!("Failed to apply profile '{}': {}", target, e));
+    }
+
+    *current_state = new_state;
+}
+
 /// Set up signal handling for the application.
 ///
 /// Returns a SignalState containing the running flag and signal receiver channel.
@@ -238,6 +704,7 @@ pub fn setup_signal_handler(debug_enabled: bool) -> Result<SignalState> {
 
     let running_clone = running.clone();
     let signal_sender_clone = signal_sender.clone();
+    let state_sender = signal_sender.clone();
 
     thread::spawn(move || {
         #[cfg(debug_assertions)]
@@ -309,45 +776,114 @@ pub fn setup_signal_handler(debug_enabled: bool) -> Result<SignalState> {
 
             match sig {
                 SIGUSR1 => {
-                    // SIGUSR1 is used for test mode
+                    // SIGUSR1 with a profile request file present is a profile switch
+                    if let Ok(profile_file_path) =
+                        crate::commands::profile::profile_file_path(std::process::id())
+                        && let Ok(content) = std::fs::read_to_string(&profile_file_path)
+                    {
+                        let _ = std::fs::remove_file(&profile_file_path);
+
+                        Log::log_pipe();
+                        Log::log_decorated("Received profile switch signal");
+
+                        let name = content.trim();
+                        let requested = (!name.is_empty()).then(|| name.to_string());
+                        if signal_sender_clone
+                            .send(SignalMessage::SwitchProfile(requested))
+                            .is_err()
+                        {
+                            break;
+                        }
+                        continue;
+                    }
+
+                    // SIGUSR1 with a nudge request file present is a temperature nudge
+                    if let Ok(nudge_file_path) =
+                        crate::commands::nudge::nudge_file_path(std::process::id())
+                        && let Ok(content) = std::fs::read_to_string(&nudge_file_path)
+                    {
+                        let _ = std::fs::remove_file(&nudge_file_path);
+
+                        Log::log_pipe();
+                        Log::log_decorated("Received temperature nudge signal");
+
+                        if let Ok(step) = content.trim().parse::<i32>()
+                            && signal_sender_clone
+                                .send(SignalMessage::Nudge(step))
+                                .is_err()
+                        {
+                            break;
+                        }
+                        continue;
+                    }
+
+                    // SIGUSR1 with a hold request file present is a timed hold
+                    if let Ok(hold_file_path) =
+                        crate::commands::hold::hold_file_path(std::process::id())
+                        && let Ok(content) = std::fs::read_to_string(&hold_file_path)
+                    {
+                        let _ = std::fs::remove_file(&hold_file_path);
+
+                        Log::log_pipe();
+                        Log::log_decorated("Received hold signal");
+
+                        if let Some(command) = HoldCommand::from_request(&content)
+                            && signal_sender_clone
+                                .send(SignalMessage::Hold(command))
+                                .is_err()
+                        {
+                            break;
+                        }
+                        continue;
+                    }
+
+                    // Otherwise SIGUSR1 is used for test mode
                     Log::log_pipe();
                     Log::log_decorated("Received test mode signal");
 
-                    // Read test parameters from temp file
-                    let test_file_path = format!("/tmp/sunsetr-test-{}.tmp", std::process::id());
+                    // Read test parameters from the request file
+                    let test_file_path =
+                        match crate::commands::test::test_file_path(std::process::id()) {
+                            Ok(path) => path,
+                            Err(_e) => {
+                                #[cfg(debug_assertions)]
+                                {
+                                    eprintln!("DEBUG: No path for the test request file: {}", _e);
+                                }
+                                continue;
+                            }
+                        };
                     match std::fs::read_to_string(&test_file_path) {
                         Ok(content) => {
                             let lines: Vec<&str> = content.trim().lines().collect();
-                            if lines.len() == 2 {
-                                if let (Ok(temp), Ok(gamma)) =
+                            if lines.len() == 2
+                                && let (Ok(temp), Ok(gamma)) =
                                     (lines[0].parse::<u32>(), lines[1].parse::<f32>())
+                            {
+                                let test_params = TestModeParams {
+                                    temperature: temp,
+                                    gamma,
+                                };
+
+                                match signal_sender_clone.send(SignalMessage::TestMode(test_params))
                                 {
-                                    let test_params = TestModeParams {
-                                        temperature: temp,
-                                        gamma,
-                                    };
-
-                                    match signal_sender_clone
-                                        .send(SignalMessage::TestMode(test_params))
-                                    {
-                                        Ok(()) => {
-                                            #[cfg(debug_assertions)]
-                                            {
-                                                eprintln!(
-                                                    "DEBUG: Test mode parameters sent: {}K @ {}%",
-                                                    temp, gamma
-                                                );
-                                            }
+                                    Ok(()) => {
+                                        #[cfg(debug_assertions)]
+                                        {
+                                            eprintln!(
+                                                "DEBUG: Test mode parameters sent: {}K @ {}%",
+                                                temp, gamma
+                                            );
                                         }
-                                        Err(_) => {
-                                            #[cfg(debug_assertions)]
-                                            {
-                                                eprintln!(
-                                                    "DEBUG: Failed to send test parameters - channel disconnected"
-                                                );
-                                            }
-                                            break;
+                                    }
+                                    Err(_) => {
+                                        #[cfg(debug_assertions)]
+                                        {
+                                            eprintln!(
+                                                "DEBUG: Failed to send test parameters - channel disconnected"
+                                            );
                                         }
+                                        break;
                                     }
                                 }
                             }
@@ -545,6 +1081,152 @@ pub fn setup_signal_handler(debug_enabled: bool) -> Result<SignalState> {
     Ok(SignalState {
         running,
         signal_receiver,
-        needs_reload: Arc::new(AtomicBool::new(false)),
+        signal_sender: state_sender,
+        needs_reload: Arc::new(ReloadRequest::default()),
+        active_profile: Arc::new(Mutex::new(None)),
+        hold: Arc::new(Mutex::new(None)),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drain_coalesces_reload_burst() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        for _ in 0..4 {
+            sender.send(SignalMessage::Reload).unwrap();
+        }
+        sender.send(SignalMessage::Nudge(-200)).unwrap();
+        sender.send(SignalMessage::Reload).unwrap();
+
+        let messages = drain_signal_messages(SignalMessage::Reload, &receiver);
+        let reloads = messages
+            .iter()
+            .filter(|msg| matches!(msg, SignalMessage::Reload))
+            .count();
+        assert_eq!(reloads, 1);
+
+        // The reload lands after the nudge, where the latest request was queued
+        assert!(matches!(
+            messages.as_slice(),
+            [SignalMessage::Nudge(-200), SignalMessage::Reload]
+        ));
+        assert!(receiver.try_recv().is_err());
+
+        // A lone message passes through untouched
+        let messages = drain_signal_messages(SignalMessage::Shutdown, &receiver);
+        assert!(matches!(messages.as_slice(), [SignalMessage::Shutdown]));
+    }
+
+    #[test]
+    fn test_reload_request_handshake() {
+        let reload = ReloadRequest::default();
+        assert_eq!(reload.pending(), None);
+
+        reload.request();
+        let generation = reload.pending().unwrap();
+
+        // A request made while that generation is being applied survives its completion
+        reload.request();
+        reload.complete(generation);
+        let next = reload.pending().unwrap();
+        assert!(next > generation);
+
+        // Completing an older generation late doesn't reopen handled requests
+        reload.complete(next);
+        reload.complete(generation);
+        assert_eq!(reload.pending(), None);
+    }
+
+    #[test]
+    fn test_reload_requests_during_applies_are_not_lost() {
+        const REQUESTS: u64 = 2000;
+        let reload = Arc::new(ReloadRequest::default());
+        let writing = Arc::new(AtomicBool::new(true));
+
+        let writer = {
+            let (reload, writing) = (Arc::clone(&reload), Arc::clone(&writing));
+            thread::spawn(move || {
+                for i in 0..REQUESTS {
+                    reload.request();
+                    if i % 64 == 0 {
+                        thread::yield_now();
+                    }
+                }
+                writing.store(false, Ordering::SeqCst);
+            })
+        };
+
+        // Main loop stand-in: every apply covers the generation read before it started
+        let mut applies = 0;
+        let mut applied_generation = 0;
+        while writing.load(Ordering::SeqCst) || reload.pending().is_some() {
+            if let Some(generation) = reload.pending() {
+                assert!(generation > applied_generation);
+                thread::sleep(Duration::from_micros(50));
+                applied_generation = generation;
+                applies += 1;
+                reload.complete(generation);
+            }
+        }
+        writer.join().unwrap();
+
+        // The last request was applied, and bursts were coalesced into fewer applies
+        assert_eq!(applied_generation, REQUESTS);
+        assert_eq!(reload.pending(), None);
+        assert!((1..=REQUESTS).contains(&applies));
+    }
+
+    #[test]
+    fn test_hold_lifecycle() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let signal_state = SignalState {
+            running: Arc::new(AtomicBool::new(true)),
+            signal_receiver: receiver,
+            signal_sender: sender,
+            needs_reload: Arc::new(ReloadRequest::default()),
+            active_profile: Arc::new(Mutex::new(None)),
+            hold: Arc::new(Mutex::new(None)),
+        };
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let minute = Duration::from_secs(60);
+
+        let previous = signal_state.set_hold(
+            HoldCommand::Start {
+                temperature: 3000,
+                gamma: 90.0,
+                minutes: 120,
+            },
+            start,
+        );
+        assert_eq!(previous, None);
+
+        let hold = signal_state.active_hold(start).unwrap();
+        assert_eq!((hold.temperature, hold.gamma), (3000, 90.0));
+        assert_eq!(hold.remaining(start + 30 * minute), Some(90 * minute));
+
+        // Still running: nothing to expire yet
+        assert_eq!(signal_state.take_expired_hold(start + 119 * minute), None);
+
+        // Once the time is up the hold is inactive and handed back exactly once
+        let end = start + 120 * minute;
+        assert_eq!(signal_state.active_hold(end), None);
+        assert_eq!(signal_state.take_expired_hold(end), Some(hold));
+        assert_eq!(signal_state.take_expired_hold(end), None);
+
+        // Releasing reports the hold that was active
+        signal_state.set_hold(
+            HoldCommand::Start {
+                temperature: 2500,
+                gamma: 80.0,
+                minutes: 10,
+            },
+            start,
+        );
+        let released = signal_state.set_hold(HoldCommand::Release, start + minute);
+        assert_eq!(released.map(|hold| hold.temperature), Some(2500));
+        assert_eq!(signal_state.active_hold(start + minute), None);
+    }
+}
@@ -74,20 +74,15 @@ impl StartupTransition {
     /// New StartupTransition ready for execution
     pub fn new(current_state: TransitionState, config: &Config) -> Self {
         // Always start from day values for consistent animation baseline
-        let start_temp = config
-            .day_temp
-            .unwrap_or(crate::constants::DEFAULT_DAY_TEMP);
-        let start_gamma = config
-            .day_gamma
-            .unwrap_or(crate::constants::DEFAULT_DAY_GAMMA);
+        let effective = config.effective();
+        let start_temp = effective.day_temp;
+        let start_gamma = effective.day_gamma;
 
         // Check if this is a dynamic target (we're starting during a transition)
         let is_dynamic_target = matches!(current_state, TransitionState::Transitioning { .. });
 
         // Get the configured startup transition duration
-        let duration_secs = config
-            .startup_transition_duration
-            .unwrap_or(DEFAULT_STARTUP_TRANSITION_DURATION);
+        let duration_secs = effective.startup_transition_duration;
 
         Self {
             start_temp,
@@ -124,9 +119,7 @@ impl StartupTransition {
         let is_dynamic_target = matches!(target_state, TransitionState::Transitioning { .. });
 
         // Get the configured startup transition duration
-        let duration_secs = config
-            .startup_transition_duration
-            .unwrap_or(DEFAULT_STARTUP_TRANSITION_DURATION);
+        let duration_secs = config.effective().startup_transition_duration;
 
         Self {
             start_temp,
@@ -156,28 +149,18 @@ impl StartupTransition {
     /// # Returns
     /// Tuple of (target_temperature, target_gamma) for the current animation frame
     fn calculate_current_target(&self, config: &Config) -> (u32, f32) {
+        let effective = config.effective();
+        let (day_temp, night_temp) = (effective.day_temp, effective.night_temp);
+        let (day_gamma, night_gamma) = (effective.day_gamma, effective.night_gamma);
+
         match self.initial_state {
             TransitionState::Stable(TimeState::Day) => {
                 // Target is day values, simple case
-                (
-                    config
-                        .day_temp
-                        .unwrap_or(crate::constants::DEFAULT_DAY_TEMP),
-                    config
-                        .day_gamma
-                        .unwrap_or(crate::constants::DEFAULT_DAY_GAMMA),
-                )
+                (day_temp, day_gamma)
             }
             TransitionState::Stable(TimeState::Night) => {
                 // Target is night values, simple case
-                (
-                    config
-                        .night_temp
-                        .unwrap_or(crate::constants::DEFAULT_NIGHT_TEMP),
-                    config
-                        .night_gamma
-                        .unwrap_or(crate::constants::DEFAULT_NIGHT_GAMMA),
-                )
+                (night_temp, night_gamma)
             }
             TransitionState::Transitioning {
                 from,
@@ -197,60 +180,32 @@ impl StartupTransition {
                         to: current_to,
                         progress: current_progress,
                     } = current_state
+                        && current_from == from
+                        && current_to == to
                     {
-                        if current_from == from && current_to == to {
-                            // We're still in the same transition, use current progress
-                            let day_temp = config
-                                .day_temp
-                                .unwrap_or(crate::constants::DEFAULT_DAY_TEMP);
-                            let night_temp = config
-                                .night_temp
-                                .unwrap_or(crate::constants::DEFAULT_NIGHT_TEMP);
-                            let day_gamma = config
-                                .day_gamma
-                                .unwrap_or(crate::constants::DEFAULT_DAY_GAMMA);
-                            let night_gamma = config
-                                .night_gamma
-                                .unwrap_or(crate::constants::DEFAULT_NIGHT_GAMMA);
-
-                            match (from, to) {
-                                (TimeState::Day, TimeState::Night) => {
-                                    // Transitioning from day to night (sunset)
-                                    let temp =
-                                        interpolate_u32(day_temp, night_temp, current_progress);
-                                    let gamma =
-                                        interpolate_f32(day_gamma, night_gamma, current_progress);
-                                    return (temp, gamma);
-                                }
-                                (TimeState::Night, TimeState::Day) => {
-                                    // Transitioning from night to day (sunrise)
-                                    let temp =
-                                        interpolate_u32(night_temp, day_temp, current_progress);
-                                    let gamma =
-                                        interpolate_f32(night_gamma, day_gamma, current_progress);
-                                    return (temp, gamma);
-                                }
-                                _ => (), // Fall through to static calculation
+                        // We're still in the same transition, use current progress
+                        match (from, to) {
+                            (TimeState::Day, TimeState::Night) => {
+                                // Transitioning from day to night (sunset)
+                                let temp = interpolate_u32(day_temp, night_temp, current_progress);
+                                let gamma =
+                                    interpolate_f32(day_gamma, night_gamma, current_progress);
+                                return (temp, gamma);
+                            }
+                            (TimeState::Night, TimeState::Day) => {
+                                // Transitioning from night to day (sunrise)
+                                let temp = interpolate_u32(night_temp, day_temp, current_progress);
+                                let gamma =
+                                    interpolate_f32(night_gamma, day_gamma, current_progress);
+                                return (temp, gamma);
                             }
+                            _ => (), // Fall through to static calculation
                         }
                     }
                 }
 
                 // If we're not in a dynamic transition or the transition changed,
                 // calculate based on the initial progress (static target)
-                let day_temp = config
-                    .day_temp
-                    .unwrap_or(crate::constants::DEFAULT_DAY_TEMP);
-                let night_temp = config
-                    .night_temp
-                    .unwrap_or(crate::constants::DEFAULT_NIGHT_TEMP);
-                let day_gamma = config
-                    .day_gamma
-                    .unwrap_or(crate::constants::DEFAULT_DAY_GAMMA);
-                let night_gamma = config
-                    .night_gamma
-                    .unwrap_or(crate::constants::DEFAULT_NIGHT_GAMMA);
-
                 match (from, to) {
                     (TimeState::Day, TimeState::Night) => {
                         // Transitioning from day to night (sunset)
@@ -283,7 +238,9 @@ impl StartupTransition {
         let percentage = (progress * 100.0) as usize;
 
         // Only redraw if percentage changed to prevent flickering
-        if self.last_progress_pct == Some(percentage) && percentage < 100 {
+        if !Log::is_console_output()
+            || (self.last_progress_pct == Some(percentage) && percentage < 100)
+        {
             return;
         }
 
@@ -392,7 +349,7 @@ impl StartupTransition {
         let update_interval = Duration::from_millis(interval_ms as u64);
 
         // Add a blank line before the progress bar for spacing
-        {
+        if Log::is_console_output() {
             let mut stdout = io::stdout().lock();
             writeln!(stdout, "┃").ok();
             stdout.flush().ok();
@@ -456,7 +413,7 @@ impl StartupTransition {
         }
 
         // Add proper newline and spacing after progress bar completion
-        {
+        if Log::is_console_output() {
             let mut stdout = io::stdout().lock();
             writeln!(stdout).ok();
             writeln!(stdout, "┃").ok();
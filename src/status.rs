@@ -0,0 +1,328 @@
+//! Runtime status snapshot shared with other sunsetr invocations.
+//!
+//! The running instance writes a small TOML snapshot of its current state to
+//! `sunsetr-status-{pid}.tmp` in the runtime directory (see
+//! [`crate::utils::runtime_dir`]) every time the main loop wakes up. Commands
+//! such as `--monitor` read it to display live information without having to
+//! recompute state themselves (which would miss runtime changes like profile
+//! switches). The file is replaced atomically and removed on shutdown.
+//!
+//! A second, smaller file at a fixed path, `sunsetr-next.toml`, holds just the
+//! current state and values and when the next state change happens. It is only
+//! rewritten when that information changes, so bar widgets can poll its
+//! modification time cheaply instead of running a command every second.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::json::JsonValue;
+use crate::time_state::{TimeState, TransitionState};
+
+/// Snapshot of the running instance's state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusSnapshot {
+    /// "day", "night", "sunset" or "sunrise"
+    pub state: String,
+    /// Transition progress in the range 0.0..=1.0 (only while transitioning)
+    pub progress: Option<f32>,
+    /// Color temperature currently applied
+    pub temperature: u32,
+    /// Gamma percentage currently applied
+    pub gamma: f32,
+    /// Unix timestamp of the next wake-up (next update or next transition)
+    pub next_event: u64,
+    /// Active profile name, if profiles are in use
+    pub profile: Option<String>,
+    /// Active `--nudge` offset in Kelvin, already included in `temperature`
+    pub nudge: Option<i32>,
+    /// Unix timestamp when an active `--hold` ends; `temperature` and `gamma` are the held values
+    pub hold_until: Option<u64>,
+    /// Backend name
+    pub backend: String,
+    /// Unix timestamp when this snapshot was written
+    pub updated: u64,
+}
+
+impl StatusSnapshot {
+    /// Build a snapshot from the main loop's view of the current state.
+    pub fn new(
+        state: TransitionState,
+        values: (u32, f32),
+        until_next_event: Duration,
+        profile: Option<String>,
+        backend: &str,
+    ) -> Self {
+        let now = unix_now();
+        let progress = match state {
+            TransitionState::Stable(_) => None,
+            TransitionState::Transitioning { progress, .. } => Some(progress),
+        };
+
+        Self {
+            state: state_name(state).to_string(),
+            progress,
+            temperature: values.0,
+            gamma: values.1,
+            next_event: now + until_next_event.as_secs(),
+            profile,
+            nudge: None,
+            hold_until: None,
+            backend: backend.to_string(),
+            updated: now,
+        }
+    }
+
+    /// Seconds until the next event relative to `now` (a Unix timestamp).
+    pub fn seconds_until_next_event(&self, now: u64) -> u64 {
+        self.next_event.saturating_sub(now)
+    }
+
+    /// The snapshot's fields for `--json` output.
+    pub fn json_fields(&self) -> Vec<(&'static str, JsonValue)> {
+        vec![
+            ("state", JsonValue::from(self.state.as_str())),
+            ("progress", JsonValue::from(self.progress)),
+            ("temperature", JsonValue::from(self.temperature)),
+            ("gamma", JsonValue::from(self.gamma)),
+            ("next_event", JsonValue::from(self.next_event)),
+            ("profile", JsonValue::from(self.profile.clone())),
+            ("nudge", JsonValue::from(self.nudge)),
+            ("hold_until", JsonValue::from(self.hold_until)),
+            ("backend", JsonValue::from(self.backend.as_str())),
+            ("updated", JsonValue::from(self.updated)),
+        ]
+    }
+}
+
+/// Name of a state as used in the status files: "day", "night", "sunset" or "sunrise".
+pub fn state_name(state: TransitionState) -> &'static str {
+    match state {
+        TransitionState::Stable(TimeState::Day) => "day",
+        TransitionState::Stable(TimeState::Night) => "night",
+        TransitionState::Transitioning {
+            from: TimeState::Day,
+            ..
+        } => "sunset",
+        TransitionState::Transitioning { .. } => "sunrise",
+    }
+}
+
+/// The next state change and the current values, for bar widgets (`sunsetr-next.toml`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NextTransition {
+    /// "day", "night", "sunset" or "sunrise"
+    pub state: String,
+    /// Color temperature currently applied
+    pub temperature: u32,
+    /// Gamma percentage currently applied
+    pub gamma: f32,
+    /// The state that begins at `next_transition`
+    pub next_state: String,
+    /// Unix timestamp when the current state ends (a transition starts or finishes),
+    /// or 0 when nothing is scheduled (`transition_mode = "appearance"`)
+    pub next_transition: u64,
+}
+
+impl NextTransition {
+    /// Describe `state`, which ends `until_change` from now (`None` if never).
+    pub fn new(state: TransitionState, values: (u32, f32), until_change: Option<Duration>) -> Self {
+        let (state_name, next_state) = match state {
+            TransitionState::Stable(TimeState::Day) => ("day", "sunset"),
+            TransitionState::Stable(TimeState::Night) => ("night", "sunrise"),
+            TransitionState::Transitioning {
+                from: TimeState::Day,
+                ..
+            } => ("sunset", "night"),
+            TransitionState::Transitioning { .. } => ("sunrise", "day"),
+        };
+        let next_transition = until_change
+            .and_then(|until| (SystemTime::now() + until).duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_secs_f64().round() as u64);
+
+        Self {
+            state: state_name.to_string(),
+            temperature: values.0,
+            gamma: values.1,
+            next_state: next_state.to_string(),
+            next_transition,
+        }
+    }
+
+    /// Whether `other` says the same, allowing a second of jitter in the timestamp.
+    fn matches(&self, other: &Self) -> bool {
+        self.state == other.state
+            && self.temperature == other.temperature
+            && self.gamma == other.gamma
+            && self.next_state == other.next_state
+            && self.next_transition.abs_diff(other.next_transition) <= 1
+    }
+}
+
+/// The last `sunsetr-next.toml` content written by this process.
+static LAST_NEXT_TRANSITION: Mutex<Option<NextTransition>> = Mutex::new(None);
+
+/// Path of `sunsetr-next.toml`.
+pub fn next_transition_file_path() -> Result<std::path::PathBuf> {
+    Ok(crate::utils::runtime_dir()?.join("sunsetr-next.toml"))
+}
+
+/// Write `sunsetr-next.toml` if its content changed since the last write.
+///
+/// Returns whether the file was written. Like the status file, it is replaced
+/// atomically.
+pub fn write_next_transition(next: &NextTransition) -> Result<bool> {
+    let mut last = LAST_NEXT_TRANSITION
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if last.as_ref().is_some_and(|last| last.matches(next)) {
+        return Ok(false);
+    }
+
+    let path = next_transition_file_path()?;
+    let staging_path = path.with_extension("toml.new");
+    let content = toml::to_string(next).context("Failed to serialize next transition")?;
+    std::fs::write(&staging_path, content)
+        .with_context(|| format!("Failed to write {}", staging_path.display()))?;
+    std::fs::rename(&staging_path, &path)
+        .with_context(|| format!("Failed to replace {}", path.display()))?;
+    *last = Some(next.clone());
+    Ok(true)
+}
+
+/// Path of the status file for the given sunsetr PID.
+pub fn status_file_path(pid: u32) -> Result<String> {
+    Ok(crate::utils::runtime_dir()?
+        .join(format!("sunsetr-status-{}.tmp", pid))
+        .to_string_lossy()
+        .into_owned())
+}
+
+/// Write the status snapshot for the current process.
+///
+/// The snapshot is written to a sibling file and renamed into place so readers
+/// never observe a partially written file.
+pub fn write_status(snapshot: &StatusSnapshot) -> Result<()> {
+    let path = status_file_path(std::process::id())?;
+    let staging_path = format!("{}.new", path);
+    let content = toml::to_string(snapshot).context("Failed to serialize status")?;
+    std::fs::write(&staging_path, content)
+        .with_context(|| format!("Failed to write {}", staging_path))?;
+    std::fs::rename(&staging_path, &path).with_context(|| format!("Failed to replace {}", path))?;
+    Ok(())
+}
+
+/// Read the status snapshot published by the sunsetr instance with the given PID.
+pub fn read_status(pid: u32) -> Result<StatusSnapshot> {
+    let path = status_file_path(pid)?;
+    let content =
+        std::fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path))?;
+    toml::from_str(&content).with_context(|| format!("Failed to parse {}", path))
+}
+
+/// Remove the status file for the current process and `sunsetr-next.toml`, if any.
+pub fn remove_status() {
+    if let Ok(path) = status_file_path(std::process::id()) {
+        let _ = std::fs::remove_file(path);
+    }
+    if let Ok(path) = next_transition_file_path() {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Current time as a Unix timestamp in seconds.
+pub fn unix_now() -> u64 {
+    unix_timestamp(SystemTime::now())
+}
+
+/// `time` as a Unix timestamp in seconds.
+pub fn unix_timestamp(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_roundtrip_and_state_names() {
+        let snapshot = StatusSnapshot::new(
+            TransitionState::Transitioning {
+                from: TimeState::Day,
+                to: TimeState::Night,
+                progress: 0.25,
+            },
+            (5500, 97.5),
+            Duration::from_secs(60),
+            Some("relax".to_string()),
+            "wayland",
+        );
+        assert_eq!(snapshot.state, "sunset");
+        assert_eq!(snapshot.seconds_until_next_event(snapshot.updated), 60);
+
+        let parsed: StatusSnapshot = toml::from_str(&toml::to_string(&snapshot).unwrap()).unwrap();
+        assert_eq!(parsed, snapshot);
+
+        let night = StatusSnapshot::new(
+            TransitionState::Stable(TimeState::Night),
+            (3300, 90.0),
+            Duration::from_secs(3600),
+            None,
+            "hyprland",
+        );
+        assert_eq!(night.state, "night");
+        assert_eq!(night.progress, None);
+        assert_eq!(night.seconds_until_next_event(night.next_event + 5), 0);
+    }
+
+    #[test]
+    fn test_next_transition_names_and_change_detection() {
+        let day = NextTransition::new(
+            TransitionState::Stable(TimeState::Day),
+            (6500, 100.0),
+            Some(Duration::from_secs(3600)),
+        );
+        assert_eq!(
+            (day.state.as_str(), day.next_state.as_str()),
+            ("day", "sunset")
+        );
+        assert!(day.next_transition.abs_diff(unix_now() + 3600) <= 1);
+
+        let sunrise = NextTransition::new(
+            TransitionState::Transitioning {
+                from: TimeState::Night,
+                to: TimeState::Day,
+                progress: 0.5,
+            },
+            (4900, 95.0),
+            Some(Duration::from_secs(600)),
+        );
+        assert_eq!(
+            (sunrise.state.as_str(), sunrise.next_state.as_str()),
+            ("sunrise", "day")
+        );
+
+        let unscheduled = NextTransition::new(
+            TransitionState::Stable(TimeState::Night),
+            (3300, 90.0),
+            None,
+        );
+        assert_eq!(unscheduled.next_transition, 0);
+
+        let parsed: NextTransition = toml::from_str(&toml::to_string(&day).unwrap()).unwrap();
+        assert_eq!(parsed, day);
+
+        // A second of jitter between wake-ups doesn't count as a change
+        let mut later = day.clone();
+        later.next_transition += 1;
+        assert!(day.matches(&later));
+        later.next_transition += 60;
+        assert!(!day.matches(&later));
+        let mut warmer = day.clone();
+        warmer.temperature = 6400;
+        assert!(!day.matches(&warmer));
+    }
+}
@@ -24,14 +24,25 @@
 //! - **DST Transitions**: Properly manages daylight saving time transitions
 //! - **NTP Corrections**: Ignores small backwards time jumps (≤5 seconds) to prevent false positives
 //! - **Large Time Jumps**: Forces state recalculation for significant time changes
-
-use chrono::{Local, NaiveTime, Timelike};
+//!
+//! ## Daylight Saving Time Semantics
+//!
+//! Configured times are **local wall-clock times**: a 19:00 sunset happens when the
+//! clock reads 19:00, on either side of a DST change. Transition progress is measured
+//! in wall-clock time as well, so a transition spanning a DST change is shortened or
+//! lengthened by the shift. Sleep durations, on the other hand, are computed in
+//! absolute time so the main loop wakes exactly when the wall clock reaches the next
+//! event. Times that fall inside a spring-forward gap are treated as occurring when
+//! the clock jumps forward, and repeated fall-back times use their first occurrence.
+
+use chrono::{DateTime, Local, LocalResult, NaiveDateTime, NaiveTime, TimeZone, Timelike};
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering as AtomicOrdering};
 use std::time::{Duration as StdDuration, SystemTime};
 
-use crate::config::Config;
+use crate::config::{Config, ManualGeoEvent};
 use crate::constants::{
-    DEFAULT_DAY_GAMMA, DEFAULT_DAY_TEMP, DEFAULT_NIGHT_GAMMA, DEFAULT_NIGHT_TEMP,
-    DEFAULT_TRANSITION_DURATION, DEFAULT_UPDATE_INTERVAL,
+    APPEARANCE_RECHECK_SECS, DEFAULT_GEO_ANCHOR, MAXIMUM_TEMP, MAXIMUM_TEMPERATURE_NUDGE,
+    MINIMUM_TEMP,
 };
 // Note: We use crate::geo:: paths directly in the code below
 use crate::logger::Log;
@@ -212,72 +223,102 @@ pub enum TransitionState {
 /// - "center": Transition is centered on the configured time
 /// - "geo": Uses geographic coordinates to calculate actual sunrise/sunset times
 ///
+/// "manual_geo" combines the last two per event (see [`calculate_manual_geo_transition_windows`]).
+///
 /// # Arguments
 /// * `config` - Configuration containing sunset/sunrise times and transition settings
 ///
 /// # Returns
 /// Tuple of (sunset_start, sunset_end, sunrise_start, sunrise_end) as NaiveTime
 fn calculate_transition_windows(config: &Config) -> (NaiveTime, NaiveTime, NaiveTime, NaiveTime) {
-    let mode = config.transition_mode.as_deref().unwrap_or("finish_by");
+    // `seasonal_modes` can pick a different mode for today's date
+    let mode = config.current_transition_mode().unwrap_or("finish_by");
 
     // Handle geo mode separately using actual sunrise/sunset calculations
     if mode == "geo" {
         // For geo mode, use actual civil twilight transition times
         return calculate_geo_transition_windows(config);
     }
+    if mode == "manual_geo" {
+        return calculate_manual_geo_transition_windows(config);
+    }
 
+    calculate_manual_transition_windows(config, mode)
+}
+
+/// The transition windows for today as (sunset_start, sunset_end, sunrise_start, sunrise_end).
+///
+/// Exposed for config validation, which needs the computed windows of modes that
+/// follow the sun.
+pub fn transition_windows(config: &Config) -> (NaiveTime, NaiveTime, NaiveTime, NaiveTime) {
+    calculate_transition_windows(config)
+}
+
+/// Calculate transition windows from the configured `sunset`/`sunrise` times for a manual mode.
+fn calculate_manual_transition_windows(
+    config: &Config,
+    mode: &str,
+) -> (NaiveTime, NaiveTime, NaiveTime, NaiveTime) {
     let (sunset, sunrise) = (
         NaiveTime::parse_from_str(&config.sunset, "%H:%M:%S").unwrap(),
         NaiveTime::parse_from_str(&config.sunrise, "%H:%M:%S").unwrap(),
     );
 
-    let transition_duration = StdDuration::from_secs(
-        config
-            .transition_duration
-            .unwrap_or(DEFAULT_TRANSITION_DURATION)
-            * 60, // Convert minutes to seconds
-    );
-
-    let mode = config.transition_mode.as_deref().unwrap_or("finish_by");
+    // sunset_duration/sunrise_duration override transition_duration per event
+    let (sunset_minutes, sunrise_minutes) = config.transition_durations();
+    let sunset_duration = StdDuration::from_secs(sunset_minutes * 60);
+    let sunrise_duration = StdDuration::from_secs(sunrise_minutes * 60);
 
     match mode {
         "center" => {
-            // Use the shared centered transition logic with uniform durations
-            apply_centered_transition(sunset, transition_duration, sunrise, transition_duration)
+            // Use the shared centered transition logic with per-event durations
+            apply_centered_transition(sunset, sunset_duration, sunrise, sunrise_duration)
         }
         "start_at" => {
             // Transition begins at the configured time
-            let full_transition = chrono::Duration::from_std(transition_duration).unwrap();
-            (
-                sunset,                    // Sunset start: at sunset
-                sunset + full_transition,  // Sunset end: sunset + 30min
-                sunrise,                   // Sunrise start: at sunrise
-                sunrise + full_transition, // Sunrise end: sunrise + 30min
-            )
-        }
-        "finish_by" => {
-            // Transition completes at the configured time (default)
-            let full_transition = chrono::Duration::from_std(transition_duration).unwrap();
+            let sunset_full = chrono::Duration::from_std(sunset_duration).unwrap();
+            let sunrise_full = chrono::Duration::from_std(sunrise_duration).unwrap();
             (
-                sunset - full_transition,  // Sunset start: sunset - 30min
-                sunset,                    // Sunset end: at sunset
-                sunrise - full_transition, // Sunrise start: sunrise - 30min
-                sunrise,                   // Sunrise end: at sunrise
+                sunset,                 // Sunset start: at sunset
+                sunset + sunset_full,   // Sunset end: sunset + 30min
+                sunrise,                // Sunrise start: at sunrise
+                sunrise + sunrise_full, // Sunrise end: sunrise + 30min
             )
         }
         _ => {
-            // Default to "finish_by" mode for any unexpected values
-            let full_transition = chrono::Duration::from_std(transition_duration).unwrap();
+            // "finish_by" (default, also used for any unexpected values):
+            // transition completes at the configured time
+            let sunset_full = chrono::Duration::from_std(sunset_duration).unwrap();
+            let sunrise_full = chrono::Duration::from_std(sunrise_duration).unwrap();
             (
-                sunset - full_transition,
-                sunset,
-                sunrise - full_transition,
-                sunrise,
+                sunset - sunset_full,   // Sunset start: sunset - 30min
+                sunset,                 // Sunset end: at sunset
+                sunrise - sunrise_full, // Sunrise start: sunrise - 30min
+                sunrise,                // Sunrise end: at sunrise
             )
         }
     }
 }
 
+/// Calculate transition windows for "manual_geo" mode.
+///
+/// The event named by `manual_geo_event` (sunset by default) gets its window from
+/// the geo calculation. The other one uses its configured time and duration the way
+/// "finish_by" does, so it completes exactly at that time.
+fn calculate_manual_geo_transition_windows(
+    config: &Config,
+) -> (NaiveTime, NaiveTime, NaiveTime, NaiveTime) {
+    let (geo_sunset_start, geo_sunset_end, geo_sunrise_start, geo_sunrise_end) =
+        calculate_geo_transition_windows(config);
+    let (sunset_start, sunset_end, sunrise_start, sunrise_end) =
+        calculate_manual_transition_windows(config, "finish_by");
+
+    match config.manual_geo_event() {
+        ManualGeoEvent::Sunset => (geo_sunset_start, geo_sunset_end, sunrise_start, sunrise_end),
+        ManualGeoEvent::Sunrise => (sunset_start, sunset_end, geo_sunrise_start, geo_sunrise_end),
+    }
+}
+
 /// Calculate transition windows for geo mode using centered transition logic with solar data.
 ///
 /// This function demonstrates the architectural unification of geo mode with center mode.
@@ -309,7 +350,7 @@ fn calculate_geo_transition_windows(
     // Priority 1: Use coordinates from config if available
     if let (Some(lat), Some(lon)) = (config.latitude, config.longitude) {
         if let Ok((sunset_start, sunset_end, sunrise_start, sunrise_end)) =
-            crate::geo::solar::calculate_geo_transition_boundaries(lat, lon)
+            calculate_geo_boundaries(config, lat, lon)
         {
             // Use actual transition boundaries from solar calculations
             return (sunset_start, sunset_end, sunrise_start, sunrise_end);
@@ -324,7 +365,7 @@ fn calculate_geo_transition_windows(
     // Priority 2: Try timezone detection for automatic coordinates
     if let Ok((lat, lon, _city_name)) = detect_timezone_coordinates() {
         if let Ok((sunset_start, sunset_end, sunrise_start, sunrise_end)) =
-            crate::geo::solar::calculate_geo_transition_boundaries(lat, lon)
+            calculate_geo_boundaries(config, lat, lon)
         {
             // Use actual transition boundaries from solar calculations
             return (sunset_start, sunset_end, sunrise_start, sunrise_end);
@@ -350,6 +391,50 @@ fn calculate_geo_transition_windows(
     apply_centered_transition(sunset, default_duration, sunrise, default_duration)
 }
 
+/// Calculate geo transition boundaries for coordinates using the configured anchor.
+///
+/// The default `"twilight"` anchor uses the +10° to -2° solar elevation boundaries.
+/// With `"solar_noon"`, each transition is centered `geo_noon_offset` hours from solar
+/// noon and lasts `transition_duration` minutes, or `sunset_duration`/`sunrise_duration`
+/// when set.
+fn calculate_geo_boundaries(
+    config: &Config,
+    latitude: f64,
+    longitude: f64,
+) -> Result<(NaiveTime, NaiveTime, NaiveTime, NaiveTime), anyhow::Error> {
+    let anchor = config.geo_anchor.as_deref().unwrap_or(DEFAULT_GEO_ANCHOR);
+    let round_to_minutes = config.effective().geo_round_to_minutes;
+    if anchor != "solar_noon" {
+        return crate::geo::solar::calculate_geo_transition_boundaries(
+            latitude,
+            longitude,
+            crate::geo::solar::SolarPrecision::from_config(config),
+            crate::geo::solar::NightBoundary::from_config(config),
+            round_to_minutes,
+        );
+    }
+
+    let offset_hours = config.effective().geo_noon_offset;
+    let (sunset, sunrise) = crate::geo::solar::calculate_solar_noon_transition_centers(
+        latitude,
+        longitude,
+        offset_hours,
+    )?;
+    let (sunset_minutes, sunrise_minutes) = config.transition_durations();
+
+    let (sunset_start, sunset_end, sunrise_start, sunrise_end) = apply_centered_transition(
+        sunset,
+        StdDuration::from_secs(sunset_minutes * 60),
+        sunrise,
+        StdDuration::from_secs(sunrise_minutes * 60),
+    );
+    let (sunset_start, sunset_end) =
+        crate::geo::solar::snap_transition_window(sunset_start, sunset_end, round_to_minutes);
+    let (sunrise_start, sunrise_end) =
+        crate::geo::solar::snap_transition_window(sunrise_start, sunrise_end, round_to_minutes);
+    Ok((sunset_start, sunset_end, sunrise_start, sunrise_end))
+}
+
 /// Detect coordinates from system timezone.
 ///
 /// # Returns
@@ -370,14 +455,146 @@ fn detect_timezone_coordinates() -> Result<(f64, f64, String), anyhow::Error> {
 /// # Returns
 /// TransitionState indicating current state and any transition progress
 pub fn get_transition_state(config: &Config) -> TransitionState {
-    let now = Local::now().time();
-    let (sunset_start, sunset_end, _sunrise_start, _sunrise_end) =
-        calculate_transition_windows(config);
+    get_transition_state_at(config, local_now().time())
+}
+
+/// The current local time the schedule is evaluated at.
+///
+/// With the `testing-support` feature, `SUNSETR_FAKE_TIME=HH:MM:SS` pins the time
+/// of day, so end-to-end tests can start the real binary mid-transition.
+pub fn local_now() -> DateTime<Local> {
+    #[cfg(feature = "testing-support")]
+    if let Some(time) = std::env::var(crate::constants::FAKE_TIME_ENV)
+        .ok()
+        .and_then(|time| NaiveTime::parse_from_str(&time, "%H:%M:%S").ok())
+    {
+        return resolve_local_time(&Local, Local::now().date_naive().and_time(time));
+    }
+    Local::now()
+}
+
+/// Determine the transition state at a specific local wall-clock time.
+///
+/// This is the time-injectable core of [`get_transition_state`], useful for
+/// evaluating arbitrary times (e.g. across DST boundaries) in tests.
+///
+/// When day and night values are identical, transitions would change nothing, so
+/// the period being transitioned into is reported as stable instead.
+pub fn get_transition_state_at(config: &Config, now: NaiveTime) -> TransitionState {
+    // The desktop's light/dark setting decides instead of the clock
+    if crate::appearance::is_appearance_mode(config) {
+        return TransitionState::Stable(crate::appearance::current_scheme().time_state());
+    }
+
+    match scheduled_state_at(config, now) {
+        TransitionState::Transitioning { to, .. } if config.is_color_shift_disabled() => {
+            TransitionState::Stable(to)
+        }
+        state => state,
+    }
+}
+
+/// Determine where `now` falls in the configured schedule.
+fn scheduled_state_at(config: &Config, now: NaiveTime) -> TransitionState {
+    let ease_out_tail = config.effective().ease_out_tail_seconds;
+    let windows = calculate_transition_windows(config);
+    match (
+        state_in_windows(now, windows, ease_out_tail),
+        sunrise_hold(config),
+    ) {
+        (
+            TransitionState::Transitioning {
+                from: TimeState::Night,
+                to: TimeState::Day,
+                ..
+            },
+            Some(hold),
+        ) => TransitionState::Transitioning {
+            from: TimeState::Night,
+            to: TimeState::Day,
+            progress: calculate_sunrise_progress(now, windows.2, windows.3, ease_out_tail, hold),
+        },
+        (state, _) => state,
+    }
+}
+
+/// A pause partway through sunrise (`sunrise_hold_temp`, `sunrise_hold_minutes`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SunriseHold {
+    /// Sunrise progress at which the hold temperature is reached
+    progress: f32,
+    /// Length of the pause
+    seconds: i64,
+}
+
+/// The sunrise pause of `config`, if one is set.
+///
+/// The pause point is where the hold temperature falls between the effective night
+/// and day temperatures, so a profile that moves them moves it along.
+fn sunrise_hold(config: &Config) -> Option<SunriseHold> {
+    let (temp, minutes) = (config.sunrise_hold_temp?, config.sunrise_hold_minutes?);
+    let effective = config.effective();
+    if effective.day_temp == effective.night_temp {
+        return None;
+    }
+
+    let progress = (temp as f32 - effective.night_temp as f32)
+        / (effective.day_temp as f32 - effective.night_temp as f32);
+    Some(SunriseHold {
+        progress: progress.clamp(0.0, 1.0),
+        seconds: (minutes * 60) as i64,
+    })
+}
 
+/// Calculate sunrise progress with a plateau at the hold point.
+///
+/// The time outside the pause is split between the ramp up to the hold temperature
+/// and the ramp on to day in proportion to the temperature each covers, and both
+/// follow the regular curve, so they start and end without a jump. Only the final
+/// ramp gets the ease-out tail. When today's window is shorter than the pause (geo
+/// modes in winter), the pause fills the whole window.
+fn calculate_sunrise_progress(
+    now: NaiveTime,
+    start: NaiveTime,
+    end: NaiveTime,
+    ease_out_tail: u64,
+    hold: SunriseHold,
+) -> f32 {
+    let total = (end - start).num_seconds().rem_euclid(24 * 3600);
+    let pause = hold.seconds.min(total);
+    let first_ramp = ((total - pause) as f32 * hold.progress).round() as i64;
+    let pause_start = start + chrono::Duration::seconds(first_ramp);
+    let pause_end = pause_start + chrono::Duration::seconds(pause);
+
+    if is_time_in_range(now, start, pause_start) {
+        hold.progress * calculate_progress(now, start, pause_start, 0)
+    } else if is_time_in_range(now, pause_start, pause_end) {
+        hold.progress
+    } else {
+        hold.progress
+            + (1.0 - hold.progress) * calculate_progress(now, pause_end, end, ease_out_tail)
+    }
+}
+
+/// Determine where `now` falls relative to the given transition windows.
+///
+/// A zero-length window (`transition_duration = 0`, or a geo window that collapsed
+/// at some latitude and date) is never entered, so the state switches straight
+/// between `Stable(Day)` and `Stable(Night)` at the event time.
+fn state_in_windows(
+    now: NaiveTime,
+    (sunset_start, sunset_end, _sunrise_start, _sunrise_end): (
+        NaiveTime,
+        NaiveTime,
+        NaiveTime,
+        NaiveTime,
+    ),
+    ease_out_tail: u64,
+) -> TransitionState {
     // Check if we're in a transition period
     if is_time_in_range(now, sunset_start, sunset_end) {
         // Sunset transition (day -> night)
-        let progress = calculate_progress(now, sunset_start, sunset_end);
+        let progress = calculate_progress(now, sunset_start, sunset_end, ease_out_tail);
         TransitionState::Transitioning {
             from: TimeState::Day,
             to: TimeState::Night,
@@ -385,7 +602,7 @@ pub fn get_transition_state(config: &Config) -> TransitionState {
         }
     } else if is_time_in_range(now, _sunrise_start, _sunrise_end) {
         // Sunrise transition (night -> day)
-        let progress = calculate_progress(now, _sunrise_start, _sunrise_end);
+        let progress = calculate_progress(now, _sunrise_start, _sunrise_end, ease_out_tail);
         TransitionState::Transitioning {
             from: TimeState::Night,
             to: TimeState::Day,
@@ -460,49 +677,80 @@ fn get_stable_state_for_time(
 /// # Returns
 /// Duration to sleep before the next state check
 pub fn time_until_next_event(config: &Config) -> StdDuration {
-    // Get current transition state
-    let current_state = get_transition_state(config);
+    time_until_next_event_at(config, &local_now())
+}
 
-    match current_state {
+/// Calculate how long until the next transition event begins, from a specific moment.
+///
+/// Transition times are wall-clock times in `now`'s timezone, but the returned
+/// duration is absolute, so it stays correct when a DST change happens before
+/// the next event.
+pub fn time_until_next_event_at<Tz: TimeZone>(config: &Config, now: &DateTime<Tz>) -> StdDuration {
+    match get_transition_state_at(config, now.time()) {
         TransitionState::Transitioning { .. } => {
             // If we're currently transitioning, return the update interval for smooth progress
-            StdDuration::from_secs(config.update_interval.unwrap_or(DEFAULT_UPDATE_INTERVAL))
+            StdDuration::from_secs(config.effective().update_interval)
+        }
+        TransitionState::Stable(_) if crate::appearance::is_appearance_mode(config) => {
+            // Nothing is scheduled; appearance changes wake the main loop directly
+            StdDuration::from_secs(APPEARANCE_RECHECK_SECS)
         }
         TransitionState::Stable(_) => {
-            // Calculate time until next transition starts
-            let now = Local::now();
-            let today = now.date_naive();
-            let tomorrow = today + chrono::Duration::days(1);
-
+            // Calculate time until the next transition starts
             let (sunset_start, _sunset_end, sunrise_start, _sunrise_end) =
                 calculate_transition_windows(config);
 
-            // Create DateTime objects for today's transitions
-            let today_sunset = today.and_time(sunset_start);
-            let today_sunrise = today.and_time(sunrise_start);
-            let tomorrow_sunset = tomorrow.and_time(sunset_start);
-            let tomorrow_sunrise = tomorrow.and_time(sunrise_start);
-
-            // Find the next transition that occurs after now
-            let candidates = [
-                (today_sunset, "sunset"),
-                (today_sunrise, "sunrise"),
-                (tomorrow_sunset, "sunset"),
-                (tomorrow_sunrise, "sunrise"),
-            ];
-
-            let next_transition = candidates
-                .iter()
-                .filter(|(datetime, _)| *datetime > today.and_time(now.time()))
-                .min_by_key(|(datetime, _)| *datetime)
+            let next_transition = [sunset_start, sunrise_start]
+                .into_iter()
+                .map(|time| next_occurrence(now, time))
+                .min()
                 .expect("Should always find a next transition");
 
-            let duration_until = next_transition.0 - today.and_time(now.time());
-            StdDuration::from_secs(duration_until.num_seconds() as u64)
+            duration_between(now, &next_transition)
         }
     }
 }
 
+/// Find the next moment (strictly after `now`) when the local wall clock reads `time`.
+///
+/// Handles DST by resolving wall-clock times in `now`'s timezone: a time skipped by
+/// spring-forward resolves to the moment the clock jumps past it, and a time
+/// repeated by fall-back resolves to its first occurrence.
+fn next_occurrence<Tz: TimeZone>(now: &DateTime<Tz>, time: NaiveTime) -> DateTime<Tz> {
+    let today = now.date_naive();
+    (0..=2)
+        .map(|days| {
+            resolve_local_time(
+                &now.timezone(),
+                (today + chrono::Duration::days(days)).and_time(time),
+            )
+        })
+        .find(|candidate| candidate > now)
+        .expect("A wall-clock time always recurs within two days")
+}
+
+/// Resolve a naive local date/time to an instant in the given timezone.
+pub fn resolve_local_time<Tz: TimeZone>(tz: &Tz, naive: NaiveDateTime) -> DateTime<Tz> {
+    match tz.from_local_datetime(&naive) {
+        LocalResult::Single(datetime) => datetime,
+        // Fall-back: the time occurs twice, use the first occurrence
+        LocalResult::Ambiguous(earliest, _) => earliest,
+        // Spring-forward gap: the time never occurs, use the first minute after the jump
+        LocalResult::None => (1..=180)
+            .map(|minutes| naive + chrono::Duration::minutes(minutes))
+            .find_map(|shifted| tz.from_local_datetime(&shifted).earliest())
+            .unwrap_or_else(|| tz.from_utc_datetime(&naive)),
+    }
+}
+
+/// Absolute duration from `from` until `to`, or zero if `to` is not in the future.
+fn duration_between<Tz: TimeZone>(from: &DateTime<Tz>, to: &DateTime<Tz>) -> StdDuration {
+    to.clone()
+        .signed_duration_since(from.clone())
+        .to_std()
+        .unwrap_or(StdDuration::ZERO)
+}
+
 /// Calculate time remaining until the current transition ends.
 ///
 /// This function is used during transitions to determine if we should sleep
@@ -516,37 +764,24 @@ pub fn time_until_next_event(config: &Config) -> StdDuration {
 /// - `Some(duration)` if currently transitioning, with time until transition ends
 /// - `None` if not currently transitioning
 pub fn time_until_transition_end(config: &Config) -> Option<StdDuration> {
-    let current_state = get_transition_state(config);
+    time_until_transition_end_at(config, &local_now())
+}
 
-    match current_state {
+/// Calculate time remaining until the current transition ends, from a specific moment.
+///
+/// Like [`time_until_next_event_at`], the end time is a wall-clock time while the
+/// returned duration is absolute.
+pub fn time_until_transition_end_at<Tz: TimeZone>(
+    config: &Config,
+    now: &DateTime<Tz>,
+) -> Option<StdDuration> {
+    match get_transition_state_at(config, now.time()) {
         TransitionState::Transitioning { from, to, .. } => {
-            let now = Local::now().time();
-
             // Get the end time for the current transition
             let transition_end = get_current_transition_end_time(config, from, to)?;
 
-            // Calculate duration until transition ends
-            // Handle potential midnight crossing
-            let now_secs =
-                now.hour() as i32 * 3600 + now.minute() as i32 * 60 + now.second() as i32;
-            let end_secs = transition_end.hour() as i32 * 3600
-                + transition_end.minute() as i32 * 60
-                + transition_end.second() as i32;
-
-            let seconds_remaining = if end_secs >= now_secs {
-                // Normal case: end time is later today
-                end_secs - now_secs
-            } else {
-                // Midnight crossing: end time is tomorrow
-                (24 * 3600 - now_secs) + end_secs
-            };
-
-            if seconds_remaining > 0 {
-                Some(StdDuration::from_secs(seconds_remaining as u64))
-            } else {
-                // We've passed the end time (shouldn't normally happen)
-                Some(StdDuration::from_secs(0))
-            }
+            // The next time the clock reads the end time (handles midnight crossing)
+            Some(duration_between(now, &next_occurrence(now, transition_end)))
         }
         TransitionState::Stable(_) => None,
     }
@@ -581,20 +816,32 @@ fn get_current_transition_end_time(
 ///
 /// This function calculates linear progress and then applies a Bezier curve
 /// transformation to create smooth, natural-looking transitions that start
-/// and end with zero slope.
+/// and end with zero slope. With a non-zero `ease_out_tail` the final seconds
+/// are reshaped to settle even more gently (see [`apply_ease_out_tail`]).
 ///
 /// # Arguments
 /// * `now` - Current time within the transition window
 /// * `start` - When the transition began
 /// * `end` - When the transition will complete
+/// * `ease_out_tail` - Seconds at the end of the transition to ease out over
 ///
 /// # Returns
 /// Progress value transformed by Bezier curve, clamped between 0.0 and 1.0
-fn calculate_progress(now: NaiveTime, start: NaiveTime, end: NaiveTime) -> f32 {
+fn calculate_progress(now: NaiveTime, start: NaiveTime, end: NaiveTime, ease_out_tail: u64) -> f32 {
     let total_duration = (end - start).num_seconds() as f32;
+    if total_duration == 0.0 {
+        // A sub-second window is already over; avoid 0/0 turning into NaN progress
+        return 1.0;
+    }
     let elapsed = (now - start).num_seconds() as f32;
     let linear_progress = (elapsed / total_duration).clamp(0.0, 1.0);
 
+    if ease_out_tail > 0 && total_duration > 0.0 {
+        // The tail never takes over more than half of the transition
+        let tail_fraction = (ease_out_tail as f32 / total_duration).min(0.5);
+        return apply_ease_out_tail(linear_progress, tail_fraction);
+    }
+
     // Apply Bezier curve with control points from constants for smooth S-curve
     // These control points create an ease-in-out effect with no sudden jumps
     crate::utils::bezier_curve(
@@ -606,6 +853,48 @@ fn calculate_progress(now: NaiveTime, start: NaiveTime, end: NaiveTime) -> f32 {
     )
 }
 
+/// Apply the transition curve with a gentler settle over its last `tail_fraction`.
+///
+/// Up to the tail, progress follows the regular Bezier curve. Over the tail it follows
+/// a quintic Hermite segment that matches the curve's value, slope and curvature where
+/// the tail begins, and arrives at 1.0 with zero slope and zero curvature. The final
+/// updates before the stable state therefore change the display less and less,
+/// instead of stopping on a small but visible step.
+fn apply_ease_out_tail(linear_progress: f32, tail_fraction: f32) -> f32 {
+    use crate::constants::{BEZIER_P1X, BEZIER_P1Y, BEZIER_P2X, BEZIER_P2Y};
+
+    let tail_start = 1.0 - tail_fraction;
+    if linear_progress <= tail_start {
+        return crate::utils::bezier_curve(
+            linear_progress,
+            BEZIER_P1X,
+            BEZIER_P1Y,
+            BEZIER_P2X,
+            BEZIER_P2Y,
+        );
+    }
+
+    // Curve value and derivatives at the start of the tail (X coordinates are unused,
+    // like in bezier_curve). Derivatives are scaled to the tail's own 0..1 parameter.
+    let (t, p1, p2) = (tail_start as f64, BEZIER_P1Y as f64, BEZIER_P2Y as f64);
+    let value = 3.0 * (1.0 - t).powi(2) * t * p1 + 3.0 * (1.0 - t) * t * t * p2 + t.powi(3);
+    let slope =
+        3.0 * (1.0 - t).powi(2) * p1 + 6.0 * (1.0 - t) * t * (p2 - p1) + 3.0 * t * t * (1.0 - p2);
+    let curvature = 6.0 * (1.0 - t) * (p2 - 2.0 * p1) + 6.0 * t * (1.0 - 2.0 * p2 + p1);
+    let length = tail_fraction as f64;
+    let (v0, m0, a0) = (value, slope * length, curvature * length * length);
+
+    // Quintic Hermite basis, ending at 1.0 with zero slope and curvature
+    let u = ((linear_progress - tail_start) / tail_fraction).clamp(0.0, 1.0) as f64;
+    let (u2, u3, u4, u5) = (u * u, u.powi(3), u.powi(4), u.powi(5));
+    let h0 = 1.0 - 10.0 * u3 + 15.0 * u4 - 6.0 * u5;
+    let h1 = u - 6.0 * u3 + 8.0 * u4 - 3.0 * u5;
+    let h2 = 0.5 * u2 - 1.5 * u3 + 1.5 * u4 - 0.5 * u5;
+    let h5 = 10.0 * u3 - 15.0 * u4 + 6.0 * u5;
+
+    ((h0 * v0 + h1 * m0 + h2 * a0 + h5) as f32).clamp(0.0, 1.0)
+}
+
 /// Check if a time falls within a given range, handling midnight crossings.
 ///
 /// This function correctly handles cases where the time range crosses midnight
@@ -637,26 +926,138 @@ fn is_time_in_range(time: NaiveTime, start: NaiveTime, end: NaiveTime) -> bool {
     }
 }
 
+/// Current transition state of each `[output_groups.<name>]` schedule, by group name.
+pub fn get_output_group_states(config: &Config) -> Vec<(String, TransitionState)> {
+    config
+        .output_group_configs()
+        .into_iter()
+        .map(|(name, _, group_config)| (name, get_transition_state(&group_config)))
+        .collect()
+}
+
+/// Temporary temperature offset set with `--nudge`, in Kelvin.
+///
+/// Lives outside the config so reloads and profile switches keep it. The main loop
+/// clears it at the next day/night or transition change.
+static TEMPERATURE_NUDGE: AtomicI32 = AtomicI32::new(0);
+
+/// The active temperature nudge in Kelvin (0 when none is active).
+pub fn temperature_nudge() -> i32 {
+    TEMPERATURE_NUDGE.load(AtomicOrdering::SeqCst)
+}
+
+/// Replace the active temperature nudge; 0 clears it.
+pub fn set_temperature_nudge(kelvin: i32) {
+    TEMPERATURE_NUDGE.store(kelvin, AtomicOrdering::SeqCst);
+}
+
+/// Add `step` to the `current` nudge, keeping the total within the nudge limit.
+///
+/// A step of 0 resets the nudge.
+pub fn combine_nudge(current: i32, step: i32) -> i32 {
+    if step == 0 {
+        return 0;
+    }
+    current
+        .saturating_add(step)
+        .clamp(-MAXIMUM_TEMPERATURE_NUDGE, MAXIMUM_TEMPERATURE_NUDGE)
+}
+
+/// Offset `temp` by `nudge` Kelvin, staying within the supported temperature range.
+pub fn apply_nudge(temp: u32, nudge: i32) -> u32 {
+    (i64::from(temp) + i64::from(nudge)).clamp(i64::from(MINIMUM_TEMP), i64::from(MAXIMUM_TEMP))
+        as u32
+}
+
+/// `temp` with the active temperature nudge applied.
+pub fn nudged_temperature(temp: u32) -> u32 {
+    match temperature_nudge() {
+        0 => temp,
+        nudge => apply_nudge(temp, nudge),
+    }
+}
+
+/// Whether the session is idle and `idle_gamma` dims the screen further.
+///
+/// Set by the main loop from the idle monitor's events, and like the nudge it lives
+/// outside the config so reloads and profile switches keep it.
+static IDLE_DIMMED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the idle dimming is currently applied.
+pub fn idle_dimmed() -> bool {
+    IDLE_DIMMED.load(AtomicOrdering::SeqCst)
+}
+
+/// Turn the idle dimming on or off.
+pub fn set_idle_dimmed(dimmed: bool) {
+    IDLE_DIMMED.store(dimmed, AtomicOrdering::SeqCst);
+}
+
+/// Scale `gamma` (in percent) by `idle_gamma` percent, the brightness kept while idle.
+pub fn apply_idle_gamma(gamma: f32, idle_gamma: f32) -> f32 {
+    gamma * idle_gamma / 100.0
+}
+
 /// Calculate the initial temperature and gamma values for a given transition state
 /// This is used to start hyprsunset with the correct initial values
+///
+/// Mid-transition temperatures are snapped to `temp_quantum`; the transition
+/// endpoints are always returned exactly. An active `--nudge` offset is applied
+/// on top, and while the session is idle gamma is scaled by `idle_gamma`.
 pub fn get_initial_values_for_state(state: TransitionState, config: &Config) -> (u32, f32) {
-    match state {
+    let effective = config.effective();
+    let (temp, gamma) = match state {
         TransitionState::Stable(time_state) => match time_state {
-            TimeState::Day => (
-                config.day_temp.unwrap_or(DEFAULT_DAY_TEMP),
-                config.day_gamma.unwrap_or(DEFAULT_DAY_GAMMA),
-            ),
-            TimeState::Night => (
-                config.night_temp.unwrap_or(DEFAULT_NIGHT_TEMP),
-                config.night_gamma.unwrap_or(DEFAULT_NIGHT_GAMMA),
-            ),
+            TimeState::Day => (effective.day_temp, effective.day_gamma),
+            TimeState::Night => (effective.night_temp, effective.night_gamma),
         },
         TransitionState::Transitioning { from, to, progress } => {
-            let temp = calculate_interpolated_temp(from, to, progress, config);
+            let mut temp = calculate_interpolated_temp(from, to, progress, config);
+            if progress > 0.0 && progress < 1.0 {
+                let start = calculate_interpolated_temp(from, to, 0.0, config);
+                let end = calculate_interpolated_temp(from, to, 1.0, config);
+                temp = snap_temperature(temp, effective.temp_quantum)
+                    .clamp(start.min(end), start.max(end));
+            }
             let gamma = calculate_interpolated_gamma(from, to, progress, config);
             (temp, gamma)
         }
+    };
+    let gamma = if idle_dimmed() {
+        apply_idle_gamma(gamma, effective.idle_gamma)
+    } else {
+        gamma
+    };
+    (nudged_temperature(temp), gamma)
+}
+
+/// Compute the temperature and gamma the schedule asks for at `at`, without a backend.
+///
+/// Combines [`get_transition_state_at`] and [`get_initial_values_for_state`], so the
+/// result is exactly what sunsetr would apply at that time: Kelvin and gamma in percent,
+/// interpolated during transitions. Only the time of day of `at` is used; in geo mode
+/// the transition windows are those of today's sun times.
+///
+/// ```no_run
+/// use sunsetr::{Config, current_color};
+///
+/// let config = Config::load()?;
+/// let (temperature, gamma) = current_color(&config, chrono::Local::now());
+/// println!("{temperature}K at {gamma:.0}%");
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[allow(dead_code)] // Only used through the library crate
+pub fn current_color(config: &Config, at: DateTime<Local>) -> (u32, f32) {
+    let state = get_transition_state_at(config, at.time());
+    get_initial_values_for_state(state, config)
+}
+
+/// Round a temperature to the nearest multiple of `quantum` Kelvin.
+fn snap_temperature(temp: u32, quantum: u32) -> u32 {
+    if quantum <= 1 {
+        return temp;
     }
+    ((temp + quantum / 2) / quantum) * quantum
 }
 
 /// Helper for calculating interpolated temperature
@@ -666,24 +1067,13 @@ pub fn calculate_interpolated_temp(
     progress: f32,
     config: &Config,
 ) -> u32 {
+    let effective = config.effective();
     let (start_temp, end_temp) = match (from, to) {
-        (TimeState::Day, TimeState::Night) => (
-            config.day_temp.unwrap_or(DEFAULT_DAY_TEMP),
-            config.night_temp.unwrap_or(DEFAULT_NIGHT_TEMP),
-        ),
-        (TimeState::Night, TimeState::Day) => (
-            config.night_temp.unwrap_or(DEFAULT_NIGHT_TEMP),
-            config.day_temp.unwrap_or(DEFAULT_DAY_TEMP),
-        ),
+        (TimeState::Day, TimeState::Night) => (effective.day_temp, effective.night_temp),
+        (TimeState::Night, TimeState::Day) => (effective.night_temp, effective.day_temp),
         // Handle edge cases
-        (TimeState::Day, TimeState::Day) => {
-            let day_temp = config.day_temp.unwrap_or(DEFAULT_DAY_TEMP);
-            (day_temp, day_temp)
-        }
-        (TimeState::Night, TimeState::Night) => {
-            let night_temp = config.night_temp.unwrap_or(DEFAULT_NIGHT_TEMP);
-            (night_temp, night_temp)
-        }
+        (TimeState::Day, TimeState::Day) => (effective.day_temp, effective.day_temp),
+        (TimeState::Night, TimeState::Night) => (effective.night_temp, effective.night_temp),
     };
 
     interpolate_u32(start_temp, end_temp, progress)
@@ -696,29 +1086,57 @@ pub fn calculate_interpolated_gamma(
     progress: f32,
     config: &Config,
 ) -> f32 {
+    let effective = config.effective();
     let (start_gamma, end_gamma) = match (from, to) {
-        (TimeState::Day, TimeState::Night) => (
-            config.day_gamma.unwrap_or(DEFAULT_DAY_GAMMA),
-            config.night_gamma.unwrap_or(DEFAULT_NIGHT_GAMMA),
-        ),
-        (TimeState::Night, TimeState::Day) => (
-            config.night_gamma.unwrap_or(DEFAULT_NIGHT_GAMMA),
-            config.day_gamma.unwrap_or(DEFAULT_DAY_GAMMA),
-        ),
+        (TimeState::Day, TimeState::Night) => (effective.day_gamma, effective.night_gamma),
+        (TimeState::Night, TimeState::Day) => (effective.night_gamma, effective.day_gamma),
         // Handle edge cases
-        (TimeState::Day, TimeState::Day) => {
-            let day_gamma = config.day_gamma.unwrap_or(DEFAULT_DAY_GAMMA);
-            (day_gamma, day_gamma)
-        }
-        (TimeState::Night, TimeState::Night) => {
-            let night_gamma = config.night_gamma.unwrap_or(DEFAULT_NIGHT_GAMMA);
-            (night_gamma, night_gamma)
-        }
+        (TimeState::Day, TimeState::Day) => (effective.day_gamma, effective.day_gamma),
+        (TimeState::Night, TimeState::Night) => (effective.night_gamma, effective.night_gamma),
     };
 
+    let progress = gamma_progress(progress, effective.gamma_progress_offset);
     interpolate_f32(start_gamma, end_gamma, progress)
 }
 
+/// Progress of the gamma ramp at transition `progress`, for `gamma_progress_offset`.
+///
+/// A positive `offset` holds gamma back until that much of the transition has
+/// passed, a negative one lets it arrive that much early. The ramp is compressed
+/// rather than shifted, so it still starts and ends within the transition.
+pub fn gamma_progress(progress: f32, offset: f32) -> f32 {
+    if offset > 0.0 {
+        ((progress - offset) / (1.0 - offset)).clamp(0.0, 1.0)
+    } else if offset < 0.0 {
+        (progress / (1.0 + offset)).clamp(0.0, 1.0)
+    } else {
+        progress
+    }
+}
+
+/// Per-channel gamma multipliers (red, green, blue) for a transition state.
+///
+/// Interpolated between the day and night multipliers with the same progress as
+/// the temperature and gamma.
+pub fn get_channel_gamma_for_state(state: TransitionState, config: &Config) -> (f32, f32, f32) {
+    let values = |time_state| match time_state {
+        TimeState::Day => config.day_channel_gamma(),
+        TimeState::Night => config.night_channel_gamma(),
+    };
+
+    match state {
+        TransitionState::Stable(time_state) => values(time_state),
+        TransitionState::Transitioning { from, to, progress } => {
+            let (start, end) = (values(from), values(to));
+            (
+                interpolate_f32(start.0, end.0, progress),
+                interpolate_f32(start.1, end.1, progress),
+                interpolate_f32(start.2, end.2, progress),
+            )
+        }
+    }
+}
+
 /// Get the name of the transition type (for use in "Commencing/Completed" messages).
 ///
 /// Returns just the transition name without the "Commencing" prefix.
@@ -778,14 +1196,28 @@ pub fn should_update_state(
         TransitionState::Transitioning { .. } => {
             // Use actual sleep duration if available (handles shortened final update)
             // Otherwise use the configured update interval
-            actual_sleep_duration
-                .or_else(|| Some(config.update_interval.unwrap_or(DEFAULT_UPDATE_INTERVAL)))
+            actual_sleep_duration.or_else(|| Some(config.effective().update_interval))
         }
         TransitionState::Stable(_) => None, // No regular interval expected in stable state
     };
 
-    let (force_update_due_to_time_jump, anomaly_message) =
-        detect_time_anomaly(current_time, last_check_time, expected_interval);
+    // In a stable state the loop sleeps until the next event, or less when a signal
+    // wakes it early. Waking by that deadline is routine and must not touch the screen;
+    // only oversleeping it (suspend) or the clock going backwards is an anomaly.
+    let woke_on_schedule = matches!(current_state, TransitionState::Stable(_))
+        && actual_sleep_duration.is_some_and(|sleep_secs| {
+            current_time
+                .duration_since(last_check_time)
+                .is_ok_and(|elapsed| {
+                    elapsed.as_secs() <= sleep_secs + crate::constants::SHORT_SUSPEND_THRESHOLD_SECS
+                })
+        });
+
+    let (force_update_due_to_time_jump, anomaly_message) = if woke_on_schedule {
+        (false, None)
+    } else {
+        detect_time_anomaly(current_time, last_check_time, expected_interval)
+    };
 
     // Log any detected time anomalies following logging style guide
     if let Some(message) = anomaly_message {
@@ -798,11 +1230,19 @@ pub fn should_update_state(
 
     match (current_state, new_state) {
         // Detect entering a transition (from stable to transitioning)
-        (TransitionState::Stable(_), TransitionState::Transitioning { progress, from, to })
-            if *progress < 0.01 =>
-        {
+        (TransitionState::Stable(_), TransitionState::Transitioning { progress, from, to }) => {
             let transition_type = get_transition_type_name(*from, *to);
-            Log::log_block_start(&format!("Commencing {}", transition_type));
+            if *progress < 0.01 {
+                Log::log_block_start(&format!("Commencing {}", transition_type));
+            } else {
+                // We woke up after the transition started (e.g. across a DST change),
+                // so join it at its current progress rather than waiting for it to end
+                Log::log_block_start(&format!(
+                    "Joining {} already in progress ({:.0}%)",
+                    transition_type,
+                    progress * 100.0
+                ));
+            }
             true
         }
         // Detect change from transitioning to stable state (transition completed)
@@ -850,6 +1290,27 @@ pub fn should_update_state(
     }
 }
 
+/// Whether `current` is on the other side of a day/night or transition boundary from
+/// `applied`, as opposed to the same transition having moved on a little.
+///
+/// Used around startup, where the state applied first can be seconds old by the time
+/// the main loop runs. Progress drift is left to the regular updates, but a crossed
+/// boundary means the screen shows the wrong phase until it is applied again.
+pub fn crossed_state_boundary(applied: TransitionState, current: TransitionState) -> bool {
+    match (applied, current) {
+        (TransitionState::Stable(a), TransitionState::Stable(b)) => a != b,
+        (
+            TransitionState::Transitioning { from, to, .. },
+            TransitionState::Transitioning {
+                from: now_from,
+                to: now_to,
+                ..
+            },
+        ) => from != now_from || to != now_to,
+        _ => true,
+    }
+}
+
 /// Get the appropriate log message for announcing a stable state.
 ///
 /// Returns the standardized message with icons for entering day or night mode.
@@ -892,27 +1353,85 @@ mod tests {
     use super::*;
     use crate::constants::{
         DEFAULT_DAY_GAMMA, DEFAULT_DAY_TEMP, DEFAULT_NIGHT_GAMMA, DEFAULT_NIGHT_TEMP,
-        DEFAULT_UPDATE_INTERVAL,
+        DEFAULT_UPDATE_INTERVAL, NEUTRAL_TEMP,
     };
     use std::time::Duration;
 
     fn create_test_config(sunset: &str, sunrise: &str, mode: &str, duration_mins: u64) -> Config {
         Config {
             start_hyprsunset: Some(false),
+            hyprsunset_socket: None,
+            hyprsunset_connect_timeout: None,
+            hyprsunset_connect_retries: None,
             backend: Some(crate::config::Backend::Auto),
+            auto_prefer: None,
             startup_transition: Some(false),
             startup_transition_duration: Some(10),
+            startup_delay_ms: None,
             latitude: None,
             longitude: None,
+            geo_anchor: None,
+            geo_noon_offset: None,
+            solar_precision: None,
+            geo_night_boundary: None,
+            geo_round_to_minutes: None,
             sunset: sunset.to_string(),
             sunrise: sunrise.to_string(),
             night_temp: Some(DEFAULT_NIGHT_TEMP),
             day_temp: Some(DEFAULT_DAY_TEMP),
             night_gamma: Some(DEFAULT_NIGHT_GAMMA),
             day_gamma: Some(DEFAULT_DAY_GAMMA),
+            color_shift: None,
+            temp_quantum: None,
+            temp_unit: None,
+            day_whitepoint: None,
+            night_gamma_r: None,
+            night_gamma_g: None,
+            night_gamma_b: None,
+            day_gamma_r: None,
+            day_gamma_g: None,
+            day_gamma_b: None,
+            outputs_include: None,
+            outputs_exclude: None,
+            hdr_outputs: None,
+            hdr_night_temp: None,
+            hdr_day_temp: None,
+            hdr_night_gamma: None,
+            hdr_day_gamma: None,
+            verify_gamma: None,
+            precompute_ramps: None,
+            gamma_table_format: None,
+            require_outputs: None,
+            reset_on_exit: None,
+            on_fatal_exit: None,
+            metrics_port: None,
+            event_fifo: None,
+            reapply_on_unlock: None,
+            wake_fade_ms: None,
+            idle_timeout: None,
+            idle_gamma: None,
+            log_file: None,
+            log_file_max_bytes: None,
+            log_file_keep: None,
+            log_to_console: None,
+            log_verbosity: None,
             transition_duration: Some(duration_mins),
+            sunset_duration: None,
+            sunrise_duration: None,
+            sunrise_hold_temp: None,
+            sunrise_hold_minutes: None,
             update_interval: Some(DEFAULT_UPDATE_INTERVAL),
+            ease_out_tail_seconds: None,
+            gamma_progress_offset: None,
             transition_mode: Some(mode.to_string()),
+            manual_geo_event: None,
+            seasonal_modes: None,
+            on_day_command: None,
+            on_night_command: None,
+            on_transition_start: None,
+            active_profile: None,
+            profiles: None,
+            output_groups: None,
         }
     }
 
@@ -952,6 +1471,254 @@ mod tests {
         assert_eq!(sunrise_end, NaiveTime::from_hms_opt(6, 15, 0).unwrap());
     }
 
+    #[test]
+    fn test_calculate_transition_windows_solar_noon_anchor() {
+        let mut config = create_test_config("19:00:00", "06:00:00", "geo", 40);
+        config.latitude = Some(69.65);
+        config.longitude = Some(18.96);
+        config.geo_anchor = Some("solar_noon".to_string());
+        config.geo_noon_offset = Some(7.0);
+
+        let (sunset_start, sunset_end, sunrise_start, sunrise_end) =
+            calculate_transition_windows(&config);
+
+        // Both transitions use transition_duration, even in geo mode
+        assert_eq!((sunset_end - sunset_start).num_minutes(), 40);
+        assert_eq!((sunrise_end - sunrise_start).num_minutes(), 40);
+
+        // The centers are 14 hours apart across noon, leaving a 10 hour night
+        let sunset_center = sunset_start + chrono::Duration::minutes(20);
+        let sunrise_center = sunrise_start + chrono::Duration::minutes(20);
+        let day =
+            (sunset_center - sunrise_center + chrono::Duration::days(1)).num_seconds() % 86400;
+        assert!((day - 14 * 3600).abs() <= 1);
+    }
+
+    #[test]
+    fn test_calculate_transition_windows_manual_geo() {
+        let mut config = create_test_config("19:00:00", "07:30:00", "manual_geo", 30);
+        config.latitude = Some(51.5074);
+        config.longitude = Some(-0.1278);
+        config.sunrise_duration = Some(20);
+
+        let mut geo_config = config.clone();
+        geo_config.transition_mode = Some("geo".to_string());
+        let (geo_sunset_start, geo_sunset_end, geo_sunrise_start, geo_sunrise_end) =
+            calculate_transition_windows(&geo_config);
+
+        // Default: sunset follows the sun, sunrise finishes at the configured time
+        let (sunset_start, sunset_end, sunrise_start, sunrise_end) =
+            calculate_transition_windows(&config);
+        assert_eq!(
+            (sunset_start, sunset_end),
+            (geo_sunset_start, geo_sunset_end)
+        );
+        assert_eq!(sunrise_start, NaiveTime::from_hms_opt(7, 10, 0).unwrap());
+        assert_eq!(sunrise_end, NaiveTime::from_hms_opt(7, 30, 0).unwrap());
+
+        // And the other way around
+        config.manual_geo_event = Some("sunrise".to_string());
+        let (sunset_start, sunset_end, sunrise_start, sunrise_end) =
+            calculate_transition_windows(&config);
+        assert_eq!(sunset_start, NaiveTime::from_hms_opt(18, 30, 0).unwrap());
+        assert_eq!(sunset_end, NaiveTime::from_hms_opt(19, 0, 0).unwrap());
+        assert_eq!(
+            (sunrise_start, sunrise_end),
+            (geo_sunrise_start, geo_sunrise_end)
+        );
+    }
+
+    #[test]
+    fn test_channel_gamma_follows_state() {
+        let mut config = create_test_config("19:00:00", "06:00:00", "finish_by", 30);
+        config.night_gamma_b = Some(0.6);
+        config.day_gamma_b = Some(0.9);
+
+        let night = TransitionState::Stable(TimeState::Night);
+        assert_eq!(get_channel_gamma_for_state(night, &config), (1.0, 1.0, 0.6));
+
+        let halfway = TransitionState::Transitioning {
+            from: TimeState::Night,
+            to: TimeState::Day,
+            progress: 0.5,
+        };
+        let (r, g, b) = get_channel_gamma_for_state(halfway, &config);
+        assert_eq!((r, g), (1.0, 1.0));
+        assert!((b - 0.75).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_gamma_progress_offset() {
+        // The endpoints never move, whatever the offset
+        for offset in [-0.5, -0.2, 0.0, 0.3, 0.5] {
+            assert_eq!(gamma_progress(0.0, offset), 0.0);
+            assert_eq!(gamma_progress(1.0, offset), 1.0);
+        }
+        assert_eq!(gamma_progress(0.4, 0.0), 0.4);
+        assert_eq!(gamma_progress(0.2, 0.25), 0.0);
+        assert!((gamma_progress(0.625, 0.25) - 0.5).abs() < 1e-6);
+        assert!((gamma_progress(0.4, -0.2) - 0.5).abs() < 1e-6);
+        assert_eq!(gamma_progress(0.9, -0.2), 1.0);
+
+        // A lagging gamma stays at day brightness while the temperature already warms
+        let mut config = create_test_config("19:00:00", "06:00:00", "finish_by", 30);
+        config.gamma_progress_offset = Some(0.5);
+        let early_sunset = TransitionState::Transitioning {
+            from: TimeState::Day,
+            to: TimeState::Night,
+            progress: 0.4,
+        };
+        let (temp, gamma) = get_initial_values_for_state(early_sunset, &config);
+        assert!(temp < config.effective().day_temp);
+        assert_eq!(gamma, config.effective().day_gamma);
+    }
+
+    #[test]
+    fn test_color_shift_disabled_only_dims() {
+        let mut config = create_test_config("19:00:00", "06:00:00", "finish_by", 30);
+        config.night_temp = Some(3300);
+        config.night_gamma = Some(80.0);
+        config.color_shift = Some(false);
+
+        let night = TransitionState::Stable(TimeState::Night);
+        assert_eq!(
+            get_initial_values_for_state(night, &config),
+            (NEUTRAL_TEMP, 80.0)
+        );
+
+        let sunset = TransitionState::Transitioning {
+            from: TimeState::Day,
+            to: TimeState::Night,
+            progress: 0.5,
+        };
+        let (temp, gamma) = get_initial_values_for_state(sunset, &config);
+        assert_eq!(temp, NEUTRAL_TEMP);
+        assert!(gamma > 80.0 && gamma < config.effective().day_gamma);
+
+        // Color shift is on by default
+        config.color_shift = None;
+        assert_eq!(get_initial_values_for_state(night, &config).0, 3300);
+    }
+
+    #[test]
+    fn test_sunrise_hold_plateau() {
+        // 05:00-06:00 sunrise, pausing at the midpoint temperature for 20 minutes
+        let mut config = create_test_config("19:00:00", "06:00:00", "finish_by", 60);
+        config.sunrise_hold_temp = Some(4900);
+        config.sunrise_hold_minutes = Some(20);
+        let temp_at = |time: &str| {
+            let time = NaiveTime::parse_from_str(time, "%H:%M:%S").unwrap();
+            get_initial_values_for_state(get_transition_state_at(&config, time), &config).0
+        };
+
+        // The 40 ramp minutes split evenly around the 05:20-05:40 pause
+        assert_eq!(temp_at("05:20:00"), 4900);
+        assert_eq!(temp_at("05:30:00"), 4900);
+        assert_eq!(temp_at("05:39:59"), 4900);
+
+        let before = temp_at("05:10:00");
+        assert!(before > 3300 && before < 4900, "{}", before);
+        let after = temp_at("05:50:00");
+        assert!(after > 4900 && after < 6500, "{}", after);
+
+        // The ramp arrives at the plateau without a jump
+        let arriving = temp_at("05:18:00");
+        assert!(arriving > 4850 && arriving < 4900, "{}", arriving);
+
+        // Sunset is untouched
+        config.sunrise_hold_temp = None;
+        config.sunrise_hold_minutes = None;
+        let plain = get_transition_state_at(&config, NaiveTime::from_hms_opt(18, 45, 0).unwrap());
+        config.sunrise_hold_temp = Some(4900);
+        config.sunrise_hold_minutes = Some(20);
+        let held = get_transition_state_at(&config, NaiveTime::from_hms_opt(18, 45, 0).unwrap());
+        assert_eq!(plain, held);
+    }
+
+    #[test]
+    fn test_start_at_sunset_boundary() {
+        // Sunset runs 18:30-19:00; starting a second before it, the startup apply
+        // captures day, but the main loop's first pass already sees the transition
+        let config = create_test_config("19:00:00", "06:00:00", "finish_by", 30);
+        let at =
+            |h, m, s| get_transition_state_at(&config, NaiveTime::from_hms_opt(h, m, s).unwrap());
+
+        let captured = at(18, 29, 59);
+        assert_eq!(captured, TransitionState::Stable(TimeState::Day));
+        let first_pass = at(18, 30, 0);
+        assert!(matches!(
+            first_pass,
+            TransitionState::Transitioning {
+                from: TimeState::Day,
+                to: TimeState::Night,
+                ..
+            }
+        ));
+        assert!(crossed_state_boundary(captured, first_pass));
+
+        // Progress drift within the transition is left to the regular updates
+        assert!(!crossed_state_boundary(first_pass, at(18, 30, 10)));
+        assert!(!crossed_state_boundary(captured, at(12, 0, 0)));
+
+        // Ending the transition during startup is a boundary too
+        let ending = at(18, 59, 59);
+        assert!(crossed_state_boundary(ending, at(19, 0, 0)));
+        assert!(crossed_state_boundary(
+            TransitionState::Stable(TimeState::Night),
+            captured
+        ));
+    }
+
+    #[test]
+    fn test_current_color_at_day_night_and_mid_transition() {
+        let config = create_test_config("19:00:00", "06:00:00", "finish_by", 30);
+        let at = |time: &str| {
+            let time = NaiveTime::parse_from_str(time, "%H:%M:%S").unwrap();
+            resolve_local_time(&Local, Local::now().date_naive().and_time(time))
+        };
+
+        assert_eq!(
+            current_color(&config, at("12:00:00")),
+            (DEFAULT_DAY_TEMP, DEFAULT_DAY_GAMMA)
+        );
+        assert_eq!(
+            current_color(&config, at("23:00:00")),
+            (DEFAULT_NIGHT_TEMP, DEFAULT_NIGHT_GAMMA)
+        );
+
+        // Halfway through the 18:30-19:00 sunset
+        let midway = at("18:45:00");
+        assert!(matches!(
+            get_transition_state_at(&config, midway.time()),
+            TransitionState::Transitioning { .. }
+        ));
+        let (temp, gamma) = current_color(&config, midway);
+        assert!(temp > DEFAULT_NIGHT_TEMP && temp < DEFAULT_DAY_TEMP);
+        assert!(gamma > DEFAULT_NIGHT_GAMMA && gamma < DEFAULT_DAY_GAMMA);
+    }
+
+    #[test]
+    fn test_calculate_transition_windows_separate_durations() {
+        let mut config = create_test_config("19:00:00", "06:00:00", "finish_by", 30);
+        config.sunrise_duration = Some(60);
+
+        let (sunset_start, sunset_end, sunrise_start, sunrise_end) =
+            calculate_transition_windows(&config);
+        assert_eq!(sunset_start, NaiveTime::from_hms_opt(18, 30, 0).unwrap());
+        assert_eq!(sunset_end, NaiveTime::from_hms_opt(19, 0, 0).unwrap());
+        assert_eq!(sunrise_start, NaiveTime::from_hms_opt(5, 0, 0).unwrap());
+        assert_eq!(sunrise_end, NaiveTime::from_hms_opt(6, 0, 0).unwrap());
+
+        config.transition_mode = Some("center".to_string());
+        config.sunset_duration = Some(10);
+        let (sunset_start, sunset_end, sunrise_start, sunrise_end) =
+            calculate_transition_windows(&config);
+        assert_eq!(sunset_start, NaiveTime::from_hms_opt(18, 55, 0).unwrap());
+        assert_eq!(sunset_end, NaiveTime::from_hms_opt(19, 5, 0).unwrap());
+        assert_eq!(sunrise_start, NaiveTime::from_hms_opt(5, 30, 0).unwrap());
+        assert_eq!(sunrise_end, NaiveTime::from_hms_opt(6, 30, 0).unwrap());
+    }
+
     #[test]
     fn test_extreme_short_transition() {
         let config = create_test_config("19:00:00", "06:00:00", "finish_by", 5); // 5 minutes
@@ -1067,21 +1834,21 @@ mod tests {
 
         // Test endpoints (should always be 0.0 and 1.0 regardless of Bezier curve)
         assert_eq!(
-            calculate_progress(NaiveTime::from_hms_opt(18, 0, 0).unwrap(), start, end),
+            calculate_progress(NaiveTime::from_hms_opt(18, 0, 0).unwrap(), start, end, 0),
             0.0
         );
         assert_eq!(
-            calculate_progress(NaiveTime::from_hms_opt(19, 0, 0).unwrap(), start, end),
+            calculate_progress(NaiveTime::from_hms_opt(19, 0, 0).unwrap(), start, end, 0),
             1.0
         );
 
         // Test monotonic increase - progress should always increase with time
         let progress_15 =
-            calculate_progress(NaiveTime::from_hms_opt(18, 15, 0).unwrap(), start, end);
+            calculate_progress(NaiveTime::from_hms_opt(18, 15, 0).unwrap(), start, end, 0);
         let progress_30 =
-            calculate_progress(NaiveTime::from_hms_opt(18, 30, 0).unwrap(), start, end);
+            calculate_progress(NaiveTime::from_hms_opt(18, 30, 0).unwrap(), start, end, 0);
         let progress_45 =
-            calculate_progress(NaiveTime::from_hms_opt(18, 45, 0).unwrap(), start, end);
+            calculate_progress(NaiveTime::from_hms_opt(18, 45, 0).unwrap(), start, end, 0);
 
         assert!(
             progress_15 < progress_30,
@@ -1122,9 +1889,9 @@ mod tests {
 
         // Verify smoothness - no sudden jumps
         let progress_29 =
-            calculate_progress(NaiveTime::from_hms_opt(18, 29, 0).unwrap(), start, end);
+            calculate_progress(NaiveTime::from_hms_opt(18, 29, 0).unwrap(), start, end, 0);
         let progress_31 =
-            calculate_progress(NaiveTime::from_hms_opt(18, 31, 0).unwrap(), start, end);
+            calculate_progress(NaiveTime::from_hms_opt(18, 31, 0).unwrap(), start, end, 0);
         let delta = (progress_31 - progress_29).abs();
 
         assert!(
@@ -1134,6 +1901,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_calculate_progress_ease_out_tail() {
+        let start = NaiveTime::from_hms_opt(18, 0, 0).unwrap();
+        let end = NaiveTime::from_hms_opt(19, 0, 0).unwrap();
+        let at = |minute: u32, second: u32, tail: u64| {
+            calculate_progress(
+                NaiveTime::from_hms_opt(18, minute, second).unwrap(),
+                start,
+                end,
+                tail,
+            )
+        };
+
+        // Before the tail nothing changes, and the endpoints stay exact
+        assert_eq!(at(30, 0, 600), at(30, 0, 0));
+        assert_eq!(at(0, 0, 600), 0.0);
+        assert_eq!(calculate_progress(end, start, end, 600), 1.0);
+
+        // The tail joins the curve without a jump
+        assert!((at(50, 1, 600) - at(50, 0, 0)).abs() < 0.001);
+
+        // Monotonic, and the last minute moves less than without the tail
+        for tail in [60, 600, 1800, 7200] {
+            let mut previous = 0.0;
+            for second in (0..3600).step_by(10) {
+                let progress = at(second / 60, second % 60, tail);
+                assert!(
+                    progress >= previous,
+                    "tail {} regressed at {}s",
+                    tail,
+                    second
+                );
+                previous = progress;
+            }
+        }
+        assert!(1.0 - at(59, 0, 600) < 1.0 - at(59, 0, 0));
+    }
+
     #[test]
     fn test_get_stable_state_for_time_normal_day() {
         // Normal case: sunset ends at 19:00, sunrise starts at 06:00
@@ -1321,15 +2126,14 @@ mod tests {
                         println!("  ❌ BUG DETECTED: Should be DAY, but got NIGHT");
                     }
                 }
-                "17:05:00" | "17:06:00" | "17:07:00" => {
+                "17:05:00" | "17:06:00" | "17:07:00"
                     // During transition - should be TRANSITIONING
-                    if !in_sunset_transition {
+                    if !in_sunset_transition => {
                         println!(
                             "  ❌ BUG DETECTED: Should be in SUNSET TRANSITION, but got {}",
                             expected_state
                         );
                     }
-                }
                 "17:09:00" => {
                     // After transition - should be NIGHT
                     assert!(
@@ -1393,29 +2197,17 @@ mod tests {
 
             // Check for unexpected behavior at boundaries
             match time_str {
-                "17:03:29" => {
-                    if state != "DAY" {
-                        println!("  ⚠️  POTENTIAL ISSUE: Expected DAY just before transition");
-                    }
+                "17:03:29" if state != "DAY" => {
+                    println!("  ⚠️  POTENTIAL ISSUE: Expected DAY just before transition");
                 }
-                "17:03:30" | "17:03:31" => {
-                    if state != "SUNSET TRANSITION" {
-                        println!(
-                            "  ⚠️  POTENTIAL ISSUE: Expected SUNSET TRANSITION at start boundary"
-                        );
-                    }
+                "17:03:30" | "17:03:31" if state != "SUNSET TRANSITION" => {
+                    println!("  ⚠️  POTENTIAL ISSUE: Expected SUNSET TRANSITION at start boundary");
                 }
-                "17:08:29" | "17:08:30" => {
-                    if state != "SUNSET TRANSITION" {
-                        println!(
-                            "  ⚠️  POTENTIAL ISSUE: Expected SUNSET TRANSITION at end boundary"
-                        );
-                    }
+                "17:08:29" | "17:08:30" if state != "SUNSET TRANSITION" => {
+                    println!("  ⚠️  POTENTIAL ISSUE: Expected SUNSET TRANSITION at end boundary");
                 }
-                "17:08:31" => {
-                    if state != "NIGHT" {
-                        println!("  ⚠️  POTENTIAL ISSUE: Expected NIGHT just after transition");
-                    }
+                "17:08:31" if state != "NIGHT" => {
+                    println!("  ⚠️  POTENTIAL ISSUE: Expected NIGHT just after transition");
                 }
                 _ => {}
             }
@@ -1534,14 +2326,14 @@ mod tests {
                 calculate_transition_windows(&config);
 
             let initial_state = if is_time_in_range(test_time, sunset_start, sunset_end) {
-                let progress = calculate_progress(test_time, sunset_start, sunset_end);
+                let progress = calculate_progress(test_time, sunset_start, sunset_end, 0);
                 TransitionState::Transitioning {
                     from: TimeState::Day,
                     to: TimeState::Night,
                     progress,
                 }
             } else if is_time_in_range(test_time, _sunrise_start, _sunrise_end) {
-                let progress = calculate_progress(test_time, _sunrise_start, _sunrise_end);
+                let progress = calculate_progress(test_time, _sunrise_start, _sunrise_end, 0);
                 TransitionState::Transitioning {
                     from: TimeState::Night,
                     to: TimeState::Day,
@@ -1566,14 +2358,14 @@ mod tests {
 
             // Step 3: Get final state (what gets applied after startup transition)
             let final_state = if is_time_in_range(final_time, sunset_start, sunset_end) {
-                let progress = calculate_progress(final_time, sunset_start, sunset_end);
+                let progress = calculate_progress(final_time, sunset_start, sunset_end, 0);
                 TransitionState::Transitioning {
                     from: TimeState::Day,
                     to: TimeState::Night,
                     progress,
                 }
             } else if is_time_in_range(final_time, _sunrise_start, _sunrise_end) {
-                let progress = calculate_progress(final_time, _sunrise_start, _sunrise_end);
+                let progress = calculate_progress(final_time, _sunrise_start, _sunrise_end, 0);
                 TransitionState::Transitioning {
                     from: TimeState::Night,
                     to: TimeState::Day,
@@ -1645,7 +2437,7 @@ mod tests {
             let in_sunset = is_time_in_range(test_time, sunset_start, sunset_end);
 
             if in_sunset {
-                let progress = calculate_progress(test_time, sunset_start, sunset_end);
+                let progress = calculate_progress(test_time, sunset_start, sunset_end, 0);
                 println!("  State: SUNSET TRANSITION (progress: {:.3})", progress);
             } else {
                 let stable_state = get_stable_state_for_time(test_time, sunset_end, _sunrise_start);
@@ -1672,7 +2464,7 @@ mod tests {
             let future_in_sunset = is_time_in_range(future_time, sunset_start, sunset_end);
 
             if future_in_sunset {
-                let progress = calculate_progress(future_time, sunset_start, sunset_end);
+                let progress = calculate_progress(future_time, sunset_start, sunset_end, 0);
                 println!(
                     "  After 10s ({}): SUNSET TRANSITION (progress: {:.3})",
                     future_time, progress
@@ -1716,7 +2508,7 @@ mod tests {
         // Check initial state (what gets captured)
         let initial_in_transition = is_time_in_range(test_time, sunset_start, sunset_end);
         let initial_state = if initial_in_transition {
-            let progress = calculate_progress(test_time, sunset_start, sunset_end);
+            let progress = calculate_progress(test_time, sunset_start, sunset_end, 0);
             println!(
                 "Initial state: SUNSET TRANSITION (progress: {:.3})",
                 progress
@@ -1741,7 +2533,7 @@ mod tests {
 
         let final_in_transition = is_time_in_range(final_time, sunset_start, sunset_end);
         if final_in_transition {
-            let progress = calculate_progress(final_time, sunset_start, sunset_end);
+            let progress = calculate_progress(final_time, sunset_start, sunset_end, 0);
             println!(
                 "Recalculated state: SUNSET TRANSITION (progress: {:.3})",
                 progress
@@ -1880,4 +2672,185 @@ mod tests {
         assert!(message.is_some());
         assert!(message.unwrap().contains("Short time jump detected"));
     }
+
+    #[test]
+    fn test_temp_quantum_snaps_mid_transition_only() {
+        let mut config = create_test_config("19:00:00", "06:00:00", "finish_by", 30);
+        config.temp_quantum = Some(50);
+
+        let sunset = |progress| TransitionState::Transitioning {
+            from: TimeState::Day,
+            to: TimeState::Night,
+            progress,
+        };
+
+        // Mid-transition temperatures land on multiples of the quantum
+        for progress in [0.1, 0.33, 0.5, 0.77, 0.99] {
+            let (temp, _) = get_initial_values_for_state(sunset(progress), &config);
+            assert_eq!(temp % 50, 0, "progress {} gave {}K", progress, temp);
+        }
+
+        // Endpoints stay exact even when they aren't multiples of the quantum
+        config.night_temp = Some(3333);
+        let (temp, _) = get_initial_values_for_state(sunset(1.0), &config);
+        assert_eq!(temp, 3333);
+
+        assert_eq!(snap_temperature(4321, 1), 4321);
+        assert_eq!(snap_temperature(4321, 50), 4300);
+        assert_eq!(snap_temperature(4325, 50), 4350);
+    }
+
+    #[test]
+    fn test_identical_day_and_night_values_skip_transitions() {
+        let mut config = create_test_config("19:00:00", "06:00:00", "finish_by", 30);
+        let mid_sunset = NaiveTime::from_hms_opt(18, 45, 0).unwrap();
+        assert!(matches!(
+            get_transition_state_at(&config, mid_sunset),
+            TransitionState::Transitioning { .. }
+        ));
+
+        config.night_temp = config.day_temp;
+        config.night_gamma = config.day_gamma;
+        assert!(config.is_color_shift_disabled());
+        assert_eq!(
+            get_transition_state_at(&config, mid_sunset),
+            TransitionState::Stable(TimeState::Night)
+        );
+
+        // HDR outputs with their own values still need the transition
+        config.hdr_outputs = Some(vec!["DP-1".to_string()]);
+        config.hdr_night_temp = Some(4000);
+        assert!(!config.is_color_shift_disabled());
+    }
+
+    #[test]
+    fn test_zero_duration_transition_switches_instantly() {
+        let config = create_test_config("19:00:00", "06:00:00", "finish_by", 0);
+        let at =
+            |h, m, s| get_transition_state_at(&config, NaiveTime::from_hms_opt(h, m, s).unwrap());
+
+        assert_eq!(at(18, 59, 59), TransitionState::Stable(TimeState::Day));
+        assert_eq!(at(19, 0, 0), TransitionState::Stable(TimeState::Night));
+        assert_eq!(at(5, 59, 59), TransitionState::Stable(TimeState::Night));
+        assert_eq!(at(6, 0, 0), TransitionState::Stable(TimeState::Day));
+
+        // Sleep until the switch, never a zero-length wait for a transition that won't come
+        let now = Local
+            .from_local_datetime(
+                &chrono::NaiveDate::from_ymd_opt(2025, 3, 1)
+                    .unwrap()
+                    .and_hms_opt(18, 0, 0)
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(
+            time_until_next_event_at(&config, &now),
+            Duration::from_secs(3600)
+        );
+        assert_eq!(time_until_transition_end_at(&config, &now), None);
+    }
+
+    #[test]
+    fn test_collapsed_geo_window_switches_instantly() {
+        // Around the June solstice at 54°N 165°W (America/Nome), civil dusk falls after
+        // midnight and the computed sunset window collapses to nothing
+        let date = chrono::NaiveDate::from_ymd_opt(2025, 6, 20).unwrap();
+        let result = crate::geo::solar::calculate_solar_times_for_date(
+            54.0,
+            -165.0,
+            date,
+            crate::geo::solar::SolarPrecision::Fast,
+            crate::geo::solar::NightBoundary::Civil,
+        )
+        .unwrap();
+        let (start, end) = (result.sunset_plus_10_start, result.sunset_minus_2_end);
+        assert_eq!(start, end);
+
+        let windows = (
+            start,
+            end,
+            result.sunrise_minus_2_start,
+            result.sunrise_plus_10_end,
+        );
+        let before = start - chrono::Duration::seconds(1);
+        assert_eq!(
+            state_in_windows(before, windows, 0),
+            TransitionState::Stable(TimeState::Day)
+        );
+        assert_eq!(
+            state_in_windows(start, windows, 0),
+            TransitionState::Stable(TimeState::Night)
+        );
+
+        // A window shorter than a second is entered but reports finished progress, not NaN
+        let sunset = NaiveTime::from_hms_opt(21, 0, 0).unwrap();
+        let sub_second = (
+            sunset,
+            sunset + chrono::Duration::milliseconds(500),
+            result.sunrise_minus_2_start,
+            result.sunrise_plus_10_end,
+        );
+        match state_in_windows(sunset, sub_second, 0) {
+            TransitionState::Transitioning { progress, .. } => assert_eq!(progress, 1.0),
+            state => panic!("expected a transition, got {:?}", state),
+        }
+    }
+
+    #[test]
+    fn test_appearance_mode_follows_color_scheme() {
+        use crate::appearance::{ColorScheme, set_current_scheme};
+
+        let config = create_test_config("19:00:00", "06:00:00", "appearance", 30);
+        let noon = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+        let sunset = NaiveTime::from_hms_opt(18, 45, 0).unwrap();
+
+        // The clock is ignored, even inside what would be the sunset transition
+        set_current_scheme(ColorScheme::Dark);
+        assert_eq!(
+            get_transition_state_at(&config, noon),
+            TransitionState::Stable(TimeState::Night)
+        );
+        set_current_scheme(ColorScheme::Light);
+        assert_eq!(
+            get_transition_state_at(&config, sunset),
+            TransitionState::Stable(TimeState::Day)
+        );
+        set_current_scheme(ColorScheme::NoPreference);
+
+        let now = Local::now();
+        assert_eq!(
+            time_until_next_event_at(&config, &now),
+            Duration::from_secs(APPEARANCE_RECHECK_SECS)
+        );
+        assert_eq!(time_until_transition_end_at(&config, &now), None);
+    }
+
+    #[test]
+    fn test_idle_gamma_scales_current_gamma() {
+        assert_eq!(apply_idle_gamma(90.0, 70.0), 63.0);
+        assert_eq!(apply_idle_gamma(100.0, 100.0), 100.0);
+        assert_eq!(apply_idle_gamma(80.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_temperature_nudge_arithmetic() {
+        // Nudges add up within the limit, and a step of 0 resets
+        assert_eq!(combine_nudge(0, -300), -300);
+        assert_eq!(combine_nudge(-300, -300), -600);
+        assert_eq!(combine_nudge(-600, 0), 0);
+        assert_eq!(
+            combine_nudge(MAXIMUM_TEMPERATURE_NUDGE - 100, 500),
+            MAXIMUM_TEMPERATURE_NUDGE
+        );
+        assert_eq!(
+            combine_nudge(-MAXIMUM_TEMPERATURE_NUDGE, -1),
+            -MAXIMUM_TEMPERATURE_NUDGE
+        );
+
+        // The nudged temperature stays within the supported range
+        assert_eq!(apply_nudge(3300, -300), 3000);
+        assert_eq!(apply_nudge(6500, 250), 6750);
+        assert_eq!(apply_nudge(MINIMUM_TEMP + 100, -500), MINIMUM_TEMP);
+        assert_eq!(apply_nudge(MAXIMUM_TEMP - 100, 500), MAXIMUM_TEMP);
+    }
 }
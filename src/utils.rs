@@ -18,7 +18,7 @@ use std::{
     io::{self, Write},
     os::unix::io::AsRawFd,
     sync::Arc,
-    sync::atomic::AtomicBool,
+    sync::atomic::{AtomicBool, Ordering},
 };
 use termios::{ECHO, TCSANOW, Termios, os::linux::ECHOCTL, tcsetattr};
 
@@ -48,6 +48,32 @@ pub fn interpolate_u32(start: u32, end: u32, progress: f32) -> u32 {
     result.round() as u32
 }
 
+/// Convert a temperature in mireds (micro reciprocal degrees) to Kelvin.
+///
+/// Mireds are 1,000,000 divided by the temperature in Kelvin, so equal steps in
+/// mireds look like roughly equal changes in color. The result is rounded to the
+/// nearest Kelvin; 0 mired (infinitely hot) maps to `u32::MAX`.
+///
+/// # Examples
+/// ```
+/// use sunsetr::utils::mired_to_kelvin;
+/// assert_eq!(mired_to_kelvin(303), 3300);
+/// assert_eq!(mired_to_kelvin(154), 6494);
+/// ```
+pub fn mired_to_kelvin(mired: u32) -> u32 {
+    if mired == 0 {
+        return u32::MAX;
+    }
+    (1_000_000.0 / mired as f64).round() as u32
+}
+
+/// Convert a temperature in Kelvin to mireds, rounded to the nearest mired.
+///
+/// This is the inverse of [`mired_to_kelvin`]; 0K maps to `u32::MAX`.
+pub fn kelvin_to_mired(kelvin: u32) -> u32 {
+    mired_to_kelvin(kelvin)
+}
+
 /// Interpolate between two f32 values based on progress (0.0 to 1.0).
 ///
 /// This function provides smooth transitions between floating-point values,
@@ -178,6 +204,77 @@ pub fn compare_versions(version1: &str, version2: &str) -> std::cmp::Ordering {
     v1.cmp(&v2)
 }
 
+/// Compute the Levenshtein edit distance between two strings.
+///
+/// Counts the minimum number of single-character insertions, deletions and
+/// substitutions needed to turn `a` into `b`. Used for "did you mean" hints.
+///
+/// # Examples
+/// ```
+/// use sunsetr::utils::levenshtein_distance;
+/// assert_eq!(levenshtein_distance("nite_temp", "night_temp"), 3);
+/// assert_eq!(levenshtein_distance("day_temp", "day_temp"), 0);
+/// ```
+pub fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b_chars.len()).collect();
+    let mut current = vec![0; b_chars.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b_chars.iter().enumerate() {
+            let substitution_cost = usize::from(a_char != *b_char);
+            current[j + 1] = (previous[j] + substitution_cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b_chars.len()]
+}
+
+/// Match a name against a simple glob pattern.
+///
+/// Supports `*` (any sequence of characters, including none) and `?` (exactly
+/// one character). All other characters match literally.
+///
+/// # Examples
+/// ```
+/// use sunsetr::utils::glob_match;
+/// assert!(glob_match("eDP-*", "eDP-1"));
+/// assert!(glob_match("DP-?", "DP-2"));
+/// assert!(!glob_match("DP-*", "eDP-1"));
+/// ```
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    let (mut p, mut n) = (0, 0);
+    // Position of the last '*' seen and the name position it was tried at
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = backtrack {
+            // Let the last '*' absorb one more character and retry
+            p = star_p + 1;
+            n = star_n + 1;
+            backtrack = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+
+    // Any remaining pattern must be all '*'
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 /// Extract semantic version string from hyprsunset command output.
 ///
 /// Parses hyprsunset output to find version information in various formats.
@@ -293,24 +390,154 @@ impl Drop for TerminalGuard {
     }
 }
 
+/// Contents of the single-instance lock file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockInfo {
+    pub pid: u32,
+    /// Compositor the owning instance was started under, if recorded and readable
+    pub compositor: Option<String>,
+}
+
+/// What to do about a lock file held by another process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockDecision {
+    /// The lock doesn't belong to a live process and can be removed
+    RemoveStale,
+    /// Another instance is running under this compositor (or we can't tell); leave it alone
+    RespectRunning(u32),
+    /// Another instance is running under a different compositor and should be replaced
+    TakeOver { pid: u32, compositor: String },
+}
+
+/// Longest compositor name accepted from the lock file.
+const MAX_LOCK_COMPOSITOR_LEN: usize = 64;
+
+/// Parse lock file contents written as `<pid>\n<compositor>\n`.
+///
+/// Blank lines and surrounding whitespace are ignored, as are any extra lines a
+/// future version might append. Returns `None` when there's no usable PID: the
+/// lock can't be attributed to a process and should be treated as stale. A missing
+/// or garbled compositor line is reported as `None` rather than failing the parse.
+pub fn parse_lock_file(content: &str) -> Option<LockInfo> {
+    let mut lines = content.lines().map(str::trim).filter(|l| !l.is_empty());
+
+    // PID 0 would address our own process group, and anything past i32::MAX isn't a pid_t
+    let pid = lines
+        .next()?
+        .parse::<u32>()
+        .ok()
+        .filter(|&pid| pid > 0 && pid <= i32::MAX as u32)?;
+
+    let compositor = lines
+        .next()
+        .filter(|name| {
+            name.chars().count() <= MAX_LOCK_COMPOSITOR_LEN
+                && name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || "-_. ".contains(c))
+        })
+        .map(str::to_string);
+
+    Some(LockInfo { pid, compositor })
+}
+
+/// Decide how to handle a lock file held by another process.
+///
+/// Only a live process recorded under a different, readable compositor is taken
+/// over. When the compositor is missing or unreadable, the running instance is
+/// respected, since killing the wrong process is worse than refusing to start.
+pub fn decide_lock_conflict(
+    info: Option<&LockInfo>,
+    current_compositor: &str,
+    is_running: impl Fn(u32) -> bool,
+) -> LockDecision {
+    let Some(info) = info else {
+        return LockDecision::RemoveStale;
+    };
+
+    if !is_running(info.pid) {
+        return LockDecision::RemoveStale;
+    }
+
+    match &info.compositor {
+        Some(compositor) if compositor != current_compositor => LockDecision::TakeOver {
+            pid: info.pid,
+            compositor: compositor.clone(),
+        },
+        _ => LockDecision::RespectRunning(info.pid),
+    }
+}
+
+/// `$XDG_RUNTIME_DIR`, when the session sets it.
+pub fn xdg_runtime_dir() -> Option<std::path::PathBuf> {
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .filter(|dir| !dir.is_empty())
+        .map(std::path::PathBuf::from)
+}
+
+/// Directory for the lock and status files.
+///
+/// This is `$XDG_RUNTIME_DIR`, or `/tmp/sunsetr-$UID` when the session doesn't set
+/// it. `/tmp` is shared by all users, so the fallback is private to the current user.
+pub fn runtime_dir() -> Result<std::path::PathBuf> {
+    match xdg_runtime_dir() {
+        Some(dir) => Ok(dir),
+        None => private_runtime_dir(std::path::Path::new("/tmp"), nix::unistd::getuid().as_raw()),
+    }
+}
+
+/// Create (or check) the per-user runtime directory `sunsetr-{uid}` below `base`.
+///
+/// The directory must be a real directory owned by `uid`; anything else (another
+/// user's directory, a symlink planted in its place) is refused. Its mode is kept
+/// at 0700 so other users can't read or replace the files inside.
+fn private_runtime_dir(base: &std::path::Path, uid: u32) -> Result<std::path::PathBuf> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+
+    let dir = base.join(format!("sunsetr-{}", uid));
+    match std::fs::DirBuilder::new().mode(0o700).create(&dir) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to create {}", dir.display()));
+        }
+    }
+
+    let metadata = std::fs::symlink_metadata(&dir)
+        .with_context(|| format!("Failed to inspect {}", dir.display()))?;
+    if !metadata.is_dir() || metadata.uid() != uid {
+        anyhow::bail!(
+            "Refusing to use {} for runtime files: it is not a directory owned by the current user",
+            dir.display()
+        );
+    }
+    if metadata.mode() & 0o077 != 0 {
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700))
+            .with_context(|| format!("Failed to restrict permissions of {}", dir.display()))?;
+    }
+
+    Ok(dir)
+}
+
+/// Path of the lock file held by the running sunsetr instance.
+pub fn lock_file_path() -> Result<String> {
+    Ok(runtime_dir()?
+        .join("sunsetr.lock")
+        .to_string_lossy()
+        .into_owned())
+}
+
 /// Get the PID of the currently running sunsetr instance
 pub fn get_running_sunsetr_pid() -> Result<u32> {
-    let runtime_dir = std::env::var("XDG_RUNTIME_DIR").unwrap_or_else(|_| "/tmp".to_string());
-    let lock_path = format!("{}/sunsetr.lock", runtime_dir);
+    let lock_path = lock_file_path()?;
 
     // Read the lock file content
     let lock_content = std::fs::read_to_string(&lock_path)
         .context("Failed to read lock file - no sunsetr instance running?")?;
 
-    let lines: Vec<&str> = lock_content.trim().lines().collect();
-
-    if lines.len() != 2 {
-        anyhow::bail!("Lock file format invalid");
-    }
-
-    let pid = lines[0]
-        .parse::<u32>()
-        .context("Invalid PID format in lock file")?;
+    let pid = parse_lock_file(&lock_content)
+        .context("Invalid PID format in lock file")?
+        .pid;
 
     // Verify the process is still running
     if is_process_running(pid) {
@@ -346,6 +573,23 @@ pub fn kill_process(pid: u32) -> bool {
     }
 }
 
+/// Set by `--safe`; see [`is_safe_mode`].
+static SAFE_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Turn on safe mode for the rest of the process.
+pub fn enable_safe_mode() {
+    SAFE_MODE.store(true, Ordering::Relaxed);
+}
+
+/// Whether sunsetr runs in safe ("demo") mode, via `--safe` or `SUNSETR_SAFE=1`.
+///
+/// In safe mode sunsetr still drives the display, but never writes config files,
+/// never prompts about conflicting configs and never terminates other processes.
+pub fn is_safe_mode() -> bool {
+    SAFE_MODE.load(Ordering::Relaxed)
+        || std::env::var(crate::constants::SAFE_MODE_ENV).is_ok_and(|value| value == "1")
+}
+
 /// Spawn a background sunsetr process using compositor-specific commands
 pub fn spawn_background_process(debug_enabled: bool) -> Result<()> {
     use crate::backend::{Compositor, detect_compositor};
@@ -370,6 +614,9 @@ pub fn spawn_background_process(debug_enabled: bool) -> Result<()> {
     let current_exe = std::env::current_exe().context("Failed to get current executable path")?;
     let sunsetr_path = current_exe.to_string_lossy();
 
+    // The compositor's environment may not carry SUNSETR_SAFE, so pass the flag on
+    let safe_args: &[&str] = if is_safe_mode() { &["--safe"] } else { &[] };
+
     #[cfg(debug_assertions)]
     eprintln!("DEBUG: sunsetr_path: {}", sunsetr_path);
 
@@ -386,6 +633,7 @@ pub fn spawn_background_process(debug_enabled: bool) -> Result<()> {
 
             let output = std::process::Command::new("niri")
                 .args(["msg", "action", "spawn", "--", &sunsetr_path])
+                .args(safe_args)
                 .output()
                 .context("Failed to execute niri command")?;
 
@@ -408,6 +656,7 @@ pub fn spawn_background_process(debug_enabled: bool) -> Result<()> {
 
             let output = std::process::Command::new("hyprctl")
                 .args(["dispatch", "exec", &sunsetr_path])
+                .args(safe_args)
                 .output()
                 .context("Failed to execute hyprctl command")?;
 
@@ -430,6 +679,7 @@ pub fn spawn_background_process(debug_enabled: bool) -> Result<()> {
 
             let output = std::process::Command::new("swaymsg")
                 .args(["exec", &sunsetr_path])
+                .args(safe_args)
                 .output()
                 .context("Failed to execute swaymsg command")?;
 
@@ -449,6 +699,7 @@ pub fn spawn_background_process(debug_enabled: bool) -> Result<()> {
 
             // Fallback to direct spawn - not ideal but better than nothing
             let _child = std::process::Command::new(&*sunsetr_path)
+                .args(safe_args)
                 .spawn()
                 .context("Failed to spawn sunsetr process directly")?;
 
@@ -479,6 +730,7 @@ pub fn spawn_background_process(debug_enabled: bool) -> Result<()> {
 /// * `backend` - The backend instance to clean up (will call backend.cleanup())
 /// * `lock_file` - File handle for the application lock (will be dropped to release)
 /// * `lock_path` - Path to the lock file for removal from filesystem
+/// * `reset_on_exit` - Whether to reset to neutral first, or only release gamma control
 /// * `debug_enabled` - Whether debug mode is enabled (affects logging separation)
 ///
 /// # Examples
@@ -496,7 +748,7 @@ pub fn spawn_background_process(debug_enabled: bool) -> Result<()> {
 /// let lock_file = File::create("/tmp/sunsetr.lock")?;
 ///
 /// // During normal shutdown
-/// cleanup_application(backend, lock_file, "/tmp/sunsetr.lock", false);
+/// cleanup_application(backend, lock_file, "/tmp/sunsetr.lock", true, false);
 /// # Ok(())
 /// # }
 /// ```
@@ -504,13 +756,19 @@ pub fn cleanup_application(
     mut backend: Box<dyn crate::backend::ColorTemperatureBackend>,
     lock_file: File,
     lock_path: &str,
+    reset_on_exit: bool,
     debug_enabled: bool,
 ) {
     Log::log_decorated("Performing cleanup...");
 
     // Reset color temperature to neutral before cleanup
     // Skip for Hyprland backend as hyprsunset v0.3.1+ now resets gamma on exit automatically
-    if backend.backend_name() != "Hyprland" {
+    if !reset_on_exit {
+        // Backend cleanup releases the gamma controls and the compositor takes over
+        if debug_enabled {
+            Log::log_decorated("Skipping gamma reset (reset_on_exit = false)");
+        }
+    } else if backend.backend_name() != "Hyprland" {
         if debug_enabled {
             Log::log_decorated("Resetting color temperature and gamma...");
             Log::log_indented("About to reset gamma via backend before stopping managed processes");
@@ -563,6 +821,18 @@ pub fn show_dropdown_menu<T>(
     options: &[(String, T)],
     prompt: Option<&str>,
     cancel_message: Option<&str>,
+) -> Result<usize> {
+    show_dropdown_menu_at(options, 0, prompt, cancel_message)
+}
+
+/// Like [`show_dropdown_menu`], with the option at `initial` highlighted at first.
+///
+/// An `initial` past the end of `options` starts at the first option.
+pub fn show_dropdown_menu_at<T>(
+    options: &[(String, T)],
+    initial: usize,
+    prompt: Option<&str>,
+    cancel_message: Option<&str>,
 ) -> Result<usize> {
     Log::log_pipe();
     if let Some(p) = prompt {
@@ -577,7 +847,7 @@ pub fn show_dropdown_menu<T>(
     // Enable raw mode to capture key events
     terminal::enable_raw_mode().context("Failed to enable raw mode")?;
 
-    let mut selected = 0;
+    let mut selected = if initial < options.len() { initial } else { 0 };
     let mut stdout = io::stdout();
 
     // Ensure we clean up on any exit
@@ -718,20 +988,103 @@ pub fn show_dropdown_menu<T>(
 /// // Returns: "~/.config/sunsetr/sunsetr.toml"
 /// ```
 pub fn path_for_display(path: &std::path::Path) -> String {
-    if let Some(home_dir) = dirs::home_dir() {
-        if let Ok(relative_path) = path.strip_prefix(&home_dir) {
-            return format!("~/{}", relative_path.display());
-        }
+    if let Some(home_dir) = dirs::home_dir()
+        && let Ok(relative_path) = path.strip_prefix(&home_dir)
+    {
+        return format!("~/{}", relative_path.display());
     }
     // Fallback to original path if home directory detection fails
     path.display().to_string()
 }
 
+/// Expand a leading `~/` in a user-supplied path to the home directory.
+///
+/// The inverse of [`path_for_display`]; other paths are returned unchanged.
+pub fn expand_home(path: &str) -> std::path::PathBuf {
+    if let Some(rest) = path.strip_prefix("~/")
+        && let Some(home_dir) = dirs::home_dir()
+    {
+        return home_dir.join(rest);
+    }
+    std::path::PathBuf::from(path)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::cmp::Ordering;
 
+    #[test]
+    fn test_private_runtime_dir() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let base = tempfile::tempdir().unwrap();
+        let uid = nix::unistd::getuid().as_raw();
+
+        // Created on first use, private to the user
+        let dir = private_runtime_dir(base.path(), uid).unwrap();
+        assert_eq!(dir, base.path().join(format!("sunsetr-{}", uid)));
+        let mode = std::fs::metadata(&dir).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+
+        // Loosened permissions are tightened again
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o777)).unwrap();
+        private_runtime_dir(base.path(), uid).unwrap();
+        let mode = std::fs::metadata(&dir).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+
+        // A directory owned by someone else is refused
+        let err = private_runtime_dir(base.path(), uid + 1).unwrap_err();
+        assert!(format!("{:#}", err).contains("not a directory owned by the current user"));
+
+        // So is a symlink planted where the directory should be
+        let other = tempfile::tempdir().unwrap();
+        std::fs::remove_dir(&dir).unwrap();
+        std::os::unix::fs::symlink(other.path(), &dir).unwrap();
+        assert!(private_runtime_dir(base.path(), uid).is_err());
+    }
+
+    #[test]
+    fn test_expand_home() {
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(
+            expand_home("~/.local/state/sunsetr.log"),
+            home.join(".local/state/sunsetr.log")
+        );
+        assert_eq!(
+            expand_home("/var/log/sunsetr.log"),
+            std::path::PathBuf::from("/var/log/sunsetr.log")
+        );
+        assert_eq!(
+            expand_home("~user/log"),
+            std::path::PathBuf::from("~user/log")
+        );
+    }
+
+    #[test]
+    fn test_mired_kelvin_round_trip() {
+        // Representative points: candlelight, warm night, neutral, daylight, limits
+        for (mired, kelvin) in [
+            (1000, 1000),
+            (526, 1901),
+            (303, 3300),
+            (200, 5000),
+            (154, 6494),
+            (50, 20000),
+        ] {
+            assert_eq!(mired_to_kelvin(mired), kelvin);
+            assert_eq!(kelvin_to_mired(kelvin), mired);
+        }
+
+        // Every mired in the supported range survives a round trip through Kelvin
+        for mired in 50..=1000 {
+            assert_eq!(kelvin_to_mired(mired_to_kelvin(mired)), mired);
+        }
+
+        assert_eq!(mired_to_kelvin(0), u32::MAX);
+        assert_eq!(kelvin_to_mired(0), u32::MAX);
+    }
+
     #[test]
     fn test_interpolate_u32_basic() {
         assert_eq!(interpolate_u32(1000, 2000, 0.0), 1000);
@@ -789,6 +1142,30 @@ mod tests {
         assert_eq!(interpolate_f32(0.0, 100.0, 1.5), 100.0);
     }
 
+    #[test]
+    fn test_levenshtein_distance() {
+        assert_eq!(levenshtein_distance("", ""), 0);
+        assert_eq!(levenshtein_distance("abc", ""), 3);
+        assert_eq!(levenshtein_distance("", "abc"), 3);
+        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+        assert_eq!(levenshtein_distance("nite_temp", "night_temp"), 3);
+        assert_eq!(levenshtein_distance("daytemp", "day_temp"), 1);
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("eDP-1", "eDP-1"));
+        assert!(glob_match("eDP-*", "eDP-1"));
+        assert!(glob_match("*", "HDMI-A-1"));
+        assert!(glob_match("*-A-*", "HDMI-A-1"));
+        assert!(glob_match("DP-?", "DP-3"));
+        assert!(!glob_match("DP-?", "DP-10"));
+        assert!(!glob_match("DP-*", "eDP-1"));
+        assert!(!glob_match("eDP-1", "eDP-12"));
+        assert!(glob_match("a*b*c", "axxbyyc"));
+        assert!(!glob_match("a*b*c", "axxbyy"));
+    }
+
     #[test]
     fn test_compare_versions_basic() {
         assert_eq!(compare_versions("v1.0.0", "v1.0.0"), Ordering::Equal);
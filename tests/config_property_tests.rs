@@ -132,20 +132,78 @@ impl Arbitrary for TransitionModeCase {
 fn create_test_config_with_combinations(args: TestConfigCreationArgs) -> Config {
     Config {
         start_hyprsunset: args.bool_combo.start_hyprsunset,
+        hyprsunset_socket: None,
+        hyprsunset_connect_timeout: None,
+        hyprsunset_connect_retries: None,
         backend: args.backend_combo.backend,
+        auto_prefer: None,
         startup_transition: args.bool_combo.startup_transition,
         startup_transition_duration: args.startup_transition_duration,
+        startup_delay_ms: None,
         latitude: None,
         longitude: None,
+        geo_anchor: None,
+        geo_noon_offset: None,
+        solar_precision: None,
+        geo_night_boundary: None,
+        geo_round_to_minutes: None,
         sunset: args.sunset,
         sunrise: args.sunrise,
         night_temp: args.night_temp,
         day_temp: args.day_temp,
         night_gamma: args.night_gamma,
         day_gamma: args.day_gamma,
+        color_shift: None,
+        temp_quantum: None,
+        temp_unit: None,
+        day_whitepoint: None,
+        night_gamma_r: None,
+        night_gamma_g: None,
+        night_gamma_b: None,
+        day_gamma_r: None,
+        day_gamma_g: None,
+        day_gamma_b: None,
+        outputs_include: None,
+        outputs_exclude: None,
+        hdr_outputs: None,
+        hdr_night_temp: None,
+        hdr_day_temp: None,
+        hdr_night_gamma: None,
+        hdr_day_gamma: None,
+        verify_gamma: None,
+        precompute_ramps: None,
+        gamma_table_format: None,
+        require_outputs: None,
+        reset_on_exit: None,
+        on_fatal_exit: None,
+        metrics_port: None,
+        event_fifo: None,
+        reapply_on_unlock: None,
+        wake_fade_ms: None,
+        idle_timeout: None,
+        idle_gamma: None,
+        log_file: None,
+        log_file_max_bytes: None,
+        log_file_keep: None,
+        log_to_console: None,
+        log_verbosity: None,
         transition_duration: args.transition_duration,
+        sunset_duration: None,
+        sunrise_duration: None,
+        sunrise_hold_temp: None,
+        sunrise_hold_minutes: None,
         update_interval: args.update_interval,
+        ease_out_tail_seconds: None,
+        gamma_progress_offset: None,
         transition_mode: Some(args.mode_combo.mode),
+        manual_geo_event: None,
+        seasonal_modes: None,
+        on_day_command: None,
+        on_night_command: None,
+        on_transition_start: None,
+        active_profile: None,
+        profiles: None,
+        output_groups: None,
     }
 }
 
@@ -565,17 +623,75 @@ mod exhaustive_tests {
                         backend,
                         startup_transition,
                         startup_transition_duration: Some(DEFAULT_STARTUP_TRANSITION_DURATION),
+                        startup_delay_ms: None,
                         latitude: None,
                         longitude: None,
+                        geo_anchor: None,
+                        geo_noon_offset: None,
+                        solar_precision: None,
+                        geo_night_boundary: None,
+                        geo_round_to_minutes: None,
                         sunset: "19:00:00".to_string(),
                         sunrise: "06:00:00".to_string(),
                         night_temp: Some(DEFAULT_NIGHT_TEMP),
                         day_temp: Some(DEFAULT_DAY_TEMP),
                         night_gamma: Some(DEFAULT_NIGHT_GAMMA),
                         day_gamma: Some(DEFAULT_DAY_GAMMA),
+                        color_shift: None,
+                        temp_quantum: None,
+                        temp_unit: None,
+                        day_whitepoint: None,
+                        night_gamma_r: None,
+                        night_gamma_g: None,
+                        night_gamma_b: None,
+                        day_gamma_r: None,
+                        day_gamma_g: None,
+                        day_gamma_b: None,
+                        outputs_include: None,
+                        outputs_exclude: None,
+                        hdr_outputs: None,
+                        hdr_night_temp: None,
+                        hdr_day_temp: None,
+                        hdr_night_gamma: None,
+                        hdr_day_gamma: None,
+                        verify_gamma: None,
+                        precompute_ramps: None,
+                        gamma_table_format: None,
+                        require_outputs: None,
+                        reset_on_exit: None,
+                        on_fatal_exit: None,
+                        metrics_port: None,
+                        event_fifo: None,
+                        reapply_on_unlock: None,
+                        wake_fade_ms: None,
+                        idle_timeout: None,
+                        idle_gamma: None,
+                        log_file: None,
+                        log_file_max_bytes: None,
+                        log_file_keep: None,
+                        log_to_console: None,
+                        log_verbosity: None,
                         transition_duration: Some(DEFAULT_TRANSITION_DURATION),
+                        sunset_duration: None,
+                        sunrise_duration: None,
+                        sunrise_hold_temp: None,
+                        sunrise_hold_minutes: None,
                         update_interval: Some(DEFAULT_UPDATE_INTERVAL),
+                        ease_out_tail_seconds: None,
+                        gamma_progress_offset: None,
                         transition_mode: Some(mode.to_string()),
+                        manual_geo_event: None,
+                        seasonal_modes: None,
+                        on_day_command: None,
+                        on_night_command: None,
+                        on_transition_start: None,
+                        active_profile: None,
+                        profiles: None,
+                        output_groups: None,
+                        hyprsunset_socket: None,
+                        hyprsunset_connect_timeout: None,
+                        hyprsunset_connect_retries: None,
+                        auto_prefer: None,
                     };
 
                     // Check for the specific incompatible combination
@@ -632,20 +748,78 @@ mod exhaustive_tests {
 
                                     let config = Config {
                                         start_hyprsunset: Some(false),
+                                        hyprsunset_socket: None,
+                                        hyprsunset_connect_timeout: None,
+                                        hyprsunset_connect_retries: None,
                                         backend: Some(Backend::Auto),
+                                        auto_prefer: None,
                                         startup_transition: Some(false),
                                         startup_transition_duration: Some(startup_duration),
+                                        startup_delay_ms: None,
                                         latitude: None,
                                         longitude: None,
+                                        geo_anchor: None,
+                                        geo_noon_offset: None,
+                                        solar_precision: None,
+                                        geo_night_boundary: None,
+                                        geo_round_to_minutes: None,
                                         sunset: "19:00:00".to_string(),
                                         sunrise: "06:00:00".to_string(),
                                         night_temp: Some(night_temp),
                                         day_temp: Some(day_temp),
                                         night_gamma: Some(night_gamma),
                                         day_gamma: Some(day_gamma),
+                                        color_shift: None,
+                                        temp_quantum: None,
+                                        temp_unit: None,
+                                        day_whitepoint: None,
+                                        night_gamma_r: None,
+                                        night_gamma_g: None,
+                                        night_gamma_b: None,
+                                        day_gamma_r: None,
+                                        day_gamma_g: None,
+                                        day_gamma_b: None,
+                                        outputs_include: None,
+                                        outputs_exclude: None,
+                                        hdr_outputs: None,
+                                        hdr_night_temp: None,
+                                        hdr_day_temp: None,
+                                        hdr_night_gamma: None,
+                                        hdr_day_gamma: None,
+                                        verify_gamma: None,
+                                        precompute_ramps: None,
+                                        gamma_table_format: None,
+                                        require_outputs: None,
+                                        reset_on_exit: None,
+                                        on_fatal_exit: None,
+                                        metrics_port: None,
+                                        event_fifo: None,
+                                        reapply_on_unlock: None,
+                                        wake_fade_ms: None,
+                                        idle_timeout: None,
+                                        idle_gamma: None,
+                                        log_file: None,
+                                        log_file_max_bytes: None,
+                                        log_file_keep: None,
+                                        log_to_console: None,
+                                        log_verbosity: None,
                                         transition_duration: Some(transition_duration),
+                                        sunset_duration: None,
+                                        sunrise_duration: None,
+                                        sunrise_hold_temp: None,
+                                        sunrise_hold_minutes: None,
                                         update_interval: Some(update_interval),
+                                        ease_out_tail_seconds: None,
+                                        gamma_progress_offset: None,
                                         transition_mode: Some("finish_by".to_string()),
+                                        manual_geo_event: None,
+                                        seasonal_modes: None,
+                                        on_day_command: None,
+                                        on_night_command: None,
+                                        on_transition_start: None,
+                                        active_profile: None,
+                                        profiles: None,
+                                        output_groups: None,
                                     };
 
                                     assert!(
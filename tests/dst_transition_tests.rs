@@ -0,0 +1,167 @@
+//! DST boundary tests for transition timing.
+//!
+//! Configured sunset/sunrise times are local wall-clock times, while sleep
+//! durations are absolute. These tests feed synthetic times around the 2024
+//! America/New_York spring-forward (Mar 10, 02:00 EST -> 03:00 EDT) and
+//! fall-back (Nov 3, 02:00 EDT -> 01:00 EST) boundaries to verify that
+//! transitions still happen at the configured wall-clock time.
+
+use chrono::{DateTime, NaiveDate, TimeZone};
+use chrono_tz::America::New_York;
+use chrono_tz::Tz;
+use std::time::{Duration, SystemTime};
+use sunsetr::config::Config;
+use sunsetr::time_state::{
+    TimeState, TransitionState, get_transition_state_at, should_update_state,
+    time_until_next_event_at, time_until_transition_end_at,
+};
+
+fn manual_config(sunset: &str, sunrise: &str, mode: &str, duration_mins: u64) -> Config {
+    toml::from_str(&format!(
+        r#"
+sunset = "{sunset}"
+sunrise = "{sunrise}"
+transition_mode = "{mode}"
+transition_duration = {duration_mins}
+"#
+    ))
+    .unwrap()
+}
+
+/// Local New York time; panics if the wall-clock time doesn't exist or is ambiguous.
+fn ny(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Tz> {
+    New_York
+        .with_ymd_and_hms(y, m, d, h, min, 0)
+        .single()
+        .expect("unambiguous local time")
+}
+
+#[test]
+fn test_spring_forward_sleep_wakes_at_wall_clock_sunrise() {
+    // Sunrise transition 05:30-06:00 wall clock
+    let config = manual_config("19:00:00", "06:00:00", "finish_by", 30);
+
+    // 00:30 EST, stable night. Only 4 real hours pass before the clock reads 05:30 EDT.
+    let now = ny(2024, 3, 10, 0, 30);
+    assert_eq!(
+        get_transition_state_at(&config, now.time()),
+        TransitionState::Stable(TimeState::Night)
+    );
+    assert_eq!(
+        time_until_next_event_at(&config, &now),
+        Duration::from_secs(4 * 3600)
+    );
+}
+
+#[test]
+fn test_fall_back_sleep_wakes_at_wall_clock_sunrise() {
+    let config = manual_config("19:00:00", "06:00:00", "finish_by", 30);
+
+    // 00:30 EDT, stable night. 6 real hours pass before the clock reads 05:30 EST.
+    let now = ny(2024, 11, 3, 0, 30);
+    assert_eq!(
+        time_until_next_event_at(&config, &now),
+        Duration::from_secs(6 * 3600)
+    );
+}
+
+#[test]
+fn test_sunset_on_dst_days_uses_wall_clock_time() {
+    // Sunset transition 18:30-19:00 wall clock
+    let config = manual_config("19:00:00", "06:00:00", "finish_by", 30);
+
+    for day in [ny(2024, 3, 10, 12, 0), ny(2024, 11, 3, 12, 0)] {
+        // Already past the DST change, so wall clock and real time agree
+        assert_eq!(
+            time_until_next_event_at(&config, &day),
+            Duration::from_secs(6 * 3600 + 30 * 60)
+        );
+    }
+
+    // Halfway through sunset by the wall clock, same as on any other day
+    let mid_sunset = ny(2024, 3, 10, 18, 45).time();
+    match get_transition_state_at(&config, mid_sunset) {
+        TransitionState::Transitioning { from, to, progress } => {
+            assert_eq!((from, to), (TimeState::Day, TimeState::Night));
+            assert!((progress - 0.5).abs() < 0.05);
+        }
+        other => panic!("Expected sunset transition, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_transition_start_inside_spring_forward_gap() {
+    // Sunrise starts at 02:30, a wall-clock time that doesn't exist on Mar 10
+    let config = manual_config("20:00:00", "02:30:00", "start_at", 60);
+
+    // From 01:00 EST the clock jumps straight from 02:00 to 03:00, one real hour later
+    let now = ny(2024, 3, 10, 1, 0);
+    assert_eq!(
+        time_until_next_event_at(&config, &now),
+        Duration::from_secs(3600)
+    );
+
+    // At 03:00 EDT the wall clock is already 30 minutes into the transition
+    let after_jump = ny(2024, 3, 10, 3, 0);
+    let new_state = get_transition_state_at(&config, after_jump.time());
+    let TransitionState::Transitioning { progress, .. } = new_state else {
+        panic!("Expected sunrise transition, got {:?}", new_state);
+    };
+    assert!(progress > 0.01);
+
+    // Waking up mid-transition must still apply it rather than waiting for the end
+    let current_state = TransitionState::Stable(TimeState::Night);
+    let check_time = SystemTime::now();
+    assert!(should_update_state(
+        &current_state,
+        &new_state,
+        check_time,
+        check_time - Duration::from_secs(3600),
+        &config,
+        None,
+    ));
+}
+
+#[test]
+fn test_transition_spanning_fall_back_is_lengthened() {
+    // Sunrise transition 01:00-02:00 wall clock; 01:xx happens twice on Nov 3
+    let config = manual_config("20:00:00", "02:00:00", "finish_by", 60);
+
+    // First 01:30 (EDT). The clock reads 02:00 EST 90 real minutes later.
+    let first_one_thirty = New_York
+        .from_local_datetime(
+            &NaiveDate::from_ymd_opt(2024, 11, 3)
+                .unwrap()
+                .and_hms_opt(1, 30, 0)
+                .unwrap(),
+        )
+        .earliest()
+        .unwrap();
+    assert_eq!(
+        time_until_transition_end_at(&config, &first_one_thirty),
+        Some(Duration::from_secs(90 * 60))
+    );
+}
+
+#[test]
+fn test_stable_state_not_disturbed_by_dst_change() {
+    let config = manual_config("19:00:00", "06:00:00", "finish_by", 30);
+
+    // The wall clock jumps an hour forward, but SystemTime (UTC) only moves by
+    // the few seconds between checks, so no anomaly should be reported
+    let before = ny(2024, 3, 10, 1, 59);
+    let after = ny(2024, 3, 10, 3, 0);
+    let current_state = get_transition_state_at(&config, before.time());
+    let new_state = get_transition_state_at(&config, after.time());
+    assert_eq!(current_state, new_state);
+
+    let check_time = SystemTime::now();
+    assert!(!should_update_state(
+        &current_state,
+        &new_state,
+        check_time,
+        check_time - Duration::from_secs(10),
+        &config,
+        None,
+    ));
+}
@@ -0,0 +1,138 @@
+//! Tests for `SUNSETR_*` environment overrides of config keys.
+//!
+//! These live in their own test binary since they change the process environment,
+//! which would race with config loading in other tests.
+
+use serial_test::serial;
+use std::fs;
+use tempfile::tempdir;
+
+use sunsetr::Config;
+
+/// Run `f` with the given environment variables set, restoring them afterwards.
+///
+/// The system-wide config is disabled unless `vars` sets `SUNSETR_SYSTEM_CONFIG`.
+fn with_env<T>(vars: &[(&str, &str)], f: impl FnOnce() -> T) -> T {
+    let vars: Vec<(&str, &str)> = std::iter::once(("SUNSETR_SYSTEM_CONFIG", ""))
+        .chain(vars.iter().copied())
+        .collect();
+    let originals: Vec<_> = vars
+        .iter()
+        .map(|(name, _)| (*name, std::env::var(name).ok()))
+        .collect();
+
+    // SAFETY: every test in this binary is serial, so nothing reads the environment concurrently
+    unsafe {
+        for (name, value) in &vars {
+            std::env::set_var(name, value);
+        }
+    }
+
+    let result = f();
+
+    unsafe {
+        for (name, original) in originals {
+            match original {
+                Some(value) => std::env::set_var(name, value),
+                None => std::env::remove_var(name),
+            }
+        }
+    }
+
+    result
+}
+
+#[test]
+#[serial]
+fn test_env_overrides_file_values() {
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("sunsetr.toml");
+    fs::write(
+        &config_path,
+        r#"
+start_hyprsunset = false
+sunset = "19:00:00"
+sunrise = "06:00:00"
+night_temp = 3300
+transition_mode = "finish_by"
+"#,
+    )
+    .unwrap();
+
+    let config = with_env(
+        &[
+            ("SUNSETR_NIGHT_TEMP", "2800"),
+            ("SUNSETR_SUNSET", "20:15:00"),
+        ],
+        || Config::load_from_path(&config_path).unwrap(),
+    );
+    assert_eq!(config.night_temp, Some(2800));
+    assert_eq!(config.sunset, "20:15:00");
+    assert_eq!(config.sunrise, "06:00:00");
+
+    // Overrides go through the same validation as the file
+    let result = with_env(&[("SUNSETR_NIGHT_TEMP", "50")], || {
+        Config::load_from_path(&config_path)
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+#[serial]
+fn test_env_only_config_writes_no_file() {
+    let temp_dir = tempdir().unwrap();
+    let xdg = temp_dir.path().to_str().unwrap();
+
+    let config = with_env(
+        &[
+            ("XDG_CONFIG_HOME", xdg),
+            ("SUNSETR_TRANSITION_MODE", "finish_by"),
+            ("SUNSETR_START_HYPRSUNSET", "false"),
+            ("SUNSETR_NIGHT_TEMP", "3000"),
+        ],
+        || Config::load().unwrap(),
+    );
+
+    assert_eq!(config.night_temp, Some(3000));
+    assert_eq!(config.transition_mode.as_deref(), Some("finish_by"));
+    assert!(!config.sunset.is_empty());
+    assert!(
+        !temp_dir
+            .path()
+            .join("sunsetr")
+            .join("sunsetr.toml")
+            .exists()
+    );
+}
+
+#[test]
+#[serial]
+fn test_system_config_from_environment() {
+    let temp_dir = tempdir().unwrap();
+    let system_path = temp_dir.path().join("system.toml");
+    fs::write(
+        &system_path,
+        r#"
+start_hyprsunset = false
+sunset = "20:00:00"
+sunrise = "07:00:00"
+night_temp = 3500
+transition_mode = "finish_by"
+"#,
+    )
+    .unwrap();
+    let config_path = temp_dir.path().join("sunsetr.toml");
+    fs::write(&config_path, "night_temp = 3000\n").unwrap();
+
+    // SUNSETR_SYSTEM_CONFIG points at another system config
+    let config = with_env(
+        &[("SUNSETR_SYSTEM_CONFIG", system_path.to_str().unwrap())],
+        || Config::load_from_path(&config_path).unwrap(),
+    );
+    assert_eq!(config.night_temp, Some(3000));
+    assert_eq!(config.sunset, "20:00:00");
+
+    // By default with_env turns it off, so the user file alone has to be complete
+    let result = with_env(&[], || Config::load_from_path(&config_path));
+    assert!(result.is_err());
+}
@@ -0,0 +1,34 @@
+//! Regression tests for the gamma table files handed to Wayland compositors.
+//!
+//! Compositors read the table from the descriptor's current offset, so a file left
+//! positioned at EOF after writing is read as empty and the apply is rejected.
+
+use std::io::{Read, Seek};
+
+use sunsetr::backend::wayland::create_gamma_file;
+use sunsetr::backend::wayland::gamma::{GammaTableFormat, create_gamma_tables};
+
+#[test]
+fn test_gamma_file_is_positioned_at_start() {
+    let gamma_data = create_gamma_tables(
+        256,
+        3300,
+        0.9,
+        (1.0, 1.0, 1.0),
+        (1.0, 1.0, 1.0),
+        GammaTableFormat::default(),
+        false,
+    )
+    .unwrap();
+    assert_eq!(gamma_data.len(), 256 * 3 * 2);
+
+    let mut file = create_gamma_file(&gamma_data, false).unwrap();
+    assert_eq!(file.stream_position().unwrap(), 0);
+    assert_eq!(file.metadata().unwrap().len(), gamma_data.len() as u64);
+
+    // Read through a duplicate descriptor, the way the compositor does after set_gamma
+    let mut compositor_view = file.try_clone().unwrap();
+    let mut received = Vec::new();
+    compositor_view.read_to_end(&mut received).unwrap();
+    assert_eq!(received, gamma_data);
+}
@@ -4,7 +4,16 @@ use tempfile::tempdir;
 
 use sunsetr::{Config, time_until_next_event};
 
+/// Keep /etc/sunsetr/sunsetr.toml on the machine running the tests out of every config.
+fn ignore_system_config() {
+    // SAFETY: every test in this binary that touches the environment is serial
+    unsafe {
+        std::env::set_var("SUNSETR_SYSTEM_CONFIG", "");
+    }
+}
+
 fn create_test_config_file(content: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+    ignore_system_config();
     let temp_dir = tempdir().unwrap();
     let config_path = temp_dir.path().join("hypr").join("sunsetr.toml");
 
@@ -257,6 +266,8 @@ fn test_integration_default_config_generation() {
     // Test default config generation when no config exists
     let temp_dir = tempdir().unwrap();
 
+    ignore_system_config();
+
     // Save and restore XDG_CONFIG_HOME
     let original = std::env::var("XDG_CONFIG_HOME").ok();
     unsafe {
@@ -284,6 +295,50 @@ fn test_integration_default_config_generation() {
     assert!(config_path.exists());
 }
 
+#[test]
+#[serial]
+fn test_integration_locate_config_without_prompting() {
+    let temp_dir = tempdir().unwrap();
+    let new_path = temp_dir.path().join("sunsetr").join("sunsetr.toml");
+    let legacy_path = temp_dir.path().join("hypr").join("sunsetr.toml");
+
+    let original = std::env::var("XDG_CONFIG_HOME").ok();
+    unsafe {
+        std::env::set_var("XDG_CONFIG_HOME", temp_dir.path());
+    }
+
+    // Nothing yet: the new location, to be created
+    let missing = Config::locate_config().unwrap();
+
+    // Only the legacy file
+    fs::create_dir_all(legacy_path.parent().unwrap()).unwrap();
+    fs::write(&legacy_path, "").unwrap();
+    let legacy = Config::locate_config().unwrap();
+
+    // Both: the new one, with the legacy one reported instead of prompting
+    fs::create_dir_all(new_path.parent().unwrap()).unwrap();
+    fs::write(&new_path, "").unwrap();
+    let both = Config::locate_config().unwrap();
+
+    unsafe {
+        match original {
+            Some(val) => std::env::set_var("XDG_CONFIG_HOME", val),
+            None => std::env::remove_var("XDG_CONFIG_HOME"),
+        }
+    }
+
+    assert_eq!(missing.path, new_path);
+    assert!(!missing.exists && !missing.legacy);
+
+    assert_eq!(legacy.path, legacy_path);
+    assert!(legacy.exists && legacy.legacy);
+    assert_eq!(legacy.conflicting, None);
+
+    assert_eq!(both.path, new_path);
+    assert!(both.exists && !both.legacy);
+    assert_eq!(both.conflicting, Some(legacy_path));
+}
+
 #[test]
 fn test_integration_time_state_calculation_scenarios() {
     // Test time state calculations with various extreme scenarios
@@ -294,20 +349,78 @@ fn test_integration_time_state_calculation_scenarios() {
     fn create_config(sunset: &str, sunrise: &str, mode: &str, duration: u64) -> Config {
         Config {
             start_hyprsunset: Some(false),
+            hyprsunset_socket: None,
+            hyprsunset_connect_timeout: None,
+            hyprsunset_connect_retries: None,
             backend: Some(sunsetr::config::Backend::Auto),
+            auto_prefer: None,
             startup_transition: Some(false),
             startup_transition_duration: Some(10),
+            startup_delay_ms: None,
             latitude: None,
             longitude: None,
+            geo_anchor: None,
+            geo_noon_offset: None,
+            solar_precision: None,
+            geo_night_boundary: None,
+            geo_round_to_minutes: None,
             sunset: sunset.to_string(),
             sunrise: sunrise.to_string(),
             night_temp: Some(3300),
             day_temp: Some(6000),
             night_gamma: Some(90.0),
             day_gamma: Some(100.0),
+            color_shift: None,
+            temp_quantum: None,
+            temp_unit: None,
+            day_whitepoint: None,
+            night_gamma_r: None,
+            night_gamma_g: None,
+            night_gamma_b: None,
+            day_gamma_r: None,
+            day_gamma_g: None,
+            day_gamma_b: None,
+            outputs_include: None,
+            outputs_exclude: None,
+            hdr_outputs: None,
+            hdr_night_temp: None,
+            hdr_day_temp: None,
+            hdr_night_gamma: None,
+            hdr_day_gamma: None,
+            verify_gamma: None,
+            precompute_ramps: None,
+            gamma_table_format: None,
+            require_outputs: None,
+            reset_on_exit: None,
+            on_fatal_exit: None,
+            metrics_port: None,
+            event_fifo: None,
+            reapply_on_unlock: None,
+            wake_fade_ms: None,
+            idle_timeout: None,
+            idle_gamma: None,
+            log_file: None,
+            log_file_max_bytes: None,
+            log_file_keep: None,
+            log_to_console: None,
+            log_verbosity: None,
             transition_duration: Some(duration),
+            sunset_duration: None,
+            sunrise_duration: None,
+            sunrise_hold_temp: None,
+            sunrise_hold_minutes: None,
             update_interval: Some(60),
+            ease_out_tail_seconds: None,
+            gamma_progress_offset: None,
             transition_mode: Some(mode.to_string()),
+            manual_geo_event: None,
+            seasonal_modes: None,
+            on_day_command: None,
+            on_night_command: None,
+            on_transition_start: None,
+            active_profile: None,
+            profiles: None,
+            output_groups: None,
         }
     }
 
@@ -386,6 +499,8 @@ sunrise = "07:00:00"
     )
     .unwrap();
 
+    ignore_system_config();
+
     // Save and restore XDG_CONFIG_HOME
     let original = std::env::var("XDG_CONFIG_HOME").ok();
     unsafe {
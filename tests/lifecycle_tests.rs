@@ -0,0 +1,209 @@
+//! End-to-end run of the sunsetr binary: startup, transition, reload and shutdown.
+//!
+//! The binary runs with the mock backend (`SUNSETR_BACKEND=mock`), which appends
+//! every apply to `SUNSETR_MOCK_LOG`, and with its clock pinned mid-transition by
+//! `SUNSETR_FAKE_TIME`. Config, lock and status files live in a temporary
+//! directory, so the run doesn't touch the user's session.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use chrono::NaiveTime;
+use nix::sys::signal::{Signal, kill};
+use nix::unistd::Pid;
+use tempfile::tempdir;
+
+use sunsetr::status::{NextTransition, unix_now};
+use sunsetr::time_state::{get_initial_values_for_state, get_transition_state_at};
+use sunsetr::{Config, TransitionState};
+
+const CONFIG: &str = r#"
+backend = "auto"
+start_hyprsunset = false
+startup_transition = true
+startup_transition_duration = 1
+sunset = "19:00:00"
+sunrise = "06:00:00"
+night_temp = 3300
+day_temp = 6500
+night_gamma = 90.0
+day_gamma = 100.0
+transition_duration = 30
+transition_mode = "finish_by"
+"#;
+
+/// Halfway through the 18:30-19:00 sunset transition.
+const FAKE_TIME: &str = "18:45:00";
+
+/// One line of the mock backend's log.
+#[derive(Debug, Clone, PartialEq)]
+struct Apply {
+    temperature: u32,
+    gamma: f32,
+    kind: String,
+}
+
+fn read_applies(path: &Path) -> Vec<Apply> {
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .map(|line| {
+            let mut fields = line.split_whitespace();
+            Apply {
+                temperature: fields.next().unwrap().parse().unwrap(),
+                gamma: fields.next().unwrap().parse().unwrap(),
+                kind: fields.next().unwrap().to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Poll the mock log until `done` holds, failing the test after a timeout.
+fn wait_for_applies(path: &Path, done: impl Fn(&[Apply]) -> bool) -> Vec<Apply> {
+    let deadline = Instant::now() + Duration::from_secs(20);
+    loop {
+        let applies = read_applies(path);
+        if done(&applies) {
+            return applies;
+        }
+        assert!(
+            Instant::now() < deadline,
+            "timed out waiting for applies, got {:?}",
+            applies
+        );
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+/// The values the schedule resolves to at `FAKE_TIME` for the given config.
+fn expected_values(config_path: &Path) -> (u32, f32) {
+    let config = Config::load_from_path(&config_path.to_path_buf()).unwrap();
+    let now = NaiveTime::parse_from_str(FAKE_TIME, "%H:%M:%S").unwrap();
+    let state = get_transition_state_at(&config, now);
+    assert!(matches!(state, TransitionState::Transitioning { .. }));
+    get_initial_values_for_state(state, &config)
+}
+
+struct Sunsetr {
+    child: Child,
+}
+
+impl Sunsetr {
+    fn spawn(home: &Path, runtime_dir: &Path, mock_log: &Path) -> Self {
+        let child = Command::new(env!("CARGO_BIN_EXE_sunsetr"))
+            .env("HOME", home)
+            .env("XDG_CONFIG_HOME", home.join(".config"))
+            .env("XDG_RUNTIME_DIR", runtime_dir)
+            .env("SUNSETR_BACKEND", "mock")
+            .env("SUNSETR_MOCK_LOG", mock_log)
+            .env("SUNSETR_FAKE_TIME", FAKE_TIME)
+            .env("SUNSETR_SYSTEM_CONFIG", "")
+            .env_remove("SUNSETR_SAFE")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        Self { child }
+    }
+
+    fn signal(&self, signal: Signal) {
+        kill(Pid::from_raw(self.child.id() as i32), signal).unwrap();
+    }
+}
+
+impl Drop for Sunsetr {
+    fn drop(&mut self) {
+        // Don't leave the process behind when an assertion fails
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn test_startup_transition_reload_and_shutdown() {
+    let temp_dir = tempdir().unwrap();
+    let home = temp_dir.path().join("home");
+    let runtime_dir = temp_dir.path().join("runtime");
+    let config_dir = home.join(".config").join("sunsetr");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::create_dir_all(&runtime_dir).unwrap();
+    let config_path: PathBuf = config_dir.join("sunsetr.toml");
+    fs::write(&config_path, CONFIG).unwrap();
+    let mock_log = temp_dir.path().join("applies.log");
+    let lock_path = runtime_dir.join("sunsetr.lock");
+    let next_path = runtime_dir.join("sunsetr-next.toml");
+
+    let mut sunsetr = Sunsetr::spawn(&home, &runtime_dir, &mock_log);
+
+    // Startup: the transition animates with direct applies, then the main loop
+    // applies the interpolated mid-transition state
+    let (temperature, gamma) = expected_values(&config_path);
+    let applies = wait_for_applies(&mock_log, |applies| {
+        applies.iter().any(|apply| apply.kind == "transitioning")
+    });
+    let first_state = applies
+        .iter()
+        .position(|apply| apply.kind == "transitioning")
+        .unwrap();
+    assert!(first_state > 0, "no startup transition: {:?}", applies);
+    assert!(
+        applies[..first_state]
+            .iter()
+            .all(|apply| apply.kind == "direct")
+    );
+    assert_eq!(applies[first_state].temperature, temperature);
+    assert!((applies[first_state].gamma - gamma).abs() < 0.01);
+    assert!(lock_path.exists());
+
+    // Bar widgets: the sunset transition ends at 19:00, 15 minutes after FAKE_TIME
+    let next: NextTransition = toml::from_str(&fs::read_to_string(&next_path).unwrap()).unwrap();
+    assert_eq!(
+        (next.state.as_str(), next.next_state.as_str()),
+        ("sunset", "night")
+    );
+    assert_eq!(next.temperature, temperature);
+    assert!(
+        next.next_transition.abs_diff(unix_now() + 15 * 60) <= 60,
+        "unexpected next transition {:?}",
+        next
+    );
+
+    // Reload: a changed night temperature is applied again after SIGUSR2
+    fs::write(
+        &config_path,
+        CONFIG.replace("night_temp = 3300", "night_temp = 3000"),
+    )
+    .unwrap();
+    let (reloaded_temperature, _) = expected_values(&config_path);
+    assert_ne!(reloaded_temperature, temperature);
+    let before_reload = applies.len();
+    sunsetr.signal(Signal::SIGUSR2);
+    let applies = wait_for_applies(&mock_log, |applies| {
+        applies[before_reload.min(applies.len())..]
+            .iter()
+            .any(|apply| apply.temperature == reloaded_temperature)
+    });
+    assert!(applies.len() > before_reload);
+
+    // Shutdown: SIGTERM resets to neutral and removes the lock
+    sunsetr.signal(Signal::SIGTERM);
+    let deadline = Instant::now() + Duration::from_secs(20);
+    let status = loop {
+        if let Some(status) = sunsetr.child.try_wait().unwrap() {
+            break status;
+        }
+        assert!(Instant::now() < deadline, "sunsetr did not exit on SIGTERM");
+        std::thread::sleep(Duration::from_millis(50));
+    };
+    assert!(status.success(), "sunsetr exited with {}", status);
+
+    let applies = read_applies(&mock_log);
+    let last = applies.last().unwrap();
+    assert_eq!((last.temperature, last.kind.as_str()), (6500, "direct"));
+    assert!((last.gamma - 100.0).abs() < 0.01);
+    assert!(!lock_path.exists());
+    assert!(!next_path.exists());
+}
@@ -0,0 +1,124 @@
+//! Property tests for lock file parsing and conflict decisions.
+//!
+//! The lock file can be left behind truncated, hand-edited or written by another
+//! version of sunsetr. Whatever it contains, startup must never panic and must never
+//! terminate a process unless the file clearly records a different compositor.
+
+use proptest::prelude::*;
+use sunsetr::utils::{LockDecision, LockInfo, decide_lock_conflict, parse_lock_file};
+
+#[test]
+fn test_parse_lock_file_formats() {
+    let expected = Some(LockInfo {
+        pid: 1234,
+        compositor: Some("hyprland".to_string()),
+    });
+    assert_eq!(parse_lock_file("1234\nhyprland\n"), expected);
+    assert_eq!(parse_lock_file("  1234 \r\n\n hyprland\n\n\n"), expected);
+    assert_eq!(parse_lock_file("1234\nhyprland\nextra\n"), expected);
+
+    // A missing or garbled compositor doesn't invalidate the PID
+    let pid_only = Some(LockInfo {
+        pid: 1234,
+        compositor: None,
+    });
+    assert_eq!(parse_lock_file("1234\n"), pid_only);
+    assert_eq!(parse_lock_file("1234\nhypr\u{1b}[0mland\n"), pid_only);
+    assert_eq!(
+        parse_lock_file(&format!("1234\n{}\n", "x".repeat(65))),
+        pid_only
+    );
+
+    // Unusable PIDs
+    assert_eq!(parse_lock_file(""), None);
+    assert_eq!(parse_lock_file("\n\n"), None);
+    assert_eq!(parse_lock_file("0\nhyprland\n"), None);
+    assert_eq!(parse_lock_file("-1\nhyprland\n"), None);
+    assert_eq!(parse_lock_file("2147483648\nhyprland\n"), None);
+    assert_eq!(parse_lock_file("hyprland\n1234\n"), None);
+}
+
+#[test]
+fn test_decide_lock_conflict() {
+    let info = |compositor: Option<&str>| LockInfo {
+        pid: 1234,
+        compositor: compositor.map(str::to_string),
+    };
+
+    assert_eq!(
+        decide_lock_conflict(None, "niri", |_| true),
+        LockDecision::RemoveStale
+    );
+    assert_eq!(
+        decide_lock_conflict(Some(&info(Some("niri"))), "niri", |_| false),
+        LockDecision::RemoveStale
+    );
+    assert_eq!(
+        decide_lock_conflict(Some(&info(Some("niri"))), "niri", |_| true),
+        LockDecision::RespectRunning(1234)
+    );
+    assert_eq!(
+        decide_lock_conflict(Some(&info(None)), "niri", |_| true),
+        LockDecision::RespectRunning(1234)
+    );
+    assert_eq!(
+        decide_lock_conflict(Some(&info(Some("hyprland"))), "niri", |_| true),
+        LockDecision::TakeOver {
+            pid: 1234,
+            compositor: "hyprland".to_string()
+        }
+    );
+}
+
+proptest! {
+    #[test]
+    fn test_parse_arbitrary_content_never_panics(content in any::<String>()) {
+        if let Some(info) = parse_lock_file(&content) {
+            prop_assert!(info.pid > 0 && info.pid <= i32::MAX as u32);
+        }
+    }
+
+    #[test]
+    fn test_parse_garbled_lock_lines(
+        pid in "[ \\t]*-?[0-9]{1,12}[ \\t]*",
+        compositor in "[ -~\\t\\r\\u{0}-\\u{1f}]{0,80}",
+        blank_lines in 0usize..4,
+    ) {
+        let content = format!("{}\n{}{}\n", pid, "\n".repeat(blank_lines), compositor);
+        let info = parse_lock_file(&content);
+
+        match pid.trim().parse::<u32>() {
+            Ok(n) if n > 0 && n <= i32::MAX as u32 => {
+                prop_assert_eq!(info.map(|i| i.pid), Some(n));
+            }
+            _ => prop_assert!(info.is_none()),
+        }
+    }
+
+    #[test]
+    fn test_never_takes_over_without_readable_compositor(
+        content in any::<String>(),
+        current in "(hyprland|niri|sway|[a-z]{1,10})",
+        running in any::<bool>(),
+    ) {
+        let info = parse_lock_file(&content);
+        let decision = decide_lock_conflict(info.as_ref(), &current, |_| running);
+
+        match decision {
+            LockDecision::TakeOver { pid, compositor } => {
+                let info = info.unwrap();
+                prop_assert!(running);
+                prop_assert_eq!(pid, info.pid);
+                prop_assert_eq!(info.compositor.as_deref(), Some(compositor.as_str()));
+                prop_assert_ne!(compositor, current);
+            }
+            LockDecision::RespectRunning(pid) => {
+                prop_assert!(running);
+                prop_assert!(pid > 0);
+            }
+            LockDecision::RemoveStale => {
+                prop_assert!(info.is_none() || !running);
+            }
+        }
+    }
+}
@@ -0,0 +1,207 @@
+//! Tests driving the transition logic through the mock backend.
+//!
+//! The mock records every apply, so a whole day can be replayed at fixed times
+//! without a compositor and the applied values checked afterwards.
+
+use std::fs;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex, Once};
+use std::time::{Duration, SystemTime};
+
+use chrono::{NaiveTime, TimeZone, Utc};
+use tempfile::tempdir;
+
+use sunsetr::backend::mock::{AppliedState, MockBackend, shared_log};
+use sunsetr::time_state::{get_transition_state_at, should_update_state, time_until_next_event_at};
+use sunsetr::{
+    BackendType, ColorTemperatureBackend, Config, TimeState, TransitionState, create_backend,
+    detect_backend,
+};
+
+fn load_config(content: &str) -> Config {
+    // Don't pick up /etc/sunsetr/sunsetr.toml from the machine running the tests
+    static IGNORE_SYSTEM_CONFIG: Once = Once::new();
+    // SAFETY: the tests in this binary only access the environment through std::env
+    IGNORE_SYSTEM_CONFIG.call_once(|| unsafe { std::env::set_var("SUNSETR_SYSTEM_CONFIG", "") });
+
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("sunsetr.toml");
+    fs::write(&config_path, content).unwrap();
+    Config::load_from_path(&config_path).unwrap()
+}
+
+fn manual_config() -> Config {
+    load_config(
+        r#"
+backend = "wayland"
+start_hyprsunset = false
+startup_transition = false
+sunset = "19:00:00"
+sunrise = "06:00:00"
+night_temp = 3300
+day_temp = 6500
+night_gamma = 90.0
+day_gamma = 100.0
+transition_duration = 30
+update_interval = 60
+transition_mode = "finish_by"
+"#,
+    )
+}
+
+#[test]
+fn test_mock_backend_replays_a_day() {
+    let config = manual_config();
+    let applied = Arc::new(Mutex::new(Vec::new()));
+    let mut backend = MockBackend::with_log(applied.clone());
+    let running = AtomicBool::new(true);
+
+    // One apply every five minutes, the way the main loop would during transitions
+    for minute in (0..24 * 60).step_by(5) {
+        let now = NaiveTime::from_hms_opt(minute / 60, minute % 60, 0).unwrap();
+        let state = get_transition_state_at(&config, now);
+        backend
+            .apply_transition_state(state, &config, &running)
+            .unwrap();
+    }
+
+    let applied = applied.lock().unwrap();
+    assert_eq!(applied.len(), 24 * 12);
+
+    let at = |hour: usize, minute: usize| applied[hour * 12 + minute / 5];
+    assert_eq!(
+        at(12, 0),
+        AppliedState {
+            temperature: 6500,
+            gamma: 100.0,
+            state: Some(TransitionState::Stable(TimeState::Day)),
+        }
+    );
+    assert_eq!(
+        at(23, 0),
+        AppliedState {
+            temperature: 3300,
+            gamma: 90.0,
+            state: Some(TransitionState::Stable(TimeState::Night)),
+        }
+    );
+
+    // The sunset transition (18:30-19:00) only ever moves towards night
+    let sunset: Vec<_> = applied[18 * 12 + 6..=19 * 12].to_vec();
+    assert!(sunset.iter().all(
+        |a| matches!(a.state, Some(TransitionState::Transitioning { .. }))
+            || a.state == Some(TransitionState::Stable(TimeState::Night))
+    ));
+    assert!(
+        sunset
+            .windows(2)
+            .all(|w| w[1].temperature <= w[0].temperature && w[1].gamma <= w[0].gamma)
+    );
+}
+
+#[test]
+fn test_long_stable_night_makes_no_applies() {
+    let config = manual_config();
+    let applied = Arc::new(Mutex::new(Vec::new()));
+    let mut backend = MockBackend::with_log(applied.clone());
+    let running = AtomicBool::new(true);
+
+    let start = Utc.with_ymd_and_hms(2025, 1, 15, 20, 0, 0).unwrap();
+    let mut current_state = get_transition_state_at(&config, start.time());
+    assert_eq!(current_state, TransitionState::Stable(TimeState::Night));
+
+    // The loop sleeps straight through to the sunrise transition at 05:30,
+    // not in update_interval steps
+    let deadline = start + time_until_next_event_at(&config, &start);
+    assert_eq!(
+        deadline,
+        Utc.with_ymd_and_hms(2025, 1, 16, 5, 30, 0).unwrap()
+    );
+
+    // Replay the main loop being woken early every 20 minutes (signals, status
+    // requests) and going back to sleep until the same deadline each time
+    let wall_clock = |at: chrono::DateTime<Utc>| {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(at.timestamp() as u64)
+    };
+    let mut last_check = start;
+    let mut sleep_secs = (deadline - start).num_seconds() as u64;
+    let mut now = start + chrono::Duration::minutes(20);
+    while now < deadline {
+        let new_state = get_transition_state_at(&config, now.time());
+        if should_update_state(
+            &current_state,
+            &new_state,
+            wall_clock(now),
+            wall_clock(last_check),
+            &config,
+            Some(sleep_secs),
+        ) {
+            backend
+                .apply_transition_state(new_state, &config, &running)
+                .unwrap();
+            current_state = new_state;
+        }
+
+        last_check = now;
+        sleep_secs = time_until_next_event_at(&config, &now).as_secs();
+        now += chrono::Duration::minutes(20);
+    }
+    assert!(applied.lock().unwrap().is_empty());
+
+    // Oversleeping a deadline still means the system was suspended, so the
+    // state is reapplied in case the compositor reset the gamma meanwhile
+    let night = TransitionState::Stable(TimeState::Night);
+    let check = wall_clock(start);
+    assert!(should_update_state(
+        &night,
+        &night,
+        check + Duration::from_secs(2 * 3600),
+        check,
+        &config,
+        Some(1200),
+    ));
+}
+
+#[test]
+fn test_mock_backend_records_direct_applies() {
+    let applied = Arc::new(Mutex::new(Vec::new()));
+    let mut backend = MockBackend::with_log(applied.clone());
+    let running = AtomicBool::new(true);
+
+    backend
+        .apply_temperature_gamma(4500, 95.0, &running)
+        .unwrap();
+
+    assert_eq!(backend.backend_name(), "Mock");
+    assert_eq!(
+        applied.lock().unwrap().as_slice(),
+        &[AppliedState {
+            temperature: 4500,
+            gamma: 95.0,
+            state: None,
+        }]
+    );
+}
+
+#[test]
+fn test_mock_backend_selected_by_environment() {
+    let config = manual_config();
+
+    // SAFETY: the tests in this binary only access the environment through std::env
+    unsafe { std::env::set_var("SUNSETR_BACKEND", "mock") };
+
+    // The override wins over backend = "wayland", even without a Wayland session
+    let backend_type = detect_backend(&config).unwrap();
+    assert_eq!(backend_type, BackendType::Mock);
+
+    let mut backend = create_backend(backend_type, &config, false).unwrap();
+    let running = AtomicBool::new(true);
+    let state = get_transition_state_at(&config, NaiveTime::from_hms_opt(12, 0, 0).unwrap());
+    backend
+        .apply_startup_state(state, &config, &running)
+        .unwrap();
+
+    let shared = shared_log();
+    let shared = shared.lock().unwrap();
+    assert_eq!(shared.last().map(|a| a.temperature), Some(6500));
+}
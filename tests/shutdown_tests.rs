@@ -0,0 +1,92 @@
+//! Graceful shutdown on SIGTERM, driven through the mock backend.
+//!
+//! Kept in its own test binary because the signal handler is registered for the
+//! whole process, and this test sends SIGTERM to itself.
+
+use std::fs::{self, File};
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::NaiveTime;
+use tempfile::tempdir;
+
+use sunsetr::backend::mock::{AppliedState, MockBackend};
+use sunsetr::signals::{SignalMessage, setup_signal_handler};
+use sunsetr::time_state::get_transition_state_at;
+use sunsetr::utils::cleanup_application;
+use sunsetr::{ColorTemperatureBackend, Config, TransitionState};
+
+fn manual_config(dir: &std::path::Path) -> Config {
+    // SAFETY: this binary has a single test, so nothing else touches the environment
+    unsafe { std::env::set_var("SUNSETR_SYSTEM_CONFIG", "") };
+
+    let config_path = dir.join("sunsetr.toml");
+    fs::write(
+        &config_path,
+        r#"
+backend = "wayland"
+start_hyprsunset = false
+startup_transition = false
+sunset = "19:00:00"
+sunrise = "06:00:00"
+night_temp = 3300
+day_temp = 6500
+transition_duration = 30
+transition_mode = "finish_by"
+"#,
+    )
+    .unwrap();
+    Config::load_from_path(&config_path).unwrap()
+}
+
+#[test]
+fn test_sigterm_mid_transition_cleans_up_once() {
+    let temp_dir = tempdir().unwrap();
+    let config = manual_config(temp_dir.path());
+    let lock_path = temp_dir.path().join("sunsetr.lock");
+    let lock_file = File::create(&lock_path).unwrap();
+
+    let signal_state = setup_signal_handler(false).unwrap();
+    let applied = Arc::new(Mutex::new(Vec::new()));
+    let mut backend: Box<dyn ColorTemperatureBackend> =
+        Box::new(MockBackend::with_log(applied.clone()));
+
+    // Halfway through the 18:30-19:00 sunset transition
+    let state = get_transition_state_at(&config, NaiveTime::from_hms_opt(18, 45, 0).unwrap());
+    assert!(matches!(state, TransitionState::Transitioning { .. }));
+
+    // SIGTERM arrives while the apply is in flight; the apply itself still completes
+    nix::sys::signal::kill(nix::unistd::getpid(), nix::sys::signal::Signal::SIGTERM).unwrap();
+    backend
+        .apply_transition_state(state, &config, &signal_state.running)
+        .unwrap();
+
+    // The main loop wakes on the Shutdown message and finds running cleared
+    let message = signal_state
+        .signal_receiver
+        .recv_timeout(Duration::from_secs(5))
+        .unwrap();
+    assert!(matches!(message, SignalMessage::Shutdown));
+    // The handler clears the flag just after queueing the message
+    for _ in 0..100 {
+        if !signal_state.running.load(Ordering::SeqCst) {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert!(!signal_state.running.load(Ordering::SeqCst));
+
+    cleanup_application(backend, lock_file, lock_path.to_str().unwrap(), true, false);
+
+    // The transition apply, then exactly one reset to neutral
+    let applied = applied.lock().unwrap();
+    assert_eq!(applied.len(), 2);
+    assert_eq!(applied[0].state, Some(state));
+    let resets: Vec<&AppliedState> = applied
+        .iter()
+        .filter(|a| a.state.is_none() && a.temperature == 6500 && a.gamma == 100.0)
+        .collect();
+    assert_eq!(resets.len(), 1);
+    assert!(!lock_path.exists());
+}